@@ -0,0 +1,182 @@
+// ====================================================
+// dyn Any AND DOWNCASTING ============================
+// ====================================================
+
+/*
+ * A trait object ([[traits#box<dyn trait>]]) forgets the type behind it:
+ * a Box<dyn Shape> can only be used as a Shape. std::any::Any is the
+ * trait for getting it back. Every 'static type implements it, and a
+ * &dyn Any can be asked "are you a T?", and then be a &T:
+ *
+ *     value.is::<T>()              bool
+ *     value.downcast_ref::<T>()    Option<&T>
+ *     value.downcast_mut::<T>()    Option<&mut T>
+ *     boxed.downcast::<T>()        Result<Box<T>, Box<dyn Any>>
+ *
+ * It's Rust's instanceof, and it's there for the few places that need
+ * it. Most of the time a trait or an enum says what the code means
+ * better: when to reach for which is at the end.
+ */
+
+// TypeId ------------------------------------------------------------------
+
+// What Any works with: a TypeId, a number for each type, the same for
+// the same type everywhere in the program. type_name is the type's name
+// for people, with no promise about its form.
+
+use std::any::{Any, TypeId, type_name};
+
+println!("{}", TypeId::of::<u32>() == TypeId::of::<u32>());   // prints: true
+println!("{}", TypeId::of::<u32>() == TypeId::of::<i32>());   // prints: false
+println!("{}", type_name::<Vec<u32>>());   // prints: alloc::vec::Vec<u32>
+
+// Only 'static types have one: a type with a borrowed lifetime in it
+// (&'a str, a struct holding one) is no type Any can name, because two
+// lifetimes would be one TypeId and could be mixed up.
+
+// DOWNCASTING ------------------------------------------------------------
+
+fn describe(value: &dyn Any) -> String {
+    if let Some(n) = value.downcast_ref::<i32>() {
+        format!("an i32, {n}")
+    } else if let Some(s) = value.downcast_ref::<String>() {
+        format!("a String, {s:?}")
+    } else if value.is::<&str>() {
+        "a &str".to_string()
+    } else {
+        "something else".to_string()
+    }
+}
+
+println!("{}", describe(&7));               // prints: an i32, 7
+println!("{}", describe(&String::from("hi")));   // prints: a String, "hi"
+println!("{}", describe(&"hi"));            // prints: a &str
+println!("{}", describe(&7u8));             // prints: something else
+
+// It's the exact type or nothing: a u8 isn't an i32, a &str isn't a
+// String, and a type isn't its trait. There's no asking "do you
+// implement Display?".
+
+// A PROPERTY BAG ---------------------------------------------------------
+
+/*
+ * A legitimate use: a map of values of any type, each found by its
+ * type. An app's extensions, a request's data passed through layers that
+ * don't know what's in it, an ECS's components. The TypeId is the key,
+ * the Box<dyn Any> the value; the downcast can't fail, because the key
+ * says what's in it. (http::Extensions and axum's request extensions
+ * are this.)
+ */
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+#[derive(Default)]
+struct Bag {
+    values: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl Bag {
+    fn insert<T: Any>(&mut self, value: T) -> Option<T> {
+        let old = self.values.insert(TypeId::of::<T>(), Box::new(value))?;
+        old.downcast().ok().map(|boxed| *boxed)
+    }
+
+    fn get<T: Any>(&self) -> Option<&T> {
+        self.values.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    fn get_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.values.get_mut(&TypeId::of::<T>())?.downcast_mut()
+    }
+}
+
+// newtypes make keys out of types: two Strings would be one entry
+struct UserName(String);
+struct RequestId(u64);
+
+let mut bag = Bag::default();
+bag.insert(UserName("ada".to_string()));
+bag.insert(RequestId(7));
+if let Some(id) = bag.get_mut::<RequestId>() {
+    id.0 += 1;
+}
+println!("{} {}", bag.get::<UserName>().unwrap().0, bag.get::<RequestId>().unwrap().0);   // prints: ada 8
+println!("{}", bag.get::<f64>().is_none());   // prints: true
+println!("{}", bag.insert(RequestId(9)).map_or(0, |old| old.0));   // prints: 8
+
+// ANY FROM A TRAIT OBJECT ------------------------------------------------
+
+/*
+ * A &dyn Shape is asked with the trait's own methods only. The way
+ * around it used to be a method on the trait returning self as a
+ * &dyn Any, written in every impl. Since Rust 1.86, with Any as a
+ * supertrait, a &dyn Shape converts to a &dyn Any by itself (trait
+ * upcasting).
+ */
+
+use std::any::Any;
+
+trait Shape: Any {
+    fn area(&self) -> f64;
+}
+
+struct Square(f64);
+struct Circle(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 { self.0 * self.0 }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 { 3.14 * self.0 * self.0 }
+}
+
+let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Square(2.0)), Box::new(Circle(1.0))];
+let squares = shapes.iter().map(|s| &**s as &dyn Any).filter(|any| any.is::<Square>()).count();
+println!("{squares}");                      // prints: 1
+
+// Careful which thing is made an Any: a Box<dyn Shape> is 'static, so
+// it's an Any itself, and asking it is a question about the Box.
+
+let first: &dyn Any = &shapes[0];            // the Box
+let inside: &dyn Any = &*shapes[0];          // the Square
+println!("{} {}", first.is::<Square>(), inside.is::<Square>());   // prints: false true
+
+// A Box<dyn Any> is what a panic's payload is (what catch_unwind hands
+// back): a &str for panic!("literal"), a String for a formatted one.
+
+let what = String::from("full");
+let payload = std::panic::catch_unwind(|| panic!("disk {what}")).unwrap_err();
+println!("{:?}", payload.downcast_ref::<String>());   // prints: Some("disk full")
+
+// WHEN IT'S A SMELL, AND WHEN IT ISN'T -----------------------------------
+
+/*
+ * A downcast is a question the types should have answered. When the
+ * set of types is known, and the code does something different for
+ * each, the language has two better ways, both checked by the compiler:
+ *
+ *     a method on the trait   each type says what it does; a new type
+ *                             can't be forgotten
+ *     an enum                 the types are the variants; a match that
+ *                             misses one doesn't compile
+ *
+ * The if-chain of downcast_refs in describe() above is the smell: add a
+ * type and it falls to "something else" without a word. So is a
+ * downcast to reach past a trait to one implementation's extras: that
+ * method belongs on the trait, or the code wanted the concrete type.
+ *
+ * The escape hatch is legitimate when the code in the middle can't know
+ * the types, by design:
+ *
+ *     a map of values keyed by type      the property bag above
+ *     a payload passed through           panics, an event bus, a plugin
+ *                                        host handing data to plugins
+ *     an error's concrete type           Error::downcast_ref, for the
+ *                                        caller that wants one case
+ *                                        ([[error_chains#context at each level]])
+ *
+ * There the downcast is in one place, next to the insert that makes it
+ * safe, rather than spread across every caller.
+ */
@@ -0,0 +1,205 @@
+// ====================================================
+// API DESIGN: SIGNATURES THAT TAKE MORE ==============
+// ====================================================
+
+/*
+ * A function's signature decides what its callers have to do to call
+ * it. The same body, with the parameter a little more general, saves
+ * every caller a conversion, a clone or an allocation. The notes already
+ * do it in places ([[ownership#slice]] takes &str over &String); here are
+ * the patterns together, each as a before and an after:
+ *
+ *     take                              rather than
+ *     &str, &[T]                        &String, &Vec<T>
+ *     impl AsRef<Path>, AsRef<str>      &PathBuf, one exact type
+ *     impl IntoIterator<Item = T>       a Vec<T> the caller must build
+ *     impl Into<String> to keep it      String, or &str then to_string()
+ *
+ *     return                            rather than
+ *     impl Iterator<Item = T>           a Vec<T> the caller may not need
+ *
+ * They're from the Rust API Guidelines
+ * (https://rust-lang.github.io/api-guidelines/), which has the rest.
+ */
+
+// BORROW THE SLICE, NOT THE CONTAINER ------------------------------------
+
+// Before: a &Vec<u32> only takes a Vec. An array, a slice of a Vec, or
+// the numbers in some other struct would each need a Vec made first.
+
+fn total(numbers: &Vec<u32>) -> u32 {
+    numbers.iter().sum()
+}
+
+println!("{}", total(&[1, 2, 3]));         // compile error: E0308
+
+// After: &[u32] takes all of them, and a &Vec<u32> still works, since it
+// derefs to &[u32]. Nothing a &Vec allows that the body needs is lost:
+// only push and capacity, which a & can't use anyway. The same for &str
+// over &String (a literal, a slice of a String, a String).
+
+fn total(numbers: &[u32]) -> u32 {
+    numbers.iter().sum()
+}
+
+let v = vec![1, 2, 3, 4];
+assert_eq!(total(&v), 10);
+assert_eq!(total(&v[1..]), 9);
+assert_eq!(total(&[5, 5]), 10);
+println!("{}", total(&v));                  // prints: 10
+
+// clippy says so too: its ptr_arg lint flags &Vec and &String parameters.
+
+// impl AsRef: WHATEVER CAN BE SEEN AS ONE ---------------------------------
+
+/*
+ * Paths come as &str, String, &Path and PathBuf. A function taking
+ * &PathBuf makes a caller with a literal write PathBuf::from("..").
+ * AsRef<Path> is implemented by all four: take impl AsRef<Path> and call
+ * .as_ref() once. std::fs does this for every function.
+ */
+
+use std::path::{Path, PathBuf};
+
+// before
+fn extension_before(path: &PathBuf) -> Option<String> {
+    Some(path.extension()?.to_str()?.to_string())
+}
+
+println!("{:?}", extension_before(&PathBuf::from("notes.rs")));   // prints: Some("rs")
+
+// after
+fn extension(path: impl AsRef<Path>) -> Option<String> {
+    Some(path.as_ref().extension()?.to_str()?.to_string())
+}
+
+let owned = PathBuf::from("b.toml");
+assert_eq!(extension("a.rs"), Some("rs".to_string()));
+assert_eq!(extension(String::from("x.json")), Some("json".to_string()));
+assert_eq!(extension(&owned), Some("toml".to_string()));
+assert_eq!(extension(Path::new("README")), None);
+println!("{:?}", extension("notes.rs"));    // prints: Some("rs")
+
+// A generic is compiled once per type it's called with. For a big body,
+// keep the generic part to the .as_ref() and hand a &Path on to an inner
+// function: [[monomorphization#keeping it small]].
+
+// impl IntoIterator: ANY COLLECTION, OR NONE -----------------------------
+
+// Before: the caller has a HashSet, or a range, or an iterator chain,
+// and has to collect it into a Vec first.
+
+fn longest_before(words: Vec<String>) -> usize {
+    words.iter().map(|w| w.len()).max().unwrap_or(0)
+}
+
+println!("{}", longest_before(vec!["a".to_string(), "abc".to_string()]));   // prints: 3
+
+// After: anything that can be iterated, and items that can be seen as
+// a &str, so &str and String both do.
+
+use std::collections::HashSet;
+
+fn longest(words: impl IntoIterator<Item = impl AsRef<str>>) -> usize {
+    words.into_iter().map(|w| w.as_ref().len()).max().unwrap_or(0)
+}
+
+let set: HashSet<&str> = ["ab", "abcd"].into_iter().collect();
+assert_eq!(longest(["a", "abc"]), 3);
+assert_eq!(longest(vec![String::from("hello")]), 5);
+assert_eq!(longest(set), 4);
+assert_eq!(longest("one two three".split(' ')), 5);
+println!("{}", longest(Vec::<String>::new()));   // prints: 0
+
+// RETURN impl Iterator ---------------------------------------------------
+
+/*
+ * A function returning Vec<T> has built all of it, and allocated,
+ * before the caller sees the first. Returning impl Iterator hands back
+ * the recipe: the caller can take the first, sum without a Vec, chain
+ * more steps, or collect if a Vec is what it wants after all.
+ */
+
+// before
+fn evens_before(numbers: &[u32]) -> Vec<u32> {
+    numbers.iter().copied().filter(|n| n % 2 == 0).collect()
+}
+
+println!("{:?}", evens_before(&[1, 2, 3, 4]));   // prints: [2, 4]
+
+// after: the '_ says the iterator borrows `numbers`
+
+fn evens(numbers: &[u32]) -> impl Iterator<Item = u32> + '_ {
+    numbers.iter().copied().filter(|n| n % 2 == 0)
+}
+
+let numbers: Vec<u32> = (1..=1_000_000).collect();
+assert_eq!(evens(&numbers).next(), Some(2));   // looked at two numbers, not a million
+assert_eq!(evens(&[1, 2, 3, 4]).sum::<u32>(), 6);
+assert_eq!(evens(&[1, 2, 3, 4]).collect::<Vec<_>>(), vec![2, 4]);
+println!("{:?}", evens(&numbers).take(3).collect::<Vec<_>>());   // prints: [2, 4, 6]
+
+// What's given up: the caller can't index it or ask its len() without
+// collecting, and it can't outlive what it borrows. For a small result
+// that's always wanted whole, a Vec is fine.
+
+// impl Into: CONSTRUCTORS THAT TAKE EITHER -------------------------------
+
+/*
+ * A struct that keeps a String should take ownership of one: a caller
+ * with a String moves it in, with no copy. But then a caller with a
+ * literal writes .to_string() at every call. impl Into<String> takes
+ * both: a String moves in as it is, a &str is copied once, inside.
+ */
+
+// before
+struct UserBefore {
+    name: String,
+}
+
+impl UserBefore {
+    fn new(name: String) -> UserBefore {
+        UserBefore { name }
+    }
+}
+
+println!("{}", UserBefore::new("ada".to_string()).name);   // prints: ada
+
+// after
+struct User {
+    name: String,
+    email: Option<String>,
+}
+
+impl User {
+    fn new(name: impl Into<String>) -> User {
+        User { name: name.into(), email: None }
+    }
+
+    fn with_email(mut self, email: impl Into<String>) -> User {
+        self.email = Some(email.into());
+        self
+    }
+}
+
+let from_input = String::from("grace");
+let a = User::new("ada");
+let b = User::new(from_input).with_email("grace@example.com");
+assert_eq!(a.name, "ada");
+assert_eq!(b.email.as_deref(), Some("grace@example.com"));
+println!("{} {}", a.name, b.name);          // prints: ada grace
+
+/*
+ * The limits, so it doesn't go everywhere:
+ *
+ *     take &str when the function only reads: Into<String> would make
+ *     a caller with a &str allocate a String for nothing
+ *
+ *     two impl Into parameters of the same type is fine; the generics
+ *     make one copy per combination of argument types (&str and String
+ *     both ways is four)
+ *
+ *     a wrong argument's error is about the bound ("the trait
+ *     From<i32> is not implemented for String"), not a plain type
+ *     mismatch: a little harder to read for the caller
+ */
@@ -0,0 +1,254 @@
+// ====================================================
+// SYNCHRONIZATION IN ASYNC CODE ======================
+// ====================================================
+
+/*
+ * Threads share state through std::sync: Mutex, Arc, the channels
+ * ([[concurrency#shared-state concurrency]]). Async tasks can too, but a
+ * task isn't a thread: many run on one, and each gives the thread up
+ * only at an .await. A thread that blocks, on a lock or a sleep, stops
+ * every task that was to run on it. tokio::sync has the same tools made
+ * for tasks: waiting in them is an .await, which hands the thread on.
+ *
+ * The code here needs tokio, so none of it is run by `quiz notes`; the
+ * outputs in the comments are from running it with
+ *
+ *     Cargo.toml: [dependencies]
+ *     tokio = { version = "1", features = ["full"] }
+ */
+
+// WHICH MUTEX ------------------------------------------------------------
+
+/*
+ *     std::sync::Mutex       lock() blocks the thread until it's free
+ *     tokio::sync::Mutex     lock().await waits as a task: other tasks run
+ *
+ * The std one is the right choice more often than not, also in async
+ * code: it's faster, and a lock taken, used and let go of between two
+ * .awaits is never held long enough to block anyone much. tokio's docs
+ * say so themselves. The tokio one is for a lock that has to be held
+ * across an .await: a connection, a file being written in steps.
+ */
+
+use std::sync::{Arc, Mutex};
+
+async fn count_hits(hits: Arc<Mutex<u64>>) {
+    {
+        let mut n = hits.lock().unwrap();
+        *n += 1;
+    }                                       // let go of here
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+}
+
+// A LOCK HELD ACROSS AN AWAIT --------------------------------------------
+
+/*
+ * The pitfall: a std guard still alive at an .await. The task stops
+ * there, with the lock held, and the thread goes to another task. If
+ * that one wants the lock, lock() blocks the thread, and the task that
+ * would let go of it is waiting for that same thread. Nothing moves.
+ *
+ * Two tasks on one thread (join! runs both on the current task; a
+ * current_thread runtime, the default for #[tokio::test], has one thread
+ * anyway):
+ */
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let counter = Mutex::new(0);
+    let a = async {
+        let mut n = counter.lock().unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;   // held here
+        *n += 1;
+    };
+    let b = async {
+        *counter.lock().unwrap() += 1;      // blocks the thread, forever
+    };
+    tokio::join!(a, b);
+    println!("never printed");
+}
+
+// It doesn't panic and prints nothing: it hangs. (A watchdog thread,
+// printing if main hadn't finished after 2 seconds, did print.) On a
+// multi-threaded runtime it's less sure: the other task may be on
+// another thread and only wait, so it works until the day it doesn't.
+
+/*
+ * tokio::spawn catches it at compile time, for most: a spawned task may
+ * move between threads, so its future must be Send, and a std
+ * MutexGuard isn't. A guard alive at an .await is kept in the future:
+ *
+ *     tokio::spawn(async move {
+ *         let mut n = counter.lock().unwrap();
+ *         tokio::time::sleep(Duration::from_millis(10)).await;
+ *         *n += 1;
+ *     });
+ *
+ *     error: future cannot be sent between threads safely
+ *       = help: within `{async block}`, the trait `Send` is not
+ *         implemented for `std::sync::MutexGuard<'_, i32>`
+ *     note: future is not `Send` as this value is used across an await
+ *
+ * join!, select! and a current_thread runtime's block_on don't need
+ * Send, which is why the deadlock above compiles. clippy's
+ * await_holding_lock lint finds these either way.
+ */
+
+// THE FIXES ---------------------------------------------------------------
+
+// Let go of the guard before the .await, in a block of its own. A
+// drop(n) before the .await does let go of the lock, but the Send check
+// above still counts n as alive to the end of its scope; the block ends
+// the scope.
+
+let counter = Mutex::new(0);
+let a = async {
+    {
+        let mut n = counter.lock().unwrap();
+        *n += 1;
+    }
+    tokio::time::sleep(Duration::from_millis(10)).await;
+};
+let b = async {
+    *counter.lock().unwrap() += 1;
+};
+tokio::join!(a, b);
+println!("{}", counter.lock().unwrap());   // 2
+
+// Or, when the lock really is wanted across the .await, tokio's Mutex:
+// b's lock().await waits as a task, and a goes on.
+
+use tokio::sync::Mutex;
+
+let counter = Mutex::new(0);
+let a = async {
+    let mut n = counter.lock().await;
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    *n += 1;
+};
+let b = async {
+    *counter.lock().await += 1;
+};
+tokio::join!(a, b);
+println!("{}", *counter.lock().await);   // 2
+
+// SEMAPHORE ---------------------------------------------------------------
+
+/*
+ * A count of permits: acquire().await takes one, waiting if there are
+ * none, and dropping the permit gives it back. The async way to say "at
+ * most N of these at a time": downloads, database connections, calls to
+ * an API with a rate limit. acquire_owned() on an Arc<Semaphore> gives
+ * a permit that can move into a spawned task.
+ */
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::Semaphore;
+
+let permits = Arc::new(Semaphore::new(3));
+let running = Arc::new(AtomicUsize::new(0));
+let most = Arc::new(AtomicUsize::new(0));
+let mut tasks = Vec::new();
+for _ in 0..10 {
+    let (permits, running, most) = (permits.clone(), running.clone(), most.clone());
+    tasks.push(tokio::spawn(async move {
+        let _permit = permits.acquire_owned().await.unwrap();
+        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+        most.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        running.fetch_sub(1, Ordering::SeqCst);
+    }));                                    // _permit dropped: the next may start
+}
+for task in tasks {
+    task.await.unwrap();
+}
+println!("at most {}", most.load(Ordering::SeqCst));   // at most 3
+
+// Ten tasks of 100 ms, three at a time: 0.4 s. acquire() fails only if
+// the semaphore is closed, hence the unwrap.
+
+// NOTIFY ------------------------------------------------------------------
+
+/*
+ * A wake-up with no data: one task waits on notified().await, another
+ * calls notify_one(). The async Condvar, without the Mutex around it.
+ * notify_waiters() wakes every task waiting at that moment.
+ */
+
+use tokio::sync::Notify;
+
+let ready = Arc::new(Notify::new());
+let waiter = {
+    let ready = ready.clone();
+    tokio::spawn(async move {
+        ready.notified().await;
+        println!("woken");
+    })
+};
+ready.notify_one();
+waiter.await.unwrap();                     // woken
+
+// What makes it easy to use right: notify_one() with nobody waiting
+// stores a permit, and the next notified() takes it at once. A wake-up
+// sent before the waiter got there isn't lost (notify_waiters() stores
+// nothing, and is).
+
+let early = Notify::new();
+early.notify_one();
+early.notified().await;                     // doesn't wait
+println!("the permit was kept");
+
+// WATCH -------------------------------------------------------------------
+
+/*
+ * A channel that holds one value, the latest: a sender replaces it,
+ * every receiver can borrow() it, and changed().await waits for a new
+ * one. Values sent while nobody looked are skipped, which is what's
+ * wanted for state rather than events: a config reloaded, a shutdown
+ * flag, a connection's status. changed() fails once the sender is gone.
+ */
+
+use tokio::sync::watch;
+
+let (tx, mut rx) = watch::channel(String::from("level=info"));
+let reader = tokio::spawn(async move {
+    let mut seen = vec![rx.borrow().clone()];
+    while rx.changed().await.is_ok() {
+        seen.push(rx.borrow_and_update().clone());
+    }
+    seen
+});
+tokio::time::sleep(Duration::from_millis(10)).await;
+tx.send(String::from("level=debug")).unwrap();
+tokio::time::sleep(Duration::from_millis(10)).await;
+tx.send(String::from("level=trace")).unwrap();
+tx.send(String::from("level=warn")).unwrap();   // before the reader looked
+drop(tx);
+println!("{:?}", reader.await.unwrap());
+// ["level=info", "level=debug", "level=warn"]
+
+// borrow() holds a read lock on the value: clone what's needed out of
+// it, and don't keep the Ref across an .await, for the reason above.
+
+/*
+ * The std tool, and what to use in a task:
+ *
+ *     std::sync                tokio::sync
+ *     Mutex, RwLock            the std ones, let go of before any .await;
+ *                              Mutex, RwLock when held across one
+ *     mpsc::sync_channel(n)    mpsc::channel(n), send().await
+ *     (one value, once)        oneshot
+ *     Condvar                  Notify
+ *     (none)                   Semaphore
+ *     (none)                   watch, latest value; broadcast, every
+ *                              value to every receiver
+ *     Barrier                  Barrier
+ *
+ * And for work that blocks with no async version at all (a big
+ * computation, a sync library): tokio::task::spawn_blocking, which runs
+ * it on a thread set aside for that.
+ */
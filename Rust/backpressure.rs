@@ -0,0 +1,204 @@
+// ====================================================
+// BOUNDED CHANNELS AND BACKPRESSURE ==================
+// ====================================================
+
+/*
+ * mpsc::channel() ([[concurrency#message passing to transfer data between threads]])
+ * is unbounded: send() never waits, and whatever the receiver hasn't
+ * taken yet is kept in the channel. As long as the receiver keeps up,
+ * that's nothing. When the producer is faster, the difference piles up,
+ * in memory, for as long as it runs: a log shipper behind a slow
+ * network, a parser ahead of a database writer.
+ *
+ * The fix is to decide, in advance, what a full channel does:
+ *
+ *     block      the producer waits: backpressure, the slow end sets
+ *                the pace of the fast one
+ *     drop       the new message is lost, and counted
+ *     replace    the oldest message is lost, for data where the latest
+ *                is what matters
+ *
+ * std has the first two in mpsc::sync_channel(n).
+ */
+
+// sync_channel ------------------------------------------------------------
+
+// A channel with room for n messages. send() waits while it's full;
+// try_send() doesn't, and hands the message back in the error.
+
+use std::sync::mpsc::{self, TrySendError};
+
+let (tx, rx) = mpsc::sync_channel(2);
+println!("{:?} {:?}", tx.try_send(1), tx.try_send(2));   // prints: Ok(()) Ok(())
+match tx.try_send(3) {
+    Err(TrySendError::Full(n)) => println!("full, {n} came back"),
+    other => println!("{other:?}"),
+}
+// prints: full, 3 came back
+println!("{:?}", rx.recv());               // prints: Ok(1)
+println!("{:?}", tx.try_send(3));          // prints: Ok(())
+
+// The blocking send, from another thread: with nobody receiving, the
+// producer gets two messages in and waits at the third.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+let (tx, rx) = mpsc::sync_channel(2);
+let sent = Arc::new(AtomicUsize::new(0));
+let producer = {
+    let sent = sent.clone();
+    thread::spawn(move || {
+        for i in 0..5 {
+            tx.send(i).unwrap();
+            sent.fetch_add(1, Ordering::SeqCst);
+        }
+    })
+};
+thread::sleep(Duration::from_millis(100));
+println!("{} sent, the third waiting", sent.load(Ordering::SeqCst));   // prints: 2 sent, the third waiting
+let all: Vec<i32> = rx.iter().collect();   // taking them lets it go on
+producer.join().unwrap();
+println!("{all:?}");                        // prints: [0, 1, 2, 3, 4]
+
+// sync_channel(0) has no room at all: each send() waits for a recv(),
+// the two threads meet (a rendezvous). A handoff, with no buffer.
+
+let (tx, rx) = mpsc::sync_channel::<&str>(0);
+let handoff = thread::spawn(move || tx.send("here").is_ok());
+thread::sleep(Duration::from_millis(50));
+println!("{:?} {}", rx.recv(), handoff.join().unwrap());   // prints: Ok("here") true
+
+// WHEN THE PRODUCER IS FASTER ---------------------------------------------
+
+/*
+ * diagrams/backpressure.rs measures it: 20000 messages of 1 KB, a
+ * producer five times as fast as the consumer, the heap counted by a
+ * global allocator:
+ *
+ *     strategy    peak heap   delivered   dropped   producer    total
+ *     unbounded     14.9 MB       20000         0     0.11 s   0.27 s
+ *     block         66.5 KB       20000         0     0.32 s   0.32 s
+ *     drop          65.4 KB        3112     16888     0.09 s   0.09 s
+ *
+ * Unbounded, the heap grows for as long as the producer runs (75 MB for
+ * 100000 messages): the backlog is in memory instead of anywhere
+ * visible, and a producer that never stops is an out-of-memory crash
+ * some hours in. Bounded at 64, it stays at 64 messages. What blocking
+ * costs is the producer's time, which was never really saved: the work
+ * isn't done until the consumer has done it, 0.27 s either way, and the
+ * unbounded producer's 0.11 s was only handing its backlog on.
+ *
+ * A bounded channel passes the pressure on: a blocked stage stops
+ * reading its own input, so the stage before it blocks too, back to
+ * whatever reads from the outside (projects/pipeline.rs is four stages
+ * like this). Backed up far enough, it reaches a socket, and TCP slows
+ * the sender down.
+ */
+
+// DROPPING THE NEW ONE ----------------------------------------------------
+
+// When the producer mustn't wait (it's reading a sensor, or serving a
+// request), try_send and count what didn't fit. The count is the part
+// not to skip: dropped data nobody knows about is a bug report later.
+
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+
+fn send_or_drop<T>(tx: &SyncSender<T>, message: T, dropped: &mut u64) -> bool {
+    match tx.try_send(message) {
+        Ok(()) => true,
+        Err(TrySendError::Full(_)) => {
+            *dropped += 1;
+            true
+        }
+        Err(TrySendError::Disconnected(_)) => false,   // the receiver is gone: stop
+    }
+}
+
+let (tx, rx) = mpsc::sync_channel(3);
+let mut dropped = 0;
+for reading in 0..5 {
+    send_or_drop(&tx, reading, &mut dropped);
+}
+drop(tx);
+println!("{:?}, dropped {}", rx.iter().collect::<Vec<_>>(), dropped);   // prints: [0, 1, 2], dropped 2
+
+// DROPPING THE OLD ONE ----------------------------------------------------
+
+/*
+ * For readings, prices, positions, the newest is worth more than the
+ * oldest: a full queue should make room. mpsc can't, as a sender can't
+ * take from the channel; a VecDeque behind a Mutex can, with a Condvar
+ * for the receiver to wait on.
+ */
+
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+
+struct Latest<T> {
+    queue: Mutex<VecDeque<T>>,
+    ready: Condvar,
+    capacity: usize,
+}
+
+impl<T> Latest<T> {
+    fn new(capacity: usize) -> Self {
+        Latest { queue: Mutex::new(VecDeque::with_capacity(capacity)), ready: Condvar::new(), capacity }
+    }
+
+    // never waits; returns the message that made room, if one did
+    fn send(&self, message: T) -> Option<T> {
+        let mut queue = self.queue.lock().unwrap();
+        let old = if queue.len() == self.capacity { queue.pop_front() } else { None };
+        queue.push_back(message);
+        self.ready.notify_one();
+        old
+    }
+
+    fn recv(&self) -> T {
+        let mut queue = self.queue.lock().unwrap();
+        loop {
+            if let Some(message) = queue.pop_front() {
+                return message;
+            }
+            queue = self.ready.wait(queue).unwrap();
+        }
+    }
+}
+
+let prices = Latest::new(3);
+let replaced: Vec<_> = (100..105).filter_map(|p| prices.send(p)).collect();
+println!("{:?} replaced, {} first", replaced, prices.recv());   // prints: [100, 101] replaced, 102 first
+
+// With a capacity of 1 it's "only the latest", and for that there's a
+// ready-made tool in async code, tokio's watch ([[async_sync#watch]]).
+// This Latest has no close: a real one would need the senders counted,
+// as mpsc does, for recv to know when to stop.
+
+// WHICH, AND HOW BIG ------------------------------------------------------
+
+/*
+ *     every message matters, a pace     sync_channel(n), send()
+ *     can be set                        (block)
+ *     the producer can't wait, losing   sync_channel(n), try_send(),
+ *     some is fine                      and count (drop new)
+ *     only recent values matter         a ring like Latest, or watch
+ *                                       (drop old)
+ *     the producer is known to stop,    channel(): unbounded is fine
+ *     and the total fits in memory      when the total is bounded
+ *
+ * How big: enough to smooth out bursts, no more. A bigger buffer
+ * doesn't make the consumer faster; past the size of a burst it only
+ * adds memory, and latency for every message that waits in it. The
+ * measurement at 1000 instead of 64 had the same times and 15 times the
+ * heap. Start small (tens to a few thousand) and make it bigger when
+ * a profile shows the producer waiting during bursts.
+ *
+ * The same choices in tokio: mpsc::channel(n) with send().await blocks
+ * the task rather than the thread, try_send() drops, and
+ * mpsc::unbounded_channel() is the unbounded one, under a name that says
+ * so.
+ */
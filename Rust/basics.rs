@@ -182,16 +182,16 @@ for element in a {
 
 // for loop and range start..end
 
-for number in 0..5 {                  // 0, 1, 2, 3, 4
+for number in 0..5 {                  // prints: 0 1 2 3 4
     println!("{number}");
 }
 
 let var = [1, 2, 3, 4, 5];
-for number in 0..var.len() {
+for number in 0..var.len() {          // prints: 0 1 2 3 4
     println!("{number}");
 }
 
-for number in (0..5).rev() {          // 4, 3, 2, 1, 0 (reverse)
+for number in (0..5).rev() {          // prints: 4 3 2 1 0
     println!("{number}");
 }
 
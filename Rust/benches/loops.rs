@@ -0,0 +1,171 @@
+// BENCHMARK: ITERATORS, INDEX LOOPS AND FOR OVER A SLICE -----------------
+
+/*
+ * The numbers behind loop_performance.rs: three workloads, each written
+ * three ways, measured with Criterion.
+ *
+ *     sum of even squares   filter, map, sum over one slice
+ *     dot product           two slices, element by element
+ *     differences           each element minus the one before it
+ *
+ * and each as
+ *
+ *     iter      an iterator chain: filter and map, zip, windows
+ *     index     for i in 0..n, with v[i]: a bounds check per access,
+ *               unless the compiler can prove it can't fail
+ *
+ * and a third way, what's usually suggested to fix the index loop:
+ *
+ *     for       (even squares) for x in v, no index at all
+ *     sliced    (dot) both slices cut to one length first, so the
+ *               compiler knows b[i] is in bounds wherever a[i] is
+ *     resized   (differences) the output sized first and written by
+ *               index, instead of a push per element
+ *
+ * Criterion runs each enough times for a stable estimate, and says when
+ * a change since the last run is noise or real. The inputs go through
+ * black_box so the work can't be done at compile time.
+ *
+ * It's a [[bench]] of the langscape crate, in the Cargo.toml at the
+ * top of the repository, with criterion as a dev-dependency there:
+ *
+ * cargo bench --bench loops            all of them; the report is in
+ *                                      target/criterion/report/index.html
+ * cargo bench --bench loops -- dot     the ones with "dot" in the name
+ */
+
+// the index loops are what's being measured
+#![allow(clippy::needless_range_loop)]
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const N: usize = 10_000;
+
+type Sum = fn(&[u64]) -> u64;
+type Dot = fn(&[u64], &[u64]) -> u64;
+type Differences = fn(&[u64], &mut Vec<u64>);
+
+// SUM OF EVEN SQUARES ----------------------------------------------------
+
+fn even_squares_iter(v: &[u64]) -> u64 {
+    v.iter().filter(|&&x| x.is_multiple_of(2)).map(|&x| x * x).sum()
+}
+
+fn even_squares_index(v: &[u64]) -> u64 {
+    let mut sum = 0;
+    for i in 0..v.len() {
+        if v[i].is_multiple_of(2) {
+            sum += v[i] * v[i];
+        }
+    }
+    sum
+}
+
+fn even_squares_for(v: &[u64]) -> u64 {
+    let mut sum = 0;
+    for &x in v {
+        if x.is_multiple_of(2) {
+            sum += x * x;
+        }
+    }
+    sum
+}
+
+// DOT PRODUCT ------------------------------------------------------------
+
+fn dot_iter(a: &[u64], b: &[u64]) -> u64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+// b[i] is checked on every round: nothing says b is as long as a
+fn dot_index(a: &[u64], b: &[u64]) -> u64 {
+    let mut sum = 0;
+    for i in 0..a.len() {
+        sum += a[i] * b[i];
+    }
+    sum
+}
+
+// cut to the same length first, and the checks can go
+fn dot_sliced(a: &[u64], b: &[u64]) -> u64 {
+    let n = a.len().min(b.len());
+    let (a, b) = (&a[..n], &b[..n]);
+    let mut sum = 0;
+    for i in 0..n {
+        sum += a[i] * b[i];
+    }
+    sum
+}
+
+// DIFFERENCES ------------------------------------------------------------
+
+fn differences_iter(v: &[u64], out: &mut Vec<u64>) {
+    out.clear();
+    out.extend(v.windows(2).map(|w| w[1].wrapping_sub(w[0])));
+}
+
+fn differences_index(v: &[u64], out: &mut Vec<u64>) {
+    out.clear();
+    for i in 1..v.len() {
+        out.push(v[i].wrapping_sub(v[i - 1]));
+    }
+}
+
+fn differences_resized(v: &[u64], out: &mut Vec<u64>) {
+    out.clear();
+    out.resize(v.len().saturating_sub(1), 0);
+    for i in 1..v.len() {
+        out[i - 1] = v[i].wrapping_sub(v[i - 1]);
+    }
+}
+
+// MAIN -------------------------------------------------------------------
+
+fn input(seed: u64) -> Vec<u64> {
+    // the same numbers every run, and not a pattern the optimizer can see
+    (0..N as u64).map(|i| (i ^ seed).wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 40).collect()
+}
+
+fn self_check() {
+    let (a, b) = (input(1), input(2));
+    assert_eq!(even_squares_iter(&a), even_squares_index(&a));
+    assert_eq!(even_squares_iter(&a), even_squares_for(&a));
+    assert_eq!(dot_iter(&a, &b), dot_index(&a, &b));
+    assert_eq!(dot_iter(&a, &b), dot_sliced(&a, &b));
+    let (mut x, mut y, mut z) = (Vec::new(), Vec::new(), Vec::new());
+    differences_iter(&a, &mut x);
+    differences_index(&a, &mut y);
+    differences_resized(&a, &mut z);
+    assert!(x == y && y == z && x.len() == N - 1);
+}
+
+fn loops(c: &mut Criterion) {
+    self_check();
+    let (a, b) = (input(1), input(2));
+
+    let mut group = c.benchmark_group("even squares");
+    let ways: [(&str, Sum); 3] = [("iter", even_squares_iter), ("index", even_squares_index), ("for", even_squares_for)];
+    for (name, f) in ways {
+        group.bench_function(name, |bench| bench.iter(|| f(black_box(&a))));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("dot");
+    let ways: [(&str, Dot); 3] = [("iter", dot_iter), ("index", dot_index), ("sliced", dot_sliced)];
+    for (name, f) in ways {
+        group.bench_function(name, |bench| bench.iter(|| f(black_box(&a), black_box(&b))));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("differences");
+    let ways: [(&str, Differences); 3] = [("iter", differences_iter), ("index", differences_index), ("resized", differences_resized)];
+    let mut out = Vec::with_capacity(N);
+    for (name, f) in ways {
+        group.bench_function(name, |bench| bench.iter(|| f(black_box(&a), &mut out)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, loops);
+criterion_main!(benches);
@@ -10,23 +10,38 @@ let add_one_v3 = |mut x, mut y| { x += 1; y += 1; x * y};
 
 let mut v = vec![1, 2, 3];
 let immutable_borrow = || println!("{v:?}");          // immutable borrow
-immutable_borrow();                                                 
+immutable_borrow();                                // step 1: v, immutable_borrow
 
 let mut mutable_borrow = || v.push(4);             // mutable borrow
-mutable_borrow();
+mutable_borrow();                                  // step 2: mutable_borrow
 
 // immutable_borrow(); // can't compile (cannot have an immutable and mutable borrowing at the same time)
 
 // but the following compiles because it's a new immutable borrow
 let echo = || println!("{v:?}");
-echo();
+echo();                                            // step 3: v
 
 // move keyword: useful in spawning threads
 let take_ownership = move || println!("{v:?}");   // move keyword forces to take ownershipp
-take_ownership();
+take_ownership();                                  // step 4: take_ownership
 
 // println!("{v:?}"); // won't work now
 
+// step by step (see diagrams/trace.rs):
+//
+//  #  step  thread     line
+//  1  1     main          3  immutable_borrow();  v = [1, 2, 3], immutable_borrow = <closure>
+//                          > [1, 2, 3]
+//  2  2     main          6  mutable_borrow();    mutable_borrow = <closure>
+//  3  3     main         12  echo();              v = [1, 2, 3, 4]
+//                          > [1, 2, 3, 4]
+//  4  4     main         16  take_ownership();    take_ownership = <closure>
+//                          > [1, 2, 3, 4]
+//
+// step 2 can't show v: mutable_borrow holds &mut v until its last call,
+// so `// step 2: v` is E0502, and `// step 4: v` is E0382 (moved into
+// take_ownership). The trace asks rustc the same question the code does.
+
 /*
  * Closures capture values from their environment in three ways,
  * which directly  map to the three ways a function can take a parameter
@@ -59,7 +74,7 @@ fn create_multiplier(factor: i32) -> impl Fn(i32) -> i32 {
 let doubler = create_multiplier(2);
 let tripler = create_multiplier(3);
 let mut a = 4;
-println!("{}", doubler(a));
+println!("{}", doubler(a));             // prints: 8
 
 // Capture variables from their surrounding scope, either by reference or by value
 
@@ -0,0 +1,205 @@
+// ====================================================
+// COHERENCE AND THE ORPHAN RULE ======================
+// ====================================================
+
+/*
+ * Coherence: for a trait and a type there is at most one impl, in the
+ * whole program, so a method call always means the same code whatever
+ * crates are linked in. Two rules keep it:
+ *
+ *   (1) the orphan rule: an impl needs the trait or the type to be local,
+ *       defined in this crate ([[generics#traits]] states it)
+ *   (2) no overlap: two impls can't both cover one type, blanket impls
+ *       (impl<T: Bound> Trait for T) included
+ *
+ * Here are both errors, then the ways around the first: a newtype, a
+ * trait of your own, and a trait that others can use but not implement.
+ */
+
+// THE ORPHAN RULE --------------------------------------------------------
+
+// Display and Vec are both std's: this impl isn't this crate's to make.
+// If it were allowed, two crates could each print a Vec their own way,
+// and a program using both would have two.
+
+use std::fmt;
+
+impl fmt::Display for Vec<String> {         // compile error: E0117
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}]", self.join(", "))
+    }
+}
+
+/*
+ * One local type in the impl's header is enough: impl Display for
+ * Wrapper, or impl From<Wrapper> for Vec<String>, where the local type is
+ * the trait's parameter. Not enough: impl Display for Vec<Wrapper>. The
+ * Vec is still std's type, whatever is in it, except for the
+ * "fundamental" ones, &, &mut and Box, which count as what they wrap:
+ * impl Display for Box<Wrapper> is allowed.
+ */
+
+// Two impls covering i32: the blanket one (every Display) and the one
+// for i32. Which would 5.describe() call?
+
+use std::fmt::Display;
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl<T: Display> Describe for T {
+    fn describe(&self) -> String {
+        format!("<{self}>")
+    }
+}
+
+impl Describe for i32 {                     // compile error: E0119
+    fn describe(&self) -> String {
+        format!("the number {self}")
+    }
+}
+
+// Specialization, picking the more specific of two such impls, is still
+// unstable; on stable the blanket impl is all or nothing.
+
+// THE NEWTYPE WRAPPER ----------------------------------------------------
+
+/*
+ * A tuple struct around the foreign type is a local type, so any trait
+ * can be implemented for it. It costs nothing at run time: a struct of
+ * one field is laid out as the field.
+ */
+
+use std::fmt;
+
+struct Wrapper(Vec<String>);
+
+impl fmt::Display for Wrapper {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}]", self.0.join(", "))
+    }
+}
+
+let w = Wrapper(vec![String::from("hello"), String::from("world")]);
+println!("w = {w}");                        // prints: w = [hello, world]
+
+// Generic over the element, with the bound the impl needs:
+
+use std::fmt;
+
+struct List<T>(Vec<T>);
+
+impl<T: fmt::Display> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let items: Vec<String> = self.0.iter().map(|i| i.to_string()).collect();
+        write!(f, "[{}]", items.join(", "))
+    }
+}
+
+println!("{}", List(vec![1, 2, 3]));        // prints: [1, 2, 3]
+
+/*
+ * What's lost: the Vec's own methods, since a List isn't a Vec. .0 gets
+ * at it, and Deref gives it all back (deref to the inner type, so
+ * list.len() works), at the price of the wrapper leaking what's in it:
+ * that's fine for a wrapper that's only there for a trait impl, less so
+ * for one that's there to restrict what can be done (a Meters(f64) that
+ * shouldn't be added to plain f64s).
+ */
+
+use std::ops::Deref;
+
+struct List<T>(Vec<T>);
+
+impl<T> Deref for List<T> {
+    type Target = Vec<T>;
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+let list = List(vec!['a', 'b']);
+println!("{} {:?}", list.len(), list.first());   // prints: 2 Some('a')
+
+// A TRAIT OF YOUR OWN ----------------------------------------------------
+
+// The other way around the rule: a local trait can be implemented for
+// any type, std's included. An "extension trait" adds methods to a type
+// that isn't yours; it's used where it's in scope.
+
+trait Shout {
+    fn shout(&self) -> String;
+}
+
+impl Shout for str {
+    fn shout(&self) -> String {
+        format!("{}!", self.to_uppercase())
+    }
+}
+
+println!("{}", "hello".shout());            // prints: HELLO!
+
+// SEALED TRAITS ----------------------------------------------------------
+
+/*
+ * A library's pub trait can be implemented by anyone, so adding a method
+ * to it later (without a default) breaks every impl outside. Sealing it
+ * keeps the impls to the library: the trait gets a supertrait that's pub
+ * but in a private module. Other crates can name and use Shape, but to
+ * implement it they'd need to implement Sealed, which they can't name.
+ */
+
+mod shapes {
+    mod private {
+        pub trait Sealed {}
+    }
+
+    pub trait Shape: private::Sealed {
+        fn sides(&self) -> u32;
+    }
+
+    pub struct Triangle;
+    pub struct Square;
+
+    impl private::Sealed for Triangle {}
+    impl private::Sealed for Square {}
+
+    impl Shape for Triangle {
+        fn sides(&self) -> u32 { 3 }
+    }
+    impl Shape for Square {
+        fn sides(&self) -> u32 { 4 }
+    }
+}
+
+use shapes::Shape;
+
+fn total(all: &[&dyn Shape]) -> u32 {
+    all.iter().map(|s| s.sides()).sum()
+}
+
+println!("{}", total(&[&shapes::Triangle, &shapes::Square]));   // prints: 7
+
+// Outside the module (in the notes, a module stands in for the crate),
+// Shape can't be implemented: its supertrait isn't,
+
+struct Pentagon;
+
+impl shapes::Shape for Pentagon {           // compile error: E0277
+    fn sides(&self) -> u32 { 5 }
+}
+
+// and can't be, since it can't even be named:
+
+struct Hexagon;
+
+impl shapes::private::Sealed for Hexagon {} // compile error: E0603
+
+/*
+ * std seals a few of its own this way, and says so in the docs: a trait
+ * with "This trait is sealed and cannot be implemented for types
+ * outside of std". A library that wants its users to implement a trait
+ * but not call a method can do the converse: a method taking an argument
+ * of a private type.
+ */
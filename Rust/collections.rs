@@ -51,6 +51,10 @@ match element {
  * to handle having either Some(&element) or None
  */
 
+let v = vec![1, 2, 3, 4, 5];
+let does_not_exist = v.get(100);  // None
+let does_not_exist = &v[100];     // panics: index out of bounds
+
 // cannot have a mutable and an immutable reference in the same scope:
 // the following code does not compile
 
@@ -62,6 +66,30 @@ v.push(6);               // mutable borrow
 println!("{:?}", v);     // compiles until here
 
 println!("{}", first);   // immutable borrow again
+                         // compile error: E0502
+
+/*
+ * The same in diagrams/borrows.rs: `first` is used on the last line, so
+ * it's still alive when push needs v mutably.
+ *
+ * In words (borrows.rs --plain):
+ *     Line 5, E0502: &mut v while first (&v) is alive.
+ *     first: a shared borrow of v, lines 3 to 8.
+ *     &mut v: a mutable borrow of v, line 5 only.
+ *     &v: a shared borrow of v, line 6 only.
+ *
+ *      first &mut v &v
+ *                         let mut v = vec![1, 2, 3, 4, 5];
+ *
+ *      ┬                  let first = &v[0];       // immutable reference to the first element
+ *      │
+ *      │     ◉          ✗ v.push(6);               // mutable borrow
+ *      │            ●     println!("{:?}", v);     // compiles until here
+ *      │
+ *      ┴                  println!("{}", first);   // immutable borrow again
+ *
+ *      ✗ line 5, E0502: &mut v while first (&v) is alive
+ */
 
 /*
  * because vectors put the values next to each other in memory,
@@ -167,7 +195,8 @@ let row = vec![
 let mut s = String::new();
 
 // to_string() can also be used on any type that implements
-// the Display trait (e.g. string literals)
+// the Display trait (e.g. string literals); the way back, from text
+// to a number or a type of your own, is [[parsing#which type]]
 
 let data = "initialization";
 
@@ -188,6 +217,37 @@ let mut s = String::from("existentialism");
 
 s.push_str(" is ");       // to append a string slice
 
+/*
+ * When the new text doesn't fit, push_str reallocates: the capacity
+ * doubles (or grows to what's needed, if that's more) and the bytes
+ * move to the bigger block, unless the allocator can grow it in
+ * place. Shortened to "exist", drawn by diagrams/memory.rs --steps:
+ *
+ * In words (--plain): after let mut s = String::from("exist");
+ *     s: a String, pointing to "exist" on the heap: len 5, cap 5.
+ * and after s.push_str(" is ");
+ *     s: a String, pointing to "exist is " on the heap: len 9, cap 10, 1 byte unused.
+ *
+ *     let mut s = String::from("exist");
+ *
+ *      stack             heap
+ *     ┌───┬───────┐     ┌───┬───┬───┬───┬───┐
+ *     │ s │ ptr   │ ──▶ │ e │ x │ i │ s │ t │
+ *     │   │ len 5 │     └───┴───┴───┴───┴───┘
+ *     │   │ cap 5 │
+ *     └───┴───────┘
+ *
+ *     s.push_str(" is ");
+ *
+ *      stack              heap
+ *     ┌───┬────────┐     ┌───┬───┬───┬───┬───┬───┬───┬───┬───┬───┐
+ *     │ s │ ptr    │ ──▶ │ e │ x │ i │ s │ t │ ␣ │ i │ s │ ␣ │   │
+ *     │   │ len 9  │     └───┴───┴───┴───┴───┴───┴───┴───┴───┴───┘
+ *     │   │ cap 10 │
+ *     └───┴────────┘
+ *
+ */
+
 // The push_str() method takes a string slice because we don’t
 // necessarily want to take ownership of the parameter.
 
@@ -273,7 +333,8 @@ let s     = &hello[0..4];        // s will be a &str that contains
                                  // the first four bytes of the string
                                  // so, s will contain "Зд"
 
-let s     = &hello[0..1];        // Rust will panic at runtime
+let s     = &hello[0..1];        // Rust will panic at runtime:
+                                 // panics: byte index 1 is not a char boundary
 
 // Use caution when creating string slices with ranges,
 // because doing so can crash your program.
@@ -430,6 +491,8 @@ scores.insert(String::from("Blue"), 20);    // replaces the old value
 
 println!("{scores:?}");
 
+// Adding a key only if it isn't there: the Entry API . . .
+
 // in the following, checks if the key exists;
 // otherwise, insert
 
@@ -0,0 +1,280 @@
+// RUST vs C --------------------------------------------------------------
+
+/*
+ * C gives you memory and trusts you with it. Rust gives you the same
+ * memory (no GC, same layouts, same speed) and checks what you do with
+ * it. Two sections of side-by-side code, then a gallery: the classic C
+ * memory bugs, each of which is a compile error in Rust.
+ *
+ * C listings compile with gcc -Wall -Wextra (gcc 12).
+ */
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+// MALLOC / FREE vs OWNERSHIP ---------------------------------------------
+
+/*
+ * C
+ *
+ *     struct buffer { int *data; size_t len; };
+ *
+ *     struct buffer buffer_new(size_t len) {
+ *         struct buffer b = { calloc(len, sizeof(int)), len };
+ *         return b;                  // caller now "owns" b.data, by convention
+ *     }
+ *     void buffer_free(struct buffer *b) { free(b->data); b->data = NULL; }
+ *
+ *     struct buffer b = buffer_new(3);
+ *     if (b.data == NULL) return 1;  // allocation can fail, check every time
+ *     b.data[0] = 42;
+ *     printf("%d %zu\n", b.data[0], b.len);     // 42 3
+ *     buffer_free(&b);               // forget this: leak; do it twice: crash
+ */
+
+struct Buffer {
+    data: Vec<i32>,                    // the Vec owns its heap memory
+}
+
+impl Buffer {
+    fn new(len: usize) -> Self {
+        Buffer { data: vec![0; len] }  // allocation failure aborts; no NULL to check
+    }
+}
+
+fn malloc_vs_ownership() {
+    let mut b = Buffer::new(3);
+    b.data[0] = 42;
+    println!("{} {}", b.data[0], b.data.len());   // 42 3
+}                                      // b dropped: Vec frees its memory, exactly once
+
+/*
+ * "Who frees this?" is answered by the type, not by a comment:
+ *     Vec<T>, Box<T>, String     owner: frees on drop
+ *     &T, &[T], &str             borrower: never frees, can't outlive the owner
+ *     Rc<T>, Arc<T>              shared owners: the last one frees
+ *
+ * The generated code is what you'd write in C: one malloc in new(), one
+ * free at the end of scope. Drop is inserted by the compiler, not run
+ * by a collector.
+ */
+
+// POINTER ARITHMETIC vs SLICES -------------------------------------------
+
+/*
+ * C
+ *
+ *     long sum(const int *p, size_t n) {         // pointer + length, passed separately
+ *         long total = 0;
+ *         for (const int *end = p + n; p != end; p++) total += *p;
+ *         return total;
+ *     }
+ *
+ *     printf("%ld %ld\n", sum(xs, 5), sum(xs + 1, 3));   // 15 9
+ *     sum(xs + 1, 5);                                    // reads past the end: UB
+ */
+
+// &[i32] is the pointer *and* the length, as one value (a "fat pointer")
+fn sum(xs: &[i32]) -> i64 {
+    xs.iter().map(|&x| x as i64).sum()
+}
+
+fn pointers_vs_slices() {
+    let xs = vec![1, 2, 3, 4, 5];
+    println!("{} {}", sum(&xs), sum(&xs[1..4]));   // 15 9
+
+    // xs[1..6] panics: "range end index 6 out of range for slice of length 5".
+    // A checked crash at the bug, not a silent read of the neighbour's memory.
+    println!("{:?}", xs.get(1..6));                 // None: the non-panicking way
+}
+
+/*
+ * Slices replace most pointer arithmetic:
+ *     p + i, *(p + i)      &xs[i..], xs[i]       (bounds-checked)
+ *     p++ loop             for x in xs / xs.iter()  (no checks needed, and
+ *                                                  the optimiser knows it)
+ *     memcpy(d, s, n)      d.copy_from_slice(s)  (lengths must match)
+ *     split at k           let (a, b) = xs.split_at(k)
+ *
+ * Raw pointers (*const T, *mut T) and p.add(i) still exist for FFI and
+ * data-structure internals, inside unsafe blocks. ffi/lib.rs is a Rust
+ * library with a C API, where they're unavoidable.
+ */
+
+// DATA RACE, FIXED -------------------------------------------------------
+
+/*
+ * C: two threads, one counter
+ *
+ *     static long count = 0;
+ *     static void *bump(void *arg) {
+ *         for (int i = 0; i < 1000000; i++) count++;   // read, add, write: not atomic
+ *         return NULL;
+ *     }
+ *     // two pthread_create(.., bump, ..), two pthread_join
+ *     printf("%ld\n", count);
+ *
+ *     $ ./race; ./race
+ *     2000000
+ *     1000000
+ *
+ * No warning, and sometimes the right answer. The Rust version that
+ * shares a plain integer is in the gallery below; these are the two
+ * versions that compile.
+ */
+
+fn data_race_fixed() {
+    let atomic = AtomicU64::new(0);
+    let locked = Mutex::new(0u64);
+
+    // scoped threads may borrow from the enclosing function
+    thread::scope(|s| {
+        for _ in 0..2 {
+            s.spawn(|| {
+                for _ in 0..1_000_000 {
+                    atomic.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+            s.spawn(|| {
+                for _ in 0..1_000 {
+                    *locked.lock().unwrap() += 1;
+                }
+            });
+        }
+    });
+    println!("{} {}", atomic.into_inner(), locked.into_inner().unwrap());   // 2000000 2000
+}
+
+// GALLERY: SAME BUG IN C, COMPILE ERROR IN RUST --------------------------
+
+/*
+ * Each entry: the C bug, the Rust attempt, and the error. The Rust
+ * snippets are fenced as ```compile_fail,<code>, the rustdoc syntax
+ * for "this must fail to compile with this error", and they're doc
+ * tests in the notes' crate (src/c_gallery.rs), so `cargo test --doc`
+ * fails if one starts compiling.
+ *
+ * gcc 12 does catch the simplest cases, when the free() is in the same
+ * function, or after inlining at -O2. Move the free into a helper in
+ * another .c file and the warnings go away.
+ *
+ * (1) Use after free
+ *
+ *     int *first = &data[0];
+ *     free(data);
+ *     printf("%d\n", *first);
+ *
+ *     ```compile_fail,E0505
+ *     let data = vec![1, 2, 3];
+ *     let first = &data[0];
+ *     drop(data);
+ *     println!("{first}");
+ *     ```
+ *
+ *     error[E0505]: cannot move out of `data` because it is borrowed
+ *     3 |     let first = &data[0];
+ *       |                  ---- borrow of `data` occurs here
+ *     4 |     drop(data);
+ *       |          ^^^^ move out of `data` occurs here
+ *     5 |     println!("{first}");
+ *       |                ----- borrow later used here
+ *
+ * (2) Double free
+ *
+ *     free(s);
+ *     free(s);
+ *
+ *     ```compile_fail,E0382
+ *     let s = String::from("hi");
+ *     drop(s);
+ *     drop(s);
+ *     ```
+ *
+ *     error[E0382]: use of moved value: `s`
+ *     3 |     drop(s);
+ *       |          - value moved here
+ *     4 |     drop(s);
+ *       |          ^ value used here after move
+ *
+ * (3) Returning the address of a local
+ *
+ *     int *make_counter(void) { int count = 0; return &count; }
+ *
+ *     ```compile_fail,E0515
+ *     fn make_counter() -> &'static i32 {
+ *         let count = 0;
+ *         &count
+ *     }
+ *     ```
+ *
+ *     error[E0515]: cannot return reference to local variable `count`
+ *
+ * (4) Uninitialised variable
+ *
+ *     int total;
+ *     for (int i = 0; i < 3; i++) total += i;   // no warning at -O0
+ *
+ *     ```compile_fail,E0381
+ *     let mut total: i32;
+ *     for i in 0..3 {
+ *         total += i;
+ *     }
+ *     println!("{total}");
+ *     ```
+ *
+ *     error[E0381]: used binding `total` is possibly-uninitialized
+ *     3 |     for i in 0..3 {
+ *       |              ---- if the `for` loop runs 0 times, `total` is not initialized
+ *
+ * (5) Pointer into a buffer that gets realloc'ed
+ *
+ *     int *first = &v->data[0];
+ *     vec_push(v, 4);                 // realloc may move the data
+ *     printf("%d\n", *first);
+ *
+ *     ```compile_fail,E0502
+ *     let mut v = vec![1, 2, 3];
+ *     let first = &v[0];
+ *     v.push(4);
+ *     println!("{first}");
+ *     ```
+ *
+ *     error[E0502]: cannot borrow `v` as mutable because it is also borrowed as immutable
+ *
+ * (6) Data race
+ *
+ *     count++ from two threads, as above
+ *
+ *     ```compile_fail,E0499
+ *     let mut count = 0;
+ *     std::thread::scope(|s| {
+ *         s.spawn(|| count += 1);
+ *         s.spawn(|| count += 1);
+ *     });
+ *     ```
+ *
+ *     error[E0499]: cannot borrow `count` as mutable more than once at a time
+ *
+ * (7) Constant out-of-bounds index
+ *
+ *     int a[4];
+ *     a[4] = 1;
+ *
+ *     ```compile_fail
+ *     let mut a = [0; 4];
+ *     a[4] = 1;
+ *     ```
+ *
+ *     error: this operation will panic at runtime
+ *       = note: `#[deny(unconditional_panic)]` on by default
+ *
+ *     A lint, not a type error, so it has no E-code; with a runtime index
+ *     it becomes a panic instead.
+ */
+
+fn main() {
+    malloc_vs_ownership();
+    pointers_vs_slices();
+    data_race_fixed();
+}
@@ -0,0 +1,214 @@
+// RUST vs C++ ------------------------------------------------------------
+
+/*
+ * C++ and Rust share a lot: no garbage collector, destructors that run at
+ * end of scope, move semantics, zero-cost abstractions. The difference
+ * is who checks the rules. In C++ it is the programmer (and sometimes a
+ * sanitizer at runtime); in Rust it is the compiler.
+ *
+ * Each section: the C++ listing (compiled with g++ -std=c++17), the Rust
+ * version, and what changed. The C++ bugs in the last section compile
+ * without a single warning under -Wall -Wextra.
+ */
+
+// RAII -------------------------------------------------------------------
+
+/*
+ * C++
+ *
+ *     struct Buffer {
+ *         std::string name;
+ *         explicit Buffer(std::string n) : name(std::move(n)) {
+ *             std::printf("open %s\n", name.c_str());
+ *         }
+ *         ~Buffer() { std::printf("close %s\n", name.c_str()); }
+ *     };
+ *
+ *     int main() {
+ *         Buffer a("a");
+ *         {
+ *             Buffer b("b");
+ *         }                              // b's destructor runs here
+ *         std::printf("end of main\n");
+ *     }                                  // a's destructor runs here
+ */
+
+struct Buffer {
+    name: String,
+}
+
+impl Buffer {
+    fn new(name: &str) -> Self {
+        println!("open {name}");
+        Buffer { name: name.to_string() }
+    }
+}
+
+// Drop is Rust's destructor
+impl Drop for Buffer {
+    fn drop(&mut self) {
+        println!("close {}", self.name);
+    }
+}
+
+fn raii() {
+    let _a = Buffer::new("a");
+    {
+        let _b = Buffer::new("b");
+    }                                  // _b dropped here
+    println!("end of raii");
+}                                      // _a dropped here
+
+/*
+ * Same output, same idea: File, MutexGuard, Box, Vec all free their
+ * resource in Drop, exactly like std::fstream or std::lock_guard.
+ *
+ * Watch out for `let _ = Buffer::new("c");`: the _ pattern does not bind,
+ * so the value is dropped immediately. `let _c = ...` keeps it alive.
+ */
+
+// COPY AND MOVE ----------------------------------------------------------
+
+/*
+ * C++
+ *
+ *     std::vector<int> a = {1, 2, 3};
+ *     std::vector<int> b = a;              // copy constructor: deep copy
+ *     std::vector<int> c = std::move(a);   // move constructor: steals the buffer
+ *     std::printf("%zu %zu %zu\n", a.size(), b.size(), c.size());   // 0 3 3
+ *     a.push_back(4);                      // legal: a is "valid but unspecified"
+ *
+ * In C++, = copies unless you ask for a move. The moved-from object
+ * still exists and its destructor still runs, so every class with a
+ * move constructor must leave behind some harmless empty state.
+ */
+
+fn copy_and_move() {
+    let a = vec![1, 2, 3];
+    let b = a.clone();                 // copying is explicit
+    let c = a;                         // = moves
+    println!("{} {}", b.len(), c.len());    // 3 3
+
+    // a.len() here would be a compile error:
+    //
+    // error[E0382]: borrow of moved value: `a`
+    //   |
+    //   |     let a = vec![1, 2, 3];
+    //   |         - move occurs because `a` has type `Vec<i32>`, which does not implement the `Copy` trait
+    //   |     let c = a;
+    //   |             - value moved here
+    //   |     println!("{} {}", b.len(), c.len());
+    //   |     a.len()
+    //   |     ^ value borrowed here after move
+
+    // small plain values are Copy, like C++ ints
+    let x = 5;
+    let y = x;
+    println!("{x} {y}");               // 5 5
+}
+
+/*
+ *                       C++                          Rust
+ *     default =         copy                         move (Copy types: copy)
+ *     deep copy         copy constructor             .clone(), always visible
+ *     move              std::move + move ctor        = , a memcpy of the struct
+ *     after a move      object still alive           name unusable, no destructor
+ *     rule of five      write or =default them       derive(Clone), impl Drop
+ *
+ * A Rust move is always a plain bitwise copy of the struct, and the
+ * source is statically dead afterwards. There is no user code in a move,
+ * so there is nothing to get wrong, and no "moved-from" state to handle.
+ */
+
+// DANGLING REFERENCES ----------------------------------------------------
+
+/*
+ * C++: two bugs, zero warnings
+ *
+ *     const std::string& longest(const std::string& a, const std::string& b) {
+ *         return a.size() > b.size() ? a : b;
+ *     }
+ *
+ *     std::vector<int> v = {1, 2, 3};
+ *     int& first = v[0];
+ *     v.push_back(4);                      // may reallocate: first now dangles
+ *     std::printf("%d\n", first);          // undefined behaviour
+ *
+ *     const std::string& s = longest("short", "much longer");
+ *     std::printf("%s\n", s.c_str());      // temporaries are gone: undefined behaviour
+ *
+ * It may even print the right thing. AddressSanitizer (-fsanitize=address)
+ * catches the first one at runtime:
+ *
+ *     ERROR: AddressSanitizer: heap-use-after-free ... READ of size 4
+ *
+ * The Rust versions do not compile.
+ *
+ * (1) Iterator/reference invalidation
+ *
+ *     let mut v = vec![1, 2, 3];
+ *     let first = &v[0];
+ *     v.push(4);
+ *     println!("{first}");
+ *
+ *     error[E0502]: cannot borrow `v` as mutable because it is also borrowed as immutable
+ *       |
+ *     3 |     let first = &v[0];
+ *       |                  - immutable borrow occurs here
+ *     4 |     v.push(4);
+ *       |     ^^^^^^^^^ mutable borrow occurs here
+ *     5 |     println!("{first}");
+ *       |                ----- immutable borrow later used here
+ *
+ * (2) Returning a reference: the signature must say where it comes from
+ *
+ *     fn longest(a: &str, b: &str) -> &str { ... }
+ *
+ *     error[E0106]: missing lifetime specifier
+ *       = help: this function's return type contains a borrowed value, but the
+ *               signature does not say whether it is borrowed from `a` or `b`
+ *
+ *     With fn longest<'a>(a: &'a str, b: &'a str) -> &'a str, the caller
+ *     that passes temporaries is rejected:
+ *
+ *     let s = longest(&String::from("short"), &String::from("much longer"));
+ *     println!("{s}");
+ *
+ *     error[E0716]: temporary value dropped while borrowed
+ *       |
+ *     5 |     let s = longest(&String::from("short"), &String::from("much longer"));
+ *       |                      ^^^^^^^^^^^^^^^^^^^^^                               - temporary value is freed at the end of this statement
+ *       |                      |
+ *       |                      creates a temporary value which is freed while still in use
+ *     6 |     println!("{s}");
+ *       |                - borrow later used here
+ */
+
+fn longest<'a>(a: &'a str, b: &'a str) -> &'a str {
+    if a.len() > b.len() { a } else { b }
+}
+
+fn dangling_fixed() {
+    let mut v = vec![1, 2, 3];
+    let first = v[0];                  // copy the i32 out instead of borrowing
+    v.push(4);
+    println!("{first}");               // 1
+
+    let (short, long) = (String::from("short"), String::from("much longer"));
+    let s = longest(&short, &long);    // both outlive s
+    println!("{s}");                   // much longer
+}
+
+/*
+ * Neither rule is new to a C++ programmer: "don't hold a reference into
+ * a vector across push_back" and "don't return a reference to a
+ * temporary" are in every style guide. Rust turns the style guide into
+ * type checking, at the cost of sometimes rejecting code that happens to
+ * be fine (see the NLL discussion in ownership.rs).
+ */
+
+fn main() {
+    raii();
+    copy_and_move();
+    dangling_fixed();
+}
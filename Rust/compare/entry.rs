@@ -0,0 +1,839 @@
+// COMPARISON ENTRIES: DATA MODEL AND RENDERERS ---------------------------
+
+/*
+ * The comparison notes in this directory all have the same shape, so
+ * they can be read as data:
+ *
+ *     // ERROR HANDLING ----------        one section = one entry
+ *     /*
+ *      * Python                           label: the other language
+ *      *
+ *      *     def parse_port(text): ...    indented: the other snippet
+ *      */
+ *     fn error_handling() { ... }         code: the Rust snippet
+ *     /*
+ *      * (1) In Python any call ...       everything else: commentary
+ *      */
+ *
+ * This program turns those files into ComparisonEntry values, stores
+ * them as JSON, and renders them side by side in the terminal or as an
+ * HTML page. A language that has no .rs file can be added by writing
+ * the JSON by hand: the renderers only ever see entries. With --run it
+ * also executes both halves of every runnable pair and compares them.
+ *
+ * cargo new entry
+ * (copy this file into src/main.rs)
+ *
+ * Cargo.toml:
+ * [dependencies]
+ * serde = { version = "1", features = ["derive"] }
+ * serde_json = "1"
+ *
+ * cargo run -- python.rs                          side by side, 100 columns
+ * cargo run -- python.rs cpp.rs --concept raii    one concept
+ * cargo run -- *.rs --json > entries.json         store
+ * cargo run -- entries.json --html > compare.html
+ * cargo run -- *.rs --run                        check runnable pairs
+ * cargo run -- *.rs --cheatsheet python           "Python to Rust", add
+ *                                                 --markdown for a .md file
+ */
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+// THE MODEL --------------------------------------------------------------
+
+/*
+ * snippets maps a language name to its code. Every entry extracted from
+ * the notes has two ("Rust" and one other), but nothing depends on that:
+ * a hand-written entry may show Rust next to Python *and* Ruby.
+ *
+ * BTreeMap rather than HashMap so the JSON is written in a stable order
+ * and diffs stay small.
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ComparisonEntry {
+    concept: String,                   // id: "error-handling"
+    title: String,                     // "ERROR HANDLING"
+    snippets: BTreeMap<String, String>,
+    commentary: String,
+    #[serde(default)]                  // absent: false, only shown, never run
+    runnable: bool,                    // the other snippet is a whole program
+}
+
+const RUST: &str = "Rust";
+
+impl ComparisonEntry {
+    // the other languages first, in map order, then Rust: the notes read
+    // "what you know" -> "what it looks like in Rust"
+    fn columns(&self) -> Vec<(&str, &str)> {
+        let mut columns: Vec<(&str, &str)> = self
+            .snippets
+            .iter()
+            .filter(|(lang, _)| lang.as_str() != RUST)
+            .map(|(lang, code)| (lang.as_str(), code.as_str()))
+            .collect();
+        if let Some(code) = self.snippets.get(RUST) {
+            columns.push((RUST, code));
+        }
+        columns
+    }
+}
+
+// EXTRACTING ENTRIES FROM THE NOTES --------------------------------------
+
+fn section_title(line: &str) -> Option<&str> {
+    let title = line.strip_prefix("// ")?.trim_end_matches('-').trim_end();
+    (line.ends_with("---") && !title.is_empty()).then_some(title)
+}
+
+fn concept_id(title: &str) -> String {
+    let mut id = String::new();
+    for c in title.chars() {
+        if c.is_alphanumeric() {
+            id.extend(c.to_lowercase());
+        } else if !id.ends_with('-') && !id.is_empty() {
+            id.push('-');
+        }
+    }
+    id.trim_end_matches('-').to_string()
+}
+
+// "SWIFT / KOTLIN" -> "Swift / Kotlin"; words already in lower case
+// ("vs") are left alone
+fn title_case(s: &str) -> String {
+    s.split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                _ if !word.chars().any(char::is_uppercase) => word.to_string(),
+                Some(first) => first.to_uppercase().chain(chars.flat_map(|c| c.to_lowercase())).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// One line of a /* */ block, with the " * " prefix removed.
+enum CommentLine<'a> {
+    Blank,
+    Indented(&'a str),                 // 4+ spaces: code, tables, compiler output
+    Prose(&'a str),
+}
+
+fn comment_line(line: &str) -> CommentLine<'_> {
+    let body = line.trim_start().trim_start_matches("/*").trim_end_matches("*/");
+    let body = body.strip_prefix('*').unwrap_or(body);
+    let body = body.strip_prefix(' ').unwrap_or(body);
+    if body.trim().is_empty() {
+        CommentLine::Blank
+    } else if let Some(code) = body.strip_prefix("    ") {
+        CommentLine::Indented(code)
+    } else {
+        CommentLine::Prose(body.trim_end())
+    }
+}
+
+// A label is "Python" or the "Go" in "Go: a worker pool": one word
+// (or symbol-word like C++) before an optional colon. "Python (runnable)"
+// marks a listing the polyglot runner may execute.
+struct Label<'a> {
+    language: &'a str,
+    runnable: bool,
+    rest: Option<&'a str>,
+}
+
+fn split_label(prose: &str) -> Option<Label<'_>> {
+    let (label, rest) = match prose.split_once(':') {
+        Some((label, rest)) => (label, Some(rest.trim())),
+        None => (prose, None),
+    };
+    let (language, runnable) = match label.strip_suffix(" (runnable)") {
+        Some(language) => (language, true),
+        None => (label, false),
+    };
+    (!language.contains(' ')).then_some(Label { language, runnable, rest })
+}
+
+#[derive(Default)]
+struct Section<'a> {
+    title: &'a str,
+    comments: Vec<Vec<CommentLine<'a>>>,
+    code: Vec<&'a str>,
+}
+
+fn sections(source: &str) -> Vec<Section<'_>> {
+    let mut sections: Vec<Section> = Vec::new();
+    let mut in_comment = false;
+    let mut in_main = false;
+
+    for line in source.lines() {
+        if let Some(title) = section_title(line).filter(|_| !in_comment) {
+            sections.push(Section { title, ..Section::default() });
+            continue;
+        }
+        let Some(section) = sections.last_mut() else { continue };
+
+        if in_comment {
+            section.comments.last_mut().unwrap().push(comment_line(line));
+            in_comment = !line.trim_end().ends_with("*/");
+        } else if line.trim_start().starts_with("/*") {
+            section.comments.push(vec![comment_line(line)]);
+            in_comment = !line.trim_end().ends_with("*/");
+        } else if line.starts_with("fn main()") || in_main {
+            in_main = line != "}";     // the driver is not part of any snippet
+        } else {
+            section.code.push(line);
+        }
+    }
+    sections
+}
+
+// drops leading and trailing blank lines, and squeezes runs of blank
+// lines (left where a comment block was cut out) down to one
+fn trim_blank_lines(lines: &[&str]) -> String {
+    let start = lines.iter().position(|l| !l.trim().is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|l| !l.trim().is_empty()).map_or(start, |i| i + 1);
+    let mut kept: Vec<&str> = Vec::new();
+    for line in &lines[start..end] {
+        if !(line.trim().is_empty() && kept.last().is_some_and(|l| l.trim().is_empty())) {
+            kept.push(line);
+        }
+    }
+    kept.join("\n")
+}
+
+/*
+ * Per section:
+ *     label     first prose line of the first comment, if it looks like one
+ *     snippet   the indented lines of the first comment, up to the first
+ *               prose line after them
+ *     commentary  everything else in the comments, indentation kept
+ * Sections without both a snippet and Rust code (the file intro, the
+ * compile-fail gallery) are not entries.
+ */
+fn extract(source: &str, fallback_language: &str) -> Vec<ComparisonEntry> {
+    let mut entries = Vec::new();
+
+    for section in sections(source) {
+        let rust = trim_blank_lines(&section.code);
+        let mut language = fallback_language.to_string();
+        let mut runnable = false;
+        let mut snippet: Vec<&str> = Vec::new();
+        let mut commentary: Vec<String> = Vec::new();
+
+        for (block_no, block) in section.comments.iter().enumerate() {
+            // 0: before the snippet, 1: in it, 2: after it
+            let mut phase = if block_no == 0 { 0 } else { 2 };
+            for line in block {
+                match (phase, line) {
+                    (0, CommentLine::Prose(p)) if commentary.iter().all(String::is_empty) => {
+                        match split_label(p) {
+                            Some(label) => {
+                                language = label.language.to_string();
+                                runnable = label.runnable;
+                                commentary.extend(label.rest.filter(|r| !r.is_empty()).map(str::to_string));
+                            }
+                            None => commentary.push(p.to_string()),
+                        }
+                    }
+                    (0 | 1, CommentLine::Indented(code)) => {
+                        phase = 1;
+                        snippet.push(code);
+                    }
+                    (1, CommentLine::Blank) => snippet.push(""),
+                    (_, CommentLine::Blank) => commentary.push(String::new()),
+                    (_, CommentLine::Prose(p)) => {
+                        if phase == 1 {
+                            phase = 2;
+                        }
+                        commentary.push(p.to_string());
+                    }
+                    (_, CommentLine::Indented(code)) => commentary.push(format!("    {code}")),
+                }
+            }
+            commentary.push(String::new());
+        }
+
+        // a section with only imports is the file header, not a comparison
+        let snippet = trim_blank_lines(&snippet);
+        if snippet.is_empty() || rust.lines().all(|l| l.is_empty() || l.starts_with("use ")) {
+            continue;
+        }
+        let commentary: Vec<&str> = commentary.iter().map(String::as_str).collect();
+        let commentary = trim_blank_lines(&commentary);
+
+        entries.push(ComparisonEntry {
+            concept: concept_id(section.title),
+            title: section.title.to_string(),
+            snippets: BTreeMap::from([(language, snippet), (RUST.to_string(), rust)]),
+            commentary,
+            runnable,
+        });
+    }
+    entries
+}
+
+// the language named in the file's first line: "// RUST vs C++ ---"
+fn file_language(source: &str) -> Option<String> {
+    let title = section_title(source.lines().next()?)?;
+    let (_, other) = title.split_once(" vs ")?;
+    let other = other.split(':').next()?.trim();
+    Some(if other.chars().any(|c| c.is_lowercase()) { other.to_string() } else { title_case(other) })
+}
+
+// STORAGE ----------------------------------------------------------------
+
+// .rs files are extracted, anything else is read as a JSON array of entries
+fn load(path: &Path) -> Result<Vec<ComparisonEntry>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    if path.extension().is_some_and(|ext| ext == "rs") {
+        // so that *.rs can be passed without picking up this file
+        let Some(language) = file_language(&text) else {
+            eprintln!("skipping {}: no `// RUST vs <language>` title", path.display());
+            return Ok(Vec::new());
+        };
+        Ok(extract(&text, &language))
+    } else {
+        serde_json::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))
+    }
+}
+
+fn to_json(entries: &[ComparisonEntry]) -> String {
+    serde_json::to_string_pretty(entries).expect("entries are always serialisable")
+}
+
+// TERMINAL RENDERER ------------------------------------------------------
+
+// Hard-wraps a line to `width` characters. Counting chars, not bytes,
+// keeps "Здравствуйте" from being cut mid-character.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+    chars.chunks(width).map(|chunk| chunk.iter().collect()).collect()
+}
+
+fn pad(s: &str, width: usize) -> String {
+    format!("{s}{}", " ".repeat(width.saturating_sub(s.chars().count())))
+}
+
+// Each column gets the width of its longest line if everything fits;
+// otherwise the space is shared in proportion, so a short Python
+// listing doesn't take half the screen from a long Rust one.
+fn column_widths(columns: &[(&str, &str)], width: usize) -> Vec<usize> {
+    let natural: Vec<usize> = columns
+        .iter()
+        .map(|(lang, code)| code.lines().chain([*lang]).map(|l| l.chars().count()).max().unwrap_or(0))
+        .collect();
+    let available = width.saturating_sub(3 * columns.len().saturating_sub(1));
+    let total: usize = natural.iter().sum();
+    if total <= available {
+        return natural;
+    }
+    natural.iter().map(|&w| (available * w / total).max(10)).collect()
+}
+
+/*
+ *     ERROR HANDLING
+ *     Python                    │ Rust
+ *     ──────────────────────────┼──────────────────────────
+ *     def parse_port(text):     │ fn error_handling() {
+ *     ...
+ *
+ *     commentary, unwrapped
+ */
+fn render_terminal(entry: &ComparisonEntry, width: usize) -> String {
+    let columns = entry.columns();
+    let widths = column_widths(&columns, width);
+
+    let wrapped: Vec<Vec<String>> = columns
+        .iter()
+        .zip(&widths)
+        .map(|((_, code), &w)| code.lines().flat_map(|l| wrap(l, w)).collect())
+        .collect();
+    let rows = wrapped.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut out = String::new();
+    writeln!(out, "{}", entry.title).unwrap();
+    let header: Vec<String> = columns.iter().zip(&widths).map(|((lang, _), &w)| pad(lang, w)).collect();
+    writeln!(out, "{}", header.join(" │ ").trim_end()).unwrap();
+    let rule: Vec<String> = widths.iter().map(|&w| "─".repeat(w)).collect();
+    writeln!(out, "{}", rule.join("─┼─")).unwrap();
+
+    for row in 0..rows {
+        let cells: Vec<String> = wrapped
+            .iter()
+            .zip(&widths)
+            .map(|(lines, &w)| pad(lines.get(row).map_or("", String::as_str), w))
+            .collect();
+        writeln!(out, "{}", cells.join(" │ ").trim_end()).unwrap();
+    }
+    if !entry.commentary.is_empty() {
+        writeln!(out, "\n{}", entry.commentary).unwrap();
+    }
+    out
+}
+
+// HTML RENDERER ----------------------------------------------------------
+
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// One self-contained page: no scripts, no external CSS. Every entry is
+// a <section id="concept"> so comparisons can be linked to directly.
+fn render_html(entries: &[ComparisonEntry]) -> String {
+    let mut out = String::from(concat!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Rust comparisons</title>\n",
+        "<style>\n",
+        "body { font-family: sans-serif; max-width: 80rem; margin: auto; }\n",
+        "table { width: 100%; table-layout: fixed; border-collapse: collapse; }\n",
+        "td { vertical-align: top; border: 1px solid #ccc; }\n",
+        "pre { margin: 0.5rem; white-space: pre-wrap; }\n",
+        "</style>\n</head>\n<body>\n",
+    ));
+
+    for entry in entries {
+        let columns = entry.columns();
+        writeln!(out, "<section id=\"{}\">", escape_html(&entry.concept)).unwrap();
+        writeln!(out, "<h2>{}</h2>\n<table>\n<tr>", escape_html(&entry.title)).unwrap();
+        for (lang, _) in &columns {
+            writeln!(out, "<th>{}</th>", escape_html(lang)).unwrap();
+        }
+        writeln!(out, "</tr>\n<tr>").unwrap();
+        for (_, code) in &columns {
+            writeln!(out, "<td><pre><code>{}</code></pre></td>", escape_html(code)).unwrap();
+        }
+        writeln!(out, "</tr>\n</table>").unwrap();
+        if !entry.commentary.is_empty() {
+            writeln!(out, "<pre class=\"commentary\">{}</pre>", escape_html(&entry.commentary)).unwrap();
+        }
+        writeln!(out, "</section>").unwrap();
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+// CHEATSHEET -------------------------------------------------------------
+
+/*
+ * --cheatsheet python: every entry that has a Python snippet, condensed
+ * into one "Python to Rust" reference. Condensed means the code and the
+ * first paragraph of commentary; the full discussion stays in the notes.
+ *
+ * --from names are matched loosely: "python" finds "Python", "kotlin"
+ * finds "Swift / Kotlin".
+ */
+fn speaks<'a>(entry: &'a ComparisonEntry, from: &str) -> Option<&'a str> {
+    entry
+        .snippets
+        .keys()
+        .filter(|lang| lang.as_str() != RUST)
+        .find(|lang| lang.split(" / ").any(|part| part.eq_ignore_ascii_case(from)))
+        .map(String::as_str)
+}
+
+fn first_paragraph(text: &str) -> &str {
+    text.split("\n\n").next().unwrap_or("")
+}
+
+fn condensed(entries: &[ComparisonEntry], from: &str) -> Vec<ComparisonEntry> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let language = speaks(entry, from)?;
+            let snippets = BTreeMap::from([
+                (language.to_string(), entry.snippets[language].clone()),
+                (RUST.to_string(), entry.snippets.get(RUST).cloned().unwrap_or_default()),
+            ]);
+            let commentary = first_paragraph(&entry.commentary).to_string();
+            Some(ComparisonEntry { title: title_case(&entry.title), snippets, commentary, ..entry.clone() })
+        })
+        .collect()
+}
+
+// ```python, ```cpp, ```swift
+fn fence_language(language: &str) -> String {
+    let first = language.split(" / ").next().unwrap_or(language);
+    first.to_lowercase().replace("++", "pp")
+}
+
+fn render_cheatsheet_markdown(entries: &[ComparisonEntry]) -> String {
+    let Some(from) = entries.first().and_then(|e| e.columns().first().map(|(lang, _)| lang.to_string())) else {
+        return String::new();
+    };
+    let mut out = format!("# {from} to Rust\n\n");
+    for entry in entries {
+        writeln!(out, "## {}\n", entry.title).unwrap();
+        for (lang, code) in entry.columns() {
+            // a longer fence if the code itself contains one
+            let fence = if code.contains("```") { "````" } else { "```" };
+            writeln!(out, "{fence}{}\n{code}\n{fence}\n", fence_language(lang)).unwrap();
+        }
+        if !entry.commentary.is_empty() {
+            writeln!(out, "{}\n", entry.commentary).unwrap();
+        }
+    }
+    out
+}
+
+// POLYGLOT RUNNER --------------------------------------------------------
+
+/*
+ * Every comparison promises that both halves print the same thing. For
+ * entries whose label says "(runnable)", --run checks it: the other
+ * snippet goes to its interpreter, the Rust half is built with rustc,
+ * and the two stdouts are compared line by line.
+ *
+ * Everything is optional. A missing interpreter is a skip, not a
+ * failure, and so is a Rust half that needs crates, which plain rustc
+ * can't build. Each section is built on its own (programs() below), so
+ * the tokio sections of go.rs and javascript.rs don't stop the rest of
+ * their files from running.
+ */
+
+struct Interpreter {
+    language: &'static str,
+    extension: &'static str,
+    command: &'static [&'static str],  // the snippet's path is appended
+}
+
+const INTERPRETERS: &[Interpreter] = &[
+    Interpreter { language: "Python", extension: "py", command: &["python3"] },
+    Interpreter { language: "JavaScript", extension: "js", command: &["node"] },
+    Interpreter { language: "Go", extension: "go", command: &["go", "run"] },
+];
+
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Same,
+    Differs { other: String, rust: String },
+    Failed(String),                    // a runnable snippet that doesn't run
+    Skipped(String),
+}
+
+// trailing whitespace is not a difference worth reporting
+fn normalise(stdout: &[u8]) -> String {
+    let text = String::from_utf8_lossy(stdout);
+    let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    lines.join("\n").trim_end().to_string()
+}
+
+// Ok(stdout), or Err(outcome) saying why there is nothing to compare
+fn stdout_of(mut command: Command) -> Result<String, Outcome> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command.output().map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => Outcome::Skipped(format!("{program} is not installed")),
+        _ => Outcome::Failed(format!("{program}: {e}")),
+    })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let last = stderr.lines().rfind(|l| !l.trim().is_empty()).unwrap_or("");
+        return Err(Outcome::Failed(format!("{program} exited with {}: {last}", output.status)));
+    }
+    Ok(normalise(&output.stdout))
+}
+
+fn defined_fn(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("async fn ").or_else(|| line.strip_prefix("fn "))?;
+    rest.split(['(', '<']).next()
+}
+
+/*
+ * The notes' main() calls one function per section, in order. The
+ * runner builds each section as a program of its own: the file's
+ * header (its `use` lines), the section's code, and a main making the
+ * section's call from the notes' main:
+ *
+ *     use std::collections::HashMap;
+ *
+ *     fn collections() { ... }
+ *
+ *     fn main() {
+ *         collections();
+ *     }
+ *
+ * Returns the programs by concept id; a section main() doesn't call
+ * has none.
+ */
+fn programs(source: &str) -> BTreeMap<String, String> {
+    let lines: Vec<&str> = source.lines().collect();
+    let Some(start) = lines.iter().position(|l| l.starts_with("fn main()")) else {
+        return BTreeMap::new();
+    };
+    let end = lines[start..].iter().position(|l| *l == "}").map_or(lines.len(), |i| start + i);
+    let calls: Vec<&str> = lines[start + 1..end].iter().map(|l| l.trim()).filter(|l| !l.is_empty()).collect();
+
+    let sections = sections(source);
+    let header = sections.first().map(|s| trim_blank_lines(&s.code)).unwrap_or_default();
+    let mut programs = BTreeMap::new();
+    for section in &sections {
+        let names: Vec<&str> = section.code.iter().filter_map(|l| defined_fn(l)).collect();
+        let call = calls.iter().find(|call| names.iter().any(|name| call.contains(&format!("{name}("))));
+        if let Some(call) = call {
+            let parts = [header.clone(), trim_blank_lines(&section.code), format!("fn main() {{\n    {call}\n}}")];
+            let parts: Vec<String> = parts.into_iter().filter(|p| !p.is_empty()).collect();
+            programs.insert(concept_id(section.title), parts.join("\n\n") + "\n");
+        }
+    }
+    programs
+}
+
+fn run_file(source: &str, language: &str, work_dir: &Path) -> Vec<(String, Outcome)> {
+    let entries: Vec<ComparisonEntry> = extract(source, language).into_iter().filter(|e| e.runnable).collect();
+    let programs = programs(source);
+
+    let mut results = Vec::new();
+    for entry in entries {
+        let (other_lang, other_code) = entry.columns()[0];
+        let outcome = (|| {
+            let interpreter = INTERPRETERS
+                .iter()
+                .find(|i| i.language == other_lang)
+                .ok_or_else(|| Outcome::Skipped(format!("no interpreter configured for {other_lang}")))?;
+            let program = programs
+                .get(&entry.concept)
+                .ok_or_else(|| Outcome::Skipped("main() doesn't call this section".to_string()))?;
+
+            // the Rust half, built alone
+            let src = work_dir.join(format!("{}.rs", entry.concept));
+            let binary = work_dir.join(&entry.concept);
+            fs::write(&src, program).map_err(|e| Outcome::Failed(e.to_string()))?;
+            let mut rustc = Command::new("rustc");
+            rustc.args(["--edition", "2021", "-A", "warnings", "-o"]).arg(&binary).arg(&src);
+            if stdout_of(rustc).is_err() {
+                return Err(Outcome::Skipped("the Rust half does not build with plain rustc (needs crates?)".to_string()));
+            }
+
+            let snippet = work_dir.join(format!("{}.{}", entry.concept, interpreter.extension));
+            fs::write(&snippet, format!("{other_code}\n")).map_err(|e| Outcome::Failed(e.to_string()))?;
+            let mut command = Command::new(interpreter.command[0]);
+            command.args(&interpreter.command[1..]).arg(&snippet);
+            let other = stdout_of(command)?;
+
+            let rust = stdout_of(Command::new(&binary))?;
+
+            Ok(if other == rust { Outcome::Same } else { Outcome::Differs { other, rust } })
+        })()
+        .unwrap_or_else(|outcome| outcome);
+        results.push((entry.concept, outcome));
+    }
+    results
+}
+
+// prints a report and returns false if anything differed or failed
+fn report(path: &str, results: &[(String, Outcome)]) -> bool {
+    println!("{path}");
+    let mut ok = true;
+    for (concept, outcome) in results {
+        match outcome {
+            Outcome::Same => println!("  same     {concept}"),
+            Outcome::Skipped(why) => println!("  skipped  {concept}: {why}"),
+            Outcome::Failed(why) => {
+                ok = false;
+                println!("  FAILED   {concept}: {why}");
+            }
+            Outcome::Differs { other, rust } => {
+                ok = false;
+                println!("  DIFFERS  {concept}");
+                // show the first line that differs, not the whole output
+                let (o, r) = other
+                    .lines()
+                    .chain(std::iter::repeat(""))
+                    .zip(rust.lines().chain(std::iter::repeat("")))
+                    .find(|(o, r)| o != r)
+                    .unwrap_or(("", ""));
+                println!("           other: {o}\n           rust:  {r}");
+            }
+        }
+    }
+    if results.is_empty() {
+        println!("  (no runnable entries)");
+    }
+    ok
+}
+
+// MAIN -------------------------------------------------------------------
+
+const SAMPLE: &str = r#"// RUST vs PYTHON ---------
+
+/*
+ * Intro prose, no snippet: not an entry.
+ */
+
+// ERROR HANDLING ----------
+
+/*
+ * Python (runnable)
+ *
+ *     try:
+ *         n = int(text)
+ *
+ *     except ValueError:
+ *         n = 0
+ */
+
+fn parse(text: &str) -> i32 {
+    text.parse().unwrap_or(0)      // <no exceptions>
+}
+
+/*
+ * (1) Result is in the signature.
+ *
+ *     table  row
+ */
+
+fn main() {
+    parse("1");
+}
+"#;
+
+fn self_check() {
+    assert_eq!(concept_id("GENERICS: ERASURE vs MONOMORPHIZATION"), "generics-erasure-vs-monomorphization");
+    assert_eq!(concept_id("DEFAULTS: ?? AND ?:"), "defaults-and");
+    assert_eq!(file_language(SAMPLE).as_deref(), Some("Python"));
+    assert_eq!(file_language("// RUST vs SWIFT / KOTLIN: OPTIONALS ---").as_deref(), Some("Swift / Kotlin"));
+    assert_eq!(file_language("// RUST vs C++ ---").as_deref(), Some("C++"));
+
+    let entries = extract(SAMPLE, "fallback");
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.concept, "error-handling");
+    assert_eq!(entry.snippets["Python"], "try:\n    n = int(text)\n\nexcept ValueError:\n    n = 0");
+    assert!(entry.snippets[RUST].starts_with("fn parse") && !entry.snippets[RUST].contains("main"));
+    assert_eq!(entry.commentary, "(1) Result is in the signature.\n\n    table  row");
+    assert!(entry.runnable);
+
+    // JSON round trip
+    let json = to_json(&entries);
+    let back: Vec<ComparisonEntry> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, entries);
+
+    // columns line up even with multi-byte text
+    let text = render_terminal(entry, 60);
+    let widths: Vec<usize> = text.lines().filter_map(|l| l.find(['│', '┼']).map(|i| l[..i].chars().count())).collect();
+    assert!(widths.len() > 3 && widths.windows(2).all(|w| w[0] == w[1]), "{widths:?}");
+
+    // the runner builds a section alone, with a main making its call
+    let programs = programs(SAMPLE);
+    assert_eq!(programs.keys().collect::<Vec<_>>(), ["error-handling"]);
+    assert!(programs["error-handling"].starts_with("fn parse") && programs["error-handling"].ends_with("fn main() {\n    parse(\"1\");\n}\n"));
+    assert_eq!(normalise(b"a  \nb\n\n"), "a\nb");
+
+    let sheet = condensed(&entries, "python");
+    assert_eq!(sheet.len(), 1);
+    assert_eq!(sheet[0].title, "Error Handling");
+    assert_eq!(sheet[0].commentary, "(1) Result is in the signature.");
+    assert!(condensed(&entries, "java").is_empty());
+    let md = render_cheatsheet_markdown(&sheet);
+    assert!(md.starts_with("# Python to Rust\n\n## Error Handling\n\n```python\ntry:"));
+    assert_eq!(fence_language("C++"), "cpp");
+    assert_eq!(fence_language("Swift / Kotlin"), "swift");
+
+    let html = render_html(&entries);
+    assert!(html.contains("<section id=\"error-handling\">"));
+    assert!(html.contains("&lt;no exceptions&gt;") && !html.contains("<no exceptions>"));
+}
+
+fn main() {
+    self_check();
+
+    let mut paths = Vec::new();
+    let (mut html, mut json, mut run, mut markdown) = (false, false, false, false);
+    let (mut concept, mut cheatsheet, mut width) = (None, None, 100);
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--html" => html = true,
+            "--json" => json = true,
+            "--run" => run = true,
+            "--markdown" => markdown = true,
+            "--cheatsheet" => cheatsheet = args.next(),
+            "--concept" => concept = args.next(),
+            "--width" => width = args.next().and_then(|w| w.parse().ok()).unwrap_or(width),
+            _ => paths.push(arg),
+        }
+    }
+
+    if run {
+        let work_dir = std::env::temp_dir().join(format!("langscape_run_{}", std::process::id()));
+        fs::create_dir_all(&work_dir).expect("cannot create a temporary directory");
+        let mut ok = true;
+        for path in &paths {
+            let Ok(source) = fs::read_to_string(path) else {
+                eprintln!("cannot read {path}");
+                ok = false;
+                continue;
+            };
+            if let Some(language) = file_language(&source) {
+                ok &= report(path, &run_file(&source, &language, &work_dir));
+            }
+        }
+        let _ = fs::remove_dir_all(&work_dir);
+        std::process::exit(if ok { 0 } else { 1 });
+    }
+
+    let mut entries = Vec::new();
+    for path in &paths {
+        match load(Path::new(path)) {
+            Ok(loaded) => entries.extend(loaded),
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        }
+    }
+    if let Some(concept) = &concept {
+        entries.retain(|e| &e.concept == concept);
+    }
+
+    if let Some(from) = &cheatsheet {
+        entries = condensed(&entries, from);
+        if entries.is_empty() {
+            eprintln!("no comparisons with {from}");
+            std::process::exit(1);
+        }
+    }
+
+    if markdown {
+        print!("{}", render_cheatsheet_markdown(&entries));
+    } else if json {
+        println!("{}", to_json(&entries));
+    } else if html {
+        print!("{}", render_html(&entries));
+    } else {
+        for entry in &entries {
+            println!("{}", render_terminal(entry, width));
+        }
+    }
+}
+
+// EXERCISES --------------------------------------------------------------
+
+/*
+ * (1) Add a `tags: Vec<String>` field with #[serde(default)] so old JSON
+ *     files still load. What breaks without the attribute?
+ * (2) Render Markdown as a third output: a table can't hold multi-line
+ *     code, so what layout would you use instead?
+ * (3) Merge entries with the same concept id from different files into
+ *     one entry with three or more snippets, and check the terminal
+ *     renderer still fits them into the width.
+ */
@@ -0,0 +1,278 @@
+// RUST vs GO -------------------------------------------------------------
+
+/*
+ * Go and Rust arrived around the same time with similar goals (fast,
+ * compiled, good at concurrency) and made opposite choices: Go keeps the
+ * language small and leans on a garbage collector and a runtime
+ * scheduler; Rust puts more into the type system and leaves scheduling
+ * to libraries.
+ *
+ * Go listings are annotated (// <- notes); the Rust halves run and print
+ * the same numbers. The listings that are whole programs say
+ * (runnable), and entry.rs --run checks them against the Rust with go
+ * run. The async section needs tokio:
+ *
+ * Cargo.toml:
+ * [dependencies]
+ * tokio = { version = "1", features = ["full"] }
+ */
+
+use std::fmt;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// GOROUTINES AND CHANNELS vs THREADS AND MPSC ----------------------------
+
+/*
+ * Go (runnable): a worker pool
+ *
+ *     package main
+ *
+ *     import "fmt"
+ *
+ *     func worker(jobs <-chan int, results chan<- int) {
+ *         for j := range jobs {         // <- ends when jobs is closed
+ *             results <- j * j
+ *         }
+ *     }
+ *
+ *     func main() {
+ *         jobs := make(chan int, 100)
+ *         results := make(chan int, 100)
+ *         for w := 0; w < 3; w++ {
+ *             go worker(jobs, results)  // <- three goroutines, one channel
+ *         }
+ *         for j := 1; j <= 5; j++ {
+ *             jobs <- j
+ *         }
+ *         close(jobs)                   // <- explicit; forgetting it deadlocks
+ *         sum := 0
+ *         for i := 0; i < 5; i++ {
+ *             sum += <-results
+ *         }
+ *         fmt.Println(sum)              // 55
+ *     }
+ */
+
+fn worker_pool_threads() {
+    let (job_tx, job_rx) = mpsc::channel::<i32>();
+    let (result_tx, result_rx) = mpsc::channel::<i32>();
+
+    // mpsc = multi-producer, *single*-consumer: a Receiver cannot be
+    // cloned. Three workers sharing one job queue need it behind a Mutex
+    // (or a multi-consumer channel such as crossbeam's).
+    let job_rx = Arc::new(Mutex::new(job_rx));
+
+    let workers: Vec<_> = (0..3)
+        .map(|_| {
+            let job_rx = Arc::clone(&job_rx);
+            let result_tx = result_tx.clone();
+            thread::spawn(move || loop {
+                let job = job_rx.lock().unwrap().recv();   // guard dropped at end of statement
+                match job {
+                    Ok(j) => result_tx.send(j * j).unwrap(),
+                    Err(_) => break,                    // all senders gone: Go's closed channel
+                }
+            })
+        })
+        .collect();
+    drop(result_tx);                   // only the workers' clones remain
+
+    for j in 1..=5 {
+        job_tx.send(j).unwrap();
+    }
+    drop(job_tx);                      // close(jobs), but by ownership: it can't be forgotten
+                                       // past the end of the scope
+
+    let sum: i32 = result_rx.iter().sum();   // ends when every worker has exited
+    println!("{sum}");                 // 55
+
+    for w in workers {
+        w.join().unwrap();
+    }
+}
+
+/*
+ *                       Go                          Rust (std)
+ *     spawn             go f()  (~2 KB stack)       thread::spawn  (OS thread, ~2 MB reserved)
+ *     channel           chan T, many readers        mpsc: many senders, one receiver
+ *     close             close(ch), by hand          drop the last Sender
+ *     send on closed    panic                       send() returns Err
+ *     shared memory     allowed; go run -race       Arc<Mutex<T>>; data races don't compile
+ *
+ * The last row is the difference that matters. Go's slogan is "share
+ * memory by communicating", but nothing stops two goroutines writing
+ * the same map; the race detector finds it at runtime, if the test hits
+ * it. In Rust, a value without Send/Sync simply can't cross the
+ * thread::spawn boundary (see concurrency.rs).
+ */
+
+// GOROUTINES vs TOKIO TASKS ----------------------------------------------
+
+/*
+ * Go: goroutines are cheap, and select waits on several channels
+ *
+ *     results := make(chan int)
+ *     for i := 1; i <= 1000; i++ {
+ *         go func(i int) { results <- i }(i)   // <- 1000 goroutines, fine
+ *     }
+ *     sum := 0
+ *     for i := 0; i < 1000; i++ {
+ *         sum += <-results
+ *     }
+ *     fmt.Println(sum)                           // 500500
+ *
+ *     select {
+ *     case v := <-slow:
+ *         fmt.Println(v)
+ *     case <-time.After(10 * time.Millisecond):
+ *         fmt.Println("timeout")
+ *     }
+ *
+ * The closest Rust equivalent of goroutines is async tasks: tokio's
+ * runtime multiplexes thousands of them over a few OS threads, just as
+ * Go's scheduler does. The difference is that Go's runtime is always
+ * there and every function can block; in Rust you opt in, and only
+ * async fns can .await.
+ */
+
+async fn tasks_tokio() {
+    use tokio::sync::mpsc;
+
+    let (tx, mut rx) = mpsc::channel(16);
+    for i in 1..=1000 {
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            tx.send(i).await.unwrap();
+        });
+    }
+    drop(tx);
+
+    let mut sum = 0;
+    while let Some(v) = rx.recv().await {
+        sum += v;
+    }
+    println!("{sum}");                 // 500500
+
+    // select! is Go's select: whichever branch is ready first wins, the
+    // others are cancelled (dropped)
+    let (_slow_tx, mut slow) = mpsc::channel::<i32>(1);
+    tokio::select! {
+        v = slow.recv() => println!("{v:?}"),
+        _ = tokio::time::sleep(Duration::from_millis(10)) => println!("timeout"),
+    }
+}
+
+// ERROR VALUES vs RESULT -------------------------------------------------
+
+/*
+ * Go
+ *
+ *     func parsePort(text string) (int, error) {
+ *         port, err := strconv.Atoi(text)
+ *         if err != nil {
+ *             return 0, fmt.Errorf("parsing %q: %w", text, err)
+ *         }
+ *         if port == 0 {
+ *             return 0, errors.New("port 0 is reserved")
+ *         }
+ *         return port, nil
+ *     }
+ *
+ *     port, _ := parsePort("http")     // <- compiles: error silently dropped,
+ *     fmt.Println(port)                //    port is the zero value, 0
+ *
+ * Both languages treat errors as values, not exceptions. Go returns a
+ * pair (value, error) and trusts you to check err before using value;
+ * both are always there.
+ */
+
+fn parse_port(text: &str) -> Result<u16, String> {
+    let port: u16 = text.parse().map_err(|e| format!("parsing {text:?}: {e}"))?;
+    if port == 0 {
+        return Err("port 0 is reserved".to_string());
+    }
+    Ok(port)
+}
+
+fn error_values() {
+    // Result is *either* a value *or* an error: there is no port to use
+    // by mistake when parsing failed. Getting at it means a match, ?,
+    // unwrap() (an explicit "crash if not"), or a chosen default:
+    let port = parse_port("http").unwrap_or(0);
+    println!("{port}");                // 0, but you had to say so
+
+    for text in ["8080", "http", "0"] {
+        match parse_port(text) {
+            Ok(port) => println!("ok {port}"),
+            Err(e) => println!("error {e}"),
+        }
+    }
+}
+
+/*
+ * `if err != nil { return err }` is what ? does. Go's %w wrapping and
+ * errors.Is/As correspond to an error enum or the source() chain of
+ * std::error::Error.
+ */
+
+// INTERFACES vs TRAITS ---------------------------------------------------
+
+/*
+ * Go (runnable): interfaces are satisfied implicitly (structural typing)
+ *
+ *     package main
+ *
+ *     import "fmt"
+ *
+ *     // in fmt: type Stringer interface { String() string }
+ *
+ *     type Celsius float64
+ *
+ *     func (c Celsius) String() string { return fmt.Sprintf("%.1f°C", float64(c)) }
+ *
+ *     func main() {
+ *         var s fmt.Stringer = Celsius(21.5)  // <- Celsius never mentions Stringer
+ *         fmt.Println(s)                      // 21.5°C
+ *     }
+ *
+ * Rust: traits are implemented explicitly (nominal typing). Having a
+ * method with the right name is not enough; `impl Display for Celsius`
+ * is the declaration that you meant it.
+ */
+
+struct Celsius(f64);
+
+impl fmt::Display for Celsius {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.1}°C", self.0)
+    }
+}
+
+fn interfaces_vs_traits() {
+    let s: &dyn fmt::Display = &Celsius(21.5);   // like a Go interface value: data + method table
+    println!("{s}");                             // 21.5°C
+}
+
+/*
+ * What explicit impls buy:
+ * (1) two unrelated methods that happen to share a name (a Close()
+ *     that means "close the file" and one that means "is it nearby?")
+ *     can't accidentally satisfy the same interface
+ * (2) generic functions: Go added generics in 1.18, constrained by
+ *     interfaces; Rust's trait bounds were there from 1.0 and are
+ *     resolved at compile time (monomorphization), while a Go interface
+ *     value is always a dynamic call, like Rust's dyn Trait
+ * What it costs: you can't make a type from another package satisfy
+ * your interface after the fact without a wrapper, unless the trait is
+ * yours (the orphan rule in generics.rs).
+ */
+
+fn main() {
+    worker_pool_threads();
+    tokio::runtime::Runtime::new().unwrap().block_on(tasks_tokio());
+    error_values();
+    interfaces_vs_traits();
+}
@@ -0,0 +1,271 @@
+// RUST vs HASKELL --------------------------------------------------------
+
+/*
+ * For functional programmers. A lot of Rust was borrowed from the ML
+ * family and Haskell: algebraic data types, pattern matching, type
+ * classes (as traits), Maybe and Either (as Option and Result), type
+ * inference. What is missing is purity by default, laziness by default,
+ * and higher-kinded types.
+ *
+ * Haskell listings are GHC; each Rust function prints what `main`
+ * would print for the listing above it.
+ */
+
+use std::fmt;
+
+// TYPECLASSES vs TRAITS --------------------------------------------------
+
+/*
+ * Haskell
+ *
+ *     class Shape a where
+ *         area :: a -> Double
+ *         describe :: a -> String
+ *         describe x = "shape with area " ++ show (area x)   -- default
+ *
+ *     data Square = Square Double
+ *     instance Shape Square where
+ *         area (Square s) = s * s
+ *
+ *     class Monoid' a where
+ *         empty :: a                                          -- no argument!
+ *         combine :: a -> a -> a
+ *
+ *     instance Monoid' [b] where
+ *         empty = []
+ *         combine = (++)
+ *
+ *     mconcat' :: Monoid' a => [a] -> a
+ *     mconcat' = foldr combine empty
+ *
+ *     main = do
+ *         putStrLn (describe (Square 3))      -- shape with area 9.0
+ *         print (mconcat' [[1, 2], [3], [4]]) -- [1,2,3,4]
+ */
+
+trait Shape {
+    fn area(&self) -> f64;
+
+    fn describe(&self) -> String {
+        format!("shape with area {:?}", self.area())
+    }
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+// Dispatch on the *return* type works too: empty() has no argument to
+// pick the instance from, the caller's expected type does it. This is
+// the part of typeclasses that Java interfaces can't do.
+trait Monoid {
+    fn empty() -> Self;
+    fn combine(self, other: Self) -> Self;
+}
+
+impl<B> Monoid for Vec<B> {
+    fn empty() -> Self {
+        Vec::new()
+    }
+    fn combine(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+fn mconcat<M: Monoid>(items: Vec<M>) -> M {
+    items.into_iter().fold(M::empty(), M::combine)
+}
+
+fn typeclasses() {
+    println!("{}", Square(3.0).describe());                    // shape with area 9.0
+    println!("{:?}", mconcat(vec![vec![1, 2], vec![3], vec![4]]));   // [1, 2, 3, 4]
+}
+
+/*
+ *                         Haskell                 Rust
+ *     constraint          Shape a =>              T: Shape
+ *     default methods     yes                     yes
+ *     return-type poly    mempty, read            Default::default(), FromStr, collect()
+ *     superclass          class Eq a => Ord a     trait Ord: Eq
+ *     deriving            deriving (Eq, Show)     #[derive(PartialEq, Debug)]
+ *     orphan instances    warning                 error (generics.rs)
+ *     higher-kinded       class Functor f         no: there is no trait over Option<_>
+ *                                                 itself, so no generic fmap
+ *
+ * The missing Functor/Monad classes are why Option, Result and
+ * Iterator each have their own map, and_then, etc. by convention, and
+ * why there is no do-notation (? covers the Maybe/Either case).
+ */
+
+// ADTs vs ENUMS ----------------------------------------------------------
+
+/*
+ * Haskell
+ *
+ *     data Expr = Num Double
+ *               | Add Expr Expr
+ *               | Mul Expr Expr
+ *               | Neg Expr
+ *
+ *     eval :: Expr -> Double
+ *     eval (Num n)   = n
+ *     eval (Add a b) = eval a + eval b
+ *     eval (Mul a b) = eval a * eval b
+ *     eval (Neg e)   = negate (eval e)
+ *
+ *     main = print (eval (Add (Num 1) (Mul (Num 2) (Neg (Num 3)))))   -- -5.0
+ */
+
+enum Expr {
+    Num(f64),
+    Add(Box<Expr>, Box<Expr>),         // Box: a recursive type needs a known size
+    Mul(Box<Expr>, Box<Expr>),
+    Neg(Box<Expr>),
+}
+
+fn eval(e: &Expr) -> f64 {
+    match e {
+        Expr::Num(n) => *n,
+        Expr::Add(a, b) => eval(a) + eval(b),
+        Expr::Mul(a, b) => eval(a) * eval(b),
+        Expr::Neg(e) => -eval(e),
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Num(n) => write!(f, "{n}"),
+            Expr::Add(a, b) => write!(f, "({a} + {b})"),
+            Expr::Mul(a, b) => write!(f, "({a} * {b})"),
+            Expr::Neg(e) => write!(f, "-{e}"),
+        }
+    }
+}
+
+fn adts() {
+    use Expr::*;
+    let e = Add(Box::new(Num(1.0)), Box::new(Mul(Box::new(Num(2.0)), Box::new(Neg(Box::new(Num(3.0)))))));
+    println!("{e} = {:?}", eval(&e));  // (1 + (2 * -3)) = -5.0
+}
+
+/*
+ * The translation is direct; the differences are about memory:
+ * (1) Haskell boxes everything behind the scenes. Rust stores enum
+ *     payloads inline, so a recursive variant needs an explicit Box
+ *     (calculator.rs in projects/ builds a whole parser on this).
+ * (2) match is checked for exhaustiveness, like -Wincomplete-patterns
+ *     but as an error instead of a warning.
+ * (3) eval takes &Expr: the tree is borrowed, not copied. Haskell's
+ *     immutability makes sharing free; Rust gets the same by borrowing.
+ */
+
+// MAYBE / EITHER vs OPTION / RESULT --------------------------------------
+
+/*
+ * Haskell
+ *
+ *     safeDiv :: Int -> Int -> Maybe Int
+ *     safeDiv _ 0 = Nothing
+ *     safeDiv a b = Just (a `div` b)
+ *
+ *     parseAge :: String -> Either String Int
+ *     parseAge s = case reads s of
+ *         [(n, "")] | n >= 0 -> Right n
+ *         _                  -> Left ("bad age: " ++ s)
+ *
+ *     halfAge :: String -> Either String Int
+ *     halfAge s = do
+ *         age <- parseAge s
+ *         maybe (Left "impossible") Right (safeDiv age 2)
+ *
+ *     main = do
+ *         print (fmap (+ 1) (safeDiv 10 2))            -- Just 6
+ *         print (safeDiv 10 0 >>= safeDiv 100)         -- Nothing
+ *         print (halfAge "42", halfAge "old")          -- (Right 21,Left "bad age: old")
+ */
+
+fn safe_div(a: i32, b: i32) -> Option<i32> {
+    if b == 0 { None } else { Some(a / b) }
+}
+
+fn parse_age(s: &str) -> Result<i32, String> {
+    match s.parse::<i32>() {
+        Ok(n) if n >= 0 => Ok(n),
+        _ => Err(format!("bad age: {s}")),
+    }
+}
+
+// ? is do-notation specialised to Option and Result
+fn half_age(s: &str) -> Result<i32, String> {
+    let age = parse_age(s)?;
+    safe_div(age, 2).ok_or_else(|| "impossible".to_string())
+}
+
+fn maybe_either() {
+    println!("{:?}", safe_div(10, 2).map(|n| n + 1));                 // Some(6)
+    println!("{:?}", safe_div(10, 0).and_then(|d| safe_div(100, d))); // None
+    println!("{:?}", (half_age("42"), half_age("old")));              // (Ok(21), Err("bad age: old"))
+}
+
+/*
+ *     Haskell              Rust
+ *     Maybe a              Option<T>
+ *     Either e a           Result<T, E>      (note: error type second)
+ *     fmap f m             m.map(f)
+ *     m >>= f              m.and_then(f)
+ *     fromMaybe d m        m.unwrap_or(d)
+ *     maybe d f m          m.map_or(d, f)
+ *     do { x <- m; .. }    let x = m?; ..
+ *     fromJust m           m.unwrap()        (both crash on Nothing/None)
+ */
+
+// LAZINESS vs STRICTNESS -------------------------------------------------
+
+/*
+ * Haskell
+ *
+ *     primes = sieve [2..] where sieve (p:xs) = p : sieve [x | x <- xs, x `mod` p /= 0]
+ *
+ *     main = do
+ *         print (take 5 primes)                      -- [2,3,5,7,11]
+ *         let xs = [1, 2, undefined]
+ *         print (length xs)                          -- 3: undefined never evaluated
+ *
+ * Everything in Haskell is lazy: a value is a thunk until something
+ * needs it, so infinite lists and unused crashes are fine.
+ */
+
+fn laziness() {
+    // Rust is strict: arguments and let bindings are evaluated right
+    // away. But *iterators* are lazy, which covers most of what
+    // infinite lists are used for:
+    let primes = (2u32..).filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0));
+    println!("{:?}", primes.take(5).collect::<Vec<_>>());   // [2, 3, 5, 7, 11]
+
+    // nothing above ran until collect() pulled five items. A Vec is
+    // eager though: every element is computed when it is built.
+    //     let xs = vec![1, 2, panic!()];    // panics here, before len()
+    // To defer a computation explicitly, store a closure (a thunk by
+    // hand) or use std::cell::LazyCell / std::sync::LazyLock.
+    let xs: Vec<Box<dyn Fn() -> i32>> = vec![Box::new(|| 1), Box::new(|| 2), Box::new(|| unimplemented!())];
+    println!("{}", xs.len());          // 3: the third closure never runs
+}
+
+/*
+ * Strict evaluation is a deliberate choice for a systems language:
+ * memory use and timing are predictable, there are no space leaks from
+ * piled-up thunks (foldl vs foldl'), and drop order is well defined.
+ */
+
+fn main() {
+    typeclasses();
+    adts();
+    maybe_either();
+    laziness();
+}
@@ -0,0 +1,225 @@
+// RUST vs JAVA -----------------------------------------------------------
+
+/*
+ * generics.rs opens its TRAITS section with "Traits are like interfaces
+ * in Java". That is the right first approximation. This file is about
+ * the second approximation: where the analogy holds and where it breaks.
+ *
+ * Java listings compile with javac 17+; each Rust function prints what
+ * the Java main prints for that section.
+ */
+
+use std::any::type_name;
+use std::fmt::Display;
+
+// TRAITS vs INTERFACES ---------------------------------------------------
+
+/*
+ * Java
+ *
+ *     interface Summary {
+ *         String summarize();
+ *         default String preview() { return summarize().substring(0, 5) + "..."; }
+ *     }
+ *
+ *     class Newspaper implements Summary {
+ *         String title;
+ *         Newspaper(String title) { this.title = title; }
+ *         public String summarize() { return "Newspaper: " + title; }
+ *     }
+ *
+ *     Summary s = new Newspaper("The Times");
+ *     System.out.println(s.summarize() + " / " + s.preview());
+ *     // Newspaper: The Times / Newsp...
+ */
+
+trait Summary {
+    fn summarize(&self) -> String;
+
+    fn preview(&self) -> String {      // default method, as in Java 8+
+        format!("{}...", &self.summarize()[..5])
+    }
+}
+
+struct Newspaper {
+    title: String,
+}
+
+impl Summary for Newspaper {
+    fn summarize(&self) -> String {
+        format!("Newspaper: {}", self.title)
+    }
+}
+
+// Where the analogy breaks: an interface must be named when the class is
+// written. A trait can be implemented later, for types you did not
+// write, including built-ins. Java cannot make Integer implement Summary.
+impl Summary for i32 {
+    fn summarize(&self) -> String {
+        format!("the number {self}")
+    }
+}
+
+fn traits_vs_interfaces() {
+    let s: Box<dyn Summary> = Box::new(Newspaper { title: "The Times".to_string() });
+    println!("{} / {}", s.summarize(), s.preview());
+    println!("{}", 42.summarize());    // the number 42
+}
+
+/*
+ *                         Java interface             Rust trait
+ *     default methods     yes                        yes
+ *     fields/state        constants only             none: data lives in the type
+ *     implemented         in the class declaration   anywhere (orphan rule, generics.rs)
+ *     on built-in types   no                         yes: impl Summary for i32
+ *     dispatch            always virtual             static with generics, dynamic with dyn
+ *     static methods      yes                        yes, and they can be abstract:
+ *                                                    fn new() -> Self, Default::default()
+ *     inheritance         class extends class        none; trait Sub: Super only
+ *                                                    requires the other trait
+ *
+ * The last row is the big one. Rust has no class inheritance at all: no
+ * abstract base classes, no protected, no super.method(). Shared
+ * behaviour goes in a trait (default methods), shared data goes in a
+ * struct that the other structs contain.
+ */
+
+// GENERICS: ERASURE vs MONOMORPHIZATION ----------------------------------
+
+/*
+ * Java
+ *
+ *     static <T extends Comparable<T>> T largest(List<T> items) { ... }
+ *
+ *     List<Integer> ints = new ArrayList<>(List.of(3, 7, 5));
+ *     List<String> strs = new ArrayList<>(List.of("b", "c", "a"));
+ *     System.out.println(largest(ints) + " " + largest(strs));   // 7 c
+ *     System.out.println(ints.getClass() == strs.getClass());   // true
+ *
+ * Java compiles largest once. Type parameters are checked and then
+ * erased: at runtime a List<Integer> and a List<String> are the same
+ * class, every element is an Object reference, and every int is boxed
+ * into an Integer on the heap. That is also why Java cannot write
+ * new T(), T.class, or List<int>.
+ */
+
+fn largest<T: PartialOrd + Copy>(items: &[T]) -> T {
+    let mut best = items[0];
+    for &item in items {
+        if item > best {
+            best = item;
+        }
+    }
+    best
+}
+
+// T::default() works because T is known at compile time: each copy of
+// this function is compiled for one concrete type. Java's `new T()`.
+fn fresh<T: Default + Display>() -> String {
+    format!("{} = {}", type_name::<T>(), T::default())
+}
+
+fn generics() {
+    println!("{} {}", largest(&[3, 7, 5]), largest(&['b', 'c', 'a']));   // 7 c
+
+    // two different types, not one erased class
+    println!("{}", type_name::<Vec<i32>>() == type_name::<Vec<char>>());  // false
+    println!("{}", fresh::<i32>());                                       // i32 = 0
+
+    // Vec<i32> stores the i32s inline: 4 bytes each, no boxing
+    println!("{}", std::mem::size_of_val(&[3i32, 7, 5][..]));             // 12
+}
+
+/*
+ * Rust monomorphizes: largest::<i32> and largest::<char> are two
+ * separate functions in the binary, each as fast as a hand-written one.
+ *
+ *                       Java (erasure)            Rust (monomorphization)
+ *     compiled copies   one                       one per concrete type
+ *     runtime types     List (raw)                Vec<i32>, Vec<char>, ...
+ *     primitives        must be boxed: Integer    stored inline
+ *     new T()           impossible                T::default(), T::new()
+ *     cost              slower calls, boxing      larger binaries, longer builds
+ *
+ * When binary size matters more than speed, dyn Trait gives you the
+ * Java model back: one compiled function, a vtable call per method.
+ */
+
+// CHECKED EXCEPTIONS vs RESULT -------------------------------------------
+
+/*
+ * Java
+ *
+ *     static int parsePort(String text) throws IOException {
+ *         int port = Integer.parseInt(text);   // throws NumberFormatException,
+ *                                              // unchecked: not in the signature
+ *         if (port == 0) throw new IOException("port 0 is reserved");
+ *         return port;
+ *     }
+ *
+ *     for (String text : new String[] {"8080", "http", "0"}) {
+ *         try {
+ *             System.out.println("ok " + parsePort(text));
+ *         } catch (IOException e) {
+ *             System.out.println("error " + e.getMessage());
+ *         } catch (NumberFormatException e) {
+ *             System.out.println("error " + e.getMessage());
+ *         }
+ *     }
+ */
+
+#[derive(Debug)]
+enum PortError {
+    NotANumber(std::num::ParseIntError),
+    Reserved,
+}
+
+impl Display for PortError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PortError::NotANumber(e) => write!(f, "{e}"),
+            PortError::Reserved => write!(f, "port 0 is reserved"),
+        }
+    }
+}
+
+fn parse_port(text: &str) -> Result<u16, PortError> {
+    let port = text.parse().map_err(PortError::NotANumber)?;
+    if port == 0 {
+        return Err(PortError::Reserved);
+    }
+    Ok(port)
+}
+
+fn exceptions_vs_result() {
+    for text in ["8080", "http", "0"] {
+        match parse_port(text) {
+            Ok(port) => println!("ok {port}"),
+            Err(e) => println!("error {e}"),
+        }
+    }
+}
+
+/*
+ * Checked exceptions were Java's attempt at "errors in the signature",
+ * and Result keeps the good half of that idea:
+ *
+ * (1) Both make the caller deal with the error or pass it on. `throws`
+ *     becomes the return type; re-throwing becomes ?.
+ * (2) Java splits errors into checked and unchecked, and the unchecked
+ *     ones (NumberFormatException above) are invisible in signatures.
+ *     Result has no such split; panics are for bugs only.
+ * (3) A Result is a plain value: it can be stored in a Vec, passed to
+ *     a closure, or mapped over with .map_err(). A checked exception
+ *     cannot escape a lambda passed to stream().map(), which is why
+ *     so much Java wraps them in RuntimeException.
+ * (4) catch matches on class hierarchy; match on an error enum is
+ *     checked for exhaustiveness, so adding a variant to PortError
+ *     flags every match that forgot it.
+ */
+
+fn main() {
+    traits_vs_interfaces();
+    generics();
+    exceptions_vs_result();
+}
@@ -0,0 +1,209 @@
+// RUST vs JAVASCRIPT / TYPESCRIPT ----------------------------------------
+
+/*
+ * For web developers. The async syntax will look familiar (async fn,
+ * .await), the type system will look like a stricter TypeScript, and
+ * the absence of null will take a week to get used to.
+ *
+ * JavaScript listings run under node 18+; TypeScript ones are shown
+ * where the types are the point. The futures section needs tokio:
+ *
+ * Cargo.toml:
+ * [dependencies]
+ * tokio = { version = "1", features = ["full"] }
+ */
+
+use std::time::Duration;
+
+// PROMISES vs FUTURES ----------------------------------------------------
+
+/*
+ * JavaScript
+ *
+ *     const p = (async () => { console.log("promise body runs"); return 1; })();
+ *     console.log("created promise");
+ *     console.log(await p);
+ *     // promise body runs
+ *     // created promise
+ *     // 1
+ *
+ *     const [a, b] = await Promise.all([delay(20, "a"), delay(10, "b")]);
+ *     console.log(a + b);                                   // ab
+ *
+ * A Promise starts running as soon as it is created: the body printed
+ * before "created promise".
+ */
+
+async fn delay(ms: u64, value: &str) -> &str {
+    tokio::time::sleep(Duration::from_millis(ms)).await;
+    value
+}
+
+async fn promises_vs_futures() {
+    let f = async {
+        println!("future body runs");
+        1
+    };
+    println!("created future");
+    println!("{}", f.await);
+    // created future
+    // future body runs
+    // 1
+
+    let (a, b) = tokio::join!(delay(20, "a"), delay(10, "b"));   // Promise.all
+    println!("{a}{b}");                                          // ab
+}
+
+/*
+ * A Future is lazy: creating it does nothing. It is a state machine
+ * that only advances when something polls it, either .await or a
+ * runtime (tokio::spawn). So:
+ *
+ * (1) the order flips: "created future" prints first
+ * (2) a future that is never awaited never runs; the compiler warns
+ *     "unused implementer of `Future` that must be used"
+ * (3) dropping a future cancels it, which JS promises can't do
+ *     (AbortController is the workaround)
+ * (4) there is no built-in event loop: node has one, Rust programs
+ *     pick a runtime (tokio here) and start it in main
+ *
+ *     JavaScript                 Rust (tokio)
+ *     Promise.all([a, b])        tokio::join!(a, b)
+ *     Promise.race([a, b])       tokio::select! { .. }
+ *     setTimeout(f, ms)          tokio::time::sleep(..).await
+ *     fire and forget            tokio::spawn(fut)
+ */
+
+// STRUCTURAL vs NOMINAL TYPING -------------------------------------------
+
+/*
+ * TypeScript: types are compatible if their shapes match
+ *
+ *     interface Point { x: number; y: number }
+ *     interface Size  { x: number; y: number }
+ *
+ *     function area(s: Size): number { return s.x * s.y; }
+ *
+ *     const p: Point = { x: 3, y: 4 };
+ *     area(p);                          // fine: same shape
+ *     area({ x: 3, y: 4, z: 5 } as any) // and any opts out entirely
+ *
+ * Rust: types are compatible only if they are the same named type.
+ */
+
+#[allow(dead_code)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+struct Size {
+    x: f64,
+    y: f64,
+}
+
+fn area(s: &Size) -> f64 {
+    s.x * s.y
+}
+
+fn structural_vs_nominal() {
+    let p = Point { x: 3.0, y: 4.0 };
+    // area(&p);     error[E0308]: mismatched types: expected `&Size`, found `&Point`
+    let s = Size { x: p.x, y: p.y };   // convert on purpose (or impl From<Point> for Size)
+    println!("{}", area(&s));          // 12
+}
+
+/*
+ * Nominal typing is what makes the "newtype" pattern useful: a
+ * struct UserId(u64) and a struct OrderId(u64) are both a u64 inside,
+ * but passing one where the other is expected is a compile error. In
+ * TypeScript that takes "branded types" tricks.
+ *
+ * There is also no `any`. The nearest thing, Box<dyn std::any::Any>,
+ * has to be downcast back before use, and a failed downcast is a
+ * None, not a runtime crash ten calls later.
+ */
+
+// NULL / UNDEFINED vs OPTION ---------------------------------------------
+
+/*
+ * JavaScript
+ *
+ *     const user = { name: "Ada", address: null };
+ *     console.log(user.address?.city ?? "no city");   // no city
+ *     console.log(user.nickname);                     // undefined: a typo
+ *                                                     // or a missing field?
+ */
+
+struct Address {
+    city: String,
+}
+
+struct User {
+    #[allow(dead_code)]
+    name: String,
+    address: Option<Address>,
+}
+
+fn null_vs_option() {
+    let user = User { name: "Ada".to_string(), address: None };
+
+    // ?. becomes .as_ref().map(..), ?? becomes .unwrap_or(..)
+    let city = user.address.as_ref().map(|a| a.city.as_str()).unwrap_or("no city");
+    println!("{city}");                // no city
+
+    // user.nickname: error[E0609]: no field `nickname` on type `User`
+}
+
+/*
+ * JavaScript has two nulls, and any value may be either. TypeScript's
+ * strictNullChecks moves that into the type (string | null), which is
+ * close to Option<String>; but it can be turned off, and `!` asserts
+ * non-null without checking.
+ *
+ * In Rust a String is always a string. Option<String> is a different
+ * type that must be unpacked before you can call String methods on it,
+ * and the unchecked assertion is spelled .unwrap(), which panics
+ * instead of producing undefined three functions later.
+ */
+
+// PROTOTYPE METHODS vs TRAITS --------------------------------------------
+
+/*
+ * JavaScript (runnable)
+ *
+ *     String.prototype.shout = function () { return this.toUpperCase() + "!"; };
+ *     console.log("hello".shout());                  // HELLO!
+ *
+ * Patching the prototype changes every string in the program, including
+ * in libraries that define their own shout().
+ */
+
+// an "extension trait": adds methods to a type you don't own
+trait Shout {
+    fn shout(&self) -> String;
+}
+
+impl Shout for str {
+    fn shout(&self) -> String {
+        format!("{}!", self.to_uppercase())
+    }
+}
+
+fn prototype_vs_traits() {
+    println!("{}", "hello".shout());   // HELLO!
+}
+
+/*
+ * The trait only takes effect where it is imported (use crate::Shout),
+ * so two libraries can each have a shout() for str without clashing:
+ * the caller picks one with a `use`. This is how crates like itertools
+ * add methods to every iterator.
+ */
+
+fn main() {
+    tokio::runtime::Runtime::new().unwrap().block_on(promises_vs_futures());
+    structural_vs_nominal();
+    null_vs_option();
+    prototype_vs_traits();
+}
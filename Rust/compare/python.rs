@@ -0,0 +1,184 @@
+// RUST vs PYTHON ---------------------------------------------------------
+
+/*
+ * Each section pairs an idiomatic Python listing (in a comment) with the
+ * Rust equivalent, followed by what the Rust version buys you.
+ *
+ * Both halves print the same output; entry.rs --run checks that they
+ * still do.
+ */
+
+use std::collections::HashMap;
+
+// OWNERSHIP --------------------------------------------------------------
+
+/*
+ * Python (runnable)
+ *
+ *     def append_world(words):
+ *         words.append("world")
+ *
+ *     words = ["hello"]
+ *     alias = words                  # a second name for the same list
+ *     append_world(alias)
+ *     print(" ".join(words))         # hello world: changed through alias
+ */
+
+fn ownership() {
+    fn append_world(words: &mut Vec<String>) {    // mutation is in the signature
+        words.push("world".to_string());
+    }
+
+    let mut words = vec!["hello".to_string()];
+    let alias = &mut words;                       // a mutable borrow, not a copy
+    append_world(alias);
+    println!("{}", words.join(" "));              // hello world
+}
+
+/*
+ * In Python every name is a reference to a shared, garbage-collected
+ * object. Anyone holding a reference can mutate it, and nothing in a
+ * function signature tells you whether it will.
+ *
+ * In Rust
+ * (1) &mut in the signature says "this function changes your Vec"
+ * (2) while `alias` is in use, `words` cannot be touched: no surprise
+ *     mutation from a second name
+ * (3) no garbage collector: the Vec is freed when `words` goes out of
+ *     scope, deterministically
+ */
+
+// COLLECTIONS ------------------------------------------------------------
+
+/*
+ * Python (runnable)
+ *
+ *     text = "the cat and the hat"
+ *     counts = {}
+ *     for word in text.split():
+ *         counts[word] = counts.get(word, 0) + 1
+ *
+ *     evens = [n * n for n in range(10) if n % 2 == 0]
+ *
+ *     print(counts["the"], evens)    # 2 [0, 4, 16, 36, 64]
+ */
+
+fn collections() {
+    let text = "the cat and the hat";
+    let mut counts = HashMap::new();
+    for word in text.split_whitespace() {
+        *counts.entry(word).or_insert(0) += 1;    // one lookup, not two
+    }
+
+    // list comprehensions become iterator chains
+    let evens: Vec<i32> = (0..10).filter(|n| n % 2 == 0).map(|n| n * n).collect();
+
+    println!("{} {:?}", counts["the"], evens);   // 2 [0, 4, 16, 36, 64]
+}
+
+/*
+ * (1) A Python list can hold anything ([1, "two", 3.0]); a Vec<T> holds
+ *     one type. Mixed data needs an enum (see SpreadsheetCell in
+ *     collections.rs), and then every use must handle every variant.
+ * (2) The HashMap's key and value types are fixed: counts[word] += "x"
+ *     is a compile error, not a TypeError at 3am.
+ * (3) Iterator chains are lazy like generator expressions, and compile
+ *     down to a plain loop: no intermediate lists.
+ * (4) counts["missing"] raises KeyError in Python and panics in Rust;
+ *     counts.get("missing") returns Option<&V> and makes you decide.
+ */
+
+// STRINGS ----------------------------------------------------------------
+
+/*
+ * Python (runnable)
+ *
+ *     name = "Ferris"
+ *     greeting = "hello, " + name            # new string
+ *     greeting += "!"
+ *     print(greeting.upper(), len("Здравствуйте"), "Здравствуйте"[0])
+ *     # HELLO, FERRIS! 12 З
+ */
+
+fn strings() {
+    let name = "Ferris";                          // &str: borrowed, read-only
+    let mut greeting = String::from("hello, ") + name;   // String: owned, growable
+    greeting.push('!');
+
+    let hello = "Здравствуйте";
+    println!(
+        "{} {} {}",
+        greeting.to_uppercase(),
+        hello.chars().count(),                    // 12 characters
+        hello.chars().next().unwrap(),            // 'З'; hello[0] does not compile
+    );
+    // hello.len() is 24: bytes, not characters
+}
+
+/*
+ * Python has one immutable str type; Rust has two:
+ *     &str     a view into string data owned by someone else
+ *     String   the owner, which can grow
+ * Functions that only read take &str, so they accept both.
+ *
+ * Python indexes strings by code point, and pays for it by storing wide
+ * strings at 2 or 4 bytes per character. Rust strings are UTF-8 bytes
+ * and refuse s[0] altogether: you choose .chars(), .bytes() or a byte
+ * range explicitly, so the cost and meaning are visible.
+ */
+
+// ERROR HANDLING ---------------------------------------------------------
+
+/*
+ * Python (runnable)
+ *
+ *     def parse_port(text):
+ *         try:
+ *             port = int(text)               # may raise ValueError
+ *         except ValueError:
+ *             raise ValueError(f"not a number: {text}")
+ *         if port == 0:
+ *             raise ValueError("port 0 is reserved")
+ *         return port
+ *
+ *     for text in ["8080", "http", "0"]:
+ *         try:
+ *             print("ok", parse_port(text))
+ *         except ValueError as e:
+ *             print("error", e)
+ */
+
+fn error_handling() {
+    fn parse_port(text: &str) -> Result<u16, String> {
+        let port: u16 = text.parse().map_err(|_| format!("not a number: {text}"))?;
+        if port == 0 {
+            return Err("port 0 is reserved".to_string());
+        }
+        Ok(port)
+    }
+
+    for text in ["8080", "http", "0"] {
+        match parse_port(text) {
+            Ok(port) => println!("ok {port}"),
+            Err(e) => println!("error {e}"),
+        }
+    }
+}
+
+/*
+ * (1) In Python any call may raise, and the signature doesn't say so.
+ *     In Rust the Result<u16, String> return type *is* the documentation,
+ *     and ignoring it is a compiler warning.
+ * (2) ? is the explicit version of "let the exception propagate": you
+ *     can see every point where the function may return early.
+ * (3) u16 also rejects 70000, which int() would happily accept.
+ * (4) panic! exists for bugs, the way an uncaught AssertionError would
+ *     be, not for expected failures like bad input.
+ */
+
+fn main() {
+    ownership();
+    collections();
+    strings();
+    error_handling();
+}
@@ -0,0 +1,452 @@
+// CROSS-LANGUAGE PERFORMANCE SHOOTOUT ------------------------------------
+
+/*
+ * The notes say "Rust is fast" in several places. This harness produces
+ * local numbers instead: the same three tasks in Rust, Python and
+ * JavaScript (node), each run as a separate process on the same input.
+ *
+ *     word count       split 400k words, count them in a hash map
+ *     json parse       parse 20k records, sum a field, count tags
+ *     matrix multiply  two 150x150 integer matrices, naive triple loop
+ *
+ * For each run the report shows
+ *     wall   the whole process, as the harness sees it (includes
+ *            interpreter start-up)
+ *     task   what the program measured around the task itself
+ *     peak   maximum resident memory, as reported by the program
+ * and whether the answer matches Rust's. A fast wrong answer is not a
+ * result, so mismatches are flagged.
+ *
+ * Interpreters that aren't installed are skipped. Peak memory comes
+ * from /proc (Rust) and getrusage (Python, node), so it is Linux-first;
+ * elsewhere the column may read "-".
+ *
+ * cargo new shootout
+ * (copy this file into src/main.rs)
+ *
+ * Cargo.toml:
+ * [dependencies]
+ * serde_json = "1"
+ *
+ * cargo run --release                        print the report
+ * cargo run --release -- --report shootout.md    and write it as Markdown
+ *
+ * Build with --release: a debug build makes Rust look 10-50x slower than
+ * it is, and the harness says so.
+ */
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+const WORDS: usize = 400_000;
+const RECORDS: usize = 20_000;
+const MATRIX_N: usize = 150;
+const RUNS: usize = 3;                 // best of
+
+const TASKS: [&str; 3] = ["wordcount", "json", "matrix"];
+
+// INPUTS -----------------------------------------------------------------
+
+// A fixed-seed generator, so every run and every language sees the same
+// input files.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        self.0 >> 33
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+fn write_inputs(dir: &Path) -> std::io::Result<()> {
+    let mut rng = Lcg(42);
+
+    // words: a 5000-word vocabulary of syllable soup, skewed so that a
+    // few words are common (like real text)
+    const SYLLABLES: [&str; 12] = ["ka", "lo", "mi", "ru", "st", "an", "ve", "to", "ne", "ish", "or", "ya"];
+    let vocabulary: Vec<String> = (0..5000)
+        .map(|_| (0..2 + rng.below(3)).map(|_| SYLLABLES[rng.below(12) as usize]).collect())
+        .collect();
+    let mut text = String::new();
+    for i in 0..WORDS {
+        let skewed = rng.below(5000) * rng.below(5000) / 5000;
+        text.push_str(&vocabulary[skewed as usize]);
+        text.push(if i % 12 == 11 { '\n' } else { ' ' });
+    }
+    fs::write(dir.join("words.txt"), text)?;
+
+    // json: [{"id":0,"name":"item0","score":17,"tags":["x","y"]}, ...]
+    let mut json = String::from("[");
+    for i in 0..RECORDS {
+        let tags: Vec<String> = (0..rng.below(5)).map(|t| format!("\"tag{t}\"")).collect();
+        if i > 0 {
+            json.push(',');
+        }
+        write!(json, "{{\"id\":{i},\"name\":\"item{i}\",\"score\":{},\"tags\":[{}]}}", rng.below(1000), tags.join(",")).unwrap();
+    }
+    json.push(']');
+    fs::write(dir.join("data.json"), json)?;
+
+    // matrix: n, then A and B, one row per line
+    let mut matrix = format!("{MATRIX_N}\n");
+    for _ in 0..2 * MATRIX_N {
+        let row: Vec<String> = (0..MATRIX_N).map(|_| rng.below(10).to_string()).collect();
+        matrix.push_str(&row.join(" "));
+        matrix.push('\n');
+    }
+    fs::write(dir.join("matrix.txt"), matrix)
+}
+
+// THE RUST CONTESTANTS ---------------------------------------------------
+
+// "distinct-words top-word top-count"; ties go to the alphabetically
+// first word, in every language
+fn word_count(text: &str) -> String {
+    let mut counts: HashMap<&str, u32> = HashMap::new();
+    for word in text.split_whitespace() {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    let (top, n) = counts.iter().max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0))).expect("input is not empty");
+    format!("{} {top} {n}", counts.len())
+}
+
+// "sum-of-scores total-tags"
+fn json_parse(text: &str) -> String {
+    let value: serde_json::Value = serde_json::from_str(text).expect("valid input");
+    let records = value.as_array().expect("an array");
+    let score: i64 = records.iter().map(|r| r["score"].as_i64().unwrap_or(0)).sum();
+    let tags: usize = records.iter().map(|r| r["tags"].as_array().map_or(0, Vec::len)).sum();
+    format!("{score} {tags}")
+}
+
+// "sum-of-all-cells trace"; i-k-j loop order walks both B and C row by
+// row, which is what every version does
+fn matrix_multiply(text: &str) -> String {
+    let mut lines = text.lines();
+    let n: usize = lines.next().and_then(|l| l.parse().ok()).expect("size line");
+    let rows: Vec<Vec<i64>> = lines.map(|l| l.split(' ').map(|x| x.parse().unwrap()).collect()).collect();
+    let (a, b) = rows.split_at(n);
+
+    let mut c = vec![vec![0i64; n]; n];
+    for i in 0..n {
+        for k in 0..n {
+            let aik = a[i][k];
+            for j in 0..n {
+                c[i][j] += aik * b[k][j];
+            }
+        }
+    }
+    let sum: i64 = c.iter().flatten().sum();
+    let trace: i64 = (0..n).map(|i| c[i][i]).sum();
+    format!("{sum} {trace}")
+}
+
+// VmHWM: the high-water mark of resident memory, in kB
+fn peak_kb() -> u64 {
+    fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|s| s.lines().find(|l| l.starts_with("VmHWM:")).map(str::to_string))
+        .and_then(|l| l.split_whitespace().nth(1).and_then(|kb| kb.parse().ok()))
+        .unwrap_or(0)
+}
+
+// The child side: `shootout --task wordcount <dir>`. Reading the input is
+// part of the task in every language.
+fn run_task(task: &str, dir: &Path) {
+    let start = Instant::now();
+    let result = match task {
+        "wordcount" => word_count(&fs::read_to_string(dir.join("words.txt")).unwrap()),
+        "json" => json_parse(&fs::read_to_string(dir.join("data.json")).unwrap()),
+        "matrix" => matrix_multiply(&fs::read_to_string(dir.join("matrix.txt")).unwrap()),
+        _ => panic!("unknown task {task}"),
+    };
+    println!("result {result}");
+    println!("ms {:.3}", start.elapsed().as_secs_f64() * 1000.0);
+    println!("peak_kb {}", peak_kb());
+}
+
+// THE OTHER CONTESTANTS --------------------------------------------------
+
+// Idiomatic, not heroic: standard library only, no numpy. Each prints
+// the same three lines as run_task.
+
+const PYTHON: &str = r#"
+import json, resource, sys, time
+from collections import Counter
+
+def wordcount(d):
+    counts = Counter(open(d + "/words.txt").read().split())
+    top, n = min(counts.items(), key=lambda kv: (-kv[1], kv[0]))
+    return f"{len(counts)} {top} {n}"
+
+def parse(d):
+    records = json.load(open(d + "/data.json"))
+    return f"{sum(r['score'] for r in records)} {sum(len(r['tags']) for r in records)}"
+
+def matrix(d):
+    lines = open(d + "/matrix.txt").read().split("\n")
+    n = int(lines[0])
+    rows = [[int(x) for x in l.split(" ")] for l in lines[1:2 * n + 1]]
+    a, b = rows[:n], rows[n:]
+    c = [[0] * n for _ in range(n)]
+    for i in range(n):
+        ci = c[i]
+        for k in range(n):
+            aik, bk = a[i][k], b[k]
+            for j in range(n):
+                ci[j] += aik * bk[j]
+    return f"{sum(map(sum, c))} {sum(c[i][i] for i in range(n))}"
+
+task, d = sys.argv[1], sys.argv[2]
+start = time.perf_counter()
+result = {"wordcount": wordcount, "json": parse, "matrix": matrix}[task](d)
+print("result", result)
+print("ms %.3f" % ((time.perf_counter() - start) * 1000))
+print("peak_kb", resource.getrusage(resource.RUSAGE_SELF).ru_maxrss)
+"#;
+
+const NODE: &str = r#"
+const fs = require("fs");
+
+function wordcount(d) {
+  const counts = new Map();
+  for (const w of fs.readFileSync(d + "/words.txt", "utf8").split(/\s+/)) {
+    if (w) counts.set(w, (counts.get(w) || 0) + 1);
+  }
+  let top = null, n = 0;
+  for (const [w, c] of counts) {
+    if (c > n || (c === n && w < top)) { top = w; n = c; }
+  }
+  return `${counts.size} ${top} ${n}`;
+}
+
+function parse(d) {
+  const records = JSON.parse(fs.readFileSync(d + "/data.json", "utf8"));
+  let score = 0, tags = 0;
+  for (const r of records) { score += r.score; tags += r.tags.length; }
+  return `${score} ${tags}`;
+}
+
+function matrix(d) {
+  const lines = fs.readFileSync(d + "/matrix.txt", "utf8").split("\n");
+  const n = parseInt(lines[0]);
+  const rows = lines.slice(1, 2 * n + 1).map(l => l.split(" ").map(Number));
+  const a = rows.slice(0, n), b = rows.slice(n);
+  const c = Array.from({ length: n }, () => new Array(n).fill(0));
+  for (let i = 0; i < n; i++)
+    for (let k = 0; k < n; k++) {
+      const aik = a[i][k], bk = b[k], ci = c[i];
+      for (let j = 0; j < n; j++) ci[j] += aik * bk[j];
+    }
+  let sum = 0, trace = 0;
+  for (let i = 0; i < n; i++) { trace += c[i][i]; for (const x of c[i]) sum += x; }
+  return `${sum} ${trace}`;
+}
+
+const [task, d] = process.argv.slice(2);
+const start = process.hrtime.bigint();
+const result = { wordcount, json: parse, matrix }[task](d);
+console.log("result", result);
+console.log("ms", (Number(process.hrtime.bigint() - start) / 1e6).toFixed(3));
+console.log("peak_kb", process.resourceUsage().maxRSS);
+"#;
+
+// THE HARNESS ------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+struct Measurement {
+    wall_ms: f64,
+    task_ms: f64,
+    peak_kb: u64,
+    result: String,
+}
+
+// runs the command once and reads the three lines it prints
+fn measure(command: &[&str]) -> Result<Measurement, String> {
+    let start = Instant::now();
+    let output = Command::new(command[0])
+        .args(&command[1..])
+        .output()
+        .map_err(|e| format!("{}: {e}", command[0]))?;
+    let wall_ms = start.elapsed().as_secs_f64() * 1000.0;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).lines().last().unwrap_or("failed").to_string());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let field = |name: &str| {
+        stdout
+            .lines()
+            .find_map(|l| l.strip_prefix(name).map(|v| v.trim().to_string()))
+            .ok_or_else(|| format!("no `{name}` line in output"))
+    };
+    Ok(Measurement {
+        wall_ms,
+        task_ms: field("ms")?.parse().map_err(|_| "bad ms")?,
+        peak_kb: field("peak_kb")?.parse().unwrap_or(0),
+        result: field("result")?,
+    })
+}
+
+// best of RUNS by wall time: the minimum is the least noisy estimate of
+// what the code costs, the rest is the machine doing other things
+fn best_of(command: &[&str]) -> Result<Measurement, String> {
+    let mut best: Option<Measurement> = None;
+    for _ in 0..RUNS {
+        let m = measure(command)?;
+        if best.as_ref().is_none_or(|b| m.wall_ms < b.wall_ms) {
+            best = Some(m);
+        }
+    }
+    Ok(best.expect("RUNS > 0"))
+}
+
+struct Row {
+    task: &'static str,
+    language: &'static str,
+    outcome: Result<Measurement, String>,
+    matches: bool,
+}
+
+fn available(program: &str) -> bool {
+    Command::new(program).arg("--version").output().is_ok()
+}
+
+fn run_all(dir: &Path) -> Vec<Row> {
+    let exe = std::env::current_exe().expect("own path");
+    let exe = exe.to_str().expect("utf-8 path");
+    let dir = dir.to_str().expect("utf-8 path");
+    let python = format!("{dir}/bench.py");
+    let node = format!("{dir}/bench.js");
+    fs::write(&python, PYTHON).unwrap();
+    fs::write(&node, NODE).unwrap();
+
+    let mut contestants: Vec<(&'static str, Vec<&str>)> = vec![("Rust", vec![exe, "--task"])];
+    if available("python3") {
+        contestants.push(("Python", vec!["python3", &python]));
+    }
+    if available("node") {
+        contestants.push(("node", vec!["node", &node]));
+    }
+
+    let mut rows = Vec::new();
+    for task in TASKS {
+        let mut reference: Option<String> = None;
+        for (language, base) in &contestants {
+            let mut command = base.clone();
+            command.extend([task, dir]);
+            let outcome = best_of(&command);
+            // Rust runs first and sets the expected answer
+            let result = outcome.as_ref().map(|m| m.result.clone()).ok();
+            let reference = reference.get_or_insert_with(|| result.clone().unwrap_or_default());
+            let matches = result.as_ref() == Some(reference);
+            rows.push(Row { task, language, outcome, matches });
+        }
+    }
+    rows
+}
+
+// REPORT -----------------------------------------------------------------
+
+fn format_peak(kb: u64) -> String {
+    if kb == 0 { "-".to_string() } else { format!("{:.1}", kb as f64 / 1024.0) }
+}
+
+fn render(rows: &[Row], markdown: bool) -> String {
+    let mut out = String::new();
+    let header = ["task", "language", "wall ms", "task ms", "peak MB", "answer"];
+    let cells: Vec<[String; 6]> = rows
+        .iter()
+        .map(|row| match &row.outcome {
+            Ok(m) => [
+                row.task.to_string(),
+                row.language.to_string(),
+                format!("{:.1}", m.wall_ms),
+                format!("{:.1}", m.task_ms),
+                format_peak(m.peak_kb),
+                if row.matches { "ok".to_string() } else { format!("MISMATCH: {}", m.result) },
+            ],
+            Err(e) => [row.task.to_string(), row.language.to_string(), "-".into(), "-".into(), "-".into(), format!("failed: {e}")],
+        })
+        .collect();
+
+    if markdown {
+        writeln!(out, "| {} |", header.join(" | ")).unwrap();
+        writeln!(out, "|{}", "---|".repeat(header.len())).unwrap();
+        for row in &cells {
+            writeln!(out, "| {} |", row.join(" | ")).unwrap();
+        }
+    } else {
+        let widths: Vec<usize> = (0..header.len())
+            .map(|i| cells.iter().map(|r| r[i].len()).chain([header[i].len()]).max().unwrap())
+            .collect();
+        let line = |row: &[&str]| -> String {
+            let padded: Vec<String> = row
+                .iter()
+                .enumerate()
+                // numbers right-aligned, text left-aligned
+                .map(|(i, c)| if (2..5).contains(&i) { format!("{c:>w$}", w = widths[i]) } else { format!("{c:<w$}", w = widths[i]) })
+                .collect();
+            padded.join("  ").trim_end().to_string()
+        };
+        writeln!(out, "{}", line(&header)).unwrap();
+        for row in &cells {
+            writeln!(out, "{}", line(&row.each_ref().map(String::as_str))).unwrap();
+        }
+    }
+    out
+}
+
+// MAIN -------------------------------------------------------------------
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("--task") {
+        run_task(&args[2], Path::new(&args[3]));
+        return;
+    }
+
+    assert_eq!(word_count("b a b a c"), "3 a 2");        // tie: alphabetical
+    assert_eq!(json_parse(r#"[{"score":2,"tags":["x"]},{"score":3,"tags":[]}]"#), "5 1");
+    assert_eq!(matrix_multiply("2\n1 2\n3 4\n5 6\n7 8\n"), "134 69");   // [[19,22],[43,50]]
+
+    if cfg!(debug_assertions) {
+        eprintln!("warning: debug build; the Rust numbers below are not meaningful (use --release)\n");
+    }
+
+    let dir = std::env::temp_dir().join(format!("shootout_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    write_inputs(&dir).expect("cannot write input files");
+
+    let rows = run_all(&dir);
+    print!("{}", render(&rows, false));
+    if let Some(i) = args.iter().position(|a| a == "--report") {
+        let path = args.get(i + 1).expect("--report needs a file name");
+        fs::write(path, render(&rows, true)).expect("cannot write report");
+        println!("\nwrote {path}");
+    }
+    fs::remove_dir_all(&dir).unwrap();
+
+    if rows.iter().any(|r| !r.matches) {
+        std::process::exit(1);
+    }
+}
+
+// EXERCISES --------------------------------------------------------------
+
+/*
+ * (1) Add numpy to the Python matrix task. How much of the gap closes,
+ *     and what does that say about where Python's speed comes from?
+ * (2) Parse the JSON into #[derive(Deserialize)] structs instead of
+ *     serde_json::Value. What happens to time and peak memory?
+ * (3) Add a fourth contestant: the C or Go version of one task, using
+ *     the same three-line output protocol.
+ */
@@ -0,0 +1,173 @@
+// RUST vs SWIFT / KOTLIN: OPTIONALS --------------------------------------
+
+/*
+ * The question mobile developers ask first: "where is ?. in Rust?"
+ *
+ * Swift and Kotlin already have what Rust has: a type system where
+ * "may be missing" is part of the type (String? vs String). The ideas
+ * carry over directly; only the spelling is different. Swift's
+ * Optional<T> is even an enum, exactly like Rust's:
+ *
+ *     enum Optional<Wrapped> { case none; case some(Wrapped) }   // Swift
+ *     enum Option<T> { None, Some(T) }                           // Rust
+ *
+ * One running example: a user who may have an address, which may have
+ * a postcode.
+ *
+ *     // Swift                                 // Kotlin
+ *     struct Address { let postcode: String? }    data class Address(val postcode: String?)
+ *     struct User { let address: Address? }       data class User(val address: Address?)
+ */
+
+struct Address {
+    postcode: Option<String>,
+}
+
+struct User {
+    address: Option<Address>,
+}
+
+fn users() -> [User; 3] {
+    [
+        User { address: Some(Address { postcode: Some("EH1 1YZ".to_string()) }) },
+        User { address: Some(Address { postcode: None }) },
+        User { address: None },
+    ]
+}
+
+// OPTIONAL CHAINING: ?. --------------------------------------------------
+
+/*
+ *     Swift:   user.address?.postcode?.count              // Int?
+ *     Kotlin:  user.address?.postcode?.length             // Int?
+ *
+ * Rust has two spellings, depending on where you are:
+ *
+ * (1) inside a function returning Option, ? after an Option means
+ *     "if None, return None from this function". That is ?. spread over
+ *     several statements:
+ */
+
+fn postcode_len(user: &User) -> Option<usize> {
+    let address = user.address.as_ref()?;
+    let postcode = address.postcode.as_ref()?;
+    Some(postcode.len())
+}
+
+/*
+ * (2) inside an expression, the combinators:
+ *         ?.field        .as_ref().and_then(|x| x.field.as_ref())   field is Option
+ *         ?.method()     .map(|x| x.method())                       result is plain
+ *
+ * as_ref() turns &Option<Address> into Option<&Address>: we only want
+ * to look at the address, not move it out of the user.
+ */
+
+fn chaining() {
+    for user in &users() {
+        let chained = user.address.as_ref().and_then(|a| a.postcode.as_ref()).map(|p| p.len());
+        assert_eq!(chained, postcode_len(user));
+        println!("{chained:?}");       // Some(7), None, None
+    }
+}
+
+// DEFAULTS: ?? AND ?: ----------------------------------------------------
+
+/*
+ *     Swift:   user.address?.postcode ?? "unknown"
+ *     Kotlin:  user.address?.postcode ?: "unknown"
+ *
+ *     Kotlin:  val p = user.address?.postcode ?: return   // elvis + early exit
+ *     Swift:   guard let p = user.address?.postcode else { return }
+ */
+
+fn postcode_or_unknown(user: &User) -> &str {
+    user.address
+        .as_ref()
+        .and_then(|a| a.postcode.as_deref())   // as_deref: &Option<String> -> Option<&str>
+        .unwrap_or("unknown")                  // ?? / ?:
+}
+
+fn defaults() {
+    for user in &users() {
+        println!("{}", postcode_or_unknown(user));   // EH1 1YZ, unknown, unknown
+    }
+
+    // Swift's guard let / Kotlin's ?: return is let-else
+    let users = users();
+    let Some(address) = &users[0].address else {
+        return;
+    };
+    println!("{}", address.postcode.is_some());      // true
+}
+
+/*
+ *     unwrap_or(x)            x is always evaluated (like a plain argument)
+ *     unwrap_or_else(|| x)    x only computed when needed: Kotlin's ?: run { .. }
+ *     unwrap_or_default()     "" for strings, 0 for numbers
+ */
+
+// FORCE UNWRAP: ! AND !! -------------------------------------------------
+
+/*
+ *     Swift:   user.address!.postcode!      // crash: "Unexpectedly found nil"
+ *     Kotlin:  user.address!!.postcode!!    // NullPointerException
+ */
+
+fn force_unwrap() {
+    let users = users();
+    let first = users[0].address.as_ref().unwrap().postcode.as_ref().unwrap();
+    println!("{first}");               // EH1 1YZ
+
+    // On None, unwrap() panics: "called `Option::unwrap()` on a `None`
+    // value". expect() is the same crash with your message, which is
+    // what to use when you're sure:
+    let second = users[1].address.as_ref().expect("every user in the fixture has an address");
+    println!("{}", second.postcode.is_none());   // true
+}
+
+/*
+ * Same rule as in Swift and Kotlin: force unwrapping is fine when None
+ * would be a bug (like an assert), not when it's a possible input.
+ * grep for unwrap( in code review the way you'd grep for !!.
+ */
+
+// IF LET -----------------------------------------------------------------
+
+/*
+ *     Swift:   if let p = user.address?.postcode { print(p) } else { print("none") }
+ *     Kotlin:  user.address?.postcode?.let { println(it) } ?: println("none")
+ */
+
+fn if_let() {
+    for user in &users() {
+        if let Some(Address { postcode: Some(p) }) = &user.address {
+            println!("{p}");           // a pattern can look two levels deep
+        } else {
+            println!("none");
+        }
+    }
+}
+
+// WHAT'S DIFFERENT -------------------------------------------------------
+
+/*
+ * (1) In Swift and Kotlin, T? is built into the language and T converts
+ *     to T? implicitly (let x: Int? = 5). In Rust, Option<T> is a normal
+ *     enum from the standard library and you write Some(5).
+ * (2) Kotlin's nullability disappears at Java boundaries (platform
+ *     types, String!). Rust has no such hole; FFI pointers are raw
+ *     pointers, in unsafe code.
+ * (3) The same ? operator works for Result, so "optional chaining" and
+ *     "error propagation" are one feature. Swift's try and Kotlin's
+ *     exceptions are separate mechanisms.
+ * (4) Option<&T> and Option<Box<T>> are the size of a pointer: None is
+ *     stored as null, so there's no cost over a nullable pointer.
+ */
+
+fn main() {
+    chaining();
+    defaults();
+    force_unwrap();
+    if_let();
+}
@@ -19,7 +19,10 @@ let handle = thread::spawn(|| {
 // main thread awaits until the spawn thread is finished
 handle.join().unwrap();
 
-// move keyword in spawn
+// join waits for a thread to end by itself; asking one to end, on
+// Ctrl-C say, is [[shutdown#a flag for the threads]]
+
+// move keyword in spawn (what a closure captures: [[closures_and_iterators#closures]])
 // force the closure to take ownership
 let name = "Rust".to_string();
 
@@ -50,6 +53,10 @@ println!("Received: {}", received);
 // When the transmitter closes, recv will return an error
 // to signal that no more values will be coming.
 
+// channel() is unbounded: send never waits, and a receiver that falls
+// behind leaves the messages piling up in memory. sync_channel(n) has
+// room for n, and what a full one does: [[backpressure#sync_channel]]
+
 // try_recv doesn’t block, but will instead return
 // a Result<T, E> immediately
 let received = rx.try_recv().unwrap();
@@ -91,7 +98,7 @@ thread::spawn(move || {
     ];
 
     for val in vals {
-	tx1.send(val).unwrap();
+	tx1.send(val).unwrap();                      // step 1: val
 	thread::sleep(Duration::from_secs(1));
     }
 });
@@ -103,15 +110,37 @@ thread::spawn(move || {
     ];
 
     for val in vals {
-	tx.send(val).unwrap();
+	tx.send(val).unwrap();                       // step 2: val
 	thread::sleep(Duration::from_secs(1));
     }
 });
 
 for received in rx {
-    println!("Received: {received}");
+    println!("Received: {received}");         // step 3: received
 }
 
+// the same, step by step (see diagrams/trace.rs, run with the three use
+// lines on top, which is where the line numbers count from):
+//
+//  #  step  thread     line
+//  1  1     thread 2     15  tx1.send(val).unwrap();            val = "some"
+//  2  2     thread 3     27  tx.send(val).unwrap();             val = "more"
+//  3  3     main         33  println!("Received: {received}");  received = "some"
+//                          > Received: some
+//  4  3     main         33  println!("Received: {received}");  received = "more"
+//                          > Received: more
+//  5  1     thread 2     15  tx1.send(val).unwrap();            val = "messages"
+//  6  2     thread 3     27  tx.send(val).unwrap();             val = "messages"
+//  7  3     main         33  println!("Received: {received}");  received = "messages"
+//                          > Received: messages
+//  8  3     main         33  println!("Received: {received}");  received = "messages"
+//                          > Received: messages
+//
+// the two producers take turns with each other and with main, in an
+// order the program doesn't promise: run it again and 1 and 2 can swap.
+// What is promised is that main gets each value after it was sent, and
+// the values from one producer in the order it sent them
+
 // shared-state concurrency ---------------------------------------------
 
 // multiple threads accessing the same shared data
@@ -192,3 +221,6 @@ for handle in handles {
 // need to lock() to access the data
 // println!("Result: {}", counter.lock().unwrap()); 
 println!("Result: {}", *counter.lock().unwrap());
+
+// in async code, where a guard alive at an .await is a bug of its own,
+// see [[async_sync#a lock held across an await]]
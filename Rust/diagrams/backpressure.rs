@@ -0,0 +1,264 @@
+// DIAGRAMS: A FAST PRODUCER, A SLOW CONSUMER, AND THE MEMORY BETWEEN -----
+
+/*
+ * backpressure.rs says an unbounded channel lets a fast producer fill
+ * memory, and a bounded one doesn't. This measures it: one thread
+ * making MESSAGES messages of 1 KB, 2 µs of work each, another taking
+ * them and spending five times that on each, connected three ways:
+ *
+ *     unbounded     mpsc::channel(): send never waits
+ *     block         mpsc::sync_channel(CAPACITY): send waits when full
+ *     drop          sync_channel(CAPACITY) with try_send: a full
+ *                   channel loses the message
+ *
+ * A counting allocator keeps the heap in use, and its peak; the heap is
+ * read each time the producer has sent a tenth of the messages. Something
+ * like:
+ *
+ *     strategy    peak heap   delivered   dropped   producer    total
+ *     unbounded     14.9 MB       20000         0     0.11 s   0.27 s
+ *     block         66.5 KB       20000         0     0.32 s   0.32 s
+ *     drop          65.4 KB        3112     16888     0.09 s   0.09 s
+ *
+ *     heap as the producer goes, a tenth of the messages each
+ *     unbounded    ▁▂▃▃▄▅▆▆▇█
+ *     block        ▁▁▁▁▁▁▁▁▁▁
+ *     drop         ▁▁▁▁▁▁▁▁▁▁
+ *
+ * Unbounded, the producer is done in a third of the time, and what the
+ * consumer hasn't got to yet waits in the channel: 15 MB of the 20 MB
+ * sent, growing for as long as the producer runs. Give it more messages
+ * and it grows with them (75 MB for 100000). Blocking makes the
+ * producer wait for the consumer, and the heap stays at CAPACITY
+ * messages; the total is the consumer's either way, a little more for
+ * the waking up. Dropping keeps both the producer's speed and the small
+ * heap, by delivering about one message in six.
+ *
+ * rustc -O backpressure.rs -o backpressure
+ * ./backpressure                      the table
+ * ./backpressure --messages 100000    more, for a bigger unbounded peak
+ * ./backpressure --capacity 1000      a bigger channel
+ */
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, TrySendError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const MESSAGES: usize = 20_000;
+const CAPACITY: usize = 64;
+const SIZE: usize = 1024;              // bytes per message
+const PRODUCE: Duration = Duration::from_micros(2);  // the producer's work, per message
+const CONSUME: Duration = Duration::from_micros(10); // the consumer's: five times as long
+
+// THE COUNTING ALLOCATOR --------------------------------------------------
+
+struct Counting;
+
+static IN_USE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let now = IN_USE.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(now, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+// THE RUN -----------------------------------------------------------------
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Strategy {
+    Unbounded,
+    Block,
+    Drop,
+}
+
+const STRATEGIES: [Strategy; 3] = [Strategy::Unbounded, Strategy::Block, Strategy::Drop];
+
+impl Strategy {
+    fn name(self) -> &'static str {
+        match self {
+            Strategy::Unbounded => "unbounded",
+            Strategy::Block => "block",
+            Strategy::Drop => "drop",
+        }
+    }
+}
+
+struct Run {
+    peak: usize,                       // heap bytes above what was in use at the start
+    delivered: usize,
+    dropped: usize,
+    producer: Duration,
+    total: Duration,
+    samples: Vec<usize>,               // heap in use after each tenth sent
+}
+
+// either kind of sender, so the producer is one loop
+enum Sender {
+    Unbounded(mpsc::Sender<Vec<u8>>),
+    Bounded(mpsc::SyncSender<Vec<u8>>),
+}
+
+// work on a message: a busy wait, as sleeping for 10 µs sleeps longer
+fn work(message: &[u8], time: Duration) -> u64 {
+    let start = Instant::now();
+    while start.elapsed() < time {
+        std::hint::spin_loop();
+    }
+    message.iter().map(|&b| b as u64).sum()
+}
+
+fn run(strategy: Strategy, messages: usize, capacity: usize) -> Run {
+    let (tx, rx) = match strategy {
+        Strategy::Unbounded => {
+            let (tx, rx) = mpsc::channel();
+            (Sender::Unbounded(tx), rx)
+        }
+        Strategy::Block | Strategy::Drop => {
+            let (tx, rx) = mpsc::sync_channel(capacity);
+            (Sender::Bounded(tx), rx)
+        }
+    };
+    let base = IN_USE.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    let start = Instant::now();
+
+    let consumer = thread::spawn(move || {
+        let mut delivered = 0;
+        for message in rx {
+            std::hint::black_box(work(&message, CONSUME));
+            delivered += 1;
+        }
+        delivered
+    });
+
+    let tenth = (messages / 10).max(1);
+    let mut samples = Vec::new();
+    let mut dropped = 0;
+    for i in 0..messages {
+        let message = vec![i as u8; SIZE];
+        std::hint::black_box(work(&message, PRODUCE));
+        let sent = match &tx {
+            Sender::Unbounded(tx) => tx.send(message).is_ok(),
+            Sender::Bounded(tx) if strategy == Strategy::Block => tx.send(message).is_ok(),
+            Sender::Bounded(tx) => match tx.try_send(message) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    dropped += 1;                // the message is dropped here
+                    true
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            },
+        };
+        if !sent {
+            break;                               // the consumer is gone
+        }
+        if (i + 1) % tenth == 0 {
+            samples.push(IN_USE.load(Ordering::Relaxed).saturating_sub(base));
+        }
+    }
+    let producer = start.elapsed();
+    drop(tx);
+    let delivered = consumer.join().unwrap_or(0);
+
+    Run {
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(base),
+        delivered,
+        dropped,
+        producer,
+        total: start.elapsed(),
+        samples,
+    }
+}
+
+// THE TABLE ---------------------------------------------------------------
+
+fn size(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KB", b as f64 / (1 << 10) as f64),
+        b => format!("{b} B"),
+    }
+}
+
+// each sample as one of eight bars, against the highest of all the runs
+fn sparkline(samples: &[usize], highest: usize) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    samples.iter().map(|&s| BARS[(s * 7).checked_div(highest).unwrap_or(0).min(7)]).collect()
+}
+
+fn table(runs: &[(Strategy, Run)]) -> String {
+    let mut out = format!("{:<10} {:>10} {:>11} {:>9} {:>10} {:>8}\n", "strategy", "peak heap", "delivered", "dropped", "producer", "total");
+    for (strategy, r) in runs {
+        out += &format!(
+            "{:<10} {:>10} {:>11} {:>9} {:>8.2} s {:>6.2} s\n",
+            strategy.name(),
+            size(r.peak),
+            r.delivered,
+            r.dropped,
+            r.producer.as_secs_f64(),
+            r.total.as_secs_f64()
+        );
+    }
+    let highest = runs.iter().flat_map(|(_, r)| r.samples.iter().copied()).max().unwrap_or(0);
+    out += "\nheap as the producer goes, a tenth of the messages each\n";
+    for (strategy, r) in runs {
+        out += &format!("{:<12} {}\n", strategy.name(), sparkline(&r.samples, highest));
+    }
+    out
+}
+
+fn flag(args: &[String], name: &str, default: usize) -> Option<usize> {
+    match args.iter().position(|a| a == name) {
+        Some(i) => args.get(i + 1).and_then(|n| n.parse().ok()).filter(|&n| n > 0),
+        None => Some(default),
+    }
+}
+
+fn self_check() {
+    assert_eq!(size(512), "512 B");
+    assert_eq!(size(2048), "2.0 KB");
+    assert_eq!(size(3 << 20), "3.0 MB");
+    assert_eq!(sparkline(&[0, 50, 100], 100), "▁▄█");
+    assert_eq!(sparkline(&[0, 0], 0), "▁▁");
+
+    let args = vec!["--capacity".to_string(), "8".to_string()];
+    assert_eq!(flag(&args, "--capacity", 64), Some(8));
+    assert_eq!(flag(&args, "--messages", 5), Some(5));
+    assert_eq!(flag(&["--capacity".to_string()], "--capacity", 64), None);
+
+    // a small run of each: everything is delivered or counted as dropped
+    for strategy in STRATEGIES {
+        let r = run(strategy, 200, 4);
+        assert_eq!(r.delivered + r.dropped, 200);
+        assert_eq!(r.samples.len(), 10);
+        if strategy != Strategy::Drop {
+            assert_eq!(r.dropped, 0);
+        }
+    }
+}
+
+fn main() {
+    self_check();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (Some(messages), Some(capacity)) = (flag(&args, "--messages", MESSAGES), flag(&args, "--capacity", CAPACITY)) else {
+        eprintln!("backpressure: --messages and --capacity take a number above 0");
+        std::process::exit(2);
+    };
+
+    let runs: Vec<(Strategy, Run)> = STRATEGIES.iter().map(|&s| (s, run(s, messages, capacity))).collect();
+    print!("{}", table(&runs));
+}
@@ -0,0 +1,229 @@
+// DIAGRAMS: WHAT MONOMORPHIZATION COSTS, IN BYTES AND SECONDS ------------
+
+/*
+ * traits.rs puts "Binary Size: Larger (due to monomorphization)" in its
+ * table, and monomorphization.rs explains why. This measures how much:
+ * the same function, report(), written once generic over T: Shape and
+ * once over &[&dyn Shape], called with 1, 10, 50 and 200 shape types,
+ * each program built with rustc -O and timed, something like:
+ *
+ *              ------------ generic ------------   -------------- dyn --------------
+ *      types      binary   copies     build           binary   copies     build
+ *          1    342.7 KB        1    0.37 s        343.0 KB        1    0.30 s
+ *        200    674.9 KB      200   11.04 s        439.9 KB        1    1.04 s
+ *
+ * binary is the stripped executable, copies how many report functions
+ * are in the optimized LLVM IR (one per T for the generic one, always
+ * one for dyn), build the best of RUNS wall times of rustc. Then what
+ * each type more adds, on each side: the generic side pays for a copy
+ * of report per type, the dyn side only for the type's own methods and
+ * its vtable.
+ *
+ * The shape types all have the same two small methods, so what grows is
+ * report(); in a real program it's every generic function the type goes
+ * through, and the ones those call.
+ *
+ * rustc bloat.rs -o bloat
+ * ./bloat                             the table
+ * ./bloat --types 1,20,400            other counts of types
+ * ./bloat --debug                     built without -O
+ * ./bloat --probe 3 [--dyn]           print the program it compiles
+ */
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+const TYPES: [usize; 4] = [1, 10, 50, 200];
+const RUNS: usize = 3;                 // best of
+
+const HEAD: &str = r#"use std::hint::black_box;
+
+pub trait Shape {
+    fn area(&self) -> u64;
+    fn name(&self) -> &'static str;
+}
+"#;
+
+// the function that's monomorphized, or not: the same body both ways
+const REPORT_BODY: &str = r#"    let mut areas: Vec<u64> = shapes.iter().map(|s| s.area()).collect();
+    areas.sort_unstable();
+    let total: u64 = areas.iter().sum();
+    let mut out = String::new();
+    for s in shapes {
+        out += &format!("{}: {}\n", s.name(), s.area());
+    }
+    out += &format!("total {total}, median {}\n", areas.get(areas.len() / 2).copied().unwrap_or(0));
+    out
+"#;
+
+// the program with `types` shape types, report generic or dyn
+fn probe(types: usize, dynamic: bool) -> String {
+    let mut out = String::from(HEAD);
+    for i in 0..types {
+        let _ = write!(
+            out,
+            "\npub struct S{i}(u64);\n\nimpl Shape for S{i} {{\n    fn area(&self) -> u64 {{ self.0 * {} }}\n    fn name(&self) -> &'static str {{ \"S{i}\" }}\n}}\n",
+            i + 1
+        );
+    }
+    let takes = if dynamic { "(shapes: &[&dyn Shape])" } else { "<T: Shape>(shapes: &[T])" };
+    let _ = write!(out, "\n#[inline(never)]\npub fn report{takes} -> String {{\n{REPORT_BODY}}}\n\nfn main() {{\n    let mut out = String::new();\n");
+    for i in 0..types {
+        if dynamic {
+            let _ = writeln!(out, "    out += &report(black_box(&[&S{i}(1) as &dyn Shape, &S{i}(2)]));");
+        } else {
+            let _ = writeln!(out, "    out += &report(black_box(&[S{i}(1), S{i}(2)]));");
+        }
+    }
+    out += "    println!(\"{}\", out.len());\n}\n";
+    out
+}
+
+// report's definitions in LLVM IR: "define ... @_ZN5probe6report17h..E(" or,
+// with v0 mangling, "@_RNvCs.._5probe6report"
+fn copies(ir: &str) -> usize {
+    ir.lines().filter(|l| l.starts_with("define ") && l.contains("6report")).count()
+}
+
+struct Build {
+    binary: u64,
+    copies: usize,
+    time: Duration,
+}
+
+fn rustc(dir: &Path, source: &Path, opt: bool, emit: &str) -> Result<Duration, String> {
+    let mut command = Command::new("rustc");
+    command.args(["--edition", "2021", "-C", "strip=symbols", "--crate-name", "probe", "--emit", emit, "--out-dir"]);
+    command.arg(dir).arg(source);
+    if opt {
+        command.arg("-O");
+    }
+    let start = Instant::now();
+    let output = command.output().map_err(|e| format!("cannot run rustc: {e}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+    Ok(start.elapsed())
+}
+
+fn build(dir: &Path, types: usize, dynamic: bool, opt: bool) -> Result<Build, String> {
+    let source = dir.join("probe.rs");
+    fs::write(&source, probe(types, dynamic)).map_err(|e| e.to_string())?;
+    let mut time = Duration::MAX;
+    for _ in 0..RUNS {
+        time = time.min(rustc(dir, &source, opt, "link")?);
+    }
+    let binary = fs::metadata(dir.join(format!("probe{}", std::env::consts::EXE_SUFFIX))).map_err(|e| e.to_string())?.len();
+    rustc(dir, &source, opt, "llvm-ir")?;
+    let ir = fs::read_to_string(dir.join("probe.ll")).map_err(|e| e.to_string())?;
+    Ok(Build { binary, copies: copies(&ir), time })
+}
+
+// "1,10,50" -> [1, 10, 50]
+fn parse_types(list: &str) -> Option<Vec<usize>> {
+    let types: Vec<usize> = list.split(',').map(|n| n.trim().parse().ok().filter(|n| *n > 0)).collect::<Option<_>>()?;
+    (!types.is_empty()).then_some(types)
+}
+
+// RENDERING --------------------------------------------------------------
+
+fn kb(bytes: u64) -> String {
+    format!("{:.1} KB", bytes as f64 / 1024.0)
+}
+
+fn table(rows: &[(usize, Build, Build)], opt: bool) -> String {
+    let mut out = format!("{}, best of {RUNS} builds\n\n", if opt { "rustc -O" } else { "rustc, no -O" });
+    out += "          ------------ generic ------------   -------------- dyn --------------\n";
+    out += "  types      binary   copies     build           binary   copies     build\n";
+    for (types, generic, dynamic) in rows {
+        for (i, build) in [generic, dynamic].into_iter().enumerate() {
+            let lead = if i == 0 { format!("  {types:>5}") } else { "    ".to_string() };
+            let _ = write!(out, "{lead}  {:>10}  {:>7}  {:>6.2} s", kb(build.binary), build.copies, build.time.as_secs_f64());
+        }
+        out += "\n";
+    }
+    let (Some((first, g1, d1)), Some((last, g2, d2))) = (rows.first(), rows.last()) else {
+        return out;
+    };
+    if last > first {
+        let more = (last - first) as f64;
+        let per = |a: &Build, b: &Build| (b.binary as f64 - a.binary as f64) / more;
+        let secs = |a: &Build, b: &Build| (b.time.as_secs_f64() - a.time.as_secs_f64()) * 1000.0 / more;
+        let _ = write!(
+            out,
+            "\neach type more: generic {:+.0} bytes, {:+.1} ms to build; dyn {:+.0} bytes, {:+.1} ms\n",
+            per(g1, g2),
+            secs(g1, g2),
+            per(d1, d2),
+            secs(d1, d2)
+        );
+    }
+    out
+}
+
+// MAIN -------------------------------------------------------------------
+
+const SAMPLE_IR: &str = "define internal void @_ZN5probe6report17h0aE(ptr %0) {\n  ret void\n}\n\
+                         define internal void @_ZN5probe6report17h1bE(ptr %0) {\n\
+                         declare void @_ZN5probe6report17h2cE(ptr)\n\
+                         define i32 @main(i32 %0, ptr %1) {\n";
+
+fn self_check() {
+    assert_eq!(copies(SAMPLE_IR), 2);
+    assert_eq!(parse_types("1, 10,50"), Some(vec![1, 10, 50]));
+    assert_eq!(parse_types("1,0"), None);
+    assert_eq!(parse_types("x"), None);
+    assert_eq!(kb(2048), "2.0 KB");
+
+    let generic = probe(3, false);
+    assert_eq!(generic.matches("impl Shape for").count(), 3);
+    assert!(generic.contains("pub fn report<T: Shape>(shapes: &[T])") && generic.contains("report(black_box(&[S2(1), S2(2)]))"));
+    let dynamic = probe(3, true);
+    assert!(dynamic.contains("pub fn report(shapes: &[&dyn Shape])") && dynamic.contains("&S2(1) as &dyn Shape"));
+}
+
+fn main() {
+    self_check();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let dynamic = args.iter().any(|a| a == "--dyn");
+    if let Some(i) = args.iter().position(|a| a == "--probe") {
+        let types = args.get(i + 1).and_then(|n| n.parse().ok()).unwrap_or(2);
+        print!("{}", probe(types, dynamic));
+        return;
+    }
+    let types = match args.iter().position(|a| a == "--types") {
+        Some(i) => match args.get(i + 1).and_then(|list| parse_types(list)) {
+            Some(types) => types,
+            None => {
+                eprintln!("bloat: --types takes counts like 1,10,50");
+                std::process::exit(2);
+            }
+        },
+        None => TYPES.to_vec(),
+    };
+    let opt = !args.iter().any(|a| a == "--debug");
+
+    let dir = std::env::temp_dir().join(format!("bloat-{}", std::process::id()));
+    let result = (|| -> Result<String, String> {
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let mut rows = Vec::new();
+        for n in types {
+            eprintln!("building with {n} types..");
+            rows.push((n, build(&dir, n, false, opt)?, build(&dir, n, true, opt)?));
+        }
+        Ok(table(&rows, opt))
+    })();
+    fs::remove_dir_all(&dir).ok();
+
+    match result {
+        Ok(out) => print!("{out}"),
+        Err(e) => {
+            eprintln!("bloat: {e}");
+            std::process::exit(1);
+        }
+    }
+}
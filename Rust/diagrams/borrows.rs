@@ -0,0 +1,339 @@
+// DIAGRAMS: HOW LONG EACH BORROW LIVES -----------------------------------
+
+/*
+ * Since non-lexical lifetimes, a reference lives from where it's made to
+ * the last place it's used, not to the end of the block. This draws that
+ * as bars in a gutter next to the code, one column per borrow:
+ *
+ *      r1 r2 r3
+ *      ┬           let r1 = &s;
+ *      │  ┬        let r2 = &s;
+ *      ┴  ┴        println!("{r1} and {r2}");
+ *            ┳     let r3 = &mut s;
+ *            ┻     println!("{r3}");
+ *
+ * Light bars are shared borrows, heavy ones mutable; ● / ◉ is a borrow
+ * that lives for one line. A borrow that starts while a conflicting one
+ * is alive is marked ✗, and listed underneath with the error rustc will
+ * give:
+ *
+ *      first &mut v
+ *      ┬               let first = &v[0];
+ *      │     ◉       ✗ v.push(6);
+ *      ┴               println!("{first}");
+ *
+ *      ✗ line 2, E0502: &mut v while first (&v) is alive
+ *
+ * What counts as a borrow, read from the text and not from the type
+ * checker, so it's a picture of the rule and not a replacement for it:
+ *
+ *     let r = &x / &mut x / &x[..] / &x.field    a named borrow of x, alive
+ *                                                to the last line naming r
+ *     x.push(..), x.clear(), ..                  a one-line &mut x, for the
+ *                                                methods in MUTATING
+ *     f(&mut x), any other mention of x          a one-line borrow, while x
+ *                                                has a named borrow somewhere
+ *
+ * With --rustc the snippet is also compiled (as the body of a main) and
+ * rustc's own errors are listed underneath, to compare.
+ *
+ * With --plain it's said in words instead, the verdict first and then
+ * each borrow and the lines it lives for, for a screen reader (the
+ * "In words:" next to the drawings in the notes):
+ *
+ *      No borrow overlaps a conflicting one.
+ *      r1: a shared borrow of s, lines 2 to 4.
+ *      ..
+ *
+ * rustc borrows.rs -o borrows
+ * ./borrows snippet.rs [--rustc] [--plain]
+ * (no file: the snippet is read from stdin)
+ */
+
+use std::fs;
+use std::io::{self, Read};
+use std::process::Command;
+
+// methods that take &mut self
+const MUTATING: [&str; 12] =
+    ["push", "push_str", "pop", "clear", "insert", "remove", "truncate", "extend", "sort", "retain", "drain", "append"];
+
+#[derive(Debug, Clone, PartialEq)]
+struct Borrow {
+    label: String,                     // "r1", or "&mut v" for a one-line borrow
+    of: String,
+    mutable: bool,
+    start: usize,                      // line index
+    end: usize,                        // the last line it's used on
+}
+
+#[derive(Debug, PartialEq)]
+struct Conflict {
+    line: usize,
+    code: &'static str,
+    message: String,
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// the code of a line, without its comment
+fn code(line: &str) -> &str {
+    line.split("//").next().unwrap_or_default()
+}
+
+// `word` appears in `text` as a whole identifier
+fn mentions(text: &str, word: &str) -> bool {
+    text.match_indices(word).any(|(i, _)| {
+        let before = text[..i].chars().next_back().is_none_or(|c| !is_ident(c));
+        let after = text[i + word.len()..].chars().next().is_none_or(|c| !is_ident(c));
+        before && after
+    })
+}
+
+// "&mut v[0]" -> (v, true)
+fn borrowed(expr: &str) -> Option<(String, bool)> {
+    let expr = expr.trim().strip_prefix('&')?;
+    let (mutable, rest) = match expr.strip_prefix("mut ") {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, expr),
+    };
+    let place: String = rest.chars().take_while(|&c| is_ident(c)).collect();
+    (!place.is_empty()).then_some((place, mutable))
+}
+
+fn borrows(lines: &[&str]) -> Vec<Borrow> {
+    let mut named = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let Some(rest) = code(line).trim().strip_prefix("let ") else { continue };
+        let Some((name, expr)) = rest.split_once('=') else { continue };
+        let name = name.trim().trim_start_matches("mut ").split(':').next().unwrap_or_default().trim();
+        let Some((of, mutable)) = borrowed(expr) else { continue };
+        let end = (i + 1..lines.len()).rfind(|&j| mentions(code(lines[j]), name)).unwrap_or(i);
+        named.push(Borrow { label: name.to_string(), of, mutable, start: i, end });
+    }
+
+    // one-line borrows of the places that have named ones
+    let mut places: Vec<String> = named.iter().map(|b| b.of.clone()).collect();
+    places.dedup();
+    let mut all = named.clone();
+    for (i, line) in lines.iter().enumerate() {
+        let code = code(line);
+        for place in &places {
+            let making = named.iter().any(|b| b.start == i && &b.of == place);
+            let declaring = code.trim().strip_prefix("let ").is_some_and(|d| {
+                mentions(d.split('=').next().unwrap_or_default(), place)
+            });
+            if making || declaring || !mentions(code, place) {
+                continue;
+            }
+            let mutable = MUTATING.iter().any(|m| code.contains(&format!("{place}.{m}(")))
+                || code.contains(&format!("&mut {place}"));
+            let label = if mutable { format!("&mut {place}") } else { format!("&{place}") };
+            all.push(Borrow { label, of: place.clone(), mutable, start: i, end: i });
+        }
+    }
+    all.sort_by_key(|b| b.start);
+    all
+}
+
+// a borrow that starts while another of the same place is alive and
+// used later, with at least one of them mutable
+fn conflicts(borrows: &[Borrow]) -> Vec<Conflict> {
+    let mut found = Vec::new();
+    for b in borrows {
+        let alive = borrows.iter().find(|a| {
+            a.of == b.of && a.start < b.start && b.start < a.end && (a.mutable || b.mutable)
+        });
+        if let Some(a) = alive {
+            let code = if a.mutable && b.mutable { "E0499" } else { "E0502" };
+            let kind = |b: &Borrow| if b.mutable { format!("&mut {}", b.of) } else { format!("&{}", b.of) };
+            let message = if b.label.starts_with('&') {
+                format!("{} while {} ({}) is alive", kind(b), a.label, kind(a))
+            } else {
+                format!("{} ({}) while {} ({}) is alive", b.label, kind(b), a.label, kind(a))
+            };
+            found.push(Conflict { line: b.start, code, message });
+        }
+    }
+    found
+}
+
+// [one line, start, alive, end], light for shared and heavy for mutable
+const GLYPHS: [[&str; 4]; 2] = [["●", "┬", "│", "┴"], ["◉", "┳", "┃", "┻"]];
+
+fn glyph(b: &Borrow, line: usize) -> &'static str {
+    let set = GLYPHS[b.mutable as usize];
+    if line < b.start || line > b.end {
+        " "
+    } else if b.start == b.end {
+        set[0]
+    } else if line == b.start {
+        set[1]
+    } else if line == b.end {
+        set[3]
+    } else {
+        set[2]
+    }
+}
+
+fn pad(s: &str, w: usize) -> String {
+    format!("{s}{}", " ".repeat(w.saturating_sub(s.chars().count())))
+}
+
+fn render(lines: &[&str]) -> String {
+    let borrows = borrows(lines);
+    let conflicts = conflicts(&borrows);
+    let widths: Vec<usize> = borrows.iter().map(|b| b.label.chars().count() + 1).collect();
+
+    let header: String = borrows.iter().zip(&widths).map(|(b, w)| pad(&b.label, *w)).collect();
+    let mut out = format!(" {}\n", header.trim_end());
+    for (i, line) in lines.iter().enumerate() {
+        let gutter: String = borrows.iter().zip(&widths).map(|(b, w)| pad(glyph(b, i), *w)).collect();
+        let mark = if conflicts.iter().any(|c| c.line == i) { "✗ " } else { "  " };
+        let text = format!(" {gutter} {mark}{line}");
+        out += text.trim_end();
+        out += "\n";
+    }
+    if conflicts.is_empty() && !borrows.is_empty() {
+        out += "\n no borrow overlaps a conflicting one\n";
+    } else {
+        out += "\n";
+    }
+    for c in &conflicts {
+        out += &format!(" ✗ line {}, {}: {}\n", c.line + 1, c.code, c.message);
+    }
+    out
+}
+
+fn render_words(lines: &[&str]) -> String {
+    let borrows = borrows(lines);
+    let conflicts = conflicts(&borrows);
+    let mut out = String::new();
+    if borrows.is_empty() {
+        out += "No borrows.\n";
+    } else if conflicts.is_empty() {
+        out += "No borrow overlaps a conflicting one.\n";
+    }
+    for c in &conflicts {
+        out += &format!("Line {}, {}: {}.\n", c.line + 1, c.code, c.message);
+    }
+    for b in &borrows {
+        let kind = if b.mutable { "mutable" } else { "shared" };
+        let lines = if b.start == b.end { format!("line {} only", b.start + 1) } else { format!("lines {} to {}", b.start + 1, b.end + 1) };
+        out += &format!("{}: a {kind} borrow of {}, {lines}.\n", b.label, b.of);
+    }
+    out
+}
+
+// rustc's errors for the snippet as the body of a main: (line, code, message)
+fn rustc_errors(lines: &[&str]) -> io::Result<Vec<(usize, String, String)>> {
+    let dir = std::env::temp_dir().join(format!("borrows-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let file = dir.join("snippet.rs");
+    fs::write(&file, format!("fn main() {{\n{}\n}}\n", lines.join("\n")))?;
+    let output = Command::new("rustc")
+        .args(["--edition", "2021", "--error-format=short", "-A", "warnings", "--emit=metadata", "--out-dir"])
+        .arg(&dir)
+        .arg(&file)
+        .output()?;
+    fs::remove_dir_all(&dir)?;
+
+    // "snippet.rs:5:1: error[E0502]: cannot borrow ..."
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Ok(stderr
+        .lines()
+        .filter_map(|l| {
+            let (_, rest) = l.split_once(".rs:")?;
+            let (line, rest) = rest.split_once(':')?;
+            let (_, rest) = rest.split_once(": error")?;
+            let code = rest.strip_prefix('[').and_then(|r| r.split_once(']')).map_or("", |(c, _)| c);
+            let message = rest.split_once(": ").map_or("", |(_, m)| m);
+            // the first line of the file is `fn main() {`
+            Some((line.parse::<usize>().ok()?.saturating_sub(2), code.to_string(), message.to_string()))
+        })
+        .collect())
+}
+
+// MAIN -------------------------------------------------------------------
+
+const NLL: &str = r#"let mut s = String::from("hello");
+let r1 = &s;
+let r2 = &s;
+println!("{r1} and {r2}");
+let r3 = &mut s;
+println!("{r3}");"#;
+
+const PUSH: &str = r#"let mut v = vec![1, 2, 3];
+let first = &v[0];
+v.push(6);
+println!("{first}");"#;
+
+fn self_check() {
+    let lines: Vec<&str> = NLL.lines().collect();
+    let found = borrows(&lines);
+    let spans: Vec<(&str, usize, usize, bool)> =
+        found.iter().map(|b| (b.label.as_str(), b.start, b.end, b.mutable)).collect();
+    assert_eq!(spans, [("r1", 1, 3, false), ("r2", 2, 3, false), ("r3", 4, 5, true)]);
+    assert!(conflicts(&found).is_empty());
+
+    let lines: Vec<&str> = PUSH.lines().collect();
+    let found = conflicts(&borrows(&lines));
+    assert_eq!(found.len(), 1);
+    assert_eq!((found[0].line, found[0].code), (2, "E0502"));
+    assert_eq!(found[0].message, "&mut v while first (&v) is alive");
+
+    // a second &mut while the first is still used: E0499
+    let lines = ["let a = &mut s;", "let b = &mut s;", "a.push('x');"];
+    assert_eq!(conflicts(&borrows(&lines))[0].code, "E0499");
+
+    assert!(mentions("println!(\"{r1}\")", "r1") && !mentions("r10 + r1x", "r1"));
+    assert_eq!(borrowed(" &mut v[0]"), Some(("v".to_string(), true)));
+
+    let text = render(&NLL.lines().collect::<Vec<_>>());
+    assert!(text.starts_with(" r1 r2 r3\n"), "{text}");
+    // the code lines up whatever the gutter holds
+    let columns = text.lines().skip(1).filter_map(|l| {
+        l.find("let ").or(l.find("println")).map(|i| l[..i].chars().count())
+    });
+    let columns: Vec<usize> = columns.collect();
+    assert!(columns.len() == 6 && columns.windows(2).all(|w| w[0] == w[1]), "{text}");
+
+    let words = render_words(&PUSH.lines().collect::<Vec<_>>());
+    assert!(words.starts_with("Line 3, E0502: &mut v while first (&v) is alive.\n"), "{words}");
+    assert!(words.contains("first: a shared borrow of v, lines 2 to 4.\n&mut v: a mutable borrow of v, line 3 only."), "{words}");
+}
+
+fn main() {
+    self_check();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let snippet = match args.iter().find(|a| !a.starts_with("--")) {
+        Some(path) => fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("borrows: {path}: {e}");
+            std::process::exit(1);
+        }),
+        None => {
+            let mut snippet = String::new();
+            io::stdin().read_to_string(&mut snippet).expect("stdin");
+            snippet
+        }
+    };
+    let lines: Vec<&str> = snippet.trim_matches('\n').lines().collect();
+    let plain = args.iter().any(|a| a == "--plain");
+    print!("{}", if plain { render_words(&lines) } else { render(&lines) });
+
+    if args.iter().any(|a| a == "--rustc") {
+        match rustc_errors(&lines) {
+            Ok(errors) if errors.is_empty() => println!("\n rustc: compiles"),
+            Ok(errors) => {
+                println!();
+                for (line, code, message) in errors {
+                    println!(" rustc, line {}: error[{code}]: {message}", line + 1);
+                }
+            }
+            Err(e) => eprintln!("borrows: cannot run rustc: {e}"),
+        }
+    }
+}
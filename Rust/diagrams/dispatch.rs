@@ -0,0 +1,256 @@
+// DIAGRAMS: STATIC VS DYNAMIC DISPATCH, IN ASSEMBLY ----------------------
+
+/*
+ * traits.rs says dyn dispatch is "slightly slower". This shows why, and
+ * by how much: the same sum of areas, once over &[Square] through a
+ * generic (monomorphized, so Square::area is known at compile time) and
+ * once over &[&dyn Shape] (looked up in the vtable on every element),
+ * compiled with -O, the assembly of each side by side:
+ *
+ *     static_call: &[Square]            │ dyn_call: &[&dyn Shape]
+ *     .LBB9_5:                          │ .LBB8_4:
+ *         movsd xmm1, [rdi + 8*rcx]     │     mov  rdi, [r14 + r15]      data pointer
+ *         mulsd xmm1, xmm1              │     mov  rax, [r14 + r15 + 8]  vtable pointer
+ *         addsd xmm1, xmm0              │     call [rax + 24]            area, 4th slot
+ *         ...four per round             │     ...
+ *
+ * then a microbenchmark of both, in nanoseconds per element.
+ *
+ * The call through [rax + 24] is cheap on its own; what dyn costs is
+ * what the compiler can't do across it: inline area(), unroll, keep the
+ * sum in a register. The vtable is drop, size, align, then the methods,
+ * so area is at 3 * 8 = 24.
+ *
+ * The assembly comes from rustc --emit asm, or with --objdump from
+ * disassembling the built binary (objdump -d), which is what ran. For
+ * a whole crate, cargo asm (cargo install cargo-show-asm) does the same
+ * by function name. The functions are #[no_mangle] so they can be found
+ * by name, and #[inline(never)] so main doesn't swallow them.
+ *
+ * rustc dispatch.rs -o dispatch
+ * ./dispatch                          asm side by side, then the timings
+ * ./dispatch --objdump                the disassembled binary instead
+ * ./dispatch --width 160 --no-bench
+ * ./dispatch --probe                  print the program it compiles
+ */
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const PROBE: &str = r#"use std::hint::black_box;
+use std::time::Instant;
+
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+pub struct Square(f64);
+pub struct Circle(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 { self.0 * self.0 }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 { 3.14159 * self.0 * self.0 }
+}
+
+pub fn total<T: Shape>(shapes: &[T]) -> f64 {
+    shapes.iter().map(|s| s.area()).sum()
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn static_call(shapes: &[Square]) -> f64 {
+    total(shapes)
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn dyn_call(shapes: &[&dyn Shape]) -> f64 {
+    shapes.iter().map(|s| s.area()).sum()
+}
+
+fn time(name: &str, elements: usize, mut f: impl FnMut() -> f64) {
+    let rounds = 2000;
+    let start = Instant::now();
+    for _ in 0..rounds {
+        black_box(f());
+    }
+    let ns = start.elapsed().as_nanos() as f64 / (rounds * elements) as f64;
+    println!("{name} {ns:.2}");
+}
+
+fn main() {
+    let squares: Vec<Square> = (0..1000).map(|i| Square(i as f64)).collect();
+    let same: Vec<&dyn Shape> = squares.iter().map(|s| s as &dyn Shape).collect();
+    let circles: Vec<Circle> = (0..500).map(|i| Circle(i as f64)).collect();
+    let mixed: Vec<&dyn Shape> =
+        squares[..500].iter().zip(&circles).flat_map(|(s, c)| [s as &dyn Shape, c as &dyn Shape]).collect();
+    time("static", squares.len(), || static_call(black_box(&squares)));
+    time("dyn", same.len(), || dyn_call(black_box(&same)));
+    time("dyn-mixed", mixed.len(), || dyn_call(black_box(&mixed)));
+}
+"#;
+
+const FUNCTIONS: [(&str, &str); 2] = [("static_call", "&[Square]"), ("dyn_call", "&[&dyn Shape]")];
+
+// builds the probe in `dir`: the binary and, next to it, probe.s
+fn build(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    fs::write(dir.join("probe.rs"), PROBE).map_err(|e| e.to_string())?;
+    let mut command = Command::new("rustc");
+    command.args(["-O", "--edition", "2021", "--emit", "asm,link", "--crate-name", "probe", "--out-dir"]);
+    command.arg(dir).arg(dir.join("probe.rs"));
+    if cfg!(target_arch = "x86_64") {
+        command.args(["-C", "llvm-args=-x86-asm-syntax=intel"]);
+    }
+    let output = command.output().map_err(|e| format!("cannot run rustc: {e}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+    Ok(())
+}
+
+// `name`'s instructions and labels from rustc's .s, without directives
+fn from_asm(asm: &str, name: &str) -> Vec<String> {
+    asm.lines()
+        .skip_while(|l| *l != format!("{name}:"))
+        .skip(1)
+        .take_while(|l| !l.trim_start().starts_with(".Lfunc_end") && !l.contains(".cfi_endproc"))
+        .filter(|l| !l.trim_start().starts_with('.') || l.ends_with(':'))
+        .map(|l| match l.strip_prefix('\t') {
+            Some(instruction) => format!("    {}", instruction.replacen('\t', " ", 1).replace('\t', " ")),
+            None => l.to_string(),
+        })
+        .collect()
+}
+
+// `name` disassembled from the binary, with the addresses it ran at
+fn from_objdump(binary: &Path, name: &str) -> Result<Vec<String>, String> {
+    let mut command = Command::new("objdump");
+    command.args(["-d", "--no-show-raw-insn", "-C", &format!("--disassemble={name}")]);
+    if cfg!(target_arch = "x86_64") {
+        command.args(["-M", "intel"]);
+    }
+    let output = command.arg(binary).output().map_err(|e| format!("cannot run objdump: {e}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+    // "   16366:\tmov    rdi,QWORD PTR [r14+r15*1]"
+    let text = String::from_utf8_lossy(&output.stdout);
+    Ok(text
+        .lines()
+        .filter_map(|l| l.split_once(":\t"))
+        .filter(|(address, _)| address.trim().chars().all(|c| c.is_ascii_hexdigit()))
+        .map(|(address, instruction)| format!("{:>6}  {}", address.trim(), instruction.split("   #").next().unwrap_or_default().trim_end()))
+        .collect())
+}
+
+// "static 0.78" lines from the probe -> (name, ns per element)
+fn timings(output: &str) -> Vec<(String, f64)> {
+    output
+        .lines()
+        .filter_map(|l| {
+            let (name, ns) = l.split_once(' ')?;
+            Some((name.to_string(), ns.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+// RENDERING --------------------------------------------------------------
+
+fn pad(s: &str, w: usize) -> String {
+    format!("{s}{}", " ".repeat(w.saturating_sub(s.chars().count())))
+}
+
+fn clip(s: &str, w: usize) -> String {
+    if s.chars().count() <= w { s.to_string() } else { s.chars().take(w.saturating_sub(1)).chain(['…']).collect() }
+}
+
+fn side_by_side(left: &[String], right: &[String], width: usize) -> String {
+    let w = (width.saturating_sub(3) / 2).max(10);
+    let mut out = String::new();
+    for i in 0..left.len().max(right.len()) {
+        let a = left.get(i).map_or("", String::as_str);
+        let b = right.get(i).map_or("", String::as_str);
+        out += format!("{} │ {}", pad(&clip(a, w), w), clip(b, w)).trim_end();
+        out += "\n";
+    }
+    out
+}
+
+fn bench_table(timings: &[(String, f64)]) -> String {
+    let fastest = timings.iter().map(|(_, ns)| *ns).fold(f64::INFINITY, f64::min).max(f64::MIN_POSITIVE);
+    let labels = [
+        ("static", "static_call, &[Square]"),
+        ("dyn", "dyn_call, &[&dyn Shape], all Squares"),
+        ("dyn-mixed", "dyn_call, Squares and Circles taking turns"),
+    ];
+    let mut out = String::from("ns per element, the average over 2000 rounds of 1000 elements\n\n");
+    for (name, ns) in timings {
+        let label = labels.iter().find(|(n, _)| n == name).map_or(name.as_str(), |(_, l)| l);
+        out += &format!("  {} {ns:>6.2} ns  {:>5.1}x\n", pad(label, 44), ns / fastest);
+    }
+    out
+}
+
+// MAIN -------------------------------------------------------------------
+
+const SAMPLE_ASM: &str = "other:\n\tret\n\t.p2align\t4\nstatic_call:\n\t.cfi_startproc\n\ttest\trsi, rsi\n.LBB9_5:\n\
+                          \tmulsd\txmm1, xmm1\n\tret\n.Lfunc_end9:\n\t.size\tstatic_call, .Lfunc_end9-static_call\n";
+
+fn self_check() {
+    assert_eq!(from_asm(SAMPLE_ASM, "static_call"), ["    test rsi, rsi", ".LBB9_5:", "    mulsd xmm1, xmm1", "    ret"]);
+    assert!(from_asm(SAMPLE_ASM, "dyn_call").is_empty());
+    assert_eq!(timings("static 0.78\ndyn 6.36\nnoise\n"), [("static".to_string(), 0.78), ("dyn".to_string(), 6.36)]);
+    assert_eq!(clip("abcdef", 4), "abc…");
+
+    let text = side_by_side(&["a".to_string()], &["x".to_string(), "y".to_string()], 23);
+    assert_eq!(text, "a          │ x\n           │ y\n");
+    assert!(PROBE.contains("#[no_mangle]") && FUNCTIONS.iter().all(|(f, _)| PROBE.contains(&format!("fn {f}("))));
+}
+
+fn main() {
+    self_check();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--probe") {
+        print!("{PROBE}");
+        return;
+    }
+    let width = args.iter().position(|a| a == "--width").and_then(|i| args.get(i + 1)?.parse().ok()).unwrap_or(120);
+
+    let dir = std::env::temp_dir().join(format!("dispatch-{}", std::process::id()));
+    let result = (|| -> Result<String, String> {
+        build(&dir)?;
+        let asm = fs::read_to_string(dir.join("probe.s")).map_err(|e| e.to_string())?;
+        let mut columns = Vec::new();
+        for (name, takes) in FUNCTIONS {
+            let mut column = vec![format!("{name}: {takes}"), String::new()];
+            if args.iter().any(|a| a == "--objdump") {
+                column.extend(from_objdump(&dir.join("probe"), name)?);
+            } else {
+                column.extend(from_asm(&asm, name));
+            }
+            columns.push(column);
+        }
+        let mut out = side_by_side(&columns[0], &columns[1], width);
+        if !args.iter().any(|a| a == "--no-bench") {
+            let output = Command::new(dir.join("probe")).output().map_err(|e| format!("cannot run the probe: {e}"))?;
+            out += "\n";
+            out += &bench_table(&timings(&String::from_utf8_lossy(&output.stdout)));
+        }
+        Ok(out)
+    })();
+    fs::remove_dir_all(&dir).ok();
+
+    match result {
+        Ok(out) => print!("{out}"),
+        Err(e) => {
+            eprintln!("dispatch: {e}");
+            std::process::exit(1);
+        }
+    }
+}
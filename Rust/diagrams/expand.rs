@@ -0,0 +1,189 @@
+// DIAGRAMS: WHAT A MACRO EXPANDS TO --------------------------------------
+
+/*
+ * Shows a snippet next to the code the compiler actually sees once
+ * macro_rules! macros, derives and println! have been expanded, which
+ * is the quickest way to stop a macro being magic:
+ *
+ *     #[derive(Clone)]                 │ impl ::core::clone::Clone for Point {
+ *     struct Point { x: i32, y: i32 }  │     #[inline]
+ *                                      │     fn clone(&self) -> Point {
+ *     ...                              │         Point {
+ *                                      │             x: ::core::clone::Clone::clone(&self.x),
+ *
+ * The expansion comes from, in order of preference:
+ *
+ *     cargo expand                     if cargo-expand is installed
+ *     rustc +nightly -Zunpretty=expanded
+ *                                      if rustup has a nightly toolchain
+ *     RUSTC_BOOTSTRAP=1 rustc -Zunpretty=expanded
+ *                                      stable rustc, told to allow the
+ *                                      unstable flag; fine for reading,
+ *                                      not something to build with
+ *
+ * They all print the same thing, since cargo expand runs the second.
+ * The lines rustc adds to every crate (the prelude import, extern crate
+ * std) are left out. A snippet without a main is expanded as the body
+ * of one, where macro_rules! and derives work as they do at the top.
+ *
+ * rustc expand.rs -o expand
+ * ./expand snippet.rs                 side by side, 120 columns
+ * ./expand snippet.rs --width 160
+ * ./expand snippet.rs --plain         just the expansion
+ * (no file: the snippet is read from stdin)
+ */
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::Command;
+
+const EDITION: &str = "2021";
+
+fn program(snippet: &str) -> String {
+    if snippet.contains("fn main(") { snippet.to_string() } else { format!("fn main() {{\n{snippet}\n}}\n") }
+}
+
+fn succeeds(command: &mut Command) -> bool {
+    command.output().is_ok_and(|o| o.status.success())
+}
+
+fn stdout_of(mut command: Command) -> Result<String, String> {
+    let output = command.output().map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+// the expansion, and what produced it
+fn expand(source: &str, dir: &Path) -> Result<(String, &'static str), String> {
+    if succeeds(Command::new("cargo").args(["expand", "--version"])) {
+        fs::create_dir_all(dir.join("src")).map_err(|e| e.to_string())?;
+        let manifest = format!("[package]\nname = \"snippet\"\nversion = \"0.1.0\"\nedition = \"{EDITION}\"\n");
+        fs::write(dir.join("Cargo.toml"), manifest).map_err(|e| e.to_string())?;
+        fs::write(dir.join("src/main.rs"), source).map_err(|e| e.to_string())?;
+        let mut command = Command::new("cargo");
+        command.args(["expand", "--quiet", "--color", "never"]).current_dir(dir);
+        return stdout_of(command).map(|text| (text, "cargo expand"));
+    }
+
+    let file = dir.join("snippet.rs");
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    fs::write(&file, source).map_err(|e| e.to_string())?;
+    let unpretty = |command: &mut Command| {
+        command.args(["--edition", EDITION, "-Zunpretty=expanded"]).arg(&file);
+    };
+    if succeeds(Command::new("rustc").args(["+nightly", "--version"])) {
+        let mut command = Command::new("rustc");
+        command.arg("+nightly");
+        unpretty(&mut command);
+        return stdout_of(command).map(|text| (text, "rustc +nightly -Zunpretty=expanded"));
+    }
+    let mut command = Command::new("rustc");
+    command.env("RUSTC_BOOTSTRAP", "1");
+    unpretty(&mut command);
+    stdout_of(command).map(|text| (text, "RUSTC_BOOTSTRAP=1 rustc -Zunpretty=expanded"))
+}
+
+// without the lines every crate gets: #![feature(prelude_import)],
+// #![no_std], extern crate std, and the prelude's use
+fn trimmed(expanded: &str) -> String {
+    let boilerplate = |line: &str| {
+        line.starts_with("#![feature(prelude_import)]")
+            || line.starts_with("#![no_std]")
+            || line.starts_with("#[prelude_import]")
+            || line.starts_with("use ::std::prelude::")
+            || line.starts_with("use std::prelude::")
+            || line.starts_with("#[macro_use]")
+            || line.starts_with("extern crate std;")
+    };
+    let lines: Vec<&str> = expanded.lines().skip_while(|l| boilerplate(l) || l.trim().is_empty()).collect();
+    lines.join("\n").trim().to_string() + "\n"
+}
+
+// RENDERING --------------------------------------------------------------
+
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+    chars.chunks(width.max(1)).map(|c| c.iter().collect()).collect()
+}
+
+fn pad(s: &str, w: usize) -> String {
+    format!("{s}{}", " ".repeat(w.saturating_sub(s.chars().count())))
+}
+
+fn side_by_side(left: &str, right: &str, width: usize) -> String {
+    // the snippet gets what it needs, up to half; the expansion the rest
+    let longest = left.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+    let lw = longest.min(width / 2 - 2).max(10);
+    let rw = width.saturating_sub(lw + 3).max(10);
+    let wrapped = |text: &str, w| -> Vec<String> { text.lines().flat_map(|l| wrap(l, w)).collect() };
+    let (l, r) = (wrapped(left, lw), wrapped(right, rw));
+
+    let mut out = String::new();
+    for i in 0..l.len().max(r.len()) {
+        let a = l.get(i).map_or("", String::as_str);
+        let b = r.get(i).map_or("", String::as_str);
+        out += format!("{} │ {b}", pad(a, lw)).trim_end();
+        out += "\n";
+    }
+    out
+}
+
+// MAIN -------------------------------------------------------------------
+
+const SAMPLE: &str = "#![feature(prelude_import)]\n#![no_std]\nextern crate std;\n#[prelude_import]\n\
+                      use ::std::prelude::rust_2021::*;\nstruct Point;\n";
+
+fn self_check() {
+    assert_eq!(trimmed(SAMPLE), "struct Point;\n");
+    assert!(program("let x = 1;").starts_with("fn main() {\n"));
+    assert_eq!(program("fn main() {}"), "fn main() {}");
+    assert_eq!(wrap("abcdef", 4), ["abcd", "ef"]);
+
+    let text = side_by_side("a\nbb", "x\ny\nz", 40);
+    let bars: Vec<usize> = text.lines().map(|l| l.chars().position(|c| c == '│').unwrap()).collect();
+    assert_eq!(bars, [11, 11, 11], "{text}");
+}
+
+fn main() {
+    self_check();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let width = args.iter().position(|a| a == "--width").and_then(|i| args.get(i + 1)?.parse().ok()).unwrap_or(120);
+    let snippet = match args.iter().find(|a| a.ends_with(".rs")) {
+        Some(path) => fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("expand: {path}: {e}");
+            std::process::exit(1);
+        }),
+        None => {
+            let mut snippet = String::new();
+            io::stdin().read_to_string(&mut snippet).expect("stdin");
+            snippet
+        }
+    };
+
+    let dir = std::env::temp_dir().join(format!("expand-{}", std::process::id()));
+    let result = expand(&program(&snippet), &dir);
+    fs::remove_dir_all(&dir).ok();
+    let (expanded, by) = match result {
+        Ok(expanded) => expanded,
+        Err(e) => {
+            eprintln!("expand: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    if args.iter().any(|a| a == "--plain") {
+        print!("{}", trimmed(&expanded));
+    } else {
+        let left = format!("snippet\n\n{}", snippet.trim_end());
+        let right = format!("expanded by {by}\n\n{}", trimmed(&expanded));
+        print!("{}", side_by_side(&left, &right, width));
+    }
+}
@@ -0,0 +1,566 @@
+// DIAGRAMS: STACK AND HEAP -----------------------------------------------
+
+/*
+ * Draws the stack-and-heap boxes that go with the ownership notes, from
+ * a few lines of Rust:
+ *
+ *     let s1 = String::from("hi");
+ *     let s2 = s1;
+ *     let s3 = s2.clone();
+ *
+ *      stack                   heap
+ *     ┌────┬────────────┐
+ *     │ s1 │ moved → s2 │
+ *     ├────┼────────────┤     ┌───┬───┐
+ *     │ s2 │ ptr        │ ──▶ │ h │ i │
+ *     │    │ len 2      │     └───┴───┘
+ *     │    │ cap 2      │
+ *     ├────┼────────────┤     ┌───┬───┐
+ *     │ s3 │ ptr        │ ──▶ │ h │ i │
+ *     ...
+ *
+ * What it understands, one statement per line (comments and ; are
+ * ignored):
+ *
+ *     let [mut] x = String::from("..") | "..".to_string() | String::new()
+ *     let [mut] x = vec![1, 2] | Vec::new() | Box::new(5)
+ *     let [mut] x = y              move, or copy if y is a number or a &
+ *     let [mut] x = y.clone()      a new heap block with y's contents
+ *     let [mut] x = &y | &mut y
+ *     let [mut] x = 5              anything else that's one token
+ *     x = ...                      the old value is dropped first
+ *     x.push_str("..") | x.push(..)  grows the block, reallocating when
+ *                                  it's full (capacity doubles, and
+ *                                  starts at 8 bytes / 4 elements, as
+ *                                  RawVec does)
+ *     drop(x)
+ *
+ * Using a moved variable is an error, as it would be for rustc (E0382).
+ * A `let` that shadows a variable keeps the old one, marked shadowed:
+ * it lives until the end of the scope, it's just unnamed.
+ *
+ * The heap shows characters for a String (␣ for a space), though len
+ * and cap count bytes, as String does; empty cells are capacity not in
+ * use.
+ *
+ * rustc memory.rs -o memory
+ * ./memory script.txt                 the final state
+ * ./memory script.txt --steps         a diagram after every line
+ * ./memory script.txt --svg > m.svg   for the HTML notes
+ * ./memory script.txt --plain         the same in words, one variable a
+ *                                     line, for a screen reader (the
+ *                                     "In words:" under the drawings in
+ *                                     the notes)
+ * (no file: the script is read from stdin)
+ *
+ * --plain isn't switched on by a pipe: the drawing is piped into the
+ * notes on purpose.
+ */
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Read};
+
+// THE MODEL --------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    String,
+    Vec,
+    Box,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Block {
+    kind: Kind,
+    cells: Vec<String>,
+    len: usize,                        // bytes for a String, else elements
+    cap: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Scalar(String),
+    Owner(usize),                      // index into Memory::heap
+    Moved(String),                     // to whom
+    Ref(String),                       // the variable pointed at
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Var {
+    name: String,
+    value: Value,
+    shadowed: bool,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Memory {
+    stack: Vec<Var>,
+    heap: Vec<Option<Block>>,          // None: freed
+}
+
+fn quoted(text: &str) -> Option<&str> {
+    text.strip_prefix('"')?.strip_suffix('"')
+}
+
+fn string_block(text: &str) -> Block {
+    Block { kind: Kind::String, cells: text.chars().map(String::from).collect(), len: text.len(), cap: text.len() }
+}
+
+// RawVec's growth: double, or what's needed if more, and never less
+// than 8 bytes (4 elements) on the first allocation
+fn grown(cap: usize, needed: usize, kind: &Kind) -> usize {
+    let min = if *kind == Kind::String { 8 } else { 4 };
+    if needed <= cap { cap } else { (cap * 2).max(needed).max(min) }
+}
+
+impl Memory {
+    fn find(&self, name: &str) -> Result<usize, String> {
+        let i = self.stack.iter().rposition(|v| v.name == name && !v.shadowed);
+        let i = i.ok_or_else(|| format!("no variable `{name}`"))?;
+        match &self.stack[i].value {
+            Value::Moved(to) => Err(format!("`{name}` was moved to `{to}`, so it can't be used (E0382)")),
+            _ => Ok(i),
+        }
+    }
+
+    fn alloc(&mut self, block: Block) -> Value {
+        self.heap.push(Some(block));
+        Value::Owner(self.heap.len() - 1)
+    }
+
+    fn free(&mut self, value: &Value) {
+        if let Value::Owner(id) = value {
+            self.heap[*id] = None;
+        }
+    }
+
+    // the value of an expression, for `target` to own
+    fn eval(&mut self, expr: &str, target: &str) -> Result<Value, String> {
+        if let Some(inner) = expr.strip_prefix("String::from(").and_then(|e| e.strip_suffix(')')) {
+            let text = quoted(inner).ok_or("String::from takes a string literal here")?;
+            return Ok(self.alloc(string_block(text)));
+        }
+        if let Some(text) = expr.strip_suffix(".to_string()").and_then(quoted) {
+            return Ok(self.alloc(string_block(text)));
+        }
+        if expr == "String::new()" || expr == "Vec::new()" {
+            let kind = if expr.starts_with("String") { Kind::String } else { Kind::Vec };
+            return Ok(self.alloc(Block { kind, cells: Vec::new(), len: 0, cap: 0 }));
+        }
+        if let Some(items) = expr.strip_prefix("vec![").and_then(|e| e.strip_suffix(']')) {
+            let cells: Vec<String> = items.split(',').map(str::trim).filter(|i| !i.is_empty()).map(String::from).collect();
+            let len = cells.len();
+            return Ok(self.alloc(Block { kind: Kind::Vec, cells, len, cap: len }));
+        }
+        if let Some(inner) = expr.strip_prefix("Box::new(").and_then(|e| e.strip_suffix(')')) {
+            return Ok(self.alloc(Block { kind: Kind::Box, cells: vec![inner.trim().to_string()], len: 1, cap: 1 }));
+        }
+        if let Some(name) = expr.strip_suffix(".clone()") {
+            let i = self.find(name)?;
+            return match self.stack[i].value.clone() {
+                Value::Owner(id) => {
+                    let mut block = self.heap[id].clone().ok_or("freed")?;
+                    block.cap = block.len;     // a clone allocates exactly what it needs
+                    Ok(self.alloc(block))
+                }
+                value => Ok(value),
+            };
+        }
+        if let Some(name) = expr.strip_prefix("&mut ").or_else(|| expr.strip_prefix('&')) {
+            let i = self.find(name.trim())?;
+            return Ok(Value::Ref(self.stack[i].name.clone()));
+        }
+        let is_name = expr.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && expr.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if is_name && !["true", "false"].contains(&expr) {
+            let i = self.find(expr)?;
+            let value = self.stack[i].value.clone();
+            if let Value::Owner(_) = value {
+                self.stack[i].value = Value::Moved(target.to_string());
+            }
+            return Ok(value);
+        }
+        if !expr.is_empty() && !expr.contains(' ') {
+            return Ok(Value::Scalar(expr.to_string()));
+        }
+        Err(format!("can't draw `{expr}`"))
+    }
+
+    fn push(&mut self, name: &str, method: &str, arg: &str) -> Result<(), String> {
+        let i = self.find(name)?;
+        let Value::Owner(id) = self.stack[i].value else { return Err(format!("`{name}` owns nothing on the heap")) };
+        let block = self.heap[id].as_mut().ok_or("freed")?;
+        let (cells, bytes) = match (method, &block.kind) {
+            ("push_str", Kind::String) => {
+                let text = quoted(arg).ok_or("push_str takes a string literal here")?;
+                (text.chars().map(String::from).collect(), text.len())
+            }
+            ("push", Kind::String) => {
+                let c = arg.strip_prefix('\'').and_then(|a| a.strip_suffix('\'')).ok_or("push takes a char literal")?;
+                (vec![c.to_string()], c.len())
+            }
+            ("push", Kind::Vec) => (vec![arg.to_string()], 1),
+            _ => return Err(format!("can't {method} onto `{name}`")),
+        };
+        block.cap = grown(block.cap, block.len + bytes, &block.kind);
+        block.len += bytes;
+        block.cells.extend(cells);
+        Ok(())
+    }
+
+    fn step(&mut self, line: &str) -> Result<(), String> {
+        let line = line.split("//").next().unwrap_or_default().trim().trim_end_matches(';').trim();
+        if line.is_empty() {
+            return Ok(());
+        }
+        if let Some(rest) = line.strip_prefix("let ") {
+            let rest = rest.strip_prefix("mut ").unwrap_or(rest);
+            let (name, expr) = rest.split_once('=').ok_or("expected `let x = ...`")?;
+            let name = name.split(':').next().unwrap_or_default().trim();
+            let value = self.eval(expr.trim(), name)?;
+            for var in self.stack.iter_mut().filter(|v| v.name == name) {
+                var.shadowed = true;
+            }
+            self.stack.push(Var { name: name.to_string(), value, shadowed: false });
+            return Ok(());
+        }
+        if let Some(name) = line.strip_prefix("drop(").and_then(|l| l.strip_suffix(')')) {
+            let i = self.find(name.trim())?;
+            let var = self.stack.remove(i);
+            self.free(&var.value);
+            return Ok(());
+        }
+        let assigned = line.split_once('=').filter(|(name, _)| name.trim().chars().all(|c| c.is_alphanumeric() || c == '_'));
+        if let Some((name, expr)) = assigned {
+            let name = name.trim();
+            let i = self.stack.iter().rposition(|v| v.name == name && !v.shadowed).ok_or(format!("no variable `{name}`"))?;
+            let value = self.eval(expr.trim(), name)?;
+            let old = std::mem::replace(&mut self.stack[i].value, value);
+            self.free(&old);
+            return Ok(());
+        }
+        if let Some((call, arg)) = line.strip_suffix(')').and_then(|l| l.split_once('(')) {
+            let (name, method) = call.split_once('.').ok_or_else(|| format!("can't draw `{line}`"))?;
+            return self.push(name.trim(), method, arg.trim());
+        }
+        Err(format!("can't draw `{line}`"))
+    }
+}
+
+fn run(script: &str) -> Result<Memory, String> {
+    let mut memory = Memory::default();
+    for (n, line) in (1..).zip(script.lines()) {
+        memory.step(line).map_err(|e| format!("line {n}: {e}"))?;
+    }
+    Ok(memory)
+}
+
+// LAYOUT -----------------------------------------------------------------
+
+// one row of a variable's box: name (first row only), value, and the
+// heap block its pointer leads to
+struct Row {
+    name: String,
+    value: String,
+    points_to: Option<usize>,
+}
+
+const MAX_CELLS: usize = 12;
+
+fn rows(memory: &Memory) -> Vec<Vec<Row>> {
+    let row = |name: String, value: String, points_to| Row { name, value, points_to };
+    memory
+        .stack
+        .iter()
+        .map(|var| {
+            let name = if var.shadowed { format!("{} (shadowed)", var.name) } else { var.name.clone() };
+            match &var.value {
+                Value::Scalar(s) => vec![row(name, s.clone(), None)],
+                Value::Moved(to) => vec![row(name, format!("moved → {to}"), None)],
+                Value::Ref(to) => vec![row(name, format!("→ {to}"), None)],
+                Value::Owner(id) => {
+                    let block = memory.heap[*id].as_ref().expect("owned blocks are live");
+                    let points_to = (block.cap > 0).then_some(*id);
+                    let ptr = if block.cap > 0 { "ptr" } else { "ptr (dangling)" };
+                    match block.kind {
+                        Kind::Box => vec![row(name, "ptr".to_string(), points_to)],
+                        _ => vec![
+                            row(name, ptr.to_string(), points_to),
+                            row(String::new(), format!("len {}", block.len), None),
+                            row(String::new(), format!("cap {}", block.cap), None),
+                        ],
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+// the cells drawn for a block: contents, then unused capacity
+fn cells(block: &Block) -> Vec<String> {
+    let spare = match block.kind {
+        Kind::String => block.cap - block.len,
+        _ => block.cap - block.cells.len(),
+    };
+    // a space would look like spare capacity
+    let contents = block.cells.iter().map(|c| if c == " " { "␣".to_string() } else { c.clone() });
+    let mut cells: Vec<String> = contents.chain(std::iter::repeat_n(String::new(), spare)).collect();
+    if cells.len() > MAX_CELLS {
+        cells.truncate(MAX_CELLS - 1);
+        cells.push("…".to_string());
+    }
+    cells
+}
+
+// TERMINAL ---------------------------------------------------------------
+
+fn width(s: &str) -> usize {
+    s.chars().count()
+}
+
+fn pad(s: &str, w: usize) -> String {
+    format!("{s}{}", " ".repeat(w.saturating_sub(width(s))))
+}
+
+// top, middle and bottom lines of a row of cells
+fn cell_lines(cells: &[String]) -> [String; 3] {
+    let widths: Vec<usize> = cells.iter().map(|c| width(c).max(1)).collect();
+    let rule = |l: &str, m: &str, r: &str| {
+        l.to_string() + &widths.iter().map(|w| "─".repeat(w + 2)).collect::<Vec<_>>().join(m) + r
+    };
+    let middle = cells.iter().zip(&widths).map(|(c, w)| format!(" {} ", pad(c, *w))).collect::<Vec<_>>().join("│");
+    [rule("┌", "┬", "┐"), format!("│{middle}│"), rule("└", "┴", "┘")]
+}
+
+fn render_terminal(memory: &Memory) -> String {
+    let vars = rows(memory);
+    if vars.is_empty() {
+        return " (nothing on the stack)\n".to_string();
+    }
+    let all = vars.iter().flatten();
+    let nw = all.clone().map(|r| width(&r.name)).max().unwrap_or(0);
+    let vw = all.map(|r| width(&r.value)).max().unwrap_or(0);
+    let rule = |l, m, r| format!("{l}{}{m}{}{r}", "─".repeat(nw + 2), "─".repeat(vw + 2));
+
+    // the stack as lines, noting which line each pointer is on
+    let mut stack = vec![rule("┌", "┬", "┐")];
+    let mut pointers = Vec::new();
+    for (i, var) in vars.iter().enumerate() {
+        if i > 0 {
+            stack.push(rule("├", "┼", "┤"));
+        }
+        for row in var {
+            if let Some(id) = row.points_to {
+                pointers.push((stack.len(), id));
+            }
+            stack.push(format!("│ {} │ {} │", pad(&row.name, nw), pad(&row.value, vw)));
+        }
+    }
+    stack.push(rule("└", "┴", "┘"));
+
+    // each block drawn level with its pointer: top rule on the line above
+    let mut heap = vec![String::new(); stack.len() + 1];
+    for &(line, id) in &pointers {
+        let block = memory.heap[id].as_ref().expect("live");
+        let [top, middle, bottom] = cell_lines(&cells(block));
+        (heap[line - 1], heap[line], heap[line + 1]) = (top, middle, bottom);
+    }
+
+    let stack_width = nw + vw + 7;
+    let mut out = format!(" {}{}heap\n", pad("stack", stack_width), " ".repeat(5));
+    for (i, heap_line) in heap.iter().enumerate() {
+        let stack_line = stack.get(i).map_or(String::new(), |s| s.clone());
+        let gap = if pointers.iter().any(|&(line, _)| line == i) { " ──▶ " } else { "     " };
+        let line = format!("{}{gap}{heap_line}", pad(&stack_line, stack_width));
+        out += line.trim_end();
+        out += "\n";
+    }
+    out.trim_end().to_string() + "\n"
+}
+
+// WORDS ------------------------------------------------------------------
+
+fn plural(n: usize, what: &str) -> String {
+    if n == 1 { format!("1 {what}") } else { format!("{n} {what}s") }
+}
+
+// one line per variable, what it holds first and the numbers after
+fn render_words(memory: &Memory) -> String {
+    if memory.stack.is_empty() {
+        return "Nothing on the stack.\n".to_string();
+    }
+    let mut out = String::new();
+    for var in &memory.stack {
+        let name = if var.shadowed { format!("{} (shadowed)", var.name) } else { var.name.clone() };
+        let what = match &var.value {
+            Value::Scalar(s) => format!("{s}, on the stack."),
+            Value::Moved(to) => format!("moved to {to}, so it holds nothing."),
+            Value::Ref(to) => format!("a reference to {to}."),
+            Value::Owner(id) => {
+                let block = memory.heap[*id].as_ref().expect("owned blocks are live");
+                let unit = if block.kind == Kind::String { "byte" } else { "element" };
+                let spare = block.cap - block.len;
+                let spare = if spare > 0 { format!(", {} unused", plural(spare, unit)) } else { String::new() };
+                match block.kind {
+                    Kind::Box => format!("a Box, pointing to {} on the heap.", block.cells.join("")),
+                    _ if block.cap == 0 => format!("an empty {:?}, with nothing allocated yet.", block.kind),
+                    Kind::String => format!("a String, pointing to \"{}\" on the heap: len {}, cap {}{spare}.", block.cells.join(""), block.len, block.cap),
+                    Kind::Vec => format!("a Vec, pointing to [{}] on the heap: len {}, cap {}{spare}.", block.cells.join(", "), block.len, block.cap),
+                }
+            }
+        };
+        writeln!(out, "{name}: {what}").unwrap();
+    }
+    out
+}
+
+// SVG --------------------------------------------------------------------
+
+const ROW: usize = 24;                 // px per row
+const CHAR: usize = 9;                 // px per character, monospace
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+// the same picture: stack rows down the left, each block level with its
+// pointer, and an arrow between them
+fn render_svg(memory: &Memory) -> String {
+    let vars = rows(memory);
+    let nw = vars.iter().flatten().map(|r| width(&r.name)).max().unwrap_or(0) * CHAR + 16;
+    let vw = vars.iter().flatten().map(|r| width(&r.value)).max().unwrap_or(0) * CHAR + 16;
+    let heap_x = 10 + nw + vw + 60;
+
+    let mut body = String::new();
+    let mut y = 30;
+    let mut widest = heap_x;
+    for var in &vars {
+        for row in var {
+            let x = 10;
+            writeln!(body, r#"<rect x="{x}" y="{y}" width="{nw}" height="{ROW}" class="cell"/>"#).unwrap();
+            writeln!(body, r#"<rect x="{}" y="{y}" width="{vw}" height="{ROW}" class="cell"/>"#, x + nw).unwrap();
+            let ty = y + 16;
+            writeln!(body, r#"<text x="{}" y="{ty}">{}</text>"#, x + 8, escape(&row.name)).unwrap();
+            writeln!(body, r#"<text x="{}" y="{ty}">{}</text>"#, x + nw + 8, escape(&row.value)).unwrap();
+
+            if let Some(id) = row.points_to {
+                let block = memory.heap[id].as_ref().expect("live");
+                let mid = y + ROW / 2;
+                let (from, to) = (x + nw + vw - 8, heap_x - 4);
+                writeln!(body, r#"<circle cx="{from}" cy="{mid}" r="3"/>"#).unwrap();
+                writeln!(body, r#"<line x1="{from}" y1="{mid}" x2="{to}" y2="{mid}" marker-end="url(#arrow)"/>"#).unwrap();
+                let mut cx = heap_x;
+                for cell in cells(block) {
+                    let w = width(&cell).max(1) * CHAR + 16;
+                    writeln!(body, r#"<rect x="{cx}" y="{y}" width="{w}" height="{ROW}" class="heap"/>"#).unwrap();
+                    writeln!(body, r#"<text x="{}" y="{ty}">{}</text>"#, cx + 8, escape(&cell)).unwrap();
+                    cx += w;
+                }
+                widest = widest.max(cx);
+            }
+            y += ROW;
+        }
+        y += 8;
+    }
+
+    let (w, h) = (widest + 10, y + 10);
+    let mut svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" font-family="monospace" font-size="14">"#);
+    svg += "\n<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"9\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\">";
+    svg += "<path d=\"M0,0 L10,5 L0,10 z\"/></marker></defs>\n";
+    svg += "<style>.cell { fill: #f4f4f4; stroke: #333; } .heap { fill: #fdf1d6; stroke: #333; } line { stroke: #333; }</style>\n";
+    writeln!(svg, r#"<text x="10" y="18">stack</text><text x="{heap_x}" y="18">heap</text>"#).unwrap();
+    svg + &body + "</svg>\n"
+}
+
+// MAIN -------------------------------------------------------------------
+
+const SAMPLE: &str = r#"
+let s1 = String::from("hi");      // on the heap
+let s2 = s1;                      // move
+let s3 = s2.clone();
+let x = 5;
+let r = &s3;
+"#;
+
+fn self_check() {
+    let memory = run(SAMPLE).unwrap();
+    let names: Vec<&str> = memory.stack.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names, ["s1", "s2", "s3", "x", "r"]);
+    assert_eq!(memory.stack[0].value, Value::Moved("s2".to_string()));
+    assert_eq!(memory.stack[1].value, Value::Owner(0));
+    assert_eq!(memory.stack[2].value, Value::Owner(1));
+    assert_eq!(memory.stack[4].value, Value::Ref("s3".to_string()));
+
+    assert!(run("let a = String::new();\nlet b = a;\nlet c = a;").unwrap_err().contains("line 3: `a` was moved to `b`"));
+
+    // growth as RawVec does it: 2 -> 8 on the first push, then doubling
+    let grown = run("let mut s = String::from(\"hi\");\ns.push_str(\"!\");").unwrap();
+    let block = grown.heap[0].as_ref().unwrap();
+    assert_eq!((block.len, block.cap, cells(block).len()), (3, 8, 8));
+
+    // shadowing keeps the old value; assignment and drop free it
+    let shadow = run("let s = String::from(\"a\");\nlet s = String::from(\"b\");\ns = String::new();").unwrap();
+    assert!(shadow.stack[0].shadowed && !shadow.stack[1].shadowed);
+    assert!(shadow.heap[0].is_some() && shadow.heap[1].is_none());
+    assert!(run("let v = vec![1, 2];\ndrop(v);").unwrap().heap[0].is_none());
+
+    // the stack's right edge lines up, and arrows meet a block
+    let text = render_terminal(&memory);
+    let stack: Vec<&str> = text.lines().filter(|l| l.starts_with(['┌', '│', '├', '└'])).collect();
+    let edge = width(stack[0]) - 1;
+    assert!(stack.iter().all(|l| l.chars().nth(edge).is_some_and(|c| "┐│┤┘".contains(c))), "{text}");
+    assert_eq!(text.matches("──▶").count(), 2, "{text}");
+    assert!(text.contains("│ h │ i │"));
+
+    let words = render_words(&memory);
+    assert!(words.starts_with("s1: moved to s2") && words.contains("s2: a String, pointing to \"hi\" on the heap: len 2, cap 2."));
+    assert!(render_words(&grown).contains("len 3, cap 8, 5 bytes unused"));
+
+    let svg = render_svg(&memory);
+    assert_eq!(svg.matches("<line").count(), 2);
+    assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+}
+
+fn main() {
+    self_check();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let flag = |name: &str| args.iter().any(|a| a == name);
+    let (steps, svg, plain) = (flag("--steps"), flag("--svg"), flag("--plain"));
+    let render = if plain { render_words } else { render_terminal };
+    let script = match args.iter().find(|a| !a.starts_with("--")) {
+        Some(path) => fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("memory: {path}: {e}");
+            std::process::exit(1);
+        }),
+        None => {
+            let mut script = String::new();
+            io::stdin().read_to_string(&mut script).expect("stdin");
+            script
+        }
+    };
+
+    if steps {
+        let mut memory = Memory::default();
+        for (n, line) in (1..).zip(script.lines()) {
+            if line.split("//").next().unwrap_or_default().trim().is_empty() {
+                continue;
+            }
+            if let Err(e) = memory.step(line) {
+                eprintln!("memory: line {n}: {e}");
+                std::process::exit(1);
+            }
+            println!("{}\n\n{}", line.trim(), render(&memory));
+        }
+        return;
+    }
+    match run(&script) {
+        Ok(memory) if svg => print!("{}", render_svg(&memory)),
+        Ok(memory) => print!("{}", render(&memory)),
+        Err(e) => {
+            eprintln!("memory: {e}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,296 @@
+// DIAGRAMS: AN EDITION MIGRATION, ONE EDITION AT A TIME -----------------
+
+/*
+ * Takes a program written for an old edition and moves it forward the
+ * way a crate would be: `cargo fix --edition` rewrites the code so it
+ * means the same in the next edition, then Cargo.toml's edition goes up,
+ * and again until the last one. Each step shows the diff cargo fix made
+ * and what the program printed before and after it:
+ *
+ *     2015 -> 2018: cargo fix made 5 changes
+ *
+ *        4 -         fn scale(&mut self, f64);
+ *        4 +         fn scale(&mut self, _: f64);
+ *          ...
+ *       26 -     let async = 1;
+ *       26 +     let r#async = 1;
+ *
+ *     output: the same
+ *
+ * The output is there because cargo fix only changes what a lint knows
+ * about. editions.rs goes through what each step changes, and the one
+ * the built-in example has that no fix covers: under 2024 it stops
+ * panicking.
+ *
+ * Without a file it migrates the built-in example, a 2015 program with
+ * something for every step (./migrate --example prints it). A file is a
+ * whole program, with its main, std only.
+ *
+ * rustc migrate.rs -o migrate
+ * ./migrate                           the example, 2015 to 2024
+ * ./migrate old.rs --from 2018        a file, from the 2018 edition
+ * ./migrate --to 2021 --final         stop at 2021, print the result
+ * ./migrate --context 2               two unchanged lines around a change
+ */
+
+#[path = "../quiz/src/diff.rs"]
+mod diff;                               // the quiz's, for `quiz snapshots`
+
+use diff::diff;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const EDITIONS: [&str; 4] = ["2015", "2018", "2021", "2024"];
+
+const EXAMPLE: &str = r#"mod shapes {
+    pub trait Shape {
+        fn area(&self) -> f64;
+        fn scale(&mut self, f64);
+    }
+
+    pub struct Square(pub f64);
+
+    impl Shape for Square {
+        fn area(&self) -> f64 { self.0 * self.0 }
+        fn scale(&mut self, by: f64) { self.0 *= by; }
+    }
+}
+
+use shapes::Shape;
+
+fn largest(shapes: &[Box<Shape>]) -> f64 {
+    shapes.iter().map(|s| s.area()).fold(0.0, f64::max)
+}
+
+macro_rules! first {
+    ($p:pat | $q:pat) => { 1 };
+}
+
+fn main() {
+    let async = 1;
+    let gen = 2;
+    let shapes: Vec<Box<Shape>> = vec![Box::new(::shapes::Square(2.0))];
+    println!("{}", largest(&shapes));
+
+    let array = [1, 2, 3];
+    for x in array.into_iter() {
+        println!("{}", x * async + gen);
+    }
+
+    let pair = (1, Noisy("pair.1"));
+    {
+        let c = move || println!("{}", pair.0);
+        c();
+    }
+    println!("after the closure");
+
+    match 5 {
+        0...9 => println!("digit"),
+        _ => {}
+    }
+    let _ = first!(Some(_) | None);
+
+    let cell = std::cell::RefCell::new(Vec::new());
+    if let Some(x) = cell.borrow().first() {
+        println!("{}", x);
+    } else {
+        cell.borrow_mut().push(1);
+    }
+
+    println!("{} {}", count(), call_abs());
+}
+
+struct Noisy(&'static str);
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        println!("drop {}", self.0);
+    }
+}
+
+static mut COUNT: u32 = 0;
+
+fn count() -> u32 {
+    unsafe {
+        COUNT += 1;
+        COUNT
+    }
+}
+
+extern "C" {
+    fn abs(x: i32) -> i32;
+}
+
+fn call_abs() -> i32 {
+    unsafe { abs(-3) }
+}
+"#;
+
+struct Step {
+    from: &'static str,
+    to: &'static str,
+    fixes: usize,
+    before: String,
+    after: String,
+    ran_before: String,
+    ran_after: String,
+}
+
+fn manifest(edition: &str) -> String {
+    format!("[package]\nname = \"migrating\"\nversion = \"0.1.0\"\nedition = \"{edition}\"\n")
+}
+
+fn cargo(dir: &Path, args: &[&str]) -> Result<std::process::Output, String> {
+    Command::new("cargo").args(args).current_dir(dir).output().map_err(|e| format!("cannot run cargo: {e}"))
+}
+
+// what the program prints, and how it panicked if it did; Err if it
+// doesn't build
+fn run(dir: &Path) -> Result<String, String> {
+    let output = cargo(dir, &["run", "--quiet"])?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut out = String::from_utf8_lossy(&output.stdout).into_owned();
+    if !output.status.success() {
+        // "thread 'main' (16180) panicked at src/main.rs:53:14:" and the message under it
+        let mut lines = stderr.lines().skip_while(|l| !l.contains("panicked at"));
+        match (lines.next(), lines.next()) {
+            (Some(_), Some(message)) => out += &format!("panicked: {message}\n"),
+            _ => return Err(format!("it doesn't build:\n{stderr}")),
+        }
+    }
+    Ok(out)
+}
+
+// "       Fixed src/main.rs (5 fixes)" -> 5
+fn fixes(stderr: &str) -> usize {
+    stderr
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("Fixed ")?.rsplit_once(" (")?.1.split_once(' ')?.0.parse::<usize>().ok())
+        .sum()
+}
+
+fn migrate(dir: &Path, source: &str, from: usize, to: usize) -> Result<Vec<Step>, String> {
+    fs::create_dir_all(dir.join("src")).map_err(|e| e.to_string())?;
+    fs::write(dir.join("src/main.rs"), source).map_err(|e| e.to_string())?;
+    let mut steps = Vec::new();
+    for i in from..to {
+        fs::write(dir.join("Cargo.toml"), manifest(EDITIONS[i])).map_err(|e| e.to_string())?;
+        let ran_before = run(dir).map_err(|e| format!("under {}, {e}", EDITIONS[i]))?;
+        let before = fs::read_to_string(dir.join("src/main.rs")).map_err(|e| e.to_string())?;
+        let output = cargo(dir, &["fix", "--edition", "--allow-no-vcs"])?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            return Err(format!("cargo fix {} -> {}:\n{stderr}", EDITIONS[i], EDITIONS[i + 1]));
+        }
+        let after = fs::read_to_string(dir.join("src/main.rs")).map_err(|e| e.to_string())?;
+        fs::write(dir.join("Cargo.toml"), manifest(EDITIONS[i + 1])).map_err(|e| e.to_string())?;
+        let ran_after = run(dir).map_err(|e| format!("under {}, after cargo fix, {e}", EDITIONS[i + 1]))?;
+        steps.push(Step { from: EDITIONS[i], to: EDITIONS[i + 1], fixes: fixes(&stderr), before, after, ran_before, ran_after });
+    }
+    Ok(steps)
+}
+
+// RENDERING --------------------------------------------------------------
+
+// the changed lines, `context` unchanged lines around each, and "..."
+// where lines are left out
+fn hunks(diff: &[(char, usize, &str)], context: usize) -> String {
+    let changed: Vec<usize> = (0..diff.len()).filter(|&k| diff[k].0 != ' ').collect();
+    let near = |k: usize| changed.iter().any(|&c| c.abs_diff(k) <= context);
+    let mut out = String::new();
+    let mut skipped = false;
+    for (k, (sign, n, line)) in diff.iter().enumerate() {
+        if !near(k) {
+            skipped = true;
+            continue;
+        }
+        if skipped && !out.is_empty() {
+            out += "         ...\n";
+        }
+        skipped = false;
+        out += format!("  {n:>4} {sign} {line}").trim_end();
+        out += "\n";
+    }
+    out
+}
+
+fn render(step: &Step, context: usize) -> String {
+    let changes = if step.fixes == 1 { "1 change".to_string() } else { format!("{} changes", step.fixes) };
+    let mut out = format!("{} -> {}: cargo fix made {changes}\n\n", step.from, step.to);
+    out += &hunks(&diff(&step.before, &step.after), context);
+    if step.ran_before == step.ran_after {
+        out += "\noutput: the same\n";
+    } else {
+        out += &format!("\noutput under {} (-) and {} (+):\n", step.from, step.to);
+        out += &hunks(&diff(&step.ran_before, &step.ran_after), usize::MAX);
+    }
+    out
+}
+
+// MAIN -------------------------------------------------------------------
+
+fn self_check() {
+    assert_eq!(fixes("   Migrating src/main.rs from 2015 edition to 2018\n       Fixed src/main.rs (5 fixes)\n"), 5);
+    assert_eq!(fixes("       Fixed src/lib.rs (1 fix)\n       Fixed src/main.rs (2 fixes)\n"), 3);
+    assert_eq!(fixes("    Finished `dev` profile\n"), 0);
+
+    let d = diff("a\nb\nc\n", "a\nB\nc\nd\n");
+    assert_eq!(d, [(' ', 1, "a"), ('-', 2, "b"), ('+', 2, "B"), (' ', 3, "c"), ('+', 4, "d")]);
+    let far = diff("1\n2\n3\n4\n5\n6\n", "x\n2\n3\n4\n5\ny\n");
+    assert_eq!(hunks(&far, 0), "     1 - 1\n     1 + x\n         ...\n     6 - 6\n     6 + y\n");
+    assert_eq!(hunks(&diff("same\n", "same\n"), 1), "");
+    assert!(EXAMPLE.contains("fn main()") && EDITIONS.windows(2).all(|w| w[0] < w[1]));
+}
+
+fn main() {
+    self_check();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--example") {
+        print!("{EXAMPLE}");
+        return;
+    }
+    let value = |flag: &str| args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).map(String::as_str);
+    let edition = |flag: &str, default: usize| match value(flag) {
+        None => default,
+        Some(e) => EDITIONS.iter().position(|known| *known == e).unwrap_or_else(|| {
+            eprintln!("migrate: {flag} {e}: the editions are {}", EDITIONS.join(", "));
+            std::process::exit(2);
+        }),
+    };
+    let (from, to) = (edition("--from", 0), edition("--to", EDITIONS.len() - 1));
+    let context = value("--context").and_then(|n| n.parse().ok()).unwrap_or(0);
+    let values: Vec<&str> = ["--from", "--to", "--context"].iter().filter_map(|f| value(f)).collect();
+    let file = args.iter().find(|a| !a.starts_with("--") && !values.contains(&a.as_str()));
+
+    let source = match file {
+        Some(path) => fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("migrate: {path}: {e}");
+            std::process::exit(1);
+        }),
+        None => EXAMPLE.to_string(),
+    };
+    if from >= to {
+        eprintln!("migrate: nothing to do from {} to {}", EDITIONS[from], EDITIONS[to]);
+        std::process::exit(2);
+    }
+
+    let dir = std::env::temp_dir().join(format!("migrate-{}", std::process::id()));
+    let result = migrate(&dir, &source, from, to);
+    fs::remove_dir_all(&dir).ok();
+
+    match result {
+        Ok(steps) => {
+            let rendered: Vec<String> = steps.iter().map(|s| render(s, context)).collect();
+            print!("{}", rendered.join("\n\n"));
+            if let (Some(last), true) = (steps.last(), args.iter().any(|a| a == "--final")) {
+                print!("\n\nin {}:\n\n{}", last.to, last.after);
+            }
+        }
+        Err(e) => {
+            eprintln!("migrate: {e}");
+            std::process::exit(1);
+        }
+    }
+}
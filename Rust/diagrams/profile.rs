@@ -0,0 +1,380 @@
+// DIAGRAMS: WHERE A PROGRAM SPENDS ITS TIME ------------------------------
+
+/*
+ * Builds one of the guided projects with debug symbols, runs it under a
+ * sampling profiler, and prints where the samples landed as a call tree,
+ * so a flamegraph can be read in the terminal (profiling.rs is the
+ * walkthrough), something like:
+ *
+ *     total   self
+ *    100.0%   0.0%  spell_checker::main
+ *     97.0%   0.4%  ├─ spell_checker::suggest_naive
+ *     96.1%  96.1%  │  └─ spell_checker::edit_distance
+ *      2.6%   0.3%  └─ spell_checker::suggest_fast
+ *      ...
+ *
+ * then the functions with the most self time. total is the share of
+ * samples with the function anywhere on the stack, self the share where
+ * it was the one running: the width of a box in a flamegraph, and the
+ * part of that box with nothing stacked on top.
+ *
+ * The profiler, in order of preference:
+ *
+ *     cargo flamegraph      (cargo install flamegraph) also writes the
+ *                           SVG; needs perf underneath
+ *     perf record -g        (linux-perf / linux-tools) the text view only
+ *
+ * Neither is there on macOS or Windows; cargo flamegraph uses dtrace on
+ * macOS, and its SVG can still be opened, but the text view needs perf.
+ * perf also needs /proc/sys/kernel/perf_event_paranoid at 2 or lower.
+ *
+ * The project is copied into a cargo project in the temp directory
+ * (kept, so the second run doesn't rebuild), with the [dependencies] its
+ * header lists, [profile.release] debug = true for function names, and
+ * frame pointers for the stacks.
+ *
+ * rustc profile.rs -o profile
+ * ./profile spell_checker [-- ARGS..]   a project in ../projects, by name
+ * ./profile path/to/project.rs
+ * ./profile life --svg life.svg         keep cargo flamegraph's SVG
+ * ./profile life --min 0.5              prune the tree below 0.5% (default 1)
+ * ./profile --folded stacks.folded      draw folded stacks (a;b;c 12 per
+ *                                       line) from any other profiler
+ */
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// "foo::bar::h0123456789abcdef" -> "foo::bar"; "edit_distance+0x53" -> "edit_distance"
+fn clean(symbol: &str) -> String {
+    let symbol = symbol.split('+').next().unwrap_or(symbol).trim();
+    match symbol.rsplit_once("::h") {
+        Some((name, hash)) if hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()) => name.to_string(),
+        _ => symbol.to_string(),
+    }
+}
+
+// perf script's output, one sample per block:
+//
+//     spell_checker 4711 123.456789:   1001001 cycles:u:
+//             55d0c0a1b2c3 spell_checker::edit_distance+0x53 (/tmp/../spell_checker)
+//             55d0c0a1b4c5 spell_checker::suggest_naive+0x95 (/tmp/../spell_checker)
+//
+// into stacks from the root down, counted
+fn fold_perf(script: &str) -> HashMap<Vec<String>, usize> {
+    let mut stacks = HashMap::new();
+    for block in script.split("\n\n") {
+        let mut frames: Vec<String> = block
+            .lines()
+            .skip(1)
+            .filter_map(|l| l.split_whitespace().nth(1))
+            .map(clean)
+            .collect();
+        if frames.is_empty() {
+            continue;
+        }
+        frames.reverse();
+        *stacks.entry(frames).or_default() += 1;
+    }
+    stacks
+}
+
+// "a;b;c 12" per line, as cargo flamegraph and inferno write them
+fn read_folded(text: &str) -> HashMap<Vec<String>, usize> {
+    let mut stacks = HashMap::new();
+    for line in text.lines() {
+        let Some((stack, count)) = line.rsplit_once(' ') else { continue };
+        let Ok(count) = count.trim().parse::<usize>() else { continue };
+        *stacks.entry(stack.split(';').map(clean).collect()).or_default() += count;
+    }
+    stacks
+}
+
+// without the frames above the program's own main: the runtime's
+// start-up is on every stack and says nothing
+fn from_main(stack: &[String]) -> &[String] {
+    let main = stack.iter().position(|f| f.ends_with("::main") && !f.starts_with("std::") && !f.starts_with("core::"));
+    &stack[main.unwrap_or(0)..]
+}
+
+// THE TREE ---------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct Node {
+    name: String,
+    total: usize,
+    own: usize,                        // samples with this frame on top
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn insert(&mut self, stack: &[String], count: usize) {
+        self.total += count;
+        let Some((first, rest)) = stack.split_first() else {
+            self.own += count;
+            return;
+        };
+        let i = match self.children.iter().position(|c| &c.name == first) {
+            Some(i) => i,
+            None => {
+                self.children.push(Node { name: first.clone(), ..Node::default() });
+                self.children.len() - 1
+            }
+        };
+        self.children[i].insert(rest, count);
+    }
+}
+
+fn tree(stacks: &HashMap<Vec<String>, usize>) -> Node {
+    let mut root = Node::default();
+    for (stack, count) in stacks {
+        root.insert(from_main(stack), *count);
+    }
+    root
+}
+
+fn percent(part: usize, whole: usize) -> f64 {
+    100.0 * part as f64 / whole.max(1) as f64
+}
+
+fn render(node: &Node, samples: usize, min: f64, prefix: &str, last: bool, depth: usize, out: &mut String) {
+    let branch = match depth {
+        0 => "",
+        _ if last => "└─ ",
+        _ => "├─ ",
+    };
+    out.push_str(&format!(
+        "{:>6.1}% {:>6.1}%  {prefix}{branch}{}\n",
+        percent(node.total, samples),
+        percent(node.own, samples),
+        node.name
+    ));
+    let mut children: Vec<&Node> = node.children.iter().filter(|c| percent(c.total, samples) >= min).collect();
+    children.sort_by_key(|c| std::cmp::Reverse(c.total));
+    let prefix = match depth {
+        0 => String::new(),
+        _ if last => format!("{prefix}   "),
+        _ => format!("{prefix}│  "),
+    };
+    for (i, child) in children.iter().enumerate() {
+        render(child, samples, min, &prefix, i + 1 == children.len(), depth + 1, out);
+    }
+}
+
+// self time per function, wherever it was called from
+fn hottest(stacks: &HashMap<Vec<String>, usize>) -> Vec<(String, usize)> {
+    let mut own: HashMap<&str, usize> = HashMap::new();
+    for (stack, count) in stacks {
+        if let Some(top) = stack.last() {
+            *own.entry(top).or_default() += count;
+        }
+    }
+    let mut own: Vec<(String, usize)> = own.into_iter().map(|(f, n)| (f.to_string(), n)).collect();
+    own.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    own
+}
+
+fn report(stacks: &HashMap<Vec<String>, usize>, min: f64) -> String {
+    let samples: usize = stacks.values().sum();
+    let mut out = format!("{samples} samples\n\n  total    self\n");
+    let root = tree(stacks);
+    // one main: start the tree there
+    let start = if root.children.len() == 1 && root.own == 0 { &root.children[0] } else { &root };
+    render(start, samples, min, "", true, 0, &mut out);
+
+    out += "\nself time\n\n";
+    for (function, n) in hottest(stacks).iter().take(10) {
+        let share = percent(*n, samples);
+        out += &format!("{share:>6.1}%  {:<30}  {function}\n", "█".repeat((share / 100.0 * 30.0).round() as usize));
+    }
+    out
+}
+
+// RUNNING THE PROJECT ----------------------------------------------------
+
+fn succeeds(command: &mut Command) -> bool {
+    command.output().is_ok_and(|o| o.status.success())
+}
+
+fn project_file(name: &str) -> Result<PathBuf, String> {
+    if name.ends_with(".rs") {
+        return Ok(PathBuf::from(name));
+    }
+    ["../projects", "projects", "Rust/projects", "."]
+        .iter()
+        .map(|dir| Path::new(dir).join(format!("{name}.rs")))
+        .find(|path| path.exists())
+        .ok_or_else(|| format!("no project `{name}` in ../projects, projects or Rust/projects"))
+}
+
+// the lines after " * [dependencies]" in the project's header
+fn dependencies(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .skip_while(|l| l.trim() != "* [dependencies]")
+        .skip(1)
+        .map(|l| l.trim().trim_start_matches('*').trim())
+        .take_while(|l| !l.is_empty() && !l.starts_with('/'))
+        .map(str::to_string)
+        .collect()
+}
+
+// the cargo project for `file`, built; the binary's path
+fn build(file: &Path, name: &str) -> Result<(PathBuf, PathBuf), String> {
+    let source = fs::read_to_string(file).map_err(|e| format!("{}: {e}", file.display()))?;
+    let dir = std::env::temp_dir().join(format!("profile-{name}"));
+    fs::create_dir_all(dir.join("src")).map_err(|e| e.to_string())?;
+    let manifest = format!(
+        "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n{}\n\n[profile.release]\ndebug = true\n",
+        dependencies(&source).join("\n")
+    );
+    fs::write(dir.join("Cargo.toml"), manifest).map_err(|e| e.to_string())?;
+    fs::write(dir.join("src/main.rs"), &source).map_err(|e| e.to_string())?;
+
+    eprintln!("building {} with debug symbols in {}", file.display(), dir.display());
+    let status = Command::new("cargo")
+        .args(["build", "--release", "--quiet"])
+        .env("RUSTFLAGS", "-C force-frame-pointers=yes")
+        .current_dir(&dir)
+        .status()
+        .map_err(|e| format!("cannot run cargo: {e}"))?;
+    if !status.success() {
+        return Err("the build failed".to_string());
+    }
+    let binary = dir.join("target/release").join(name);
+    Ok((dir, binary))
+}
+
+// perf.data in `dir`, from cargo flamegraph or perf itself
+fn record(dir: &Path, binary: &Path, args: &[String], svg: Option<&str>) -> Result<(), String> {
+    let flamegraph = succeeds(Command::new("cargo").args(["flamegraph", "--version"]));
+    let perf = succeeds(Command::new("perf").arg("--version"));
+    if !perf {
+        return Err("perf is not installed (apt install linux-perf, or linux-tools-generic on Ubuntu); \
+                    cargo flamegraph needs it too"
+            .to_string());
+    }
+    let status = if flamegraph {
+        // cargo flamegraph runs in `dir`, so a relative --svg is made absolute
+        let svg = svg.map_or_else(|| dir.join("flamegraph.svg"), |s| std::env::current_dir().unwrap_or_default().join(s));
+        eprintln!("running under cargo flamegraph, the SVG goes to {}", svg.display());
+        Command::new("cargo")
+            .args(["flamegraph", "--release", "--output"])
+            .arg(&svg)
+            .arg("--")
+            .args(args)
+            .env("RUSTFLAGS", "-C force-frame-pointers=yes")
+            .current_dir(dir)
+            .status()
+    } else {
+        if svg.is_some() {
+            eprintln!("--svg needs cargo flamegraph (cargo install flamegraph); the text view only");
+        }
+        Command::new("perf")
+            .args(["record", "-F", "999", "-g", "--output"])
+            .arg(dir.join("perf.data"))
+            .arg("--")
+            .arg(binary)
+            .args(args)
+            .status()
+    };
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(_) => Err("profiling failed; is perf_event_paranoid above 2?".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn perf_script(dir: &Path) -> Result<String, String> {
+    let output = Command::new("perf")
+        .args(["script", "--input"])
+        .arg(dir.join("perf.data"))
+        .output()
+        .map_err(|e| format!("cannot run perf script: {e}"))?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// MAIN -------------------------------------------------------------------
+
+const SAMPLE_PERF: &str = "\
+spell_checker 4711 1.000: 1001001 cycles:u:
+\t    55d0c0a1b2c3 spell_checker::edit_distance::h0123456789abcdef+0x53 (/tmp/p/spell_checker)
+\t    55d0c0a1b4c5 spell_checker::suggest_naive+0x95 (/tmp/p/spell_checker)
+\t    55d0c0a1b6c7 spell_checker::main+0x10 (/tmp/p/spell_checker)
+\t    55d0c0a1b8c9 std::rt::lang_start_internal+0x20 (/tmp/p/spell_checker)
+
+spell_checker 4711 1.001: 1001001 cycles:u:
+\t    55d0c0a1b2c3 spell_checker::edit_distance+0x60 (/tmp/p/spell_checker)
+\t    55d0c0a1b4c5 spell_checker::suggest_naive+0x95 (/tmp/p/spell_checker)
+\t    55d0c0a1b6c7 spell_checker::main+0x10 (/tmp/p/spell_checker)
+
+spell_checker 4711 1.002: 1001001 cycles:u:
+\t    55d0c0a1b4c5 spell_checker::suggest_fast+0x11 (/tmp/p/spell_checker)
+\t    55d0c0a1b6c7 spell_checker::main+0x10 (/tmp/p/spell_checker)
+";
+
+fn self_check() {
+    assert_eq!(clean("a::b::h0123456789abcdef+0x1"), "a::b");
+    assert_eq!(clean("Vec<T>::push+0x2"), "Vec<T>::push");
+
+    let stacks = fold_perf(SAMPLE_PERF);
+    assert_eq!(stacks.values().sum::<usize>(), 3);
+    let naive: Vec<String> = ["spell_checker::main", "spell_checker::suggest_naive", "spell_checker::edit_distance"]
+        .map(String::from)
+        .to_vec();
+    assert_eq!(stacks[&naive], 1);
+    assert_eq!(from_main(&[vec!["std::rt::lang_start_internal".to_string()], naive.clone()].concat()), &naive[..]);
+
+    let folded = read_folded("main;suggest_naive;edit_distance 8\nmain;suggest_fast 2\nnoise\n");
+    let root = tree(&folded);
+    assert_eq!((root.total, root.children[0].name.as_str(), root.children[0].total), (10, "main", 10));
+    assert_eq!(hottest(&folded)[0], ("edit_distance".to_string(), 8));
+
+    let text = report(&folded, 1.0);
+    assert!(text.contains(" 100.0%    0.0%  main\n"), "{text}");
+    assert!(text.contains("  80.0%   80.0%  │  └─ edit_distance\n"), "{text}");
+
+    let header = "/*\n * [dependencies]\n * rayon = \"1\"\n *\n * cargo run --release\n */\n";
+    assert_eq!(dependencies(header), ["rayon = \"1\""]);
+}
+
+fn main() {
+    self_check();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (ours, theirs) = match args.iter().position(|a| a == "--") {
+        Some(i) => (&args[..i], &args[i + 1..]),
+        None => (&args[..], &[][..]),
+    };
+    let flag = |name: &str| ours.iter().position(|a| a == name).and_then(|i| ours.get(i + 1)).map(String::as_str);
+    let min = flag("--min").and_then(|m| m.parse().ok()).unwrap_or(1.0);
+
+    let result = if let Some(path) = flag("--folded") {
+        fs::read_to_string(path).map(|text| read_folded(&text)).map_err(|e| format!("{path}: {e}"))
+    } else {
+        // the first argument that isn't a flag or a flag's value
+        let is_value = |i: usize| i > 0 && ["--min", "--svg"].contains(&ours[i - 1].as_str());
+        let project = ours.iter().enumerate().find(|&(i, a)| !a.starts_with("--") && !is_value(i));
+        match project {
+            None => Err("usage: profile PROJECT [--svg FILE] [--min PERCENT] [-- ARGS..]".to_string()),
+            Some((_, project)) => (|| {
+                let file = project_file(project)?;
+                let name = file.file_stem().unwrap_or_default().to_string_lossy().replace('-', "_");
+                let (dir, binary) = build(&file, &name)?;
+                record(&dir, &binary, theirs, flag("--svg"))?;
+                Ok(fold_perf(&perf_script(&dir)?))
+            })(),
+        }
+    };
+
+    match result {
+        Ok(stacks) if stacks.is_empty() => eprintln!("profile: no samples; did the program run long enough?"),
+        Ok(stacks) => print!("{}", report(&stacks, min)),
+        Err(e) => {
+            eprintln!("profile: {e}");
+            std::process::exit(1);
+        }
+    }
+}
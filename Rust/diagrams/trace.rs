@@ -0,0 +1,331 @@
+// DIAGRAMS: A SNIPPET, ONE STEP AT A TIME --------------------------------
+
+/*
+ * Plays a snippet back step by step, like a debugger that only stops
+ * where the notes want it to. A step is a comment on the line it stops
+ * at, with the variables to show:
+ *
+ *     let tx1 = tx.clone();
+ *     thread::spawn(move || {
+ *         for val in vals {
+ *             tx1.send(val).unwrap();    // step 1: val
+ *         }
+ *     });
+ *     for received in rx {
+ *         println!("{received}");        // step 2: received
+ *     }
+ *
+ * A step stops *before* its line runs, as a debugger's arrow does, so
+ * `// step 1: val` shows the val about to be sent. It stops every time
+ * the line runs (in a loop, in every thread), and the steps are played
+ * back in the order they really happened, which with threads isn't the
+ * order of the numbers:
+ *
+ *      #  step  thread    line
+ *      1  1     thread 2    4  tx1.send(val).unwrap();     val = "some"
+ *      2  2     main        8  println!("{received}");     received = "some"
+ *
+ * The numbers are the order to read the steps in; when the program
+ * doesn't keep to it, that's the lesson.
+ *
+ * How: a call that records the step and the variables (with Debug, or
+ * the type's name for what has no Debug, like a closure) is put before
+ * each marked line; the snippet (the body of a main if it has none) is
+ * compiled and run. So the line has to start a statement, and the
+ * variables have to be usable there: asking for one that was moved is
+ * E0382, for one a live &mut closure holds is E0502, and rustc's error
+ * is shown, at the snippet's line. That's a lesson too.
+ *
+ * rustc trace.rs -o trace
+ * ./trace snippet.rs                  one step per Enter (q to stop)
+ * ./trace snippet.rs --list           all of them, as the table above
+ * (no file: the snippet is read from stdin, and --list is implied)
+ */
+
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Read};
+use std::process::Command;
+
+#[derive(Debug, PartialEq)]
+struct Step {
+    line: usize,                       // index into the snippet's lines
+    number: u32,
+    vars: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Step { number: u32, line: usize, thread: String, vars: Vec<(String, String)> },
+    Output(String),
+}
+
+// "tx1.send(val).unwrap();    // step 1: val, tx1" -> (1, [val, tx1])
+fn step_of(line: &str) -> Option<(u32, Vec<String>)> {
+    let (_, comment) = line.split_once("//")?;
+    let rest = comment.trim().strip_prefix("step ")?;
+    let (number, vars) = rest.split_once(':').unwrap_or((rest, ""));
+    let number = number.trim().parse().ok()?;
+    let vars = vars.split(',').map(str::trim).filter(|v| !v.is_empty()).map(str::to_string).collect();
+    Some((number, vars))
+}
+
+fn steps(lines: &[&str]) -> Vec<Step> {
+    (0..lines.len())
+        .filter_map(|i| step_of(lines[i]).map(|(number, vars)| Step { line: i, number, vars }))
+        .collect()
+}
+
+// written to stdout, so it lands in order with what the snippet prints
+const MARK: &str = "\u{1}step";
+
+// the recording call, and Debug-or-type-name by autoref specialization:
+// (&Show(&x)).show() picks ViaDebug when x: Debug, ViaType otherwise
+const PRELUDE: &str = r#"
+struct Show<'a, T: ?Sized>(&'a T);
+trait ViaDebug { fn show(&self) -> String; }
+impl<T: ?Sized + std::fmt::Debug> ViaDebug for Show<'_, T> {
+    fn show(&self) -> String { format!("{:?}", self.0) }
+}
+trait ViaType { fn show(&self) -> String; }
+impl<T: ?Sized> ViaType for &Show<'_, T> {
+    fn show(&self) -> String {
+        let name = std::any::type_name::<T>();
+        if name.contains("{{closure}}") { "<closure>".to_string() } else { format!("<{name}>") }
+    }
+}
+fn record_step(number: u32, line: usize, vars: &[(&str, String)]) {
+    let thread = std::thread::current();
+    let name = match thread.name() {
+        Some(name) => name.to_string(),
+        None => format!("{:?}", thread.id()).replace("ThreadId(", "thread ").replace(')', ""),
+    };
+    let mut out = format!("MARK\t{number}\t{line}\t{name}");
+    for (var, value) in vars {
+        out += &format!("\t{var}={}", value.replace(['\t', '\n'], " "));
+    }
+    println!("{out}");
+}
+"#;
+
+// the program, and for each of its lines the snippet line it came from
+// (None for what was added)
+fn instrument(lines: &[&str], steps: &[Step]) -> (String, Vec<Option<usize>>) {
+    let has_main = lines.iter().any(|l| l.contains("fn main("));
+    let mut out: Vec<String> = Vec::new();
+    let mut origin = Vec::new();
+    if !has_main {
+        out.push("fn main() {".to_string());
+        origin.push(None);
+    }
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(step) = steps.iter().find(|s| s.line == i) {
+            let indent: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+            let vars: Vec<String> = step.vars.iter().map(|v| format!("(\"{v}\", (&Show(&{v})).show())")).collect();
+            out.push(format!("{indent}record_step({}, {}, &[{}]);", step.number, i + 1, vars.join(", ")));
+            origin.push(Some(i));
+        }
+        out.push(line.to_string());
+        origin.push(Some(i));
+    }
+    if !has_main {
+        out.push("}".to_string());
+        origin.push(None);
+    }
+    let prelude = PRELUDE.replace("MARK", &MARK.escape_default().to_string());
+    (out.join("\n") + "\n" + &prelude, origin)
+}
+
+fn events(stdout: &str) -> Vec<Event> {
+    stdout
+        .lines()
+        .map(|line| {
+            let Some(rest) = line.strip_prefix(MARK) else { return Event::Output(line.to_string()) };
+            let fields: Vec<&str> = rest.trim_start_matches('\t').split('\t').collect();
+            let vars = fields.iter().skip(3).filter_map(|f| f.split_once('=')).map(|(k, v)| (k.to_string(), v.to_string()));
+            Event::Step {
+                number: fields[0].parse().unwrap_or(0),
+                line: fields.get(1).and_then(|l| l.parse().ok()).unwrap_or(0),
+                thread: fields.get(2).unwrap_or(&"").to_string(),
+                vars: vars.collect(),
+            }
+        })
+        .collect()
+}
+
+// compiles and runs the program; rustc's errors with the snippet's line
+// numbers if it doesn't compile
+fn run(program: &str, origin: &[Option<usize>]) -> Result<String, String> {
+    let dir = std::env::temp_dir().join(format!("trace-{}", std::process::id()));
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let result = (|| {
+        let file = dir.join("snippet.rs");
+        fs::write(&file, program).map_err(|e| e.to_string())?;
+        let output = Command::new("rustc")
+            .args(["--edition", "2021", "--error-format=short", "-A", "warnings", "-o"])
+            .arg(dir.join("snippet"))
+            .arg(&file)
+            .output()
+            .map_err(|e| format!("cannot run rustc: {e}"))?;
+        if !output.status.success() {
+            // "snippet.rs:12:5: error[E0382]: ..." -> "line 10: error[E0382]: ..."
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let errors: Vec<String> = stderr
+                .lines()
+                .filter_map(|l| {
+                    let (_, rest) = l.split_once("snippet.rs:")?;
+                    let (line, rest) = rest.split_once(':')?;
+                    let (_, message) = rest.split_once(": ")?;
+                    let line = origin.get(line.parse::<usize>().ok()? - 1).copied().flatten();
+                    Some(line.map_or(message.to_string(), |l| format!("line {}: {message}", l + 1)))
+                })
+                .collect();
+            return Err(format!("the snippet doesn't compile with these steps:\n  {}", errors.join("\n  ")));
+        }
+        let output = Command::new(dir.join("snippet")).output().map_err(|e| e.to_string())?;
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    })();
+    fs::remove_dir_all(&dir).ok();
+    result
+}
+
+// RENDERING --------------------------------------------------------------
+
+fn pad(s: &str, w: usize) -> String {
+    format!("{s}{}", " ".repeat(w.saturating_sub(s.chars().count())))
+}
+
+fn vars_text(vars: &[(String, String)]) -> String {
+    vars.iter().map(|(k, v)| format!("{k} = {v}")).collect::<Vec<_>>().join(", ")
+}
+
+fn table(lines: &[&str], events: &[Event]) -> String {
+    let code = |line: usize| lines.get(line.wrapping_sub(1)).map_or("", |l| l.split("//").next().unwrap_or_default().trim());
+    let code_width = events
+        .iter()
+        .filter_map(|e| match e {
+            Event::Step { line, .. } => Some(code(*line).chars().count()),
+            Event::Output(_) => None,
+        })
+        .max()
+        .unwrap_or(0);
+    let mut out = String::from(" #  step  thread     line\n");
+    let mut n = 0;
+    for event in events {
+        match event {
+            Event::Output(text) => out += &format!("                         > {text}\n"),
+            Event::Step { number, line, thread, vars } => {
+                n += 1;
+                let code = code(*line);
+                let text = format!("{n:>2}  {:<5} {} {line:>4}  {}  {}", number, pad(thread, 10), pad(code, code_width), vars_text(vars));
+                out += text.trim_end();
+                out += "\n";
+            }
+        }
+    }
+    out
+}
+
+// one step: the whole snippet with an arrow at the line, the variables,
+// and what the program has printed so far
+fn frame(lines: &[&str], events: &[Event], at: usize) -> String {
+    let Event::Step { number, line, thread, vars } = &events[at] else { return String::new() };
+    let total = events.iter().filter(|e| matches!(e, Event::Step { .. })).count();
+    let n = events[..=at].iter().filter(|e| matches!(e, Event::Step { .. })).count();
+    let mut out = format!("step {number}, {n} of {total}, {thread}\n\n");
+    for (i, text) in lines.iter().enumerate() {
+        let arrow = if i + 1 == *line { "▶" } else { " " };
+        out += &format!(" {arrow} {:>3}  {text}\n", i + 1);
+    }
+    out += "\n";
+    for (k, v) in vars {
+        out += &format!("   {k} = {v}\n");
+    }
+    let printed: Vec<&str> = events[..at]
+        .iter()
+        .filter_map(|e| match e {
+            Event::Output(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    if !printed.is_empty() {
+        out += &format!("\n   printed so far:\n     {}\n", printed.join("\n     "));
+    }
+    out
+}
+
+// MAIN -------------------------------------------------------------------
+
+const SAMPLE: &str = "let mut v = vec![1];
+let add = |x| x + 1;
+v.push(2);                 // step 1: v, add
+println!(\"{}\", v.len());   // step 2: v";
+
+fn self_check() {
+    assert_eq!(step_of("x; // step 3: a, b"), Some((3, vec!["a".to_string(), "b".to_string()])));
+    assert_eq!(step_of("x; // step 12"), Some((12, vec![])));
+    assert_eq!(step_of("x; // steps are cheap"), None);
+
+    let lines: Vec<&str> = SAMPLE.lines().collect();
+    let found = steps(&lines);
+    assert_eq!(found.iter().map(|s| (s.line, s.number)).collect::<Vec<_>>(), [(2, 1), (3, 2)]);
+
+    let (program, origin) = instrument(&lines, &found);
+    assert!(program.starts_with("fn main() {\nlet mut v = vec![1];\nlet add = |x| x + 1;\nrecord_step(1, 3, &[(\"v\", (&Show(&v)).show()), "));
+    // main's line, the two lines, the step, its line, the step, its line, the }
+    assert_eq!(origin[..8], [None, Some(0), Some(1), Some(2), Some(2), Some(3), Some(3), None]);
+
+    let stdout = format!("{MARK}\t1\t3\tmain\tv=[1]\tadd=<closure>\n2\n");
+    let parsed = events(&stdout);
+    assert_eq!(parsed[1], Event::Output("2".to_string()));
+    let Event::Step { number, line, thread, vars } = &parsed[0] else { panic!() };
+    assert_eq!((*number, *line, thread.as_str(), vars.len()), (1, 3, "main", 2));
+    assert!(table(&lines, &parsed).contains(" 1  1     main          3  v.push(2);  v = [1], add = <closure>\n"));
+}
+
+fn main() {
+    self_check();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let path = args.iter().find(|a| !a.starts_with("--"));
+    let snippet = match path {
+        Some(path) => fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("trace: {path}: {e}");
+            std::process::exit(1);
+        }),
+        None => {
+            let mut snippet = String::new();
+            io::stdin().read_to_string(&mut snippet).expect("stdin");
+            snippet
+        }
+    };
+    let lines: Vec<&str> = snippet.trim_matches('\n').lines().collect();
+    let steps = steps(&lines);
+    if steps.is_empty() {
+        eprintln!("trace: no `// step N: vars` comments in the snippet");
+        std::process::exit(1);
+    }
+
+    let (program, origin) = instrument(&lines, &steps);
+    let events = match run(&program, &origin) {
+        Ok(stdout) => events(&stdout),
+        Err(e) => {
+            eprintln!("trace: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    let interactive = path.is_some() && !args.iter().any(|a| a == "--list") && io::stdin().is_terminal();
+    if !interactive {
+        print!("{}", table(&lines, &events));
+        return;
+    }
+    let mut input = io::stdin().lock();
+    for at in (0..events.len()).filter(|&i| matches!(events[i], Event::Step { .. })) {
+        print!("\x1b[2J\x1b[H{}\n[Enter] next, q to stop ", frame(&lines, &events, at));
+        io::Write::flush(&mut io::stdout()).ok();
+        let mut answer = String::new();
+        if input.read_line(&mut answer).unwrap_or(0) == 0 || answer.trim() == "q" {
+            break;
+        }
+    }
+}
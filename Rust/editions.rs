@@ -0,0 +1,164 @@
+// ====================================================
+// EDITIONS ===========================================
+// ====================================================
+
+/*
+ * An edition is a set of small breaking changes to the language that a
+ * crate opts into, in Cargo.toml:
+ *     [package]
+ *     edition = "2021"             # 2015 if it's left out
+ *
+ * - the compiler is the same one for every edition; a new rustc still
+ *   compiles 2015 code, and always will
+ * - it's per crate: a 2024 crate can depend on a 2015 one and call it,
+ *   so nobody has to move at the same time
+ * - what changes is only what the surface syntax means: new keywords,
+ *   lints that become errors, a few rules about scopes and captures.
+ *   Anything that would split the ecosystem (the standard library, the
+ *   type system) stays the same across editions
+ *
+ * cargo new writes the latest one. The notes are written for 2021
+ * (quiz/src/notes.rs checks them as 2021).
+ */
+
+/*
+ * MIGRATING A CRATE
+ *
+ *     cargo fix --edition          rewrite the code so it means the same
+ *                                  in the NEXT edition (still compiled as
+ *                                  the current one)
+ *     edition = "..."              then raise it in Cargo.toml
+ *     cargo build && cargo test    and check
+ *
+ * one edition at a time: 2015 -> 2024 is three rounds of that. cargo fix
+ * applies what the edition's migration lints suggest; it doesn't touch
+ * code that's already fine, and it doesn't make the code idiomatic for
+ * the new edition (cargo fix --edition-idioms is a separate, optional
+ * pass).
+ *
+ * diagrams/migrate.rs does the rounds on one program and shows each
+ * diff, and what the program printed before and after:
+ *     rustc migrate.rs -o migrate && ./migrate
+ * Its built-in example is a 2015 program with something for every round;
+ * what it shows is below.
+ */
+
+/*
+ * 2015 -> 2018: MODULES AND KEYWORDS
+ *
+ *      4 -         fn scale(&mut self, f64);
+ *      4 +         fn scale(&mut self, _: f64);
+ *          ...
+ *     15 - use shapes::Shape;
+ *     15 + use crate::shapes::Shape;
+ *          ...
+ *     26 -     let async = 1;
+ *     26 +     let r#async = 1;
+ *          ...
+ *     28 -     ... Box::new(::shapes::Square(2.0))
+ *     28 +     ... Box::new(crate::shapes::Square(2.0))
+ *
+ * - paths: in 2015 a `use` path started at the crate root and ::shapes
+ *   meant the same; in 2018 they start from an external crate's name or
+ *   from crate::, self::, super::
+ * - async, await, dyn and try became keywords; a variable named async is
+ *   still possible as the raw identifier r#async
+ * - a trait method's parameters need names (or _)
+ */
+
+/*
+ * 2018 -> 2021: CLOSURES, ARRAYS, MACROS
+ *
+ *     17 - fn largest(shapes: &[Box<Shape>]) -> f64 {
+ *     17 + fn largest(shapes: &[Box<dyn Shape>]) -> f64 {
+ *          ...
+ *     22 -     ($p:pat | $q:pat) => { 1 };
+ *     22 +     ($p:pat_param | $q:pat) => { 1 };
+ *          ...
+ *     32 -     for x in array.into_iter() {
+ *     32 +     for x in array.iter() {
+ *          ...
+ *     38 -         let c = move || println!("{}", pair.0);
+ *     38 +         let c = move || { let _ = &pair; println!("{}", pair.0) };
+ *          ...
+ *     44 -         0...9 => println!("digit"),
+ *     44 +         0..=9 => println!("digit"),
+ *
+ * - a trait object is written dyn Trait; a bare Trait is an error
+ * - `...` in a range pattern is an error, it's ..=
+ * - array.into_iter() iterated by reference up to 2018 (arrays had no
+ *   IntoIterator of their own, so it auto-referenced to the slice's);
+ *   in 2021 it's by value. The fix keeps the 2018 meaning with .iter()
+ * - $p:pat matches `A | B` in 2021, so a macro that puts a | after it
+ *   gets pat_param, the old meaning
+ * - closures capture fields, not whole variables (disjoint captures):
+ *   the closure uses pair.0 only, so in 2021 it moves only pair.0 and
+ *   pair.1 is no longer dropped with the closure. Since Noisy prints when
+ *   it's dropped, that would change the output; `let _ = &pair;` makes
+ *   the closure use all of pair again, so it's dropped where it was
+ */
+
+let pair = (1, String::from("one"));
+let c = move || println!("{}", pair.0);  // 2021: moves pair.0 only (an i32, so a copy)
+c();
+println!("{}", pair.1);                  // so pair.1 is still here; in 2018, E0382
+
+/*
+ * 2021 -> 2024: KEYWORDS, UNSAFE, AND A CHANGE NO FIX COVERS
+ *
+ *     27 -     let gen = 2;
+ *     27 +     let r#gen = 2;
+ *          ...
+ *     76 - extern "C" {
+ *     76 + unsafe extern "C" {
+ *
+ * - gen is reserved (for generators)
+ * - an extern block says unsafe, since declaring a C function with the
+ *   wrong signature is already a promise (see unsafe_rust.rs)
+ *
+ * and then the output:
+ *
+ *     output under 2021 (-) and 2024 (+):
+ *          ...
+ *          9 - panicked: RefCell already borrowed
+ *          9 + 1 3
+ *
+ * from this, which cargo fix left alone:
+ *
+ *     let cell = std::cell::RefCell::new(Vec::new());
+ *     if let Some(x) = cell.borrow().first() {
+ *         println!("{}", x);
+ *     } else {
+ *         cell.borrow_mut().push(1);
+ *     }
+ *
+ * A RefCell checks borrows at run time: cell.borrow() makes a temporary
+ * Ref, a guard that keeps the cell borrowed until it's dropped, and
+ * borrow_mut() while one is alive panics. Up to 2021, a temporary in an
+ * if let's condition lives until the end of the whole if let, else
+ * included, so borrow_mut() in the else finds the cell still borrowed.
+ * In 2024 it's dropped before the else runs. The same with a Mutex is a
+ * deadlock in 2021.
+ *
+ * There's a migration lint for it (if_let_rescope), but it didn't fire
+ * on this one, so the fix left the code as it was and 2024 changed what
+ * it does, here for the better. That's why the recipe ends with the
+ * tests: cargo fix is "the lints have nothing more to say", not "the
+ * program does the same".
+ */
+
+/*
+ * Other 2024 changes the example doesn't have:
+ * - a temporary in a block's tail expression is dropped before the
+ *   block's locals, not after
+ * - impl Trait in a return type captures all the lifetimes in scope;
+ *   + use<'a> says which
+ * - unsafe_op_in_unsafe_fn warns: an unsafe fn's body needs unsafe { }
+ *   blocks of its own around the unsafe parts
+ * - references to a static mut (&COUNT) are an error; COUNT += 1 isn't
+ *
+ * Exercise: ./migrate --example > old.rs, rewrite the if let in old.rs
+ * as a match on cell.borrow().first(), and ./migrate old.rs again: does
+ * it still panic in 2024? (A match's temporaries live to its end in
+ * every edition.)
+ */
@@ -0,0 +1,261 @@
+// ====================================================
+// ENUMS IN DEPTH =====================================
+// ====================================================
+
+/*
+ * The basics are in [[enums_pattern_matching#enums]], and a vector of
+ * them in [[collections#using an enum to store multiple types]]. This is
+ * the rest: what a variant can carry, the numbers behind the variants,
+ * enums that promise to grow, matching without the noise, and what an
+ * enum costs in memory.
+ *
+ * Exercises: quiz/packs/enums.toml (quiz practice enums).
+ */
+
+// DATA IN VARIANTS -------------------------------------------------------
+
+// A variant is unit, tuple or struct-like, and they mix in one enum.
+// The enum is one type: a function takes any of its variants.
+
+#[derive(Debug)]
+enum Shape {
+    Point,                                  // unit
+    Circle(f64),                            // tuple: the radius
+    Rect { w: f64, h: f64 },                // struct-like
+}
+
+fn area(shape: &Shape) -> f64 {
+    match shape {
+        Shape::Point => 0.0,
+        Shape::Circle(r) => 3.0 * r * r,    // close enough
+        Shape::Rect { w, h } => w * h,
+    }
+}
+
+let shapes = [Shape::Point, Shape::Circle(1.0), Shape::Rect { w: 2.0, h: 3.0 }];
+for s in &shapes {
+    println!("{}", area(s));                // prints: 0 3 6
+}
+
+// A tuple variant's name is a function too: it can be passed to map.
+
+let circles: Vec<Shape> = vec![1.0, 2.0].into_iter().map(Shape::Circle).collect();
+println!("{circles:?}");                    // prints: [Circle(1.0), Circle(2.0)]
+
+// An enum can hold itself only behind a pointer: the size must be known.
+
+#[derive(Debug)]
+enum List {
+    Cons(i32, Box<List>),
+    Nil,
+}
+
+let list = List::Cons(1, Box::new(List::Cons(2, Box::new(List::Nil))));
+println!("{list:?}");                       // prints: Cons(1, Cons(2, Nil))
+
+// DISCRIMINANTS ----------------------------------------------------------
+
+/*
+ * Every variant has a number, the discriminant, which is how the enum
+ * tells its variants apart. For an enum of unit variants only (a
+ * "C-like" enum) it can be set, and read with `as`; the ones not set
+ * count on from the one before, starting at 0.
+ */
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Status {
+    Created = 201,
+    NotFound = 404,
+    Teapot = 418,
+}
+
+enum Weekday {
+    Mon = 1,
+    Tue,                                    // 2
+    Wed,                                    // 3
+}
+
+println!("{} {}", Status::NotFound as u16, Weekday::Wed as u8);   // prints: 404 3
+
+// The other way isn't `as`: a number may be no variant at all.
+// A TryFrom (or a match) says which are.
+
+impl TryFrom<u16> for Status {
+    type Error = u16;
+    fn try_from(code: u16) -> Result<Status, u16> {
+        match code {
+            201 => Ok(Status::Created),
+            404 => Ok(Status::NotFound),
+            418 => Ok(Status::Teapot),
+            other => Err(other),
+        }
+    }
+}
+
+println!("{:?} {:?}", Status::try_from(418), Status::try_from(500));  // prints: Ok(Teapot) Err(500)
+
+/*
+ * #[repr(u8)] (or u16, i32, ..) fixes the type the discriminant is
+ * stored as: for matching a C enum over FFI, or a byte in a file format.
+ * Without it the compiler picks, and only `as` is promised to work.
+ * With a repr, variants with data can have discriminants too (Rust
+ * 1.66), though `as` then works on none of them.
+ */
+
+#[repr(u8)]
+enum Opcode {
+    Nop = 0x00,
+    Jump = 0xEB,
+}
+
+println!("{:#x} {}", Opcode::Jump as u8, std::mem::size_of::<Opcode>());   // prints: 0xeb 1
+
+// NON-EXHAUSTIVE ENUMS ---------------------------------------------------
+
+/*
+ * A library that will add variants later marks the enum
+ * #[non_exhaustive]; outside the crate, every match on it then needs a
+ * `_` arm, so adding a variant doesn't break anyone's code:
+ *
+ *     // in the library
+ *     #[non_exhaustive]
+ *     pub enum Error { NotFound, PermissionDenied }
+ *
+ *     // in a crate that uses it
+ *     match e {
+ *         Error::NotFound => ..,
+ *         Error::PermissionDenied => ..,
+ *         _ => ..,                          // without it: E0004
+ *     }
+ *
+ * Inside the defining crate it changes nothing, so the snippet below
+ * compiles without the `_`. std::io::ErrorKind is one: a match on it
+ * always needs the `_`. On a struct, #[non_exhaustive] stops other crates
+ * from building it with a literal, or matching it without `..`.
+ */
+
+#[non_exhaustive]
+enum Level {
+    Low,
+    High,
+}
+
+fn describe(level: Level) -> &'static str {
+    match level {
+        Level::Low => "low",
+        Level::High => "high",
+    }
+}
+
+println!("{}", describe(Level::High));     // prints: high
+
+// A match that misses a variant doesn't compile, in any crate:
+
+enum Light {
+    Red,
+    Amber,
+    Green,
+}
+
+fn next(light: Light) -> Light {
+    match light {                           // compile error: E0004
+        Light::Red => Light::Green,
+        Light::Green => Light::Amber,
+    }
+}
+
+// MATCHING ERGONOMICS ----------------------------------------------------
+
+/*
+ * Matching a reference against a pattern without one (Rust 2018's
+ * "default binding modes"): the & is taken care of, and the bindings
+ * become references. No `ref`, no `&Shape::Circle(r)`.
+ */
+
+#[derive(Debug)]
+enum Shape {
+    Circle(f64),
+    Rect { w: f64, h: f64 },
+}
+
+fn radius(shape: &Shape) -> Option<f64> {
+    match shape {                           // &Shape against Shape::..
+        Shape::Circle(r) => Some(*r),       // r: &f64
+        _ => None,
+    }
+}
+
+println!("{:?}", radius(&Shape::Circle(2.5)));   // prints: Some(2.5)
+
+// Through &mut the bindings are &mut: the data can be changed in place.
+
+let mut shape = Shape::Rect { w: 1.0, h: 1.0 };
+if let Shape::Rect { w, .. } = &mut shape {
+    *w *= 4.0;                              // w: &mut f64
+}
+println!("{shape:?}");                      // prints: Rect { w: 4.0, h: 1.0 }
+
+// Variants with the same data share an arm with |; `..` skips the
+// fields that don't matter, and @ binds a value while testing it.
+
+enum Event {
+    Key(char),
+    Click { x: i32, y: i32 },
+    Scroll { x: i32, y: i32 },
+}
+
+fn place(event: &Event) -> String {
+    match event {
+        Event::Click { x, .. } | Event::Scroll { x, .. } if *x < 0 => "off screen".to_string(),
+        Event::Click { x, y } | Event::Scroll { x, y } => format!("{x},{y}"),
+        Event::Key(c @ 'a'..='z') => format!("letter {c}"),
+        Event::Key(_) => "key".to_string(),
+    }
+}
+
+println!("{} / {} / {}", place(&Event::Scroll { x: -1, y: 0 }), place(&Event::Click { x: 3, y: 4 }), place(&Event::Key('q')));
+// prints: off screen / 3,4 / letter q
+
+// let-else: the variant wanted, or leave. matches! for just a yes or no.
+
+fn key(event: &Event) -> char {
+    let Event::Key(c) = event else { return '?' };
+    *c
+}
+
+println!("{} {}", key(&Event::Key('x')), matches!(Event::Key('x'), Event::Key('a'..='z')));   // prints: x true
+
+// SIZE -------------------------------------------------------------------
+
+/*
+ * An enum is as big as its biggest variant, plus the discriminant, plus
+ * padding to the alignment. One big variant makes every value big, the
+ * small ones too: a Vec of them pays for the biggest on every element.
+ *
+ *     enum Packet { Ping, Data([u8; 1024]) }   1025 bytes, a Ping too
+ *
+ * (sizes on a 64-bit target, as below)
+ *
+ * The tag often costs nothing: when a variant's data has values it can
+ * never take (a reference is never null, a bool is only 0 or 1, a char
+ * is never above 0x10FFFF), the compiler keeps the other variants in
+ * those "niches". That's why Option<&T> and Option<Box<T>> are the size
+ * of a pointer: more in [[option_layout#niches]].
+ */
+
+use std::mem::size_of;
+
+enum Small { A(u8), B(u16) }
+enum Big { A(u8), B([u8; 100]) }
+
+println!("{} {}", size_of::<Small>(), size_of::<Big>());   // prints: 4 101
+println!("{} {}", size_of::<&u64>(), size_of::<Option<&u64>>());   // prints: 8 8
+println!("{} {}", size_of::<bool>(), size_of::<Option<Option<bool>>>());   // prints: 1 1
+
+// The big variant boxed: every value is a pointer's size and a tag, and
+// only the big ones allocate. clippy's large_enum_variant lint says
+// when it's worth it.
+
+enum Boxed { A(u8), B(Box<[u8; 100]>) }
+
+println!("{}", size_of::<Boxed>());        // prints: 16
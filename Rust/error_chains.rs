@@ -0,0 +1,209 @@
+// ====================================================
+// ERROR CHAINS AND CONTEXT ===========================
+// ====================================================
+
+/*
+ * Box<dyn Error> ([[error_handling#recoverable errors with result]]) gets
+ * any error up to main. What it gets there is often "No such file or
+ * directory (os error 2)": true, but which file, and what was being done
+ * with it? A real application's error says each: what was being done,
+ * what went wrong in doing it, and so on down to what the OS said.
+ *
+ *     error: cannot start the server
+ *     caused by:
+ *         0: cannot load the config from /etc/app.toml
+ *         1: No such file or directory (os error 2)
+ *
+ * Each of those is an error of its own, wrapping the one below; each
+ * says only its own part, and hands on the one it wraps as its source().
+ */
+
+// A LAYERED ERROR --------------------------------------------------------
+
+/*
+ * The convention (the Rust API guidelines, and std's own errors): an
+ * error's Display is its own message, without the source's; source()
+ * returns the error it wraps. Whoever prints the error decides how much
+ * of the chain to show.
+ */
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+struct ConfigError {
+    path: String,
+    source: io::Error,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot load the config from {}", self.path)
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+fn load(path: &str) -> Result<String, ConfigError> {
+    std::fs::read_to_string(path).map_err(|source| ConfigError { path: path.to_string(), source })
+}
+
+let e = load("/no/such/app.toml").unwrap_err();
+println!("{e}");                            // prints: cannot load the config from /no/such/app.toml
+println!("{}", e.source().unwrap());        // prints: No such file or directory (os error 2)
+
+// Put the source's message in the Display too, and whoever prints the
+// chain prints it twice:
+//
+//     write!(f, "cannot load the config from {}: {}", self.path, self.source)
+//
+//     cannot load the config from /no/such/app.toml: No such file or directory (os error 2)
+//     caused by: No such file or directory (os error 2)
+
+// CONTEXT AT EACH LEVEL --------------------------------------------------
+
+/*
+ * A struct per layer is what a library does: its callers can match on
+ * the kinds. An application mostly wants to add a sentence and move on.
+ * One error type that's a message and a boxed source does, with an
+ * extension trait to add it on any Result, the way anyhow's .context()
+ * does:
+ *
+ *     load(path).context("cannot start the server")?
+ */
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+struct Context {
+    message: String,
+    source: Box<dyn Error + Send + Sync>,
+}
+
+impl fmt::Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for Context {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+trait WithContext<T> {
+    fn context(self, message: &str) -> Result<T, Context>;
+    fn with_context(self, message: impl FnOnce() -> String) -> Result<T, Context>;
+}
+
+impl<T, E: Error + Send + Sync + 'static> WithContext<T> for Result<T, E> {
+    fn context(self, message: &str) -> Result<T, Context> {
+        self.with_context(|| message.to_string())
+    }
+    fn with_context(self, message: impl FnOnce() -> String) -> Result<T, Context> {
+        self.map_err(|e| Context { message: message(), source: Box::new(e) })
+    }
+}
+
+// Each level says what it was doing; with_context builds the message
+// only on an error, for one that costs a format! to make.
+
+fn port(text: &str) -> Result<u16, Context> {
+    let line = text.lines().find(|l| l.starts_with("port")).unwrap_or("port = ");
+    let value = line.trim_start_matches("port").trim_start_matches([' ', '=']);
+    value.parse::<u16>().with_context(|| format!("invalid port `{value}`"))
+}
+
+fn start(config: &str) -> Result<u16, Context> {
+    port(config).context("cannot start the server")
+}
+
+let e = start("name = \"app\"\nport = 80a").unwrap_err();
+println!("{e} / {}", e.source().unwrap());   // prints: cannot start the server / invalid port `80a`
+
+// A Context is an Error + Send + Sync + 'static itself, so .context()
+// works on a Result<_, Context> too: that's how the chain grows a level
+// per function. What it can't be is matched on: a caller wanting to
+// know "was it the port?" needs downcast_ref on a source, or a library's
+// enum of kinds instead.
+
+// PRINTING THE CHAIN -----------------------------------------------------
+
+// source() is a linked list: std::iter::successors walks it, from the
+// error down to the one at the bottom. The first is the headline, the
+// rest are numbered below it (the way anyhow prints an error with {:?}).
+
+use std::fmt::Write;
+
+fn report(error: &(dyn Error + 'static)) -> String {
+    let mut out = format!("error: {error}");
+    let causes: Vec<&dyn Error> = std::iter::successors(error.source(), |&e| e.source()).collect();
+    if !causes.is_empty() {
+        out += "\ncaused by:";
+    }
+    for (i, cause) in causes.iter().enumerate() {
+        let _ = write!(out, "\n    {i}: {cause}");
+    }
+    out
+}
+
+println!("{}", report(&start("port = 80a").unwrap_err()));
+// prints: error: cannot start the server
+// prints: caused by:
+// prints:     0: invalid port `80a`
+// prints:     1: invalid digit found in string
+
+let not_a_number = "80a".parse::<u16>().unwrap_err();
+println!("{}", report(&not_a_number));      // prints: error: invalid digit found in string
+
+// BACKTRACES -------------------------------------------------------------
+
+/*
+ * A chain says what was being done; a backtrace says where in the code.
+ * std::backtrace::Backtrace::capture() records the call stack, if
+ * RUST_BACKTRACE or RUST_LIB_BACKTRACE is 1 (else it's Disabled, and
+ * costs nothing); force_capture() always does. Captured where the error
+ * is made, at the bottom of the chain, and kept in it:
+ */
+
+use std::backtrace::{Backtrace, BacktraceStatus};
+
+#[derive(Debug)]
+struct Failure {
+    message: String,
+    backtrace: Backtrace,
+}
+
+fn fail(message: &str) -> Failure {
+    Failure { message: message.to_string(), backtrace: Backtrace::force_capture() }
+}
+
+let failure = fail("disk full");
+println!("{} {}", failure.message, failure.backtrace.status() == BacktraceStatus::Captured);   // prints: disk full true
+
+/*
+ * Printing it (with {}) lists the frames, from capture()
+ * up to main, with file and line when the build has debug info. report()
+ * can print one after the chain when the headline error has it: the
+ * error types in a chain are dyn Error, so it has to be looked for with
+ * downcast_ref::<Failure>(), or the error type keeps it at the top. An
+ * error from anyhow captures one for itself, and {:?} prints it after
+ * the chain.
+ *
+ * For a real application, the anyhow crate is the Context above (and
+ * the report, and the backtrace), and thiserror the ConfigError one:
+ *
+ *     #[derive(Debug, thiserror::Error)]
+ *     #[error("cannot load the config from {path}")]
+ *     struct ConfigError { path: String, #[source] source: io::Error }
+ *
+ * thiserror for a library's errors, that callers match on; anyhow for
+ * an application's, that are reported.
+ */
@@ -215,6 +215,19 @@ fn read_username_from_file() -> Result<String, io::Error> {
  * into that String, and returns it.
  */
 
+// Every version above opens "esabi.txt" in whatever directory the
+// program runs in, and so would its tests, all of them the same file.
+// Taking the path lets each test hand it a file of its own, in a
+// temporary directory: [[temp_files#take the path]]
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+fn read_username(path: impl AsRef<Path>) -> Result<String, io::Error> {
+    fs::read_to_string(path)
+}
+
 // where the ? operator can be used . . .
 
 /*
@@ -290,6 +303,9 @@ fn main() -> Result<(), Box<dyn Error>> {
  * ever return errors of type std::io::Error, by specifying
  * Box<dyn Error>, this signature will continue to be correct even if
  * more code that returns other errors is added to the body of main.
+ *
+ * What an error that reaches main should say, and how each function on
+ * the way adds to it: [[error_chains#context at each level]]
  */
 
 /*
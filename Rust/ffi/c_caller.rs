@@ -0,0 +1,64 @@
+// FFI: TESTING FROM C ----------------------------------------------------
+
+/*
+ * An integration test (tests/c_caller.rs) that treats the library the
+ * way a C user would: compile caller.c against wordstats.h, link it to
+ * the cdylib cargo just built, run it, and compare its output.
+ *
+ * cargo test builds the library with all its crate types before running
+ * tests, so libwordstats.so exists by the time this runs: in
+ * target/debug/deps/, next to the test binary itself.
+ * The C compiler comes from $CC, default "cc"; the test is skipped with
+ * a message if there isn't one.
+ */
+
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+fn c_program_links_and_runs() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let exe = env::current_exe().unwrap();
+    let lib_dir = exe.parent().unwrap();
+    let out = lib_dir.join("c_caller");
+
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let compiled = Command::new(&cc)
+        .arg(root.join("caller.c"))
+        .arg("-I")
+        .arg(&root)
+        .arg("-L")
+        .arg(lib_dir)
+        .arg("-lwordstats")
+        // rpath: tell the program where to find the .so at run time, so no
+        // LD_LIBRARY_PATH is needed
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-o")
+        .arg(&out)
+        .output();
+    let compiled = match compiled {
+        Ok(output) => output,
+        Err(_) => {
+            eprintln!("skipping: no C compiler ({cc})");
+            return;
+        }
+    };
+    assert!(compiled.status.success(), "C build failed:\n{}", String::from_utf8_lossy(&compiled.stderr));
+
+    let run = Command::new(&out).output().unwrap();
+    assert!(run.status.success());
+    let stdout = String::from_utf8(run.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "version 1\n\
+         words 4\n\
+         stats ok 14 3 2\n\
+         null null argument\n\
+         count it 2\n\
+         top it\n\
+         average 3.00 -1.00\n"
+    );
+}
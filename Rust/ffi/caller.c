@@ -0,0 +1,41 @@
+/* FFI: THE C SIDE ------------------------------------------------------
+ *
+ * A C program using the Rust library through wordstats.h. Nothing here
+ * knows the library is written in Rust.
+ *
+ * Built and run by c_caller.rs; by hand, from the crate root:
+ *     cargo build
+ *     cc caller.c -I. -Ltarget/debug -lwordstats -o caller
+ *     LD_LIBRARY_PATH=target/debug ./caller
+ */
+
+#include <stdio.h>
+#include "wordstats.h"
+
+int main(void) {
+    printf("version %u\n", ws_version());
+    printf("words %lld\n", (long long)ws_count_words("the quick brown fox"));
+
+    /* struct out-parameter plus status code */
+    WsStats stats;
+    WsStatus status = ws_stats("one two\nthree\n", &stats);
+    printf("stats %s %llu %llu %llu\n", ws_status_message(status),
+           (unsigned long long)stats.bytes, (unsigned long long)stats.words,
+           (unsigned long long)stats.lines);
+    printf("null %s\n", ws_status_message(ws_stats(NULL, &stats)));
+
+    /* opaque handle: new, use, free */
+    WsCounter *counter = ws_counter_new();
+    ws_counter_feed(counter, "It was the best of times");
+    ws_counter_feed(counter, "it was the worst of times");
+    printf("count it %u\n", ws_counter_get(counter, "it"));
+
+    char *top = ws_counter_top(counter);    /* Rust-allocated: Rust frees */
+    printf("top %s\n", top);
+    ws_string_free(top);
+    ws_counter_free(counter);
+
+    /* a panic inside the library comes back as an error value */
+    printf("average %.2f %.2f\n", ws_average_word_length("ab abcd"), ws_average_word_length(""));
+    return 0;
+}
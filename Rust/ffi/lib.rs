@@ -0,0 +1,313 @@
+// FFI: CALLING RUST FROM C -----------------------------------------------
+
+/*
+ * The usual FFI direction is Rust calling an existing C library. This is
+ * the other direction: a Rust library that C (or anything that speaks
+ * the C ABI: Python's ctypes, Go's cgo, Swift, ...) can link against.
+ *
+ * The pieces, all in this directory:
+ *     lib.rs        the Rust side: exported functions (this file)
+ *     build.rs      runs cbindgen to write the C header
+ *     wordstats.h   the generated header, checked in so C users don't
+ *                   need Rust installed to read the API
+ *     caller.c      a C program that uses the library
+ *     c_caller.rs   an integration test: compiles caller.c with the
+ *                   system C compiler, links it against the library, runs
+ *                   it and checks what it prints
+ *
+ * cargo new --lib wordstats
+ * (copy lib.rs and build.rs into src/lib.rs and build.rs, c_caller.rs
+ *  into tests/, wordstats.h and caller.c next to Cargo.toml)
+ *
+ * Cargo.toml:
+ * [lib]
+ * crate-type = ["cdylib", "rlib"]
+ *
+ * [build-dependencies]
+ * cbindgen = "0.29"
+ *
+ * cargo test                 builds libwordstats.so, then the C caller
+ *
+ * crate-type: "cdylib" is a shared library for non-Rust callers
+ * (libwordstats.so / .dylib / wordstats.dll), with only the exported
+ * symbols visible. "staticlib" (libwordstats.a) also works and needs no
+ * library path at run time. "rlib" is the normal Rust library, kept so
+ * Rust code and tests can still use the crate.
+ */
+
+use std::collections::HashMap;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+// EXPORTING A FUNCTION ---------------------------------------------------
+
+/*
+ * Two things make a function callable from C:
+ *     extern "C"     use the C calling convention, not Rust's (which is
+ *                    unspecified and may change between compiler versions)
+ *     no_mangle      keep the symbol name as written. Rust normally
+ *                    mangles names (_ZN9wordstats3add17h..E) so that
+ *                    different crates can have a function `add`
+ *
+ * `#[no_mangle]` is written `#[unsafe(no_mangle)]` since edition 2024:
+ * two crates exporting the same name is undefined behaviour at link
+ * time, and the compiler can't check it. The new spelling works in
+ * edition 2021 as well.
+ *
+ * Only C-compatible types may cross: integers, floats, raw pointers,
+ * and #[repr(C)] structs and enums. No String, Vec, &str, Option<String>
+ * or trait objects; they have no layout C knows about.
+ */
+
+/// Library version, to check that the header and the .so match.
+#[unsafe(no_mangle)]
+pub extern "C" fn ws_version() -> u32 {
+    1
+}
+
+// STRINGS IN -------------------------------------------------------------
+
+/*
+ * A C string is a pointer to bytes ending in NUL. Rust can't know the
+ * pointer is valid, so reading it is unsafe, and the function that
+ * does it is `unsafe extern "C"`: the caller promises what the
+ * `# Safety` section says. CStr::from_ptr finds the NUL; to_str()
+ * checks the bytes are UTF-8 (C doesn't promise that either).
+ */
+
+/// Counts whitespace-separated words. Returns -1 if `text` is NULL or
+/// not valid UTF-8.
+///
+/// # Safety
+/// `text` must be NULL or point to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ws_count_words(text: *const c_char) -> i64 {
+    match unsafe { text_arg(text) } {
+        Some(text) => text.split_whitespace().count() as i64,
+        None => -1,
+    }
+}
+
+// every function taking a string goes through this
+unsafe fn text_arg<'a>(text: *const c_char) -> Option<&'a str> {
+    if text.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(text) }.to_str().ok()
+}
+
+// STRUCTS AND OUT-PARAMETERS ---------------------------------------------
+
+/*
+ * #[repr(C)] lays the fields out in order with C's padding rules, so
+ * the header's `struct WsStats` is the same bytes. Without it, Rust may
+ * reorder fields.
+ *
+ * C functions usually return a status code and write the result through
+ * a pointer, since there's no Result to return. Zero is success, as in
+ * most C APIs.
+ */
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct WsStats {
+    pub bytes: u64,
+    pub words: u64,
+    pub lines: u64,
+}
+
+/// Status codes returned by the ws_* functions.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WsStatus {
+    Ok = 0,
+    NullArgument = 1,
+    InvalidUtf8 = 2,
+}
+
+/// Fills `out` with byte, word and line counts for `text`.
+///
+/// # Safety
+/// `text` must be NULL or a NUL-terminated string; `out` must be NULL or
+/// point to a writable WsStats.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ws_stats(text: *const c_char, out: *mut WsStats) -> WsStatus {
+    if text.is_null() || out.is_null() {
+        return WsStatus::NullArgument;
+    }
+    let Some(text) = (unsafe { text_arg(text) }) else {
+        return WsStatus::InvalidUtf8;
+    };
+    let stats = WsStats {
+        bytes: text.len() as u64,
+        words: text.split_whitespace().count() as u64,
+        lines: text.lines().count() as u64,
+    };
+    unsafe { out.write(stats) };
+    WsStatus::Ok
+}
+
+// OPAQUE HANDLES ---------------------------------------------------------
+
+/*
+ * Rust types with Rust fields (a HashMap here) can't be shown to C. The
+ * pattern is an opaque handle: C only ever holds a pointer, declared in
+ * the header as an incomplete `typedef struct WsCounter WsCounter;`.
+ *
+ *     new      Box::new(..) then Box::into_raw: Rust stops owning it,
+ *              the pointer is C's now
+ *     methods  &*ptr / &mut *ptr: borrow through the pointer
+ *     free     Box::from_raw: Rust owns it again, and drops it
+ *
+ * The allocation must be freed by the library that made it. C's free()
+ * on a Box pointer is undefined behaviour (different allocator, maybe),
+ * so every _new has a matching _free.
+ */
+
+pub struct WsCounter {
+    counts: HashMap<String, u32>,
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ws_counter_new() -> *mut WsCounter {
+    Box::into_raw(Box::new(WsCounter { counts: HashMap::new() }))
+}
+
+/// Adds every word in `text` to the counter.
+///
+/// # Safety
+/// `counter` must come from ws_counter_new and not be freed yet; `text`
+/// must be NULL or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ws_counter_feed(counter: *mut WsCounter, text: *const c_char) -> WsStatus {
+    let Some(counter) = (unsafe { counter.as_mut() }) else {
+        return WsStatus::NullArgument;
+    };
+    let Some(text) = (unsafe { text_arg(text) }) else {
+        return if text.is_null() { WsStatus::NullArgument } else { WsStatus::InvalidUtf8 };
+    };
+    for word in text.split_whitespace() {
+        *counter.counts.entry(word.to_lowercase()).or_insert(0) += 1;
+    }
+    WsStatus::Ok
+}
+
+/// How often `word` has been seen (case-insensitive).
+///
+/// # Safety
+/// As ws_counter_feed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ws_counter_get(counter: *const WsCounter, word: *const c_char) -> u32 {
+    match unsafe { (counter.as_ref(), text_arg(word)) } {
+        (Some(counter), Some(word)) => counter.counts.get(&word.to_lowercase()).copied().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// Frees the counter. NULL is allowed and does nothing, like free().
+///
+/// # Safety
+/// `counter` must come from ws_counter_new and not be freed already.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ws_counter_free(counter: *mut WsCounter) {
+    if !counter.is_null() {
+        drop(unsafe { Box::from_raw(counter) });
+    }
+}
+
+// STRINGS OUT ------------------------------------------------------------
+
+/*
+ * Returning a string: CString::into_raw hands out a NUL-terminated
+ * buffer that Rust allocated, so it needs a Rust free function, same as
+ * the handle. The alternative, common in C APIs, is for the caller to
+ * pass in a buffer and its size (snprintf style); then nothing needs
+ * freeing, but the caller has to guess the size.
+ */
+
+/// The most frequent word (ties: alphabetically first), or NULL if the
+/// counter is empty. Free the result with ws_string_free.
+///
+/// # Safety
+/// As ws_counter_feed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ws_counter_top(counter: *const WsCounter) -> *mut c_char {
+    let Some(counter) = (unsafe { counter.as_ref() }) else {
+        return ptr::null_mut();
+    };
+    counter
+        .counts
+        .iter()
+        .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+        // split_whitespace never yields a word containing NUL, so new() can't fail
+        .map_or(ptr::null_mut(), |(word, _)| CString::new(word.as_str()).unwrap().into_raw())
+}
+
+/// Frees a string returned by this library. NULL is allowed.
+///
+/// # Safety
+/// `s` must come from a ws_* function and not be freed already.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ws_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+// PANICS AT THE BOUNDARY -------------------------------------------------
+
+/*
+ * A panic must not unwind into C: C frames have no unwinding
+ * information. Since Rust 1.81 a panic escaping an extern "C" function
+ * aborts the process, which is safe but takes the C program down with
+ * it. To turn a panic into an error code instead, catch it:
+ */
+
+/// Average word length in `text`, or -1 on error.
+///
+/// # Safety
+/// `text` must be NULL or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ws_average_word_length(text: *const c_char) -> f64 {
+    let Some(text) = (unsafe { text_arg(text) }) else {
+        return -1.0;
+    };
+    // AssertUnwindSafe: we promise nothing is left half-modified if the
+    // closure panics (it only reads)
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        assert!(!words.is_empty(), "no words");
+        words.iter().map(|w| w.chars().count()).sum::<usize>() as f64 / words.len() as f64
+    }))
+    .unwrap_or(-1.0)
+}
+
+/*
+ * The default panic hook still prints "thread panicked at .." to stderr
+ * before catch_unwind returns. A library that must stay quiet can
+ * install its own hook, or avoid the panic (here: check is_empty() and
+ * return early, which is what real code should do; the assert is only
+ * there to have something to catch).
+ *
+ * Things not to do at the boundary:
+ *     return &str or String      C gets a pointer without a length or
+ *                                a NUL, and memory it can't free
+ *     take a Rust enum with data  no C layout (unless #[repr(C)], and
+ *                                then it is a tagged union in the header)
+ *     bool in old C code          fine in C99 (stdbool.h), but check the
+ *                                caller's compiler
+ */
+
+/// Converts a status code to a short English message. The returned
+/// pointer is static; do not free it.
+#[unsafe(no_mangle)]
+pub extern "C" fn ws_status_message(status: c_int) -> *const c_char {
+    let message: &'static CStr = match status {
+        0 => c"ok",
+        1 => c"null argument",
+        2 => c"invalid UTF-8",
+        _ => c"unknown status",
+    };
+    message.as_ptr()
+}
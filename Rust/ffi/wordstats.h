@@ -0,0 +1,117 @@
+#ifndef WORDSTATS_H
+#define WORDSTATS_H
+
+/* Generated by cbindgen from src/lib.rs. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Status codes returned by the ws_* functions.
+ */
+typedef enum WsStatus {
+  WsStatus_Ok = 0,
+  WsStatus_NullArgument = 1,
+  WsStatus_InvalidUtf8 = 2,
+} WsStatus;
+
+typedef struct WsCounter WsCounter;
+
+typedef struct WsStats {
+  uint64_t bytes;
+  uint64_t words;
+  uint64_t lines;
+} WsStats;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Library version, to check that the header and the .so match.
+ */
+uint32_t ws_version(void);
+
+/**
+ * Counts whitespace-separated words. Returns -1 if `text` is NULL or
+ * not valid UTF-8.
+ *
+ * # Safety
+ * `text` must be NULL or point to a NUL-terminated string.
+ */
+int64_t ws_count_words(const char *text);
+
+/**
+ * Fills `out` with byte, word and line counts for `text`.
+ *
+ * # Safety
+ * `text` must be NULL or a NUL-terminated string; `out` must be NULL or
+ * point to a writable WsStats.
+ */
+enum WsStatus ws_stats(const char *text, struct WsStats *out);
+
+struct WsCounter *ws_counter_new(void);
+
+/**
+ * Adds every word in `text` to the counter.
+ *
+ * # Safety
+ * `counter` must come from ws_counter_new and not be freed yet; `text`
+ * must be NULL or a NUL-terminated string.
+ */
+enum WsStatus ws_counter_feed(struct WsCounter *counter, const char *text);
+
+/**
+ * How often `word` has been seen (case-insensitive).
+ *
+ * # Safety
+ * As ws_counter_feed.
+ */
+uint32_t ws_counter_get(const struct WsCounter *counter, const char *word);
+
+/**
+ * Frees the counter. NULL is allowed and does nothing, like free().
+ *
+ * # Safety
+ * `counter` must come from ws_counter_new and not be freed already.
+ */
+void ws_counter_free(struct WsCounter *counter);
+
+/**
+ * The most frequent word (ties: alphabetically first), or NULL if the
+ * counter is empty. Free the result with ws_string_free.
+ *
+ * # Safety
+ * As ws_counter_feed.
+ */
+char *ws_counter_top(const struct WsCounter *counter);
+
+/**
+ * Frees a string returned by this library. NULL is allowed.
+ *
+ * # Safety
+ * `s` must come from a ws_* function and not be freed already.
+ */
+void ws_string_free(char *s);
+
+/**
+ * Average word length in `text`, or -1 on error.
+ *
+ * # Safety
+ * `text` must be NULL or a NUL-terminated string.
+ */
+double ws_average_word_length(const char *text);
+
+/**
+ * Converts a status code to a short English message. The returned
+ * pointer is static; do not free it.
+ */
+const char *ws_status_message(int status);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* WORDSTATS_H */
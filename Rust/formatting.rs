@@ -0,0 +1,203 @@
+// ====================================================
+// FORMATTING =========================================
+// ====================================================
+
+/*
+ * println!, format!, write! and the rest share one little language, in
+ * the braces: which argument, then after a colon how to show it.
+ *
+ *     {which:fill align sign # 0 width .precision type}
+ *
+ *     which       0, 1, .. or a name; nothing for the next one
+ *     fill align  a character, then < left, ^ centre, > right
+ *     sign #  0   + always a sign; # the alternate form; 0 pad with zeros
+ *     width       at least this many characters
+ *     .precision  digits after the point, or most characters of a string
+ *     type        nothing for Display, ? Debug, x X o b e E
+ *
+ * Every part is optional: {}, {:?}, {:>8.2}, {name:#x}. A literal brace
+ * is doubled: {{ and }}. `quiz notes` compares output word by word, so
+ * where the spaces matter below, the line asserts the exact string too.
+ */
+
+// ARGUMENTS --------------------------------------------------------------
+
+// By position, by name, or a variable in scope captured by its name
+// (Rust 2021). A position or name can be used more than once.
+
+let (city, degrees) = ("Oslo", -3);
+println!("{} is at {}", city, degrees);                  // prints: Oslo is at -3
+println!("{0} is at {1}, and {0} is cold", city, degrees);   // prints: Oslo is at -3, and Oslo is cold
+println!("{place} is at {t}", place = city, t = degrees);   // prints: Oslo is at -3
+println!("{city} is at {degrees}");                     // prints: Oslo is at -3
+println!("{{{city}}}");                                 // prints: {Oslo}
+
+// Only a name can be captured, not an expression: {city.len()} doesn't
+// compile; {} with city.len() after it does.
+
+// DISPLAY AND DEBUG ------------------------------------------------------
+
+/*
+ * {} is Display: what a user should read, implemented by hand, and not
+ * there for most types (a Vec has none: what would it look like?). {:?}
+ * is Debug: what a programmer wants to see, derived, and there for
+ * nearly everything. Strings show the difference: Debug quotes and
+ * escapes them. {:#?} is Debug spread over lines, indented, for nested
+ * structs.
+ */
+
+#[derive(Debug)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+let name = "tab\there";
+println!("{name} / {name:?}");                          // prints: tab	here / "tab\there"
+println!("{:?}", vec![Some(1), None]);                  // prints: [Some(1), None]
+println!("{:#?}", Point { x: 1, y: 2 });
+// prints: Point {
+// prints:     x: 1,
+// prints:     y: 2,
+// prints: }
+
+// WIDTH, FILL AND ALIGNMENT ----------------------------------------------
+
+/*
+ * A width pads to at least that many characters; it never cuts. Strings
+ * go left by default and numbers right; < ^ > say otherwise, with a
+ * fill character before them (a space if none).
+ */
+
+assert_eq!(format!("[{:6}]", "ab"), "[ab    ]");
+assert_eq!(format!("[{:6}]", 42), "[    42]");
+assert_eq!(format!("[{:^6}]", "ab"), "[  ab  ]");
+println!("{:*<6}|{:*^6}|{:*>6}", "ab", "ab", "ab");      // prints: ab****|**ab**|****ab
+println!("{:-^20}", " menu ");                          // prints: ------- menu -------
+
+// The width can come from an argument: by position with 1$, by name
+// with name$, or a captured variable.
+
+let width = 8;
+assert_eq!(format!("[{:>1$}]", "ab", width), "[      ab]");
+assert_eq!(format!("[{:>width$}]", "ab"), "[      ab]");
+println!("{:.>width$}", 7);                             // prints: .......7
+
+// Width counts chars, not what a terminal draws: "é" written as e and
+// a combining accent is two chars, and a CJK character is one that
+// takes two columns. A table with those needs the unicode-width crate.
+
+// PRECISION --------------------------------------------------------------
+
+// On a float, the digits after the point (rounded); on a string, the
+// most characters shown. .* takes it from the arguments, before the
+// value.
+
+let pi = 3.14159265;
+println!("{:.2} {:.0} {:.4}", pi, pi, 2.0);             // prints: 3.14 3 2.0000
+println!("{:.3}", "truncated");                         // prints: tru
+println!("{:.*}", 1, pi);                               // prints: 3.1
+assert_eq!(format!("[{:8.3}]", pi), "[   3.142]");     // width and precision
+
+// Rounding is to the nearest, ties to even, on the binary value: 0.125
+// is exact and goes to 0.12, 2.675 is a little under and goes to 2.67.
+
+println!("{:.2} {:.2}", 0.125, 2.675);                  // prints: 0.12 2.67
+
+// NUMBERS ----------------------------------------------------------------
+
+/*
+ * + shows the sign of positive numbers too; 0 pads with zeros after the
+ * sign; the types x X o b e print in hex, octal, binary, exponent; # adds
+ * the 0x, 0o, 0b prefix, and counts it in the width.
+ */
+
+println!("{:+} {:+}", 5, -5);                           // prints: +5 -5
+println!("{:05} {:+05} {:05.1}", 42, 42, -1.5);         // prints: 00042 +0042 -01.5
+println!("{:x} {:X} {:#x} {:o} {:#b}", 255, 255, 255, 8, 5);   // prints: ff FF 0xff 10 0b101
+println!("{:#010x} {:08b}", 255, 5u8);                 // prints: 0x000000ff 00000101
+println!("{:e} {:.2e}", 1234.5, 0.000123);              // prints: 1.2345e3 1.23e-4
+
+// There's no thousands separator in std, nor locale: 1234567 is
+// 1234567. Negative numbers in {:x} are the two's complement bits.
+
+println!("{:x}", -1i8);                                 // prints: ff
+
+// IMPLEMENTING DISPLAY ---------------------------------------------------
+
+/*
+ * A type gets {} by implementing fmt::Display; {:?} by Debug (derive
+ * it, or write it the same way). fmt gets a Formatter, which holds what
+ * was asked for in the braces: f.width(), f.precision(), f.alternate(),
+ * f.fill(). write! into it ignores all of those.
+ */
+
+use std::fmt;
+
+struct Money {
+    cents: i64,
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "${}.{:02}", self.cents / 100, self.cents % 100)
+    }
+}
+
+let price = Money { cents: 1999 };
+println!("{price}");                                    // prints: $19.99
+assert_eq!(format!("[{:>10}]", price), "[$19.99]");     // the width is lost
+
+/*
+ * To take part in width, fill and alignment, build the text and hand it
+ * to f.pad(), which applies them (and a precision, as a string's). For
+ * a number-like type, f.pad_integral() does the sign and 0 too. And
+ * f.alternate() is there for a second form, under {:#}.
+ */
+
+use std::fmt;
+
+struct Money {
+    cents: i64,
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = if f.alternate() {
+            format!("{} cents", self.cents)
+        } else {
+            format!("${}.{:02}", self.cents / 100, self.cents % 100)
+        };
+        f.pad(&text)
+    }
+}
+
+let price = Money { cents: 1999 };
+assert_eq!(format!("[{:>10}]", price), "[    $19.99]");
+println!("{:*<10}|{:#}", price, price);                 // prints: $19.99****|1999 cents
+
+// Display also gives to_string(), for free: ToString is implemented for
+// every Display type.
+
+let text: String = price.to_string();
+println!("{}", text.len());                             // prints: 6
+
+// WRITING ELSEWHERE ------------------------------------------------------
+
+/*
+ * format! makes a String; print!/println! go to stdout, eprint!/eprintln!
+ * to stderr; write!/writeln! into anything with a write_fmt: a String
+ * (with std::fmt::Write in scope), a file or stdout (std::io::Write).
+ * write! returns a Result, which for a String can't be an error.
+ */
+
+use std::fmt::Write;
+
+let mut table = String::new();
+for (item, qty) in [("apples", 3), ("kiwis", 12)] {
+    writeln!(table, "{item:<8}{qty:>4}").unwrap();
+}
+assert_eq!(table, "apples     3\nkiwis     12\n");
+print!("{table}");
+// prints: apples 3
+// prints: kiwis 12
@@ -0,0 +1,193 @@
+// ====================================================
+// FUZZING: INPUTS NOBODY THOUGHT TO TRY ==============
+// ====================================================
+
+/*
+ * A test checks the inputs its author thought of. A fuzzer makes up
+ * inputs, millions a minute, and calls the code with each, looking for
+ * the ones that crash it: a panic, an index out of bounds, an overflow
+ * in a debug build, a stack overflow, an out-of-memory. Anything that
+ * reads bytes it doesn't control is a candidate: a parser, a decoder, a
+ * file format, a protocol.
+ *
+ * Plain random bytes don't get far: a parser rejects nearly all of them
+ * at the first character. A coverage-guided fuzzer (libFuzzer, AFL)
+ * builds the code with instrumentation that records which branches an
+ * input took, and keeps any input that took a new one, to change it
+ * further. Step by step the inputs get past the first check, then the
+ * next, without the fuzzer knowing the format at all.
+ *
+ * cargo-fuzz drives libFuzzer for Rust. Applied here to the calculator
+ * project: fuzz/fuzz_targets/calculate.rs, at the top of the repository,
+ * is the target, and what it found is below.
+ */
+
+// WHAT A FUZZER DOES, BY HAND --------------------------------------------
+
+/*
+ * Without the coverage, the idea fits in a loop: make inputs, call the
+ * function under catch_unwind, report the first that panicked. Here for
+ * a function that takes the first letter of each word, with a byte
+ * slice that assumes each word has one:
+ */
+
+use std::panic;
+
+fn initials(name: &str) -> String {
+    name.split(' ').map(|word| &word[..1]).collect()
+}
+
+// xorshift: random enough, and the same every run
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+println!("{}", initials("Grace Brewster Hopper"));   // prints: GBH
+
+let alphabet = ['a', 'B', ' ', 'é'];
+let mut rng = Rng(1);
+panic::set_hook(Box::new(|_| {}));          // quiet: the loop reports
+let found = (1..=1000).find_map(|tries| {
+    let len = rng.next() % 8;
+    let input: String = (0..len).map(|_| alphabet[(rng.next() % 4) as usize]).collect();
+    panic::catch_unwind(|| initials(&input)).is_err().then_some((tries, input))
+});
+let _ = panic::take_hook();
+println!("{found:?}");                      // prints: Some((3, "BBBé "))
+
+/*
+ * The third input, and a case nobody writes a test for: a trailing
+ * space makes an empty last word, and ""[..1] panics. A word starting
+ * with é would too: [..1] cuts the two-byte é in half. The fix is to
+ * ask for chars, and skip words with none:
+ *
+ *     name.split(' ').filter_map(|word| word.chars().next()).collect()
+ *
+ * The alphabet did the work here: four characters picked to be
+ * trouble. A real fuzzer can't be told; coverage is how it finds out.
+ */
+
+// A FUZZ TARGET -----------------------------------------------------------
+
+/*
+ * cargo fuzz init makes a fuzz/ directory in the project: a crate of
+ * its own, depending on libfuzzer-sys, with one binary per target. A
+ * target is a function from bytes to nothing, that must not crash:
+ *
+ *     #![no_main]
+ *     use libfuzzer_sys::fuzz_target;
+ *
+ *     fuzz_target!(|data: &[u8]| {
+ *         if let Ok(source) = std::str::from_utf8(data) {
+ *             let _ = calculate(source);     // an Err is fine; a panic isn't
+ *         }
+ *     });
+ *
+ * It calls into the code as a library would, so the calculator, a
+ * main.rs, is a module of the langscape crate too, and fuzz/Cargo.toml
+ * depends on that.
+ * cargo fuzz run builds it with nightly rustc (the instrumentation and
+ * AddressSanitizer need flags stable doesn't have) and starts:
+ *
+ *     cargo +nightly fuzz run calculate
+ *
+ *     #2        INITED cov: 31 ft: 31 corp: 1/1b exec/s: 0 rss: 32Mb
+ *     ...
+ *     #16777216 pulse  cov: 175 ft: 740 corp: 275/15320b lim: 4096 exec/s: 72005 rss: 528Mb
+ *
+ * cov is the branches reached so far, corp the inputs kept for reaching
+ * new ones. It runs until a crash, or Ctrl-C; -max_total_time=300 after
+ * the -- sets a limit.
+ */
+
+// WHAT IT FOUND -----------------------------------------------------------
+
+/*
+ * The first try, calling calculate() straight from the target: eight
+ * minutes, 42 million inputs, inputs allowed up to 16 KB, coverage at
+ * 175 branches, and no crash. And there is one: "((((" 10000 deep
+ * overflows the stack, since each ( is a call of the parser's expr()
+ * inside the last. The fuzzer didn't get there because nesting deeper
+ * reaches no new branch: to coverage, 50 parentheses are the same as 5.
+ *
+ * The target in fuzz/fuzz_targets/calculate.rs runs each input on a
+ * thread with a 128 KB stack instead. Six seconds:
+ *
+ *     ==10152==ERROR: AddressSanitizer: stack-overflow on address ...
+ *         #102 in <calculate::Parser>::expr
+ *         #103 in <calculate::Parser>::expr
+ *         ...
+ *         #113 in calculate::calculate
+ *     SUMMARY: AddressSanitizer: stack-overflow
+ *     ...
+ *     artifact_prefix='fuzz/artifacts/calculate/'; Test unit written to
+ *         fuzz/artifacts/calculate/crash-aa9da337a3e86ebe615ae71ba406e5068bff2e80
+ *
+ * The input is in the file, run it again with the path after the
+ * target's name. cargo fuzz tmin shrinks it while it still crashes; this
+ * one, 230 bytes of minus signs and ones, came down to 111 minuses and
+ * a 1. Unary minus recurses like a parenthesis, and -------1 is a valid
+ * expression, so the fuzzer found the short way to depth that the first
+ * run couldn't be pushed towards.
+ *
+ * A stack overflow isn't a panic: Rust aborts the process, no
+ * catch_unwind can stop it. For the calculator's REPL, a line pasted in
+ * would end the program.
+ */
+
+// THE FIX -----------------------------------------------------------------
+
+/*
+ * A limit, and an error past it. calculator.rs's parser counts the
+ * calls of expr() running and stops at MAX_DEPTH, 100:
+ *
+ *     if self.depth == MAX_DEPTH {
+ *         return Err(CalcError::new("expression nested too deeply", self.peek().span));
+ *     }
+ *     self.depth += 1;
+ *     ...
+ *     self.depth -= 1;
+ *     Ok(lhs)
+ *
+ * and main() asserts the fuzzer's input is an error now, so it can't
+ * come back. The same target then ran five minutes, 3.3 million inputs,
+ * without a crash. The limit has to fit the smallest stack the code
+ * runs on: 100 levels took about half of the target's 128 KB, with
+ * AddressSanitizer's larger frames.
+ *
+ * serde_json has the same limit, 128 deep, for the same reason; so has
+ * any parser that takes input from outside and recurses.
+ */
+
+// MAKING THE MOST OF IT ---------------------------------------------------
+
+/*
+ *     the corpus          fuzz/corpus/calculate/ keeps the inputs that
+ *                         reached new branches; commit it, and the next
+ *                         run starts where this one stopped. Seed it
+ *                         with real examples ("1 + 2", "-(4 - 10) ^ 2")
+ *     a dictionary        -dict=calc.dict with tokens ("+", "^", "(")
+ *                         helps a fuzzer through a text format
+ *     structured input    fuzz_target!(|expr: MyExpr| ..) with
+ *                         #[derive(Arbitrary)] makes typed values
+ *                         from the bytes, for an API that isn't text
+ *     more than crashes   assert what must hold, and the fuzzer looks
+ *                         for where it doesn't: eval of a parse of a
+ *                         Display of an Expr gives the same number; a
+ *                         fast version agrees with a simple one
+ *     in CI               a few minutes per target per change, with
+ *                         -max_total_time, and the crash inputs kept
+ *                         as tests
+ *
+ * Coverage shows what it can't see, as above: an input that crashes by
+ * being big, or deep, or slow (-timeout=N for that) isn't more
+ * interesting to it than a small one. A small stack, a low -rss_limit_mb
+ * or a limit of your own makes those failures reachable.
+ */
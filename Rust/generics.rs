@@ -36,7 +36,8 @@ fn main() {
 // TRAITS ==================================================
 // =========================================================
 
-// Traits are like interfaces in Java
+// Traits are like interfaces in Java; dispatched at runtime: [[traits#trait objects]]
+// (mostly: compare/java.rs covers where the analogy breaks)
 
 pub trait Summary {         // pub, because we want this to be visible from outside this module
     fn summarize(&self) -> String;    // no definition, waiting for an implementer, observe the semi-colon
@@ -91,6 +92,7 @@ fn main() {
  * This rule ensures that other people's code can't break our code and vice versa. Otherwise,
  *        two crates could implement the same trait for the same type and Rust will not know
  *        which implementation to use.
+ * The error it gives, and the ways around it: [[coherence#the orphan rule]]
  */
 
 // Default implementation: use it or override it
@@ -138,6 +140,7 @@ pub fn notify(item: &impl Summary) {   // the argument must be of any type for w
 // The above is a syntax sugar for a longer form known as trait bound
 
 // TRAIT BOUND . . .
+// (practice: quiz/packs/bounds.toml, fill in the missing bound)
 
 pub fn notify<T: Summary>(item: &T) {
     //
@@ -197,6 +200,7 @@ fn returns_summarizable() -> impl Summary {
 
 // Every reference has a lifetime and we need to specify lifetime parameters
 //                                for functions or structs that use references
+// (practice: quiz/packs/lifetimes.toml, ten "add the lifetimes" questions)
 
 fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {  // The returned reference will be valid as long as
                                                      // both the parameters are valid     
@@ -249,7 +253,7 @@ struct Excerpt<'a> { // an instance of Excerpt cannot outlive the reference it h
  *              because the compiler can't infer the lifetime parameter of the output
  */
 
-fn function(x: &str, y: &str) -> &str {        // won't compile
+fn function(x: &str, y: &str) -> &str {        // compile error: E0106
     //
 }
 
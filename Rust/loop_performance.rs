@@ -0,0 +1,171 @@
+// ====================================================
+// ITERATORS AND INDEX LOOPS, MEASURED ================
+// ====================================================
+
+/*
+ * Iterators are said to be a "zero-cost abstraction": a chain of map,
+ * filter and sum ([[closures_and_iterators#iterator]]) compiles to the
+ * loop you'd have written by hand, closures and all inlined away. The
+ * worry the other way: an index loop checks v[i] against v.len() on
+ * every access. Both are claims about what rustc -O makes, so here they
+ * are measured: benches/loops.rs, three workloads written each way, with
+ * Criterion (cargo bench).
+ *
+ * Microseconds for 10,000 u64s, on one x86-64 machine, the middle of
+ * Criterion's estimate:
+ *
+ *                        iter     index    third way
+ *     sum of even         5.9       7.9      7.3   for x in v
+ *       squares
+ *     dot product         7.2       7.5      6.5   slices cut to one length
+ *     differences         3.5      16.0      5.5   output sized first
+ *
+ * Runs on this machine moved by 10-20% from one to the next, and the
+ * order of the first two rows with them (another run had the chain
+ * last, at 10.2 against 9.0): those are the same code. The third row
+ * isn't, and not for the reason one would guess.
+ */
+
+// THE SAME LOOP ----------------------------------------------------------
+
+// The three sums of even squares: the chain, the index loop, the for
+// loop. The same results, and nearly the same machine code.
+
+fn even_squares_iter(v: &[u64]) -> u64 {
+    v.iter().filter(|&&x| x % 2 == 0).map(|&x| x * x).sum()
+}
+
+fn even_squares_index(v: &[u64]) -> u64 {
+    let mut sum = 0;
+    for i in 0..v.len() {
+        if v[i] % 2 == 0 {
+            sum += v[i] * v[i];
+        }
+    }
+    sum
+}
+
+fn even_squares_for(v: &[u64]) -> u64 {
+    let mut sum = 0;
+    for &x in v {
+        if x % 2 == 0 {
+            sum += x * x;
+        }
+    }
+    sum
+}
+
+let v: Vec<u64> = (1..=10).collect();
+println!("{} {} {}", even_squares_iter(&v), even_squares_index(&v), even_squares_for(&v));   // prints: 220 220 220
+
+/*
+ * In the assembly (rustc -O --emit asm, diagrams/dispatch.rs shows how)
+ * all three are the same branchless loop: load, multiply, test the low
+ * bit, cmov a 0 for the odd ones, add. No closure calls are left, and
+ * the index loop has no bounds check: i comes from 0..v.len(), so v[i]
+ * can't fail and the compiler drops the check. The one difference is
+ * that the chain's loop is unrolled two elements a round and the loops'
+ * four. "Zero-cost" holds: what a chain costs is what LLVM happens to do
+ * with the loop it becomes, and that goes either way by a little.
+ */
+
+// BOUNDS CHECKS ----------------------------------------------------------
+
+/*
+ * A check goes when the compiler can prove the index is in bounds. Over
+ * two slices it can't: nothing says b is as long as a.
+ */
+
+fn dot_index(a: &[u64], b: &[u64]) -> u64 {
+    let mut sum = 0;
+    for i in 0..a.len() {
+        sum += a[i] * b[i];                 // a[i] is fine, b[i] may not be
+    }
+    sum
+}
+
+println!("{}", dot_index(&[1, 2, 3], &[4, 5, 6]));   // prints: 32
+
+// And yet it measured the same as the zip. LLVM doesn't drop the check,
+// it moves it: one comparison before the loop, b.len() >= a.len(), a
+// fast loop without checks if so, and the panic if not. Nothing the loop
+// does can be seen before a panic (a sum in a register), so the panic
+// may come first.
+
+println!("{:?}", std::panic::catch_unwind(|| dot_index(&[1, 2, 3], &[4, 5])).is_err());   // prints: true
+
+/*
+ * Cutting both slices to one length first makes it provable, and is the
+ * usual advice; it's also what zip does. Here it made no difference, as
+ * LLVM had already hoisted the check; where it does is a loop LLVM can't
+ * version like that, and an index not from the loop's range at all:
+ *
+ *     v[idx[i]]           a lookup through another slice
+ *     v[i * stride]       a product it can't bound
+ *
+ * Those keep a check per access, and it's the check's branch, more than
+ * the compare, that costs: it can stop the loop being vectorized.
+ */
+
+fn dot_sliced(a: &[u64], b: &[u64]) -> u64 {
+    let n = a.len().min(b.len());
+    let (a, b) = (&a[..n], &b[..n]);         // now b[i] is in bounds wherever a[i] is
+    let mut sum = 0;
+    for i in 0..n {
+        sum += a[i] * b[i];
+    }
+    sum
+}
+
+println!("{}", dot_sliced(&[1, 2, 3], &[4, 5, 6]));   // prints: 32
+
+// A loop that writes as it goes is versioned too: the part of it that's
+// sure to be in bounds runs vectorized, and only what's after it is
+// checked. Finding a check that costs anything takes some looking:
+// cargo asm, or perf annotate (profiling.rs), before rewriting a loop.
+
+// WHERE IT WASN'T THE SAME -----------------------------------------------
+
+/*
+ * The differences, each element minus the one before: the chain ran
+ * four or five times faster than the index loop, and half again as fast
+ * as the index loop writing into an output sized first.
+ */
+
+fn differences_iter(v: &[u64]) -> Vec<u64> {
+    v.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+fn differences_index(v: &[u64]) -> Vec<u64> {
+    let mut out = Vec::new();
+    for i in 1..v.len() {
+        out.push(v[i] - v[i - 1]);
+    }
+    out
+}
+
+println!("{:?} {:?}", differences_iter(&[1, 4, 9, 16]), differences_index(&[1, 4, 9, 16]));   // prints: [3, 5, 7] [3, 5, 7]
+
+/*
+ * Not the bounds checks: the push. Each one checks the Vec's capacity
+ * and bumps its length, a store the next round depends on, so the loop
+ * can't be vectorized. collect() and extend() on windows().map() know
+ * the exact count up front (the iterator is TrustedLen, a promise std's
+ * iterators make about size_hint), reserve once and write straight
+ * into the buffer, in a loop that can be vectorized.
+ *
+ * The resized version (out.resize(n - 1, 0), then out[i - 1] = ..)
+ * avoids the pushes but writes every element twice, zeros first; the
+ * chain writes each once.
+ *
+ * So, for writing loops:
+ *
+ *     for x in v, or a chain       the same speed; pick the clearer
+ *     for i in 0..v.len()          the same, when i only indexes v (and
+ *                                  clippy's needless_range_loop says so)
+ *     an index into another slice  cut the slices to one length first, or
+ *                                  zip; measure before worrying
+ *     building a Vec               collect or extend from an iterator
+ *                                  rather than push in a loop, or
+ *                                  Vec::with_capacity first at least
+ */
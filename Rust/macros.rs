@@ -0,0 +1,88 @@
+// ====================================================
+// MACROS =============================================
+// ====================================================
+
+/*
+ * A macro is code that writes code: it's expanded before type checking,
+ * so what the compiler checks is the expansion, not what we wrote.
+ * Calls end with a ! (println!, vec!, panic!), which is how to tell a
+ * macro from a function. They can take any number of arguments, which a
+ * function can't.
+ *
+ * To see the expansion (see diagrams/expand.rs):
+ *     ./expand snippet.rs
+ * it puts the snippet and what the compiler sees side by side.
+ */
+
+// macro_rules!: a match on the tokens of the call, each arm a
+// pattern => what it expands to
+
+macro_rules! square_all {
+    ($($x:expr),*) => {                 // $x: an expression; $(...),* : zero or more, comma separated
+        vec![$($x * $x),*]              // one $x * $x per $x, comma separated again
+    };
+}
+
+let squares = square_all!(1, 2, 3);
+println!("{squares:?}");                // prints: [1, 4, 9]
+
+// the expansion, before vec! itself is expanded:
+//     let squares = vec![1 * 1, 2 * 2, 3 * 3];
+
+// fragment types: expr, ident, ty, pat, stmt, block, item, literal, tt (a single token tree)
+// repetitions: $(...)* zero or more, $(...)+ one or more, $(...)? zero or one
+
+macro_rules! maximum {
+    ($x:expr) => { $x };
+    ($x:expr, $($rest:expr),+) => {     // arms are tried in order; this one recurses
+        { let a = $x; let b = maximum!($($rest),+); if a > b { a } else { b } }
+    };
+}
+
+println!("{}", maximum!(3, 8, 5));      // prints: 8
+
+/*
+ * Hygiene: names a macro_rules! macro makes up (the a and b above) live
+ * in their own scope, so they can't clash with the caller's a and b.
+ * Names the caller passes in ($x) are the caller's.
+ *
+ * println! is a macro too. Nothing is formatted at run time from the
+ * string, the format string is checked and taken apart at compile time:
+ *
+ *     println!("hello {name}");
+ * expands to
+ *     { ::std::io::_print(format_args!("hello {0}\n", name)); };
+ *
+ * which is why a {} without an argument is a compile error and not a
+ * wrong output.
+ */
+
+// derive: a procedural macro, a Rust function run by the compiler on the
+// item it's attached to, which writes the impl we would otherwise write by hand
+
+#[derive(Clone)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+// expands to, next to the struct:
+//     #[automatically_derived]
+//     impl ::core::clone::Clone for Point {
+//         #[inline]
+//         fn clone(&self) -> Point {
+//             Point {
+//                 x: ::core::clone::Clone::clone(&self.x),
+//                 y: ::core::clone::Clone::clone(&self.y),
+//             }
+//         }
+//     }
+// a field-by-field clone, so it only compiles if every field is Clone
+
+/*
+ * The other kinds of procedural macro:
+ *     attribute-like  #[route(GET, "/")] fn index() {}  rewrites the item
+ *     function-like   sql!(SELECT * FROM posts)         like macro_rules!,
+ *                                                       but any Rust code
+ * They have to live in their own crate, with proc-macro = true.
+ */
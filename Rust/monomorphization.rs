@@ -0,0 +1,148 @@
+// ====================================================
+// MONOMORPHIZATION ===================================
+// ====================================================
+
+/*
+ * A generic function isn't compiled once: it's compiled again for every
+ * type it's used with, each copy as if it had been written by hand for
+ * that type. That's monomorphization, and it's why generics cost nothing
+ * at run time: every call is a plain call, and can be inlined. A
+ * function taking a trait object ([[traits#trait objects: dynamic polymorphism]])
+ * is compiled once, and finds the method in a vtable when it runs.
+ *
+ * The table in traits.rs says the price of the first is binary size.
+ * This is that price measured, what else it costs (build time), how to
+ * keep it down, and when to pick which.
+ */
+
+// WHAT THE COMPILER MAKES ------------------------------------------------
+
+// largest is written once; largest::<i32> and largest::<char> are two
+// functions in the binary, at two addresses.
+
+fn largest<T: PartialOrd + Copy>(list: &[T]) -> T {
+    let mut largest = list[0];
+    for &item in list {
+        if item > largest {
+            largest = item;
+        }
+    }
+    largest
+}
+
+println!("{} {}", largest(&[3, 7, 2]), largest(&['q', 'z', 'a']));   // prints: 7 z
+let for_i32 = largest::<i32> as fn(&[i32]) -> i32 as usize;
+let for_char = largest::<char> as fn(&[char]) -> char as usize;
+println!("{}", for_i32 != for_char);       // prints: true
+
+// The same with dyn: one function, whatever the types behind it.
+
+use std::fmt::Display;
+
+fn show(items: &[&dyn Display]) -> String {
+    items.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+println!("{}", show(&[&7, &'z', &"text"]));   // prints: 7 z text
+
+/*
+ * Where a copy is made: wherever a generic is used with a new set of
+ * type arguments, in any crate. Vec<String> and Vec<u8> are two copies of
+ * every Vec method they call; a closure is a type of its own, so each
+ * closure passed to a generic map or sort_by_key makes one more. And
+ * `impl Trait` in argument position is a generic too: fn f(x: impl
+ * Display) is fn f<T: Display>(x: T).
+ */
+
+// WHAT IT COSTS ----------------------------------------------------------
+
+/*
+ * diagrams/bloat.rs builds the same report() (collect, sort, format a
+ * line per element) generic over T: Shape and over &[&dyn Shape], used
+ * with more and more shape types, with rustc -O:
+ *
+ *              ------------ generic ------------   -------------- dyn --------------
+ *      types      binary   copies     build           binary   copies     build
+ *          1    342.7 KB        1    0.37 s        343.0 KB        1    0.30 s
+ *         10    357.3 KB       10    1.00 s        347.3 KB        1    0.39 s
+ *         50    423.9 KB       50    3.23 s        366.5 KB        1    0.49 s
+ *        200    674.9 KB      200   11.04 s        439.9 KB        1    1.04 s
+ *
+ *     each type more: generic +1710 bytes, +53.6 ms to build;
+ *                     dyn      +499 bytes,  +3.8 ms
+ *
+ * (copies: the report functions in the optimized LLVM IR). With one
+ * type there's no difference; every type after it costs the generic side
+ * a whole report(), the dyn side only the type's methods and its vtable.
+ *
+ * The build time is the bigger cost: every copy goes through LLVM's
+ * optimizations on its own, and those are most of a release build. In a
+ * crate it shows as a slow build of whichever crate uses the generics
+ * with its own types, often the last one, the binary.
+ *
+ * To see which generics make the most code in a crate,
+ *
+ *     cargo install cargo-llvm-lines
+ *     cargo llvm-lines --release | head -20
+ *
+ * lists the functions by lines of LLVM IR, with how many copies of each.
+ */
+
+// KEEPING IT SMALL -------------------------------------------------------
+
+/*
+ * Most of a generic function often doesn't depend on T at all: T is only
+ * there to accept a few types for one argument. The part that does is
+ * turned into a concrete type first, and the rest is an inner function
+ * that isn't generic: one copy of it, however many types the outer one
+ * is called with. std::fs::read does this, and so does most of std::fs.
+ */
+
+use std::path::Path;
+
+fn size<P: AsRef<Path>>(path: P) -> u64 {
+    fn inner(path: &Path) -> u64 {
+        std::fs::metadata(path).map_or(0, |m| m.len())
+    }
+    inner(path.as_ref())                    // the only line copied per P
+}
+
+println!("{} {}", size("/no/such/file"), size(String::from("/no/such/file")));   // prints: 0 0
+
+// The same for a trait: the generic function turns its T into a &dyn
+// and hands it on. Callers keep the generic signature, the body is
+// compiled once.
+
+use std::fmt::Display;
+
+fn log<T: Display>(value: T) -> String {
+    fn inner(value: &dyn Display) -> String {
+        format!("[log] {value}")
+    }
+    inner(&value)
+}
+
+println!("{}", log(42));                    // prints: [log] 42
+
+// WHEN TO PREFER WHICH ---------------------------------------------------
+
+/*
+ *                         generic                    dyn
+ *     a call             direct, can be inlined     through the vtable;
+ *                                                   can't be inlined
+ *     the code           a copy per type            one
+ *     build time         a copy per type            one
+ *     the types          known at compile time      any, even in one Vec
+ *
+ * Generic, when the function is called in a hot loop on small methods:
+ * traits.rs measures a tight loop ~9x slower through dyn, because of
+ * the inlining lost. And when there are few types, which costs little.
+ *
+ * dyn, when there are many types and the function isn't hot: a report,
+ * an error path, setting something up once. The copies buy nothing there
+ * but build time. And when the types aren't known together: a Vec of
+ * different ones, plugins.
+ *
+ * And the two together: a generic signature for the callers, an inner
+ * function over &dyn or a concrete type doing the work.
+ */
@@ -0,0 +1,270 @@
+// NO_STD: RUST WITHOUT AN OPERATING SYSTEM -------------------------------
+
+/*
+ * For the embedded-curious. A microcontroller has no OS: no files, no
+ * threads, often no heap, and a few KB of RAM. Rust runs there with
+ * #![no_std], which drops the parts of the standard library that need
+ * an OS and keeps the rest.
+ *
+ * This crate is a small piece of firmware logic: a moving average for
+ * sensor readings and a parser for commands arriving over a serial line.
+ * It's no_std, so it would run on a chip, but nothing in it is
+ * chip-specific, so it builds and tests on a laptop like any crate.
+ * That split is the usual design: keep hardware access thin, and put the
+ * logic in a no_std library you can test on the host.
+ *
+ * The pieces, in this directory, a crate of the workspace:
+ *     src/lib.rs      the no_std library (this file)
+ *     tests/host.rs   tests that run on the host with std
+ *     Cargo.toml      heapless, its one dependency
+ *
+ * cargo test                                 on the host (in Rust/no_std;
+ *                                            at the top, with the rest)
+ * cargo build --target thumbv7em-none-eabihf for a Cortex-M4F
+ *     (rustup target add thumbv7em-none-eabihf first; proves nothing
+ *      in here needs std)
+ */
+
+#![no_std]
+
+use core::fmt::{self, Write};
+use heapless::{String, Vec};
+
+// CORE, ALLOC, STD -------------------------------------------------------
+
+/*
+ * The standard library is three layers:
+ *
+ *     core    needs nothing: Option, Result, iterators, slices, str,
+ *             integer and float ops, fmt, cell, atomics (where the chip
+ *             has them), traits like Copy, Iterator, Display
+ *     alloc   needs a heap (a #[global_allocator]): Box, Vec, String,
+ *             Rc, Arc, BTreeMap, format!
+ *     std     needs an OS: files, threads, networking, time, env, the
+ *             HashMap (its random seed comes from the OS), println!
+ *
+ * std re-exports core and alloc, which is why std::option::Option and
+ * core::option::Option are the same type. In a no_std crate, write core::
+ * paths; everything from core is available as usual.
+ *
+ * With an allocator, `extern crate alloc;` brings back Vec and String.
+ * Many embedded projects skip the heap entirely: no allocator means no
+ * out-of-memory at run time and no fragmentation, and memory use is
+ * known at link time. That's what heapless is for.
+ */
+
+// A MOVING AVERAGE, CORE ONLY --------------------------------------------
+
+/*
+ * A ring buffer in a fixed-size array. The const generic N is the
+ * capacity, so MovingAverage<8> is 8 i32s plus two counters, decided
+ * at compile time; it can live in a static.
+ */
+
+pub struct MovingAverage<const N: usize> {
+    samples: [i32; N],
+    next: usize,                       // where the next sample goes
+    filled: usize,                     // how many slots hold data
+}
+
+impl<const N: usize> MovingAverage<N> {
+    // const fn: usable in `static AVG: Mutex<MovingAverage<8>> = ..`
+    pub const fn new() -> Self {
+        MovingAverage { samples: [0; N], next: 0, filled: 0 }
+    }
+
+    pub fn push(&mut self, sample: i32) {
+        self.samples[self.next] = sample;
+        self.next = (self.next + 1) % N;
+        self.filled = (self.filled + 1).min(N);
+    }
+
+    /// None until the first sample arrives.
+    pub fn average(&self) -> Option<i32> {
+        if self.filled == 0 {
+            return None;
+        }
+        // i64 so that N large readings can't overflow the sum
+        let sum: i64 = self.samples[..self.filled].iter().map(|&s| s as i64).sum();
+        Some((sum / self.filled as i64) as i32)
+    }
+}
+
+impl<const N: usize> Default for MovingAverage<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// COLLECTIONS WITHOUT A HEAP: heapless -----------------------------------
+
+/*
+ * heapless has Vec, String, Deque, IndexMap and friends with the
+ * capacity as a const generic: Vec<u8, 16> is 16 bytes of storage plus
+ * a length, inline, wherever the Vec itself lives (stack, static).
+ *
+ * The API mirrors std's, with one big difference: a push can fail.
+ *     std:       v.push(x)                   grows, or aborts on OOM
+ *     heapless:  v.push(x) -> Result<(), T>  Err(x) when full, and
+ *                                            you get x back
+ * so "what if it's full?" becomes an error you handle, instead of an
+ * allocation you hope succeeds.
+ *
+ * The parser below reads lines like "SET 2 1500" from a serial port.
+ * Words are collected into a Vec<&str, 3>: a fourth word is an error,
+ * not a reallocation.
+ */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Set { channel: u8, value: u16 },
+    Get { channel: u8 },
+    Reset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    NotUtf8,
+    TooManyWords,
+    UnknownCommand,
+    MissingArgument,
+    BadNumber,
+}
+
+pub const CHANNELS: usize = 4;
+
+pub fn parse(line: &[u8]) -> Result<Command, ParseError> {
+    // bytes off the wire aren't promised to be text
+    let line = core::str::from_utf8(line).map_err(|_| ParseError::NotUtf8)?;
+
+    let mut words: Vec<&str, 3> = Vec::new();
+    for word in line.split_ascii_whitespace() {
+        words.push(word).map_err(|_| ParseError::TooManyWords)?;
+    }
+
+    let number = |i: usize| -> Result<u16, ParseError> {
+        words.get(i).ok_or(ParseError::MissingArgument)?.parse().map_err(|_| ParseError::BadNumber)
+    };
+    let channel = |i: usize| -> Result<u8, ParseError> {
+        match number(i)? {
+            n if (n as usize) < CHANNELS => Ok(n as u8),
+            _ => Err(ParseError::BadNumber),
+        }
+    };
+
+    match words.first().copied() {
+        None => Err(ParseError::Empty),
+        Some("SET") => Ok(Command::Set { channel: channel(1)?, value: number(2)? }),
+        Some("GET") => Ok(Command::Get { channel: channel(1)? }),
+        Some("RESET") => Ok(Command::Reset),
+        Some(_) => Err(ParseError::UnknownCommand),
+    }
+}
+
+// FORMATTING WITHOUT format! ---------------------------------------------
+
+/*
+ * format! needs alloc (it returns a String). But the formatting
+ * machinery is in core: write! works on anything that implements
+ * core::fmt::Write, and heapless::String does. If the text doesn't fit,
+ * write! returns fmt::Error instead of growing.
+ *
+ * No floats here on purpose: many small chips have no FPU, and f32
+ * formatting pulls several KB of code into the binary. Fixed point
+ * (integers counting hundredths) is the embedded habit.
+ */
+
+/// Formats hundredths as a decimal: 2150 -> "21.50", -5 -> "-0.05".
+pub fn format_centi(value: i32) -> Result<String<12>, fmt::Error> {
+    let mut out = String::new();
+    let sign = if value < 0 { "-" } else { "" };
+    let abs = value.unsigned_abs();
+    write!(out, "{sign}{}.{:02}", abs / 100, abs % 100)?;
+    Ok(out)
+}
+
+/// Runs a command against the channel values and writes the reply line.
+pub fn respond(command: Command, channels: &mut [u16; CHANNELS]) -> String<24> {
+    let mut reply = String::new();
+    // every reply fits in 24 bytes, so these writes can't fail; a
+    // firmware would still rather send a short reply than panic
+    let _ = match command {
+        Command::Set { channel, value } => {
+            channels[channel as usize] = value;
+            write!(reply, "OK")
+        }
+        Command::Get { channel } => write!(reply, "{channel}={}", channels[channel as usize]),
+        Command::Reset => {
+            *channels = [0; CHANNELS];
+            write!(reply, "OK reset")
+        }
+    };
+    reply
+}
+
+// PANIC HANDLERS ---------------------------------------------------------
+
+/*
+ * std decides what a panic does: print the message, unwind, exit. A
+ * no_std *binary* has to decide itself, by providing exactly one
+ * function marked #[panic_handler]. Libraries like this one don't:
+ * the final binary picks, and a library that defined one would clash
+ * with it (and with std's, when tested on the host).
+ *
+ * The firmware binary using this crate (src/main.rs of another crate,
+ * Cortex-M, with the cortex-m-rt runtime):
+ *
+ *     #![no_std]
+ *     #![no_main]                       // no normal main: the runtime's
+ *                                       // reset handler calls ours
+ *     use core::panic::PanicInfo;
+ *     use cortex_m_rt::entry;
+ *
+ *     #[entry]
+ *     fn main() -> ! {                  // ! : firmware never returns
+ *         let mut channels = [0u16; sensor_logic::CHANNELS];
+ *         loop {
+ *             let line = read_line_from_uart();
+ *             let reply = match sensor_logic::parse(&line) {
+ *                 Ok(command) => sensor_logic::respond(command, &mut channels),
+ *                 Err(_) => heapless::String::try_from("ERR").unwrap(),
+ *             };
+ *             write_to_uart(reply.as_bytes());
+ *         }
+ *     }
+ *
+ *     #[panic_handler]
+ *     fn panic(_info: &PanicInfo) -> ! {
+ *         // no console to print to; common choices:
+ *         //     loop {}                 halt, wait for the watchdog to reset
+ *         //     cortex_m::asm::udf()    trap into the debugger
+ *         //     write info to a log over the debug probe (defmt, rtt)
+ *         loop {}
+ *     }
+ *
+ * Ready-made handlers exist as crates (panic-halt, panic-reset,
+ * panic-probe): depend on one and `use panic_halt as _;`.
+ *
+ * With no unwinding, set `panic = "abort"` in the profile. Unwinding
+ * needs support code that these targets don't have, and aborting keeps
+ * the binary smaller.
+ */
+
+// TESTING ON THE HOST ----------------------------------------------------
+
+/*
+ * A #![no_std] library can still be tested with plain `cargo test`:
+ *     tests/         integration tests are separate crates. They get std
+ *                    (and println!, and the test harness), and use the
+ *                    library's public API like any other user.
+ *     #[cfg(test)]   unit tests inside the library need std too; write
+ *                    #![cfg_attr(not(test), no_std)] instead of
+ *                    #![no_std] so test builds link it.
+ * host.rs is the first kind.
+ *
+ * What host tests can't catch: stack overflow (a chip may have 8 KB of
+ * stack, the host 8 MB), integer widths (usize is 32 bits on most
+ * microcontrollers) and timing. Test the hardware layer on hardware, or
+ * in an emulator like QEMU.
+ */
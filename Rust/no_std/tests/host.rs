@@ -0,0 +1,67 @@
+// NO_STD: TESTS ON THE HOST ----------------------------------------------
+
+/*
+ * The tests of the crate in src/lib.rs. This file is its own crate and
+ * links std, so the usual test harness works even though the library
+ * itself is #![no_std].
+ */
+
+use sensor_logic::{format_centi, parse, respond, Command, MovingAverage, ParseError, CHANNELS};
+
+#[test]
+fn moving_average_fills_then_slides() {
+    let mut avg = MovingAverage::<3>::new();
+    assert_eq!(avg.average(), None);
+    avg.push(10);
+    assert_eq!(avg.average(), Some(10));
+    avg.push(20);
+    avg.push(30);
+    assert_eq!(avg.average(), Some(20));
+    avg.push(60);                      // 10 drops out: (20 + 30 + 60) / 3
+    assert_eq!(avg.average(), Some(36));
+}
+
+#[test]
+fn moving_average_does_not_overflow() {
+    let mut avg = MovingAverage::<4>::new();
+    for _ in 0..4 {
+        avg.push(i32::MAX);
+    }
+    assert_eq!(avg.average(), Some(i32::MAX));
+}
+
+#[test]
+fn parses_commands() {
+    assert_eq!(parse(b"SET 2 1500"), Ok(Command::Set { channel: 2, value: 1500 }));
+    assert_eq!(parse(b"  GET 0\r\n"), Ok(Command::Get { channel: 0 }));
+    assert_eq!(parse(b"RESET"), Ok(Command::Reset));
+}
+
+#[test]
+fn rejects_bad_input() {
+    assert_eq!(parse(b""), Err(ParseError::Empty));
+    assert_eq!(parse(b"JUMP"), Err(ParseError::UnknownCommand));
+    assert_eq!(parse(b"SET 1"), Err(ParseError::MissingArgument));
+    assert_eq!(parse(b"SET 9 1"), Err(ParseError::BadNumber));      // no channel 9
+    assert_eq!(parse(b"SET 1 70000"), Err(ParseError::BadNumber));  // > u16::MAX
+    assert_eq!(parse(b"SET 1 2 3"), Err(ParseError::TooManyWords)); // Vec<_, 3> is full
+    assert_eq!(parse(&[0xff, 0xfe]), Err(ParseError::NotUtf8));
+}
+
+#[test]
+fn formats_fixed_point() {
+    assert_eq!(format_centi(2150).unwrap().as_str(), "21.50");
+    assert_eq!(format_centi(-5).unwrap().as_str(), "-0.05");
+    assert_eq!(format_centi(i32::MIN).unwrap().as_str(), "-21474836.48");   // the longest: 12 bytes
+}
+
+#[test]
+fn responds_to_a_session() {
+    let mut channels = [0u16; CHANNELS];
+    let session = ["SET 1 42", "GET 1", "RESET", "GET 1"];
+    let replies: Vec<String> = session
+        .iter()
+        .map(|line| respond(parse(line.as_bytes()).unwrap(), &mut channels).to_string())
+        .collect();
+    assert_eq!(replies, ["OK", "1=42", "OK reset", "1=0"]);
+}
@@ -0,0 +1,129 @@
+// ====================================================
+// OPTION LAYOUT ======================================
+// ====================================================
+
+/*
+ * Option<T> is the null of Rust ([[enums_pattern_matching#enums]]): a
+ * value that may not be there, and the compiler making sure the None is
+ * handled. It would be fair to expect it to cost something over a plain
+ * nullable pointer, a tag next to the value. For references, boxes and
+ * the NonZero integers it costs nothing: Option<&T> is one pointer, and
+ * None is that pointer being null. This is where the niches from
+ * [[enums#size]] pay off.
+ *
+ * The sizes below are for a 64-bit target, and the assertions are run by
+ * `quiz notes` with the prints, so they hold for the rustc the notes were
+ * checked with.
+ */
+
+// THE TAG ----------------------------------------------------------------
+
+/*
+ * Option is an enum like any other: in general it's the value plus a
+ * tag for which variant it is, and the tag is padded out to the value's
+ * alignment. An Option<u32> is twice a u32, an Option<u64> twice a u64.
+ */
+
+use std::mem::size_of;
+
+assert_eq!(size_of::<Option<u8>>(), 2);
+assert_eq!(size_of::<Option<u32>>(), 8);
+assert_eq!(size_of::<Option<u64>>(), 16);
+println!("{} {}", size_of::<u32>(), size_of::<Option<u32>>());   // prints: 4 8
+
+// Every bit pattern of a u32 is some number, so there's no spare one to
+// say None with; the same for i32, f64 and the rest.
+
+// NICHES -----------------------------------------------------------------
+
+/*
+ * A niche is a bit pattern a type never takes: a reference is never
+ * null, nor is a Box, a bool is only 0 or 1, a char never above
+ * 0x10FFFF. The compiler puts the variants without data in those, and
+ * the tag goes away:
+ *
+ *     Option<&T>            None is the null pointer
+ *     Option<Box<T>>        the same
+ *     Option<NonZeroU32>    None is 0
+ *     Option<bool>          None is 2
+ *
+ * For the first three (and Option<NonNull<T>>, Option<fn()>, the other
+ * NonZero types) the std docs promise it: the same size, and None all
+ * zero bits. For the rest it's what rustc does, not a promise.
+ */
+
+use std::mem::size_of;
+use std::num::NonZeroU32;
+
+assert_eq!(size_of::<Option<&u64>>(), size_of::<&u64>());
+assert_eq!(size_of::<Option<Box<u64>>>(), size_of::<Box<u64>>());
+assert_eq!(size_of::<Option<NonZeroU32>>(), size_of::<u32>());
+println!("{} {} {}", size_of::<Option<&u64>>(), size_of::<Option<Box<u64>>>(), size_of::<Option<NonZeroU32>>());   // prints: 8 8 4
+
+// Since None is promised to be zero, it's the null pointer, bit for bit.
+// (This is what lets a C function pointer that may be NULL be an
+// Option<extern "C" fn(..)> over FFI.)
+
+let none: Option<&u8> = None;
+println!("{}", unsafe { std::mem::transmute::<Option<&u8>, usize>(none) });   // prints: 0
+
+// A niche anywhere inside counts: a struct or tuple with a reference in
+// it has one, and so do String and Vec, whose pointer is never null.
+
+use std::mem::size_of;
+
+assert_eq!(size_of::<Option<(u32, &u8)>>(), size_of::<(u32, &u8)>());
+assert_eq!(size_of::<Option<String>>(), size_of::<String>());
+println!("{} {}", size_of::<String>(), size_of::<Option<String>>());   // prints: 24 24
+
+// NONZERO ----------------------------------------------------------------
+
+/*
+ * NonZeroU32 (and NonZeroU8, NonZeroI64, ..) is an integer that can't be
+ * 0: new gives None for a 0. That one missing value is a niche, so an id
+ * that's never 0 can be an Option for free, a table of them optional ids
+ * without a byte more. Rust 1.79 added NonZero<T>, the same types under
+ * one name.
+ */
+
+use std::mem::size_of;
+use std::num::NonZeroU32;
+
+#[derive(Debug, Clone, Copy)]
+struct Id(NonZeroU32);
+
+let ids = [NonZeroU32::new(7).map(Id), NonZeroU32::new(0).map(Id)];
+println!("{ids:?}");                        // prints: [Some(Id(7)), None]
+println!("{}", size_of::<[Option<Id>; 1000]>());   // prints: 4000
+
+// Getting the number back out is get().
+
+let id = NonZeroU32::new(42).unwrap();
+println!("{}", id.get() + 1);               // prints: 43
+
+// WHERE THE NICHES RUN OUT -----------------------------------------------
+
+/*
+ * A reference has one niche, the null: an Option of one uses it, and an
+ * Option around that has none left, so it gets a tag. A bool has 254
+ * spare values, and Options nest in it for a while. A type with no niche
+ * at all, f64 say, pays for the tag the first time.
+ */
+
+use std::mem::size_of;
+
+assert_eq!(size_of::<Option<Option<&u64>>>(), 16);
+assert_eq!(size_of::<Option<Option<bool>>>(), 1);
+assert_eq!(size_of::<Option<f64>>(), 16);
+println!("{} {}", size_of::<Option<&u64>>(), size_of::<Option<Option<&u64>>>());   // prints: 8 16
+
+/*
+ * So, for a field that may be empty:
+ *
+ *     Option<&T>, Option<Box<T>>     as cheap as a raw nullable pointer,
+ *                                    and None can't be dereferenced
+ *     Option<NonZeroU32>             when 0 can't be a real value anyway
+ *     Option<u32>, Option<f64>       the size doubles: in a large table a
+ *                                    sentinel, or a separate bitmap, may
+ *                                    be worth it
+ */
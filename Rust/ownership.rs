@@ -41,6 +41,38 @@ let s1 = String::from("new string");
 
 let s2 = s1.clone();                    // like deep copy
 
+/*
+ * The four lines above in memory (diagrams/memory.rs draws these from
+ * the lines themselves). The first s1 and s2 are shadowed, not gone:
+ * the "string" block is freed only at the end of the scope.
+ *
+ * In words (memory.rs --plain):
+ *     s1 (shadowed): moved to s2, so it holds nothing.
+ *     s2 (shadowed): a String, pointing to "string" on the heap: len 6, cap 6.
+ *     s1: a String, pointing to "new string" on the heap: len 10, cap 10.
+ *     s2: a String, pointing to "new string" on the heap: len 10, cap 10.
+ *
+ *      stack                              heap
+ *     ┌───────────────┬────────────┐
+ *     │ s1 (shadowed) │ moved → s2 │
+ *     ├───────────────┼────────────┤     ┌───┬───┬───┬───┬───┬───┐
+ *     │ s2 (shadowed) │ ptr        │ ──▶ │ s │ t │ r │ i │ n │ g │
+ *     │               │ len 6      │     └───┴───┴───┴───┴───┴───┘
+ *     │               │ cap 6      │
+ *     ├───────────────┼────────────┤     ┌───┬───┬───┬───┬───┬───┬───┬───┬───┬───┐
+ *     │ s1            │ ptr        │ ──▶ │ n │ e │ w │ ␣ │ s │ t │ r │ i │ n │ g │
+ *     │               │ len 10     │     └───┴───┴───┴───┴───┴───┴───┴───┴───┴───┘
+ *     │               │ cap 10     │
+ *     ├───────────────┼────────────┤     ┌───┬───┬───┬───┬───┬───┬───┬───┬───┬───┐
+ *     │ s2            │ ptr        │ ──▶ │ n │ e │ w │ ␣ │ s │ t │ r │ i │ n │ g │
+ *     │               │ len 10     │     └───┴───┴───┴───┴───┴───┴───┴───┴───┴───┘
+ *     │               │ cap 10     │
+ *     └───────────────┴────────────┘
+ */
+
+// (practice: quiz/packs/ownership.toml asks "is s1 still usable?" for
+//  many variations of the lines above)
+
 // Passing a variable to a function will move or copy (like assignment).
 
 // see the following........
@@ -115,6 +147,10 @@ calculate_len(&s1);                   // function call using reference
 
 // can't modify something we're borrowing
 
+fn change_borrowed(some_string: &String) {
+    some_string.push_str(", world");         // compile error: E0596
+}
+
 // mutable references allows modifying borrowed values
 
 fn main() {
@@ -147,6 +183,30 @@ println!("{r1} and {r2}");
 let r3 = &mut s; // no problem
 println!("{r3}");
 
+/*
+ * Each reference's scope as a bar, drawn by diagrams/borrows.rs: r1
+ * and r2 end on the println, so the &mut s after it overlaps nothing.
+ *
+ * In words (borrows.rs --plain):
+ *     No borrow overlaps a conflicting one.
+ *     r1: a shared borrow of s, lines 3 to 5.
+ *     r2: a shared borrow of s, lines 4 to 5.
+ *     r3: a mutable borrow of s, lines 8 to 9.
+ *
+ *      r1 r2 r3
+ *                  let mut s = String::from("hello");
+ *
+ *      ┬           let r1 = &s; // no problem
+ *      │  ┬        let r2 = &s; // no problem
+ *      ┴  ┴        println!("{r1} and {r2}");
+ *                  // variables r1 and r2 will not be used after this point
+ *
+ *            ┳     let r3 = &mut s; // no problem
+ *            ┻     println!("{r3}");
+ *
+ *      no borrow overlaps a conflicting one
+ */
+
 // SLICE ---------------------------------------------------------------
 
 // A string slice is a reference to part of a String
@@ -159,6 +219,8 @@ let girlfriend = &s[14..];           // [14..24]
 // Defining a function to take a string slice
 //          instead of a reference to a String makes our API
 //          more general and useful without losing any functionality
+//          (the same idea for slices, paths and constructors:
+//          [[api_design#borrow the slice, not the container]])
 
 fn funct(s: &str) -> &str {
     // body
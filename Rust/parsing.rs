@@ -0,0 +1,179 @@
+// ====================================================
+// PARSING STRINGS: str::parse AND FromStr ============
+// ====================================================
+
+/*
+ * Text comes in (a file, a command line, a form) and numbers are wanted.
+ * str::parse does it for any type that implements the trait FromStr:
+ * the integers, floats, bool, char, IpAddr, and your own types once
+ * they implement it. What it returns is a Result, since the text may
+ * not be a number at all.
+ *
+ * It brings three things together: strings ([[collections#updating a string]]
+ * for String and &str), errors ([[error_handling#propagating errors]]
+ * for ?), and a trait ([[traits#traits: defining shared behavior]]).
+ */
+
+// WHICH TYPE -------------------------------------------------------------
+
+// parse is generic over what it returns, so it has to be told: by the
+// type of the variable, or by the turbofish, ::<T> after the name.
+
+let a: i32 = "42".parse().unwrap();
+let b = "42".parse::<u8>().unwrap();
+let c = "2.5".parse::<f64>().unwrap();
+println!("{} {} {}", a, b, c);              // prints: 42 42 2.5
+
+// Without either, rustc can't guess:
+
+let n = "42".parse().unwrap();              // compile error: E0284
+
+// The type can come from further along, too: here from the function
+// the value is passed to.
+
+fn double(x: u64) -> u64 {
+    x * 2
+}
+
+let n = "21".parse().unwrap();
+println!("{}", double(n));                  // prints: 42
+
+// WHAT CAN GO WRONG ------------------------------------------------------
+
+/*
+ * Integers give a ParseIntError; its kind() says which way it went
+ * wrong. Nothing is trimmed: " 42" is an error, and "+42" isn't.
+ */
+
+use std::num::IntErrorKind;
+
+for text in ["42", " 42", "+42", "", "4x2", "300", "-1"] {
+    match text.parse::<u8>() {
+        Ok(n) => println!("{text:?}: {n}"),
+        Err(e) => println!("{text:?}: {:?}", e.kind()),
+    }
+}
+// prints: "42": 42
+// prints: " 42": InvalidDigit
+// prints: "+42": 42
+// prints: "": Empty
+// prints: "4x2": InvalidDigit
+// prints: "300": PosOverflow
+// prints: "-1": InvalidDigit
+
+// Floats give a ParseFloatError, and take more than digits: "1e3",
+// "inf", "NaN". Another base than 10 is from_str_radix.
+
+println!("{:?} {:?}", "1e3".parse::<f64>(), "inf".parse::<f64>());   // prints: Ok(1000.0) Ok(inf)
+println!("{:?}", u8::from_str_radix("ff", 16));   // prints: Ok(255)
+
+// A parser of your own should be as hard to crash as these: any text
+// gives an Ok or an Err. Finding the text that doesn't is what a fuzzer
+// is for: [[fuzzing#what a fuzzer does, by hand]]
+
+// WITH ? -----------------------------------------------------------------
+
+/*
+ * In a function that returns a Result, ? hands the error up. If the
+ * function's error type is ParseIntError, as is; if it's Box<dyn Error>,
+ * ? boxes it (From). Trim first for text from a file or a prompt: a line
+ * read with read_line still has its "\n".
+ */
+
+use std::num::ParseIntError;
+
+fn sum(text: &str) -> Result<i64, ParseIntError> {
+    let mut total = 0;
+    for word in text.split(',') {
+        total += word.trim().parse::<i64>()?;
+    }
+    Ok(total)
+}
+
+println!("{:?}", sum("1, 2, 3"));          // prints: Ok(6)
+println!("{:?}", sum("1, two, 3"));        // prints: Err(ParseIntError { kind: InvalidDigit })
+
+// Or collect straight into a Result: the first error stops it, like ?.
+
+let numbers: Result<Vec<u32>, _> = "4 8 15".split(' ').map(str::parse::<u32>).collect();
+println!("{numbers:?}");                    // prints: Ok([4, 8, 15])
+
+// And when a bad value should just be skipped, filter_map with ok().
+
+let good: Vec<u32> = "4 x 15".split(' ').filter_map(|w| w.parse().ok()).collect();
+println!("{good:?}");                       // prints: [4, 15]
+
+// FromStr FOR YOUR OWN TYPE ----------------------------------------------
+
+/*
+ * Implement FromStr, with the error type of your choosing, and parse
+ * works for the type like for a number. A rectangle written "3x4":
+ * split at the x, parse both sides, and say what was wrong if it fails.
+ */
+
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+struct Rectangle {
+    width: u32,
+    height: u32,
+}
+
+#[derive(Debug, PartialEq)]
+enum RectangleError {
+    NoX,                                    // nothing to split at
+    Side(ParseIntError),                    // a side that isn't a number
+}
+
+impl fmt::Display for RectangleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RectangleError::NoX => write!(f, "expected WIDTHxHEIGHT, like 3x4"),
+            RectangleError::Side(e) => write!(f, "bad side: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for RectangleError {}
+
+// lets ? turn a ParseIntError into a RectangleError
+impl From<ParseIntError> for RectangleError {
+    fn from(e: ParseIntError) -> Self {
+        RectangleError::Side(e)
+    }
+}
+
+impl FromStr for Rectangle {
+    type Err = RectangleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (width, height) = s.trim().split_once('x').ok_or(RectangleError::NoX)?;
+        Ok(Rectangle { width: width.parse()?, height: height.parse()? })
+    }
+}
+
+println!("{:?}", "3x4".parse::<Rectangle>());   // prints: Ok(Rectangle { width: 3, height: 4 })
+assert_eq!("3x4".parse(), Ok(Rectangle { width: 3, height: 4 }));
+assert_eq!(Rectangle::from_str("3 by 4"), Err(RectangleError::NoX));
+for bad in ["3 by 4", "3xfour"] {
+    println!("{}", bad.parse::<Rectangle>().unwrap_err());
+}
+// prints: expected WIDTHxHEIGHT, like 3x4
+// prints: bad side: invalid digit found in string
+
+/*
+ * Where it sits among the conversions:
+ *
+ *     FromStr        text -> T, can fail, and the one parse calls
+ *     TryFrom<&str>  the same, can fail, but parse doesn't use it
+ *     From<&str>     can't fail: String::from, not for parsing
+ *     Display        T -> text, the way back: parse(x.to_string())
+ *                    giving x again is a good property to have, and
+ *                    to test
+ *
+ * FromStr's from_str can't borrow from the text (it takes &str, but the
+ * result can't keep it): a type that should, a parser's token say,
+ * gets a function of its own.
+ */
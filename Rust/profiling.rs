@@ -0,0 +1,118 @@
+// ====================================================
+// PROFILING ==========================================
+// ====================================================
+
+/*
+ * Measure first, then optimize, and measure what the optimized build
+ * does: a debug build (cargo run) can be 10-50x slower, in different
+ * places. Two tools, for two questions:
+ *
+ *   (1) how long does it take?            std::time::Instant, a benchmark
+ *   (2) where does the time go?           a sampling profiler
+ *
+ * A sampling profiler stops the program ~1000 times a second and writes
+ * down the call stack each time. A function on 30% of the stacks was
+ * running, or had called what was running, 30% of the time. Nothing
+ * is instrumented, so the program runs at full speed.
+ */
+
+// (1) timing: the spell checker project already does this
+
+let start = std::time::Instant::now();
+let total: u64 = (0..1_000_000u64).map(|i| i * i).sum();
+println!("{total} in {:?}", start.elapsed());
+// in --release the compiler may compute the whole thing at compile time
+// and report 100ns: std::hint::black_box(x) hides a value from the
+// optimizer (diagrams/dispatch.rs uses it)
+
+/*
+ * (2) PROFILING A GUIDED PROJECT
+ *
+ * Cargo.toml needs, to have function names in the profile:
+ *     [profile.release]
+ *     debug = true                     # symbols; the code is just as fast
+ * and the stacks are only complete with frame pointers:
+ *     RUSTFLAGS="-C force-frame-pointers=yes" cargo build --release
+ *
+ * Then, on Linux:
+ *     cargo install flamegraph
+ *     cargo flamegraph --release -- document.txt     # writes flamegraph.svg
+ * or with perf itself:
+ *     perf record -g ./target/release/spell_checker document.txt
+ *     perf report                      # an interactive tree
+ *
+ * diagrams/profile.rs does all of that for a project in projects/:
+ *     ./profile spell_checker
+ * and prints the profile as a tree, which is what's read below.
+ */
+
+/*
+ * READING A FLAMEGRAPH
+ *
+ *    [edit_distance                     ]      [edits1  ]
+ *    [suggest_naive                        ][suggest_fast]
+ *    [main                                                ]
+ *
+ * - bottom to top: caller to callee; each box is a function, sitting on
+ *   the one that called it
+ * - the WIDTH of a box is the share of samples it's on: its total time
+ * - left to right is NOT time: boxes are sorted alphabetically, so the
+ *   same function called from the same place is merged into one box
+ * - the part of a box with nothing on top is its SELF time: the samples
+ *   where that function itself was running
+ *
+ * Look for a wide box with a flat top: that function is where the time
+ * goes. A wide box with a tall tower on it is just a caller; look
+ * higher. In the text view:
+ *
+ *     total    self
+ *     100.0%   0.0%  spell_checker::main
+ *      97.0%   0.4%  ├─ spell_checker::suggest_naive
+ *      96.1%  96.1%  │  └─ spell_checker::edit_distance
+ *       2.6%   0.3%  └─ spell_checker::suggest_fast
+ *       2.3%   2.3%     └─ spell_checker::edits1
+ *
+ * total is the width, self the flat top. main has 100% total and 0%
+ * self: everything happens under it, nothing in it. edit_distance has
+ * both: it's the function to make faster, or, better, to call less.
+ */
+
+/*
+ * THE SPELL CHECKER, WALKED THROUGH
+ *
+ * ./profile spell_checker, with /usr/share/dict/words installed (on
+ * the small built-in dictionary there's nothing to see):
+ *
+ * 1. suggest_naive and edit_distance should be most of the picture.
+ *    suggest_naive calls edit_distance once per dictionary word, for
+ *    every typo: with 100k words that's 600k tables of ~10x10 cells for
+ *    the six samples in main.
+ * 2. edit_distance's own code is small; what to look at is above it:
+ *    how often is it called? That is what suggest_fast changes: it
+ *    generates ~54n + 25 candidate strings and looks them up, so the
+ *    dictionary's size drops out.
+ * 3. In suggest_fast's box, edits1 and the String allocations under it
+ *    (alloc::..., format!) are what's left. That's the next thing to try
+ *    (one String buffer, reused), and the profile says whether it's
+ *    worth it: if suggest_fast is 3% of the run, it isn't.
+ *
+ * A name that's missing from the profile was probably inlined into its
+ * caller: in release, small functions don't get frames of their own.
+ * perf report --inline shows them, or #[inline(never)] while looking.
+ */
+
+/*
+ * WHAT A PROFILE DOESN'T SAY
+ * - time blocked on I/O, a lock or a sleep is off-CPU: perf samples
+ *   running code, so a program waiting on the network looks idle, not
+ *   slow (the downloader project is mostly that)
+ * - a function taking 90% of the time may already be as fast as it
+ *   gets; the fix is usually calling it less (an algorithm, a cache),
+ *   not making it faster
+ * - allocation shows up as malloc/free and alloc::raw_vec::finish_grow;
+ *   many small boxes of those mean Vec::with_capacity or reusing buffers
+ *
+ * Exercise: profile the brainfuck project on a long program, stage 1
+ * against stage 3 (run_optimized); the Add(5) folding should show as the
+ * interpreter loop's box getting narrower.
+ */
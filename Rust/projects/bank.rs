@@ -0,0 +1,196 @@
+// GUIDED PROJECT: CONCURRENT BANK TRANSFERS -------------------------------
+
+/*
+ * Many threads move money between accounts at the same time.
+ * Two things must hold:
+ * (1) no money is created or destroyed: the total never changes
+ * (2) the program never deadlocks
+ *
+ * Each account has its own Mutex, so transfers between unrelated
+ * accounts run in parallel. A transfer needs *two* locks, and that is
+ * where deadlock sneaks in.
+ *
+ * Uses: Arc, Mutex, thread::scope, lock ordering, a hand-rolled
+ *       property test (many random runs, one invariant)
+ *
+ * cargo new bank
+ * (copy this file into src/main.rs)
+ * cargo run --release
+ */
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// ACCOUNTS ----------------------------------------------------------------
+
+#[derive(Debug)]
+struct Account {
+    id: usize,                         // also decides the lock order
+    balance: Mutex<i64>,               // cents; i64 so a bug shows up as negative
+}
+
+#[derive(Debug, PartialEq)]
+enum TransferError {
+    SameAccount,
+    InsufficientFunds { needed: i64, available: i64 },
+}
+
+// THE DEADLOCK ------------------------------------------------------------
+
+/*
+ * The obvious version:
+ *
+ *     fn transfer(from: &Account, to: &Account, amount: i64) {
+ *         let mut a = from.balance.lock().unwrap();
+ *         let mut b = to.balance.lock().unwrap();
+ *         ...
+ *     }
+ *
+ * Thread 1: transfer(alice, bob, 10)   locks alice, waits for bob
+ * Thread 2: transfer(bob, alice, 5)    locks bob,   waits for alice
+ *
+ * Each holds the lock the other needs: both wait forever.
+ * The compiler cannot catch this; Rust prevents data races, not deadlocks.
+ *
+ * The fix: every thread takes locks in the same global order (here, by
+ * account id). Then a cycle of waiting threads is impossible: whoever
+ * holds the lower id can always go on to take the higher one.
+ */
+
+fn transfer(from: &Account, to: &Account, amount: i64) -> Result<(), TransferError> {
+    if from.id == to.id {
+        // locking the same Mutex twice on one thread deadlocks (or panics)
+        return Err(TransferError::SameAccount);
+    }
+
+    // lock in id order, whichever direction the money flows
+    let (first, second) = if from.id < to.id { (from, to) } else { (to, from) };
+    let mut first_balance = first.balance.lock().unwrap();
+    let mut second_balance = second.balance.lock().unwrap();
+
+    // then give the guards their roles back
+    let (from_balance, to_balance) = if from.id < to.id {
+        (&mut *first_balance, &mut *second_balance)
+    } else {
+        (&mut *second_balance, &mut *first_balance)
+    };
+
+    // check and update while holding both locks: nobody can sneak in
+    // between the check and the withdrawal
+    if *from_balance < amount {
+        return Err(TransferError::InsufficientFunds { needed: amount, available: *from_balance });
+    }
+    *from_balance -= amount;
+    *to_balance += amount;
+    Ok(())
+}                                      // both guards dropped: locks released
+
+// Summing locks the accounts one by one. That is only a consistent
+// snapshot when no transfers are running, which is how we use it.
+fn total(accounts: &[Account]) -> i64 {
+    accounts.iter().map(|a| *a.balance.lock().unwrap()).sum()
+}
+
+// THE SIMULATION ----------------------------------------------------------
+
+// xorshift: deterministic pseudo-random numbers from a seed, so a failing
+// run can be replayed exactly
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % n
+    }
+}
+
+struct Outcome {
+    succeeded: usize,
+    rejected: usize,
+}
+
+fn simulate(seed: u64, accounts: usize, threads: usize, transfers_per_thread: usize) -> (Arc<Vec<Account>>, Outcome) {
+    let bank: Arc<Vec<Account>> = Arc::new(
+        (0..accounts).map(|id| Account { id, balance: Mutex::new(1_000) }).collect(),
+    );
+
+    // thread::scope: threads may borrow from this stack frame and are all
+    // joined at the end of the scope. (Arc is kept so the result can
+    // outlive the function; inside the scope a &Vec would do.)
+    let outcomes: Vec<(usize, usize)> = thread::scope(|s| {
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                let bank = Arc::clone(&bank);
+                s.spawn(move || {
+                    let mut rng = Rng(seed.wrapping_mul(31).wrapping_add(t as u64) | 1);
+                    let (mut ok, mut rejected) = (0, 0);
+                    for _ in 0..transfers_per_thread {
+                        let from = &bank[rng.below(accounts as u64) as usize];
+                        let to = &bank[rng.below(accounts as u64) as usize];
+                        let amount = rng.below(400) as i64;
+                        match transfer(from, to, amount) {
+                            Ok(()) => ok += 1,
+                            Err(_) => rejected += 1,
+                        }
+                    }
+                    (ok, rejected)
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    let succeeded = outcomes.iter().map(|(ok, _)| ok).sum();
+    let rejected = outcomes.iter().map(|(_, r)| r).sum();
+    (bank, Outcome { succeeded, rejected })
+}
+
+// MAIN --------------------------------------------------------------------
+
+fn main() {
+    // unit checks
+    let alice = Account { id: 0, balance: Mutex::new(100) };
+    let bob = Account { id: 1, balance: Mutex::new(0) };
+    assert_eq!(transfer(&alice, &bob, 30), Ok(()));
+    assert_eq!(transfer(&bob, &alice, 10), Ok(()));       // reverse direction
+    assert_eq!(
+        transfer(&bob, &alice, 50),
+        Err(TransferError::InsufficientFunds { needed: 50, available: 20 })
+    );
+    assert_eq!(transfer(&alice, &alice, 1), Err(TransferError::SameAccount));
+    assert_eq!((*alice.balance.lock().unwrap(), *bob.balance.lock().unwrap()), (80, 20));
+
+    /*
+     * Property test: for many random seeds and shapes, the invariants
+     * hold. A crate like proptest would also shrink a failing case to a
+     * minimal one; the idea is the same.
+     *
+     * Few accounts + many threads = lots of contention and lots of
+     * opposite-direction transfers: exactly where a missing lock order
+     * would deadlock.
+     */
+    for seed in 1..=50u64 {
+        let accounts = 2 + (seed % 7) as usize;
+        let threads = 2 + (seed % 5) as usize;
+        let (bank, outcome) = simulate(seed, accounts, threads, 2_000);
+
+        assert_eq!(total(&bank), accounts as i64 * 1_000, "money leaked with seed {seed}");
+        assert!(bank.iter().all(|a| *a.balance.lock().unwrap() >= 0), "overdraft with seed {seed}");
+        assert_eq!(outcome.succeeded + outcome.rejected, threads * 2_000);
+    }
+    println!("50 random simulations: total conserved, no overdrafts, no deadlock");
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Replace the lock ordering with the naive version and run it in a
+ *     loop. How long until it hangs? (Add a watchdog thread that prints
+ *     a message if no progress is made for a second.)
+ * (2) total() is not atomic with respect to running transfers. Sketch
+ *     why, then fix it by locking all accounts in id order first.
+ * (3) Rewrite with a single Mutex<Vec<i64>>. Simpler, deadlock-free,
+ *     and slower under contention: measure it.
+ */
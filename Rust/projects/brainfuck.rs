@@ -0,0 +1,290 @@
+// GUIDED PROJECT: BRAINFUCK INTERPRETER -----------------------------------
+
+/*
+ * Brainfuck has eight commands operating on a tape of byte cells:
+ *
+ *     >   move the pointer right          <   move the pointer left
+ *     +   increment the current cell      -   decrement the current cell
+ *     .   output the current cell         ,   read one byte into the cell
+ *     [   if cell is 0, jump past the matching ]
+ *     ]   if cell is not 0, jump back to the matching [
+ *
+ * Everything else is a comment.
+ *
+ * Stage 1: a straightforward interpreter (tape as Vec<u8>)
+ * Stage 2: precomputed bracket matching (a stack of indices)
+ * Stage 3: an optimizing pass that folds runs like +++++ into Add(5)
+ *
+ * Uses: enums, match, Vec as a stack, wrapping arithmetic,
+ *       Read/Write trait objects for IO
+ *
+ * cargo new brainfuck
+ * (copy this file into src/main.rs)
+ * cargo run --release
+ */
+
+use std::io::{self, Read, Write};
+
+const TAPE_LEN: usize = 30_000;        // the traditional tape size
+
+// STAGE 1 AND 2: PARSE, MATCH BRACKETS, INTERPRET -------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Command {
+    Right,
+    Left,
+    Inc,
+    Dec,
+    Output,
+    Input,
+    JumpIfZero(usize),                 // index of the matching JumpUnlessZero
+    JumpUnlessZero(usize),             // index of the matching JumpIfZero
+}
+
+#[derive(Debug, PartialEq)]
+enum BfError {
+    UnmatchedOpen(usize),              // byte offset in the source
+    UnmatchedClose(usize),
+    PointerOutOfRange,
+}
+
+// Matching brackets once, up front, with a stack:
+//     push the index of every [, pop it at the next ]
+// Afterwards every jump is O(1) instead of a scan through the program.
+fn parse(source: &str) -> Result<Vec<Command>, BfError> {
+    let mut program = Vec::new();
+    let mut open = Vec::new();         // (program index, source offset)
+
+    for (offset, byte) in source.bytes().enumerate() {
+        let command = match byte {
+            b'>' => Command::Right,
+            b'<' => Command::Left,
+            b'+' => Command::Inc,
+            b'-' => Command::Dec,
+            b'.' => Command::Output,
+            b',' => Command::Input,
+            b'[' => {
+                open.push((program.len(), offset));
+                Command::JumpIfZero(0)             // patched below
+            }
+            b']' => {
+                let (start, _) = open.pop().ok_or(BfError::UnmatchedClose(offset))?;
+                let here = program.len();
+                program[start] = Command::JumpIfZero(here);
+                Command::JumpUnlessZero(start)
+            }
+            _ => continue,                         // comment
+        };
+        program.push(command);
+    }
+
+    match open.pop() {
+        Some((_, offset)) => Err(BfError::UnmatchedOpen(offset)),
+        None => Ok(program),
+    }
+}
+
+// input and output are trait objects: stdin/stdout for real runs,
+// byte slices and Vec<u8> for checking results
+fn run(program: &[Command], input: &mut dyn Read, output: &mut dyn Write) -> Result<(), BfError> {
+    let mut tape = vec![0u8; TAPE_LEN];
+    let mut ptr = 0usize;
+    let mut pc = 0usize;               // program counter
+
+    while pc < program.len() {
+        match program[pc] {
+            Command::Right => {
+                ptr += 1;
+                if ptr == TAPE_LEN {
+                    return Err(BfError::PointerOutOfRange);
+                }
+            }
+            // checked_sub: moving left of cell 0 is an error, not a panic
+            Command::Left => ptr = ptr.checked_sub(1).ok_or(BfError::PointerOutOfRange)?,
+            // cells are u8 and wrap: 255 + 1 == 0 and 0 - 1 == 255
+            // (plain + would panic on overflow in debug builds)
+            Command::Inc => tape[ptr] = tape[ptr].wrapping_add(1),
+            Command::Dec => tape[ptr] = tape[ptr].wrapping_sub(1),
+            Command::Output => output.write_all(&[tape[ptr]]).expect("write failed"),
+            Command::Input => {
+                let mut byte = [0u8];
+                // end of input leaves 0 in the cell (a common convention)
+                tape[ptr] = match input.read(&mut byte) {
+                    Ok(1) => byte[0],
+                    _ => 0,
+                };
+            }
+            Command::JumpIfZero(target) if tape[ptr] == 0 => pc = target,
+            Command::JumpUnlessZero(target) if tape[ptr] != 0 => pc = target,
+            Command::JumpIfZero(_) | Command::JumpUnlessZero(_) => (),
+        }
+        pc += 1;
+    }
+
+    Ok(())
+}
+
+// STAGE 3: AN OPTIMIZING PASS ---------------------------------------------
+
+/*
+ * Real programs are full of runs such as +++++++ and >>>>, and the
+ * idiom [-] which just sets a cell to zero. Folding them into single
+ * operations with a count speeds the interpreter up several times.
+ */
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Move(isize),                       // >>> is Move(3), << is Move(-2)
+    Add(u8),                           // wrapping: --- is Add(253)
+    Clear,                             // [-] or [+]
+    Output,
+    Input,
+    JumpIfZero(usize),
+    JumpUnlessZero(usize),
+}
+
+fn optimize(program: &[Command]) -> Vec<Op> {
+    let mut ops: Vec<Op> = Vec::new();
+    let mut open = Vec::new();
+    let mut i = 0;
+
+    while i < program.len() {
+        // look for [-] and [+] first, a three-command window
+        if let [Command::JumpIfZero(_), Command::Inc | Command::Dec, Command::JumpUnlessZero(_), ..] = program[i..] {
+            ops.push(Op::Clear);
+            i += 3;
+            continue;
+        }
+
+        let op = match program[i] {
+            Command::Right | Command::Left => Op::Move(0),
+            Command::Inc | Command::Dec => Op::Add(0),
+            Command::Output => Op::Output,
+            Command::Input => Op::Input,
+            Command::JumpIfZero(_) => {
+                open.push(ops.len());
+                Op::JumpIfZero(0)
+            }
+            Command::JumpUnlessZero(_) => {
+                // brackets were validated by parse(), so this never fails
+                let start = open.pop().expect("brackets already matched");
+                ops[start] = Op::JumpIfZero(ops.len());
+                Op::JumpUnlessZero(start)
+            }
+        };
+
+        // fold a run of moves or adds into the op we just created
+        let op = match op {
+            Op::Move(_) | Op::Add(_) => {
+                let mut delta: isize = 0;
+                while let Some(c) = program.get(i) {
+                    match (op, c) {
+                        (Op::Move(_), Command::Right) | (Op::Add(_), Command::Inc) => delta += 1,
+                        (Op::Move(_), Command::Left) | (Op::Add(_), Command::Dec) => delta -= 1,
+                        _ => break,
+                    }
+                    i += 1;
+                }
+                i -= 1;                                  // the loop overshoots by one
+                match op {
+                    Op::Move(_) => Op::Move(delta),
+                    _ => Op::Add(delta.rem_euclid(256) as u8),
+                }
+            }
+            other => other,
+        };
+
+        ops.push(op);
+        i += 1;
+    }
+
+    ops
+}
+
+fn run_optimized(ops: &[Op], input: &mut dyn Read, output: &mut dyn Write) -> Result<(), BfError> {
+    let mut tape = vec![0u8; TAPE_LEN];
+    let mut ptr = 0usize;
+    let mut pc = 0usize;
+
+    while pc < ops.len() {
+        match ops[pc] {
+            Op::Move(delta) => {
+                ptr = ptr
+                    .checked_add_signed(delta)
+                    .filter(|&p| p < TAPE_LEN)
+                    .ok_or(BfError::PointerOutOfRange)?;
+            }
+            Op::Add(n) => tape[ptr] = tape[ptr].wrapping_add(n),
+            Op::Clear => tape[ptr] = 0,
+            Op::Output => output.write_all(&[tape[ptr]]).expect("write failed"),
+            Op::Input => {
+                let mut byte = [0u8];
+                tape[ptr] = match input.read(&mut byte) {
+                    Ok(1) => byte[0],
+                    _ => 0,
+                };
+            }
+            Op::JumpIfZero(target) if tape[ptr] == 0 => pc = target,
+            Op::JumpUnlessZero(target) if tape[ptr] != 0 => pc = target,
+            Op::JumpIfZero(_) | Op::JumpUnlessZero(_) => (),
+        }
+        pc += 1;
+    }
+
+    Ok(())
+}
+
+// USING IT ----------------------------------------------------------------
+
+const HELLO: &str = "++++++++[>++++[>++>+++>+++>+<<<<-]>+>+>->>+[<]<-]>>.>---.+++++++..+++.>>.<-.<.+++.------.--------.>>+.>++.";
+
+// reverses its input: read bytes until 0 (end of input), then print backwards
+const REVERSE: &str = ">,[>,]<[.<]";
+
+fn main() {
+    let program = parse(HELLO).unwrap();
+
+    let mut out = Vec::new();
+    run(&program, &mut io::empty(), &mut out).unwrap();
+    assert_eq!(out, b"Hello World!\n");
+
+    // the optimized version must behave identically
+    let ops = optimize(&program);
+    let mut out_opt = Vec::new();
+    run_optimized(&ops, &mut io::empty(), &mut out_opt).unwrap();
+    assert_eq!(out, out_opt);
+    println!("{} commands folded into {} ops", program.len(), ops.len());
+
+    // &[u8] implements Read, handy for feeding input
+    let mut out = Vec::new();
+    run(&parse(REVERSE).unwrap(), &mut &b"stressed"[..], &mut out).unwrap();
+    assert_eq!(out, b"desserts");
+
+    assert_eq!(parse("[[]"), Err(BfError::UnmatchedOpen(0)));
+    assert_eq!(parse("+]"), Err(BfError::UnmatchedClose(1)));
+    assert_eq!(run(&parse("<").unwrap(), &mut io::empty(), &mut io::sink()), Err(BfError::PointerOutOfRange));
+
+    // the real thing: a program file from the command line, stdin/stdout
+    if let Some(path) = std::env::args().nth(1) {
+        let source = std::fs::read_to_string(&path).expect("could not read program");
+        match parse(&source) {
+            Ok(program) => {
+                let ops = optimize(&program);
+                if let Err(e) = run_optimized(&ops, &mut io::stdin(), &mut io::stdout()) {
+                    eprintln!("runtime error: {e:?}");
+                }
+            }
+            Err(e) => eprintln!("parse error: {e:?}"),
+        }
+    }
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Time both interpreters with std::time::Instant on a heavy program
+ *     (e.g. a mandelbrot.bf from the web) in --release mode.
+ * (2) Add Op::AddTo(offset) for the "move value" idiom [->+<].
+ * (3) Instead of interpreting, emit C source from Vec<Op> and compile it
+ *     with gcc: a tiny compiler.
+ */
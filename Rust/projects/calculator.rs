@@ -0,0 +1,358 @@
+// GUIDED PROJECT: EXPRESSION CALCULATOR -----------------------------------
+
+/*
+ * A calculator for arithmetic expressions such as
+ *
+ *     1 + 2 * 3          => 7
+ *     -(4 - 10) ^ 2 / 4  => 9
+ *     2 ^ 3 ^ 2          => 512   (^ is right associative)
+ *
+ * built in four stages:
+ * (1) tokenizer:  &str          -> Vec<Token>    (every token has a span)
+ * (2) parser:     Vec<Token>    -> Expr          (Pratt parser)
+ * (3) evaluator:  Expr          -> f64
+ * (4) errors:     point at the offending span with a caret
+ *
+ * and fuzzed: fuzz/fuzz_targets/calculate.rs feeds it generated input,
+ * through the langscape crate's calculator module (this file), and the
+ * nesting limit in the parser is what that found (fuzzing.rs)
+ *
+ * Uses: enums with data, Box for recursive types, match, Result and ?,
+ *       Peekable iterators, impl Display
+ *
+ * cargo new calculator
+ * (copy this file into src/main.rs)
+ * cargo run
+ */
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+// SPANS AND ERRORS --------------------------------------------------------
+
+// a span is a byte range into the source text: [start, end)
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Span {
+    start: usize,
+    end: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CalcError {
+    message: String,
+    span: Span,
+}
+
+impl CalcError {
+    fn new(message: impl Into<String>, span: Span) -> Self {
+        CalcError { message: message.into(), span }
+    }
+
+    // print the source, then a caret line under the bad part
+    //
+    //     1 + * 2
+    //         ^ expected a number or '(', found '*'
+    fn report(&self, source: &str) -> String {
+        let width = (self.span.end - self.span.start).max(1);
+        format!(
+            "{}\n{}{} {}",
+            source,
+            " ".repeat(self.span.start),
+            "^".repeat(width),
+            self.message
+        )
+    }
+}
+
+// STAGE 1: TOKENIZER ------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TokenKind {
+    Number(f64),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    LParen,
+    RParen,
+    Eof,                                 // marks the end, simplifies the parser
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    span: Span,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TokenKind::Number(n) => write!(f, "{n}"),
+            TokenKind::Plus      => write!(f, "'+'"),
+            TokenKind::Minus     => write!(f, "'-'"),
+            TokenKind::Star      => write!(f, "'*'"),
+            TokenKind::Slash     => write!(f, "'/'"),
+            TokenKind::Caret     => write!(f, "'^'"),
+            TokenKind::LParen    => write!(f, "'('"),
+            TokenKind::RParen    => write!(f, "')'"),
+            TokenKind::Eof       => write!(f, "end of input"),
+        }
+    }
+}
+
+// char_indices() yields (byte offset, char): exactly what spans need
+fn tokenize(source: &str) -> Result<Vec<Token>, CalcError> {
+    let mut tokens = Vec::new();
+    let mut chars: Peekable<CharIndices> = source.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        let kind = match c {
+            ' ' | '\t' => {
+                chars.next();
+                continue;                  // skip whitespace, no token
+            }
+            '+' => TokenKind::Plus,
+            '-' => TokenKind::Minus,
+            '*' => TokenKind::Star,
+            '/' => TokenKind::Slash,
+            '^' => TokenKind::Caret,
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            '0'..='9' | '.' => {
+                // consume the whole literal, then parse the slice
+                let mut end = start;
+                while let Some(&(i, d)) = chars.peek() {
+                    if d.is_ascii_digit() || d == '.' {
+                        end = i + d.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let span = Span { start, end };
+                let value = source[start..end]
+                    .parse::<f64>()
+                    .map_err(|_| CalcError::new("malformed number", span))?;
+                tokens.push(Token { kind: TokenKind::Number(value), span });
+                continue;                  // already advanced past the literal
+            }
+            other => {
+                let span = Span { start, end: start + other.len_utf8() };
+                return Err(CalcError::new(format!("unexpected character '{other}'"), span));
+            }
+        };
+        chars.next();
+        tokens.push(Token { kind, span: Span { start, end: start + 1 } });
+    }
+
+    let end = source.len();
+    tokens.push(Token { kind: TokenKind::Eof, span: Span { start: end, end } });
+    Ok(tokens)
+}
+
+// STAGE 2: AST AND PARSER -------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinOp { Add, Sub, Mul, Div, Pow }
+
+// Expr is recursive, so the children must live behind a pointer (Box),
+// otherwise the size of Expr would be infinite
+#[derive(Debug, PartialEq)]
+enum Expr {
+    Number(f64),
+    Negate(Box<Expr>),
+    Binary { op: BinOp, lhs: Box<Expr>, rhs: Box<Expr>, span: Span },
+}
+
+/*
+ * Pratt parsing (precedence climbing) in one sentence:
+ *     parse a prefix (number, unary minus, parenthesis), then keep
+ *     absorbing infix operators as long as they bind tighter than
+ *     the caller's minimum binding power.
+ *
+ * Binding powers (left, right):
+ *     + -      (1, 2)      left associative:  left < right
+ *     * /      (3, 4)
+ *     ^        (6, 5)      right associative: left > right
+ *     unary -  7
+ */
+
+fn infix_binding_power(kind: TokenKind) -> Option<(u8, u8, BinOp)> {
+    match kind {
+        TokenKind::Plus  => Some((1, 2, BinOp::Add)),
+        TokenKind::Minus => Some((1, 2, BinOp::Sub)),
+        TokenKind::Star  => Some((3, 4, BinOp::Mul)),
+        TokenKind::Slash => Some((3, 4, BinOp::Div)),
+        TokenKind::Caret => Some((6, 5, BinOp::Pow)),
+        _ => None,
+    }
+}
+
+const PREFIX_MINUS_POWER: u8 = 7;
+
+// Each '(' and each unary '-' is a call to expr() inside the one before,
+// so "((((..." as deep as the input is long would overflow the stack,
+// which aborts the program rather than panicking. The fuzz target
+// found it, with 111 minuses; past this depth it's an error instead.
+const MAX_DEPTH: usize = 100;
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,                      // calls to expr() still running
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Parser { tokens, pos: 0, depth: 0 }
+    }
+
+    fn peek(&self) -> Token {
+        self.tokens[self.pos]              // Token is Copy, no borrow kept
+    }
+
+    fn bump(&mut self) -> Token {
+        let token = self.peek();
+        if token.kind != TokenKind::Eof {
+            self.pos += 1;
+        }
+        token
+    }
+
+    // entry point: a whole expression followed by end of input
+    fn parse(mut self) -> Result<Expr, CalcError> {
+        let expr = self.expr(0)?;
+        let next = self.peek();
+        match next.kind {
+            TokenKind::Eof => Ok(expr),
+            other => Err(CalcError::new(format!("expected an operator, found {other}"), next.span)),
+        }
+    }
+
+    fn expr(&mut self, min_power: u8) -> Result<Expr, CalcError> {
+        if self.depth == MAX_DEPTH {
+            return Err(CalcError::new("expression nested too deeply", self.peek().span));
+        }
+        self.depth += 1;                   // not undone on an error: that ends the parse
+        let mut lhs = self.prefix()?;
+
+        loop {
+            let op_token = self.peek();
+            let Some((left, right, op)) = infix_binding_power(op_token.kind) else {
+                break;                     // not an infix operator: we're done
+            };
+            if left < min_power {
+                break;                     // binds too loosely for this level
+            }
+            self.bump();
+            let rhs = self.expr(right)?;
+            lhs = Expr::Binary { op, lhs: Box::new(lhs), rhs: Box::new(rhs), span: op_token.span };
+        }
+
+        self.depth -= 1;
+        Ok(lhs)
+    }
+
+    fn prefix(&mut self) -> Result<Expr, CalcError> {
+        let token = self.bump();
+        match token.kind {
+            TokenKind::Number(n) => Ok(Expr::Number(n)),
+            TokenKind::Minus => {
+                let operand = self.expr(PREFIX_MINUS_POWER)?;
+                Ok(Expr::Negate(Box::new(operand)))
+            }
+            TokenKind::LParen => {
+                let inner = self.expr(0)?;
+                let close = self.bump();
+                if close.kind != TokenKind::RParen {
+                    return Err(CalcError::new(
+                        format!("expected ')' to close '(' at {}, found {}", token.span.start, close.kind),
+                        close.span,
+                    ));
+                }
+                Ok(inner)
+            }
+            other => Err(CalcError::new(format!("expected a number or '(', found {other}"), token.span)),
+        }
+    }
+}
+
+// STAGE 3: EVALUATION -----------------------------------------------------
+
+// a recursive walk of the tree; match makes every variant explicit
+fn eval(expr: &Expr) -> Result<f64, CalcError> {
+    match expr {
+        Expr::Number(n) => Ok(*n),
+        Expr::Negate(inner) => Ok(-eval(inner)?),
+        Expr::Binary { op, lhs, rhs, span } => {
+            let (a, b) = (eval(lhs)?, eval(rhs)?);
+            match op {
+                BinOp::Add => Ok(a + b),
+                BinOp::Sub => Ok(a - b),
+                BinOp::Mul => Ok(a * b),
+                BinOp::Div if b == 0.0 => Err(CalcError::new("division by zero", *span)),
+                BinOp::Div => Ok(a / b),
+                BinOp::Pow => Ok(a.powf(b)),
+            }
+        }
+    }
+}
+
+// STAGE 4: PUTTING IT TOGETHER --------------------------------------------
+
+// each stage returns Result, so ? chains them; pub for the fuzz target
+pub fn calculate(source: &str) -> Result<f64, CalcError> {
+    let tokens = tokenize(source)?;
+    let expr = Parser::new(tokens).parse()?;
+    eval(&expr)
+}
+
+fn main() {
+    assert_eq!(calculate("1 + 2 * 3"), Ok(7.0));
+    assert_eq!(calculate("(1 + 2) * 3"), Ok(9.0));
+    assert_eq!(calculate("-(4 - 10) ^ 2 / 4"), Ok(9.0));
+    assert_eq!(calculate("2 ^ 3 ^ 2"), Ok(512.0));      // 2 ^ (3 ^ 2)
+    assert_eq!(calculate("10 - 4 - 3"), Ok(3.0));       // (10 - 4) - 3
+    assert_eq!(calculate(&format!("{}1", "-".repeat(99))), Ok(-1.0));   // 99 and the outermost: 100 deep
+    assert!(calculate(&format!("{}1", "-".repeat(111))).is_err());   // the fuzzer's find
+    assert!(calculate(&"(".repeat(100_000)).is_err());
+
+    // errors carry spans that point into the input
+    let err = calculate("1 + * 2").unwrap_err();
+    assert_eq!(err.span, Span { start: 4, end: 5 });
+    println!("{}\n", err.report("1 + * 2"));
+
+    let err = calculate("8 / (2 - 2)").unwrap_err();
+    println!("{}\n", err.report("8 / (2 - 2)"));
+
+    // a small REPL: one expression per line, Ctrl-D to quit
+    let stdin = io::stdin();
+    print!("> ");
+    io::stdout().flush().unwrap();
+    for line in stdin.lock().lines() {
+        let line = line.expect("failed to read stdin");
+        if !line.trim().is_empty() {
+            match calculate(&line) {
+                Ok(value) => println!("{value}"),
+                Err(err)  => println!("{}", err.report(&line)),
+            }
+        }
+        print!("> ");
+        io::stdout().flush().unwrap();
+    }
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Add the % operator with the same precedence as * and /.
+ * (2) Add variables: `let x = 3` stores into a HashMap<String, f64>,
+ *     and identifiers in expressions look it up (error with a span
+ *     if undefined).
+ * (3) Add function calls such as sqrt(16) and max(1, 2).
+ * (4) Replace f64 with an enum Value { Int(i64), Float(f64) } so that
+ *     7 / 2 gives 3 while 7.0 / 2 gives 3.5.
+ */
@@ -0,0 +1,212 @@
+// GUIDED PROJECT: CSV ANALYTICS -------------------------------------------
+
+/*
+ * Read a sales export, compute per-region and per-product aggregates,
+ * and write the summary back out as CSV: the everyday data-munging task.
+ *
+ *     date,region,product,units,unit_price
+ *     2024-01-03,north,widget,12,2.50
+ *     ...
+ *
+ * Pieces:
+ * (1) csv + serde: each row deserializes straight into a struct
+ * (2) bad rows are collected as errors with their line number,
+ *     not fatal
+ * (3) grouping with the HashMap entry API (the word-count pattern
+ *     from collections.rs, with a struct as the value)
+ * (4) writing a Vec of structs back out with csv::Writer
+ *
+ * cargo new csv_analytics
+ * (copy this file into src/main.rs)
+ *
+ * Cargo.toml:
+ * [dependencies]
+ * csv = "1"
+ * serde = { version = "1", features = ["derive"] }
+ *
+ * cargo run -- sales.csv summary.csv
+ */
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+// ROWS --------------------------------------------------------------------
+
+// field names match the CSV header; serde does the parsing,
+// so "12" becomes a u32 and "2.50" an f64 (or an error with a position)
+#[derive(Debug, Deserialize)]
+struct Sale {
+    date: String,
+    region: String,
+    product: String,
+    units: u32,
+    unit_price: f64,
+}
+
+impl Sale {
+    fn revenue(&self) -> f64 {
+        self.units as f64 * self.unit_price
+    }
+}
+
+// READING -----------------------------------------------------------------
+
+// generic over any reader: a File in main, a &[u8] in the checks
+fn read_sales<R: Read>(input: R) -> (Vec<Sale>, Vec<String>) {
+    let mut reader = csv::Reader::from_reader(input);
+    let mut sales = Vec::new();
+    let mut errors = Vec::new();
+
+    for result in reader.deserialize::<Sale>() {
+        match result {
+            Ok(sale) => sales.push(sale),
+            // csv::Error knows where it happened
+            Err(e) => {
+                let line = e.position().map(|p| p.line()).unwrap_or(0);
+                errors.push(format!("line {line}: {e}"));
+            }
+        }
+    }
+    (sales, errors)
+}
+
+// AGGREGATING -------------------------------------------------------------
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Totals {
+    orders: u32,
+    units: u32,
+    revenue: f64,
+}
+
+impl Totals {
+    fn add(&mut self, sale: &Sale) {
+        self.orders += 1;
+        self.units += sale.units;
+        self.revenue += sale.revenue();
+    }
+}
+
+/*
+ * Group by any key: pass a closure that extracts it.
+ *
+ * entry(key).or_default() returns &mut Totals, inserting
+ * Totals::default() the first time a key is seen. One lookup per row,
+ * no "if contains_key then get_mut else insert" dance.
+ *
+ * The 'a ties the closure's argument to the slice, so a key may borrow
+ * from a row (|s| s.region.as_str()). With a plain Fn(&Sale) -> K the
+ * closure would have to work for *any* lifetime, and a borrowed key
+ * could not escape it.
+ */
+fn group_by<'a, K, F>(sales: &'a [Sale], key: F) -> HashMap<K, Totals>
+where
+    K: std::hash::Hash + Eq,
+    F: Fn(&'a Sale) -> K,
+{
+    let mut groups: HashMap<K, Totals> = HashMap::new();
+    for sale in sales {
+        groups.entry(key(sale)).or_default().add(sale);
+    }
+    groups
+}
+
+// WRITING -----------------------------------------------------------------
+
+#[derive(Debug, Serialize)]
+struct SummaryRow {
+    region: String,
+    product: String,
+    orders: u32,
+    units: u32,
+    revenue: String,                   // pre-formatted to 2 decimals
+    average_price: String,
+}
+
+fn summarize(sales: &[Sale]) -> Vec<SummaryRow> {
+    // a tuple of borrowed strings works as a key: no cloning while grouping
+    let groups = group_by(sales, |s| (s.region.as_str(), s.product.as_str()));
+
+    let mut rows: Vec<SummaryRow> = groups
+        .into_iter()
+        .map(|((region, product), t)| SummaryRow {
+            region: region.to_string(),
+            product: product.to_string(),
+            orders: t.orders,
+            units: t.units,
+            revenue: format!("{:.2}", t.revenue),
+            average_price: format!("{:.2}", t.revenue / t.units as f64),
+        })
+        .collect();
+
+    // HashMap order is arbitrary; sort for stable output
+    rows.sort_by(|a, b| (&a.region, &a.product).cmp(&(&b.region, &b.product)));
+    rows
+}
+
+fn write_summary<W: Write>(rows: &[SummaryRow], output: W) -> Result<(), Box<dyn Error>> {
+    let mut writer = csv::Writer::from_writer(output);
+    for row in rows {
+        writer.serialize(row)?;        // the header is written automatically
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+// MAIN --------------------------------------------------------------------
+
+const SAMPLE: &str = "\
+date,region,product,units,unit_price
+2024-01-03,north,widget,12,2.50
+2024-01-03,south,widget,4,2.50
+2024-01-04,north,gadget,1,19.99
+2024-01-05,north,widget,8,2.25
+2024-01-05,south,gadget,two,19.99
+2024-01-06,south,gadget,3,18.00
+";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let (sales, errors) = read_sales(SAMPLE.as_bytes());
+    assert_eq!(sales.len(), 5);
+    assert_eq!(errors.len(), 1);       // "two" is not a u32
+    assert!(errors[0].starts_with("line 6"), "{errors:?}");
+
+    let by_region = group_by(&sales, |s| s.region.clone());
+    assert_eq!(by_region["north"], Totals { orders: 3, units: 21, revenue: 30.0 + 19.99 + 18.0 });
+
+    let busiest_day = group_by(&sales, |s| s.date.as_str())
+        .into_iter()
+        .max_by_key(|(_, t)| t.units)
+        .map(|(date, _)| date);
+    assert_eq!(busiest_day, Some("2024-01-03"));
+
+    let mut out = Vec::new();
+    write_summary(&summarize(&sales), &mut out)?;
+    print!("{}", String::from_utf8(out)?);
+
+    // real files: csv_analytics <input.csv> <output.csv>
+    let args: Vec<String> = std::env::args().collect();
+    if let [_, input, output] = args.as_slice() {
+        let (sales, errors) = read_sales(std::fs::File::open(input)?);
+        for e in &errors {
+            eprintln!("skipped {e}");
+        }
+        write_summary(&summarize(&sales), std::fs::File::create(output)?)?;
+        println!("{} rows summarized into {output}", sales.len());
+    }
+    Ok(())
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Parse `date` into a proper type with #[serde(deserialize_with)]
+ *     or the chrono crate, and group by month.
+ * (2) Store money as integer cents (u64) instead of f64. Why do
+ *     accountants insist on this?
+ * (3) Stream a 10 GB file: group_by already only needs one pass, so
+ *     feed it rows from the reader instead of collecting a Vec first.
+ */
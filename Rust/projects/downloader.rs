@@ -0,0 +1,281 @@
+// GUIDED PROJECT: ASYNC DOWNLOAD MANAGER ----------------------------------
+
+/*
+ * Download a list of URLs concurrently:
+ * (1) at most N downloads at a time  (a Semaphore, as in the port scanner)
+ * (2) retries with exponential backoff for errors worth retrying
+ * (3) one progress bar per download  (indicatif)
+ * (4) a final report: which succeeded, which failed and why
+ *
+ * The theme is error handling in async code: every failure is a value
+ * (Result) that we classify, retry or report; nothing is unwrapped on
+ * the network path.
+ *
+ * cargo new downloader
+ * (copy this file into src/main.rs)
+ *
+ * Cargo.toml:
+ * [dependencies]
+ * reqwest = "0.12"
+ * tokio = { version = "1", features = ["full"] }
+ * indicatif = "0.17"
+ *
+ * cargo run --release -- out/ https://example.com/a.zip https://example.com/b.zip
+ */
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use reqwest::{Client, StatusCode};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+const MAX_PARALLEL: usize = 4;
+const MAX_ATTEMPTS: u32 = 4;
+const FIRST_BACKOFF: Duration = Duration::from_millis(200);
+
+// ERRORS ------------------------------------------------------------------
+
+/*
+ * Not all errors are equal:
+ *     a 503 or a dropped connection may well work next time  -> retry
+ *     a 404 or a disk error will not                         -> give up
+ * Encoding that in the type makes the retry loop a simple match.
+ */
+#[derive(Debug)]
+enum DownloadError {
+    Http(StatusCode),
+    Network(reqwest::Error),
+    Io(std::io::Error),
+}
+
+impl DownloadError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            DownloadError::Http(status) => status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS,
+            DownloadError::Network(e) => e.is_timeout() || e.is_connect() || e.is_request() || e.is_body(),
+            DownloadError::Io(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DownloadError::Http(status) => write!(f, "server answered {status}"),
+            DownloadError::Network(e) => write!(f, "network error: {e}"),
+            DownloadError::Io(e) => write!(f, "could not write file: {e}"),
+        }
+    }
+}
+
+// From impls let ? convert the library errors for us
+impl From<reqwest::Error> for DownloadError {
+    fn from(e: reqwest::Error) -> Self {
+        DownloadError::Network(e)
+    }
+}
+
+impl From<std::io::Error> for DownloadError {
+    fn from(e: std::io::Error) -> Self {
+        DownloadError::Io(e)
+    }
+}
+
+// ONE ATTEMPT -------------------------------------------------------------
+
+// Streams the body to disk chunk by chunk: memory use stays flat no
+// matter how big the file is.
+async fn attempt(client: &Client, url: &str, dest: &Path, bar: &ProgressBar) -> Result<u64, DownloadError> {
+    let mut response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(DownloadError::Http(response.status()));
+    }
+
+    // Content-Length may be missing: then the bar becomes a spinner
+    match response.content_length() {
+        Some(len) => bar.set_length(len),
+        None => bar.set_style(ProgressStyle::default_spinner()),
+    }
+    bar.set_position(0);
+
+    let mut file = tokio::fs::File::create(dest).await?;
+    let mut written = 0;
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk).await?;
+        written += chunk.len() as u64;
+        bar.set_position(written);
+    }
+    file.flush().await?;
+    Ok(written)
+}
+
+// RETRIES -----------------------------------------------------------------
+
+// backoff doubles after each failure: 200ms, 400ms, 800ms, ...
+async fn download(client: &Client, url: &str, dest: &Path, bar: &ProgressBar) -> Result<u64, DownloadError> {
+    let mut backoff = FIRST_BACKOFF;
+    let mut attempt_no = 1;
+    loop {
+        match attempt(client, url, dest, bar).await {
+            Ok(bytes) => return Ok(bytes),
+            Err(e) if e.is_retryable() && attempt_no < MAX_ATTEMPTS => {
+                bar.set_message(format!("retry {attempt_no} after {e}"));
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt_no += 1;
+            }
+            Err(e) => {
+                // don't leave half-written files behind
+                let _ = tokio::fs::remove_file(dest).await;
+                return Err(e);
+            }
+        }
+    }
+}
+
+// THE MANAGER -------------------------------------------------------------
+
+fn file_name(url: &str) -> String {
+    let name = url.rsplit('/').next().unwrap_or("");
+    if name.is_empty() { "index.html".to_string() } else { name.to_string() }
+}
+
+/*
+ * Client is cheap to clone (an Arc inside) and should be shared: it
+ * keeps a connection pool, so downloads from the same host reuse
+ * connections.
+ */
+async fn download_all(urls: Vec<String>, out_dir: PathBuf, progress: MultiProgress) -> Vec<(String, Result<u64, DownloadError>)> {
+    let client = Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .expect("default TLS backend available");
+    let limit = Arc::new(Semaphore::new(MAX_PARALLEL));
+    let style = ProgressStyle::with_template("{prefix:>16} [{bar:30}] {bytes}/{total_bytes} {msg}")
+        .expect("valid template")
+        .progress_chars("=> ");
+
+    let mut tasks = JoinSet::new();
+    for url in urls {
+        let (client, limit) = (client.clone(), limit.clone());
+        let dest = out_dir.join(file_name(&url));
+        let bar = progress.add(ProgressBar::new(0).with_style(style.clone()).with_prefix(file_name(&url)));
+
+        tasks.spawn(async move {
+            // tasks are spawned at once but wait here for a slot
+            let _permit = limit.acquire_owned().await.expect("semaphore never closed");
+            let result = download(&client, &url, &dest, &bar).await;
+            match &result {
+                Ok(_) => bar.finish_with_message("done"),
+                Err(e) => bar.abandon_with_message(format!("failed: {e}")),
+            }
+            (url, result)
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        results.push(joined.expect("download task panicked"));
+    }
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    results
+}
+
+// A TEST SERVER -----------------------------------------------------------
+
+/*
+ * A deliberately tiny HTTP server for the self-check:
+ *     /ok/<n>   n bytes
+ *     /flaky    503 twice, then 200
+ *     anything else 404
+ * Real projects would reach for axum or wiremock here.
+ */
+async fn test_server() -> String {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tokio::io::AsyncReadExt;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let flaky_hits = Arc::new(AtomicU32::new(0));
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else { continue };
+            let flaky_hits = flaky_hits.clone();
+            tokio::spawn(async move {
+                let mut buf = [0u8; 1024];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+                let (status, body) = if let Some(n) = path.strip_prefix("/ok/") {
+                    ("200 OK", "x".repeat(n.parse().unwrap_or(0)))
+                } else if path == "/flaky" && flaky_hits.fetch_add(1, Ordering::SeqCst) < 2 {
+                    ("503 Service Unavailable", String::new())
+                } else if path == "/flaky" {
+                    ("200 OK", "finally".to_string())
+                } else {
+                    ("404 Not Found", String::new())
+                };
+                let response = format!("HTTP/1.1 {status}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}", body.len());
+                let _ = socket.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    base
+}
+
+// MAIN --------------------------------------------------------------------
+
+#[tokio::main]
+async fn main() {
+    // self-check against the local server
+    let base = test_server().await;
+    let out = std::env::temp_dir().join(format!("downloads_{}", std::process::id()));
+    tokio::fs::create_dir_all(&out).await.unwrap();
+
+    let urls = vec![format!("{base}/ok/100000"), format!("{base}/flaky"), format!("{base}/missing")];
+    let results = download_all(urls, out.clone(), MultiProgress::new()).await;
+
+    for (url, result) in &results {
+        match result {
+            Ok(bytes) => println!("{url}: {bytes} bytes"),
+            Err(e) => println!("{url}: {e}"),
+        }
+    }
+    // results are sorted by url: flaky, missing, ok
+    assert_eq!(results[0].1.as_ref().ok(), Some(&7));                                  // retried twice
+    assert!(matches!(results[1].1, Err(DownloadError::Http(StatusCode::NOT_FOUND))));   // not retried
+    assert_eq!(results[2].1.as_ref().ok(), Some(&100_000));
+    assert_eq!(std::fs::read_to_string(out.join("flaky")).unwrap(), "finally");
+    tokio::fs::remove_dir_all(&out).await.unwrap();
+
+    // downloader <out dir> <url>...
+    let mut args = std::env::args().skip(1);
+    if let Some(dir) = args.next() {
+        let dir = PathBuf::from(dir);
+        tokio::fs::create_dir_all(&dir).await.expect("cannot create output directory");
+        let results = download_all(args.collect(), dir, MultiProgress::new()).await;
+        let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+        println!("{} downloaded, {failed} failed", results.len() - failed);
+    }
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Add jitter to the backoff (a random 0-50% extra) and explain why
+ *     a thousand clients retrying in lockstep is a problem.
+ * (2) Resume partial downloads with a Range: bytes=<n>- header when the
+ *     file already exists.
+ * (3) Honour a Retry-After header on 429/503 responses.
+ * (4) Test download()'s retries without test_server: put attempt()
+ *     behind a trait and the sleep behind a parameter, as in
+ *     test_doubles.rs, and check a 503, 503, 200 is tried three times
+ *     with 200ms and 400ms between, in no time at all.
+ */
@@ -0,0 +1,186 @@
+// GUIDED PROJECT: DUPLICATE FILE FINDER -----------------------------------
+
+/*
+ * Find files with identical contents under a directory.
+ *
+ * Hashing every file is wasteful: two files can only be equal if they
+ * have the same size. So, in three passes:
+ * (1) walk the tree, group paths by file size       (cheap: metadata only)
+ * (2) hash only the files whose size is shared      (rayon, in parallel)
+ * (3) group by (size, hash), keep groups of 2 or more, print a report
+ *
+ * Uses: std::fs and Path/PathBuf, recursion, HashMap and the entry API,
+ *       iterator chains, rayon's par_iter, io::Result with ?
+ *
+ * cargo new duplicate_finder
+ * (copy this file into src/main.rs)
+ *
+ * Cargo.toml:
+ * [dependencies]
+ * rayon = "1"
+ *
+ * cargo run --release -- ~/Downloads
+ */
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::hash::Hasher;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+// PASS 1: WALK THE TREE ---------------------------------------------------
+
+/*
+ * A recursive walk. Unreadable entries are reported and skipped rather
+ * than aborting the whole scan: one permission error in a subdirectory
+ * should not throw away everything else we found.
+ *
+ * symlink_metadata does not follow symlinks, so a link pointing back up
+ * the tree cannot send us around in circles.
+ */
+fn walk(dir: &Path, by_size: &mut HashMap<u64, Vec<PathBuf>>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("skipping {}: {e}", dir.display());
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {                  // flatten skips Err entries
+        let path = entry.path();
+        let Ok(meta) = fs::symlink_metadata(&path) else { continue };
+
+        if meta.is_dir() {
+            walk(&path, by_size);
+        } else if meta.is_file() && meta.len() > 0 {
+            by_size.entry(meta.len()).or_default().push(path);
+        }
+    }
+}
+
+// PASS 2: HASH CANDIDATES IN PARALLEL -------------------------------------
+
+// Read in fixed-size chunks so a 4 GB file doesn't need 4 GB of memory.
+//
+// DefaultHasher (SipHash, 64 bits) keeps this dependency-free; for real
+// use pick a cryptographic hash such as blake3 or sha2 (see exercises).
+fn hash_file(path: &Path) -> io::Result<u64> {
+    let mut file = File::open(path)?;
+    let mut hasher = DefaultHasher::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.write(&buffer[..n]);
+    }
+    Ok(hasher.finish())
+}
+
+// PASS 3: GROUP -----------------------------------------------------------
+
+fn find_duplicates(root: &Path) -> Vec<(u64, Vec<PathBuf>)> {
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    walk(root, &mut by_size);
+
+    // only sizes shared by at least two files are candidates
+    let candidates: Vec<(u64, PathBuf)> = by_size
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .flat_map(|(size, paths)| paths.into_iter().map(move |p| (size, p)))
+        .collect();
+
+    // par_iter: the same iterator chain, but rayon spreads the items over
+    // a thread pool. Hashing is IO + CPU bound per file and the files are
+    // independent, so this parallelizes perfectly.
+    let hashed: Vec<((u64, u64), PathBuf)> = candidates
+        .into_par_iter()
+        .filter_map(|(size, path)| match hash_file(&path) {
+            Ok(hash) => Some(((size, hash), path)),
+            Err(e) => {
+                eprintln!("skipping {}: {e}", path.display());
+                None
+            }
+        })
+        .collect();
+
+    // back on one thread: group with the entry API
+    let mut groups: HashMap<(u64, u64), Vec<PathBuf>> = HashMap::new();
+    for (key, path) in hashed {
+        groups.entry(key).or_default().push(path);
+    }
+
+    let mut duplicates: Vec<(u64, Vec<PathBuf>)> = groups
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|((size, _), mut paths)| {
+            paths.sort();
+            (size, paths)
+        })
+        .collect();
+
+    // biggest waste first: size * (copies - 1)
+    duplicates.sort_by_key(|(size, paths)| std::cmp::Reverse(size * (paths.len() as u64 - 1)));
+    duplicates
+}
+
+// REPORT ------------------------------------------------------------------
+
+fn human(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+fn print_report(duplicates: &[(u64, Vec<PathBuf>)]) {
+    let mut wasted = 0;
+    for (size, paths) in duplicates {
+        println!("{} x {}:", paths.len(), human(*size));
+        for path in paths {
+            println!("    {}", path.display());
+        }
+        wasted += size * (paths.len() as u64 - 1);
+    }
+    println!("{} groups, {} reclaimable", duplicates.len(), human(wasted));
+}
+
+// MAIN --------------------------------------------------------------------
+
+fn main() -> io::Result<()> {
+    // self-check on a scratch directory
+    let scratch = std::env::temp_dir().join(format!("dupes_{}", std::process::id()));
+    fs::create_dir_all(scratch.join("nested"))?;
+    fs::write(scratch.join("a.txt"), "same contents")?;
+    fs::write(scratch.join("nested/b.txt"), "same contents")?;
+    fs::write(scratch.join("c.txt"), "same length!!")?;    // same size, different bytes
+    fs::write(scratch.join("d.txt"), "unique")?;
+
+    let found = find_duplicates(&scratch);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].1, [scratch.join("a.txt"), scratch.join("nested/b.txt")]);
+    fs::remove_dir_all(&scratch)?;
+
+    let root = std::env::args().nth(1).unwrap_or_else(|| ".".to_string());
+    print_report(&find_duplicates(Path::new(&root)));
+    Ok(())
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Swap DefaultHasher for blake3 and compare the running time.
+ * (2) Add a cheap pass between size and full hash: hash only the first
+ *     4 KiB. Most same-size files differ early.
+ * (3) Add --delete that keeps the first path of each group and replaces
+ *     the others with hard links (std::fs::hard_link).
+ */
@@ -0,0 +1,248 @@
+// GUIDED PROJECT: HUFFMAN COMPRESSION -------------------------------------
+
+/*
+ * Huffman coding gives frequent bytes short bit codes and rare bytes
+ * long ones. For "abracadabra":
+ *
+ *     a: 5 times  -> 0
+ *     b: 2        -> 110
+ *     r: 2        -> 111        (exact codes depend on tie-breaking)
+ *     c: 1        -> 100
+ *     d: 1        -> 101
+ *
+ *     11 bytes = 88 bits  ->  23 bits
+ *
+ * Pieces, each a collection from collections.rs (or its neighbours):
+ * (1) HashMap<u8, usize>    byte frequencies
+ * (2) BinaryHeap            repeatedly take the two rarest subtrees
+ * (3) Box<Node> tree enum   the code tree itself (recursive type)
+ * (4) HashMap<u8, Vec<bool>> the code table, built by walking the tree
+ * (5) BitWriter/BitReader   packing bools into bytes
+ *
+ * cargo new huffman
+ * (copy this file into src/main.rs)
+ * cargo run
+ */
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
+
+// THE TREE ----------------------------------------------------------------
+
+// Leaves hold a byte; internal nodes hold two children.
+// Box is required: without it, Node would contain itself and have
+// infinite size.
+#[derive(Debug)]
+enum Node {
+    Leaf(u8),
+    Internal(Box<Node>, Box<Node>),
+}
+
+/*
+ * BinaryHeap is a max-heap: pop() returns the greatest element. We want
+ * the *least* frequent first, so we wrap the weight in Reverse.
+ *
+ * Node itself has no sensible order, so the heap entry is a small
+ * struct that orders by (weight, tie-breaker) only. The tie-breaker
+ * (insertion order) makes the tree, and so the output, deterministic.
+ */
+struct Entry {
+    weight: Reverse<usize>,
+    order: Reverse<usize>,
+    node: Node,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Entry {}
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.weight, self.order).cmp(&(other.weight, other.order))
+    }
+}
+
+fn frequencies(data: &[u8]) -> HashMap<u8, usize> {
+    let mut freq = HashMap::new();
+    for &byte in data {
+        *freq.entry(byte).or_insert(0) += 1;     // the word-count idiom
+    }
+    freq
+}
+
+fn build_tree(freq: &HashMap<u8, usize>) -> Option<Node> {
+    // sort the leaves first: HashMap iteration order is random
+    let mut leaves: Vec<(u8, usize)> = freq.iter().map(|(&b, &w)| (b, w)).collect();
+    leaves.sort();
+
+    let mut heap = BinaryHeap::new();
+    let mut order = 0;
+    for (byte, weight) in leaves {
+        heap.push(Entry { weight: Reverse(weight), order: Reverse(order), node: Node::Leaf(byte) });
+        order += 1;
+    }
+
+    // merge the two lightest trees until one is left
+    while heap.len() > 1 {
+        let a = heap.pop()?;
+        let b = heap.pop()?;
+        let weight = a.weight.0 + b.weight.0;
+        let node = Node::Internal(Box::new(a.node), Box::new(b.node));
+        heap.push(Entry { weight: Reverse(weight), order: Reverse(order), node });
+        order += 1;
+    }
+
+    heap.pop().map(|entry| entry.node)           // None for empty input
+}
+
+// left edge = 0 (false), right edge = 1 (true)
+fn build_codes(node: &Node, prefix: &mut Vec<bool>, codes: &mut HashMap<u8, Vec<bool>>) {
+    match node {
+        // a tree with a single leaf still needs a 1-bit code
+        Node::Leaf(byte) if prefix.is_empty() => {
+            codes.insert(*byte, vec![false]);
+        }
+        Node::Leaf(byte) => {
+            codes.insert(*byte, prefix.clone());
+        }
+        Node::Internal(left, right) => {
+            prefix.push(false);
+            build_codes(left, prefix, codes);
+            prefix.pop();
+            prefix.push(true);
+            build_codes(right, prefix, codes);
+            prefix.pop();
+        }
+    }
+}
+
+// BIT-LEVEL I/O -----------------------------------------------------------
+
+// Collects bits most-significant first into bytes.
+struct BitWriter {
+    bytes: Vec<u8>,
+    filled: u8,                        // bits used in the last byte, 0..8
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        BitWriter { bytes: Vec::new(), filled: 0 }
+    }
+
+    fn write_bit(&mut self, bit: bool) {
+        if self.filled == 0 {
+            self.bytes.push(0);
+        }
+        if bit {
+            let last = self.bytes.last_mut().expect("pushed above");
+            *last |= 1 << (7 - self.filled);
+        }
+        self.filled = (self.filled + 1) % 8;
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,                   // in bits
+}
+
+// an Iterator over bits: decoding becomes a simple for loop
+impl<'a> Iterator for BitReader<'a> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        let byte = self.bytes.get(self.position / 8)?;
+        let bit = byte & (1 << (7 - self.position % 8)) != 0;
+        self.position += 1;
+        Some(bit)
+    }
+}
+
+// COMPRESS AND DECOMPRESS -------------------------------------------------
+
+/*
+ * A real file format must also store the tree (or the frequencies);
+ * here we return the tree alongside the bits to keep the focus on the
+ * algorithm. Storing it is exercise (1).
+ */
+struct Compressed {
+    tree: Option<Node>,
+    bits: usize,                       // the last byte may be partly padding
+    bytes: Vec<u8>,
+}
+
+fn compress(data: &[u8]) -> Compressed {
+    let tree = build_tree(&frequencies(data));
+    let mut codes = HashMap::new();
+    if let Some(root) = &tree {
+        build_codes(root, &mut Vec::new(), &mut codes);
+    }
+
+    let mut writer = BitWriter::new();
+    let mut bits = 0;
+    for byte in data {
+        for &bit in &codes[byte] {
+            writer.write_bit(bit);
+            bits += 1;
+        }
+    }
+    Compressed { tree, bits, bytes: writer.bytes }
+}
+
+fn decompress(compressed: &Compressed) -> Vec<u8> {
+    let Some(root) = &compressed.tree else { return Vec::new() };
+    let reader = BitReader { bytes: &compressed.bytes, position: 0 };
+
+    let mut out = Vec::new();
+    let mut node = root;
+    for bit in reader.take(compressed.bits) {    // take(): ignore the padding
+        // walk down one edge per bit; emit and restart at each leaf
+        if let Node::Internal(left, right) = node {
+            node = if bit { right } else { left };
+        }
+        if let Node::Leaf(byte) = node {
+            out.push(*byte);
+            node = root;
+        }
+    }
+    out
+}
+
+// MAIN --------------------------------------------------------------------
+
+fn main() {
+    for text in ["abracadabra", "aaaaaaa", "", "the quick brown fox jumps over the lazy dog"] {
+        let compressed = compress(text.as_bytes());
+        assert_eq!(decompress(&compressed), text.as_bytes());
+        println!("{:>44}: {:>3} bits -> {:>3} bits", format!("{text:?}"), text.len() * 8, compressed.bits);
+    }
+
+    let compressed = compress(b"abracadabra");
+    assert_eq!(compressed.bits, 23);
+
+    // a skewed distribution compresses far better than uniform data
+    let skewed: Vec<u8> = (0..10_000u32).map(|i| if i % 10 == 0 { b'x' } else { b'.' }).collect();
+    let uniform: Vec<u8> = (0..10_000u32).map(|i| (i % 256) as u8).collect();
+    for (name, data) in [("skewed", &skewed), ("uniform", &uniform)] {
+        let c = compress(data);
+        assert_eq!(&decompress(&c), data);
+        println!("{name:>8}: {:.1}% of original", 100.0 * c.bytes.len() as f64 / data.len() as f64);
+    }
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Serialize the tree (pre-order: 0 = internal, 1 + byte = leaf) in
+ *     front of the bits, so compress returns a single Vec<u8>.
+ * (2) Turn this into a CLI: huffman compress <in> <out> / decompress.
+ * (3) Canonical Huffman codes: store only the code *lengths* and rebuild
+ *     the codes from them. How many bytes does the header shrink to?
+ */
@@ -0,0 +1,231 @@
+// GUIDED PROJECT: A REUSABLE LEXER OVER &str ------------------------------
+
+/*
+ * A lexer (tokenizer) that never copies the input: every token is a
+ * slice of the source string, so a Token borrows from the source.
+ *
+ * This is the Excerpt example from generics.rs put to work:
+ *
+ *     struct Excerpt<'a> { part: &'a str }
+ *
+ * becomes
+ *
+ *     struct Token<'src> { kind: TokenKind, text: &'src str, ... }
+ *
+ * and a token cannot outlive the source text it points into.
+ *
+ * Uses: lifetimes in structs and impls, implementing Iterator,
+ *       matching on chars and ranges, Option/Result
+ *
+ * cargo new lexer
+ * (copy this file into src/main.rs)
+ * cargo run
+ */
+
+use std::fmt;
+
+// TOKENS ------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TokenKind {
+    Ident,
+    Keyword,
+    Number,
+    Str,                               // text excludes the quotes
+    Punct,
+}
+
+// 'src: the token is only valid while the source string is alive
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Token<'src> {
+    kind: TokenKind,
+    text: &'src str,
+    line: usize,                       // 1-based, for error messages
+    column: usize,
+}
+
+#[derive(Debug, PartialEq)]
+struct LexError {
+    message: &'static str,             // messages are literals: 'static
+    line: usize,
+    column: usize,
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+// THE LEXER ---------------------------------------------------------------
+
+/*
+ * Two lifetimes, because the lexer borrows two unrelated things:
+ *     'src  the text being tokenized
+ *     'kw   the keyword table (a language definition)
+ *
+ * Tokens only ever point into 'src. If we had used a single 'a for both,
+ * tokens would be needlessly tied to the keyword table as well.
+ */
+struct Lexer<'src, 'kw> {
+    rest: &'src str,                   // the not-yet-consumed tail
+    keywords: &'kw [&'kw str],
+    punct: &'kw [&'kw str],            // longest first: "==" before "="
+    line: usize,
+    column: usize,
+}
+
+impl<'src, 'kw> Lexer<'src, 'kw> {
+    fn new(source: &'src str, keywords: &'kw [&'kw str], punct: &'kw [&'kw str]) -> Self {
+        Lexer { rest: source, keywords, punct, line: 1, column: 1 }
+    }
+
+    // split off the first `len` bytes of rest, keeping line/column in sync
+    fn take(&mut self, len: usize) -> &'src str {
+        let (taken, rest) = self.rest.split_at(len);
+        for c in taken.chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.rest = rest;
+        taken                          // still borrows from 'src, not from self
+    }
+
+    // length in bytes of the prefix whose chars satisfy pred
+    fn prefix_len(&self, pred: impl Fn(char) -> bool) -> usize {
+        self.rest
+            .char_indices()
+            .find(|&(_, c)| !pred(c))
+            .map(|(i, _)| i)
+            .unwrap_or(self.rest.len())
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        loop {
+            let ws = self.prefix_len(char::is_whitespace);
+            self.take(ws);
+            if self.rest.starts_with("//") {
+                let line_len = self.rest.find('\n').unwrap_or(self.rest.len());
+                self.take(line_len);
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn error(&self, message: &'static str) -> LexError {
+        LexError { message, line: self.line, column: self.column }
+    }
+}
+
+// Implementing Iterator gives us for loops, collect, filter, ... for free.
+// The Item mentions 'src: what we hand out borrows from the source.
+impl<'src, 'kw> Iterator for Lexer<'src, 'kw> {
+    type Item = Result<Token<'src>, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.skip_whitespace_and_comments();
+
+        let first = self.rest.chars().next()?;          // None: end of input
+        let (line, column) = (self.line, self.column);
+
+        let (kind, len) = match first {
+            'a'..='z' | 'A'..='Z' | '_' => {
+                let len = self.prefix_len(|c| c.is_alphanumeric() || c == '_');
+                let word = &self.rest[..len];
+                let kind = if self.keywords.contains(&word) {
+                    TokenKind::Keyword
+                } else {
+                    TokenKind::Ident
+                };
+                (kind, len)
+            }
+            '0'..='9' => (TokenKind::Number, self.prefix_len(|c| c.is_ascii_digit() || c == '.' || c == '_')),
+            '"' => {
+                // find the closing quote after the opening one
+                let Some(close) = self.rest[1..].find('"') else {
+                    let err = self.error("unterminated string literal");
+                    self.rest = "";                      // stop lexing
+                    return Some(Err(err));
+                };
+                self.take(1);                            // opening quote
+                let text = self.take(close);
+                self.take(1);                            // closing quote
+                return Some(Ok(Token { kind: TokenKind::Str, text, line, column }));
+            }
+            _ => match self.punct.iter().find(|p| self.rest.starts_with(**p)) {
+                Some(p) => (TokenKind::Punct, p.len()),
+                None => {
+                    let err = self.error("unexpected character");
+                    self.take(first.len_utf8());         // skip it and carry on
+                    return Some(Err(err));
+                }
+            },
+        };
+
+        let text = self.take(len);
+        Some(Ok(Token { kind, text, line, column }))
+    }
+}
+
+// USING IT ----------------------------------------------------------------
+
+// a "language definition": just two slices, nothing lexer-specific
+const TOY_KEYWORDS: &[&str] = &["let", "fn", "if", "else", "return"];
+const TOY_PUNCT: &[&str] = &["==", "!=", "<=", ">=", "->", "=", "+", "-", "*", "/", "<", ">", "(", ")", "{", "}", ",", ";"];
+
+// returns tokens borrowing from `source`: with one reference in, the
+// &strs out get its lifetime (elided; spelled out, `<'a>(source: &'a
+// str) -> Vec<&'a str>`)
+fn identifiers(source: &str) -> Vec<&str> {
+    Lexer::new(source, TOY_KEYWORDS, TOY_PUNCT)
+        .filter_map(Result::ok)
+        .filter(|t| t.kind == TokenKind::Ident)
+        .map(|t| t.text)
+        .collect()
+}
+
+fn main() {
+    let program = String::from(
+        "fn add(a, b) -> num {\n    // sum\n    return a + b;\n}\nlet greeting = \"hi there\";",
+    );
+
+    for token in Lexer::new(&program, TOY_KEYWORDS, TOY_PUNCT) {
+        match token {
+            Ok(t)  => println!("{:>2}:{:<2} {:<8} {:?}", t.line, t.column, format!("{:?}", t.kind), t.text),
+            Err(e) => println!("error: {e}"),
+        }
+    }
+
+    assert_eq!(identifiers(&program), ["add", "a", "b", "num", "a", "b", "greeting"]);
+
+    // errors do not stop the iterator (except an unterminated string)
+    let errors: Vec<LexError> = Lexer::new("let x = 1 $ 2;", TOY_KEYWORDS, TOY_PUNCT)
+        .filter_map(Result::err)
+        .collect();
+    assert_eq!(errors, [LexError { message: "unexpected character", line: 1, column: 11 }]);
+
+    // The borrow checker in action: tokens cannot outlive the source.
+    //
+    // let tokens: Vec<Token>;
+    // {
+    //     let temp = String::from("let y = 2;");
+    //     tokens = Lexer::new(&temp, TOY_KEYWORDS, TOY_PUNCT).filter_map(Result::ok).collect();
+    // }                                     // temp dropped here
+    // println!("{:?}", tokens);             // error[E0597]: `temp` does not live long enough
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Support escape sequences (\" and \n) in string literals. Why can't
+ *     the token text stay a plain &'src str once you unescape?
+ *     (hint: look up std::borrow::Cow<'src, str>)
+ * (2) Add block comments /* ... */, reporting an error when unterminated.
+ * (3) Feed this lexer into the calculator project instead of its own
+ *     tokenizer.
+ */
@@ -0,0 +1,225 @@
+// GUIDED PROJECT: CONWAY'S GAME OF LIFE -----------------------------------
+
+/*
+ * Rules, applied to every cell at once:
+ *     a live cell with 2 or 3 live neighbours stays alive
+ *     a dead cell with exactly 3 live neighbours becomes alive
+ *     every other cell is dead in the next generation
+ *
+ * Pieces:
+ * (1) a Grid type: one flat Vec<bool> indexed by (row, col)
+ * (2) neighbour counting on a torus (the edges wrap around)
+ * (3) double buffering: read from `cells`, write into `next`, swap
+ * (4) a terminal renderer
+ * (5) a rayon version that computes rows in parallel, and a timing
+ *     comparison of the two
+ *
+ * cargo new life
+ * (copy this file into src/main.rs)
+ *
+ * Cargo.toml:
+ * [dependencies]
+ * rayon = "1"
+ *
+ * cargo run --release          (always time things in release mode)
+ */
+
+use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+
+// THE GRID ----------------------------------------------------------------
+
+/*
+ * Why a flat Vec<bool> and not Vec<Vec<bool>>?
+ *     one allocation instead of one per row,
+ *     rows sit next to each other in memory (cache friendly),
+ *     and chunks_mut(width) hands out rows as slices, which is
+ *     exactly what rayon needs later on.
+ */
+#[derive(Clone, PartialEq)]
+struct Grid {
+    width: usize,
+    height: usize,
+    cells: Vec<bool>,
+    next: Vec<bool>,                   // the second buffer
+}
+
+impl Grid {
+    fn new(width: usize, height: usize) -> Self {
+        Grid { width, height, cells: vec![false; width * height], next: vec![false; width * height] }
+    }
+
+    // build from a picture: '#' is alive, anything else is dead
+    fn from_str(width: usize, height: usize, picture: &str) -> Self {
+        let mut grid = Grid::new(width, height);
+        for (row, line) in picture.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                grid.set(row, col, c == '#');
+            }
+        }
+        grid
+    }
+
+    fn index(&self, row: usize, col: usize) -> usize {
+        row * self.width + col
+    }
+
+    fn get(&self, row: usize, col: usize) -> bool {
+        self.cells[self.index(row, col)]
+    }
+
+    fn set(&mut self, row: usize, col: usize, alive: bool) {
+        let i = self.index(row, col);
+        self.cells[i] = alive;
+    }
+
+    fn alive_count(&self) -> usize {
+        self.cells.iter().filter(|&&c| c).count()
+    }
+
+    // Fill with pseudo-random cells (a tiny LCG, no dependency needed)
+    fn randomize(&mut self, mut seed: u64) {
+        for cell in self.cells.iter_mut() {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            *cell = (seed >> 33).is_multiple_of(4); // about 25% alive
+        }
+    }
+}
+
+// NEIGHBOURS AND RULES ----------------------------------------------------
+
+// a free function over a slice rather than a method on &self:
+// the parallel version can call it while `next` is mutably borrowed
+fn live_neighbours(cells: &[bool], width: usize, height: usize, row: usize, col: usize) -> u8 {
+    let mut count = 0;
+    // adding height - 1 instead of subtracting 1 avoids usize underflow;
+    // % then wraps the edges around
+    for dr in [height - 1, 0, 1] {
+        for dc in [width - 1, 0, 1] {
+            if dr == 0 && dc == 0 {
+                continue;                          // the cell itself
+            }
+            let r = (row + dr) % height;
+            let c = (col + dc) % width;
+            count += cells[r * width + c] as u8;   // true as u8 == 1
+        }
+    }
+    count
+}
+
+fn rule(alive: bool, neighbours: u8) -> bool {
+    matches!((alive, neighbours), (true, 2) | (true, 3) | (false, 3))
+}
+
+// one row of the next generation, written into `out`
+fn step_row(cells: &[bool], width: usize, height: usize, row: usize, out: &mut [bool]) {
+    for (col, cell) in out.iter_mut().enumerate() {
+        let n = live_neighbours(cells, width, height, row, col);
+        *cell = rule(cells[row * width + col], n);
+    }
+}
+
+impl Grid {
+    // Double buffering: every cell of the next generation must be computed
+    // from the *current* generation. Updating in place would let early
+    // cells see already-updated neighbours.
+    fn step(&mut self) {
+        let (w, h) = (self.width, self.height);
+        for (row, out) in self.next.chunks_mut(w).enumerate() {
+            step_row(&self.cells, w, h, row, out);
+        }
+        std::mem::swap(&mut self.cells, &mut self.next);   // O(1): swaps pointers
+    }
+
+    // The same loop with par_chunks_mut: rayon splits the rows across
+    // threads. The borrow checker is what makes this safe:
+    //     `cells` is only read    (shared borrow, fine to share)
+    //     each row of `next` goes to exactly one closure (disjoint &mut)
+    fn par_step(&mut self) {
+        let (w, h) = (self.width, self.height);
+        let cells = &self.cells;
+        self.next
+            .par_chunks_mut(w)
+            .enumerate()
+            .for_each(|(row, out)| step_row(cells, w, h, row, out));
+        std::mem::swap(&mut self.cells, &mut self.next);
+    }
+}
+
+// RENDERING ---------------------------------------------------------------
+
+// implementing Display lets println!("{grid}") and format! work
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.cells.chunks(self.width) {
+            let line: String = row.iter().map(|&c| if c { '#' } else { '.' }).collect();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+fn animate(grid: &mut Grid, generations: usize) {
+    for generation in 0..generations {
+        // \x1b[2J clears the screen, \x1b[H moves the cursor home
+        println!("\x1b[2J\x1b[H{grid}generation {generation}, alive {}", grid.alive_count());
+        grid.step();
+        thread::sleep(Duration::from_millis(80));
+    }
+}
+
+// BENCHMARKING ------------------------------------------------------------
+
+fn time_it(label: &str, generations: usize, grid: &mut Grid, step: fn(&mut Grid)) -> Duration {
+    let start = Instant::now();
+    for _ in 0..generations {
+        step(grid);
+    }
+    let elapsed = start.elapsed();
+    println!("{label:>10}: {generations} generations in {elapsed:?}");
+    elapsed
+}
+
+fn main() {
+    // a glider: after 4 generations the same shape, moved one cell diagonally
+    let mut glider = Grid::from_str(6, 6, ".#\n..#\n###");
+    let moved = Grid::from_str(6, 6, "\n..#\n...#\n.###");
+    for _ in 0..4 {
+        glider.step();
+    }
+    assert_eq!(glider.cells, moved.cells);
+    assert!(glider.get(1, 2) && !glider.get(0, 1));
+
+    // a blinker oscillates with period 2, in both versions
+    let mut a = Grid::from_str(5, 5, "\n\n.###");
+    let mut b = a.clone();
+    a.step();
+    b.par_step();
+    assert_eq!(a.cells, b.cells);
+    assert_eq!(a.to_string(), ".....\n..#..\n..#..\n..#..\n.....\n");
+
+    animate(&mut Grid::from_str(20, 12, ".#\n..#\n###"), 30);
+
+    // sequential vs parallel on a big board; results must agree
+    let mut seq = Grid::new(1000, 1000);
+    seq.randomize(42);
+    let mut par = seq.clone();
+
+    let t_seq = time_it("sequential", 50, &mut seq, Grid::step);
+    let t_par = time_it("rayon", 50, &mut par, Grid::par_step);
+    assert_eq!(seq.cells, par.cells);
+    println!("speedup: {:.1}x on {} threads", t_seq.as_secs_f64() / t_par.as_secs_f64(), rayon::current_num_threads());
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Run on a 50x50 board and the 1000x1000 board: below what size is
+ *     the parallel version slower, and why? (splitting work is not free)
+ * (2) Move the timing into a Criterion benchmark (benches/life.rs) with
+ *     benchmark groups for several board sizes.
+ * (3) Pack 64 cells into a u64 and count neighbours with bit operations.
+ */
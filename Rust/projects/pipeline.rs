@@ -0,0 +1,279 @@
+// GUIDED PROJECT: STAGED PRODUCER-CONSUMER PIPELINE -----------------------
+
+/*
+ * A four-stage pipeline, each stage on its own thread, connected by
+ * bounded channels:
+ *
+ *     read ──lines──> parse ──records──> transform ──records──> write
+ *
+ * While "write" handles record 1, "transform" works on record 2,
+ * "parse" on record 3 and "read" on record 4: an assembly line.
+ *
+ * Pieces:
+ * (1) std::sync::mpsc::sync_channel: a channel with a capacity; send()
+ *     blocks when it is full, so a fast stage cannot run away from a
+ *     slow one (backpressure) and memory stays bounded; backpressure.rs
+ *     measures the difference, and has the other things a full channel
+ *     can do
+ * (2) shutdown by dropping senders: each stage's loop ends when its
+ *     input channel closes, then drops its own sender, and so on down
+ *     the line
+ * (3) throughput measurement with Instant
+ * (4) the same pipeline with tokio tasks and tokio::sync::mpsc
+ * (5) Ctrl-C: a shared flag stops the reader, and the stages after it
+ *     drain what was already read, through the same shutdown as (2)
+ *
+ * cargo new pipeline
+ * (copy this file into src/main.rs)
+ *
+ * Cargo.toml:
+ * [dependencies]
+ * tokio = { version = "1", features = ["full"] }
+ * ctrlc = "3"
+ *
+ * cargo run --release
+ */
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+const CAPACITY: usize = 1024;          // per channel
+const RECORDS: usize = 500_000;
+
+// THE DATA ----------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+struct Record {
+    user: String,
+    amount: i64,
+}
+
+// the input: lines like "user17,250", with every 1000th line malformed
+fn synthetic_line(i: usize) -> String {
+    if i % 1000 == 999 {
+        "garbage".to_string()
+    } else {
+        format!("user{},{}", i % 97, (i * 7919) % 1000)
+    }
+}
+
+// STAGE LOGIC -------------------------------------------------------------
+
+// The work each stage does is kept in plain functions, so the threaded
+// and async versions share it and must agree.
+
+fn parse(line: &str) -> Option<Record> {
+    let (user, amount) = line.split_once(',')?;
+    Some(Record { user: user.to_string(), amount: amount.parse().ok()? })
+}
+
+fn transform(mut record: Record) -> Option<Record> {
+    if record.amount == 0 {
+        return None;                   // a filtering stage
+    }
+    record.user.make_ascii_uppercase();
+    record.amount *= 100;              // to cents
+    Some(record)
+}
+
+// the sink writes CSV and returns a checksum to compare runs
+fn write_record(out: &mut impl Write, record: &Record, checksum: &mut u64) -> io::Result<()> {
+    writeln!(out, "{},{}", record.user, record.amount)?;
+    *checksum = checksum.wrapping_mul(31).wrapping_add(record.amount as u64);
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+struct Summary {
+    written: usize,
+    checksum: u64,
+}
+
+// THREADED PIPELINE -------------------------------------------------------
+
+/*
+ * Each stage: loop over the input Receiver (ends when all senders are
+ * gone), send to the output SyncSender. If the next stage has hung up,
+ * send() returns Err and we stop early.
+ *
+ * The senders are moved into the stage closures. When a stage returns,
+ * its sender is dropped, which closes the channel for the next stage.
+ */
+fn stage<I, O, F>(input: Receiver<I>, output: SyncSender<O>, f: F)
+where
+    F: FnMut(I) -> Option<O>,
+{
+    for out in input.into_iter().filter_map(f) {
+        if output.send(out).is_err() {
+            break;                     // downstream is gone
+        }
+    }
+}
+
+fn run_threaded(records: usize, stop: Arc<AtomicBool>) -> Summary {
+    let (line_tx, line_rx) = sync_channel::<String>(CAPACITY);
+    let (parsed_tx, parsed_rx) = sync_channel::<Record>(CAPACITY);
+    let (done_tx, done_rx) = sync_channel::<Record>(CAPACITY);
+
+    // only the reader looks at the stop flag: once it stops, the stages
+    // after it finish what's in the channels and end as they always do
+    let reader = thread::spawn(move || {
+        for i in 0..records {
+            if stop.load(Ordering::Relaxed) || line_tx.send(synthetic_line(i)).is_err() {
+                break;
+            }
+        }
+    });                                // line_tx dropped: parse stage will finish
+    let parser = thread::spawn(move || stage(line_rx, parsed_tx, |line| parse(&line)));
+    let transformer = thread::spawn(move || stage(parsed_rx, done_tx, transform));
+
+    // the writer runs on the current thread; io::sink() discards the
+    // bytes but still goes through the Write machinery
+    let mut out = io::BufWriter::new(io::sink());
+    let mut summary = Summary { written: 0, checksum: 0 };
+    for record in done_rx {
+        write_record(&mut out, &record, &mut summary.checksum).unwrap();
+        summary.written += 1;
+    }
+
+    for handle in [reader, parser, transformer] {
+        handle.join().expect("stage panicked");
+    }
+    summary
+}
+
+// ASYNC PIPELINE ----------------------------------------------------------
+
+/*
+ * The same shape with tokio: tasks instead of threads, tokio::sync::mpsc
+ * instead of sync_channel. tokio's mpsc::channel(n) is also bounded, and
+ * send().await *suspends the task* instead of blocking a thread.
+ *
+ * This work is CPU-bound, so async brings no speedup here; the point is
+ * that the structure carries over one to one, ready for stages that wait
+ * on the network instead (see the download manager project).
+ */
+async fn run_async(records: usize, stop: Arc<AtomicBool>) -> Summary {
+    use tokio::sync::mpsc::channel;
+
+    let (line_tx, mut line_rx) = channel::<String>(CAPACITY);
+    let (parsed_tx, mut parsed_rx) = channel::<Record>(CAPACITY);
+    let (done_tx, mut done_rx) = channel::<Record>(CAPACITY);
+
+    let reader = tokio::spawn(async move {
+        for i in 0..records {
+            if stop.load(Ordering::Relaxed) || line_tx.send(synthetic_line(i)).await.is_err() {
+                break;
+            }
+        }
+    });
+    let parser = tokio::spawn(async move {
+        while let Some(line) = line_rx.recv().await {
+            let Some(record) = parse(&line) else { continue };
+            if parsed_tx.send(record).await.is_err() {
+                break;
+            }
+        }
+    });
+    let transformer = tokio::spawn(async move {
+        while let Some(record) = parsed_rx.recv().await {
+            let Some(record) = transform(record) else { continue };
+            if done_tx.send(record).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut out = io::BufWriter::new(io::sink());
+    let mut summary = Summary { written: 0, checksum: 0 };
+    while let Some(record) = done_rx.recv().await {
+        write_record(&mut out, &record, &mut summary.checksum).unwrap();
+        summary.written += 1;
+    }
+
+    for handle in [reader, parser, transformer] {
+        handle.await.expect("stage panicked");
+    }
+    summary
+}
+
+// MAIN --------------------------------------------------------------------
+
+// the single-threaded baseline: the same functions, no channels
+fn run_sequential(records: usize, stop: &AtomicBool) -> Summary {
+    let mut out = io::BufWriter::new(io::sink());
+    let mut summary = Summary { written: 0, checksum: 0 };
+    let lines = (0..records).take_while(|_| !stop.load(Ordering::Relaxed)).map(synthetic_line);
+    for record in lines.filter_map(|l| parse(&l)).filter_map(transform) {
+        write_record(&mut out, &record, &mut summary.checksum).unwrap();
+        summary.written += 1;
+    }
+    summary
+}
+
+fn measure(label: &str, run: impl FnOnce() -> Summary) -> Summary {
+    let start = Instant::now();
+    let summary = run();
+    let secs = start.elapsed().as_secs_f64();
+    println!("{label:>10}: {} records in {secs:.3}s ({:.0} records/s)", summary.written, summary.written as f64 / secs);
+    summary
+}
+
+// a run cut short stopped at its own record, so there's nothing to compare
+fn interrupted(stop: &AtomicBool) -> bool {
+    let stopped = stop.load(Ordering::SeqCst);
+    if stopped {
+        println!("interrupted: the last run is partial, and the rest skipped");
+    }
+    stopped
+}
+
+fn main() {
+    assert_eq!(parse("bob,12"), Some(Record { user: "bob".into(), amount: 12 }));
+    assert_eq!(parse("garbage"), None);
+    assert_eq!(transform(Record { user: "bob".into(), amount: 0 }), None);
+
+    // the first Ctrl-C asks the runs to stop; a second one doesn't wait
+    let stop = Arc::new(AtomicBool::new(false));
+    let flag = stop.clone();
+    ctrlc::set_handler(move || {
+        if flag.swap(true, Ordering::SeqCst) {
+            std::process::exit(130);       // 128 + SIGINT, what a shell expects
+        }
+        eprintln!("stopping: draining what was read (Ctrl-C again to quit now)");
+    })
+    .expect("could not set the Ctrl-C handler");
+
+    let baseline = measure("sequential", || run_sequential(RECORDS, &stop));
+    if interrupted(&stop) {
+        return;
+    }
+    let threaded = measure("threads", || run_threaded(RECORDS, stop.clone()));
+    if interrupted(&stop) {
+        return;
+    }
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let asynced = measure("tokio", || runtime.block_on(run_async(RECORDS, stop.clone())));
+    if interrupted(&stop) {
+        return;
+    }
+
+    // every version sees the records in the same order: same checksum
+    assert_eq!(baseline, threaded);
+    assert_eq!(baseline, asynced);
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Change CAPACITY to 1 and to 100_000. What happens to throughput?
+ *     To peak memory? (/usr/bin/time -v shows the max resident size.)
+ * (2) Make "transform" slow (thread::sleep for 1µs per record), then run
+ *     two transform threads sharing one Receiver behind Arc<Mutex<_>>.
+ *     Why does the checksum now differ between runs?
+ * (3) Read real lines from a file with BufRead::lines in the read stage.
+ */
@@ -0,0 +1,286 @@
+// GUIDED PROJECT: A PLUGIN SYSTEM WITH TRAIT OBJECTS ----------------------
+
+/*
+ * A text-processing tool whose commands are plugins:
+ *
+ *     > upper hello world        HELLO WORLD
+ *     > count the cat sat        3 words, 11 characters
+ *     > reverse stressed         desserts
+ *
+ * The host only knows the Plugin trait. Each plugin is a different
+ * concrete type, so they are stored as Vec<Box<dyn Plugin>>: the
+ * "dynamic plugin systems where types are unknown at compile time"
+ * case from traits.rs.
+ *
+ * Stages:
+ * (1) the Plugin trait and a registry of Box<dyn Plugin>
+ * (2) a declarative macro that removes the registration boilerplate
+ * (3) (optional) loading plugins from shared libraries at runtime
+ *     with libloading
+ *
+ * cargo new plugins
+ * (copy this file into src/main.rs)
+ *
+ * Cargo.toml, for stage 3 only:
+ * [dependencies]
+ * libloading = { version = "0.8", optional = true }
+ * [features]
+ * dynamic = ["dep:libloading"]
+ *
+ * cargo run                      (built-in plugins)
+ * cargo run --features dynamic -- ./libmy_plugin.so
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+// STAGE 1: THE TRAIT ------------------------------------------------------
+
+/*
+ * Object safety: to be used as dyn Plugin, the trait must not have
+ * generic methods or return Self. &self methods returning concrete
+ * types are fine.
+ *
+ * Send + Sync as supertraits: the registry can then be shared with
+ * other threads later without changing every plugin.
+ */
+trait Plugin: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn description(&self) -> &str {
+        "(no description)"             // default implementation
+    }
+
+    fn run(&self, input: &str) -> Result<String, PluginError>;
+}
+
+#[derive(Debug, PartialEq)]
+struct PluginError(String);
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// A few built-ins. Unit structs: no data, just behaviour.
+struct Upper;
+struct Count;
+struct Reverse;
+
+// a plugin with configuration: state lives in the struct
+struct Repeat {
+    times: usize,
+}
+
+impl Plugin for Upper {
+    fn name(&self) -> &str { "upper" }
+    fn description(&self) -> &str { "convert to upper case" }
+    fn run(&self, input: &str) -> Result<String, PluginError> {
+        Ok(input.to_uppercase())
+    }
+}
+
+impl Plugin for Count {
+    fn name(&self) -> &str { "count" }
+    fn description(&self) -> &str { "count words and characters" }
+    fn run(&self, input: &str) -> Result<String, PluginError> {
+        Ok(format!("{} words, {} characters", input.split_whitespace().count(), input.chars().count()))
+    }
+}
+
+impl Plugin for Reverse {
+    fn name(&self) -> &str { "reverse" }
+    fn run(&self, input: &str) -> Result<String, PluginError> {
+        Ok(input.chars().rev().collect())
+    }
+}
+
+impl Plugin for Repeat {
+    fn name(&self) -> &str { "repeat" }
+    fn description(&self) -> &str { "repeat the input" }
+    fn run(&self, input: &str) -> Result<String, PluginError> {
+        if input.is_empty() {
+            return Err(PluginError("nothing to repeat".to_string()));
+        }
+        Ok(vec![input; self.times].join(" "))
+    }
+}
+
+// THE REGISTRY ------------------------------------------------------------
+
+/*
+ * Box<dyn Plugin>: each box holds a (data pointer, vtable pointer) pair.
+ * Calling plugin.run(..) looks up `run` in the vtable at runtime.
+ *
+ * The HashMap maps names to positions in the Vec so lookups are O(1)
+ * and listing keeps registration order.
+ */
+#[derive(Default)]
+struct Registry {
+    plugins: Vec<Box<dyn Plugin>>,
+    by_name: HashMap<String, usize>,
+}
+
+impl Registry {
+    fn register(&mut self, plugin: Box<dyn Plugin>) -> Result<(), PluginError> {
+        let name = plugin.name().to_string();
+        if self.by_name.contains_key(&name) {
+            return Err(PluginError(format!("plugin '{name}' registered twice")));
+        }
+        self.by_name.insert(name, self.plugins.len());
+        self.plugins.push(plugin);
+        Ok(())
+    }
+
+    // &dyn Plugin: borrow out of the box, no ownership change
+    fn get(&self, name: &str) -> Option<&dyn Plugin> {
+        self.by_name.get(name).map(|&i| self.plugins[i].as_ref())
+    }
+
+    fn run_line(&self, line: &str) -> Result<String, PluginError> {
+        let (name, input) = line.split_once(' ').unwrap_or((line, ""));
+        let plugin = self.get(name).ok_or_else(|| PluginError(format!("unknown command '{name}'")))?;
+        plugin.run(input)
+    }
+
+    fn help(&self) -> String {
+        self.plugins
+            .iter()
+            .map(|p| format!("{:>10}  {}", p.name(), p.description()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+// STAGE 2: A REGISTRATION MACRO -------------------------------------------
+
+/*
+ * Without the macro:
+ *     registry.register(Box::new(Upper)).unwrap();
+ *     registry.register(Box::new(Count)).unwrap();
+ *     ...
+ *
+ * macro_rules! matches on syntax: $( ... ),* repeats for every
+ * comma-separated expression, and the body is expanded once per item.
+ * The result is a Registry with every plugin boxed and registered.
+ */
+macro_rules! plugins {
+    ( $( $plugin:expr ),* $(,)? ) => {{
+        let mut registry = Registry::default();
+        $(
+            registry
+                .register(Box::new($plugin))
+                .expect("duplicate built-in plugin");
+        )*
+        registry
+    }};
+}
+
+// STAGE 3: DYNAMIC LOADING (OPTIONAL) -------------------------------------
+
+/*
+ * A plugin compiled separately, as a shared library:
+ *
+ *     # my_plugin/Cargo.toml
+ *     [lib]
+ *     crate-type = ["cdylib"]
+ *
+ *     // my_plugin/src/lib.rs  (with the same Plugin trait definition)
+ *     #[no_mangle]
+ *     pub extern "C" fn create_plugin() -> *mut Box<dyn Plugin> {
+ *         Box::into_raw(Box::new(Box::new(Shout)))
+ *     }
+ *
+ * The host looks up the `create_plugin` symbol and calls it.
+ *
+ * Big caveat: Rust has no stable ABI. Trait-object layout and vtables
+ * are only guaranteed to match if host and plugin are built with the
+ * *same compiler version* and the *same trait definition*. Production
+ * systems use a C ABI (abi_stable crate), WebAssembly, or separate
+ * processes instead. This stage is a demonstration of the mechanics.
+ */
+#[cfg(feature = "dynamic")]
+mod dynamic {
+    use super::{Plugin, PluginError};
+    use libloading::{Library, Symbol};
+
+    type Constructor = unsafe extern "C" fn() -> *mut Box<dyn Plugin>;
+
+    // The Library must outlive the plugin: its code lives inside the
+    // library. Keeping both in one struct, with the plugin declared
+    // first, makes the plugin drop before the library is unloaded
+    // (fields drop in declaration order).
+    pub struct LoadedPlugin {
+        plugin: Box<dyn Plugin>,
+        _library: Library,
+    }
+
+    impl Plugin for LoadedPlugin {
+        fn name(&self) -> &str { self.plugin.name() }
+        fn description(&self) -> &str { self.plugin.description() }
+        fn run(&self, input: &str) -> Result<String, PluginError> {
+            self.plugin.run(input)
+        }
+    }
+
+    pub fn load(path: &str) -> Result<LoadedPlugin, PluginError> {
+        let err = |e: libloading::Error| PluginError(format!("{path}: {e}"));
+        // unsafe: loading a library runs its initialisers, and we trust
+        // the symbol to have the signature we claim
+        unsafe {
+            let library = Library::new(path).map_err(err)?;
+            let constructor: Symbol<Constructor> = library.get(b"create_plugin").map_err(err)?;
+            let plugin = *Box::from_raw(constructor());
+            Ok(LoadedPlugin { plugin, _library: library })
+        }
+    }
+}
+
+// MAIN --------------------------------------------------------------------
+
+fn main() {
+    #[allow(unused_mut)]
+    let mut registry = plugins![Upper, Count, Reverse, Repeat { times: 2 }];
+
+    assert_eq!(registry.run_line("upper hello"), Ok("HELLO".to_string()));
+    assert_eq!(registry.run_line("count the cat sat"), Ok("3 words, 11 characters".to_string()));
+    assert_eq!(registry.run_line("repeat ha"), Ok("ha ha".to_string()));
+    assert_eq!(registry.run_line("repeat"), Err(PluginError("nothing to repeat".to_string())));
+    assert!(registry.run_line("shout hi").is_err());
+    assert!(registry.register(Box::new(Upper)).is_err());      // names are unique
+    assert_eq!(registry.get("reverse").map(|p| p.description()), Some("(no description)"));
+
+    #[cfg(feature = "dynamic")]
+    for path in std::env::args().skip(1) {
+        match dynamic::load(&path).and_then(|p| registry.register(Box::new(p))) {
+            Ok(()) => println!("loaded {path}"),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+
+    println!("{}\n", registry.help());
+    print!("> ");
+    io::stdout().flush().unwrap();
+    for line in io::stdin().lock().lines() {
+        let line = line.expect("failed to read stdin");
+        match registry.run_line(line.trim()) {
+            Ok(output) => println!("{output}"),
+            Err(e) => println!("error: {e}"),
+        }
+        print!("> ");
+        io::stdout().flush().unwrap();
+    }
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Add a Pipe plugin holding Vec<Box<dyn Plugin>> that runs its
+ *     inner plugins in sequence ("upper | reverse").
+ * (2) Give Plugin a fn init(&mut self, config: &str). Is the trait still
+ *     object safe? What changes in Registry?
+ * (3) Replace Box<dyn Plugin> with an enum of all built-ins. What do you
+ *     gain (speed, no heap) and lose (extensibility, stage 3)?
+ */
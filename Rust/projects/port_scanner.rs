@@ -0,0 +1,169 @@
+// GUIDED PROJECT: ASYNC PORT SCANNER --------------------------------------
+
+/*
+ * Try to open a TCP connection to every port in a range and report which
+ * ones accept. Thousands of connection attempts, most of which just wait
+ * for a timeout: a textbook case for async, where a waiting task costs a
+ * few hundred bytes instead of a whole OS thread.
+ *
+ * Pieces:
+ * (1) fan-out:  one tokio task per port
+ * (2) bounded concurrency with a Semaphore (don't open 65535 sockets at once)
+ * (3) a timeout on each attempt
+ * (4) fan-in:   collect results from the JoinSet, print a summary
+ *
+ * Only scan hosts you own or have permission to scan.
+ *
+ * cargo new port_scanner
+ * (copy this file into src/main.rs)
+ *
+ * Cargo.toml:
+ * [dependencies]
+ * tokio = { version = "1", features = ["full"] }
+ *
+ * cargo run --release -- 127.0.0.1 1 1024
+ */
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time::timeout;
+
+const MAX_IN_FLIGHT: usize = 500;
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(300);
+
+// RESULTS -----------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PortState {
+    Open,
+    Closed,                            // connection refused: host said no
+    Filtered,                          // no answer before the timeout
+}
+
+#[derive(Debug, Default)]
+struct Report {
+    open: Vec<u16>,
+    closed: usize,
+    filtered: usize,
+}
+
+// ONE PROBE ---------------------------------------------------------------
+
+// timeout() wraps any future: Err(Elapsed) if it takes too long,
+// otherwise the future's own output (here a Result from connect)
+async fn probe(addr: SocketAddr) -> PortState {
+    match timeout(CONNECT_TIMEOUT, TcpStream::connect(addr)).await {
+        Ok(Ok(_stream)) => PortState::Open,      // dropped at once: closes the connection
+        Ok(Err(_)) => PortState::Closed,
+        Err(_elapsed) => PortState::Filtered,
+    }
+}
+
+// FAN-OUT, FAN-IN ---------------------------------------------------------
+
+/*
+ * Semaphore: a counter of permits. acquire_owned() waits until a permit
+ * is free; the permit is given back when it is dropped. Holding one for
+ * the duration of each probe caps the number of probes in flight.
+ *
+ * acquire_owned needs the semaphore in an Arc because the permit is
+ * moved into a 'static task (the same reason threads need Arc).
+ *
+ * Acquiring *before* spawning also keeps us from creating 65535 idle
+ * tasks up front: the loop itself waits.
+ */
+async fn scan(host: IpAddr, ports: std::ops::RangeInclusive<u16>) -> Report {
+    let semaphore = Arc::new(Semaphore::new(MAX_IN_FLIGHT));
+    let mut tasks = JoinSet::new();
+
+    for port in ports {
+        let permit = semaphore.clone().acquire_owned().await.expect("semaphore never closed");
+        tasks.spawn(async move {
+            let state = probe(SocketAddr::new(host, port)).await;
+            drop(permit);                        // explicit, for the reader
+            (port, state)
+        });
+    }
+
+    // join_next yields results in completion order, not port order
+    let mut report = Report::default();
+    while let Some(joined) = tasks.join_next().await {
+        let (port, state) = joined.expect("probe task panicked");
+        match state {
+            PortState::Open => report.open.push(port),
+            PortState::Closed => report.closed += 1,
+            PortState::Filtered => report.filtered += 1,
+        }
+    }
+    report.open.sort_unstable();
+    report
+}
+
+fn well_known(port: u16) -> &'static str {
+    match port {
+        22 => "ssh",
+        25 => "smtp",
+        53 => "dns",
+        80 => "http",
+        443 => "https",
+        5432 => "postgres",
+        6379 => "redis",
+        8080 => "http-alt",
+        _ => "",
+    }
+}
+
+fn print_report(host: IpAddr, report: &Report, elapsed: Duration) {
+    println!("scan of {host} finished in {elapsed:.2?}");
+    for port in &report.open {
+        println!("  {port:>5}/tcp  open  {}", well_known(*port));
+    }
+    println!(
+        "{} open, {} closed, {} filtered",
+        report.open.len(),
+        report.closed,
+        report.filtered
+    );
+}
+
+// MAIN --------------------------------------------------------------------
+
+#[tokio::main]
+async fn main() {
+    // self-check: open two local listeners and make sure we find them
+    let a = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let b = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let (pa, pb) = (a.local_addr().unwrap().port(), b.local_addr().unwrap().port());
+    let (lo, hi) = (pa.min(pb), pa.max(pb));
+    let report = scan("127.0.0.1".parse().unwrap(), lo..=hi).await;
+    assert_eq!(report.open.first(), Some(&lo));
+    assert_eq!(report.open.last(), Some(&hi));
+    drop((a, b));
+
+    // usage: port_scanner <host> [first] [last]
+    let args: Vec<String> = std::env::args().collect();
+    let host: IpAddr = args.get(1).map(|h| h.parse().expect("host must be an IP address"))
+        .unwrap_or_else(|| "127.0.0.1".parse().unwrap());
+    let first: u16 = args.get(2).map(|p| p.parse().expect("bad port")).unwrap_or(1);
+    let last: u16 = args.get(3).map(|p| p.parse().expect("bad port")).unwrap_or(1024);
+
+    let start = Instant::now();
+    let report = scan(host, first..=last).await;
+    print_report(host, &report, start.elapsed());
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Make MAX_IN_FLIGHT and CONNECT_TIMEOUT command-line options and
+ *     measure how the total time changes against a remote host.
+ * (2) Print open ports as soon as they are found, while keeping the
+ *     final sorted summary (hint: an mpsc channel to a printer task).
+ * (3) Scan a list of hosts concurrently, with a global limit shared by
+ *     all of them (one Semaphore passed to every scan).
+ */
@@ -0,0 +1,253 @@
+// GUIDED PROJECT: TERMINAL SNAKE ------------------------------------------
+
+/*
+ * The classic snake game in the terminal: a capstone that pulls together
+ * structs, enums, VecDeque, match, Instant and Duration, and error
+ * handling with ?.
+ *
+ * Pieces:
+ * (1) raw mode: keys arrive immediately, without waiting for Enter
+ * (2) a game loop driven by fixed ticks measured with Instant
+ * (3) game state as an enum: Playing, Paused, GameOver
+ * (4) rendering by moving the cursor and printing characters
+ *
+ * cargo new snake
+ * (copy this file into src/main.rs)
+ *
+ * Cargo.toml:
+ * [dependencies]
+ * crossterm = "0.27"
+ *
+ * cargo run            (arrows or hjkl to steer, p to pause, q to quit)
+ */
+
+use std::collections::VecDeque;
+use std::io::{self, Stdout, Write};
+use std::time::{Duration, Instant, SystemTime};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+
+const WIDTH: u16 = 30;
+const HEIGHT: u16 = 15;
+const TICK: Duration = Duration::from_millis(120);
+
+// GAME STATE --------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction { Up, Down, Left, Right }
+
+impl Direction {
+    fn opposite(self) -> Direction {
+        match self {
+            Direction::Up    => Direction::Down,
+            Direction::Down  => Direction::Up,
+            Direction::Left  => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Playing,
+    Paused,
+    GameOver { score: usize },         // variant carrying data
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Pos { x: u16, y: u16 }
+
+struct Game {
+    // VecDeque: push a new head at the front, pop the tail at the back,
+    // both O(1)
+    snake: VecDeque<Pos>,
+    direction: Direction,
+    pending: Direction,                // last key pressed, applied on the next tick
+    food: Pos,
+    state: State,
+    rng: u64,
+}
+
+impl Game {
+    fn new() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0x2545_F491);
+        let start = Pos { x: WIDTH / 2, y: HEIGHT / 2 };
+        let mut game = Game {
+            snake: VecDeque::from([start, Pos { x: start.x - 1, ..start }, Pos { x: start.x - 2, ..start }]),
+            direction: Direction::Right,
+            pending: Direction::Right,
+            food: start,
+            state: State::Playing,
+            rng: seed | 1,             // xorshift must not start at 0
+        };
+        game.place_food();
+        game
+    }
+
+    // xorshift64: a few lines of pseudo-randomness instead of a dependency
+    fn random(&mut self, bound: u16) -> u16 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng % bound as u64) as u16
+    }
+
+    fn place_food(&mut self) {
+        loop {
+            let pos = Pos { x: self.random(WIDTH), y: self.random(HEIGHT) };
+            if !self.snake.contains(&pos) {
+                self.food = pos;
+                return;
+            }
+        }
+    }
+
+    fn steer(&mut self, direction: Direction) {
+        // turning back onto yourself is ignored, not fatal
+        if direction != self.direction.opposite() {
+            self.pending = direction;
+        }
+    }
+
+    // one tick of the simulation
+    fn update(&mut self) {
+        if self.state != State::Playing {
+            return;
+        }
+        self.direction = self.pending;
+
+        let head = self.snake[0];
+        // checked arithmetic: leaving the board on the left/top
+        // would underflow a u16
+        let next = match self.direction {
+            Direction::Up    => head.y.checked_sub(1).map(|y| Pos { y, ..head }),
+            Direction::Down  => Some(Pos { y: head.y + 1, ..head }).filter(|p| p.y < HEIGHT),
+            Direction::Left  => head.x.checked_sub(1).map(|x| Pos { x, ..head }),
+            Direction::Right => Some(Pos { x: head.x + 1, ..head }).filter(|p| p.x < WIDTH),
+        };
+
+        match next {
+            Some(pos) if !self.snake.contains(&pos) => {
+                self.snake.push_front(pos);
+                if pos == self.food {
+                    self.place_food();             // grow: keep the tail
+                } else {
+                    self.snake.pop_back();
+                }
+            }
+            _ => self.state = State::GameOver { score: self.snake.len() - 3 },
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match (self.state, key.code) {
+            (_, KeyCode::Char('q') | KeyCode::Esc) => return false,
+            (State::Playing, KeyCode::Up | KeyCode::Char('k')) => self.steer(Direction::Up),
+            (State::Playing, KeyCode::Down | KeyCode::Char('j')) => self.steer(Direction::Down),
+            (State::Playing, KeyCode::Left | KeyCode::Char('h')) => self.steer(Direction::Left),
+            (State::Playing, KeyCode::Right | KeyCode::Char('l')) => self.steer(Direction::Right),
+            (State::Playing, KeyCode::Char('p')) => self.state = State::Paused,
+            (State::Paused, KeyCode::Char('p')) => self.state = State::Playing,
+            (State::GameOver { .. }, KeyCode::Char('r')) => *self = Game::new(),
+            _ => (),
+        }
+        true                                       // keep running
+    }
+}
+
+// RENDERING ---------------------------------------------------------------
+
+// queue! buffers commands, flush() sends them in one write:
+// far less flicker than printing cell by cell
+fn render(out: &mut Stdout, game: &Game) -> io::Result<()> {
+    queue!(out, terminal::Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+
+    let border = format!("+{}+", "-".repeat(WIDTH as usize));
+    queue!(out, Print(&border))?;
+    for y in 0..HEIGHT {
+        queue!(out, cursor::MoveTo(0, y + 1), Print('|'), cursor::MoveTo(WIDTH + 1, y + 1), Print('|'))?;
+    }
+    queue!(out, cursor::MoveTo(0, HEIGHT + 1), Print(&border))?;
+
+    queue!(out, cursor::MoveTo(game.food.x + 1, game.food.y + 1), Print('*'))?;
+    for (i, part) in game.snake.iter().enumerate() {
+        let glyph = if i == 0 { '@' } else { 'o' };
+        queue!(out, cursor::MoveTo(part.x + 1, part.y + 1), Print(glyph))?;
+    }
+
+    let status = match game.state {
+        State::Playing => format!("score: {}", game.snake.len() - 3),
+        State::Paused => "paused (p to resume)".to_string(),
+        State::GameOver { score } => format!("game over, score {score} (r to restart, q to quit)"),
+    };
+    queue!(out, cursor::MoveTo(0, HEIGHT + 2), Print(status))?;
+    out.flush()
+}
+
+// THE GAME LOOP -----------------------------------------------------------
+
+/*
+ * Fixed-tick loop:
+ *     wait for input, but never past the next tick deadline;
+ *     when the deadline passes, update and redraw.
+ *
+ * Measuring with Instant (a monotonic clock) keeps the speed steady no
+ * matter how many keys arrive between ticks.
+ */
+fn run(out: &mut Stdout) -> io::Result<()> {
+    let mut game = Game::new();
+    let mut next_tick = Instant::now() + TICK;
+    render(out, &game)?;
+
+    loop {
+        let timeout = next_tick.saturating_duration_since(Instant::now());
+        if event::poll(timeout)? {
+            let quit = match event::read()? {
+                Event::Key(key) => !game.handle_key(key),
+                _ => false,            // resizes, mouse, focus
+            };
+            if quit {
+                return Ok(());
+            }
+        }
+
+        if Instant::now() >= next_tick {
+            game.update();
+            render(out, &game)?;
+            next_tick += TICK;
+        }
+    }
+}
+
+fn main() -> io::Result<()> {
+    let mut out = io::stdout();
+
+    terminal::enable_raw_mode()?;
+    execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    // run the game, but restore the terminal whatever happened:
+    // keep the result and only then propagate it with ?
+    let result = run(&mut out);
+
+    execute!(out, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Speed up: shorten the tick by 5ms every time food is eaten.
+ * (2) Wrap around the edges instead of dying (hint: rem_euclid).
+ * (3) Restoring the terminal is skipped if run() panics. Put the cleanup
+ *     in a struct's Drop implementation (a guard) so it always happens.
+ */
@@ -0,0 +1,222 @@
+// GUIDED PROJECT: SPELL CHECKER -------------------------------------------
+
+/*
+ * Load a dictionary, flag unknown words in a document, and suggest
+ * corrections:
+ *
+ *     "Teh quick brwn fox"  ->  teh: the?   brwn: brown?
+ *
+ * Pieces:
+ * (1) a HashSet<String> dictionary: O(1) "is this a word?"
+ * (2) splitting text into words with char methods (not bytes: the text
+ *     is UTF-8)
+ * (3) Levenshtein edit distance with a two-row table
+ * (4) performance tuning: the naive suggester compares against every
+ *     word in the dictionary; the tuned one generates candidate edits
+ *     and looks them up. Both are timed on the same input.
+ *
+ * cargo new spell_checker
+ * (copy this file into src/main.rs)
+ * cargo run --release -- document.txt      (uses /usr/share/dict/words if present)
+ */
+
+use std::collections::HashSet;
+use std::time::Instant;
+
+// THE DICTIONARY ----------------------------------------------------------
+
+struct Dictionary {
+    words: HashSet<String>,
+}
+
+impl Dictionary {
+    // one word per line; normalised to lower case
+    fn from_lines(text: &str) -> Self {
+        let words = text
+            .lines()
+            .map(|w| w.trim().to_lowercase())
+            .filter(|w| !w.is_empty())
+            .collect();
+        Dictionary { words }
+    }
+
+    // HashSet<String>::contains accepts a &str: String: Borrow<str>,
+    // so no allocation is needed for a lookup
+    fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+}
+
+// SPLITTING TEXT ----------------------------------------------------------
+
+// Words are runs of alphabetic characters (and inner apostrophes:
+// "don't"). Returned as slices of the input: no copying.
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !(c.is_alphabetic() || c == '\''))
+        .map(|w| w.trim_matches('\''))
+        .filter(|w| !w.is_empty())
+}
+
+// EDIT DISTANCE -----------------------------------------------------------
+
+/*
+ * Levenshtein distance: the minimum number of single-character
+ * insertions, deletions and substitutions turning a into b.
+ *
+ * The textbook table has (len a + 1) x (len b + 1) cells, but each row
+ * only depends on the previous one, so two rows suffice.
+ *
+ * We compare chars, not bytes: "café" vs "cafe" is one edit, not two.
+ */
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + (ca != cb) as usize;
+            let deletion = previous[j + 1] + 1;
+            let insertion = current[j] + 1;
+            current[j + 1] = substitution.min(deletion).min(insertion);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+// SUGGESTIONS, NAIVE ------------------------------------------------------
+
+// compare against every dictionary word: O(dictionary size) per lookup
+fn suggest_naive(dict: &Dictionary, word: &str, max: usize) -> Vec<String> {
+    let mut scored: Vec<(usize, &String)> = dict
+        .words
+        .iter()
+        .map(|w| (edit_distance(word, w), w))
+        .filter(|&(d, _)| d <= 2)
+        .collect();
+    scored.sort();                     // by distance, then alphabetically
+    scored.into_iter().take(max).map(|(_, w)| w.clone()).collect()
+}
+
+// SUGGESTIONS, TUNED ------------------------------------------------------
+
+/*
+ * Turn the problem around: instead of measuring the distance to 100k
+ * words, generate every string one edit away from the typo (for a word
+ * of length n over a 26-letter alphabet, about 54n + 25 strings) and
+ * keep those that are in the HashSet.
+ *
+ * Distance 1 covers most typos; if nothing is found, apply edits to the
+ * edits (distance 2), which is more expensive but still independent of
+ * the dictionary size.
+ */
+fn edits1(word: &str) -> HashSet<String> {
+    const LETTERS: &str = "abcdefghijklmnopqrstuvwxyz";
+    let chars: Vec<char> = word.chars().collect();
+    let mut out = HashSet::new();
+
+    for i in 0..=chars.len() {
+        let (left, right) = chars.split_at(i);
+        let left: String = left.iter().collect();
+
+        if let Some((_, rest)) = right.split_first() {
+            let rest: String = rest.iter().collect();
+            out.insert(format!("{left}{rest}"));                           // delete
+            for c in LETTERS.chars() {
+                out.insert(format!("{left}{c}{rest}"));                    // replace
+            }
+            if let Some((second, rest2)) = rest.chars().next().map(|c| (c, &rest[c.len_utf8()..])) {
+                out.insert(format!("{left}{second}{}{rest2}", right[0]));  // transpose
+            }
+        }
+        let right: String = right.iter().collect();
+        for c in LETTERS.chars() {
+            out.insert(format!("{left}{c}{right}"));                       // insert
+        }
+    }
+    out
+}
+
+fn suggest_fast(dict: &Dictionary, word: &str, max: usize) -> Vec<String> {
+    let one = edits1(word);
+    let mut found: Vec<String> = one.iter().filter(|w| dict.contains(w)).cloned().collect();
+
+    if found.is_empty() {
+        let two: HashSet<String> = one
+            .iter()
+            .flat_map(|w| edits1(w))
+            .filter(|w| dict.contains(w))
+            .collect();
+        found = two.into_iter().collect();
+    }
+
+    // transposition counts as 2 in plain Levenshtein; order by it anyway
+    found.sort_by_key(|w| (edit_distance(word, w), w.clone()));
+    found.truncate(max);
+    found
+}
+
+// CHECKING A DOCUMENT -----------------------------------------------------
+
+fn check<'a>(dict: &Dictionary, text: &'a str) -> Vec<(&'a str, Vec<String>)> {
+    let mut reported = HashSet::new();
+    words(text)
+        .filter(|w| !dict.contains(&w.to_lowercase()))
+        .filter(|w| reported.insert(w.to_lowercase()))      // report each typo once
+        .map(|w| (w, suggest_fast(dict, &w.to_lowercase(), 3)))
+        .collect()
+}
+
+// MAIN --------------------------------------------------------------------
+
+const SMALL_DICTIONARY: &str = "the\nquick\nbrown\nfox\njumps\nover\nlazy\ndog\nspell\nchecker\nrust\nis\nfast\nand\nsafe\nwords\n";
+
+fn main() {
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("café", "cafe"), 1);
+    assert_eq!(edit_distance("", "abc"), 3);
+    assert_eq!(words("Don't panic, it's 42!").collect::<Vec<_>>(), ["Don't", "panic", "it's"]);
+
+    let small = Dictionary::from_lines(SMALL_DICTIONARY);
+    let typos = check(&small, "Teh quick brwn fox jumsp over the lazy dgo. Teh end.");
+    let flagged: Vec<&str> = typos.iter().map(|(w, _)| *w).collect();
+    assert_eq!(flagged, ["Teh", "brwn", "jumsp", "dgo", "end"]);
+    assert_eq!(typos[0].1, ["the"]);
+    assert_eq!(typos[1].1, ["brown"]);
+
+    // With a real dictionary, compare the two suggesters. On the tiny
+    // built-in list the naive one wins easily; the edits approach only
+    // pays off once the dictionary has tens of thousands of words.
+    let big = std::fs::read_to_string("/usr/share/dict/words")
+        .map(|text| Dictionary::from_lines(&text))
+        .unwrap_or(small);
+    println!("dictionary: {} words", big.words.len());
+
+    let samples = ["speling", "recieve", "definately", "langauge", "rsut", "borow"];
+    for (name, suggest) in [("naive", suggest_naive as fn(&Dictionary, &str, usize) -> Vec<String>), ("edits", suggest_fast)] {
+        let start = Instant::now();
+        let results: Vec<Vec<String>> = samples.iter().map(|w| suggest(&big, w, 3)).collect();
+        println!("{name:>6}: {:?} for {} words", start.elapsed(), samples.len());
+        println!("        {results:?}");
+    }
+
+    if let Some(path) = std::env::args().nth(1) {
+        let text = std::fs::read_to_string(&path).expect("could not read document");
+        for (word, suggestions) in check(&big, &text) {
+            println!("{word}: {}", suggestions.join(", "));
+        }
+    }
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Rank suggestions by word frequency from a corpus (a HashMap<String,
+ *     u32>) instead of alphabetically: "teh" should suggest "the" first.
+ * (2) The naive suggester can skip words whose length differs by more
+ *     than 2. How much faster does that make it?
+ * (3) Move the timing into Criterion benchmarks and try a BK-tree, which
+ *     prunes the search using the triangle inequality.
+ */
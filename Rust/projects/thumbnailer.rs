@@ -0,0 +1,167 @@
+// GUIDED PROJECT: IMAGE THUMBNAILER ---------------------------------------
+
+/*
+ * Turn a folder of images into grayscale thumbnails, using every core,
+ * while the main thread shows progress.
+ *
+ * This is the mpsc material from concurrency.rs applied to real work:
+ *     workers (rayon threads)  --tx-->  channel  --rx-->  main thread
+ * The workers only send small Progress messages; all printing happens
+ * on one thread, so output lines never interleave.
+ *
+ * Pieces:
+ * (1) the image crate: open, resize keeping aspect ratio, grayscale, save
+ * (2) rayon for the parallel batch
+ * (3) an mpsc channel carrying an enum of progress events
+ * (4) per-file errors reported, not fatal
+ *
+ * cargo new thumbnailer
+ * (copy this file into src/main.rs)
+ *
+ * Cargo.toml:
+ * [dependencies]
+ * image = "0.25"
+ * rayon = "1"
+ *
+ * cargo run --release -- photos/ thumbs/ 200
+ */
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Instant;
+
+use image::{ImageBuffer, Rgb};
+use rayon::prelude::*;
+
+// MESSAGES ----------------------------------------------------------------
+
+// what a worker tells the main thread; the channel carries owned values,
+// so the message owns its PathBuf and error String
+enum Progress {
+    Done { input: PathBuf, output: PathBuf },
+    Failed { input: PathBuf, error: String },
+}
+
+// ONE IMAGE ---------------------------------------------------------------
+
+/*
+ * thumbnail() keeps the aspect ratio and fits the image inside
+ * max x max. It is much faster than resize() for big downscales.
+ *
+ * ImageError converts into Box<dyn Error> through ?, so the function
+ * can mix image errors and io errors freely.
+ */
+fn make_thumbnail(input: &Path, out_dir: &Path, max: u32) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let img = image::open(input)?;
+    let thumb = img.thumbnail(max, max).grayscale();
+
+    let name = input.file_stem().ok_or("file has no name")?;
+    let output = out_dir.join(name).with_extension("png");
+    thumb.save(&output)?;
+    Ok(output)
+}
+
+fn is_image(path: &Path) -> bool {
+    let ext = path.extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase());
+    matches!(ext.as_deref(), Some("png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp"))
+}
+
+// THE BATCH ---------------------------------------------------------------
+
+/*
+ * The batch runs on a separate thread so that main is free to receive.
+ *
+ * for_each_with(tx, ..) gives every rayon worker its own clone of the
+ * Sender (the "multiple producers" in mpsc). When the batch finishes,
+ * all clones are dropped, the channel closes, and the receiving
+ * for-loop in main ends by itself.
+ */
+fn run_batch(inputs: Vec<PathBuf>, out_dir: PathBuf, max: u32) -> (usize, usize) {
+    let total = inputs.len();
+    let (tx, rx) = mpsc::channel();
+
+    let worker = thread::spawn(move || {
+        inputs.par_iter().for_each_with(tx, |tx, input| {
+            let message = match make_thumbnail(input, &out_dir, max) {
+                Ok(output) => Progress::Done { input: input.clone(), output },
+                Err(e) => Progress::Failed { input: input.clone(), error: e.to_string() },
+            };
+            // send fails only if the receiver is gone; nothing to do then
+            let _ = tx.send(message);
+        });
+    });
+
+    let (mut done, mut failed) = (0, 0);
+    for message in rx {                              // ends when all senders drop
+        match message {
+            Progress::Done { input, output } => {
+                done += 1;
+                println!("[{:>3}/{total}] {} -> {}", done + failed, input.display(), output.display());
+            }
+            Progress::Failed { input, error } => {
+                failed += 1;
+                eprintln!("[{:>3}/{total}] {} failed: {error}", done + failed, input.display());
+            }
+        }
+    }
+
+    worker.join().expect("batch thread panicked");
+    (done, failed)
+}
+
+// MAIN --------------------------------------------------------------------
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // self-check: draw a few gradients, thumbnail them, look at the result
+    let scratch = std::env::temp_dir().join(format!("thumbs_{}", std::process::id()));
+    let (src, dst) = (scratch.join("src"), scratch.join("dst"));
+    fs::create_dir_all(&src)?;
+    fs::create_dir_all(&dst)?;
+    for i in 0..4u32 {
+        let img = ImageBuffer::from_fn(640, 480, |x, y| Rgb([(x / 3) as u8, (y / 2) as u8, (i * 60) as u8]));
+        img.save(src.join(format!("gradient{i}.png")))?;
+    }
+    fs::write(src.join("broken.png"), b"not really a png")?;
+
+    let inputs: Vec<PathBuf> = fs::read_dir(&src)?.flatten().map(|e| e.path()).filter(|p| is_image(p)).collect();
+    let (done, failed) = run_batch(inputs, dst.clone(), 100);
+    assert_eq!((done, failed), (4, 1));
+
+    let thumb = image::open(dst.join("gradient0.png"))?;
+    assert_eq!((thumb.width(), thumb.height()), (100, 75));    // aspect ratio kept
+    assert!(thumb.as_luma8().is_some());                       // grayscale
+    fs::remove_dir_all(&scratch)?;
+
+    // thumbnailer <input dir> <output dir> [max size]
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() >= 3 {
+        let (in_dir, out_dir) = (PathBuf::from(&args[1]), PathBuf::from(&args[2]));
+        let max: u32 = args.get(3).map(|m| m.parse()).transpose()?.unwrap_or(200);
+        fs::create_dir_all(&out_dir)?;
+
+        let inputs: Vec<PathBuf> = fs::read_dir(&in_dir)?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| is_image(p))
+            .collect();
+
+        let start = Instant::now();
+        let (done, failed) = run_batch(inputs, out_dir, max);
+        println!("{done} thumbnails, {failed} failures in {:.2?}", start.elapsed());
+    }
+    Ok(())
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Replace the per-file lines with a single updating progress line
+ *     ("\r[ 37/120] 30%") printed from main.
+ * (2) Add a Progress::Started variant and show which files are in
+ *     flight right now.
+ * (3) Compare run times with rayon::ThreadPoolBuilder::num_threads(1)
+ *     and with the default. Where does the speedup flatten out, and is
+ *     the bottleneck then the CPU or the disk?
+ */
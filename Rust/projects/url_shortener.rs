@@ -0,0 +1,319 @@
+// GUIDED PROJECT: URL SHORTENER WEB SERVICE -------------------------------
+
+/*
+ * A small HTTP service:
+ *
+ *     POST /shorten   {"url": "https://www.rust-lang.org"}
+ *                  -> 201 {"code": "b", "short_url": "http://localhost:3000/b"}
+ *     GET  /b      -> 308 redirect to https://www.rust-lang.org
+ *     GET  /stats/b -> {"url": "...", "hits": 3}
+ *
+ * Pieces:
+ * (1) axum routes and handlers (async functions with extractors)
+ * (2) shared state: Arc<RwLock<HashMap<..>>>, the same Arc + lock
+ *     pattern as Arc<Mutex<T>> in concurrency.rs
+ * (3) serde for JSON requests and responses
+ * (4) persistence: the map is written to a JSON file and reloaded
+ *     on startup
+ * (5) integration checks that drive the router without a network
+ * (6) graceful shutdown: on Ctrl-C or SIGTERM, stop accepting, let the
+ *     requests in flight finish, then save the hit counts
+ *
+ * cargo new url_shortener
+ * (copy this file into src/main.rs)
+ *
+ * Cargo.toml:
+ * [dependencies]
+ * axum = "0.7"
+ * tokio = { version = "1", features = ["full"] }
+ * serde = { version = "1", features = ["derive"] }
+ * serde_json = "1"
+ * tower = { version = "0.5", features = ["util"] }
+ * http-body-util = "0.1"
+ *
+ * cargo run -- --check     (run the integration checks and exit)
+ * cargo run                (serve on localhost:3000)
+ *
+ * curl -X POST localhost:3000/shorten -H 'content-type: application/json' \
+ *      -d '{"url": "https://www.rust-lang.org"}'
+ */
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+const BASE_URL: &str = "http://localhost:3000";
+
+// THE STORE ---------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Link {
+    url: String,
+    hits: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Store {
+    next_id: u64,
+    links: HashMap<String, Link>,
+}
+
+/*
+ * Every request runs on some tokio worker thread, so the store must be
+ * shared between threads:
+ *     Arc      many owners (one clone per handler invocation)
+ *     RwLock   many readers at once, or a single writer
+ *
+ * A std RwLock is fine here because no handler holds the guard across
+ * an .await; the lock is taken, used and dropped in one go.
+ */
+type Shared = Arc<RwLock<Store>>;
+
+#[derive(Clone)]
+struct AppState {
+    store: Shared,
+    file: Option<PathBuf>,             // None: in-memory only
+}
+
+// ids become short codes in base 62: 0 -> "0", 61 -> "Z", 62 -> "10"
+fn encode(mut n: u64) -> String {
+    const ALPHABET: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let mut code = Vec::new();
+    loop {
+        code.push(ALPHABET[(n % 62) as usize]);
+        n /= 62;
+        if n == 0 {
+            break;
+        }
+    }
+    code.reverse();
+    String::from_utf8(code).expect("alphabet is ASCII")
+}
+
+// PERSISTENCE -------------------------------------------------------------
+
+fn load(file: &PathBuf) -> Store {
+    match std::fs::read_to_string(file) {
+        Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+            eprintln!("ignoring corrupt store {}: {e}", file.display());
+            Store::default()
+        }),
+        Err(_) => Store::default(),        // first run: no file yet
+    }
+}
+
+// write to a temporary file, then rename: a crash mid-write
+// never leaves a half-written store behind
+fn save(file: &PathBuf, store: &Store) -> std::io::Result<()> {
+    let tmp = file.with_extension("tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(store)?)?;
+    std::fs::rename(tmp, file)
+}
+
+// ERRORS ------------------------------------------------------------------
+
+// one error type for all handlers; IntoResponse turns it into HTTP
+enum ApiError {
+    InvalidUrl,
+    NotFound,
+    Storage(std::io::Error),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::InvalidUrl => (StatusCode::UNPROCESSABLE_ENTITY, "url must start with http:// or https://".to_string()),
+            ApiError::NotFound => (StatusCode::NOT_FOUND, "no such short code".to_string()),
+            ApiError::Storage(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("could not save: {e}")),
+        };
+        (status, Json(serde_json::json!({ "error": message }))).into_response()
+    }
+}
+
+// HANDLERS ----------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct ShortenRequest {
+    url: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ShortenResponse {
+    code: String,
+    short_url: String,
+}
+
+// Extractors in the argument list: State gives the shared state,
+// Json parses the body (a malformed body is rejected before we run)
+async fn shorten(
+    State(app): State<AppState>,
+    Json(request): Json<ShortenRequest>,
+) -> Result<(StatusCode, Json<ShortenResponse>), ApiError> {
+    if !(request.url.starts_with("http://") || request.url.starts_with("https://")) {
+        return Err(ApiError::InvalidUrl);
+    }
+
+    let code = {
+        let mut store = app.store.write().unwrap();  // write lock
+        let code = encode(store.next_id);
+        store.next_id += 1;
+        store.links.insert(code.clone(), Link { url: request.url, hits: 0 });
+        if let Some(file) = &app.file {
+            save(file, &store).map_err(ApiError::Storage)?;
+        }
+        code
+    };                                               // lock released here
+
+    let short_url = format!("{BASE_URL}/{code}");
+    Ok((StatusCode::CREATED, Json(ShortenResponse { code, short_url })))
+}
+
+async fn follow(State(app): State<AppState>, Path(code): Path<String>) -> Result<Redirect, ApiError> {
+    let mut store = app.store.write().unwrap();      // write: we count hits
+    let link = store.links.get_mut(&code).ok_or(ApiError::NotFound)?;
+    link.hits += 1;
+    Ok(Redirect::permanent(&link.url))
+}
+
+async fn stats(State(app): State<AppState>, Path(code): Path<String>) -> Result<Json<Link>, ApiError> {
+    let store = app.store.read().unwrap();           // read lock is enough
+    store.links.get(&code).cloned().map(Json).ok_or(ApiError::NotFound)
+}
+
+fn router(app: AppState) -> Router {
+    Router::new()
+        .route("/shorten", post(shorten))
+        .route("/stats/:code", get(stats))
+        .route("/:code", get(follow))
+        .with_state(app)
+}
+
+// INTEGRATION CHECKS ------------------------------------------------------
+
+/*
+ * A Router is a tower Service: we can hand it requests directly with
+ * oneshot() and inspect the responses. No port, no network, no flakiness.
+ * (In a crate with a lib target these would live in tests/api.rs.)
+ */
+async fn check() {
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    async fn send(app: &Router, request: Request<Body>) -> (StatusCode, Vec<u8>) {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, body.to_vec())
+    }
+
+    fn post_json(uri: &str, json: &str) -> Request<Body> {
+        Request::post(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(json.to_string()))
+            .unwrap()
+    }
+
+    let file = std::env::temp_dir().join(format!("url_shortener_check_{}.json", std::process::id()));
+    let app_state = AppState { store: Shared::default(), file: Some(file.clone()) };
+    let app = router(app_state);
+
+    // shorten, then follow the redirect
+    let (status, body) = send(&app, post_json("/shorten", r#"{"url": "https://www.rust-lang.org"}"#)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let created: ShortenResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(created.code, "0");
+
+    let response = app.clone().oneshot(Request::get("/0").body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(response.headers()["location"], "https://www.rust-lang.org");
+
+    let (_, body) = send(&app, Request::get("/stats/0").body(Body::empty()).unwrap()).await;
+    let link: Link = serde_json::from_slice(&body).unwrap();
+    assert_eq!(link.hits, 1);
+
+    // errors
+    let (status, _) = send(&app, post_json("/shorten", r#"{"url": "ftp://example.com"}"#)).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let (status, _) = send(&app, Request::get("/nope").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // persistence: a fresh store loaded from the file sees the link
+    let reloaded = load(&file);
+    assert_eq!(reloaded.links["0"].url, "https://www.rust-lang.org");
+    assert_eq!(encode(61), "Z");
+    assert_eq!(encode(62), "10");
+
+    std::fs::remove_file(&file).unwrap();
+    println!("all checks passed");
+}
+
+// MAIN --------------------------------------------------------------------
+
+#[tokio::main]
+async fn main() {
+    if std::env::args().any(|a| a == "--check") {
+        check().await;
+        return;
+    }
+
+    let file = PathBuf::from("links.json");
+    let store = Arc::new(RwLock::new(load(&file)));
+    let app = router(AppState { store: store.clone(), file: Some(file.clone()) });
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await.unwrap();
+    println!("listening on {BASE_URL}");
+    axum::serve(listener, app).with_graceful_shutdown(shutdown_signal()).await.unwrap();
+
+    // every request has finished: the hits counted since the last POST
+    // are only in memory, so save them once more
+    if let Err(e) = save(&file, &store.read().unwrap()) {
+        eprintln!("could not save {}: {e}", file.display());
+    }
+    println!("stopped");
+}
+
+// resolves on Ctrl-C, or on SIGTERM, which is what `kill`, systemd and
+// docker stop send
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.expect("could not listen for Ctrl-C");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        signal(SignalKind::terminate()).expect("could not listen for SIGTERM").recv().await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    println!("shutting down: finishing the requests in flight");
+}
+
+// EXERCISES ---------------------------------------------------------------
+
+/*
+ * (1) Let clients choose a custom code ({"url": ..., "code": "rust"}),
+ *     answering 409 Conflict when it is taken.
+ * (2) Saving the whole map on every write is O(n). Append each new link
+ *     to a log file instead and replay the log on startup.
+ * (3) Add an expiry time per link and a background tokio task that
+ *     purges expired links every minute.
+ * (4) with_graceful_shutdown waits for every connection, however long.
+ *     Give the drain a deadline: after the signal, wait at most 10
+ *     seconds for the server, then save and exit anyway (shutdown.rs).
+ */
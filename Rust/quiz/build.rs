@@ -0,0 +1,54 @@
+// QUIZ: THE NOTES, BUILT INTO THE BINARY ---------------------------------
+
+/*
+ * Writes $OUT_DIR/topics.rs for embedded.rs: a perfect-hash map
+ * (phf_codegen) from each topic's id to its text, the text an
+ * include_str! of the notes file, so the binary has the notes in it and
+ * finding one is a hash and a compare, however many there are.
+ *
+ * The notes are $QUIZ_NOTES at build time, or the directory above the
+ * crate, as notes::notes_dir finds them at run time; a directory without
+ * basics.rs fails the build, rather than building a quiz with no notes.
+ * Cargo reruns this when a file there changes, or one is added, so
+ * `cargo run` always shows the notes as they are.
+ *
+ * (build.rs goes next to Cargo.toml, not into src/.)
+ */
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=QUIZ_NOTES");
+    let notes = env::var_os("QUIZ_NOTES").map_or_else(|| Path::new(&env::var_os("CARGO_MANIFEST_DIR").unwrap()).join(".."), PathBuf::from);
+    let notes = notes.canonicalize().unwrap_or(notes);
+    if !notes.join("basics.rs").is_file() {
+        eprintln!("{} has no basics.rs, so it isn't the notes: set QUIZ_NOTES to the notes directory (Rust/)", notes.display());
+        std::process::exit(1);
+    }
+    println!("cargo:rerun-if-changed={}", notes.display());
+
+    let mut files: Vec<PathBuf> = fs::read_dir(&notes)
+        .map(|entries| entries.filter_map(|e| e.ok().map(|e| e.path())).collect())
+        .unwrap_or_default();
+    files.retain(|f| f.extension().is_some_and(|e| e == "rs"));
+    files.sort();
+
+    let topics: Vec<(String, String)> = files
+        .iter()
+        .map(|file| {
+            println!("cargo:rerun-if-changed={}", file.display());
+            let id = file.file_stem().unwrap().to_string_lossy().into_owned();
+            (id, format!("include_str!({:?})", file.display().to_string()))
+        })
+        .collect();
+    let mut map = phf_codegen::Map::new();
+    for (id, text) in &topics {
+        map.entry(id.as_str(), text);
+    }
+    let out = Path::new(&env::var_os("OUT_DIR").unwrap()).join("topics.rs");
+    let mut out = fs::File::create(out).unwrap();
+    writeln!(out, "static TOPICS: phf::Map<&'static str, &'static str> = {};", map.build()).unwrap();
+}
@@ -0,0 +1,180 @@
+// QUIZ: ADAPTIVE PRACTICE ------------------------------------------------
+
+/*
+ *     quiz adapt [--topics ownership,traits] [--count 10] [--strategy weakest]
+ *
+ * Practice without picking a pack: after every answer, a strategy picks
+ * the next question from everything on those topics (all of them if
+ * none are given) that hasn't come up in this session.
+ *
+ * What a strategy sees, besides the questions left:
+ *
+ *     history   every answer so far (progress.rs), this session's too
+ *     mastery   the score per topic, from that history
+ *     tags      per question: its topic, the error code it's about and
+ *               that code's family, e.g. ownership, E0502, borrowing
+ *
+ * The error code comes from a fix's `error`, from the cached verdict of
+ * a "will it compile?" question, or is E0382 for a variable that is no
+ * longer usable after a move. Choice, fill-in and output questions have
+ * only their topic.
+ *
+ * Strategies (the Strategy trait is the place to add another):
+ *
+ *     weakest    the default. Each question scores
+ *                  100 - mastery of its topic
+ *                  + 25 per tag it shares with a recent mistake
+ *                  + 15 if never answered, 10 if last answered wrong
+ *                and the highest goes next. Miss two E0502 questions and
+ *                the other borrowing questions jump the queue; a topic
+ *                that's mastered comes up only when nothing else is left.
+ *     in-order   pack by pack, question by question, like `practice`
+ */
+
+use crate::bank::{Kind, Pack, Question};
+use crate::cache::{self, Verdicts};
+use crate::profiles::Profile;
+use crate::progress::{self, Attempt, Mastery};
+use crate::theme::Theme;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::io;
+
+const RECENT: usize = 10;              // answers that count as "recent mistakes"
+
+// error codes by what they're about
+const FAMILIES: [(&str, &[&str]); 5] = [
+    ("borrowing", &["E0499", "E0502", "E0506", "E0596"]),
+    ("moves", &["E0382", "E0505", "E0507", "E0373"]),
+    ("lifetimes", &["E0106", "E0515", "E0597", "E0621", "E0700", "E0716", "E0726"]),
+    ("traits", &["E0038", "E0117", "E0277", "E0283", "E0599"]),
+    ("types", &["E0004", "E0282", "E0308"]),
+];
+
+pub struct State<'a> {
+    pub history: &'a [Attempt],
+    pub mastery: &'a [Mastery],
+    pub tags: &'a HashMap<String, Vec<String>>, // "pack/question" -> tags
+}
+
+pub trait Strategy {
+    // the index in `left` of the next question; None ends the session
+    fn pick(&mut self, left: &[(&Pack, &Question)], state: &State) -> Option<usize>;
+}
+
+pub struct Weakest;
+
+impl Strategy for Weakest {
+    fn pick(&mut self, left: &[(&Pack, &Question)], state: &State) -> Option<usize> {
+        let recent = &state.history[state.history.len().saturating_sub(RECENT)..];
+        let mut missed: HashMap<&str, usize> = HashMap::new();
+        for attempt in recent.iter().filter(|a| !a.correct) {
+            for tag in state.tags.get(&attempt.question).into_iter().flatten() {
+                *missed.entry(tag).or_default() += 1;
+            }
+        }
+
+        let score = |pack: &Pack, question: &Question| {
+            let name = format!("{}/{}", pack.id, question.id);
+            let mastered = state.mastery.iter().find(|m| m.topic == pack.topic).map_or(0, |m| m.score as usize);
+            let echoes: usize = state.tags.get(&name).into_iter().flatten().filter_map(|t| missed.get(t.as_str())).sum();
+            let retry = match state.history.iter().rev().find(|a| a.question == name) {
+                None => 15,
+                Some(last) if !last.correct => 10,
+                Some(_) => 0,
+            };
+            100 - mastered + 25 * echoes + retry
+        };
+        // ties go to the earlier question, so packs still read in order
+        (0..left.len()).max_by_key(|&i| (score(left[i].0, left[i].1), Reverse(i)))
+    }
+}
+
+pub struct InOrder;
+
+impl Strategy for InOrder {
+    fn pick(&mut self, left: &[(&Pack, &Question)], _: &State) -> Option<usize> {
+        if left.is_empty() { None } else { Some(0) }
+    }
+}
+
+pub fn strategy(name: &str) -> Option<Box<dyn Strategy>> {
+    match name {
+        "weakest" => Some(Box::new(Weakest)),
+        "in-order" => Some(Box::new(InOrder)),
+        _ => None,
+    }
+}
+
+// "error[E0502]: cannot borrow ..." -> "E0502"
+fn error_code(summary: &str) -> Option<&str> {
+    let start = summary.find("[E")? + 1;
+    summary.get(start..start + 5)
+}
+
+pub fn tags(pack: &Pack, question: &Question, verdicts: &Verdicts) -> Vec<String> {
+    let mut tags = vec![pack.topic.clone()];
+    let code = match &question.kind {
+        Kind::Fix { error, .. } => error.clone(),
+        Kind::Compiles { code } => verdicts
+            .get(&cache::key(pack.edition_of(question), code))
+            .and_then(|cached| error_code(&cached.summary).map(str::to_string)),
+        Kind::StillUsable { usable: false, .. } => Some("E0382".to_string()),
+        _ => None,
+    };
+    match code {
+        // "lifetime may not live long enough" has no code
+        Some(message) if message.contains("lifetime") && !message.starts_with('E') => tags.push("lifetimes".to_string()),
+        Some(code) => {
+            if let Some((family, _)) = FAMILIES.iter().find(|(_, codes)| codes.contains(&code.as_str())) {
+                tags.push(family.to_string());
+            }
+            tags.push(code);
+        }
+        None => {}
+    }
+    tags
+}
+
+pub fn adapt(
+    packs: &[Pack],
+    verdicts: &mut Verdicts,
+    topics: &[String],
+    count: usize,
+    strategy: &mut dyn Strategy,
+    profile: &Profile,
+    theme: &Theme,
+) -> Result<(), String> {
+    if let Some(topic) = topics.iter().find(|t| !packs.iter().any(|p| p.topic == **t)) {
+        return Err(format!("no packs on `{topic}`"));
+    }
+    let mut left: Vec<(&Pack, &Question)> = packs
+        .iter()
+        .filter(|p| topics.is_empty() || topics.contains(&p.topic))
+        .flat_map(|p| p.questions.iter().map(move |q| (p, q)))
+        .collect();
+    let tags: HashMap<String, Vec<String>> = packs
+        .iter()
+        .flat_map(|p| p.questions.iter().map(move |q| (p, q)))
+        .map(|(p, q)| (format!("{}/{}", p.id, q.id), tags(p, q, verdicts)))
+        .collect();
+    let mut history = progress::load(profile).map_err(|e| format!("cannot read progress: {e}"))?;
+
+    let mut input = io::stdin().lock();
+    let (mut asked, mut right) = (0, 0);
+    while asked < count {
+        let mastery = progress::mastery(packs, &history, progress::now());
+        let state = State { history: &history, mastery: &mastery, tags: &tags };
+        let Some(i) = strategy.pick(&left, &state).filter(|&i| i < left.len()) else { break };
+        let (pack, question) = left.remove(i);
+
+        println!("[{}/{count}, {}] {}\n", asked + 1, pack.topic, question.prompt.trim());
+        let Some(correct) = crate::practice_one(pack, question, &mut input, verdicts, profile, theme) else { break };
+        asked += 1;
+        right += correct as usize;
+        // practice_one recorded it; kept here too, so the next pick sees it
+        history.push(Attempt::now(&pack.id, &question.id, correct));
+    }
+    println!("{right} of {asked} right");
+    Ok(())
+}
@@ -0,0 +1,260 @@
+// QUIZ: THE CONTENT AS A JSON API ----------------------------------------
+
+/*
+ *     quiz serve --api [--port 3000] [--bind 127.0.0.1] [--progress]
+ *
+ * What the command line shows, for a front-end of someone else's to
+ * show instead: a phone app, a web dashboard. Read only, apart from
+ * answering questions:
+ *
+ *     GET  /api/topics                  [{ id, title, provider }], from
+ *                                       every provider (providers.rs)
+ *     GET  /api/topics/:id              { id, title, text }
+ *     GET  /api/topics/:id/snippets     [{ line, code }]: its paragraphs
+ *                                       of code, as quiz notes sees them
+ *     GET  /api/packs                   [{ id, title, topic, questions }]
+ *     GET  /api/packs/:id               { id, title, topic, tasks }: the
+ *                                       questions, without answers
+ *     POST /api/packs/:id/:question     { "answer": ".." } -> a mark:
+ *                                       right or not, the key and the
+ *                                       explanation, graded as the
+ *                                       classroom grades (rustc decides)
+ *     GET  /api/progress                { streak, mastery }, with
+ *                                       --progress only
+ *
+ * Errors are { "error": ".." } with a 4xx or 5xx status, like the
+ * classroom's. Every response allows any origin (CORS), so a page
+ * served from elsewhere can call it; with --progress, only pages from
+ * localhost, so one on some site can't read the history or answer in
+ * your name.
+ *
+ * --progress also records each answer in the history of the profile
+ * the server runs as (profiles.rs), and serves it: that's one
+ * learner's data, so it's off unless asked for, and the server listens
+ * on localhost unless --bind says otherwise (--bind 0.0.0.0 for a phone
+ * on the same network). There is no login: anyone who can reach the
+ * port can read what it serves.
+ */
+
+use crate::bank::Pack;
+use crate::cache::Verdicts;
+use crate::classroom::{self, ApiError, Mark, Task};
+use crate::notes;
+use crate::profiles::Profile;
+use crate::progress::{self, Mastery, Streak};
+use crate::providers::Registry;
+use axum::{
+    extract::{Path, Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Serialize)]
+struct TopicListed {
+    id: String,
+    title: String,
+    provider: String,
+}
+
+#[derive(Serialize)]
+struct TopicText {
+    id: String,
+    title: String,
+    text: String,
+}
+
+#[derive(Serialize)]
+struct Snippet {
+    line: usize,
+    code: String,
+}
+
+#[derive(Serialize)]
+struct PackListed {
+    id: String,
+    title: String,
+    topic: String,
+    questions: usize,
+}
+
+#[derive(Serialize)]
+struct PackTasks {
+    id: String,
+    title: String,
+    topic: String,
+    tasks: Vec<Task>,
+}
+
+#[derive(Deserialize)]
+struct Answer {
+    answer: String,
+}
+
+#[derive(Serialize)]
+struct Progress {
+    streak: Streak,
+    mastery: Vec<Mastery>,
+}
+
+/*
+ * Nothing here changes while the server runs but the verdict cache,
+ * which locks itself (cache.rs), so there's no lock to wait on: grading
+ * runs rustc in spawn_blocking, as the classroom's does, and answers
+ * are graded side by side while the reads go on.
+ */
+#[derive(Clone)]
+struct AppState {
+    topics: Arc<Vec<(String, TopicText)>>, // the provider's name, and the topic
+    packs: Arc<Vec<Pack>>,
+    verdicts: Arc<Verdicts>,
+    profile: Option<Arc<Profile>>,     // Some with --progress
+}
+
+// HANDLERS ---------------------------------------------------------------
+
+async fn topics(State(app): State<AppState>) -> Json<Vec<TopicListed>> {
+    let listed = app.topics.iter().map(|(provider, t)| TopicListed {
+        id: t.id.clone(),
+        title: t.title.clone(),
+        provider: provider.clone(),
+    });
+    Json(listed.collect())
+}
+
+async fn topic(State(app): State<AppState>, Path(id): Path<String>) -> Result<Json<TopicText>, ApiError> {
+    let (_, topic) = app.topics.iter().find(|(_, t)| t.id == id).ok_or_else(|| ApiError::NotFound(format!("topic `{id}`")))?;
+    Ok(Json(TopicText { id: topic.id.clone(), title: topic.title.clone(), text: topic.text.clone() }))
+}
+
+async fn snippets(State(app): State<AppState>, Path(id): Path<String>) -> Result<Json<Vec<Snippet>>, ApiError> {
+    let (_, topic) = app.topics.iter().find(|(_, t)| t.id == id).ok_or_else(|| ApiError::NotFound(format!("topic `{id}`")))?;
+    Ok(Json(notes::snippets(&topic.text).into_iter().map(|(line, code)| Snippet { line, code }).collect()))
+}
+
+async fn packs(State(app): State<AppState>) -> Json<Vec<PackListed>> {
+    let listed = app.packs.iter().map(|p| PackListed {
+        id: p.id.clone(),
+        title: p.title.clone(),
+        topic: p.topic.clone(),
+        questions: p.questions.len(),
+    });
+    Json(listed.collect())
+}
+
+async fn pack(State(app): State<AppState>, Path(id): Path<String>) -> Result<Json<PackTasks>, ApiError> {
+    let pack = app.packs.iter().find(|p| p.id == id).ok_or_else(|| ApiError::NotFound(format!("pack `{id}`")))?;
+    let tasks = pack.questions.iter().map(|q| classroom::task(&format!("{}/{}", pack.id, q.id), q)).collect();
+    Ok(Json(PackTasks { id: pack.id.clone(), title: pack.title.clone(), topic: pack.topic.clone(), tasks }))
+}
+
+async fn answer(
+    State(app): State<AppState>,
+    Path((pack, question)): Path<(String, String)>,
+    Json(request): Json<Answer>,
+) -> Result<Json<Mark>, ApiError> {
+    let marked = tokio::task::spawn_blocking(move || {
+        let reference = format!("{pack}/{question}");
+        let (pack, question) = classroom::find(&app.packs, &reference).ok_or_else(|| ApiError::NotFound(format!("question `{reference}`")))?;
+        let mark = classroom::mark(pack, question, &request.answer, &app.verdicts).map_err(|e| ApiError::Internal(format!("cannot run rustc: {e}")))?;
+        app.verdicts.save().map_err(|e| ApiError::Internal(format!("could not save verdicts: {e}")))?;
+        if let (Some(profile), false) = (&app.profile, request.answer.trim().is_empty()) {
+            let attempt = progress::Attempt::now(&pack.id, &question.id, mark.correct);
+            progress::record(profile, &[attempt]).map_err(|e| ApiError::Internal(format!("cannot record progress: {e}")))?;
+        }
+        Ok(mark)
+    });
+    marked.await.map_err(|e| ApiError::Internal(e.to_string()))?.map(Json)
+}
+
+async fn progress(State(app): State<AppState>) -> Result<Json<Progress>, ApiError> {
+    let Some(profile) = &app.profile else { return Err(ApiError::NotFound("progress: the server runs without --progress".to_string())) };
+    let history = progress::load(profile).map_err(|e| ApiError::Internal(format!("cannot read progress: {e}")))?;
+    let now = progress::now();
+    Ok(Json(Progress { streak: progress::streak(&history, now, 0), mastery: progress::mastery(&app.packs, &history, now) }))
+}
+
+// a browser asks before POSTing JSON from another origin
+async fn preflight() -> Response {
+    let headers = [(header::ACCESS_CONTROL_ALLOW_METHODS, "GET, POST"), (header::ACCESS_CONTROL_ALLOW_HEADERS, "content-type")];
+    (StatusCode::NO_CONTENT, headers).into_response()
+}
+
+// any origin, or with --progress, the page's own if it's on localhost
+async fn allow_origin(State(app): State<AppState>, request: Request, next: Next) -> Response {
+    let origin = request.headers().get(header::ORIGIN).cloned();
+    let mut response = next.run(request).await;
+    let allowed = match (&app.profile, origin) {
+        (None, _) => Some(HeaderValue::from_static("*")),
+        (Some(_), Some(origin)) if origin.to_str().is_ok_and(local) => Some(origin),
+        _ => None,
+    };
+    if let Some(allowed) = allowed {
+        response.headers_mut().insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, allowed);
+    }
+    if app.profile.is_some() {
+        response.headers_mut().insert(header::VARY, HeaderValue::from_static("origin"));
+    }
+    response
+}
+
+// http://localhost:8080, http://127.0.0.1, http://[::1]:3000
+fn local(origin: &str) -> bool {
+    let Some(host) = origin.strip_prefix("http://").or_else(|| origin.strip_prefix("https://")) else { return false };
+    let host = match host.rsplit_once(':') {
+        Some((host, port)) if !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit()) => host,
+        _ => host,
+    };
+    ["localhost", "127.0.0.1", "[::1]"].contains(&host)
+}
+
+fn router(app: AppState) -> Router {
+    Router::new()
+        .route("/api/topics", get(topics))
+        .route("/api/topics/:id", get(topic))
+        .route("/api/topics/:id/snippets", get(snippets))
+        .route("/api/packs", get(packs))
+        .route("/api/packs/:id", get(pack))
+        .route("/api/packs/:id/:question", axum::routing::post(answer).options(preflight))
+        .route("/api/progress", get(progress))
+        .layer(middleware::from_fn_with_state(app.clone(), allow_origin))
+        .with_state(app)
+}
+
+// the topics are read once, at the start: a provider that's a program
+// isn't run again per request
+pub fn serve(registry: &Registry, packs: Vec<Pack>, verdicts: Verdicts, bind: &str, port: u16, profile: Option<Profile>) -> Result<(), String> {
+    let mut topics = Vec::new();
+    for provider in registry.providers() {
+        for t in provider.topics()? {
+            topics.push((provider.name().to_string(), TopicText { id: t.id, title: t.title, text: t.text }));
+        }
+    }
+    println!("API on http://{bind}:{port}/api/topics");
+    if let Some(profile) = &profile {
+        println!("serving and recording the progress of {}", profile.name().unwrap_or("the default profile"));
+    }
+
+    let app = AppState { topics: Arc::new(topics), packs: Arc::new(packs), verdicts: Arc::new(verdicts), profile: profile.map(Arc::new) };
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+    let result = runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind((bind, port)).await?;
+        axum::serve(listener, router(app)).await
+    });
+    result.map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_localhost_is_local() {
+        assert!(local("http://localhost:8080") && local("http://127.0.0.1") && local("http://[::1]:3000"));
+        assert!(!local("http://localhost.example.com") && !local("https://example.com") && !local("null"));
+    }
+}
@@ -0,0 +1,388 @@
+// QUIZ: THE STD API A TOPIC USES ------------------------------------------
+
+/*
+ *     quiz apis collections [--json]   what its snippets use from std,
+ *                                      with the lines and the docs
+ *     quiz apis [--json]               every topic's, and where
+ *
+ *     HashMap::entry        190, 203       https://doc.rust-lang.org/std/
+ *                                          collections/struct.HashMap.html
+ *                                          #method.entry
+ *
+ * Read from the code, not compiled: a table of the std items the notes
+ * are about (STD below: types, their methods, free functions, macros)
+ * and a walk over each snippet's tokens that keeps track of what type
+ * a value has where that's plain to see:
+ *
+ *     let mut v = Vec::new();          v is a Vec, from what made it
+ *     let s: String = ..;              or from its annotation
+ *     v.push(5);                       so this is Vec::push
+ *     v.iter().map(..)                 iter gives an Iterator: map is
+ *                                      Iterator::map
+ *     s.push_str("!")                  an unknown receiver is fine when
+ *                                      only one type has the method
+ *
+ * Bindings last to the end of the topic, as the notes' snippets build
+ * on each other. A method called on something it can't see the type
+ * of, and that several types have (`unwrap`, `len`), isn't listed:
+ * missing an item is better than naming the wrong one. Nor is anything
+ * outside the table: it's std as the notes teach it, not all of it.
+ *
+ * The links are to doc.rust-lang.org, where docs.rs sends std; a slice
+ * method on a Vec is on Vec's page, as rustdoc lists what Deref gives.
+ */
+
+use crate::notes;
+use crate::providers::Topic;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+const DOCS: &str = "https://doc.rust-lang.org/std";
+
+struct Std {
+    name: &'static str,
+    page: &'static str,                // under DOCS, without .html
+    module: bool,                      // a module's free functions, not a type's methods
+    items: &'static [&'static str],
+}
+
+const STD: &[Std] = &[
+    Std {
+        name: "Vec",
+        page: "vec/struct.Vec",
+        module: false,
+        items: &[
+            "new", "with_capacity", "push", "pop", "insert", "remove", "get", "get_mut", "len", "is_empty", "iter", "iter_mut",
+            "contains", "sort", "sort_by", "extend", "first", "last", "retain", "drain", "clear", "truncate", "capacity", "dedup",
+            "split_at", "windows", "chunks", "swap",
+        ],
+    },
+    Std {
+        name: "String",
+        page: "string/struct.String",
+        module: false,
+        items: &[
+            "new", "from", "with_capacity", "push_str", "push", "len", "is_empty", "as_str", "chars", "bytes", "char_indices",
+            "split_whitespace", "split", "lines", "trim", "to_uppercase", "to_lowercase", "contains", "starts_with", "replace",
+            "capacity", "insert", "clear",
+        ],
+    },
+    Std {
+        name: "HashMap",
+        page: "collections/struct.HashMap",
+        module: false,
+        items: &["new", "insert", "get", "get_mut", "entry", "contains_key", "remove", "keys", "values", "iter", "len"],
+    },
+    Std {
+        name: "Entry",
+        page: "collections/hash_map/enum.Entry",
+        module: false,
+        items: &["or_insert", "or_insert_with", "or_default", "and_modify"],
+    },
+    Std { name: "HashSet", page: "collections/struct.HashSet", module: false, items: &["new", "insert", "contains", "remove"] },
+    Std {
+        name: "Option",
+        page: "option/enum.Option",
+        module: false,
+        items: &[
+            "unwrap", "expect", "unwrap_or", "unwrap_or_else", "unwrap_or_default", "map", "and_then", "ok_or", "is_some", "is_none",
+            "take", "as_ref",
+        ],
+    },
+    Std {
+        name: "Result",
+        page: "result/enum.Result",
+        module: false,
+        items: &["unwrap", "expect", "unwrap_or", "unwrap_or_else", "map", "map_err", "and_then", "ok", "is_ok", "is_err"],
+    },
+    Std { name: "Box", page: "boxed/struct.Box", module: false, items: &["new", "leak", "into_raw", "from_raw"] },
+    Std { name: "Rc", page: "rc/struct.Rc", module: false, items: &["new", "clone", "strong_count", "downgrade"] },
+    Std { name: "Arc", page: "sync/struct.Arc", module: false, items: &["new", "clone", "strong_count"] },
+    Std { name: "Mutex", page: "sync/struct.Mutex", module: false, items: &["new", "lock", "into_inner"] },
+    Std { name: "RefCell", page: "cell/struct.RefCell", module: false, items: &["new", "borrow", "borrow_mut"] },
+    Std {
+        name: "Iterator",
+        page: "iter/trait.Iterator",
+        module: false,
+        items: &[
+            "next", "map", "filter", "collect", "sum", "fold", "enumerate", "zip", "rev", "take", "skip", "count", "any", "all",
+            "find", "chain", "flat_map", "max", "min", "for_each", "filter_map", "position",
+        ],
+    },
+    Std { name: "JoinHandle", page: "thread/struct.JoinHandle", module: false, items: &["join"] },
+    Std { name: "Sender", page: "sync/mpsc/struct.Sender", module: false, items: &["send", "clone"] },
+    Std { name: "Receiver", page: "sync/mpsc/struct.Receiver", module: false, items: &["recv", "try_recv", "iter"] },
+    Std { name: "thread", page: "thread", module: true, items: &["spawn", "sleep", "scope", "current"] },
+    Std { name: "mpsc", page: "sync/mpsc", module: true, items: &["channel", "sync_channel"] },
+    Std { name: "mem", page: "mem", module: true, items: &["swap", "replace", "take", "drop", "size_of"] },
+    Std { name: "ptr", page: "ptr", module: true, items: &["null", "null_mut", "read", "write"] },
+];
+
+const MACROS: &[&str] = &[
+    "println", "print", "eprintln", "format", "vec", "panic", "assert", "assert_eq", "write", "writeln", "matches", "dbg", "todo",
+    "unreachable",
+];
+
+// what a call gives back, where it's one of the types above; calls not
+// here give something unknown
+const RETURNS: &[(&str, &str)] = &[
+    ("iter", "Iterator"), ("iter_mut", "Iterator"), ("into_iter", "Iterator"), ("chars", "Iterator"), ("bytes", "Iterator"),
+    ("lines", "Iterator"), ("split_whitespace", "Iterator"), ("keys", "Iterator"), ("values", "Iterator"), ("map", "Iterator"),
+    ("filter", "Iterator"), ("enumerate", "Iterator"), ("zip", "Iterator"), ("rev", "Iterator"), ("skip", "Iterator"),
+    ("take", "Iterator"), ("chain", "Iterator"), ("flat_map", "Iterator"), ("filter_map", "Iterator"), ("pop", "Option"),
+    ("get", "Option"), ("first", "Option"), ("last", "Option"), ("entry", "Entry"), ("spawn", "JoinHandle"),
+    ("to_string", "String"), ("to_uppercase", "String"), ("to_lowercase", "String"), ("format", "String"), ("vec", "Vec"),
+    ("channel", CHANNEL), ("sync_channel", CHANNEL),
+];
+
+// what mpsc::channel gives, for `let (tx, rx) = ..`
+const CHANNEL: &str = "(Sender, Receiver)";
+
+#[derive(Serialize, Deserialize)]
+pub struct Used {
+    pub item: String,                  // "Vec::push", "Vec", "println!"
+    pub url: String,
+    pub lines: Vec<usize>,
+}
+
+fn std_type(name: &str) -> Option<&'static Std> {
+    STD.iter().find(|s| s.name == name)
+}
+
+fn url(std: &Std, item: Option<&str>) -> String {
+    match item {
+        None if std.module => format!("{DOCS}/{}/index.html", std.page),
+        None => format!("{DOCS}/{}.html", std.page),
+        Some(item) if std.module => format!("{DOCS}/{}/fn.{item}.html", std.page),
+        // a trait's required method has its own anchor
+        Some("next") if std.name == "Iterator" => format!("{DOCS}/{}.html#tymethod.next", std.page),
+        Some(item) => format!("{DOCS}/{}.html#method.{item}", std.page),
+    }
+}
+
+// TOKENS -----------------------------------------------------------------
+
+#[derive(Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str,                               // a string literal
+    Punct(char),
+    Path,                              // ::
+}
+
+// the code's tokens with their line numbers, comments left out
+fn tokens(code: &str, first: usize) -> Vec<(usize, Token)> {
+    let mut out = Vec::new();
+    for (n, line) in (first..).zip(code.lines()) {
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '/' if chars.peek() == Some(&'/') => break,
+                '"' => {
+                    let mut escaped = false;
+                    for c in chars.by_ref() {
+                        match c {
+                            '"' if !escaped => break,
+                            '\\' => escaped = !escaped,
+                            _ => escaped = false,
+                        }
+                    }
+                    out.push((n, Token::Str));
+                }
+                ':' if chars.peek() == Some(&':') => {
+                    chars.next();
+                    out.push((n, Token::Path));
+                }
+                c if c.is_alphanumeric() || c == '_' => {
+                    let mut ident = c.to_string();
+                    while let Some(&c) = chars.peek().filter(|c| c.is_alphanumeric() || **c == '_') {
+                        ident.push(c);
+                        chars.next();
+                    }
+                    out.push((n, Token::Ident(ident)));
+                }
+                c if c.is_whitespace() => {}
+                c => out.push((n, Token::Punct(c))),
+            }
+        }
+    }
+    out
+}
+
+// THE WALK ---------------------------------------------------------------
+
+#[derive(Default)]
+struct Walk {
+    bindings: HashMap<String, &'static str>, // a name, and the type it was last bound to
+    found: BTreeMap<String, (String, Vec<usize>)>, // item -> (url, lines)
+}
+
+impl Walk {
+    fn found(&mut self, item: String, url: String, line: usize) {
+        let (_, lines) = self.found.entry(item).or_insert((url, Vec::new()));
+        if lines.last() != Some(&line) {
+            lines.push(line);
+        }
+    }
+
+    // `receiver.method(` with the receiver's type if it's known
+    fn method(&mut self, receiver: Option<&'static str>, method: &str, line: usize) {
+        let has = |s: &&Std| !s.module && s.items.contains(&method);
+        let std = match receiver.and_then(std_type).filter(has) {
+            Some(std) => Some(std),
+            None => {
+                let mut candidates = STD.iter().filter(has);
+                match (candidates.next(), candidates.next(), receiver) {
+                    (Some(only), None, None) => Some(only),
+                    _ => None,
+                }
+            }
+        };
+        if let Some(std) = std {
+            self.found(format!("{}::{method}", std.name), url(std, Some(method)), line);
+        }
+    }
+
+    // a `let`'s pattern, from just after the `let`: the names bound, the
+    // type of the annotation if it's one of the table's, and where the
+    // value starts
+    fn pattern(&mut self, tokens: &[(usize, Token)], mut i: usize) -> (Vec<String>, Option<&'static str>, usize) {
+        let (mut names, mut annotated) = (Vec::new(), None);
+        let mut annotating = false;
+        while let Some((line, token)) = tokens.get(i) {
+            match token {
+                Token::Punct('=') => return (names, annotated, i + 1),
+                Token::Punct(';') => break,
+                Token::Punct(':') => annotating = true,
+                Token::Ident(name) if annotating => {
+                    self.type_named(name, *line);
+                    if annotated.is_none() {
+                        annotated = std_type(name).filter(|s| !s.module).map(|s| s.name);
+                    }
+                }
+                Token::Ident(name) if name != "mut" && name != "ref" => names.push(name.clone()),
+                _ => {}
+            }
+            i += 1;
+        }
+        (names, annotated, i)
+    }
+
+    fn snippet(&mut self, tokens: &[(usize, Token)]) {
+        // the type of the value just read, and, per open bracket, the
+        // type the bracketed expression gives once it closes
+        let mut current: Option<&'static str> = None;
+        let mut open: Vec<Option<&'static str>> = Vec::new();
+        // a `let` whose value is being read, and the depth of brackets
+        // it's at
+        let mut binding: Option<(Vec<String>, Option<&'static str>, usize)> = None;
+        let before = |i: usize| i.checked_sub(1).and_then(|i| tokens.get(i)).map(|(_, t)| t);
+        let mut i = 0;
+        while let Some((line, token)) = tokens.get(i) {
+            let (line, after) = (*line, tokens.get(i + 1).map(|(_, t)| t));
+            let call = after == Some(&Token::Punct('('));
+            let returns = |name: &str| RETURNS.iter().find(|(call, _)| *call == name).map(|(_, t)| *t);
+            match token {
+                Token::Ident(name) if name == "let" => {
+                    let (names, annotated, next) = self.pattern(tokens, i + 1);
+                    binding = Some((names, annotated, open.len()));
+                    current = None;
+                    i = next;
+                    continue;
+                }
+                Token::Ident(name) if after == Some(&Token::Punct('!')) && MACROS.contains(&name.as_str()) => {
+                    self.found(format!("{name}!"), format!("{DOCS}/macro.{name}.html"), line);
+                    current = returns(name);
+                }
+                Token::Ident(name) if before(i) == Some(&Token::Punct('.')) => {
+                    if call {
+                        self.method(current, name, line);
+                    }
+                    current = if call { returns(name) } else { None };
+                }
+                Token::Ident(name) if after == Some(&Token::Path) => {
+                    current = None;
+                    if let Some(std) = std_type(name) {
+                        match tokens.get(i + 2).map(|(_, t)| t) {
+                            Some(Token::Ident(item)) if std.items.contains(&item.as_str()) => {
+                                self.found(format!("{}::{item}", std.name), url(std, Some(item)), line);
+                                current = match item.as_str() {
+                                    "new" | "from" | "with_capacity" if !std.module => Some(std.name),
+                                    item => returns(item),
+                                };
+                            }
+                            _ => self.type_named(name, line),
+                        }
+                    }
+                }
+                Token::Ident(_) if before(i) == Some(&Token::Path) => {} // a path's item, seen with its type
+                Token::Ident(name) => {
+                    self.type_named(name, line);
+                    current = self.bindings.get(name).copied();
+                }
+                Token::Str => current = Some("str"),
+                Token::Path | Token::Punct('.' | '?' | '!') => {}
+                // a call's or a macro's brackets give what was worked out
+                // for the name before them
+                Token::Punct('(' | '[' | '{') => {
+                    let called = matches!(before(i), Some(Token::Ident(_) | Token::Punct('!')));
+                    open.push(if called { current } else { None });
+                    current = None;
+                }
+                Token::Punct(')' | ']' | '}') => current = open.pop().flatten(),
+                Token::Punct(';') => {
+                    if let Some((names, annotated, _)) = binding.take_if(|(.., depth)| open.len() <= *depth) {
+                        self.bind(&names, annotated.or(current));
+                    }
+                    current = None;
+                }
+                Token::Punct(_) => current = None,
+            }
+            i += 1;
+        }
+    }
+
+    fn bind(&mut self, names: &[String], value: Option<&'static str>) {
+        match (names, value) {
+            ([tx, rx], Some(CHANNEL)) => {
+                self.bindings.insert(tx.clone(), "Sender");
+                self.bindings.insert(rx.clone(), "Receiver");
+            }
+            ([name], Some(value)) => {
+                self.bindings.insert(name.clone(), value);
+            }
+            _ => {
+                for name in names {
+                    self.bindings.remove(name);
+                }
+            }
+        }
+    }
+
+    // a type of the table named on its own: `Vec<i32>`, `Option<&T>`
+    fn type_named(&mut self, name: &str, line: usize) {
+        if let Some(std) = std_type(name).filter(|s| !s.module) {
+            self.found(std.name.to_string(), url(std, None), line);
+        }
+    }
+}
+
+// what a topic's snippets use from std, by item
+pub fn used(text: &str) -> Vec<Used> {
+    let mut walk = Walk::default();
+    for (first, code) in notes::snippets(text) {
+        walk.snippet(&tokens(&code, first));
+    }
+    walk.found.into_iter().map(|(item, (url, lines))| Used { item, url, lines }).collect()
+}
+
+// every topic's: item -> (url, the topics that use it)
+pub fn index(topics: &[Topic]) -> BTreeMap<String, (String, Vec<String>)> {
+    let mut index: BTreeMap<String, (String, Vec<String>)> = BTreeMap::new();
+    for topic in topics {
+        for used in &topic.parsed().apis {
+            index.entry(used.item.clone()).or_insert((used.url.clone(), Vec::new())).1.push(topic.id.clone());
+        }
+    }
+    index
+}
@@ -0,0 +1,312 @@
+// QUIZ: THE QUESTION BANK ------------------------------------------------
+
+/*
+ * Questions live in packs/, one TOML file per pack. TOML because code
+ * sits comfortably in its ''' literal strings: no escaping, and the
+ * snippet in the file is exactly what rustc sees.
+ *
+ *     title = "Add the lifetimes"
+ *     topic = "generics"                  the notes file it drills
+ *
+ *     [[question]]
+ *     id = "two-inputs"
+ *     kind = "fix"
+ *     prompt = "..."
+ *     broken = '''fn longest(x: &str, y: &str) -> &str { .. }'''
+ *     solved = '''fn longest<'a>(x: &'a str, y: &'a str) -> &'a str { .. }'''
+ *     error = "E0106"                     what the broken form fails with
+ *     explanation = "..."
+ *
+ * `kind` picks the question type; the fields after it depend on the
+ * kind. The pack id is the file name without .toml.
+ *
+ *     fix            broken, solved, error      make this compile
+ *     still-usable   prelude, code, variable,   is the variable still
+ *                    usable                     usable after the code?
+ *     compiles       code                       will it compile?
+ *     output         code, answer               what does it evaluate to?
+ *     choice         code, choices, correct     pick one of the choices
+ *     fill-in        code, answers              fill in the ___ in code
+ *
+ * A [[variations]] block writes many still-usable questions at once: one
+ * snippet with a {x} hole, and a list of fillers with their answers.
+ *
+ *     [[variations]]
+ *     id = "assign"
+ *     code = '''
+ *     let s1 = {x};
+ *     let s2 = s1;
+ *     '''
+ *     variable = "s1"
+ *     explanation = "..."                 shown after every variant
+ *     variants = [
+ *         { x = 'String::from("hello")', usable = false, why = "..." },
+ *         { x = '5', usable = true, why = "..." },
+ *     ]
+ *
+ * becomes questions assign-1, assign-2, ... when the pack is loaded.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+pub struct Pack {
+    #[serde(skip)]
+    pub id: String,
+    pub title: String,
+    pub topic: String,
+    #[serde(default = "default_edition")]
+    pub edition: String,               // for rustc; a question can override it
+    #[serde(rename = "question", default)]
+    pub questions: Vec<Question>,
+    #[serde(default)]
+    variations: Vec<Variations>,
+}
+
+fn default_edition() -> String {
+    "2021".to_string()
+}
+
+// Serialize is for import.rs, which writes questions into pack files;
+// fields are in the order the packs write them
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Question {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub prompt: String,                // each kind has a default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edition: Option<String>,
+    #[serde(flatten)]
+    pub kind: Kind,
+    pub explanation: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Kind {
+    // "Make this compile": broken must fail (with `error`, if given),
+    // solved must compile
+    Fix {
+        broken: String,
+        solved: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    // "After the last line, is `variable` still usable?" The code runs
+    // inside a function, after the items in `prelude`
+    StillUsable {
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        prelude: String,
+        code: String,
+        variable: String,
+        usable: bool,
+    },
+    // "Will this compile?" No answer stored: rustc decides (cache.rs)
+    Compiles { code: String },
+    // "What does this evaluate to?" `code` is a block body whose last
+    // expression is the value; `answer` is its {:?} output
+    Output { code: String, answer: String },
+    // Multiple choice. With a ___ in `code`, each choice is tried in the
+    // blank and only the `correct` one (counting from 1) may compile;
+    // without one, the code is context and the prompt asks the question
+    Choice {
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        code: String,
+        choices: Vec<String>,
+        correct: usize,
+    },
+    // "Fill in the ___": every one of `answers` must make `code` compile,
+    // and the blank left empty must not
+    FillIn { code: String, answers: Vec<String> },
+}
+
+pub const BLANK: &str = "___";
+
+impl Kind {
+    // as written in the packs
+    pub fn name(&self) -> &'static str {
+        match self {
+            Kind::Fix { .. } => "fix",
+            Kind::StillUsable { .. } => "still-usable",
+            Kind::Compiles { .. } => "compiles",
+            Kind::Output { .. } => "output",
+            Kind::Choice { .. } => "choice",
+            Kind::FillIn { .. } => "fill-in",
+        }
+    }
+
+    // the snippet the question shows; the broken form for a fix
+    pub fn code(&self) -> &str {
+        match self {
+            Kind::Fix { broken: code, .. }
+            | Kind::StillUsable { code, .. }
+            | Kind::Compiles { code }
+            | Kind::Output { code, .. }
+            | Kind::Choice { code, .. }
+            | Kind::FillIn { code, .. } => code,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Variations {
+    id: String,
+    prompt: Option<String>,
+    #[serde(default)]
+    prelude: String,
+    code: String,
+    variable: String,
+    explanation: String,
+    variants: Vec<Variant>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Variant {
+    x: String,
+    usable: bool,
+    why: String,
+    variable: Option<String>,          // when this variant asks about another name
+}
+
+impl Variations {
+    fn expand(self) -> impl Iterator<Item = Question> {
+        self.variants.into_iter().enumerate().map(move |(i, variant)| {
+            let variable = variant.variable.unwrap_or_else(|| self.variable.clone());
+            Question {
+                id: format!("{}-{}", self.id, i + 1),
+                prompt: self.prompt.clone().unwrap_or_default(),
+                edition: None,
+                kind: Kind::StillUsable {
+                    prelude: self.prelude.clone(),
+                    code: self.code.replace("{x}", &variant.x),
+                    variable,
+                    usable: variant.usable,
+                },
+                explanation: format!("{}\n\n{}", variant.why.trim(), self.explanation.trim()),
+            }
+        })
+    }
+}
+
+fn default_prompt(kind: &Kind) -> String {
+    match kind {
+        Kind::Fix { .. } => "Make this compile.".to_string(),
+        Kind::StillUsable { variable, .. } => format!("After the last line, is `{variable}` still usable?"),
+        Kind::Compiles { .. } => "Will this compile?".to_string(),
+        Kind::Output { .. } => "What does this evaluate to? (answer as {:?} prints it)".to_string(),
+        Kind::Choice { code, .. } if code.contains(BLANK) => "Which of these makes it compile?".to_string(),
+        Kind::Choice { .. } => String::new(), // the question is the prompt
+        Kind::FillIn { .. } => format!("Fill in the {BLANK} so that it compiles."),
+    }
+}
+
+pub const EDITIONS: [&str; 4] = ["2015", "2018", "2021", "2024"];
+
+// what's wrong with a question, as (field, problem) pairs; empty if
+// nothing. Pack loading stops at the first, import reports them all
+pub fn validate(question: &Question) -> Vec<(&'static str, String)> {
+    let mut problems = Vec::new();
+    let id_chars = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-';
+    if question.id.is_empty() || !question.id.chars().all(id_chars) {
+        problems.push(("id", format!("`{}`: use lowercase letters, digits and -", question.id)));
+    }
+    if let Some(edition) = question.edition.as_deref().filter(|e| !EDITIONS.contains(e)) {
+        problems.push(("edition", format!("`{edition}` is not an edition ({})", EDITIONS.join(", "))));
+    }
+    let texts = match &question.kind {
+        Kind::Fix { broken, solved, .. } => vec![("broken", broken), ("solved", solved)],
+        Kind::StillUsable { code, variable, .. } => vec![("code", code), ("variable", variable)],
+        Kind::Compiles { code } => vec![("code", code)],
+        Kind::Output { code, answer } => vec![("code", code), ("answer", answer)],
+        Kind::Choice { code, choices, correct } => {
+            if choices.len() < 2 {
+                problems.push(("choices", "needs at least two".to_string()));
+            }
+            if !(1..=choices.len()).contains(correct) {
+                problems.push(("correct", format!("{correct}: the choices are numbered from 1")));
+            }
+            if !code.contains(BLANK) && question.prompt.trim().is_empty() {
+                problems.push(("prompt", format!("needed when the code has no {BLANK}")));
+            }
+            choices.iter().map(|choice| ("choices", choice)).collect()
+        }
+        Kind::FillIn { code, answers } => {
+            if !code.contains(BLANK) {
+                problems.push(("code", format!("no {BLANK} to fill in")));
+            }
+            if answers.is_empty() {
+                problems.push(("answers", "needs at least one".to_string()));
+            }
+            answers.iter().map(|answer| ("answers", answer)).chain([("code", code)]).collect()
+        }
+    };
+    for (field, text) in texts.into_iter().chain([("explanation", &question.explanation)]) {
+        if text.trim().is_empty() {
+            problems.push((field, "empty".to_string()));
+        }
+    }
+    problems
+}
+
+impl Pack {
+    // an empty pack, for questions about to go into a new file
+    pub fn new(id: &str, title: &str, topic: &str) -> Pack {
+        Pack {
+            id: id.to_string(),
+            title: title.to_string(),
+            topic: topic.to_string(),
+            edition: default_edition(),
+            questions: Vec::new(),
+            variations: Vec::new(),
+        }
+    }
+
+    pub fn edition_of<'a>(&'a self, question: &'a Question) -> &'a str {
+        question.edition.as_deref().unwrap_or(&self.edition)
+    }
+}
+
+pub fn load(path: &Path) -> Result<Pack, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let id = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    parse(id, &text, &path.display().to_string())
+}
+
+// a pack from its TOML; `origin` is where it came from, for the errors
+pub fn parse(id: &str, text: &str, origin: &str) -> Result<Pack, String> {
+    let mut pack: Pack = toml::from_str(text).map_err(|e| format!("{origin}: {e}"))?;
+    pack.id = id.to_string();
+    for variations in std::mem::take(&mut pack.variations) {
+        pack.questions.extend(variations.expand());
+    }
+
+    for question in &mut pack.questions {
+        if question.prompt.trim().is_empty() {
+            question.prompt = default_prompt(&question.kind);
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    for question in &pack.questions {
+        if let Some((field, problem)) = validate(question).into_iter().next() {
+            return Err(format!("{origin}: question `{}`: `{field}`: {problem}", question.id));
+        }
+        if !seen.insert(&question.id) {
+            return Err(format!("{origin}: duplicate question id `{}`", question.id));
+        }
+    }
+    Ok(pack)
+}
+
+// every *.toml in the directory, sorted by file name
+pub fn load_dir(dir: &Path) -> Result<Vec<Pack>, String> {
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .map_err(|e| format!("{}: {e}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+    paths.iter().map(|p| load(p)).collect()
+}
@@ -0,0 +1,173 @@
+// QUIZ: BUILDS KEPT BETWEEN RUNS -----------------------------------------
+
+/*
+ *     quiz cache                 what's kept, and how much of the limit
+ *     quiz cache clean           all of it: builds, parsed topics (parsed.rs)
+ *                                and the search index (similar.rs)
+ *
+ * Every snippet the harness compiles is kept, in
+ *
+ *     ~/.cache/langscape/builds/<fingerprint>/
+ *
+ * (config::cache_home), the fingerprint a sha256 of the source, its
+ * edition, whether it's a program or a library, and rustc --version.
+ * The directory is what rustc wrote, the program when there is one, and
+ * verdict.json with what rustc said. Compiling the same snippet again,
+ * as quiz notes does for every file when one was edited, quiz verify
+ * for a solution that hasn't changed, or practice for an answer given
+ * before, finds it there instead of running rustc. Programs are still
+ * run every time: what they print is what's checked. A new rustc is a
+ * new fingerprint for everything, so nothing it didn't compile is
+ * believed; Miri's builds aren't kept (harness::miri).
+ *
+ * A build is made in a directory of its own next to the others
+ * (tmp-PID-N) and renamed into place when it's whole, so two quizzes
+ * compiling the same snippet can't see half of one; the second rename
+ * loses, and its copy goes. So does one whose rustc failed or took too
+ * long. A quiz killed mid-build leaves its directory behind: one that
+ * hasn't changed for STALE counts against the cap, and goes first.
+ *
+ * The builds are capped at cache_mb in the config file (1024 without
+ * it): after each build, the ones used longest ago go until the rest
+ * fit. Using one touches its verdict.json, which is how "used" is told.
+ */
+
+use crate::config;
+use crate::harness::Verdict;
+use crate::report;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime};
+
+const VERDICT: &str = "verdict.json";
+
+// a build directory no quiz has written to for this long was left by
+// one that stopped; rustc gets harness.rs's 30 s
+const STALE: Duration = Duration::from_secs(10 * 60);
+
+// the cap, in bytes: cache_mb, set by main.rs
+static LIMIT: AtomicU64 = AtomicU64::new(1024 * 1024 * 1024);
+
+pub fn limit(bytes: u64) {
+    LIMIT.store(bytes, Ordering::Relaxed);
+}
+
+pub fn root() -> PathBuf {
+    config::cache_home().join("builds")
+}
+
+pub fn fingerprint(source: &str, edition: &str, crate_type: &str, rustc: &str) -> String {
+    report::sha256(&[source, edition, crate_type, rustc].join("\0"))
+}
+
+// what rustc said about a build that's kept, which is now used
+pub fn load(fingerprint: &str) -> Option<(PathBuf, Verdict)> {
+    let dir = root().join(fingerprint);
+    let json = fs::read(dir.join(VERDICT)).ok()?;
+    let verdict = serde_json::from_slice(&json).ok()?;
+    File::options().write(true).open(dir.join(VERDICT)).and_then(|f| f.set_modified(SystemTime::now())).ok();
+    Some((dir, verdict))
+}
+
+// a directory that goes when it's dropped, whatever happened in it,
+// unless it's been renamed
+pub struct Scratch(PathBuf);
+
+impl Scratch {
+    pub fn new(dir: PathBuf) -> io::Result<Scratch> {
+        fs::create_dir_all(&dir)?;
+        Ok(Scratch(dir))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        fs::remove_dir_all(&self.0).ok();
+    }
+}
+
+// an empty directory to build in, on the same file system as the
+// builds, so it can be renamed to one
+pub fn scratch() -> io::Result<Scratch> {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let n = NEXT.fetch_add(1, Ordering::Relaxed);
+    Scratch::new(root().join(format!("tmp-{}-{n}", std::process::id())))
+}
+
+// `scratch`, built, kept as the build of `fingerprint`; if there's one
+// already, from another quiz, that one's used and `scratch` goes
+pub fn store(scratch: Scratch, fingerprint: &str, verdict: &Verdict) -> io::Result<PathBuf> {
+    fs::write(scratch.path().join(VERDICT), serde_json::to_vec(verdict)?)?;
+    let dir = root().join(fingerprint);
+    fs::rename(scratch.path(), &dir).ok();
+    trim(LIMIT.load(Ordering::Relaxed), &dir)?;
+    Ok(dir)
+}
+
+// the files in `dir`, in bytes
+pub fn size(dir: &Path) -> u64 {
+    let files = fs::read_dir(dir).into_iter().flatten().filter_map(|e| e.ok()?.metadata().ok());
+    files.map(|m| m.len()).sum()
+}
+
+// a build directory left by a quiz that stopped mid-build
+fn stale(dir: &Path) -> bool {
+    let Some(name) = dir.file_name().and_then(|n| n.to_str()) else { return false };
+    let Some(pid) = name.strip_prefix("tmp-").and_then(|rest| rest.split('-').next()) else { return false };
+    let changed = fs::metadata(dir).and_then(|m| m.modified()).unwrap_or(SystemTime::UNIX_EPOCH);
+    pid != std::process::id().to_string() && changed.elapsed().is_ok_and(|age| age > STALE)
+}
+
+// every build, and every stale one: its directory, size, and when it
+// was last used, UNIX_EPOCH for the stale ones, so they go first
+fn builds() -> Vec<(PathBuf, u64, SystemTime)> {
+    let entries = fs::read_dir(root()).into_iter().flatten().filter_map(|e| e.ok().map(|e| e.path()));
+    let kept = entries.filter(|dir| stale(dir) || !dir.file_name().is_some_and(|n| n.to_string_lossy().starts_with("tmp-")));
+    let used = |dir: &Path| fs::metadata(dir.join(VERDICT)).and_then(|m| m.modified()).unwrap_or(SystemTime::UNIX_EPOCH);
+    kept.map(|dir| {
+        let (size, used) = if stale(&dir) { (tree_size(&dir), SystemTime::UNIX_EPOCH) } else { (size(&dir), used(&dir)) };
+        (dir, size, used)
+    })
+    .collect()
+}
+
+// a stale build's files, in bytes: rustc may have been writing anywhere
+fn tree_size(dir: &Path) -> u64 {
+    let entries = fs::read_dir(dir).into_iter().flatten().filter_map(|e| e.ok());
+    entries.map(|e| if e.path().is_dir() { tree_size(&e.path()) } else { e.metadata().map_or(0, |m| m.len()) }).sum()
+}
+
+// the builds, the stale ones among them, and their size in bytes
+pub fn usage() -> (usize, usize, u64) {
+    let builds = builds();
+    let stale = builds.iter().filter(|(dir, _, _)| stale(dir)).count();
+    (builds.len(), stale, builds.iter().map(|(_, size, _)| size).sum())
+}
+
+// the stale builds, and those used longest ago, go until the rest are
+// `limit` bytes or less; `keep`, just built, stays whatever its size
+fn trim(limit: u64, keep: &Path) -> io::Result<()> {
+    let mut builds = builds();
+    builds.sort_by_key(|(_, _, used)| *used);
+    let mut total: u64 = builds.iter().map(|(_, size, _)| size).sum();
+    for (dir, size, used) in builds {
+        if total <= limit && used != SystemTime::UNIX_EPOCH {
+            break;
+        }
+        if dir == keep {
+            continue;
+        }
+        // another quiz trimming at the same time may have got there first
+        match fs::remove_dir_all(&dir) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => total -= size,
+        }
+    }
+    Ok(())
+}
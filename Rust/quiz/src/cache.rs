@@ -0,0 +1,105 @@
+// QUIZ: CACHED VERDICTS --------------------------------------------------
+
+/*
+ * "Will it compile?" questions store no answer: rustc is the answer
+ * key. Compiling takes a moment per snippet, so verdicts are cached in
+ * packs/verdicts.json, keyed by a hash of the snippet and the edition.
+ * Change one character of the code and it gets a new key, so a stale
+ * verdict can't be served for edited code.
+ *
+ * The file is meant to be committed: practice works from it without
+ * compiling anything, and `quiz check` recompiles everything and says
+ * when the compiler's verdict changed since it was cached (a new rustc
+ * accepting code it used to reject, say), since the question's
+ * explanation probably needs updating then.
+ *
+ * quiz check runs its questions at once (one per core), and they share
+ * the one Verdicts, so the entries are behind a lock; it's held for a
+ * lookup or an insert, never for a compile.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cached {
+    pub compiles: bool,
+    pub summary: String,               // "compiles", or rustc's first error
+    pub rustc: String,                 // the rustc --version that decided
+}
+
+// Default: none, for a command that doesn't use them (main.rs's BANK)
+#[derive(Default)]
+pub struct Verdicts {
+    path: PathBuf,
+    entries: Mutex<BTreeMap<String, Cached>>, // BTreeMap: stable order, small diffs
+    dirty: AtomicBool,
+}
+
+// FNV-1a, 64 bit. std's DefaultHasher may change between Rust releases,
+// and these keys are stored in a file, so they need a hash that won't.
+pub fn key(edition: &str, code: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in edition.bytes().chain([0]).chain(code.trim().bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{hash:016x}")
+}
+
+impl Verdicts {
+    pub fn load(path: &Path) -> Result<Verdicts, String> {
+        let entries = match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(format!("{}: {e}", path.display())),
+        };
+        Ok(Verdicts { path: path.to_path_buf(), entries: Mutex::new(entries), dirty: AtomicBool::new(false) })
+    }
+
+    // a check that panicked while holding it left the map as it was
+    fn entries(&self) -> MutexGuard<'_, BTreeMap<String, Cached>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn get(&self, key: &str) -> Option<Cached> {
+        self.entries().get(key).cloned()
+    }
+
+    pub fn insert(&self, key: String, cached: Cached) {
+        let mut entries = self.entries();
+        if entries.get(&key) != Some(&cached) {
+            entries.insert(key, cached);
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+    }
+
+    // drops entries no question uses any more
+    pub fn retain(&mut self, live: &[String]) {
+        let entries = self.entries.get_mut().unwrap_or_else(PoisonError::into_inner);
+        let before = entries.len();
+        entries.retain(|key, _| live.contains(key));
+        if entries.len() != before {
+            *self.dirty.get_mut() = true;
+        }
+    }
+
+    // the entries stay locked while they're written, so two saves at
+    // once (the classroom server's submissions) take turns
+    pub fn save(&self) -> io::Result<()> {
+        let entries = self.entries();
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+        let written = serde_json::to_string_pretty(&*entries).map_err(io::Error::from).and_then(|json| fs::write(&self.path, json + "\n"));
+        if written.is_err() {
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+        written
+    }
+}
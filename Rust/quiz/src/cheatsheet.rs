@@ -0,0 +1,82 @@
+// QUIZ: A TOPIC ON A SCREEN OR TWO ---------------------------------------
+
+/*
+ *     quiz cheatsheet ownership      the code, and a line on each point
+ *
+ * For going over a topic in a few minutes, before an interview say: the
+ * notes with the prose taken out. What stays, in the notes' order:
+ *
+ *     the headings              as they are
+ *     the code                  with its comments, the blank lines in
+ *                               a function taken out
+ *     a line of the text        the first sentence of each block comment
+ *                               or // paragraph, when it fits on a line
+ *                               (WIDTH); a title in capitals stays too
+ *
+ * and what doesn't: the rest of the text, and the tables and drawings
+ * (a paragraph indented four spaces, export.rs). The blocks are the
+ * ones the book export has, so what's text there is text here.
+ */
+
+use crate::export::{self, Block};
+
+const WIDTH: usize = 100;
+
+// the first sentence of a paragraph, if it's short enough to keep
+fn sentence(lines: &[&str]) -> Option<String> {
+    let text = lines.iter().map(|l| l.trim()).collect::<Vec<_>>().join(" ");
+    let end = text.find(". ").map_or(text.len(), |i| i + 1);
+    let first = text[..end].trim();
+    (!first.is_empty() && first.chars().count() <= WIDTH).then(|| first.to_string())
+}
+
+// a title written in the text: "ON STACK AND HEAP"
+fn title(lines: &[&str]) -> bool {
+    let [line] = lines else { return false };
+    line.chars().any(char::is_alphabetic) && !line.chars().any(char::is_lowercase)
+}
+
+// the lines a block of text leaves: its title, and its first sentence
+fn takeaway(text: &[String]) -> Vec<String> {
+    let paragraphs = export::paragraphs(text);
+    let mut prose = paragraphs.iter().filter(|(indented, _)| !indented).map(|(_, lines)| lines.as_slice());
+    let mut out = Vec::new();
+    let mut first = prose.next();
+    if let Some(lines) = first.filter(|lines| title(lines)) {
+        out.push(format!("// {}", lines[0].trim()));
+        first = prose.next();
+    }
+    out.extend(first.and_then(sentence).map(|s| format!("// {s}")));
+    out
+}
+
+pub fn cheatsheet(text: &str) -> String {
+    let mut out = String::new();
+    // a point's line goes right above its code, and a blank line in a
+    // function (between braces) goes
+    let (mut after_point, mut depth) = (false, 0i32);
+    for block in export::blocks(text) {
+        let code = matches!(block, Block::Code(_));
+        let (lines, point) = match block {
+            Block::Heading(title, true) => (vec![format!("// {title} {}", "-".repeat(70usize.saturating_sub(title.len())))], false),
+            Block::Heading(title, false) => (vec![format!("// {title} . . .")], false),
+            Block::Text(text) => (takeaway(&text), true),
+            Block::Code(code) => (code, false),
+        };
+        if lines.is_empty() {
+            continue;
+        }
+        if !out.is_empty() && !(code && (after_point || depth > 0)) {
+            out += "\n";
+        }
+        after_point = point;
+        for line in lines {
+            if code {
+                depth += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+            }
+            out += &line;
+            out += "\n";
+        }
+    }
+    out
+}
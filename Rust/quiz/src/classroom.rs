@@ -0,0 +1,591 @@
+// QUIZ: CLASSROOM SERVER -------------------------------------------------
+
+/*
+ * A small course platform on top of the question bank. The instructor
+ * runs the server where the packs are:
+ *
+ *     quiz serve [--port 3000] [--bind 0.0.0.0] [--data classroom.json]
+ *
+ * It listens on localhost unless --bind says otherwise; students on
+ * their own machines need --bind 0.0.0.0, or the address of the
+ * classroom's network.
+ *
+ * It prints an instructor token ($QUIZ_TOKEN, if set) that is needed
+ * to publish assignments, a set of topics or a list of questions:
+ *
+ *     QUIZ_TOKEN=.. quiz publish week3 --title "Week 3" --topics ownership,traits
+ *
+ * Students work through an assignment on their own machine, and their
+ * answers are sent in at the end:
+ *
+ *     quiz submit week3 --name ann
+ *
+ * (--server URL, or $QUIZ_SERVER, when the server isn't on
+ * localhost:3000.) The server grades with the same rules as practice,
+ * rustc deciding wherever the answer is code. Fix questions, self-graded
+ * in practice, are graded for real here (fixes.rs): the submitted code
+ * must compile, still define the items the broken version did, and fit
+ * the solution's signatures, so neither deleting the function nor
+ * stubbing it out counts as fixing it.
+ *
+ *     GET  /assignments                   what's published
+ *     POST /assignments                   publish (instructor)
+ *     GET  /assignments/:id               the questions, without answers
+ *     POST /assignments/:id/submissions   answers in, marks out
+ *     GET  /dashboard?token=..            (instructor) scores per
+ *                                         student, hardest questions
+ *
+ * Students are known by the name they give; it's meant for a classroom,
+ * not for the open internet. Assignments and submissions are kept in
+ * the --data file, written after every change.
+ *
+ * Cargo.toml, on top of the quiz's own dependencies:
+ * axum = "0.7"
+ * tokio = { version = "1", features = ["full"] }
+ * ureq = { version = "3", features = ["json"] }
+ */
+
+use crate::bank::{Kind, Pack, Question};
+use crate::cache::Verdicts;
+use crate::profiles::Profile;
+use crate::progress::{self, now};
+use crate::fixes::{self, Fix};
+use crate::Graded;
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{Html, IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, BufRead};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+pub const DEFAULT_SERVER: &str = "http://localhost:3000";
+
+// WHAT GOES OVER THE WIRE ------------------------------------------------
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Publish {
+    pub id: String,
+    pub title: String,
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub questions: Vec<String>,        // "pack/question"; empty: all on the topics
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Listed {
+    pub id: String,
+    pub title: String,
+    pub questions: usize,
+}
+
+// a question as a student sees it: no answer, no explanation
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Task {
+    pub question: String,
+    pub kind: String,
+    pub prompt: String,
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Sheet {
+    pub id: String,
+    pub title: String,
+    pub tasks: Vec<Task>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Answers {
+    pub student: String,
+    pub answers: BTreeMap<String, String>, // question -> answer
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mark {
+    pub question: String,
+    pub answer: String,
+    pub correct: bool,
+    pub key: String,
+    pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Marked {
+    pub score: usize,
+    pub marks: Vec<Mark>,
+}
+
+// THE SERVER'S RECORDS ---------------------------------------------------
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Assignment {
+    id: String,
+    title: String,
+    topics: Vec<String>,
+    questions: Vec<String>,
+    published: u64,                    // unix time
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Submission {
+    student: String,
+    assignment: String,
+    at: u64,
+    score: usize,
+    marks: Vec<Mark>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Records {
+    assignments: Vec<Assignment>,
+    submissions: Vec<Submission>,
+}
+
+struct Classroom {
+    records: Records,
+    file: PathBuf,
+}
+
+/*
+ * Grading runs rustc, which blocks, so it happens in spawn_blocking,
+ * and without the lock: a submission takes what it needs from the
+ * records, lets go, grades, and locks again to add itself. The packs
+ * don't change while the server runs, and the verdict cache locks
+ * itself (cache.rs), so submissions are graded side by side, and a
+ * slow one holds up nobody; rustc gets harness.rs's time limit.
+ */
+#[derive(Clone)]
+struct AppState {
+    packs: Arc<Vec<Pack>>,
+    verdicts: Arc<Verdicts>,
+    classroom: Arc<Mutex<Classroom>>,
+    token: Arc<str>,
+}
+
+impl AppState {
+    // a handler that panicked while holding it left whole records: each
+    // change is made and saved in one go
+    fn classroom(&self) -> MutexGuard<'_, Classroom> {
+        self.classroom.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// 128 random bits from std: RandomState is seeded from the OS
+fn new_token() -> String {
+    let half = || std::collections::hash_map::RandomState::new().build_hasher().finish();
+    format!("{:016x}{:016x}", half(), half())
+}
+
+pub fn find<'a>(packs: &'a [Pack], reference: &str) -> Option<(&'a Pack, &'a Question)> {
+    let (pack, id) = reference.split_once('/')?;
+    let pack = packs.iter().find(|p| p.id == pack)?;
+    Some((pack, pack.questions.iter().find(|q| q.id == id)?))
+}
+
+fn load(file: &PathBuf) -> Result<Records, String> {
+    match std::fs::read_to_string(file) {
+        Ok(text) => serde_json::from_str(&text).map_err(|e| format!("{}: {e}", file.display())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Records::default()),
+        Err(e) => Err(format!("{}: {e}", file.display())),
+    }
+}
+
+// through a temporary file, so a crash never leaves half the records
+fn save(file: &PathBuf, records: &Records) -> io::Result<()> {
+    let tmp = file.with_extension("tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(records)?)?;
+    std::fs::rename(tmp, file)
+}
+
+// GRADING ----------------------------------------------------------------
+
+// `question` without its answer, as "pack/question"
+pub fn task(reference: &str, question: &Question) -> Task {
+    Task {
+        question: reference.to_string(),
+        kind: question.kind.name().to_string(),
+        prompt: question.prompt.trim().to_string(),
+        code: question.kind.code().to_string(),
+        choices: match &question.kind {
+            Kind::Choice { choices, .. } => choices.iter().map(|c| c.trim().to_string()).collect(),
+            _ => Vec::new(),
+        },
+    }
+}
+
+// A fix is graded by fixes.rs; one that keeps rustc busy past its time
+// limit doesn't compile
+fn grade_fix(broken: &str, solved: &str, answer: &str, edition: &str) -> io::Result<Graded> {
+    let key = solved.trim().to_string();
+    if answer.trim().is_empty() {
+        return Ok(Graded { correct: false, key });
+    }
+    match fixes::grade(broken, solved, answer, edition) {
+        Ok(fix) => Ok(Graded { correct: matches!(fix, Fix::Passes), key }),
+        Err(e) if e.kind() == io::ErrorKind::TimedOut => Ok(Graded { correct: false, key }),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn mark(pack: &Pack, question: &Question, answer: &str, verdicts: &Verdicts) -> io::Result<Mark> {
+    let graded = match &question.kind {
+        Kind::Fix { broken, solved, .. } => grade_fix(broken, solved, answer, pack.edition_of(question))?,
+        _ => crate::grade(pack, question, answer, verdicts)?,
+    };
+    Ok(Mark {
+        question: format!("{}/{}", pack.id, question.id),
+        answer: answer.to_string(),
+        // an empty answer is a skipped question, never a "no"
+        correct: !answer.trim().is_empty() && graded.correct,
+        key: graded.key,
+        explanation: question.explanation.trim().to_string(),
+    })
+}
+
+// ERRORS -----------------------------------------------------------------
+
+// also the JSON API's (api.rs)
+pub enum ApiError {
+    Unauthorized,
+    NotFound(String),
+    Invalid(String),
+    Conflict(String),
+    Internal(String),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "needs the instructor token".to_string()),
+            ApiError::NotFound(what) => (StatusCode::NOT_FOUND, format!("no such {what}")),
+            ApiError::Invalid(message) => (StatusCode::UNPROCESSABLE_ENTITY, message),
+            ApiError::Conflict(message) => (StatusCode::CONFLICT, message),
+            ApiError::Internal(message) => (StatusCode::INTERNAL_SERVER_ERROR, message),
+        };
+        (status, Json(serde_json::json!({ "error": message }))).into_response()
+    }
+}
+
+// HANDLERS ---------------------------------------------------------------
+
+fn instructor(app: &AppState, token: Option<&str>) -> Result<(), ApiError> {
+    if token == Some(&*app.token) { Ok(()) } else { Err(ApiError::Unauthorized) }
+}
+
+async fn list(State(app): State<AppState>) -> Json<Vec<Listed>> {
+    let classroom = app.classroom();
+    let listed = classroom.records.assignments.iter();
+    Json(listed.map(|a| Listed { id: a.id.clone(), title: a.title.clone(), questions: a.questions.len() }).collect())
+}
+
+async fn publish(
+    State(app): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<Publish>,
+) -> Result<(StatusCode, Json<Listed>), ApiError> {
+    let bearer = headers.get("authorization").and_then(|v| v.to_str().ok()).and_then(|v| v.strip_prefix("Bearer "));
+    instructor(&app, bearer)?;
+
+    let packs = &app.packs;
+    let mut classroom = app.classroom();
+    if request.id.is_empty() || !request.id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(ApiError::Invalid(format!("`{}`: use letters, digits, - and _ in ids", request.id)));
+    }
+    if classroom.records.assignments.iter().any(|a| a.id == request.id) {
+        return Err(ApiError::Conflict(format!("`{}` is already published", request.id)));
+    }
+    if let Some(topic) = request.topics.iter().find(|t| !packs.iter().any(|p| &p.topic == *t)) {
+        return Err(ApiError::Invalid(format!("no packs on `{topic}`")));
+    }
+    if let Some(missing) = request.questions.iter().find(|q| find(packs, q).is_none()) {
+        return Err(ApiError::Invalid(format!("no question `{missing}` (write pack/question)")));
+    }
+    let questions = if request.questions.is_empty() {
+        let on_topics = packs.iter().filter(|p| request.topics.contains(&p.topic));
+        on_topics.flat_map(|p| p.questions.iter().map(move |q| format!("{}/{}", p.id, q.id))).collect()
+    } else {
+        request.questions
+    };
+    if questions.is_empty() {
+        return Err(ApiError::Invalid("no questions: give topics or questions".to_string()));
+    }
+
+    // a list of questions brings its own topics
+    let mut topics: Vec<String> = request.topics;
+    for (pack, _) in questions.iter().filter_map(|q| find(packs, q)) {
+        if !topics.contains(&pack.topic) {
+            topics.push(pack.topic.clone());
+        }
+    }
+
+    let assignment = Assignment { id: request.id, title: request.title, topics, questions, published: now() };
+    let listed = Listed { id: assignment.id.clone(), title: assignment.title.clone(), questions: assignment.questions.len() };
+    classroom.records.assignments.push(assignment);
+    save(&classroom.file, &classroom.records).map_err(|e| ApiError::Internal(format!("could not save: {e}")))?;
+    Ok((StatusCode::CREATED, Json(listed)))
+}
+
+async fn sheet(State(app): State<AppState>, Path(id): Path<String>) -> Result<Json<Sheet>, ApiError> {
+    let classroom = app.classroom();
+    let assignment = classroom.records.assignments.iter().find(|a| a.id == id);
+    let assignment = assignment.ok_or_else(|| ApiError::NotFound(format!("assignment `{id}`")))?;
+    let tasks = assignment
+        .questions
+        .iter()
+        .filter_map(|reference| find(&app.packs, reference).map(|(_, q)| task(reference, q)))
+        .collect();
+    Ok(Json(Sheet { id: assignment.id.clone(), title: assignment.title.clone(), tasks }))
+}
+
+async fn submit(
+    State(app): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<Answers>,
+) -> Result<Json<Marked>, ApiError> {
+    if request.student.trim().is_empty() {
+        return Err(ApiError::Invalid("a submission needs the student's name".to_string()));
+    }
+    let graded = tokio::task::spawn_blocking(move || {
+        let questions = {
+            let classroom = app.classroom();
+            let assignment = classroom.records.assignments.iter().find(|a| a.id == id);
+            assignment.ok_or_else(|| ApiError::NotFound(format!("assignment `{id}`")))?.questions.clone()
+        };
+
+        let mut marks = Vec::new();
+        for reference in &questions {
+            let Some((pack, question)) = find(&app.packs, reference) else { continue };
+            let answer = request.answers.get(reference).map_or("", |a| a.as_str());
+            let mark = mark(pack, question, answer, &app.verdicts);
+            marks.push(mark.map_err(|e| ApiError::Internal(format!("cannot run rustc: {e}")))?);
+        }
+        let score = marks.iter().filter(|m| m.correct).count();
+        let student = request.student.trim().to_string();
+        {
+            let mut classroom = app.classroom();
+            let Classroom { records, file } = &mut *classroom;
+            records.submissions.push(Submission { student, assignment: id, at: now(), score, marks: marks.clone() });
+            save(file, records).map_err(|e| ApiError::Internal(format!("could not save: {e}")))?;
+        }
+        app.verdicts.save().map_err(|e| ApiError::Internal(format!("could not save verdicts: {e}")))?;
+        Ok(Marked { score, marks })
+    });
+    graded.await.map_err(|e| ApiError::Internal(e.to_string()))?.map(Json)
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+async fn dashboard(
+    State(app): State<AppState>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<Html<String>, ApiError> {
+    instructor(&app, query.get("token").map(String::as_str))?;
+    let classroom = app.classroom();
+    let records = &classroom.records;
+
+    let mut html = String::from(
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>Classroom</title>\n<style>\n\
+         body { font-family: sans-serif; margin: 2em; }\n\
+         table { border-collapse: collapse; margin-bottom: 1.5em; }\n\
+         th, td { border: 1px solid #ccc; padding: .3em .7em; text-align: left; }\n\
+         .bar { display: inline-block; height: .8em; background: #5a9; }\n\
+         </style></head><body>\n<h1>Classroom</h1>\n",
+    );
+    if records.assignments.is_empty() {
+        html += "<p>Nothing published yet.</p>\n";
+    }
+    for assignment in &records.assignments {
+        let total = assignment.questions.len();
+        let submissions: Vec<&Submission> = records.submissions.iter().filter(|s| s.assignment == assignment.id).collect();
+        html += &format!(
+            "<h2>{} <small>({}, {total} questions on {})</small></h2>\n",
+            escape(&assignment.title),
+            escape(&assignment.id),
+            escape(&assignment.topics.join(", "))
+        );
+
+        // each student's best attempt
+        let mut students: BTreeMap<&str, (usize, usize, u64)> = BTreeMap::new(); // best, attempts, last
+        for s in &submissions {
+            let entry = students.entry(&s.student).or_default();
+            *entry = (entry.0.max(s.score), entry.1 + 1, entry.2.max(s.at));
+        }
+        html += "<table>\n<tr><th>Student</th><th>Best</th><th></th><th>Attempts</th><th>Last</th></tr>\n";
+        for (student, (best, attempts, last)) in &students {
+            let width = best * 100 / total.max(1);
+            html += &format!(
+                "<tr><td>{}</td><td>{best} / {total}</td><td><span class=\"bar\" style=\"width: {width}px\"></span></td>\
+                 <td>{attempts}</td><td>{}</td></tr>\n",
+                escape(student),
+                crate::exam::utc(*last)
+            );
+        }
+        html += "</table>\n";
+
+        // the questions most often missed first: the ones to go over
+        let mut questions: Vec<(&String, usize, usize)> = assignment
+            .questions
+            .iter()
+            .map(|q| {
+                let marks = submissions.iter().flat_map(|s| &s.marks).filter(|m| &m.question == q);
+                let (right, all) = marks.fold((0, 0), |(right, all), m| (right + m.correct as usize, all + 1));
+                (q, right, all)
+            })
+            .collect();
+        questions.sort_by_key(|&(_, right, all)| right * 100 / all.max(1));
+        if !submissions.is_empty() {
+            html += "<table>\n<tr><th>Question</th><th>Answered right</th></tr>\n";
+            for (question, right, all) in questions {
+                html += &format!("<tr><td>{}</td><td>{right} of {all}</td></tr>\n", escape(question));
+            }
+            html += "</table>\n";
+        }
+    }
+    html += "</body></html>\n";
+    Ok(Html(html))
+}
+
+fn router(app: AppState) -> Router {
+    Router::new()
+        .route("/assignments", get(list).post(publish))
+        .route("/assignments/:id", get(sheet))
+        .route("/assignments/:id/submissions", post(submit))
+        .route("/dashboard", get(dashboard))
+        .with_state(app)
+}
+
+pub fn serve(packs: Vec<Pack>, verdicts: Verdicts, bind: &str, port: u16, file: PathBuf) -> Result<(), String> {
+    let records = load(&file)?;
+    let token = std::env::var("QUIZ_TOKEN").unwrap_or_else(|_| new_token());
+    println!("classroom on {bind}:{port}, records in {}", file.display());
+    println!("instructor token: {token}");
+    println!("dashboard: http://localhost:{port}/dashboard?token={token}");
+
+    let app = AppState {
+        packs: Arc::new(packs),
+        verdicts: Arc::new(verdicts),
+        classroom: Arc::new(Mutex::new(Classroom { records, file })),
+        token: token.into(),
+    };
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+    let result = runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind((bind, port)).await?;
+        axum::serve(listener, router(app)).await
+    });
+    result.map_err(|e| e.to_string())
+}
+
+// THE CLIENT SIDE --------------------------------------------------------
+
+// --server, else $QUIZ_SERVER, else localhost
+pub fn server_url(flag: Option<&str>) -> String {
+    let url = flag.map(str::to_string).or_else(|| std::env::var("QUIZ_SERVER").ok());
+    url.unwrap_or_else(|| DEFAULT_SERVER.to_string()).trim_end_matches('/').to_string()
+}
+
+// the body of a 2xx response, or the server's {"error": ..}
+fn reply<T: DeserializeOwned>(response: Result<ureq::http::Response<ureq::Body>, ureq::Error>) -> Result<T, String> {
+    let mut response = response.map_err(|e| e.to_string())?;
+    if response.status().is_success() {
+        return response.body_mut().read_json().map_err(|e| e.to_string());
+    }
+    let body: serde_json::Value = response.body_mut().read_json().unwrap_or_default();
+    Err(body["error"].as_str().map_or_else(|| response.status().to_string(), str::to_string))
+}
+
+// error statuses come back as responses, so their message can be read
+fn agent() -> ureq::Agent {
+    ureq::Agent::config_builder().http_status_as_error(false).build().into()
+}
+
+pub fn publish_assignment(server: &str, token: &str, request: &Publish) -> Result<Listed, String> {
+    let url = format!("{server}/assignments");
+    reply(agent().post(&url).header("authorization", &format!("Bearer {token}")).send_json(request))
+}
+
+// a fix is answered with code: lines up to one holding just "."
+fn ask_code(input: &mut impl BufRead) -> Option<String> {
+    println!("\n(the fixed code; end with a line holding just .)");
+    let mut code = String::new();
+    for line in input.lines() {
+        let line = line.ok()?;
+        if line.trim() == "." {
+            return Some(code);
+        }
+        code += &line;
+        code += "\n";
+    }
+    (!code.is_empty()).then_some(code)
+}
+
+pub fn submit_answers(server: &str, assignment: &str, student: &str, profile: &Profile) -> Result<bool, String> {
+    let sheet: Sheet = reply(agent().get(&format!("{server}/assignments/{assignment}")).call())?;
+    println!("{} ({} questions), answering as {student}\n", sheet.title, sheet.tasks.len());
+
+    let mut input = io::stdin().lock();
+    let mut answers = BTreeMap::new();
+    for (i, task) in sheet.tasks.iter().enumerate() {
+        println!("[{}/{}] {}\n", i + 1, sheet.tasks.len(), task.prompt);
+        if !task.code.trim().is_empty() {
+            print!("{}", crate::indent(&task.code));
+        }
+        for (n, choice) in (1..).zip(&task.choices) {
+            println!("  {n}) {choice}");
+        }
+        let answer = match task.kind.as_str() {
+            "fix" => ask_code(&mut input),
+            "still-usable" | "compiles" => crate::ask(&mut input, "\n[y/n] "),
+            "fill-in" => crate::ask(&mut input, &format!("\n{} = ", crate::bank::BLANK)),
+            _ => crate::ask(&mut input, "\n> "),
+        };
+        let Some(answer) = answer else { return Err("input ended; nothing was sent".to_string()) };
+        answers.insert(task.question.clone(), answer);
+        println!();
+    }
+
+    let url = format!("{server}/assignments/{assignment}/submissions");
+    let marked: Marked = reply(agent().post(&url).send_json(Answers { student: student.to_string(), answers }))?;
+    for mark in &marked.marks {
+        if mark.correct {
+            println!("right  {}", mark.question);
+        } else {
+            println!("wrong  {}: {}", mark.question, mark.key.lines().next().unwrap_or_default());
+        }
+    }
+    let attempts: Vec<progress::Attempt> = marked
+        .marks
+        .iter()
+        .filter(|m| !m.answer.trim().is_empty())
+        .map(|m| progress::Attempt { question: m.question.clone(), correct: m.correct, at: now() })
+        .collect();
+    progress::record(profile, &attempts).map_err(|e| format!("cannot record progress: {e}"))?;
+    println!("\n{} of {} right", marked.score, marked.marks.len());
+    Ok(marked.score == marked.marks.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_fix_in_a_comment_is_wrong() {
+        let broken = "fn longest(x: &str, y: &str) -> &str { x }";
+        // rejected before rustc is asked
+        let graded = grade_fix(broken, "", "// fn longest", "2021").unwrap();
+        assert!(!graded.correct);
+        let graded = grade_fix(broken, "", "const _: &str = \"fn longest\";", "2021").unwrap();
+        assert!(!graded.correct);
+    }
+}
@@ -0,0 +1,159 @@
+// QUIZ: WHERE THE NOTES SAY IT -------------------------------------------
+
+/*
+ *     quiz where-is "deref coercion"    the topics and lines that say it
+ *     quiz where-is                     the whole concordance
+ *
+ *     trait object
+ *       generics.rs:39             TRAITS
+ *       traits.rs:66-68            Trait objects: Dynamic polymorphism
+ *
+ * A concordance: every Rust keyword, and every concept in CONCEPTS
+ * below, with the topics and lines where it appears, alphabetically.
+ * The book export has it as its last page (export.rs), each place a
+ * link to its section.
+ *
+ * A concept is found in the text and the comments alike, in any case,
+ * as whole words, with a plural taking the singular's place ("trait
+ * objects", "closures") and a hyphen or a line break a space's. A
+ * keyword is found in the code only, as written: `move` is the keyword
+ * and not every "move" in the prose. Keywords that are on every other
+ * line (`let`, `fn`, `if`..) are left out of the concordance; where-is
+ * still finds them.
+ *
+ * Lines no more than three apart are one place, so a paragraph about
+ * something is one range and not a list of its lines. A phrase that's
+ * nowhere suggests the nearest concept (fuzzy.rs).
+ */
+
+use crate::providers::Topic;
+use std::ops::RangeInclusive;
+
+const KEYWORDS: &[&str] = &[
+    "async", "await", "break", "const", "continue", "crate", "dyn", "enum", "extern", "impl", "loop", "match", "mod", "move", "ref",
+    "Self", "static", "super", "trait", "type", "unsafe", "where", "while",
+];
+
+// keywords too, but on every other line
+const COMMON: &[&str] = &["as", "else", "fn", "for", "if", "in", "let", "mut", "pub", "return", "self", "use"];
+
+const CONCEPTS: &[&str] = &[
+    "associated type", "borrow checker", "borrowing", "box", "channel", "clone", "closure", "coherence", "copy", "dangling reference",
+    "data race", "deadlock", "default implementation", "deref coercion", "drop", "dynamic dispatch", "edition", "elision", "error handling",
+    "generic", "heap", "interior mutability", "iterator", "lifetime", "macro", "monomorphization", "move semantics", "mutex",
+    "object safety", "orphan rule", "ownership", "panic", "pattern matching", "reference counting", "shadowing", "slice",
+    "smart pointer", "stack", "static dispatch", "string slice", "thread", "trait bound", "trait object", "undefined behavior",
+    "unwrap", "vtable", "zero-cost abstraction",
+];
+
+// lines no further apart than this are one place
+const GAP: usize = 3;
+
+pub struct Place {
+    pub topic: String,
+    pub lines: RangeInclusive<usize>,
+    pub heading: Option<String>,       // the section it's in
+}
+
+pub struct Entry {
+    pub term: String,
+    pub places: Vec<Place>,
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).map(str::to_lowercase).collect()
+}
+
+// `word` as written, or its plural
+fn same_word(word: &str, wanted: &str) -> bool {
+    let plural = |suffix: &str| word.strip_suffix(suffix) == Some(wanted);
+    word == wanted || plural("s") || plural("es") || wanted.strip_suffix('y').is_some_and(|stem| word.strip_suffix("ies") == Some(stem))
+}
+
+// the lines (from 1) that have the phrase in them, its words over a
+// line break counted on the line it starts
+fn phrase_lines(text: &str, phrase: &str) -> Vec<usize> {
+    let wanted = words(phrase);
+    let Some(first) = wanted.first() else { return Vec::new() };
+    let mut all: Vec<(usize, String)> = Vec::new();
+    for (n, line) in (1..).zip(text.lines()) {
+        all.extend(words(line).into_iter().map(|w| (n, w)));
+    }
+    let mut lines = Vec::new();
+    for (i, (n, word)) in all.iter().enumerate() {
+        let rest = all[i..].iter().zip(&wanted).filter(|((_, w), wanted)| same_word(w, wanted)).count();
+        if same_word(word, first) && rest == wanted.len() && lines.last() != Some(n) {
+            lines.push(*n);
+        }
+    }
+    lines
+}
+
+// the lines with `keyword` in their code, outside comments and strings
+fn keyword_lines(text: &str, keyword: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+    let mut comment = false;
+    for (n, line) in (1..).zip(text.lines()) {
+        let trimmed = line.trim_start();
+        if comment || trimmed.starts_with("/*") {
+            comment = !trimmed.contains("*/");
+            continue;
+        }
+        let code = line.split("//").next().unwrap_or_default();
+        let code: String = code.split('"').step_by(2).collect::<Vec<_>>().join(" ");
+        if code.split(|c: char| !c.is_alphanumeric() && c != '_').any(|w| w == keyword) {
+            lines.push(n);
+        }
+    }
+    lines
+}
+
+// lines close together as ranges, each with the heading it's under
+fn places(topic: &Topic, lines: Vec<usize>) -> Vec<Place> {
+    let parts = &topic.parsed().parts;
+    let heading = |line: usize| parts.iter().rev().find(|p| p.line <= line).and_then(|p| p.heading.clone());
+    let mut ranges: Vec<RangeInclusive<usize>> = Vec::new();
+    for n in lines {
+        match ranges.last_mut() {
+            Some(range) if n <= range.end() + GAP => *range = *range.start()..=n,
+            _ => ranges.push(n..=n),
+        }
+    }
+    ranges.into_iter().map(|lines| Place { topic: topic.id.clone(), heading: heading(*lines.start()), lines }).collect()
+}
+
+// where `term` is: a keyword in code, anything else in the text
+pub fn find(term: &str, topics: &[Topic]) -> Vec<Place> {
+    let keyword = KEYWORDS.contains(&term) || COMMON.contains(&term);
+    let mut found = Vec::new();
+    for topic in topics {
+        let lines = if keyword { keyword_lines(&topic.text, term) } else { phrase_lines(&topic.text, term) };
+        found.extend(places(topic, lines));
+    }
+    found
+}
+
+pub fn concepts() -> &'static [&'static str] {
+    CONCEPTS
+}
+
+// every keyword and concept that the topics have, alphabetically
+pub fn concordance(topics: &[Topic]) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = KEYWORDS
+        .iter()
+        .chain(CONCEPTS)
+        .map(|term| Entry { term: term.to_string(), places: find(term, topics) })
+        .filter(|e| !e.places.is_empty())
+        .collect();
+    entries.sort_by_key(|e| e.term.to_lowercase());
+    entries
+}
+
+// "131-133", or "160" for one line
+pub fn span(lines: &RangeInclusive<usize>) -> String {
+    if lines.start() == lines.end() {
+        lines.start().to_string()
+    } else {
+        format!("{}-{}", lines.start(), lines.end())
+    }
+}
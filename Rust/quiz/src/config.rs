@@ -0,0 +1,262 @@
+// QUIZ: THE CONFIGURATION FILE -------------------------------------------
+
+/*
+ * Defaults for the command line, in ~/.config/langscape/config.toml
+ * ($XDG_CONFIG_HOME/langscape/config.toml, or the file in $QUIZ_CONFIG):
+ *
+ *     edition = "2024"            for the notes' snippets and quiz miri;
+ *                                 a pack's questions keep the pack's
+ *     editor = "hx"               for quiz verify ID without a FILE
+ *     pager = "less -R"           for quiz show, on a terminal ($PAGER
+ *                                 without it)
+ *     picker = "fzf --height 40%" for plain quiz (picker.rs)
+ *     packs = ["~/rust-packs"]    more pack directories, after packs/
+ *     remote = "https://.."       where quiz update gets packages (sync.rs)
+ *     theme = "light"             dark, light or high-contrast (theme.rs)
+ *     profile = "ann"             whose progress it is (profiles.rs)
+ *     plain = true                no colour, bars or box drawings: words,
+ *                                 for a screen reader
+ *     jobs = 4                    snippets compiled at once, by quiz
+ *                                 check, notes and snapshots
+ *     cache_mb = 1024             the most the kept builds may take
+ *                                 (builds.rs)
+ *
+ * Every key can be left out: the edition is then 2021 (what the notes
+ * are written for), the editor $VISUAL, $EDITOR or vi, the theme dark,
+ * the pager $PAGER, or none, the picker sk or fzf if either is on the
+ * PATH, or quiz's own, and the jobs one per core (or
+ * $RAYON_NUM_THREADS). less gets LESS=FRX unless LESS is set, as git
+ * gives it: output that fits on the screen isn't paged, colour comes
+ * through, and the text stays on the screen after quitting. Everything
+ * goes to the pager, so its / searches the whole file. A key that isn't
+ * one of these is an error, so a typo doesn't quietly do nothing.
+ *
+ * A flag wins over the file, before or after the command:
+ *
+ *     quiz notes --edition 2024
+ *     quiz verify lifetimes/two-inputs --editor nano
+ *     quiz show ownership.rs --pager "less -R"    (--no-pager: none)
+ *     quiz --picker sk
+ *     quiz practice lifetimes --theme high-contrast
+ *     quiz status --profile ann                   ($QUIZ_PROFILE between)
+ *     quiz show ownership.rs --plain
+ *     quiz show collections --section entry
+ *     quiz check --jobs 1
+ *
+ * Output that isn't to a terminal (a pipe, a file) is always plain.
+ *
+ * The $QUIZ_* variables (QUIZ_PACKS, QUIZ_NOTES, ..) say where things
+ * are, and aren't settings of this file.
+ */
+
+use crate::bank;
+use crate::profiles::{self, Profile};
+use crate::theme::{self, Theme};
+use serde::Deserialize;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    edition: Option<String>,
+    editor: Option<String>,
+    pager: Option<String>,
+    picker: Option<String>,
+    #[serde(default)]
+    packs: Vec<PathBuf>,
+    remote: Option<String>,
+    theme: Option<String>,
+    #[serde(default)]
+    plain: bool,
+    profile: Option<String>,
+    jobs: Option<usize>,
+    cache_mb: Option<u64>,
+}
+
+pub fn home() -> PathBuf {
+    PathBuf::from(std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")).unwrap_or_default())
+}
+
+// ~/.local/share/langscape, or under $XDG_DATA_HOME
+pub fn data_home() -> PathBuf {
+    let data = std::env::var_os("XDG_DATA_HOME").map_or_else(|| home().join(".local").join("share"), PathBuf::from);
+    data.join("langscape")
+}
+
+// ~/.cache/langscape, or under $XDG_CACHE_HOME; $QUIZ_CACHE in its place
+pub fn cache_home() -> PathBuf {
+    if let Some(dir) = std::env::var_os("QUIZ_CACHE") {
+        return PathBuf::from(dir);
+    }
+    let cache = std::env::var_os("XDG_CACHE_HOME").map_or_else(|| home().join(".cache"), PathBuf::from);
+    cache.join("langscape")
+}
+
+fn path() -> PathBuf {
+    if let Some(path) = std::env::var_os("QUIZ_CONFIG") {
+        return PathBuf::from(path);
+    }
+    let config = std::env::var_os("XDG_CONFIG_HOME").map_or_else(|| home().join(".config"), PathBuf::from);
+    config.join("langscape").join("config.toml")
+}
+
+// takes `--flag VALUE` out of args
+fn take(args: &mut Vec<String>, flag: &str) -> Result<Option<String>, String> {
+    let Some(i) = args.iter().position(|a| a == flag) else { return Ok(None) };
+    if i + 1 >= args.len() {
+        return Err(format!("{flag} needs a value"));
+    }
+    args.remove(i);
+    Ok(Some(args.remove(i)))
+}
+
+impl Config {
+    // the file (no file: the defaults), then the flags in `args`, which
+    // are taken out so the commands don't see them
+    pub fn load(args: &mut Vec<String>) -> Result<Config, String> {
+        let path = path();
+        let mut config: Config = match std::fs::read_to_string(&path) {
+            Ok(text) => toml::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(e) => return Err(format!("{}: {e}", path.display())),
+        };
+        if let Some(edition) = take(args, "--edition")? {
+            config.edition = Some(edition);
+        }
+        if let Some(editor) = take(args, "--editor")? {
+            config.editor = Some(editor);
+        }
+        if let Some(pager) = take(args, "--pager")? {
+            config.pager = Some(pager);
+        }
+        if let Some(picker) = take(args, "--picker")? {
+            config.picker = Some(picker);
+        }
+        if let Some(theme) = take(args, "--theme")? {
+            config.theme = Some(theme);
+        }
+        if let Some(jobs) = take(args, "--jobs")? {
+            config.jobs = Some(jobs.parse().map_err(|_| format!("--jobs takes a number, not `{jobs}`"))?);
+        }
+        if let Some(profile) = take(args, "--profile")?.or_else(|| std::env::var("QUIZ_PROFILE").ok().filter(|p| !p.is_empty())) {
+            config.profile = Some(profile);
+        }
+        if let Some(i) = args.iter().position(|a| a == "--plain") {
+            args.remove(i);
+            config.plain = true;
+        }
+        if let Some(i) = args.iter().position(|a| a == "--no-pager") {
+            args.remove(i);
+            config.pager = Some(String::new()); // and not $PAGER
+        }
+        if let Some(edition) = config.edition.as_deref().filter(|e| !bank::EDITIONS.contains(e)) {
+            return Err(format!("`{edition}` is not an edition ({})", bank::EDITIONS.join(", ")));
+        }
+        if let Some(name) = config.theme.as_deref().filter(|t| !theme::NAMES.contains(t)) {
+            return Err(format!("there's no theme `{name}` ({})", theme::NAMES.join(", ")));
+        }
+        if let Some(name) = config.profile.as_deref() {
+            profiles::check_name(name)?;
+        }
+        // "~/rust-packs" -> "$HOME/rust-packs"
+        for dir in &mut config.packs {
+            if let Ok(rest) = dir.strip_prefix("~") {
+                *dir = home().join(rest);
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn edition(&self) -> &str {
+        self.edition.as_deref().unwrap_or("2021")
+    }
+
+    pub fn editor(&self) -> String {
+        let from_env = || std::env::var("VISUAL").or_else(|_| std::env::var("EDITOR")).ok();
+        self.editor.clone().or_else(from_env).unwrap_or_else(|| "vi".to_string())
+    }
+
+    // 0: one per core
+    pub fn jobs(&self) -> usize {
+        self.jobs.unwrap_or(0)
+    }
+
+    pub fn cache_mb(&self) -> u64 {
+        self.cache_mb.unwrap_or(1024)
+    }
+
+    pub fn packs(&self) -> &[PathBuf] {
+        &self.packs
+    }
+
+    pub fn remote(&self) -> Option<&str> {
+        self.remote.as_deref()
+    }
+
+    pub fn profile(&self) -> Profile {
+        Profile::new(self.profile.clone())
+    }
+
+    // said in words, not drawn: asked for, or not to a terminal
+    pub fn plain(&self) -> bool {
+        self.plain || !io::stdout().is_terminal()
+    }
+
+    // in colour only when not plain, and without NO_COLOR
+    pub fn theme(&self) -> Theme {
+        let colour = !self.plain() && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty());
+        Theme::new(self.theme.as_deref().unwrap_or("dark"), colour)
+    }
+
+    // the fuzzy finder for plain quiz; None: quiz's own
+    pub fn picker(&self) -> Option<String> {
+        if let Some(picker) = &self.picker {
+            return Some(picker.clone()).filter(|p| !p.trim().is_empty());
+        }
+        let path = std::env::var_os("PATH").unwrap_or_default();
+        let found = |name: &str| std::env::split_paths(&path).any(|dir| dir.join(name).is_file());
+        ["sk", "fzf"].into_iter().find(|name| found(name)).map(str::to_string)
+    }
+
+    // opens `file` in the editor and waits; false if the editor failed
+    pub fn edit(&self, file: &Path) -> io::Result<bool> {
+        Ok(shell(&format!("{} \"{}\"", self.editor(), file.display())).status()?.success())
+    }
+
+    // `text` through the pager when there is one and stdout is a
+    // terminal; printed as it is otherwise
+    pub fn page(&self, text: &str) -> io::Result<()> {
+        let pager = self.pager.clone().or_else(|| std::env::var("PAGER").ok()).filter(|p| !p.trim().is_empty());
+        let Some(pager) = pager.filter(|_| io::stdout().is_terminal()) else {
+            print!("{text}");
+            return Ok(());
+        };
+        let mut command = shell(&pager);
+        if std::env::var_os("LESS").is_none() {
+            command.env("LESS", "FRX");
+        }
+        let mut child = command.stdin(Stdio::piped()).spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // the reader quitting early closes the pipe: not an error
+            stdin.write_all(text.as_bytes()).or_else(|e| if e.kind() == io::ErrorKind::BrokenPipe { Ok(()) } else { Err(e) })?;
+        }
+        child.wait()?;
+        Ok(())
+    }
+}
+
+// a command line from the config ("less -R", "code --wait") as the
+// shell would run it
+pub fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+}
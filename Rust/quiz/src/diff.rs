@@ -0,0 +1,53 @@
+// QUIZ: A LINE DIFF -------------------------------------------------------
+
+/*
+ * Two texts, line by line: what both have, what only the old one has and
+ * what only the new one has, in order. `quiz snapshots` shows a changed
+ * rustc message with it, and diagrams/migrate.rs (which has this file as
+ * a module of its own, `rustc migrate.rs` and all) what cargo fix
+ * changed, so it needs nothing from the rest of the quiz, and has to
+ * stay that way.
+ *
+ * The longest common subsequence of the lines is kept; of two ways to
+ * keep as many, the old line goes first.
+ */
+
+// ' ' kept, '-' only in old, '+' only in new, each with its line number
+// (the old one for '-')
+pub fn diff<'a>(old: &'a str, new: &'a str) -> Vec<(char, usize, &'a str)> {
+    let (a, b): (Vec<&str>, Vec<&str>) = (old.lines().collect(), new.lines().collect());
+    // longest common subsequence of the lines after i in a and j in b
+    let mut lcs = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+    let (mut i, mut j, mut out) = (0, 0, Vec::new());
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            out.push((' ', j + 1, a[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push(('-', i + 1, a[i]));
+            i += 1;
+        } else {
+            out.push(('+', j + 1, b[j]));
+            j += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kept_removed_and_added() {
+        let d = diff("a\nb\nc\n", "a\nB\nc\nd\n");
+        assert_eq!(d, [(' ', 1, "a"), ('-', 2, "b"), ('+', 2, "B"), (' ', 3, "c"), ('+', 4, "d")]);
+        assert_eq!(diff("", "x\n"), [('+', 1, "x")]);
+        assert_eq!(diff("x\n", ""), [('-', 1, "x")]);
+    }
+}
@@ -0,0 +1,56 @@
+// QUIZ: THE NOTES IN THE BINARY ------------------------------------------
+
+/*
+ * The builtin provider's topics, compiled in by build.rs: quiz show,
+ * search and the rest read no notes file, and look a topic up in a
+ * perfect-hash map rather than a directory. Packs are still files
+ * (packs/ has the verdicts cache, which is written to), and so are the
+ * notes for `quiz notes` and `quiz snapshots`, which check files being
+ * edited.
+ *
+ * $QUIZ_NOTES at run time reads the directory instead, as before, for
+ * trying an edit without a rebuild.
+ *
+ * A snippet isn't in the map: notes::snippets finds them in their
+ * topic's text, which is one file's worth of work once the topic is
+ * found.
+ */
+
+use crate::bank::{self, Pack};
+use crate::providers::{ContentProvider, Topic, title_of};
+use std::path::PathBuf;
+
+include!(concat!(env!("OUT_DIR"), "/topics.rs"));
+
+pub struct Embedded {
+    pub packs: PathBuf,
+}
+
+fn topic(id: &str, text: &str) -> Topic {
+    Topic::new(id.to_string(), title_of(id, text), text.to_string())
+}
+
+impl ContentProvider for Embedded {
+    fn name(&self) -> &str {
+        "builtin"
+    }
+
+    fn description(&self) -> &str {
+        "the packs in this repository, and the notes built in"
+    }
+
+    // sorted, as a directory's are
+    fn topics(&self) -> Result<Vec<Topic>, String> {
+        let mut topics: Vec<Topic> = TOPICS.entries().map(|(id, text)| topic(id, text)).collect();
+        topics.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(topics)
+    }
+
+    fn topic(&self, id: &str) -> Result<Option<Topic>, String> {
+        Ok(TOPICS.get(id).map(|text| topic(id, text)))
+    }
+
+    fn packs(&self) -> Result<Vec<Pack>, String> {
+        bank::load_dir(&self.packs)
+    }
+}
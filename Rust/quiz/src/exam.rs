@@ -0,0 +1,253 @@
+// QUIZ: EXAM MODE --------------------------------------------------------
+
+/*
+ *     quiz exam --topics ownership,traits --minutes 30 [--report FILE]
+ *
+ * Every question from the packs on those topics, against the clock and
+ * without hints: no explanations, and no right or wrong until the end.
+ * When time runs out, the question on screen and the ones after it are
+ * recorded as unanswered. Fix questions are left out, since they are
+ * self-graded and only work by showing the solution.
+ *
+ * The report is written twice, to FILE.json and FILE.md (FILE is
+ * exam-report unless given): the JSON for tools, the Markdown to send
+ * to a mentor or instructor. Both list every question with the answer
+ * given, the expected one and the time it took; the Markdown adds the
+ * code and explanation of each question that was missed. The JSON is
+ * what `quiz report --exam` reads back (report.rs).
+ */
+
+use crate::bank::{Kind, Pack, Question};
+use crate::cache::Verdicts;
+use crate::profiles::Profile;
+use crate::progress;
+use crate::theme::Theme;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Report {
+    pub topics: Vec<String>,
+    pub minutes: u64,
+    pub started: u64,                  // unix time
+    pub seconds: u64,                  // time taken
+    pub score: usize,
+    pub questions: Vec<Answered>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Answered {
+    pub pack: String,
+    pub id: String,
+    pub kind: String,
+    pub prompt: String,
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<String>,
+    pub answer: Option<String>,        // None: never reached (time, or end of input)
+    pub expected: String,
+    pub correct: bool,
+    pub seconds: u64,
+    pub explanation: String,
+}
+
+// stdin is read on its own thread, so that waiting for an answer can
+// time out
+fn lines() -> Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+// 754 seconds -> "12:34"
+pub fn clock(seconds: u64) -> String {
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+// unix time -> "2026-10-16 14:05 UTC", with the days-to-date
+// conversion from Howard Hinnant's date algorithms
+pub fn utc(unix: u64) -> String {
+    let z = (unix / 86400) as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    let seconds = unix % 86400;
+    format!("{year}-{month:02}-{day:02} {:02}:{:02} UTC", seconds / 3600, seconds % 3600 / 60)
+}
+
+fn session(questions: &[(&Pack, &Question)], minutes: u64, verdicts: &mut Verdicts, theme: &Theme) -> io::Result<Vec<Answered>> {
+    let input = lines();
+    let deadline = Instant::now() + Duration::from_secs(minutes * 60);
+    let mut open = true;               // time left, and input not closed
+    let mut answered = Vec::new();
+
+    for (i, (pack, question)) in questions.iter().enumerate() {
+        let asked = Instant::now();
+        let left = deadline.saturating_duration_since(asked);
+        let mut answer = None;
+        if open && !left.is_zero() {
+            let (n, left) = (questions.len(), clock(left.as_secs()));
+            println!("[{}/{n}, {left} left] {}\n", i + 1, question.prompt.trim());
+            crate::present(question, theme);
+            print!("{}", crate::answer_prompt(&question.kind));
+            io::stdout().flush().ok();
+            match input.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(line) => answer = Some(line.trim().to_string()),
+                Err(RecvTimeoutError::Timeout) => {
+                    println!("\n\nTime is up.");
+                    open = false;
+                }
+                Err(RecvTimeoutError::Disconnected) => open = false,
+            }
+            println!();
+        }
+
+        // graded even when unanswered, for the answer key; an empty line
+        // is a skipped question, not a "no"
+        let graded = crate::grade(pack, question, answer.as_deref().unwrap_or(""), verdicts)?;
+        let correct = answer.as_deref().is_some_and(|a| !a.is_empty()) && graded.correct;
+        answered.push(Answered {
+            pack: pack.id.clone(),
+            id: question.id.clone(),
+            kind: question.kind.name().to_string(),
+            prompt: question.prompt.trim().to_string(),
+            code: question.kind.code().trim().to_string(),
+            choices: match &question.kind {
+                Kind::Choice { choices, .. } => choices.iter().map(|c| c.trim().to_string()).collect(),
+                _ => Vec::new(),
+            },
+            answer,
+            expected: graded.key,
+            correct,
+            seconds: asked.elapsed().as_secs(),
+            explanation: question.explanation.trim().to_string(),
+        });
+    }
+    Ok(answered)
+}
+
+// a table cell: pipes escaped, on one line, as code
+fn cell(text: &str) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    format!("`{}`", text.replace('|', "\\|").split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+impl Report {
+    pub fn markdown(&self) -> String {
+        let total = self.questions.len();
+        let mut md = format!("# Exam: {}\n\n", self.topics.join(", "));
+        md += &format!(
+            "**{} of {total} right** ({}%) in {} of {} minutes, {}.\n\n",
+            self.score,
+            self.score * 100 / total.max(1),
+            clock(self.seconds),
+            self.minutes,
+            utc(self.started)
+        );
+
+        md += "| # | Question | Answer | Expected | Result | Time |\n";
+        md += "|---|----------|--------|----------|--------|------|\n";
+        for (n, q) in (1..).zip(&self.questions) {
+            let result = match (&q.answer, q.correct) {
+                (None, _) => "unanswered",
+                (Some(a), _) if a.is_empty() => "skipped",
+                (_, true) => "right",
+                (_, false) => "wrong",
+            };
+            let answer = cell(q.answer.as_deref().unwrap_or(""));
+            let time = clock(q.seconds);
+            md += &format!("| {n} | {}/{} | {answer} | {} | {result} | {time} |\n", q.pack, q.id, cell(&q.expected));
+        }
+
+        let missed: Vec<_> = (1..).zip(&self.questions).filter(|(_, q)| !q.correct).collect();
+        if !missed.is_empty() {
+            md += "\n## To go over\n";
+        }
+        for (n, q) in missed {
+            md += &format!("\n### {n}. {}/{}\n\n{}\n\n", q.pack, q.id, q.prompt);
+            if !q.code.is_empty() {
+                md += &format!("```rust\n{}\n```\n\n", q.code);
+            }
+            for (i, choice) in (1..).zip(&q.choices) {
+                md += &format!("{i}. `{choice}`\n");
+            }
+            if !q.choices.is_empty() {
+                md += "\n";
+            }
+            let answer = q.answer.as_deref().filter(|a| !a.is_empty()).unwrap_or("(none)");
+            md += &format!("Answer: `{answer}`  \nExpected: `{}`\n\n{}\n", q.expected, q.explanation);
+        }
+        md
+    }
+}
+
+// runs the exam and writes the report next to `report` (.json and .md)
+pub fn exam(
+    packs: &[Pack],
+    verdicts: &mut Verdicts,
+    topics: &[String],
+    minutes: u64,
+    report: &str,
+    profile: &Profile,
+    theme: &Theme,
+) -> Result<(), String> {
+    let mut known: Vec<&str> = packs.iter().map(|p| p.topic.as_str()).collect();
+    known.sort();
+    known.dedup();
+    if let Some(topic) = topics.iter().find(|t| !known.contains(&t.as_str())) {
+        return Err(format!("no packs on `{topic}` (topics: {})", known.join(", ")));
+    }
+    let questions: Vec<(&Pack, &Question)> = packs
+        .iter()
+        .filter(|p| topics.contains(&p.topic))
+        .flat_map(|p| p.questions.iter().map(move |q| (p, q)))
+        .filter(|(_, q)| !matches!(q.kind, Kind::Fix { .. }))
+        .collect();
+    if questions.is_empty() {
+        return Err("no questions to ask: fix questions aren't used in exams".to_string());
+    }
+
+    println!("Exam: {} questions on {}, {minutes} minutes. No hints until the end.\n", questions.len(), topics.join(", "));
+    let started = progress::now();
+    let clock_started = Instant::now();
+    let answered = session(&questions, minutes, verdicts, theme).map_err(|e| format!("cannot run rustc: {e}"))?;
+    let attempts: Vec<progress::Attempt> = answered
+        .iter()
+        .filter(|a| a.answer.as_deref().is_some_and(|a| !a.is_empty()))
+        .map(|a| progress::Attempt::now(&a.pack, &a.id, a.correct))
+        .collect();
+    progress::record(profile, &attempts).map_err(|e| format!("cannot record progress: {e}"))?;
+    let report_data = Report {
+        topics: topics.to_vec(),
+        minutes,
+        started,
+        seconds: clock_started.elapsed().as_secs(),
+        score: answered.iter().filter(|a| a.correct).count(),
+        questions: answered,
+    };
+
+    let json = serde_json::to_string_pretty(&report_data).map_err(|e| e.to_string())? + "\n";
+    for (path, text) in [(format!("{report}.json"), json), (format!("{report}.md"), report_data.markdown())] {
+        fs::write(&path, text).map_err(|e| format!("{path}: {e}"))?;
+    }
+    println!("{} of {} right in {}", report_data.score, report_data.questions.len(), clock(report_data.seconds));
+    println!("report: {report}.json, {report}.md");
+    Ok(())
+}
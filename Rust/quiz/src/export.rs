@@ -0,0 +1,390 @@
+// QUIZ: THE NOTES AS PAGES -----------------------------------------------
+
+/*
+ *     quiz export --html DIR       a page per topic, and index.html
+ *     quiz export --mdbook DIR     book.toml and src/, for `mdbook build`
+ *     quiz export --man DIR        man1/quiz.1, and a page per topic in
+ *                                  man7: MANPATH=DIR man langscape-traits
+ *
+ * The notes are code with comments in it; a page is the same turned
+ * inside out, the comments as text and the code in blocks:
+ *
+ *     // SLICE ---------            a heading: a part's is one level
+ *     // Updating a string . . .    above a section's
+ *     a block comment, or a        text, where a paragraph indented
+ *     paragraph of nothing but     four spaces stays as written (the
+ *     // lines                     notes' tables and diagrams)
+ *     any other paragraph          code, its comments and all
+ *
+ * Every topic from every provider gets a page, so an installed pack's
+ * notes are in the book too, and the last page is the concordance
+ * (concordance.rs), each place in it a link to its section. The links between topics (links.rs) are
+ * links to the heading's id; inside code they're links in HTML and
+ * their label in mdBook, whose code blocks are only code.
+ *
+ * The man pages are the topics the same way, a link a page's name,
+ * langscape-traits(7), with the pages linked under SEE ALSO; the
+ * concordance is quiz where-is there. quiz(1) is the usage at the top of
+ * main.rs, read from it as it's compiled in, so the page can't say
+ * other than the code.
+ */
+
+use crate::classroom::escape;
+use crate::concordance::{self, Entry};
+use crate::links::{self, Target};
+use crate::notes;
+use crate::providers::Topic;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub enum Block {
+    Heading(String, bool),             // true: a part
+    Text(Vec<String>),
+    Code(Vec<String>),
+}
+
+// what's left of a block comment's line without the comment marks
+fn uncommented(line: &str) -> &str {
+    let line = line.trim_start();
+    let line = line.strip_prefix("/*").unwrap_or(line);
+    let line = line.strip_suffix("*/").unwrap_or(line).trim_end();
+    line.strip_prefix("* ").or_else(|| line.strip_prefix('*')).unwrap_or(line)
+}
+
+fn paragraph(lines: &mut Vec<&str>, blocks: &mut Vec<Block>) {
+    if lines.is_empty() {
+        return;
+    }
+    if lines.iter().all(|l| l.trim_start().starts_with("//")) {
+        let text = lines.iter().map(|l| {
+            let l = l.trim_start().trim_start_matches('/');
+            l.strip_prefix(' ').unwrap_or(l).to_string()
+        });
+        blocks.push(Block::Text(text.collect()));
+    } else {
+        blocks.push(Block::Code(lines.iter().map(|l| l.to_string()).collect()));
+    }
+    lines.clear();
+}
+
+// the notes turned inside out, as above; cheatsheet.rs has them too
+pub fn blocks(text: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut lines: Vec<&str> = Vec::new();
+    let mut comment: Option<Vec<String>> = None;
+    for line in text.lines() {
+        if let Some(text) = &mut comment {
+            text.push(uncommented(line).to_string());
+            if line.contains("*/") {
+                blocks.push(Block::Text(comment.take().unwrap_or_default()));
+            }
+            continue;
+        }
+        let rule = line.trim_start().starts_with("//") && line.chars().all(|c| matches!(c, '/' | '-' | '=' | ' ' | '\t'));
+        if line.trim().is_empty() || rule {
+            paragraph(&mut lines, &mut blocks);
+        } else if let Some((title, part)) = notes::heading(line) {
+            paragraph(&mut lines, &mut blocks);
+            blocks.push(Block::Heading(title.to_string(), part));
+        } else if line.trim_start().starts_with("/*") {
+            paragraph(&mut lines, &mut blocks);
+            let first = vec![uncommented(line).to_string()];
+            if line.contains("*/") {
+                blocks.push(Block::Text(first));
+            } else {
+                comment = Some(first);
+            }
+        } else {
+            lines.push(line);
+        }
+    }
+    paragraph(&mut lines, &mut blocks);
+    blocks
+}
+
+// a block of text cut at its blank lines: true for a paragraph to keep
+// as written, indented four spaces
+pub fn paragraphs(lines: &[String]) -> Vec<(bool, Vec<&str>)> {
+    let mut out: Vec<(bool, Vec<&str>)> = Vec::new();
+    let mut open = false;
+    for line in lines {
+        if line.trim().is_empty() {
+            open = false;
+            continue;
+        }
+        match out.last_mut() {
+            Some((_, lines)) if open => lines.push(line),
+            _ => out.push((line.starts_with("    "), vec![line])),
+        }
+        open = true;
+    }
+    out
+}
+
+// HTML -------------------------------------------------------------------
+
+const STYLE: &str = "<style>\n\
+    body { font-family: sans-serif; max-width: 50em; margin: 2em auto; line-height: 1.4; }\n\
+    pre { background: #f4f4f4; padding: .6em; overflow-x: auto; }\n\
+    </style>";
+
+fn html_link(target: &Target) -> String {
+    format!("<a href=\"{}\">{}</a>", escape(&target.href("html")), escape(&target.label()))
+}
+
+fn html_page(topic: &Topic, topics: &[Topic]) -> String {
+    let mut html = format!(
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>{title}</title>\n{STYLE}</head><body>\n\
+         <p><a href=\"index.html\">Contents</a></p>\n<h1>{title}</h1>\n",
+        title = escape(&topic.title)
+    );
+    let linked = |text: &str| links::replace(text, topics, escape, html_link);
+    for block in blocks(&topic.text) {
+        match block {
+            Block::Heading(title, part) => {
+                let level = if part { 2 } else { 3 };
+                html += &format!("<h{level} id=\"{}\">{}</h{level}>\n", links::slug(&title), escape(&title));
+            }
+            Block::Text(lines) => {
+                for (verbatim, lines) in paragraphs(&lines) {
+                    let (open, close) = if verbatim { ("<pre>", "</pre>") } else { ("<p>", "</p>") };
+                    html += &format!("{open}{}{close}\n", linked(&lines.join("\n")).trim_end());
+                }
+            }
+            Block::Code(lines) => html += &format!("<pre><code>{}</code></pre>\n", linked(&lines.join("\n")).trim_end()),
+        }
+    }
+    html + "</body></html>\n"
+}
+
+fn html_concordance(entries: &[Entry]) -> String {
+    let mut html = format!(
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>Concordance</title>\n{STYLE}</head><body>\n\
+         <p><a href=\"index.html\">Contents</a></p>\n<h1>Concordance</h1>\n<dl>\n"
+    );
+    for entry in entries {
+        html += &format!("<dt>{}</dt>\n", escape(&entry.term));
+        let places = entry.places.iter().map(|p| {
+            let target = Target { topic: p.topic.clone(), heading: p.heading.clone() };
+            format!("<a href=\"{}\">{} {}</a>", escape(&target.href("html")), p.topic, concordance::span(&p.lines))
+        });
+        html += &format!("<dd>{}</dd>\n", places.collect::<Vec<_>>().join(", "));
+    }
+    html + "</dl>\n</body></html>\n"
+}
+
+fn html(topics: &[Topic], dir: &Path) -> io::Result<()> {
+    let mut index = format!("<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>Notes</title>\n{STYLE}</head><body>\n<h1>Notes</h1>\n<ul>\n");
+    for topic in topics {
+        fs::write(dir.join(format!("{}.html", topic.id)), html_page(topic, topics))?;
+        index += &format!("<li><a href=\"{}.html\">{}</a></li>\n", topic.id, escape(&topic.title));
+    }
+    fs::write(dir.join("concordance.html"), html_concordance(&concordance::concordance(topics)))?;
+    index += "<li><a href=\"concordance.html\">Concordance</a></li>\n";
+    fs::write(dir.join("index.html"), index + "</ul>\n</body></html>\n")
+}
+
+// MDBOOK -----------------------------------------------------------------
+
+// what markdown would read as markup in the notes' prose: `*p`, `<T>`, a
+// line starting with #
+fn markdown_escape(text: &str) -> String {
+    let text = text.replace('\\', "\\\\").replace('*', "\\*").replace('<', "&lt;").replace('`', "\\`");
+    match text.strip_prefix('#') {
+        Some(rest) => format!("\\#{rest}"),
+        None => text,
+    }
+}
+
+fn markdown_link(target: &Target) -> String {
+    format!("[{}]({})", target.label().replace(['[', ']'], ""), target.href("md"))
+}
+
+fn markdown_page(topic: &Topic, topics: &[Topic]) -> String {
+    let mut md = format!("# {}\n\n", markdown_escape(&topic.title));
+    for block in blocks(&topic.text) {
+        match block {
+            // an explicit id: mdBook's own would drop the `<` of "Box<dyn Trait>"
+            Block::Heading(title, part) => {
+                let hashes = if part { "##" } else { "###" };
+                md += &format!("<a id=\"{}\"></a>\n\n{hashes} {}\n\n", links::slug(&title), markdown_escape(&title));
+            }
+            Block::Text(lines) => {
+                for (verbatim, lines) in paragraphs(&lines) {
+                    if verbatim {
+                        md += &format!("```text\n{}```\n\n", links::replace(&lines.join("\n"), topics, str::to_string, Target::label));
+                    } else {
+                        md += &format!("{}\n", links::replace(&lines.join("\n"), topics, markdown_escape, markdown_link));
+                    }
+                }
+            }
+            Block::Code(lines) => {
+                md += &format!("```rust\n{}```\n\n", links::replace(&lines.join("\n"), topics, str::to_string, Target::label));
+            }
+        }
+    }
+    md
+}
+
+fn markdown_concordance(entries: &[Entry]) -> String {
+    let mut md = String::from("# Concordance\n\n");
+    for entry in entries {
+        let places = entry.places.iter().map(|p| {
+            let target = Target { topic: p.topic.clone(), heading: p.heading.clone() };
+            format!("[{} {}]({})", p.topic, concordance::span(&p.lines), target.href("md"))
+        });
+        md += &format!("- **{}**: {}\n", markdown_escape(&entry.term), places.collect::<Vec<_>>().join(", "));
+    }
+    md
+}
+
+fn mdbook(topics: &[Topic], dir: &Path) -> io::Result<()> {
+    let src = dir.join("src");
+    fs::create_dir_all(&src)?;
+    fs::write(dir.join("book.toml"), "[book]\ntitle = \"Notes\"\nsrc = \"src\"\n\n[output.html]\n")?;
+    let mut summary = String::from("# Summary\n\n");
+    for topic in topics {
+        fs::write(src.join(format!("{}.md", topic.id)), markdown_page(topic, topics))?;
+        summary += &format!("- [{}]({}.md)\n", topic.title.replace(['[', ']'], ""), topic.id);
+    }
+    fs::write(src.join("concordance.md"), markdown_concordance(&concordance::concordance(topics)))?;
+    fs::write(src.join("SUMMARY.md"), summary + "- [Concordance](concordance.md)\n")
+}
+
+// MAN PAGES --------------------------------------------------------------
+
+// text as roff reads it: a backslash is \e, and a hyphen a minus sign, so
+// code copied from the page is code
+fn roff_escape(text: &str) -> String {
+    text.replace('\\', "\\e").replace('-', "\\-")
+}
+
+// a line starting with . or ' would be a request: \& in front keeps it text
+fn roff_lines(text: &str) -> String {
+    let line = |l: &str| if l.starts_with(['.', '\'']) { format!("\\&{l}\n") } else { format!("{l}\n") };
+    text.lines().map(line).collect()
+}
+
+fn man_name(id: &str) -> String {
+    format!("langscape-{id}")
+}
+
+fn man_page(topic: &Topic, topics: &[Topic]) -> String {
+    let name = man_name(&topic.id);
+    let mut man = format!(".TH {} 7 \"\" langscape \"Langscape notes\"\n.SH NAME\n{} \\- {}\n", name.to_uppercase(), roff_escape(&name), roff_escape(&topic.title));
+    let mut see_also = vec![topic.id.clone()];
+    let mut link = |target: &Target| {
+        see_also.push(target.topic.clone());
+        let page = format!("\\fB{}\\fR(7)", roff_escape(&man_name(&target.topic)));
+        match &target.heading {
+            Some(heading) => format!("{page}, \"{}\"", roff_escape(heading)),
+            None => page,
+        }
+    };
+    for block in blocks(&topic.text) {
+        match block {
+            Block::Heading(title, true) => man += &roff_lines(&format!(".SH {}", roff_escape(&title.to_uppercase()))),
+            Block::Heading(title, false) => man += &roff_lines(&format!(".SS {}", roff_escape(&title))),
+            Block::Text(lines) => {
+                for (verbatim, lines) in paragraphs(&lines) {
+                    if verbatim {
+                        man += &format!(".PP\n.nf\n{}.fi\n", roff_lines(&links::replace(&lines.join("\n"), topics, roff_escape, &mut link)));
+                    } else {
+                        // filled: a line starting with a space would break it
+                        let lines: Vec<&str> = lines.iter().map(|l| l.trim()).collect();
+                        man += &format!(".PP\n{}", roff_lines(&links::replace(&lines.join("\n"), topics, roff_escape, &mut link)));
+                    }
+                }
+            }
+            Block::Code(lines) => {
+                let code = roff_lines(&links::replace(&lines.join("\n"), topics, roff_escape, |t: &Target| roff_escape(&t.label())));
+                man += &format!(".PP\n.RS 4\n.nf\n{code}.fi\n.RE\n");
+            }
+        }
+    }
+    see_also.sort();
+    see_also.dedup();
+    see_also.retain(|id| *id != topic.id);
+    let pages = see_also.iter().map(|id| format!("\\fB{}\\fR(7)", roff_escape(&man_name(id))));
+    man + &format!(".SH SEE ALSO\n{}\n", pages.chain(["\\fBquiz\\fR(1)".to_string()]).collect::<Vec<_>>().join(",\n"))
+}
+
+// the commands, from the usage at the top of main.rs: each
+// `cargo run -- ` line, what's in its first 36 columns (and the lines
+// under it's) the synopsis, the rest what it does; and the paragraphs
+// after them
+fn usage() -> (Vec<(String, String)>, Vec<String>) {
+    let header = include_str!("main.rs").lines().take_while(|l| !l.starts_with(" */"));
+    let lines: Vec<&str> = header.map(|l| l.strip_prefix(" *").unwrap_or(l).strip_prefix(' ').unwrap_or_default()).collect();
+    let Some(first) = lines.iter().position(|l| l.starts_with("cargo run -- ")) else { return Default::default() };
+    let split = |line: &str| match line.char_indices().nth(36) {
+        Some((i, _)) if line[..i].ends_with("  ") || line[..i].trim().is_empty() => (line[..i].trim().to_string(), line[i..].trim().to_string()),
+        _ => (line.trim().to_string(), String::new()),
+    };
+    let mut commands: Vec<(String, String)> = Vec::new();
+    let mut rest = lines.len();
+    for (i, line) in lines.iter().enumerate().skip(first) {
+        let add = |to: &mut String, more: String| {
+            if !more.is_empty() {
+                *to = if to.is_empty() { more } else { format!("{to} {more}") };
+            }
+        };
+        match (line.starts_with("cargo run -- "), commands.last_mut()) {
+            (true, _) => {
+                let (synopsis, what) = split(line);
+                commands.push((synopsis.replacen("cargo run --", "quiz", 1), what));
+            }
+            (false, Some((synopsis, what))) if line.starts_with("    ") => {
+                let (more, more_what) = split(line);
+                add(synopsis, more);
+                add(what, more_what);
+            }
+            _ => {
+                rest = i;
+                break;
+            }
+        }
+    }
+    let paragraphs = lines[rest..].split(|l| l.trim().is_empty()).filter(|p| !p.is_empty()).map(|p| p.join("\n")).collect();
+    (commands, paragraphs)
+}
+
+fn quiz_page(topics: &[Topic]) -> String {
+    let what = include_str!("main.rs").lines().skip(3).take_while(|l| l.trim() != "*");
+    let what: Vec<&str> = what.map(|l| l.trim_start_matches([' ', '*'])).collect();
+    let mut man = String::from(".TH QUIZ 1 \"\" langscape \"Langscape\"\n.SH NAME\nquiz \\- practice questions for the notes, checked by the compiler\n");
+    man += ".SH SYNOPSIS\n.B quiz\n.I COMMAND\n.RI [ OPTIONS ]\n";
+    man += &format!(".SH DESCRIPTION\n{}", roff_lines(&roff_escape(&what.join("\n"))));
+    let (commands, paragraphs) = usage();
+    man += ".SH COMMANDS\n";
+    for (synopsis, what) in commands {
+        man += &format!(".TP\n.B {}\n{}", roff_escape(&synopsis), roff_lines(&roff_escape(&what)));
+    }
+    for paragraph in paragraphs {
+        man += &format!(".PP\n{}", roff_lines(&roff_escape(&paragraph)));
+    }
+    let pages = topics.iter().map(|t| format!("\\fB{}\\fR(7)", roff_escape(&man_name(&t.id))));
+    man + &format!(".SH SEE ALSO\n{}\n", pages.collect::<Vec<_>>().join(",\n"))
+}
+
+fn man(topics: &[Topic], dir: &Path) -> io::Result<()> {
+    let (man1, man7) = (dir.join("man1"), dir.join("man7"));
+    fs::create_dir_all(&man1)?;
+    fs::create_dir_all(&man7)?;
+    fs::write(man1.join("quiz.1"), quiz_page(topics))?;
+    for topic in topics {
+        fs::write(man7.join(format!("{}.7", man_name(&topic.id))), man_page(topic, topics))?;
+    }
+    Ok(())
+}
+
+// `format` is "html", "mdbook" or "man"
+pub fn export(topics: &[Topic], format: &str, dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    match format {
+        "html" => html(topics, dir),
+        "man" => man(topics, dir),
+        _ => mdbook(topics, dir),
+    }
+}
@@ -0,0 +1,592 @@
+// QUIZ: GRADING A FIX ----------------------------------------------------
+
+/*
+ * A fix is free-form code, so it's graded as code: it has to compile,
+ * and still define what the broken version did. That alone passes
+ * `fn longest() {}` on a question about longest's lifetimes, so the fix
+ * is compiled once more, with uses of what the solution defines after
+ * it, each the way the solution defines it:
+ *
+ *     fn __use_1<'a>(__0: &'a str, __1: &'a str) -> &'a str {
+ *         longest(__0, __1)
+ *     }
+ *
+ * a function with the solution's signature, calling the fix's. A method
+ * is called the same way through its type, <Excerpt<'a>>::part(__self),
+ * and a struct is taken apart field by field, so its fields have to be
+ * the solution's too. The fix needn't be written as the solution is:
+ * `impl Excerpt<'_>` for `impl<'a> Excerpt<'a>`, a lifetime of another
+ * name, or a signature more general than the solution's all compile
+ * there. Enums, traits and the rest are only checked by name.
+ *
+ * The classroom server (classroom.rs) and `quiz verify` (solutions.rs)
+ * grade with this, and `quiz check` compiles every solution with its own
+ * uses, so a question whose solution they don't fit can't ship.
+ */
+
+use crate::harness::{self, Verdict};
+use std::io;
+
+pub enum Fix {
+    Passes,
+    Missing(Vec<String>),              // what the broken version defined, and the fix doesn't
+    Fails(Verdict),                    // doesn't compile
+    Differs(Verdict),                  // compiles, but not with the solution's uses
+}
+
+pub fn grade(broken: &str, solved: &str, answer: &str, edition: &str) -> io::Result<Fix> {
+    let defined = items(answer);
+    let missing: Vec<String> = items(broken).into_iter().filter(|item| !defined.contains(item)).collect();
+    if !missing.is_empty() {
+        return Ok(Fix::Missing(missing));
+    }
+    let verdict = harness::compile(answer, edition)?;
+    if !verdict.compiles {
+        return Ok(Fix::Fails(verdict));
+    }
+    let verdict = harness::compile(&with_uses(answer, solved), edition)?;
+    Ok(if verdict.compiles { Fix::Passes } else { Fix::Differs(verdict) })
+}
+
+// `code`, with the uses of what `solved` defines after it
+pub fn with_uses(code: &str, solved: &str) -> String {
+    format!("{code}\n\n// the solution's items, used the way it defines them\n{}", uses(solved))
+}
+
+// ITEMS ------------------------------------------------------------------
+
+// the names a snippet defines: fn longest, struct Person, ... Only in
+// the code itself: `// fn longest` or "fn longest" defines nothing
+pub fn items(code: &str) -> Vec<String> {
+    let code = blank_comments_and_literals(code);
+    let words: Vec<&str> = code.split(|c: char| !is_ident(c)).filter(|w| !w.is_empty()).collect();
+    let keywords = ["fn", "struct", "enum", "trait", "type", "const", "static", "mod"];
+    words.windows(2).filter(|w| keywords.contains(&w[0])).map(|w| w[1].to_string()).collect()
+}
+
+// `code` with each comment, string and char literal a single space.
+// Lifetimes stay: 'a isn't a char
+fn blank_comments_and_literals(code: &str) -> String {
+    let chars: Vec<char> = code.chars().collect();
+    let mut blanked = String::with_capacity(code.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let rest = &chars[i..];
+        let skip = match rest {
+            ['/', '/', ..] => rest.iter().position(|&c| c == '\n').unwrap_or(rest.len()),
+            ['/', '*', ..] => block_comment(rest),
+            ['"', ..] => string(rest),
+            ['r', ..] if i == 0 || !is_ident(chars[i - 1]) || chars[i - 1] == 'b' => raw_string(rest),
+            ['\'', '\\', ..] => rest[2..].iter().position(|&c| c == '\'').map_or(rest.len(), |end| end + 3),
+            ['\'', _, '\'', ..] => 3,
+            _ => 0,
+        };
+        if skip == 0 {
+            blanked.push(rest[0]);
+            i += 1;
+        } else {
+            blanked.push(' ');
+            i += skip;
+        }
+    }
+    blanked
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// the length of the comment `rest` starts with; they nest
+fn block_comment(rest: &[char]) -> usize {
+    let (mut depth, mut i) = (0, 0);
+    while i < rest.len() {
+        match rest[i..] {
+            ['/', '*', ..] => (depth, i) = (depth + 1, i + 2),
+            ['*', '/', ..] if depth == 1 => return i + 2,
+            ['*', '/', ..] => (depth, i) = (depth - 1, i + 2),
+            _ => i += 1,
+        }
+    }
+    rest.len()
+}
+
+// the length of the "string" `rest` starts with, escapes and all
+fn string(rest: &[char]) -> usize {
+    let mut i = 1;
+    while i < rest.len() {
+        match rest[i] {
+            '\\' => i += 2,
+            '"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    rest.len()
+}
+
+// the length of the r#"raw string"# `rest` starts with; 0 if it's an
+// r that starts something else, a name or an r#raw identifier
+fn raw_string(rest: &[char]) -> usize {
+    let hashes = rest[1..].iter().take_while(|&&c| c == '#').count();
+    if rest.get(1 + hashes) != Some(&'"') {
+        return 0;
+    }
+    let start = 2 + hashes;
+    let end = (start..rest.len()).find(|&i| rest[i] == '"' && rest[i + 1..].iter().take(hashes).filter(|&&c| c == '#').count() == hashes);
+    end.map_or(rest.len(), |end| end + 1 + hashes)
+}
+
+// USES -------------------------------------------------------------------
+
+// a function per fn, method and struct in `solved` that uses the one of
+// that name the way `solved` defines it
+fn uses(solved: &str) -> String {
+    let code = blank_comments_and_literals(solved);
+    let mut uses = Vec::new();
+    for item in top_level(&code) {
+        let (header, body) = split_body(item);
+        match keyword(header) {
+            Some("fn") => uses.extend(parse_fn(header).map(|f| use_fn(&f, None))),
+            Some("struct") => uses.extend(use_struct(header, body)),
+            Some("impl") => {
+                let Some(imp) = parse_impl(header) else { continue };
+                for method in top_level(body.unwrap_or_default()) {
+                    let (header, _) = split_body(method);
+                    if keyword(header) == Some("fn") {
+                        uses.extend(parse_fn(header).map(|f| use_fn(&f, Some(&imp))));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    let numbered = (1..).zip(uses).map(|(n, u)| u.replacen("fn __use", &format!("fn __use_{n}"), 1));
+    numbered.collect::<Vec<_>>().join("\n")
+}
+
+struct Signature<'a> {
+    name: &'a str,
+    asynchronous: bool,
+    unsafe_: bool,
+    generics: Vec<&'a str>,            // "'a", "T: Clone"
+    params: Vec<&'a str>,              // "&self", "x: &'a str"
+    tail: &'a str,                     // after the ): "-> &'a str where .."
+}
+
+struct Impl<'a> {
+    generics: Vec<&'a str>,
+    trait_: Option<&'a str>,
+    ty: &'a str,
+    bounds: Option<&'a str>,           // its where clause, without the `where`
+}
+
+// fn NAME<GENERICS>(PARAMS) TAIL
+fn parse_fn(header: &str) -> Option<Signature<'_>> {
+    let at = word(header, "fn")?;
+    let qualifiers: Vec<&str> = header[..at].split_whitespace().collect();
+    let (name, rest) = ident(&header[at + 2..])?;
+    let (generics, rest) = generics(rest)?;
+    let rest = rest.trim_start();
+    let end = rest.starts_with('(').then(|| closing(rest, '(', ')'))??;
+    Some(Signature {
+        name,
+        asynchronous: qualifiers.contains(&"async"),
+        unsafe_: qualifiers.contains(&"unsafe"),
+        generics,
+        params: split_top(&rest[1..end], ','),
+        tail: rest[end + 1..].trim(),
+    })
+}
+
+// impl<GENERICS> [TRAIT for] TYPE [where BOUNDS]
+fn parse_impl(header: &str) -> Option<Impl<'_>> {
+    let at = word(header, "impl")?;
+    let (generics, rest) = generics(&header[at + 4..])?;
+    let (rest, bounds) = match word(rest, "where") {
+        Some(at) => (&rest[..at], Some(rest[at + 5..].trim())),
+        None => (rest, None),
+    };
+    let (trait_, ty) = match word(rest, "for") {
+        Some(at) => (Some(rest[..at].trim()), rest[at + 3..].trim()),
+        None => (None, rest.trim()),
+    };
+    (!ty.is_empty() && !trait_.is_some_and(|t| t.starts_with('!'))).then_some(Impl { generics, trait_, ty, bounds })
+}
+
+// fn __use<GENERICS>(__0: T, ..) TAIL { name(__0, ..) }, the method's
+// receiver first, as __self
+fn use_fn(f: &Signature, imp: Option<&Impl>) -> String {
+    let this = |text: &str| imp.map_or(text.to_string(), |imp| replace_word(text, "Self", imp.ty));
+    let (mut params, mut args, mut borrowed) = (Vec::new(), Vec::new(), false);
+    for (n, param) in f.params.iter().enumerate() {
+        match receiver(param, imp.map(|imp| imp.ty)) {
+            // rule (3): what the output borrows, unnamed, it borrows
+            // from self, so self's borrow gets a name to give it
+            Some(ty) if ty.starts_with('&') && !ty[1..].trim_start().starts_with('\'') => {
+                params.push(format!("__self: &'__s {}", this(&ty[1..])));
+                borrowed = true;
+            }
+            Some(ty) => params.push(format!("__self: {}", this(&ty))),
+            None => {
+                let ty = split_top(param, ':').get(1).map_or(String::new(), |ty| this(ty));
+                params.push(format!("__{n}: {ty}"));
+            }
+        }
+        args.push(if params.last().unwrap().starts_with("__self") { "__self".to_string() } else { format!("__{n}") });
+    }
+    let path = match imp {
+        Some(Impl { trait_: Some(tr), ty, .. }) => format!("<{ty} as {tr}>::{}", f.name),
+        Some(Impl { ty, .. }) => format!("<{ty}>::{}", f.name),
+        None => f.name.to_string(),
+    };
+    let mut call = format!("{path}({})", args.join(", "));
+    if f.asynchronous {
+        call += ".await";
+    }
+    if f.unsafe_ {
+        call = format!("unsafe {{ {call} }}");
+    }
+    let mut generics = imp.map_or(Vec::new(), |imp| imp.generics.clone());
+    generics.extend(&f.generics);
+    let mut tail = this(f.tail);
+    if borrowed {
+        generics.push("'__s");
+        let end = word(&tail, "where").unwrap_or(tail.len());
+        tail = format!("{}{}", named(&tail[..end], "'__s"), &tail[end..]);
+    }
+    if let Some(bounds) = imp.and_then(|imp| imp.bounds) {
+        tail += if word(&tail, "where").is_some() { ", " } else { " where " };
+        tail += bounds;
+    }
+    let qualifiers = format!("{}{}", if f.asynchronous { "async " } else { "" }, if f.unsafe_ { "unsafe " } else { "" });
+    let header = format!("{qualifiers}fn __use{}({}) {tail}", angle(&generics), params.join(", "));
+    format!("{} {{\n    {call}\n}}", header.trim_end())
+}
+
+// `ty` with each lifetime it leaves out as `lifetime`: &str, &mut T
+// and '_, but not those of an Fn(&str) -> &str, which are its own
+fn named(ty: &str, lifetime: &str) -> String {
+    let (mut named, mut depth, mut sugar) = (String::new(), 0, None);
+    let bytes = ty.as_bytes();
+    for (i, c) in ty.char_indices() {
+        match c {
+            '(' if sugar.is_none() && ["Fn", "FnMut", "FnOnce"].iter().any(|f| ty[..i].trim_end().ends_with(f)) => {
+                sugar = Some(depth);
+                depth += 1;
+            }
+            '(' | '[' | '<' => depth += 1,
+            ')' | ']' => depth -= 1,
+            '>' if i == 0 || bytes[i - 1] != b'-' => depth -= 1,
+            _ => {}
+        }
+        // the sugar ends with what it's in, or at the next , or +
+        if sugar.is_some_and(|d| depth < d || (depth == d && (c == ',' || c == '+'))) {
+            sugar = None;
+        }
+        let rest = &ty[i + c.len_utf8()..];
+        if sugar.is_some() {
+            named.push(c);
+        } else if c == '&' && !rest.trim_start().starts_with('\'') {
+            named += &format!("&{lifetime} ");
+        } else if c == '\'' && rest.starts_with('_') && !rest[1..].starts_with(is_ident) {
+            named += lifetime;
+        } else if !(c == '_' && ty[..i].ends_with('\'') && !rest.starts_with(is_ident)) {
+            named.push(c);
+        }
+    }
+    named
+}
+
+// the type of a receiver, self or &'a mut self or self: Box<Self>; None
+// for any other parameter
+fn receiver(param: &str, ty: Option<&str>) -> Option<String> {
+    let ty = ty?;
+    let parts = split_top(param, ':');
+    let pattern = parts[0].trim_start_matches("mut ").trim();
+    match (pattern.strip_suffix("self"), parts.get(1)) {
+        (Some(""), Some(explicit)) => Some(explicit.to_string()),
+        (Some(reference), None) if reference.is_empty() || reference.starts_with('&') => Some(format!("{reference}{ty}")),
+        _ => None,
+    }
+}
+
+// fn __use<'__v, GENERICS>(__v: &'__v Name<ARGS>) -> (&'__v FIELD, ..)
+fn use_struct(header: &str, body: Option<&str>) -> Option<String> {
+    let at = word(header, "struct")?;
+    let (name, rest) = ident(&header[at + 6..])?;
+    let (generics, rest) = generics(rest)?;
+    let rest = rest.trim_start();
+    let (fields, tail): (Vec<(String, &str)>, &str) = match body {
+        Some(body) => {
+            let fields = split_top(body, ',').into_iter().filter_map(|field| {
+                let parts = split_top(strip_attributes(field), ':');
+                let name = parts[0].split_whitespace().last()?;
+                Some((name.to_string(), *parts.get(1)?))
+            });
+            (fields.collect(), rest)
+        }
+        None if rest.starts_with('(') => {
+            let end = closing(rest, '(', ')')?;
+            let fields = split_top(&rest[1..end], ',').into_iter().map(|field| strip_visibility(strip_attributes(field)));
+            ((0..).map(|n: usize| n.to_string()).zip(fields).collect(), rest[end + 1..].trim_end_matches(';').trim())
+        }
+        None => (Vec::new(), rest.trim_end_matches(';').trim()),
+    };
+    let args: Vec<&str> = generics.iter().map(|g| g.trim_start_matches("const ").split([':', '=']).next().unwrap_or_default().trim()).collect();
+    let mut all = vec!["'__v"];
+    all.extend(&generics);
+    let types: Vec<String> = fields.iter().map(|(_, ty)| format!("&'__v {ty},")).collect();
+    let values: Vec<String> = fields.iter().map(|(field, _)| format!("&__v.{field},")).collect();
+    let header = format!("fn __use{}(__v: &'__v {name}{}) -> ({}) {tail}", angle(&all), angle(&args), types.join(" "));
+    Some(format!("{} {{\n    ({})\n}}", header.trim_end(), values.join(" ")))
+}
+
+// SCANNING ---------------------------------------------------------------
+
+// the items in `code`, each to its `;` or closing `}`
+fn top_level(code: &str) -> Vec<&str> {
+    let (mut items, mut start, mut depth) = (Vec::new(), 0, 0);
+    for (i, c) in code.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' => depth -= 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    items.push(code[start..=i].trim());
+                    start = i + 1;
+                }
+            }
+            ';' if depth == 0 => {
+                items.push(code[start..=i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items
+}
+
+// an item's header, without its attributes, and what's inside its
+// braces, if it has any
+fn split_body(item: &str) -> (&str, Option<&str>) {
+    let item = strip_attributes(item);
+    let mut depth = 0;
+    for (i, c) in item.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            '{' if depth == 0 => return (&item[..i], item.get(i + 1..item.len() - 1)),
+            _ => {}
+        }
+    }
+    (item.trim_end_matches(';'), None)
+}
+
+fn strip_attributes(mut text: &str) -> &str {
+    text = text.trim_start();
+    while let Some(rest) = text.strip_prefix("#!").or_else(|| text.strip_prefix('#')) {
+        match closing(rest, '[', ']') {
+            Some(end) => text = rest[end + 1..].trim_start(),
+            None => break,
+        }
+    }
+    text
+}
+
+fn strip_visibility(text: &str) -> &str {
+    match text.strip_prefix("pub") {
+        Some(rest) if rest.trim_start().starts_with('(') => {
+            let rest = rest.trim_start();
+            closing(rest, '(', ')').map_or(text, |end| rest[end + 1..].trim())
+        }
+        Some(rest) if rest.starts_with(char::is_whitespace) => rest.trim(),
+        _ => text,
+    }
+}
+
+// the first of the item keywords in `header`: "fn" for `pub const fn`
+fn keyword(header: &str) -> Option<&str> {
+    let words: Vec<&str> = header.split(|c: char| !is_ident(c)).filter(|w| !w.is_empty()).collect();
+    let keywords = ["fn", "struct", "impl", "enum", "trait", "type", "static", "mod", "use", "union", "macro_rules"];
+    words.iter().find(|w| keywords.contains(w)).copied()
+}
+
+// where `word` is in `text` as a word of its own, outside brackets
+fn word(text: &str, word: &str) -> Option<usize> {
+    let mut depth = 0;
+    let bytes = text.as_bytes();
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '>' if i == 0 || bytes[i - 1] != b'-' => depth -= 1,
+            _ if depth == 0 && text[i..].starts_with(word) => {
+                let before = text[..i].chars().next_back().is_none_or(|c| !is_ident(c));
+                let after = text[i + word.len()..].chars().next().is_none_or(|c| !is_ident(c));
+                if before && after {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// the name `text` starts with, and what's after it
+fn ident(text: &str) -> Option<(&str, &str)> {
+    let text = text.trim_start();
+    let end = text.find(|c: char| !is_ident(c)).unwrap_or(text.len());
+    (end > 0).then(|| text.split_at(end))
+}
+
+// the <generics> `text` starts with, if any, split, and what's after
+fn generics(text: &str) -> Option<(Vec<&str>, &str)> {
+    let text = text.trim_start();
+    if !text.starts_with('<') {
+        return Some((Vec::new(), text));
+    }
+    let end = closing(text, '<', '>')?;
+    Some((split_top(&text[1..end], ','), &text[end + 1..]))
+}
+
+// <'a, 'b, T>: lifetimes first, as rustc wants them
+fn angle(generics: &[&str]) -> String {
+    if generics.is_empty() {
+        return String::new();
+    }
+    let (lifetimes, others): (Vec<&str>, Vec<&str>) = generics.iter().partition(|g| g.starts_with('\''));
+    format!("<{}>", [lifetimes, others].concat().join(", "))
+}
+
+// the index of the bracket closing the one `text` starts with; the >
+// of an -> closes nothing
+fn closing(text: &str, open: char, close: char) -> Option<usize> {
+    let (mut depth, mut previous) = (0, ' ');
+    for (i, c) in text.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close && !(c == '>' && previous == '-') {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+        previous = c;
+    }
+    None
+}
+
+// `text` split at each `separator` outside brackets, trimmed; a `:`
+// of a `::` path doesn't split
+fn split_top(text: &str, separator: char) -> Vec<&str> {
+    let (mut parts, mut start, mut depth) = (Vec::new(), 0, 0);
+    let bytes = text.as_bytes();
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '>' if i == 0 || bytes[i - 1] != b'-' => depth -= 1,
+            ':' if bytes.get(i + 1) == Some(&b':') || (i > 0 && bytes[i - 1] == b':') => {}
+            _ if c == separator && depth == 0 => {
+                parts.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(text[start..].trim());
+    parts.retain(|p| !p.is_empty());
+    parts
+}
+
+// `text` with every `word` that's a word of its own as `with`
+fn replace_word(text: &str, word: &str, with: &str) -> String {
+    let mut replaced = String::new();
+    let mut rest = text;
+    while let Some(at) = rest.find(word) {
+        let before = rest[..at].chars().next_back().is_none_or(|c| !is_ident(c));
+        let after = rest[at + word.len()..].chars().next().is_none_or(|c| !is_ident(c));
+        replaced += &rest[..at];
+        replaced += if before && after { with } else { word };
+        rest = &rest[at + word.len()..];
+    }
+    replaced + rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn items_are_found_in_code_only() {
+        assert_eq!(items("struct Person;\nfn longest() {}"), ["Person", "longest"]);
+        assert!(items("// fn longest").is_empty());
+        assert!(items("/* fn longest /* nested */ fn shortest */").is_empty());
+        assert!(items(r#"const _: &str = "fn longest \" fn shortest";"#) == ["_"]);
+        assert!(items(r##"let s = r#"fn longest"#;"##).is_empty());
+        assert_eq!(items("fn f<'a>(c: char) -> bool { c == '\"' }\nfn g() {}"), ["f", "g"]);
+    }
+
+    #[test]
+    fn a_function_is_called_with_the_solutions_signature() {
+        let solved = "fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {\n    if x.len() > y.len() { x } else { y }\n}";
+        assert_eq!(uses(solved), "fn __use_1<'a>(__0: &'a str, __1: &'a str) -> &'a str {\n    longest(__0, __1)\n}");
+    }
+
+    #[test]
+    fn a_method_through_its_type() {
+        let solved = "struct Excerpt<'a> {\n    part: &'a str,\n}\n\nimpl<'a> Excerpt<'a> {\n    fn announce<'m>(&self, message: &'m str) -> &'m str {\n        message\n    }\n}";
+        let uses = uses(solved);
+        let uses: Vec<&str> = uses.lines().collect();
+        assert_eq!(
+            uses,
+            [
+                "fn __use_1<'__v, 'a>(__v: &'__v Excerpt<'a>) -> (&'__v &'a str,) {",
+                "    (&__v.part,)",
+                "}",
+                "fn __use_2<'a, 'm, '__s>(__self: &'__s Excerpt<'a>, __1: &'m str) -> &'m str {",
+                "    <Excerpt<'a>>::announce(__self, __1)",
+                "}",
+            ]
+        );
+    }
+
+    #[test]
+    fn trait_methods_tuple_structs_and_self() {
+        let solved = "#[derive(Debug)]\npub struct Meters(pub f64);\n\nimpl std::ops::Add for Meters {\n    type Output = Self;\n    fn add(self, other: Self) -> Self { Meters(self.0 + other.0) }\n}";
+        let uses = uses(solved);
+        assert!(uses.contains("fn __use_1<'__v>(__v: &'__v Meters) -> (&'__v f64,)"));
+        assert!(uses.contains("fn __use_2(__self: Meters, __1: Meters) -> Meters {\n    <Meters as std::ops::Add>::add(__self, __1)\n}"));
+    }
+
+    #[test]
+    fn what_self_lends_is_named() {
+        assert_eq!(named("-> &str", "'s"), "-> &'s str");
+        assert_eq!(named("-> impl Iterator<Item = char> + '_", "'s"), "-> impl Iterator<Item = char> + 's");
+        assert_eq!(named("-> Box<dyn Fn(&str) -> &str>", "'s"), "-> Box<dyn Fn(&str) -> &str>");
+        assert_eq!(named("-> &'a str", "'s"), "-> &'a str");
+        assert_eq!(named("-> (Box<dyn FnMut(&u8) -> &u8 + '_>, &str)", "'s"), "-> (Box<dyn FnMut(&u8) -> &u8 + 's>, &'s str)");
+    }
+
+    const BROKEN: &str = "fn longest(x: &str, y: &str) -> &str {\n    if x.len() > y.len() { x } else { y }\n}";
+    const SOLVED: &str = "fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {\n    if x.len() > y.len() { x } else { y }\n}";
+
+    #[test]
+    fn a_stub_is_no_fix() {
+        assert!(matches!(grade(BROKEN, SOLVED, "fn longest() {}", "2021").unwrap(), Fix::Differs(_)));
+        let other = "fn longest(x: &'static str, _: &'static str) -> &'static str { x }";
+        assert!(matches!(grade(BROKEN, SOLVED, other, "2021").unwrap(), Fix::Differs(_)));
+        assert!(matches!(grade(BROKEN, SOLVED, SOLVED, "2021").unwrap(), Fix::Passes));
+    }
+
+    #[test]
+    fn a_fix_can_be_written_otherwise() {
+        let broken = "struct Excerpt<'a> {\n    part: &'a str,\n}\n\nimpl Excerpt {\n    fn part(&self) -> &str {\n        self.part\n    }\n}";
+        let solved = broken.replace("impl Excerpt", "impl<'a> Excerpt<'a>");
+        let answer = broken.replace("impl Excerpt", "impl Excerpt<'_>");
+        assert!(matches!(grade(broken, &solved, &answer, "2021").unwrap(), Fix::Passes));
+        let renamed = SOLVED.replace("'a", "'long");
+        assert!(matches!(grade(BROKEN, SOLVED, &renamed, "2021").unwrap(), Fix::Passes));
+    }
+}
@@ -0,0 +1,75 @@
+// QUIZ: DID YOU MEAN ------------------------------------------------------
+
+/*
+ * A name that's nearly right, for quiz show and for commands:
+ *
+ *     quiz show lifetmes     did you mean generics, "LIFETIME
+ *                            ANNOTATIONS"? [Y/n]
+ *     quiz show hashmp       did you mean collections, "Accessing values
+ *                            in a hash map"? [Y/n]
+ *     quiz stauts            unknown command `stauts`: did you mean
+ *                            `status`?
+ *
+ * A topic is matched by its id and title, and by the headings in it
+ * (notes::headings), so a word that only names a section finds the
+ * topic it's in, shown from that section. Yes shows it; on anything
+ * but a terminal it's an error naming the command to type instead. A
+ * command is never run from a guess.
+ *
+ * How near is near: the edit distance (optimal string alignment, a
+ * swap of two letters is one edit) from the query to the name, or to
+ * any run of up to three of its words written together ("hash map" is
+ * "hashmap"), ignoring case, spaces, - and _. A name that contains the
+ * query is nearest of all. Up to one edit is allowed in a query of four
+ * letters, two up to eight, three beyond.
+ */
+
+// edits from `a` to `b`: insert, delete, replace, or swap neighbours
+pub fn distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    d[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = (a[i - 1] != b[j - 1]) as usize;
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+fn squashed(text: &str) -> String {
+    text.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+// how far `query` is from `name`; None when too far to be a typo
+pub fn closeness(query: &str, name: &str) -> Option<usize> {
+    let query = squashed(query);
+    if query.is_empty() {
+        return None;
+    }
+    if squashed(name).contains(&query) {
+        return Some(0);
+    }
+    let words: Vec<String> = name.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).map(squashed).collect();
+    let runs = (0..words.len()).flat_map(|i| (i + 1..=words.len().min(i + 3)).map(move |j| (i, j)));
+    let nearest = runs.map(|(i, j)| distance(&query, &words[i..j].concat())).min()?;
+    let allowed = match query.chars().count() {
+        0..=4 => 1,
+        5..=8 => 2,
+        _ => 3,
+    };
+    (nearest <= allowed).then_some(nearest)
+}
+
+// the nearest of `names`, the first of those equally near
+pub fn nearest<'a>(query: &str, names: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let scored = names.into_iter().filter_map(|name| closeness(query, name).map(|d| (d, name)));
+    scored.min_by_key(|(d, _)| *d).map(|(_, name)| name)
+}
@@ -0,0 +1,253 @@
+// QUIZ: THE COMPILE HARNESS ----------------------------------------------
+
+/*
+ * Every question in the bank is backed by rustc. This module compiles
+ * one snippet and reports what happened, reading rustc's JSON
+ * diagnostics (--error-format=json) instead of scraping the human
+ * output: each error comes with its code (E0106) and message as fields.
+ *
+ * Snippets are compiled as library crates, so they need no main(), and
+ * with warnings allowed, so unused variables in a two-line example don't
+ * clutter the report. Deny-by-default lints still fail the build, as
+ * they would for a reader. Output questions are the exception: run()
+ * builds a program and executes it.
+ *
+ * What rustc said, and the program, are kept between runs by a
+ * fingerprint of the source (builds.rs): the same snippet compiled a
+ * second time isn't compiled, and a program is only run again.
+ *
+ * rustc and the programs get TIME_LIMIT each, then they're killed: code
+ * from a student (classroom.rs) can be made to compile for ever, with
+ * a const that loops, and mustn't hold up the server.
+ *
+ * miri() runs a program under Miri instead (cargo +nightly miri run),
+ * for unsafe code: a program with undefined behaviour can print the
+ * right thing anyway, and Miri is what notices. It needs the nightly
+ * toolchain with its miri component:
+ *     rustup +nightly component add miri
+ */
+
+use crate::builds::{self, Scratch};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub code: Option<String>,          // "E0106"; lints and some borrowck errors have none
+    pub message: String,
+    pub rendered: String,              // as rustc prints it: the spans, notes and help
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Verdict {
+    pub compiles: bool,
+    pub errors: Vec<Diagnostic>,
+}
+
+impl Verdict {
+    // `expected` is an error code or a piece of the message, since
+    // errors like "lifetime may not live long enough" have no code
+    pub fn has_error(&self, expected: &str) -> bool {
+        self.errors.iter().any(|e| e.code.as_deref() == Some(expected) || e.message.contains(expected))
+    }
+
+    pub fn summary(&self) -> String {
+        match self.errors.first() {
+            None => "compiles".to_string(),
+            Some(Diagnostic { code: Some(code), message, .. }) => format!("error[{code}]: {message}"),
+            Some(Diagnostic { code: None, message, .. }) => format!("error: {message}"),
+        }
+    }
+}
+
+const TIME_LIMIT: Duration = Duration::from_secs(30);
+
+// `command` run to the end, like Command::output(), or killed with a
+// TimedOut error after TIME_LIMIT. Its pipes are drained on threads
+// meanwhile, so it can't block on a full one
+fn output(command: &mut Command) -> io::Result<Output> {
+    let mut child = command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    let (stdout, stderr) = (drain(child.stdout.take()), drain(child.stderr.take()));
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() > TIME_LIMIT {
+            child.kill()?;
+            child.wait()?;
+            let program = command.get_program().to_string_lossy();
+            return Err(io::Error::new(io::ErrorKind::TimedOut, format!("{program} took longer than {}s", TIME_LIMIT.as_secs())));
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    let joined = |reader: JoinHandle<io::Result<Vec<u8>>>| reader.join().unwrap_or_else(|_| Err(io::Error::other("a pipe reader panicked")));
+    Ok(Output { status, stdout: joined(stdout)?, stderr: joined(stderr)? })
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut bytes)?;
+        }
+        Ok(bytes)
+    })
+}
+
+// a fresh scratch directory per compile, so checks can't see each other's
+// output
+fn scratch_dir() -> io::Result<Scratch> {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let n = NEXT.fetch_add(1, Ordering::Relaxed);
+    Scratch::new(std::env::temp_dir().join(format!("quiz-{}-{n}", std::process::id())))
+}
+
+// `source` built, or found built before: the directory it's in, and
+// what rustc said
+fn build(source: &str, edition: &str, crate_type: &str) -> io::Result<(PathBuf, Verdict)> {
+    let fingerprint = builds::fingerprint(source, edition, crate_type, &rustc_version()?);
+    if let Some(built) = builds::load(&fingerprint) {
+        return Ok(built);
+    }
+    // on an error, rustc's or its time limit, the scratch goes with it
+    let scratch = builds::scratch()?;
+    let verdict = rustc(scratch.path(), source, edition, crate_type)?;
+    Ok((builds::store(scratch, &fingerprint, &verdict)?, verdict))
+}
+
+pub fn compile(source: &str, edition: &str) -> io::Result<Verdict> {
+    Ok(build(source, edition, "lib")?.1)
+}
+
+// What a program did: output questions need the snippet run, not just
+// compiled
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    CompileError(Verdict),
+    Panicked(String, String),          // the panic message, and what it printed before
+    Printed(String),
+    Undefined(Violation),              // only from miri()
+}
+
+// Miri's "error: Undefined Behavior: ..." and where it happened
+#[derive(Debug, Clone)]
+pub struct Violation {
+    pub message: String,               // "memory access failed: alloc238 has been freed, ..."
+    pub line: Option<usize>,           // in the source given to miri()
+    pub report: String,                // the whole of it, with Miri's help lines
+}
+
+// "thread 'main' panicked at src/..:\n<message>": keep the message
+fn panic_message(stderr: &str) -> String {
+    stderr.lines().skip_while(|l| !l.contains("panicked at")).nth(1).unwrap_or("crashed").to_string()
+}
+
+// compiles `source` as a program (it needs a main) and runs it
+pub fn run(source: &str, edition: &str) -> io::Result<Outcome> {
+    let (dir, verdict) = build(source, edition, "bin")?;
+    if !verdict.compiles {
+        return Ok(Outcome::CompileError(verdict));
+    }
+    let output = output(&mut Command::new(dir.join("snippet")))?;
+    let printed = String::from_utf8_lossy(&output.stdout).into_owned();
+    Ok(if output.status.success() {
+        Outcome::Printed(printed)
+    } else {
+        Outcome::Panicked(panic_message(&String::from_utf8_lossy(&output.stderr)), printed)
+    })
+}
+
+// like run(), under Miri; the snippet is compiled by rustc first (and
+// that's kept), so a compile error reads the same as it does from run().
+// Miri's own build isn't: it's in a scratch directory, gone after
+pub fn miri(source: &str, edition: &str) -> io::Result<Outcome> {
+    let verdict = build(source, edition, "bin")?.1;
+    if !verdict.compiles {
+        return Ok(Outcome::CompileError(verdict));
+    }
+    let scratch = scratch_dir()?;
+    let dir = scratch.path();
+
+    fs::create_dir_all(dir.join("src"))?;
+    let manifest = format!("[package]\nname = \"snippet\"\nversion = \"0.1.0\"\nedition = \"{edition}\"\n");
+    fs::write(dir.join("Cargo.toml"), manifest)?;
+    fs::write(dir.join("src/main.rs"), source)?;
+    let output = Command::new("cargo")
+        .args(["+nightly", "miri", "run", "--quiet"])
+        .current_dir(dir)
+        .env("RUSTFLAGS", "-A warnings")
+        .output()?;
+    drop(scratch);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("is not installed") || stderr.contains("no such command") {
+        let hint = "Miri is not installed: rustup +nightly component add miri";
+        return Err(io::Error::new(io::ErrorKind::NotFound, hint));
+    }
+    Ok(if output.status.success() {
+        Outcome::Printed(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else if let Some(violation) = violation(&stderr) {
+        Outcome::Undefined(violation)
+    } else {
+        Outcome::Panicked(panic_message(&stderr), String::from_utf8_lossy(&output.stdout).into_owned())
+    })
+}
+
+// error: Undefined Behavior: memory access failed: alloc238 has been freed, ...
+//  --> src/main.rs:5:29
+//   ...
+// note: some details are omitted, ...
+fn violation(stderr: &str) -> Option<Violation> {
+    let lines: Vec<&str> = stderr.lines().skip_while(|l| !l.starts_with("error: Undefined Behavior")).collect();
+    let message = lines.first()?.strip_prefix("error: Undefined Behavior: ")?.to_string();
+    let line = lines.iter().find_map(|l| l.trim().strip_prefix("--> src/main.rs:")?.split(':').next()?.parse().ok());
+    let report = lines.iter().take_while(|l| !l.starts_with("note: some details") && !l.starts_with("error: aborting"));
+    let report = report.copied().collect::<Vec<_>>().join("\n").trim_end().to_string();
+    Some(Violation { message, line, report })
+}
+
+fn rustc(dir: &Path, source: &str, edition: &str, crate_type: &str) -> io::Result<Verdict> {
+    let file = dir.join("snippet.rs");
+    fs::write(&file, source)?;
+
+    let output = output(
+        Command::new("rustc")
+            .args(["--edition", edition, "--crate-type", crate_type, "--crate-name", "snippet"])
+            .args(["--error-format=json", "-A", "warnings", "--out-dir"])
+            .arg(dir)
+            .arg(&file),
+    )?;
+
+    let errors = String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|d| d["level"] == "error")
+        .map(|d| Diagnostic {
+            code: d["code"]["code"].as_str().map(str::to_string),
+            message: d["message"].as_str().unwrap_or_default().to_string(),
+            rendered: d["rendered"].as_str().unwrap_or_default().to_string(),
+        })
+        // rustc's closing "aborting due to N previous errors" is not an error of its own
+        .filter(|d| !d.message.starts_with("aborting due to"))
+        .collect();
+    Ok(Verdict { compiles: output.status.success(), errors })
+}
+
+// "rustc 1.95.0 (59807616e 2026-..)", recorded with cached verdicts and
+// in every build's fingerprint; asked once a quiz
+pub fn rustc_version() -> io::Result<String> {
+    static VERSION: OnceLock<String> = OnceLock::new();
+    if let Some(version) = VERSION.get() {
+        return Ok(version.clone());
+    }
+    let output = Command::new("rustc").arg("--version").output()?;
+    Ok(VERSION.get_or_init(|| String::from_utf8_lossy(&output.stdout).trim().to_string()).clone())
+}
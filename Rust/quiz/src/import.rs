@@ -0,0 +1,402 @@
+// QUIZ: IMPORTING QUESTIONS ----------------------------------------------
+
+/*
+ * Questions written outside the repo, in a spreadsheet or by someone
+ * else's tool, come in through
+ *
+ *     quiz import FILE PACK
+ *
+ * FILE is CSV, one question per row, or TOML, [[question]] tables as in
+ * a pack file (bank.rs). Both use the pack format's field names: in CSV
+ * they're the header row, and an empty cell is a missing field.
+ *
+ *     id,kind,code,answer,explanation
+ *     sum-range,output,(1..=4).sum::<i32>(),10,"1 + 2 + 3 + 4, nothing lazy"
+ *
+ * The whole file is validated before anything is written, and every
+ * problem is reported with the row and the field it's about:
+ *
+ *     extra.csv:4: `usable`: expected true or false, got `maybe`
+ *     extra.toml, question 2 (`two-inputs`): `solved`: missing
+ *
+ * A CSV cell holding a list (choices, answers) has one item per line.
+ * For CSV the place is the line the record starts on (the header is
+ * line 1).
+ * After that each question is checked with rustc like `quiz check`
+ * does, and only if all of them pass are they appended to
+ * packs/PACK.toml. A new pack takes its title and topic from the TOML
+ * file's own title = and topic = lines, so a CSV can only go into a
+ * pack that exists.
+ */
+
+use crate::bank::{self, Question};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use toml::{Table, Value};
+
+// the fields of every kind: the first three are required
+const COMMON: [&str; 5] = ["id", "kind", "explanation", "prompt", "edition"];
+
+const KINDS: [&str; 6] = ["fix", "still-usable", "compiles", "output", "choice", "fill-in"];
+
+// (required, optional) fields per kind; kept in step with bank::Kind
+fn fields_of(kind: &str) -> Option<(&'static [&'static str], &'static [&'static str])> {
+    match kind {
+        "fix" => Some((&["broken", "solved"], &["error"])),
+        "still-usable" => Some((&["code", "variable", "usable"], &["prelude"])),
+        "compiles" => Some((&["code"], &[])),
+        "output" => Some((&["code", "answer"], &[])),
+        "choice" => Some((&["choices", "correct"], &["code"])),
+        "fill-in" => Some((&["code", "answers"], &[])),
+        _ => None,
+    }
+}
+
+// what a field holds
+#[derive(Clone, Copy)]
+enum Shape {
+    Text,
+    Flag,
+    Number,                            // from 1
+    List,                              // of strings
+}
+
+fn shape_of(field: &str) -> Shape {
+    match field {
+        "usable" => Shape::Flag,
+        "correct" => Shape::Number,
+        "choices" | "answers" => Shape::List,
+        _ => Shape::Text,
+    }
+}
+
+impl Shape {
+    fn fits(self, value: &Value) -> bool {
+        match self {
+            Shape::Text => value.is_str(),
+            Shape::Flag => value.is_bool(),
+            Shape::Number => value.as_integer().is_some_and(|n| n > 0),
+            Shape::List => value.as_array().is_some_and(|items| items.iter().all(Value::is_str)),
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Shape::Text => "a string",
+            Shape::Flag => "true or false",
+            Shape::Number => "a number from 1",
+            Shape::List => "a list of strings",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    pub place: String,                 // "extra.csv:4", "extra.toml, question 2 (`two-inputs`)"
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.field {
+            Some(field) => write!(f, "{}: `{field}`: {}", self.place, self.message),
+            None => write!(f, "{}: {}", self.place, self.message),
+        }
+    }
+}
+
+fn problem(place: &str, field: Option<&str>, message: impl Into<String>) -> Problem {
+    Problem { place: place.to_string(), field: field.map(str::to_string), message: message.into() }
+}
+
+#[derive(Debug, Default)]
+pub struct Incoming {
+    pub title: Option<String>,         // TOML only, for a new pack
+    pub topic: Option<String>,
+    pub questions: Vec<(String, Question)>, // each with its place in the file
+}
+
+// each question's fields, with its place in the file
+type Entries = Vec<(String, Table)>;
+
+// `taken`: ids already in the pack being imported into
+pub fn read(path: &Path, taken: &[&str]) -> Result<Incoming, Vec<Problem>> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+    let text = fs::read_to_string(path).map_err(|e| vec![problem(&name, None, e.to_string())])?;
+    let mut problems = Vec::new();
+    let (mut incoming, entries) = match path.extension().and_then(|e| e.to_str()) {
+        Some("csv") => (Incoming::default(), csv_entries(&name, &text, &mut problems)),
+        Some("toml") => toml_entries(&name, &text, &mut problems),
+        _ => return Err(vec![problem(&name, None, "expected a .csv or .toml file")]),
+    };
+
+    let mut seen = HashSet::new();
+    for (place, table) in entries {
+        match question(&place, table) {
+            Ok(q) if taken.contains(&q.id.as_str()) => {
+                problems.push(problem(&place, Some("id"), format!("`{}` is already in the pack", q.id)))
+            }
+            Ok(q) if !seen.insert(q.id.clone()) => {
+                problems.push(problem(&place, Some("id"), format!("`{}` appears twice in this file", q.id)))
+            }
+            Ok(q) => incoming.questions.push((place, q)),
+            Err(mut found) => problems.append(&mut found),
+        }
+    }
+    if problems.is_empty() { Ok(incoming) } else { Err(problems) }
+}
+
+// one table per row, from the non-empty cells
+fn csv_entries(name: &str, text: &str, problems: &mut Vec<Problem>) -> Entries {
+    let mut reader = csv::Reader::from_reader(text.as_bytes());
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(e) => {
+            problems.push(problem(name, None, e.to_string()));
+            return Entries::new();
+        }
+    };
+
+    let header_line = format!("{name}:1");
+    let mut columns = HashSet::new();
+    let known = |column: &str| {
+        COMMON.contains(&column)
+            || KINDS.iter().filter_map(|k| fields_of(k)).any(|(required, optional)| {
+                required.contains(&column) || optional.contains(&column)
+            })
+    };
+    for column in &headers {
+        if !known(column) {
+            problems.push(problem(&header_line, Some(column), "unknown column"));
+        } else if !columns.insert(column) {
+            problems.push(problem(&header_line, Some(column), "column appears twice"));
+        }
+    }
+    // with the columns wrong, every row would be wrong too
+    if !problems.is_empty() {
+        return Entries::new();
+    }
+
+    let mut entries = Vec::new();
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                // a row with the wrong number of cells, usually
+                let line = e.position().map_or(0, |p| p.line());
+                problems.push(problem(&format!("{name}:{line}"), None, e.to_string()));
+                continue;
+            }
+        };
+        let place = format!("{name}:{}", record.position().map_or(0, |p| p.line()));
+        let mut table = Table::new();
+        for (column, cell) in headers.iter().zip(record.iter()) {
+            // a cell that doesn't parse stays a string, for question() to
+            // report along with everything else
+            let value = match (shape_of(column), cell.trim()) {
+                (_, "") => continue,
+                (Shape::Flag, "true") => Value::Boolean(true),
+                (Shape::Flag, "false") => Value::Boolean(false),
+                (Shape::Number, n) if n.parse::<i64>().is_ok() => Value::Integer(n.parse().unwrap_or_default()),
+                (Shape::List, _) => {
+                    Value::Array(cell.lines().filter(|l| !l.trim().is_empty()).map(|l| l.trim().into()).collect())
+                }
+                _ => Value::String(cell.to_string()),
+            };
+            table.insert(column.to_string(), value);
+        }
+        entries.push((place, table));
+    }
+    entries
+}
+
+// the [[question]] tables; a top-level edition applies to each question
+// that doesn't name its own
+fn toml_entries(name: &str, text: &str, problems: &mut Vec<Problem>) -> (Incoming, Entries) {
+    // toml's own message says which line and column
+    let mut file: Table = match toml::from_str(text) {
+        Ok(file) => file,
+        Err(e) => {
+            problems.push(problem(name, None, e.to_string()));
+            return (Incoming::default(), Entries::new());
+        }
+    };
+    let string = |file: &Table, key: &str| file.get(key).and_then(Value::as_str).map(str::to_string);
+    let incoming = Incoming { title: string(&file, "title"), topic: string(&file, "topic"), questions: Vec::new() };
+    let edition = file.remove("edition");
+
+    for key in file.keys().filter(|k| !["title", "topic", "question"].contains(&k.as_str())) {
+        let message = if key == "variations" { "not imported: write the questions out" } else { "unknown key" };
+        problems.push(problem(name, Some(key), message));
+    }
+    let questions = match file.remove("question") {
+        Some(Value::Array(questions)) => questions,
+        _ => {
+            problems.push(problem(name, None, "no [[question]] tables"));
+            Vec::new()
+        }
+    };
+
+    let mut entries = Vec::new();
+    for (i, value) in questions.into_iter().enumerate() {
+        let mut place = format!("{name}, question {}", i + 1);
+        let Value::Table(mut table) = value else {
+            problems.push(problem(&place, None, "not a table"));
+            continue;
+        };
+        if let Some(id) = table.get("id").and_then(Value::as_str) {
+            place += &format!(" (`{id}`)");
+        }
+        if let Some(edition) = &edition {
+            table.entry("edition").or_insert_with(|| edition.clone());
+        }
+        entries.push((place, table));
+    }
+    (incoming, entries)
+}
+
+// the fields checked by hand first, so each problem can name its field;
+// serde's own errors only say what was wrong
+fn question(place: &str, table: Table) -> Result<Question, Vec<Problem>> {
+    let kind = match table.get("kind") {
+        Some(Value::String(kind)) => kind.clone(),
+        Some(_) => return Err(vec![problem(place, Some("kind"), "expected a string")]),
+        None => return Err(vec![problem(place, Some("kind"), "missing")]),
+    };
+    let Some((required, optional)) = fields_of(&kind) else {
+        let message = format!("unknown kind `{kind}` ({})", KINDS.join(", "));
+        return Err(vec![problem(place, Some("kind"), message)]);
+    };
+
+    let mut problems = Vec::new();
+    for field in COMMON[..3].iter().chain(required) {
+        if !table.contains_key(*field) {
+            problems.push(problem(place, Some(field), "missing"));
+        }
+    }
+    for (key, value) in &table {
+        if !COMMON.iter().chain(required).chain(optional).any(|field| field == key) {
+            problems.push(problem(place, Some(key), format!("not a field of a {kind} question")));
+        } else if !shape_of(key).fits(value) {
+            let got = value.as_str().map_or_else(|| value.to_string(), str::to_string);
+            problems.push(problem(place, Some(key), format!("expected {}, got `{got}`", shape_of(key).describe())));
+        }
+    }
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+
+    let question: Question = Value::Table(table).try_into().map_err(|e| vec![problem(place, None, e.to_string())])?;
+    let problems: Vec<Problem> =
+        bank::validate(&question).into_iter().map(|(field, message)| problem(place, Some(field), message)).collect();
+    if problems.is_empty() { Ok(question) } else { Err(problems) }
+}
+
+#[derive(Serialize)]
+struct Header<'a> {
+    title: &'a str,
+    topic: &'a str,
+}
+
+#[derive(Serialize)]
+struct Tables<'a> {
+    question: &'a [Question],
+}
+
+// Appends the questions to the pack file. With a header, the file is
+// new and starts with the pack's title and topic; otherwise what's
+// there, comments included, stays as it was.
+pub fn append(path: &Path, header: Option<(&str, &str)>, questions: &[Question]) -> Result<(), String> {
+    let mut text = match header {
+        Some((title, topic)) => toml::to_string(&Header { title, topic }).map_err(|e| e.to_string())?,
+        None => fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?.trim_end().to_string() + "\n",
+    };
+    text += "\n";
+    text += &toml::to_string(&Tables { question: questions }).map_err(|e| e.to_string())?;
+    fs::write(path, text).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `text` read as a file called `name`: the questions' ids, or the
+    // problems as they'd be printed
+    fn import(name: &str, text: &str, taken: &[&str]) -> Result<Vec<String>, Vec<String>> {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(name);
+        fs::write(&path, text).unwrap();
+        match read(&path, taken) {
+            Ok(incoming) => Ok(incoming.questions.into_iter().map(|(_, q)| q.id).collect()),
+            Err(problems) => Err(problems.iter().map(Problem::to_string).collect()),
+        }
+    }
+
+    #[test]
+    fn a_good_csv_comes_in_whole() {
+        let csv = "id,kind,code,answer,explanation\n\
+                   sum-range,output,(1..=4).sum::<i32>(),10,\"1 + 2 + 3 + 4, nothing lazy\"\n\
+                   len,output,\"[1, 2].len()\",2,two\n";
+        assert_eq!(import("extra.csv", csv, &[]), Ok(vec!["sum-range".to_string(), "len".to_string()]));
+    }
+
+    #[test]
+    fn every_problem_has_its_row_and_field() {
+        let csv = "id,kind,code,variable,usable,answer,explanation\n\
+                   fine,output,1,,,1,one\n\
+                   no-answer,output,1,,,,one\n\
+                   maybe,still-usable,let s = 1;,s,maybe,,one\n\
+                   fine,output,1,,,1,twice\n";
+        let problems = import("extra.csv", csv, &[]).unwrap_err();
+        assert_eq!(problems, [
+            "extra.csv:3: `answer`: missing",
+            "extra.csv:4: `usable`: expected true or false, got `maybe`",
+            "extra.csv:5: `id`: `fine` appears twice in this file",
+        ]);
+    }
+
+    #[test]
+    fn a_bad_header_stops_before_the_rows() {
+        let csv = "id,kind,code,code,colour,explanation\nx,compiles,1,1,red,one\n";
+        let problems = import("extra.csv", csv, &[]).unwrap_err();
+        assert_eq!(problems, ["extra.csv:1: `code`: column appears twice", "extra.csv:1: `colour`: unknown column"]);
+    }
+
+    #[test]
+    fn a_short_row_and_a_taken_id_are_problems() {
+        let csv = "id,kind,code,explanation\nx,compiles\ntaken,compiles,1,one\n";
+        let problems = import("extra.csv", csv, &["taken"]).unwrap_err();
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(problems[0].starts_with("extra.csv:2: "), "{problems:?}");
+        assert_eq!(problems[1], "extra.csv:3: `id`: `taken` is already in the pack");
+    }
+
+    #[test]
+    fn a_list_cell_has_an_item_a_line_and_rows_keep_their_lines() {
+        // the first record takes lines 2 to 4, so the second starts on 5
+        let csv = "id,kind,prompt,choices,correct,explanation\n\
+                   pick,choice,Which?,\"Clone\n\nCopy\",2,one\n\
+                   wrong,choice,Which?,\"Clone\nCopy\",3,one\n";
+        let problems = import("extra.csv", csv, &[]).unwrap_err();
+        assert_eq!(problems, ["extra.csv:5: `correct`: 3: the choices are numbered from 1"]);
+        let csv = "id,kind,prompt,choices,correct,explanation\npick,choice,Which?,\"Clone\n\nCopy\",2,one\n";
+        assert!(import("extra.csv", csv, &[]).is_ok());
+    }
+
+    #[test]
+    fn toml_problems_name_the_question() {
+        let toml = "edition = \"2018\"\n\
+                    [[question]]\nid = \"ok\"\nkind = \"compiles\"\ncode = \"1\"\nexplanation = \"one\"\n\
+                    [[question]]\nid = \"two-inputs\"\nkind = \"fix\"\nbroken = \"x\"\nexplanation = \"one\"\n\
+                    [[question]]\nid = \"odd\"\nkind = \"riddle\"\nexplanation = \"one\"\n";
+        let problems = import("extra.toml", toml, &[]).unwrap_err();
+        assert_eq!(problems, [
+            "extra.toml, question 2 (`two-inputs`): `solved`: missing",
+            "extra.toml, question 3 (`odd`): `kind`: unknown kind `riddle` (fix, still-usable, compiles, output, choice, fill-in)",
+        ]);
+    }
+}
@@ -0,0 +1,195 @@
+// QUIZ: LINKS BETWEEN TOPICS ---------------------------------------------
+
+/*
+ * A comment in the notes can point at another topic, or at a part of
+ * one:
+ *
+ *     // the rules the compiler fills them in by: [[generics#elision]]
+ *
+ * [[TOPIC]] or [[TOPIC#HEADING]], where TOPIC is a topic's id and
+ * HEADING is found the way quiz show --section finds it: the first
+ * heading with it in, in any case. So [[generics#elision]] is "Lifetime
+ * elision" in generics.rs, and stays that if the heading is reworded
+ * around the word.
+ *
+ * `quiz notes` checks every link next to the notes' other claims, so a
+ * topic renamed or a heading reworded fails there and not in front of
+ * a reader. Each output renders them its own way:
+ *
+ *     quiz show            generics, "Lifetime elision" [1], and under
+ *                          the text: [1] quiz show generics --section
+ *                          "Lifetime elision", the command to jump there
+ *     quiz export --html   <a href="generics.html#lifetime-elision">
+ *     quiz export --mdbook [generics, "Lifetime elision"](generics.md#lifetime-elision)
+ *
+ * A link that doesn't resolve is left as written.
+ */
+
+use crate::notes;
+use crate::providers::Topic;
+use crate::theme::{Role, Theme};
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::PathBuf;
+
+pub struct Target {
+    pub topic: String,
+    pub heading: Option<String>,       // None: the whole topic
+}
+
+// "Lifetime elision" -> "lifetime-elision", a heading's id in the
+// exported pages
+pub fn slug(title: &str) -> String {
+    let words = title.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty());
+    words.map(str::to_lowercase).collect::<Vec<_>>().join("-")
+}
+
+impl Target {
+    // generics, "Lifetime elision"
+    pub fn label(&self) -> String {
+        match &self.heading {
+            Some(heading) => format!("{}, \"{heading}\"", self.topic),
+            None => self.topic.clone(),
+        }
+    }
+
+    pub fn command(&self) -> String {
+        match &self.heading {
+            Some(heading) => format!("quiz show {} --section \"{heading}\"", self.topic),
+            None => format!("quiz show {}", self.topic),
+        }
+    }
+
+    // "generics.html#lifetime-elision", for `extension` "html"
+    pub fn href(&self, extension: &str) -> String {
+        match &self.heading {
+            Some(heading) => format!("{}.{extension}#{}", self.topic, slug(heading)),
+            None => format!("{}.{extension}", self.topic),
+        }
+    }
+}
+
+// the pieces of a line: text, and what's between [[ and ]] when that
+// looks like a link, a topic id then maybe #HEADING (so Rust's
+// `[[1, 2], [3, 4]]` stays text)
+enum Piece<'a> {
+    Text(&'a str),
+    Link(&'a str),
+}
+
+fn pieces(mut line: &str) -> Vec<Piece<'_>> {
+    let mut pieces = Vec::new();
+    while let Some(start) = line.find("[[") {
+        let Some(len) = line[start + 2..].find("]]") else { break };
+        let inner = &line[start + 2..start + 2 + len];
+        let topic = inner.split('#').next().unwrap_or_default();
+        let is_link = !topic.is_empty() && topic.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') && !inner.contains('[');
+        let end = if is_link { start + 2 + len + 2 } else { start + 2 };
+        if is_link {
+            pieces.push(Piece::Text(&line[..start]));
+            pieces.push(Piece::Link(inner));
+        } else {
+            pieces.push(Piece::Text(&line[..end]));
+        }
+        line = &line[end..];
+    }
+    pieces.push(Piece::Text(line));
+    pieces
+}
+
+// every link in `text`: its line number, and what's between the brackets
+pub fn links(text: &str) -> Vec<(usize, &str)> {
+    let mut links = Vec::new();
+    for (n, line) in (1..).zip(text.lines()) {
+        for piece in pieces(line) {
+            if let Piece::Link(link) = piece {
+                links.push((n, link));
+            }
+        }
+    }
+    links
+}
+
+pub fn resolve(link: &str, topics: &[Topic]) -> Result<Target, String> {
+    let (id, query) = match link.split_once('#') {
+        Some((id, query)) => (id, Some(query)),
+        None => (link, None),
+    };
+    let topic = topics.iter().find(|t| t.id == id).ok_or_else(|| format!("no topic `{id}`"))?;
+    let Some(query) = query else { return Ok(Target { topic: topic.id.clone(), heading: None }) };
+    let headings = notes::headings(&topic.text);
+    let lower = query.to_lowercase();
+    match headings.iter().find(|h| h.to_lowercase().contains(&lower)) {
+        Some(heading) => Ok(Target { topic: topic.id.clone(), heading: Some(heading.to_string()) }),
+        None => Err(format!("no heading in {id} has `{query}` in it")),
+    }
+}
+
+// `text` for one output: what isn't a link (or is one that doesn't
+// resolve) through `text_as`, escaped for it, and the links through
+// `link_as`
+pub fn replace(text: &str, topics: &[Topic], text_as: impl Fn(&str) -> String, mut link_as: impl FnMut(&Target) -> String) -> String {
+    let mut out = String::new();
+    for line in text.lines() {
+        for piece in pieces(line) {
+            match piece {
+                Piece::Text(text) => out += &text_as(text),
+                Piece::Link(link) => match resolve(link, topics) {
+                    Ok(target) => out += &link_as(&target),
+                    Err(_) => out += &text_as(&format!("[[{link}]]")),
+                },
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// for quiz show: the links in `lines` as labels numbered from 1, and,
+// to go under the text, the command each one jumps to
+pub fn terminal(text: &str, lines: Range<usize>, topics: &[Topic], theme: &Theme) -> (String, String) {
+    let mut commands = Vec::new();
+    let mut out = String::new();
+    for (n, line) in (1..).zip(text.lines()) {
+        if !lines.contains(&n) {
+            out += line;
+            out.push('\n');
+            continue;
+        }
+        out += &replace(line, topics, str::to_string, |target| {
+            commands.push(target.command());
+            format!("{} [{}]", target.label(), commands.len())
+        });
+    }
+    if commands.is_empty() {
+        return (out, String::new());
+    }
+    let mut footer = format!("\n{}\n", theme.paint(Role::Dim, "Links:"));
+    for (i, command) in commands.iter().enumerate() {
+        footer += &format!("  [{}] {command}\n", i + 1);
+    }
+    (out, footer)
+}
+
+// quiz notes: false if any link doesn't resolve
+pub fn check(files: &[PathBuf], topics: &[Topic], theme: &Theme) -> io::Result<bool> {
+    let mut failures = 0;
+    for file in files {
+        let text = fs::read_to_string(file).map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", file.display())))?;
+        let name = file.file_name().unwrap_or_default().to_string_lossy();
+        for (line, link) in links(&text) {
+            match resolve(link, topics) {
+                Ok(_) => println!("{}    {name}:{line}: [[{link}]]", theme.paint(Role::Right, "ok")),
+                Err(why) => {
+                    println!("{}  {name}:{line}: [[{link}]]: {why}", theme.paint(Role::Wrong, "FAIL"));
+                    failures += 1;
+                }
+            }
+        }
+    }
+    if failures > 0 {
+        println!("\n{failures} broken");
+    }
+    Ok(failures == 0)
+}
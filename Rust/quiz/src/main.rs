@@ -0,0 +1,1697 @@
+// QUIZ: PRACTICE QUESTIONS CHECKED BY THE COMPILER -----------------------
+
+/*
+ * A bank of practice questions for the notes, where every answer is
+ * backed by rustc. A question that says "this fails with E0106" is
+ * compiled to prove it, so the answer key can't rot when the compiler
+ * changes.
+ *
+ *     main.rs      the command line (this file)
+ *     bank.rs      question packs: the TOML format and loading
+ *     harness.rs   compiles snippets and reads rustc's verdict
+ *     builds.rs    what it compiled, kept by fingerprint, capped
+ *     cache.rs     verdicts for "will it compile?", cached by hash
+ *     import.rs    questions from CSV or TOML files, validated
+ *     exam.rs      timed exams, with a report to share
+ *     classroom.rs a server for assignments and submissions
+ *     export.rs    the notes as HTML pages, an mdBook, or man pages
+ *     cheatsheet.rs a topic's code and a line on each point, to go over
+ *     api.rs       the content, and progress, as a JSON API
+ *     config.rs    ~/.config/langscape/config.toml, and the flags over it
+ *     progress.rs  the answer history, mastery per topic, and streaks
+ *     profiles.rs  a history per learner, for a shared machine
+ *     remind.rs    a daily reminder to practise, on a timer or cron
+ *     report.rs    a stamped report of mastery, projects and exams
+ *     providers.rs where packs and topics come from, builtin or not
+ *     embedded.rs  the notes built into the binary (with build.rs)
+ *     parsed.rs    a topic's sections and std items, cached by hash
+ *     packages.rs  packs packaged to share, and installing them
+ *     sync.rs      updates to those from a remote, merged with your edits
+ *     theme.rs     colours for code, diffs and feedback, in three themes
+ *     fuzzy.rs     "did you mean" for topics and commands
+ *     similar.rs   related topics and search, by tf-idf
+ *     links.rs     [[topic#heading]] links between the notes
+ *     apis.rs      the std items each topic uses, linked to the docs
+ *     concordance.rs  every keyword and concept, and where it is
+ *     fixes.rs     a fix, graded against the solution's signatures
+ *     solutions.rs fix solutions graded from a file, kept in git
+ *     adaptive.rs  practice that picks the next question from it
+ *     picker.rs    plain quiz: a topic or a pack, picked as you type
+ *     path.rs      the order to learn the topics in, and where to resume
+ *     tips.rs      a snippet from a finished topic, for the login message
+ *     notes.rs     runs the notes' snippets, to check what they print,
+ *                  and under Miri when they're unsafe; shows
+ *                  rustc's errors under the ones that don't compile
+ *     snapshots.rs rustc's messages for those, kept to diff
+ *     diff.rs      the line diff, shared with diagrams/migrate.rs
+ *     packs/       the questions, and verdicts.json
+ *     snapshots/   the messages, one .snap per snippet
+ *
+ * A member of the workspace at the top of the repository, with the
+ * notes' own crate, so `cargo test --workspace` there tests this too.
+ * Its dependencies are in Rust/quiz/Cargo.toml, next to build.rs and
+ * packs/; in Rust/quiz:
+ *
+ * cargo run                           on a terminal, pick a topic to read
+ *                                     or a pack to practise (picker.rs)
+ * cargo run -- list                   topics and packs, by provider
+ * cargo run -- next                   the next part to read, or questions to
+ *                                     answer, on the path (path.rs)
+ * cargo run -- random                 a tip from a topic finished, for the
+ *                                     login message (tips.rs)
+ * cargo run -- check [PACK..]         verify every answer with rustc
+ * cargo run -- practice PACK          go through a pack's questions
+ * cargo run -- adapt [--topics T,..] [--count N] [--strategy S]
+ *                                     practice what you get wrong most
+ * cargo run -- import FILE PACK       add questions from a .csv or .toml
+ * cargo run -- pack install PATH|URL [--force]
+ *                                     install a packaged pack (packages.rs)
+ * cargo run -- pack remove NAME
+ * cargo run -- update [--remote URL]  new and newer packages (sync.rs)
+ * cargo run -- exam --topics ownership,traits --minutes 30
+ *                                     timed, no hints, report at the end
+ * cargo run -- notes [FILE..]         check the notes' `// prints:` lines,
+ *                                     and their [[links]] (links.rs)
+ * cargo run -- miri [SNIPPET]         run a snippet under Miri (notes.rs)
+ * cargo run -- run TOPIC [--block N | --name NAME]
+ *                                     run one of a topic's snippets;
+ *                                     NAME can be the end or any part of
+ *                                     one, if only one has it; without
+ *                                     N or NAME, list them
+ * cargo run -- show FILE|TOPIC [--rustc] [--section HEADING]
+ *                                     a notes file (or a part of it), with
+ *                                     rustc's errors under its
+ *                                     `// compile error:` snippets, and
+ *                                     the topics related to it
+ * cargo run -- cheatsheet FILE|TOPIC  its code, and the first sentence of
+ *                                     each point (cheatsheet.rs)
+ * cargo run -- search WORD..          the sections that say it, or the
+ *                                     nearest (similar.rs)
+ * cargo run -- export --html DIR | --mdbook DIR | --man DIR
+ *                                     the notes as pages, or man pages
+ *                                     (export.rs)
+ * cargo run -- apis [TOPIC] [--json]  the std items a topic uses, with
+ *                                     links to their docs (apis.rs)
+ * cargo run -- where-is [PHRASE]      the topics and lines that say it;
+ *                                     without one, the concordance
+ *                                     (concordance.rs)
+ * cargo run -- cache [clean]          the builds, parsed topics and search
+ *                                     index kept, or none (builds.rs)
+ * cargo run -- snapshots [FILE..] [--accept|--reject]
+ *                                     diff those errors against snapshots/
+ * cargo run -- verify PACK/QUESTION [FILE] [--learner NAME]
+ *                                     grade a fix (no FILE: in the editor)
+ * cargo run -- history PACK/QUESTION [--learner NAME]
+ *                                     how the solution evolved (solutions.rs)
+ * cargo run -- status [--json]        the streak, and mastery per topic
+ *                                     (progress.rs)
+ * cargo run -- profiles               the learners here (profiles.rs)
+ * cargo run -- remind [install [--at HH:MM] [--cron] | remove]
+ *                                     a nudge to practise, daily (remind.rs)
+ * cargo run -- report [--name NAME] [--exam FILE.json].. [--project DIR]..
+ *                     [--out FILE] [--pdf]
+ *                                     a report to share (report.rs), and
+ * cargo run -- report verify FILE.md  its stamp checked, with the profile's
+ *                                     key
+ * cargo run -- serve                  the classroom server (classroom.rs)
+ * cargo run -- serve --api [--progress]
+ *                                     topics, packs and progress as JSON
+ *                                     (api.rs)
+ * cargo run -- publish ID --title T --topics ownership,..
+ * cargo run -- submit ID --name NAME
+ *
+ * `check` needs rustc on the PATH and exits non-zero if any question is
+ * wrong, so it can run in CI next to the notes.
+ *
+ * Any command also takes --edition, --editor, --pager, --no-pager,
+ * --picker, --theme, --plain and --profile, over the defaults in the config file
+ * (config.rs).
+ */
+
+mod adaptive;
+mod api;
+mod apis;
+mod bank;
+mod builds;
+mod cache;
+mod cheatsheet;
+mod classroom;
+mod concordance;
+mod config;
+mod diff;
+mod embedded;
+mod exam;
+mod export;
+mod fixes;
+mod fuzzy;
+mod harness;
+mod import;
+mod links;
+mod notes;
+mod packages;
+mod parsed;
+mod path;
+mod picker;
+mod profiles;
+mod progress;
+mod providers;
+mod remind;
+mod report;
+mod similar;
+mod snapshots;
+mod solutions;
+mod split;
+mod sync;
+mod theme;
+mod tips;
+
+use bank::{Kind, Pack, Question};
+use cache::{Cached, Verdicts};
+use config::Config;
+use profiles::Profile;
+use providers::{Registry, Topic};
+use rayon::prelude::*;
+use similar::Section;
+use theme::{Role, Theme};
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+fn packs_dir() -> PathBuf {
+    std::env::var_os("QUIZ_PACKS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("packs"))
+}
+
+// CHECKING THE ANSWER KEY ------------------------------------------------
+
+// None if the question's claims hold, otherwise what went wrong
+fn check_question(pack: &Pack, question: &Question, verdicts: &Verdicts, rustc: &str) -> io::Result<Option<String>> {
+    let edition = pack.edition_of(question);
+    match &question.kind {
+        Kind::Fix { broken, solved, error } => {
+            let before = harness::compile(broken, edition)?;
+            if before.compiles {
+                return Ok(Some("broken form compiles".to_string()));
+            }
+            if let Some(expected) = error.as_deref().filter(|e| !before.has_error(e)) {
+                return Ok(Some(format!("expected {expected}, got {}", before.summary())));
+            }
+            let after = harness::compile(solved, edition)?;
+            if !after.compiles {
+                return Ok(Some(format!("solved form fails: {}", after.summary())));
+            }
+            // fixes are graded with these uses after them (fixes.rs), so
+            // the solution has to pass them itself
+            let used = harness::compile(&fixes::with_uses(solved, solved), edition)?;
+            if !used.compiles {
+                return Ok(Some(format!("solved form fails its own uses: {}", used.summary())));
+            }
+            Ok(None)
+        }
+        Kind::StillUsable { prelude, code, variable, usable } => {
+            // the snippet has to compile as shown, or the question is
+            // about a different error than it thinks
+            let plain = harness::compile(&usable_source(prelude, code, None), edition)?;
+            if !plain.compiles {
+                return Ok(Some(format!("snippet fails on its own: {}", plain.summary())));
+            }
+            let probed = harness::compile(&usable_source(prelude, code, Some(variable)), edition)?;
+            if probed.compiles != *usable {
+                let actual = if probed.compiles { "usable" } else { "not usable" };
+                return Ok(Some(format!("answer key says usable = {usable}, rustc says {actual}")));
+            }
+            Ok(None)
+        }
+        Kind::Compiles { code } => {
+            // nothing to be wrong about, except that the verdict the
+            // explanation was written against has changed
+            let now = harness::compile(code, edition)?;
+            let key = cache::key(edition, code);
+            let before = verdicts.get(&key);
+            verdicts.insert(key, Cached { compiles: now.compiles, summary: now.summary(), rustc: rustc.to_string() });
+            match before {
+                Some(before) if before.compiles != now.compiles => Ok(Some(format!(
+                    "verdict changed since {}: was `{}`, now `{}` (check the explanation; the cache is updated)",
+                    before.rustc,
+                    before.summary,
+                    now.summary()
+                ))),
+                _ => Ok(None),
+            }
+        }
+        Kind::Output { code, answer } => match harness::run(&output_source(code), edition)? {
+            harness::Outcome::Printed(printed) if printed.trim() == answer.trim() => Ok(None),
+            harness::Outcome::Printed(printed) => Ok(Some(format!("answer key says {}, it prints {printed}", answer.trim()))),
+            harness::Outcome::Panicked(message, _) => Ok(Some(format!("panics: {message}"))),
+            harness::Outcome::CompileError(verdict) => Ok(Some(format!("does not compile: {}", verdict.summary()))),
+            harness::Outcome::Undefined(v) => Ok(Some(format!("undefined behaviour: {}", v.message))),
+        },
+        // without a blank there's nothing to compile: the key is only as
+        // good as its author
+        Kind::Choice { code, .. } if !code.contains(bank::BLANK) => Ok(None),
+        Kind::Choice { code, choices, correct } => {
+            for (n, choice) in (1..).zip(choices) {
+                let verdict = harness::compile(&code.replace(bank::BLANK, choice), edition)?;
+                if n == *correct && !verdict.compiles {
+                    return Ok(Some(format!("the correct choice ({n}) fails: {}", verdict.summary())));
+                }
+                if n != *correct && verdict.compiles {
+                    return Ok(Some(format!("choice {n} compiles too")));
+                }
+            }
+            Ok(None)
+        }
+        Kind::FillIn { code, answers } => {
+            // a blank that can be left empty isn't asking anything
+            if harness::compile(&code.replace(bank::BLANK, ""), edition)?.compiles {
+                return Ok(Some("compiles with the blank left empty".to_string()));
+            }
+            for answer in answers {
+                let verdict = harness::compile(&code.replace(bank::BLANK, answer), edition)?;
+                if !verdict.compiles {
+                    return Ok(Some(format!("`{answer}` fails: {}", verdict.summary())));
+                }
+            }
+            Ok(None)
+        }
+    }
+}
+
+// the block becomes main(), which prints its value
+fn output_source(code: &str) -> String {
+    format!("fn main() {{\n    let value = {{\n{code}\n    }};\n    print!(\"{{value:?}}\");\n}}\n")
+}
+
+// typed answers compared without whitespace, so "[1,2]" matches
+// "[1, 2]" and "T:Clone" matches "T: Clone"
+fn same_answer(a: &str, b: &str) -> bool {
+    a.split_whitespace().collect::<String>() == b.split_whitespace().collect::<String>()
+}
+
+// from the cache, or compiled (and cached) on a miss
+fn verdict_for(verdicts: &Verdicts, edition: &str, code: &str) -> io::Result<Cached> {
+    let key = cache::key(edition, code);
+    if let Some(cached) = verdicts.get(&key) {
+        return Ok(cached);
+    }
+    let verdict = harness::compile(code, edition)?;
+    let cached = Cached { compiles: verdict.compiles, summary: verdict.summary(), rustc: harness::rustc_version()? };
+    verdicts.insert(key, cached.clone());
+    Ok(cached)
+}
+
+// the code in a function, optionally followed by a use of the variable
+fn usable_source(prelude: &str, code: &str, probe: Option<&str>) -> String {
+    let probe = probe.map(|v| format!("    let _probe = &{v};\n")).unwrap_or_default();
+    format!("{prelude}\nfn quiz() {{\n{code}\n{probe}}}\n")
+}
+
+// `all`: every pack was checked, so cached verdicts for snippets that
+// no longer exist can go. The questions are checked at once, as many as
+// there are jobs (config.rs), and reported in the packs' order
+fn check(packs: &[&Pack], verdicts: &mut Verdicts, all: bool, theme: &Theme) -> io::Result<bool> {
+    let rustc = harness::rustc_version()?;
+    let questions: Vec<(&Pack, &Question)> = packs.iter().flat_map(|pack| pack.questions.iter().map(move |q| (*pack, q))).collect();
+    let shared: &Verdicts = verdicts;
+    let problems: Vec<io::Result<Option<String>>> =
+        questions.par_iter().map(|(pack, question)| check_question(pack, question, shared, &rustc)).collect();
+    let mut failures = 0;
+    for ((pack, question), problem) in questions.iter().zip(problems) {
+        let problem = problem?;
+        match &problem {
+            None => println!("{}    {}/{}", theme.paint(Role::Right, "ok"), pack.id, question.id),
+            Some(why) => println!("{}  {}/{}: {why}", theme.paint(Role::Wrong, "FAIL"), pack.id, question.id),
+        }
+        failures += problem.is_some() as usize;
+    }
+    if all {
+        let live: Vec<String> = packs
+            .iter()
+            .flat_map(|pack| pack.questions.iter().map(move |q| (pack, q)))
+            .filter_map(|(pack, q)| match &q.kind {
+                Kind::Compiles { code } => Some(cache::key(pack.edition_of(q), code)),
+                _ => None,
+            })
+            .collect();
+        verdicts.retain(&live);
+    }
+    verdicts.save()?;
+
+    let total: usize = packs.iter().map(|p| p.questions.len()).sum();
+    println!("\n{} of {total} questions verified", total - failures);
+    Ok(failures == 0)
+}
+
+// PRACTICE ---------------------------------------------------------------
+
+// code shown with a four-space margin, whatever its indentation in the
+// pack
+fn indent(code: &str) -> String {
+    let margin = code.lines().filter(|l| !l.trim().is_empty()).map(|l| l.len() - l.trim_start().len()).min().unwrap_or(0);
+    code.trim_matches('\n').trim_end().lines().map(|l| format!("    {}\n", l.get(margin..).unwrap_or("").trim_end())).collect()
+}
+
+// reads one trimmed line; None at end of input
+fn ask(input: &mut impl BufRead, prompt: &str) -> Option<String> {
+    print!("{prompt}");
+    io::stdout().flush().ok();
+    let mut line = String::new();
+    match input.read_line(&mut line) {
+        Ok(n) if n > 0 => Some(line.trim().to_string()),
+        _ => None,
+    }
+}
+
+fn yes(answer: &str) -> bool {
+    answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes")
+}
+
+// the question's code, and its choices if it has any
+fn present(question: &Question, theme: &Theme) {
+    match &question.kind {
+        Kind::Choice { code, choices, .. } => {
+            if !code.trim().is_empty() {
+                println!("{}", theme.code(&indent(code)));
+            }
+            for (n, choice) in (1..).zip(choices) {
+                println!("  {n}) {}", theme.code(choice.trim()));
+            }
+        }
+        kind => print!("{}", theme.code(&indent(kind.code()))),
+    }
+}
+
+// what the reader is asked to type
+fn answer_prompt(kind: &Kind) -> String {
+    match kind {
+        Kind::Fix { .. } => "\n(work it out, then press Enter) ".to_string(),
+        Kind::StillUsable { .. } | Kind::Compiles { .. } => "\n[y/n] ".to_string(),
+        Kind::FillIn { .. } => format!("\n{} = ", bank::BLANK),
+        Kind::Output { .. } | Kind::Choice { .. } => "\n> ".to_string(),
+    }
+}
+
+struct Graded {
+    correct: bool,
+    key: String,                       // the right answer, as shown afterwards
+}
+
+// `answer` as typed. A fix can't be graded from what's typed: the
+// answer there is the reader's own "did you get it?"
+fn grade(pack: &Pack, question: &Question, answer: &str, verdicts: &Verdicts) -> io::Result<Graded> {
+    let edition = pack.edition_of(question);
+    let graded = match &question.kind {
+        Kind::Fix { solved, .. } => Graded { correct: yes(answer), key: solved.trim().to_string() },
+        Kind::StillUsable { usable, .. } => {
+            let key = if *usable { "usable" } else { "not usable" };
+            Graded { correct: yes(answer) == *usable, key: key.to_string() }
+        }
+        Kind::Compiles { code } => {
+            let verdict = verdict_for(verdicts, edition, code)?;
+            Graded { correct: yes(answer) == verdict.compiles, key: verdict.summary }
+        }
+        Kind::Output { answer: expected, .. } => {
+            Graded { correct: same_answer(answer, expected), key: expected.trim().to_string() }
+        }
+        Kind::Choice { choices, correct, .. } => Graded {
+            correct: answer.parse() == Ok(*correct),
+            key: format!("{correct}) {}", choices[correct - 1].trim()),
+        },
+        Kind::FillIn { code, answers } => {
+            let expected = answers[0].trim().to_string();
+            if answers.iter().any(|a| same_answer(a, answer)) {
+                Graded { correct: true, key: expected }
+            } else if fills_in_only(answer) && harness::compile(&code.replace(bank::BLANK, answer), edition)?.compiles {
+                // not in the list, but rustc takes it: Debug + Clone is as
+                // good as Clone + Debug
+                Graded { correct: true, key: format!("{expected} (yours compiles too)") }
+            } else {
+                Graded { correct: false, key: expected }
+            }
+        }
+    };
+    Ok(graded)
+}
+
+// An answer that could only fill in the blank: no braces, statements,
+// comments or strings to close it early and add items of its own after
+fn fills_in_only(answer: &str) -> bool {
+    let closes = ["{", "}", ";", "//", "/*", "\""];
+    !answer.is_empty() && !closes.iter().any(|c| answer.contains(c)) && fixes::items(answer).is_empty()
+}
+
+// One question, with the answer and explanation after it; the answer
+// goes into the progress history. None when input ends or rustc can't
+// be run.
+fn practice_one(
+    pack: &Pack,
+    question: &Question,
+    input: &mut impl BufRead,
+    verdicts: &mut Verdicts,
+    profile: &Profile,
+    theme: &Theme,
+) -> Option<bool> {
+    present(question, theme);
+    let mut answer = ask(input, &answer_prompt(&question.kind))?;
+    if let Kind::Fix { solved, .. } = &question.kind {
+        // nothing to compare a free-form fix against: self-graded
+        println!("\n{}", theme.code(&indent(solved)));
+        println!("{}\n", question.explanation.trim());
+        answer = ask(input, "Did you get it? [y/n] ")?;
+    }
+    let graded = match grade(pack, question, &answer, verdicts) {
+        Ok(graded) => graded,
+        Err(e) => {
+            eprintln!("quiz: cannot run rustc: {e}");
+            return None;
+        }
+    };
+    if !matches!(question.kind, Kind::Fix { .. }) {
+        let right = if graded.correct { theme.paint(Role::Right, "Right") } else { theme.paint(Role::Wrong, "No") };
+        println!("\n{right}: {}\n\n{}\n", graded.key, question.explanation.trim());
+    }
+    if let Err(e) = progress::record(profile, &[progress::Attempt::now(&pack.id, &question.id, graded.correct)]) {
+        eprintln!("quiz: cannot record progress: {e}");
+    }
+    println!();
+    Some(graded.correct)
+}
+
+// `questions` of the pack: all of them, or for quiz next the ones not
+// answered yet
+fn practice(pack: &Pack, questions: &[&Question], verdicts: &mut Verdicts, profile: &Profile, theme: &Theme) {
+    let mut input = io::stdin().lock();
+    let mut right = 0;
+    println!("{} ({} questions)\n", pack.title, questions.len());
+
+    for (i, question) in questions.iter().enumerate() {
+        println!("[{}/{}] {}\n", i + 1, questions.len(), question.prompt.trim());
+        let Some(correct) = practice_one(pack, question, &mut input, verdicts, profile, theme) else { return };
+        right += correct as usize;
+    }
+    println!("{right} of {} right", questions.len());
+}
+
+// IMPORT -----------------------------------------------------------------
+
+// All or nothing: the file is validated, its answers are checked with
+// rustc, and only then is the pack file written
+fn import(file: &Path, pack_id: &str, packs: &[Pack], verdicts: &mut Verdicts) -> Result<bool, String> {
+    let existing = packs.iter().find(|p| p.id == pack_id);
+    let taken: Vec<&str> = existing.map(|p| p.questions.iter().map(|q| q.id.as_str()).collect()).unwrap_or_default();
+    let incoming = match import::read(file, &taken) {
+        Ok(incoming) => incoming,
+        Err(problems) => {
+            for problem in &problems {
+                println!("{problem}");
+            }
+            println!("\nnothing imported: {} problem(s)", problems.len());
+            return Ok(false);
+        }
+    };
+
+    let new_pack;
+    let pack = match (existing, &incoming.title, &incoming.topic) {
+        (Some(pack), _, _) => pack,
+        (None, Some(title), Some(topic)) => {
+            new_pack = Pack::new(pack_id, title, topic);
+            &new_pack
+        }
+        (None, _, _) => {
+            println!("`{pack_id}` is a new pack: import a TOML file that sets title and topic, or create packs/{pack_id}.toml first");
+            return Ok(false);
+        }
+    };
+
+    let rustc = harness::rustc_version().map_err(|e| format!("cannot run rustc: {e}"))?;
+    let mut failures = 0;
+    for (place, question) in &incoming.questions {
+        let problem = check_question(pack, question, verdicts, &rustc).map_err(|e| format!("cannot run rustc: {e}"))?;
+        if let Some(why) = problem {
+            println!("FAIL  {place}: {why}");
+            failures += 1;
+        }
+    }
+    if failures > 0 {
+        println!("\nnothing imported: {failures} answer(s) don't hold");
+        return Ok(false);
+    }
+
+    let path = packs_dir().join(format!("{pack_id}.toml"));
+    let header = existing.is_none().then_some((pack.title.as_str(), pack.topic.as_str()));
+    let questions: Vec<Question> = incoming.questions.into_iter().map(|(_, q)| q).collect();
+    import::append(&path, header, &questions)?;
+    verdicts.save().map_err(|e| format!("cannot save verdicts: {e}"))?;
+    println!("{} question(s) added to {}", questions.len(), path.display());
+    Ok(true)
+}
+
+// INSTALLING PACKAGES ----------------------------------------------------
+
+// All or nothing, like import: nothing is copied until the package is
+// known to fit in with what's there, and its answers hold
+fn install(package: &packages::Package, force: bool, registry: &Registry, verdicts: &mut Verdicts) -> Result<bool, String> {
+    let manifest = &package.manifest;
+    // one installed already is replaced, even if it can't be used (a
+    // package for another quiz version)
+    let there = packages::installed()?.into_iter().find(|(dir, _)| dir.file_name().is_some_and(|n| n == manifest.name.as_str()));
+    let replacing = there.is_some();
+    let installed = there.and_then(|(_, old)| old.ok());
+    match &installed {
+        Some(old) if old.version == manifest.version && !force => {
+            println!("{} {} is installed already (--force to install it again)", old.name, old.version);
+            return Ok(true);
+        }
+        Some(old) if old.version > manifest.version && !force => {
+            println!("{} {} is installed, and {} is older (--force to install it anyway)", old.name, old.version, manifest.version);
+            return Ok(false);
+        }
+        None if !replacing && registry.providers().iter().any(|p| p.name() == manifest.name) => {
+            return Err(format!("there's a content provider called `{}` already", manifest.name));
+        }
+        _ => {}
+    }
+
+    // the version being replaced doesn't count: its packs go with it
+    let mut problems = Vec::new();
+    for provider in registry.providers().iter().filter(|p| !replacing || p.name() != manifest.name) {
+        for pack in provider.packs()? {
+            if package.packs.iter().any(|p| p.id == pack.id) {
+                problems.push(format!("packs/{}.toml: {} has a pack `{}` already", pack.id, provider.name(), pack.id));
+            }
+        }
+    }
+    for pack in &package.packs {
+        let own = package.topics.iter().any(|t| t.file_stem().is_some_and(|s| s == pack.topic.as_str()));
+        if !own && registry.topic(&pack.topic)?.is_none() {
+            problems.push(format!("packs/{}.toml: no topic `{}`, here or installed", pack.id, pack.topic));
+        }
+    }
+    if !problems.is_empty() {
+        for problem in &problems {
+            println!("{problem}");
+        }
+        println!("\nnothing installed: {} problem(s)", problems.len());
+        return Ok(false);
+    }
+
+    let rustc = harness::rustc_version().map_err(|e| format!("cannot run rustc: {e}"))?;
+    let mut failures = 0;
+    for pack in &package.packs {
+        for question in &pack.questions {
+            let problem = check_question(pack, question, verdicts, &rustc).map_err(|e| format!("cannot run rustc: {e}"))?;
+            if let Some(why) = problem {
+                println!("FAIL  {}/{}: {why}", pack.id, question.id);
+                failures += 1;
+            }
+        }
+    }
+    if failures > 0 {
+        println!("\nnothing installed: {failures} answer(s) don't hold");
+        return Ok(false);
+    }
+
+    let (dir, changes) = packages::place(package).map_err(|e| format!("cannot install into {}: {e}", packages::content_dir().display()))?;
+    verdicts.save().map_err(|e| format!("cannot save verdicts: {e}"))?;
+    let questions: usize = package.packs.iter().map(|p| p.questions.len()).sum();
+    let was = installed.map(|old| format!(", was {}", old.version)).unwrap_or_default();
+    println!(
+        "installed {} {}{was}: {} pack(s), {questions} questions, {} topic(s), in {}",
+        manifest.name,
+        manifest.version,
+        package.packs.len(),
+        package.topics.len(),
+        dir.display()
+    );
+    // a fresh install adds everything; an upgrade says what it did
+    if !was.is_empty() {
+        for (file, change) in &changes {
+            println!("    {:<28} {}", file.display(), change.describe());
+        }
+    }
+    Ok(true)
+}
+
+// `quiz update`. The registry is discovered again for each package, so
+// one installed a moment ago counts when the next is checked
+fn update(remote: &str, config: &Config, verdicts: &mut Verdicts) -> Result<bool, String> {
+    let mut current = Vec::new();
+    let mut failures = 0;
+    for offer in sync::fetch(remote)? {
+        let offer = match offer {
+            Ok(offer) => offer,
+            Err(e) => {
+                println!("FAIL  {e}");
+                failures += 1;
+                continue;
+            }
+        };
+        let installed = packages::installed()?.into_iter().filter_map(|(_, m)| m.ok()).find(|m| m.name == offer.name);
+        if let Some(manifest) = installed.filter(|m| m.version >= offer.version) {
+            current.push(format!("{} {}", manifest.name, manifest.version));
+            continue;
+        }
+        let registry = providers::discover(&packs_dir(), config)?;
+        match packages::open(&offer.source).and_then(|package| install(&package, false, &registry, verdicts)) {
+            Ok(true) => {}
+            Ok(false) => failures += 1,
+            Err(e) => {
+                println!("FAIL  {}: {e}", offer.name);
+                failures += 1;
+            }
+        }
+    }
+    if !current.is_empty() {
+        println!("up to date: {}", current.join(", "));
+    }
+    Ok(failures == 0)
+}
+
+// STATUS -----------------------------------------------------------------
+
+fn ago(now: u64, then: u64) -> String {
+    match now.saturating_sub(then) / 86400 {
+        0 => "today".to_string(),
+        1 => "yesterday".to_string(),
+        days => format!("{days} days ago"),
+    }
+}
+
+// plain: the score in words, without the bar
+fn status(packs: &[Pack], profile: &Profile, json: bool, plain: bool) -> Result<(), String> {
+    let history = progress::load(profile).map_err(|e| format!("cannot read progress: {e}"))?;
+    let now = progress::now();
+    let mastery = progress::mastery(packs, &history, now);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&mastery).map_err(|e| e.to_string())?);
+        return Ok(());
+    }
+    let streak = progress::streak(&history, now, 0);
+    let plural = |n: u64| if n == 1 { "day" } else { "days" };
+    match (streak.days, streak.today) {
+        (0, _) => println!("No streak yet; longest {} {}.\n", streak.longest, plural(streak.longest)),
+        (n, true) => println!("Streak: {n} {}, today included; longest {}.\n", plural(n), streak.longest),
+        (n, false) => println!("Streak: {n} {}, and today's still to do; longest {}.\n", plural(n), streak.longest),
+    }
+    for m in &mastery {
+        let detail = match m.last {
+            None => "not started".to_string(),
+            Some(last) => format!(
+                "{} of {} answered, {:.0}% right lately, {}",
+                m.answered,
+                m.questions,
+                m.accuracy * 100.0,
+                ago(now, last)
+            ),
+        };
+        if plain {
+            println!("{}: {} out of 100, {detail}", m.topic, m.score);
+        } else {
+            println!("{:<24} {} {:>3}   {detail}", m.topic, progress::bar(m.score, 20), m.score);
+        }
+    }
+    Ok(())
+}
+
+// the default profile and the named ones, with how much each has done
+fn list_profiles() -> Result<(), String> {
+    let named = profiles::list().map_err(|e| format!("cannot list profiles: {e}"))?;
+    let now = progress::now();
+    for profile in std::iter::once(Profile::new(None)).chain(named) {
+        let history = progress::load(&profile).map_err(|e| format!("cannot read progress: {e}"))?;
+        let streak = progress::streak(&history, now, 0);
+        let last = history.iter().map(|a| a.at).max().map_or("never".to_string(), |at| ago(now, at));
+        let name = profile.name().unwrap_or("(default)");
+        let answers = if history.len() == 1 { "answer " } else { "answers" };
+        println!("{name:<16} {:>5} {answers}, last {last}, streak {}", history.len(), streak.days);
+    }
+    Ok(())
+}
+
+// SHOW -------------------------------------------------------------------
+
+// the topic nearest to `query` by its id, its title or a heading in it
+// (fuzzy.rs), with the heading when that's what was near
+fn nearest_topic(query: &str, registry: &Registry) -> Result<Option<(Topic, Option<String>)>, String> {
+    let mut nearest: Option<(usize, Topic, Option<String>)> = None;
+    for topic in registry.topics()? {
+        let names = [(topic.id.as_str(), None), (topic.title.as_str(), None)];
+        let headings = notes::headings(&topic.text).into_iter().map(|h| (h, Some(h)));
+        let found = names.into_iter().chain(headings).filter_map(|(name, heading)| Some((fuzzy::closeness(query, name)?, heading)));
+        let Some((distance, heading)) = found.min_by_key(|(d, _)| *d) else { continue };
+        if nearest.as_ref().is_none_or(|(d, ..)| distance < *d) {
+            let heading = heading.map(str::to_string);
+            nearest = Some((distance, topic, heading));
+        }
+    }
+    Ok(nearest.map(|(_, topic, heading)| (topic, heading)))
+}
+
+// what quiz show shows: a file, or a topic by name from any provider
+// (`quiz show unsafe_rust`), or one whose name is near, once confirmed.
+// The file's name, its text, and the heading to show it from
+fn to_show(file: &str, registry: &Registry) -> io::Result<(String, String, Option<String>)> {
+    let error = match (fs::read_to_string(file), registry.topic(file)) {
+        (Ok(text), _) => return Ok((Path::new(file).file_name().unwrap_or_default().to_string_lossy().into_owned(), text, None)),
+        (Err(_), Ok(Some(topic))) => return Ok((format!("{}.rs", topic.id), topic.text, None)),
+        (Err(e), _) => e,
+    };
+    let not_found = |hint: String| io::Error::new(error.kind(), format!("{file}: {error}, and no topic has that name{hint}"));
+    let Some((topic, heading)) = nearest_topic(file, registry).map_err(io::Error::other)? else { return Err(not_found(String::new())) };
+    let guess = match &heading {
+        Some(heading) => format!("{}, \"{heading}\"", topic.id),
+        None => topic.id.clone(),
+    };
+    if !(io::stdin().is_terminal() && io::stdout().is_terminal()) {
+        let command = match &heading {
+            Some(heading) => format!("quiz show {} --section \"{heading}\"", topic.id),
+            None => format!("quiz show {}", topic.id),
+        };
+        return Err(not_found(format!("; did you mean `{command}`?")));
+    }
+    match ask(&mut io::stdin().lock(), &format!("did you mean {guess}? [Y/n] ")) {
+        Some(answer) if answer.is_empty() || yes(&answer) => Ok((format!("{}.rs", topic.id), topic.text, heading)),
+        _ => Err(not_found(String::new())),
+    }
+}
+
+// a section as quiz show's --section would name it
+fn described(section: &Section) -> String {
+    match &section.heading {
+        Some(heading) => format!("{}, \"{heading}\"", section.topic),
+        None => section.topic.clone(),
+    }
+}
+
+// the line under quiz show: the other topics nearest to what it showed
+fn related(name: &str, text: &str, lines: Range<usize>, topics: &[Topic], theme: &Theme) -> String {
+    let index = similar::Index::new(topics);
+    let shown = text.lines().skip(lines.start - 1).take(lines.len()).collect::<Vec<_>>().join("\n");
+    let topic = name.strip_suffix(".rs").unwrap_or(name);
+    let related: Vec<String> = index.related(topic, &shown, 3).into_iter().map(described).collect();
+    if related.is_empty() {
+        return String::new();
+    }
+    format!("\n{} {}\n", theme.paint(Role::Dim, "Related:"), related.join("; "))
+}
+
+// quiz show: the `lines` of the file, its links numbered with where
+// they go under it (links.rs), and the topics related to it
+fn shown(name: &str, text: &str, lines: Range<usize>, rustc: bool, registry: &Registry, config: &Config) -> io::Result<String> {
+    let topics = registry.topics().map_err(io::Error::other)?;
+    let (linked, links) = links::terminal(text, lines.clone(), &topics, &config.theme());
+    let shown = notes::show(name, &linked, lines.clone(), rustc, config.edition(), &config.theme(), config.plain())?;
+    Ok(shown + &links + &related(name, text, lines, &topics, &config.theme()))
+}
+
+// the parts of the topic shown, as read (path.rs), for quiz next
+fn read(name: &str, text: &str, lines: &Range<usize>, profile: &Profile) {
+    let topic = Topic::new(name.strip_suffix(".rs").unwrap_or(name).to_string(), String::new(), text.to_string());
+    if let Err(e) = progress::record_reads(profile, &path::reads(&topic, lines, progress::now())) {
+        eprintln!("quiz: cannot record reading: {e}");
+    }
+}
+
+// the sections with every word of the query in them, or when there are
+// none, the nearest by what they say
+fn search(query: &str, registry: &Registry, theme: &Theme) -> Result<String, String> {
+    let index = similar::Index::new(&registry.topics()?);
+    let found = |section: &Section| {
+        let at = theme.paint(Role::Dim, &format!("{}:{}", section.topic, section.line));
+        match &section.heading {
+            Some(heading) => format!("{at}  {heading}\n"),
+            None => format!("{at}\n"),
+        }
+    };
+    let exact = index.exact(query);
+    if !exact.is_empty() {
+        return Ok(exact.into_iter().map(found).collect());
+    }
+    let nearest = index.nearest(query, 5);
+    if nearest.is_empty() {
+        return Err(format!("nothing in the notes says \"{query}\", or anything like it"));
+    }
+    let mut out = format!("No section has every word of \"{query}\"; the nearest by what they say:\n\n");
+    out.extend(nearest.into_iter().map(|(_, section)| found(section)));
+    Ok(out)
+}
+
+// the std items `topic` uses and where, or with none, which topics use
+// each one
+fn list_apis(topic: Option<&str>, registry: &Registry, json: bool, theme: &Theme) -> Result<(), String> {
+    let Some(id) = topic else {
+        let index = apis::index(&registry.topics()?);
+        if json {
+            println!("{}", serde_json::to_string_pretty(&index).map_err(|e| e.to_string())?);
+            return Ok(());
+        }
+        let width = index.keys().map(String::len).max().unwrap_or(0);
+        for (item, (url, topics)) in &index {
+            println!("{item:width$}  {}\n{:width$}  {}", topics.join(", "), "", theme.paint(Role::Dim, url));
+        }
+        return Ok(());
+    };
+    let topic = registry.topic(id)?.ok_or_else(|| format!("no topic `{id}`"))?;
+    let used = &topic.parsed().apis;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&used).map_err(|e| e.to_string())?);
+        return Ok(());
+    }
+    let width = used.iter().map(|u| u.item.len()).max().unwrap_or(0);
+    for u in used {
+        let lines: Vec<String> = u.lines.iter().map(usize::to_string).collect();
+        println!("{:width$}  {}\n{:width$}  {}", u.item, lines.join(", "), "", theme.paint(Role::Dim, &u.url));
+    }
+    Ok(())
+}
+
+// quiz run: one snippet of a topic, by number or name, run, printing
+// what it printed and how it failed; with neither, the list of them.
+// False if it didn't run to the end
+fn run_block(id: &str, number: Option<&str>, name: Option<&str>, registry: &Registry, config: &Config) -> Result<bool, String> {
+    let topic = registry.topic(id)?.ok_or_else(|| format!("no topic `{id}`"))?;
+    let blocks = notes::blocks(&topic.id, &topic.text);
+    let theme = config.theme();
+    let block = match (number, name) {
+        (Some(number), _) => {
+            let n: usize = number.parse().map_err(|_| format!("--block takes a number, not `{number}`"))?;
+            blocks.iter().find(|b| b.number == n).ok_or_else(|| format!("{id} has blocks 1 to {}", blocks.len()))?
+        }
+        (None, Some(name)) => named(&blocks, name).map_err(|e| format!("{id}: {e}"))?,
+        (None, None) => {
+            let width = blocks.iter().map(|b| b.name.len()).max().unwrap_or(0);
+            for b in &blocks {
+                let first = b.code.lines().find(|l| !l.trim_start().starts_with("//")).unwrap_or_default().trim();
+                let at = theme.paint(Role::Dim, &format!("{id}.rs:{:<4}", b.line));
+                println!("{:3}  {:width$}  {at}  {first}", b.number, b.name);
+            }
+            return Ok(true);
+        }
+    };
+    let file = format!("{}.rs", topic.id);
+    match notes::run(&file, &topic.text, block.line, config.edition()).map_err(|e| e.to_string())? {
+        harness::Outcome::Printed(printed) => {
+            print!("{printed}");
+            Ok(true)
+        }
+        harness::Outcome::Panicked(message, printed) => {
+            print!("{printed}");
+            eprintln!("{} {message}", theme.paint(Role::Wrong, "panicked:"));
+            Ok(false)
+        }
+        harness::Outcome::CompileError(verdict) => {
+            eprint!("{}", notes::rendered(&verdict, &file));
+            Ok(false)
+        }
+        harness::Outcome::Undefined(v) => {
+            eprintln!("{}\n\n{}", theme.paint(Role::Wrong, "Miri found undefined behaviour:"), v.report);
+            Ok(false)
+        }
+    }
+}
+
+// the block called `name`, or else the one whose name ends with it, or
+// else the one whose name has it anywhere: "entry_api" for
+// "adding_a_key_only_if_it_isn_t_there_the_entry_api"
+fn named<'a>(blocks: &'a [notes::Block], name: &str) -> Result<&'a notes::Block, String> {
+    let tests: [&dyn Fn(&str) -> bool; 3] = [&|b| b == name, &|b| b.ends_with(name), &|b| b.contains(name)];
+    for test in tests {
+        match blocks.iter().filter(|b| test(&b.name)).collect::<Vec<_>>()[..] {
+            [] => continue,
+            [block] => return Ok(block),
+            ref several => {
+                let names: Vec<&str> = several.iter().map(|b| b.name.as_str()).collect();
+                return Err(format!("`{name}` could be any of {}", names.join(", ")));
+            }
+        }
+    }
+    let hint = fuzzy::nearest(name, blocks.iter().map(|b| b.name.as_str())).map_or(String::new(), |n| format!("; did you mean `{n}`?"));
+    Err(format!("no block is called `{name}`{hint}"))
+}
+
+// where the notes say `phrase`, or with none, the whole concordance
+fn where_is(phrase: Option<&str>, registry: &Registry, theme: &Theme) -> Result<String, String> {
+    let topics = registry.topics()?;
+    let place = |p: &concordance::Place| {
+        let at = format!("{}.rs:{}", p.topic, concordance::span(&p.lines));
+        format!("  {:28}  {}\n", at, theme.paint(Role::Dim, p.heading.as_deref().unwrap_or_default())).trim_end().to_string() + "\n"
+    };
+    let Some(phrase) = phrase else {
+        let mut out = String::new();
+        for entry in concordance::concordance(&topics) {
+            out += &format!("{}\n", theme.paint(Role::Header, &entry.term));
+            out.extend(entry.places.iter().map(place));
+        }
+        return Ok(out);
+    };
+    let places = concordance::find(phrase, &topics);
+    if places.is_empty() {
+        let hint = match fuzzy::nearest(phrase, concordance::concepts().iter().copied()) {
+            Some(near) => format!("; did you mean \"{near}\"?"),
+            None => String::new(),
+        };
+        return Err(format!("the notes don't say \"{phrase}\"{hint}"));
+    }
+    Ok(places.iter().map(place).collect())
+}
+
+// what's kept in the cache directory, or with `clean` nothing
+fn cache(clean: bool, limit_mb: u64) -> io::Result<()> {
+    let (builds, stale, size) = builds::usage();
+    let (parsed, parsed_size) = (fs::read_dir(parsed::dir()).map_or(0, |entries| entries.count()), builds::size(&parsed::dir()));
+    let search_size = fs::metadata(similar::file()).map_or(0, |m| m.len());
+    let mb = |bytes: u64| format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0));
+    if clean {
+        let gone = |result: io::Result<()>, path: PathBuf| match result {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(io::Error::new(e.kind(), format!("{}: {e}", path.display()))),
+            _ => Ok(()),
+        };
+        gone(fs::remove_dir_all(builds::root()), builds::root())?;
+        gone(fs::remove_dir_all(parsed::dir()), parsed::dir())?;
+        gone(fs::remove_file(similar::file()), similar::file())?;
+        println!("removed {builds} builds, {parsed} parsed topics and the search index ({})", mb(size + parsed_size + search_size));
+        return Ok(());
+    }
+    println!("builds  {builds:5}  {} of {limit_mb} MB  {}", mb(size), builds::root().display());
+    if stale > 0 {
+        println!("        {stale:5}  of them left by a quiz stopped mid-build, to go first");
+    }
+    println!("parsed  {parsed:5}  {}  {}", mb(parsed_size), parsed::dir().display());
+    println!("search         {}  {}", mb(search_size), similar::file().display());
+    Ok(())
+}
+
+// THE PATH ---------------------------------------------------------------
+
+// quiz next: the next part on the path shown, or its questions practised
+fn next(packs: &[Pack], verdicts: &mut Verdicts, registry: &Registry, config: &Config) -> Result<(), String> {
+    let profile = config.profile();
+    let history = progress::load(&profile).map_err(|e| format!("cannot read progress: {e}"))?;
+    let reads = progress::reads(&profile).map_err(|e| format!("cannot read the reading log: {e}"))?;
+    match path::next(registry.topics()?, packs, &reads, &history) {
+        path::Next::Read { topic, lines } => {
+            let name = format!("{}.rs", topic.id);
+            let shown = shown(&name, &topic.text, lines.clone(), false, registry, config).map_err(|e| e.to_string())?;
+            config.page(&shown).map_err(|e| e.to_string())?;
+            read(&name, &topic.text, &lines, &profile);
+        }
+        path::Next::Practise { pack, questions } => {
+            practice(pack, &questions, verdicts, &profile, &config.theme());
+            verdicts.save().map_err(|e| format!("cannot save verdicts: {e}"))?;
+        }
+        path::Next::Done => println!("Every part read and every question answered: `quiz adapt` goes back to the weakest."),
+    }
+    Ok(())
+}
+
+// plain quiz on a terminal: a topic to read or a pack to practise,
+// picked (picker.rs)
+fn pick(packs: &[Pack], verdicts: &mut Verdicts, registry: &Registry, config: &Config) -> Result<(), String> {
+    let topics = registry.topics()?;
+    match picker::pick(&topics, packs, config).map_err(|e| format!("cannot run the picker: {e}"))? {
+        Some(picker::Choice::Read(id)) => {
+            let Some(topic) = topics.iter().find(|t| t.id == id) else { return Ok(()) };
+            let (name, lines) = (format!("{id}.rs"), 1..topic.text.lines().count() + 1);
+            let shown = shown(&name, &topic.text, lines.clone(), false, registry, config).map_err(|e| e.to_string())?;
+            config.page(&shown).map_err(|e| e.to_string())?;
+            read(&name, &topic.text, &lines, &config.profile());
+        }
+        Some(picker::Choice::Practise(id)) => {
+            let Some(pack) = packs.iter().find(|p| p.id == id) else { return Ok(()) };
+            practice(pack, &pack.questions.iter().collect::<Vec<_>>(), verdicts, &config.profile(), &config.theme());
+            verdicts.save().map_err(|e| format!("cannot save verdicts: {e}"))?;
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+// quiz random: a tip from a topic finished, for the login message
+fn random(packs: &[Pack], registry: &Registry, config: &Config) -> Result<(), String> {
+    let profile = config.profile();
+    let history = progress::load(&profile).map_err(|e| format!("cannot read progress: {e}"))?;
+    let reads = progress::reads(&profile).map_err(|e| format!("cannot read the reading log: {e}"))?;
+    let finished = path::finished(registry.topics()?, packs, &reads, &history);
+    let Some((topic, tip)) = tips::random(&finished, packs, &reads, &history, progress::now()) else {
+        println!("No topic finished yet, so no tip: `quiz next` goes on with the first.");
+        return Ok(());
+    };
+    let theme = config.theme();
+    let at = theme.paint(Role::Dim, &format!("{}.rs:{}", topic.id, tip.line));
+    println!("{at}  {}
+
+{}", tip.heading.as_deref().unwrap_or(&topic.title), theme.code(&tip.text).trim_end());
+    Ok(())
+}
+
+// MAIN -------------------------------------------------------------------
+
+// for "did you mean"
+const COMMANDS: [&str; 30] = [
+    "list", "next", "random", "check", "practice", "adapt", "import", "pack", "update", "exam", "notes", "miri", "run", "show",
+    "cheatsheet", "search", "export", "apis", "where-is", "cache", "snapshots", "verify", "history", "status", "profiles", "remind",
+    "report", "serve", "publish", "submit",
+];
+
+// the commands that practise, check or count the questions: only these
+// load the packs (every provider's, parsed and validated) and the
+// verdicts, so quiz show, search and the rest start without them
+const BANK: [&str; 14] =
+    ["next", "random", "check", "practice", "adapt", "import", "pack", "update", "exam", "verify", "status", "remind", "report", "serve"];
+
+// the value after `--name`
+fn flag<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    let i = args.iter().position(|a| a == name)?;
+    args.get(i + 1).map(String::as_str)
+}
+
+// every value of a flag given more than once: `--exam a.json --exam b.json`
+fn flags<'a>(args: &'a [String], name: &str) -> Vec<&'a str> {
+    args.windows(2).filter(|w| w[0] == name).map(|w| w[1].as_str()).collect()
+}
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let config = match Config::load(&mut args) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("quiz: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // the threads quiz check and quiz notes compile on
+    rayon::ThreadPoolBuilder::new().num_threads(config.jobs()).build_global().ok();
+    builds::limit(config.cache_mb() * 1024 * 1024);
+    let registry = match providers::discover(&packs_dir(), &config) {
+        Ok(registry) => registry,
+        Err(e) => {
+            eprintln!("quiz: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // none for the commands that don't use them; plain quiz picks a pack
+    // to practise, on a terminal
+    let picking = args.is_empty() && io::stdin().is_terminal() && io::stdout().is_terminal();
+    let bank = picking || args.first().is_some_and(|command| BANK.contains(&command.as_str()));
+    let loaded = if bank {
+        registry.packs().and_then(|packs| Ok((packs, Verdicts::load(&packs_dir().join("verdicts.json"))?)))
+    } else {
+        Ok(Default::default())
+    };
+    let (packs, mut verdicts) = match loaded {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("quiz: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let find = |id: &str| packs.iter().find(|p| p.id == id);
+
+    match args.first().map(String::as_str) {
+        None if picking => {
+            if let Err(e) = pick(&packs, &mut verdicts, &registry, &config) {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("list") | None => {
+            for provider in registry.providers() {
+                let (topics, provided) = match provider.topics().and_then(|t| Ok((t, provider.packs()?))) {
+                    Ok(content) => content,
+                    Err(e) => {
+                        eprintln!("quiz: {e}");
+                        return ExitCode::FAILURE;
+                    }
+                };
+                println!("{}: {}\n", provider.name(), provider.description());
+                for topic in &topics {
+                    println!("  {:<28} {}", format!("{}.rs", topic.id), topic.title);
+                }
+                if !topics.is_empty() && !provided.is_empty() {
+                    println!();
+                }
+                for pack in &provided {
+                    println!("  {:<16} {:>3} questions   {} ({}.rs)", pack.id, pack.questions.len(), pack.title, pack.topic);
+                }
+                println!();
+            }
+        }
+        Some("next") => {
+            if let Err(e) = next(&packs, &mut verdicts, &registry, &config) {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("random") => {
+            if let Err(e) = random(&packs, &registry, &config) {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("check") => {
+            let all = args.len() == 1;
+            let selected: Vec<&Pack> = packs.iter().filter(|p| all || args[1..].contains(&p.id)).collect();
+            match check(&selected, &mut verdicts, all, &config.theme()) {
+                Ok(true) => {}
+                Ok(false) => return ExitCode::FAILURE,
+                Err(e) => {
+                    eprintln!("quiz: cannot run rustc: {e}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Some("practice") => match args.get(1).and_then(|id| find(id)) {
+            Some(pack) => {
+                practice(pack, &pack.questions.iter().collect::<Vec<_>>(), &mut verdicts, &config.profile(), &config.theme());
+                if let Err(e) = verdicts.save() {
+                    eprintln!("quiz: cannot save verdicts: {e}");
+                }
+            }
+            None => {
+                eprintln!("usage: quiz practice PACK (see `quiz list`)");
+                return ExitCode::FAILURE;
+            }
+        },
+        Some("adapt") => {
+            let topics = flag(&args, "--topics").map_or(Vec::new(), |t| t.split(',').map(|t| t.trim().to_string()).collect());
+            let count = flag(&args, "--count").map_or(Some(10), |n| n.parse::<usize>().ok());
+            let strategy = adaptive::strategy(flag(&args, "--strategy").unwrap_or("weakest"));
+            let (Some(count), Some(mut strategy)) = (count, strategy) else {
+                eprintln!("usage: quiz adapt [--topics TOPIC,..] [--count N] [--strategy weakest|in-order]");
+                return ExitCode::FAILURE;
+            };
+            let result = adaptive::adapt(&packs, &mut verdicts, &topics, count, strategy.as_mut(), &config.profile(), &config.theme());
+            if let Err(e) = result.and_then(|()| verdicts.save().map_err(|e| format!("cannot save verdicts: {e}"))) {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("import") => match (args.get(1), args.get(2)) {
+            (Some(file), Some(pack)) if pack.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') => {
+                match import(Path::new(file), pack, &packs, &mut verdicts) {
+                    Ok(true) => {}
+                    Ok(false) => return ExitCode::FAILURE,
+                    Err(e) => {
+                        eprintln!("quiz: {e}");
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+            _ => {
+                eprintln!("usage: quiz import FILE.csv|FILE.toml PACK");
+                return ExitCode::FAILURE;
+            }
+        },
+        Some("pack") => {
+            let result = match (args.get(1).map(String::as_str), args.get(2)) {
+                (Some("install"), Some(source)) => packages::open(source)
+                    .and_then(|package| install(&package, args.iter().any(|a| a == "--force"), &registry, &mut verdicts)),
+                (Some("remove"), Some(name)) => packages::remove(name).map(|m| {
+                    println!("removed {name}{}", m.map(|m| format!(" {}", m.version)).unwrap_or_default());
+                    true
+                }),
+                _ => {
+                    eprintln!("usage: quiz pack install PATH|URL [--force], or quiz pack remove NAME");
+                    return ExitCode::FAILURE;
+                }
+            };
+            match result {
+                Ok(true) => {}
+                Ok(false) => return ExitCode::FAILURE,
+                Err(e) => {
+                    eprintln!("quiz: {e}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Some("update") => {
+            let Some(remote) = flag(&args, "--remote").or(config.remote()) else {
+                eprintln!("usage: quiz update [--remote URL], or a `remote` in the config file");
+                return ExitCode::FAILURE;
+            };
+            match update(remote, &config, &mut verdicts) {
+                Ok(true) => {}
+                Ok(false) => return ExitCode::FAILURE,
+                Err(e) => {
+                    eprintln!("quiz: {e}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Some("exam") => {
+            let topics = flag(&args, "--topics").map(|t| t.split(',').map(|t| t.trim().to_string()).collect::<Vec<_>>());
+            let minutes = flag(&args, "--minutes").and_then(|m| m.parse::<u64>().ok()).filter(|&m| m > 0);
+            let (Some(topics), Some(minutes)) = (topics, minutes) else {
+                eprintln!("usage: quiz exam --topics TOPIC,.. --minutes N [--report FILE]");
+                return ExitCode::FAILURE;
+            };
+            let report = flag(&args, "--report").unwrap_or("exam-report");
+            let result = exam::exam(&packs, &mut verdicts, &topics, minutes, report, &config.profile(), &config.theme());
+            if let Err(e) = result.and_then(|()| verdicts.save().map_err(|e| format!("cannot save verdicts: {e}"))) {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("notes") => {
+            let files = match &args[1..] {
+                [] => notes::notes_dir().and_then(|dir| notes::files_in(&dir)),
+                files => Ok(files.iter().map(PathBuf::from).collect()),
+            };
+            let checked = files.and_then(|files| {
+                let claims = notes::check(&files, config.edition(), &config.theme())?;
+                let topics = registry.topics().map_err(io::Error::other)?;
+                Ok(links::check(&files, &topics, &config.theme())? && claims)
+            });
+            match checked {
+                Ok(true) => {}
+                Ok(false) => return ExitCode::FAILURE,
+                Err(e) => {
+                    eprintln!("quiz: {e}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Some("miri") => {
+            let snippet = match args.get(1) {
+                Some(path) => fs::read_to_string(path).map_err(|e| io::Error::new(e.kind(), format!("{path}: {e}"))),
+                None => io::read_to_string(io::stdin()),
+            };
+            match snippet.and_then(|snippet| notes::miri(&snippet, config.edition())) {
+                Ok(true) => {}
+                Ok(false) => return ExitCode::FAILURE,
+                Err(e) => {
+                    eprintln!("quiz: {e}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Some("run") => {
+            let Some(topic) = args.get(1).filter(|a| !a.starts_with("--")) else {
+                eprintln!("usage: quiz run TOPIC [--block N | --name NAME]");
+                return ExitCode::FAILURE;
+            };
+            match run_block(topic, flag(&args, "--block"), flag(&args, "--name"), &registry, &config) {
+                Ok(true) => {}
+                Ok(false) => return ExitCode::FAILURE,
+                Err(e) => {
+                    eprintln!("quiz: {e}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Some("show") => {
+            let Some(file) = args.get(1) else {
+                eprintln!("usage: quiz show FILE|TOPIC [--rustc] [--section HEADING]");
+                return ExitCode::FAILURE;
+            };
+            let rustc = args.iter().any(|a| a == "--rustc");
+            let shown = to_show(file, &registry).and_then(|(name, text, heading)| {
+                let lines = match flag(&args, "--section").or(heading.as_deref()) {
+                    Some(query) => notes::section_lines(&name, &text, query)?,
+                    None => 1..text.lines().count() + 1,
+                };
+                let shown = shown(&name, &text, lines.clone(), rustc, &registry, &config)?;
+                read(&name, &text, &lines, &config.profile());
+                Ok(shown)
+            });
+            if let Err(e) = shown.and_then(|text| config.page(&text)) {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("cheatsheet") => {
+            let Some(file) = args.get(1) else {
+                eprintln!("usage: quiz cheatsheet FILE|TOPIC");
+                return ExitCode::FAILURE;
+            };
+            let sheet = to_show(file, &registry).map(|(_, text, _)| config.theme().code(&cheatsheet::cheatsheet(&text)));
+            if let Err(e) = sheet.and_then(|sheet| config.page(&sheet)) {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("search") => {
+            if args.len() < 2 {
+                eprintln!("usage: quiz search WORD..");
+                return ExitCode::FAILURE;
+            }
+            let query = args[1..].join(" ");
+            if let Err(e) = search(&query, &registry, &config.theme()).and_then(|found| config.page(&found).map_err(|e| e.to_string())) {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("export") => {
+            let (format, dir) = match (flag(&args, "--html"), flag(&args, "--mdbook"), flag(&args, "--man")) {
+                (Some(dir), None, None) => ("html", dir),
+                (None, Some(dir), None) => ("mdbook", dir),
+                (None, None, Some(dir)) => ("man", dir),
+                _ => {
+                    eprintln!("usage: quiz export --html DIR | --mdbook DIR | --man DIR");
+                    return ExitCode::FAILURE;
+                }
+            };
+            let exported = registry.topics().map_err(io::Error::other).and_then(|topics| export::export(&topics, format, Path::new(dir)));
+            match exported {
+                Ok(()) => println!("wrote {dir}"),
+                Err(e) => {
+                    eprintln!("quiz: {e}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Some("apis") => {
+            let topic = args.get(1).filter(|a| !a.starts_with("--"));
+            if let Err(e) = list_apis(topic.map(String::as_str), &registry, args.iter().any(|a| a == "--json"), &config.theme()) {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("where-is") => {
+            let phrase = (args.len() > 1).then(|| args[1..].join(" "));
+            if let Err(e) = where_is(phrase.as_deref(), &registry, &config.theme()).and_then(|out| config.page(&out).map_err(|e| e.to_string())) {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("cache") => {
+            let clean = match args.get(1).map(String::as_str) {
+                None => false,
+                Some("clean") => true,
+                Some(_) => {
+                    eprintln!("usage: quiz cache [clean]");
+                    return ExitCode::FAILURE;
+                }
+            };
+            if let Err(e) = cache(clean, config.cache_mb()) {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("snapshots") => {
+            let dir = snapshots::snapshots_dir();
+            let result = match args.get(1).map(String::as_str) {
+                Some(flag @ ("--accept" | "--reject")) => snapshots::resolve(&dir, flag == "--accept").map(|()| true),
+                _ => {
+                    let files = match &args[1..] {
+                        [] => notes::notes_dir().and_then(|dir| notes::files_in(&dir)),
+                        files => Ok(files.iter().map(PathBuf::from).collect()),
+                    };
+                    files.and_then(|files| snapshots::check(&files, &dir, config.edition(), &config.theme()))
+                }
+            };
+            match result {
+                Ok(true) => {}
+                Ok(false) => return ExitCode::FAILURE,
+                Err(e) => {
+                    eprintln!("quiz: {e}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Some("verify") => {
+            let Some(reference) = args.get(1) else {
+                eprintln!("usage: quiz verify PACK/QUESTION [FILE] [--learner NAME]");
+                return ExitCode::FAILURE;
+            };
+            let file = match args.get(2).filter(|a| !a.starts_with("--")) {
+                Some(file) => Ok(PathBuf::from(file)),
+                None => solutions::draft(&packs, reference, &config),
+            };
+            let profile = config.profile();
+            let learner = solutions::learner(flag(&args, "--learner"), &profile);
+            match file.and_then(|file| solutions::verify(&packs, reference, &file, &learner, &profile)) {
+                Ok(true) => {}
+                Ok(false) => return ExitCode::FAILURE,
+                Err(e) => {
+                    eprintln!("quiz: {e}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Some("history") => {
+            let Some(reference) = args.get(1) else {
+                eprintln!("usage: quiz history PACK/QUESTION [--learner NAME]");
+                return ExitCode::FAILURE;
+            };
+            if let Err(e) = solutions::history(reference, &solutions::learner(flag(&args, "--learner"), &config.profile())) {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("status") => {
+            if let Err(e) = status(&packs, &config.profile(), args.iter().any(|a| a == "--json"), config.plain()) {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("profiles") => {
+            if let Err(e) = list_profiles() {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("remind") => {
+            let at = flag(&args, "--at").unwrap_or("19:00");
+            let result = match (args.get(1).map(String::as_str), remind::parse_time(at)) {
+                (None, _) => remind::remind(&packs, &config.profile()),
+                (Some("install"), Some(at)) => remind::install(at, args.iter().any(|a| a == "--cron"), &config.profile()),
+                (Some("remove"), _) => remind::remove(&config.profile()).map(|removed| {
+                    if !removed {
+                        println!("no reminder was installed");
+                    }
+                }),
+                _ => {
+                    eprintln!("usage: quiz remind [install [--at HH:MM] [--cron] | remove]");
+                    return ExitCode::FAILURE;
+                }
+            };
+            if let Err(e) = result {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("report") => {
+            if args.get(1).map(String::as_str) == Some("verify") {
+                let Some(file) = args.get(2) else {
+                    eprintln!("usage: quiz report verify FILE.md");
+                    return ExitCode::FAILURE;
+                };
+                match report::verify(file, &config.profile()) {
+                    Ok(true) => println!("{file}: the stamp matches, with this profile's key"),
+                    Ok(false) => {
+                        println!("{file}: edited since it was stamped, or stamped with another key");
+                        return ExitCode::FAILURE;
+                    }
+                    Err(e) => {
+                        eprintln!("quiz: {e}");
+                        return ExitCode::FAILURE;
+                    }
+                }
+                return ExitCode::SUCCESS;
+            }
+            let projects: Result<Vec<_>, _> = flags(&args, "--project").into_iter().map(|dir| report::project(Path::new(dir))).collect();
+            let exams: Result<Vec<_>, _> = flags(&args, "--exam").into_iter().map(report::read_exam).collect();
+            let out = flag(&args, "--out").unwrap_or("progress-report");
+            let result = projects
+                .and_then(|projects| Ok((projects, exams?)))
+                .and_then(|(projects, exams)| report::markdown(flag(&args, "--name").or(config.profile().name()), &packs, &config.profile(), &projects, &exams))
+                .and_then(|md| report::write(&md, out, args.iter().any(|a| a == "--pdf"), &config.profile()));
+            if let Err(e) = result {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("serve") => {
+            let port = flag(&args, "--port").map_or(Some(3000), |p| p.parse().ok());
+            let Some(port) = port else {
+                eprintln!("usage: quiz serve [--port N] [--bind ADDR] [--data FILE], or quiz serve --api [--port N] [--bind ADDR] [--progress]");
+                return ExitCode::FAILURE;
+            };
+            let bind = flag(&args, "--bind").unwrap_or("127.0.0.1");
+            let result = if args.iter().any(|a| a == "--api") {
+                let profile = args.iter().any(|a| a == "--progress").then(|| config.profile());
+                api::serve(&registry, packs, verdicts, bind, port, profile)
+            } else {
+                classroom::serve(packs, verdicts, bind, port, PathBuf::from(flag(&args, "--data").unwrap_or("classroom.json")))
+            };
+            if let Err(e) = result {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some("publish") => {
+            let (Some(id), Some(title), Ok(token)) = (args.get(1), flag(&args, "--title"), std::env::var("QUIZ_TOKEN"))
+            else {
+                eprintln!("usage: QUIZ_TOKEN=.. quiz publish ID --title T [--topics T,..] [--questions PACK/ID,..]");
+                return ExitCode::FAILURE;
+            };
+            let list = |name| flag(&args, name).map(|l| l.split(',').map(|t| t.trim().to_string()).collect());
+            let request = classroom::Publish {
+                id: id.clone(),
+                title: title.to_string(),
+                topics: list("--topics").unwrap_or_default(),
+                questions: list("--questions").unwrap_or_default(),
+            };
+            match classroom::publish_assignment(&classroom::server_url(flag(&args, "--server")), &token, &request) {
+                Ok(listed) => println!("published `{}`: {} ({} questions)", listed.id, listed.title, listed.questions),
+                Err(e) => {
+                    eprintln!("quiz: {e}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Some("submit") => {
+            let profile = config.profile();
+            // the profile's name, when there is one, is a learner's name
+            let (Some(id), Some(name)) = (args.get(1), flag(&args, "--name").or(profile.name())) else {
+                eprintln!("usage: quiz submit ASSIGNMENT --name NAME [--server URL] (or --profile NAME)");
+                return ExitCode::FAILURE;
+            };
+            if let Err(e) = classroom::submit_answers(&classroom::server_url(flag(&args, "--server")), id, name, &profile) {
+                eprintln!("quiz: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        Some(other) => {
+            match fuzzy::nearest(other, COMMANDS) {
+                Some(command) => eprintln!("quiz: unknown command `{other}`: did you mean `{command}`?"),
+                None => eprintln!("quiz: unknown command `{other}` ({})", COMMANDS.join(", ")),
+            }
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACK: &str = r##"
+title = "grading"
+topic = "basics"
+
+[[question]]
+id = "usable"
+kind = "still-usable"
+code = "let s = String::new();\nlet t = s;"
+variable = "s"
+usable = false
+explanation = "moved"
+
+[[question]]
+id = "compiles"
+kind = "compiles"
+code = "let x: i32 = 1;"
+explanation = "an i32"
+
+[[question]]
+id = "output"
+kind = "output"
+code = "vec![1, 2]"
+answer = "[1, 2]"
+explanation = "Debug"
+
+[[question]]
+id = "choice"
+kind = "choice"
+prompt = "Which?"
+choices = ["Copy", "Clone"]
+correct = 2
+explanation = "Clone"
+
+[[question]]
+id = "fill-in"
+kind = "fill-in"
+code = "#[derive(___)] struct S;"
+answers = ["Debug, Clone"]
+explanation = "any order"
+
+[[question]]
+id = "fix"
+kind = "fix"
+broken = "fn f() -> &str { \"\" }"
+solved = "fn f() -> &'static str { \"\" }"
+explanation = "static"
+"##;
+
+    // `answer` to the question `id`, graded with a cache that says "compiles"
+    // for the compiles question, so nothing needs rustc
+    fn graded(id: &str, answer: &str) -> Graded {
+        let pack = bank::parse("grading", PACK, "test").unwrap();
+        let question = pack.questions.iter().find(|q| q.id == id).unwrap();
+        let verdicts = Verdicts::default();
+        let Kind::Compiles { code } = &pack.questions[1].kind else { unreachable!() };
+        let cached = Cached { compiles: true, summary: "compiles".to_string(), rustc: "rustc".to_string() };
+        verdicts.insert(cache::key("2021", code), cached);
+        grade(&pack, question, answer, &verdicts).unwrap()
+    }
+
+    #[test]
+    fn yes_and_no_answers() {
+        assert!(graded("usable", "n").correct && !graded("usable", "y").correct);
+        assert_eq!(graded("usable", "n").key, "not usable");
+        assert!(graded("compiles", "YES").correct && !graded("compiles", "no").correct);
+        assert_eq!(graded("compiles", "y").key, "compiles");
+    }
+
+    #[test]
+    fn output_ignores_whitespace() {
+        assert!(graded("output", "[1,2]").correct && graded("output", " [1, 2] ").correct);
+        assert!(!graded("output", "[2, 1]").correct);
+    }
+
+    #[test]
+    fn choices_by_number() {
+        assert!(graded("choice", "2").correct && !graded("choice", "1").correct && !graded("choice", "Clone").correct);
+        assert_eq!(graded("choice", "1").key, "2) Clone");
+    }
+
+    #[test]
+    fn a_listed_fill_in_needs_no_rustc() {
+        assert!(graded("fill-in", "Debug,Clone").correct);
+        assert!(!graded("fill-in", "").correct);
+    }
+
+    #[test]
+    fn an_unlisted_fill_in_only_fills_in() {
+        assert!(graded("fill-in", "Clone, Debug").correct);
+        // it would compile, with an item of its own after the blank
+        assert!(!graded("fill-in", "Debug)] struct S; fn _z() {} #[derive(Clone").correct);
+        assert!(!fills_in_only("Copy>(x: T) -> (T, T) { (x, x) } fn _z<T: Clone"));
+        assert!(fills_in_only("Clone + Debug") && !fills_in_only("Clone /* */"));
+    }
+
+    #[test]
+    fn a_fix_in_practice_is_self_graded() {
+        assert!(graded("fix", "y").correct && !graded("fix", "").correct);
+        assert_eq!(graded("fix", "y").key, "fn f() -> &'static str { \"\" }");
+    }
+
+    #[test]
+    fn an_empty_answer_is_never_right_in_the_classroom() {
+        let pack = bank::parse("grading", PACK, "test").unwrap();
+        let question = pack.questions.iter().find(|q| q.id == "usable").unwrap();
+        let mark = classroom::mark(&pack, question, "", &Verdicts::default()).unwrap();
+        assert!(!mark.correct);
+        assert_eq!((mark.question.as_str(), mark.explanation.as_str()), ("grading/usable", "moved"));
+    }
+
+    #[test]
+    fn a_block_by_the_end_or_a_part_of_its_name() {
+        let block = |name: &str| notes::Block { number: 0, name: name.to_string(), line: 0, code: String::new() };
+        let blocks = [block("the_entry_api"), block("the_entry_api_2"), block("hash_map"), block("hash_map_entry")];
+        let name = |query| named(&blocks, query).map(|b| b.name.as_str());
+        assert_eq!(name("the_entry_api"), Ok("the_entry_api"));
+        assert_eq!(name("entry_api"), Ok("the_entry_api"));
+        assert_eq!(name("api_2"), Ok("the_entry_api_2"));
+        assert_eq!(name("map_entry"), Ok("hash_map_entry"));
+        assert_eq!(name("entry"), Ok("hash_map_entry"));
+        assert_eq!(name("hash"), Err("`hash` could be any of hash_map, hash_map_entry".to_string()));
+        assert!(name("heap").unwrap_err().starts_with("no block is called `heap`"));
+    }
+}
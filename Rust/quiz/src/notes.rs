@@ -0,0 +1,506 @@
+// QUIZ: CHECKING WHAT THE NOTES SAY A SNIPPET DOES -----------------------
+
+/*
+ *     quiz notes [FILE..]
+ *     quiz miri [SNIPPET]                one snippet under Miri, and its
+ *                                        report if it finds UB
+ *     quiz show FILE|TOPIC [--rustc]     the file, with what rustc says
+ *               [--section HEADING]      under each snippet that fails
+ *
+ * A line in the notes can say what the code prints, or that it panics:
+ *
+ *     let tuple1 = (729, 2.18, true, "Roza");
+ *     println!("{0:?}", tuple1.3);       // prints: "Roza"
+ *
+ *     let s = &hello[0..1];              // panics: not a char boundary
+ *
+ * and `quiz notes` runs the snippet to hold it to that, so "this prints
+ * X" and "this panics" can't go stale the way a plain comment does.
+ * Without FILEs it reads every .rs file in $QUIZ_NOTES, or in the
+ * directory above this crate (Rust/, when the quiz sits in Rust/quiz),
+ * and refuses one that has no basics.rs: that's no notes directory.
+ *
+ * What the snippet is: the paragraph the annotation is in, that is, the
+ * lines between two blank lines. The notes build up examples over a few
+ * paragraphs, so if it doesn't compile alone, the paragraphs before it
+ * are added one by one, back to the last block comment, until it does.
+ * Unless the snippet has a main of its own it's run as the body of one,
+ * where struct and fn items are fine too.
+ *
+ * Every `prints:` in the snippet is a line of its expected output, in
+ * order; each paragraph with annotations is one check. Output is
+ * compared word by word, so what a loop prints on five lines can be
+ * written on one: `// prints: 0 1 2 3 4`.
+ *
+ * A `panics:` is a piece of the panic message, which the snippet must
+ * panic with. The output before a panic isn't checked, so a snippet
+ * that panics has its `prints:` lines left unchecked.
+ *
+ * A snippet with `unsafe` in it is run under Miri (harness::miri), so an
+ * unsafe example that prints the right thing by luck fails the check.
+ * The examples of what not to do say what Miri finds instead, with a
+ * piece of its message:
+ *
+ *     println!("{}", unsafe { *p });     // ub: has been freed
+ *
+ * And a snippet that's there to not compile says which error it gets,
+ * by code or a piece of the message:
+ *
+ *     println!("{}", first);             // compile error: E0502
+ *
+ * It's compiled with the paragraphs before it added until it fails with
+ * that error (a borrow conflict needs the borrow), and it's left out of
+ * the snippets after it. `quiz show --rustc` prints rustc's full message,
+ * suggestions included, under each of those, with the file's own line
+ * numbers: the snippet is compiled with every line where it is in the file.
+ *
+ * Plain (--plain, or not to a terminal), quiz show leaves out the gutter
+ * next to rustc's messages, and the drawings that have their words
+ * written out before them: a paragraph starting "In words", after which
+ * the drawing is the rest of the comment.
+ *
+ * --section shows one part of the file: the first heading with HEADING
+ * in it (any case), to the next heading as big. The headings are the
+ * notes' own comment lines:
+ *
+ *     // REFERENCE ------------        a part, to the next part
+ *     // CLOSURES ============
+ *     // Updating a string . . .       a section in one, to the next
+ *                                      heading of either kind
+ *
+ * so `quiz show collections --section entry` is the Entry API, from
+ * its heading to the next.
+ *
+ * The checks are independent of each other, each in its own scratch
+ * directory (harness.rs), so `quiz notes` and `quiz snapshots` run as
+ * many at once as there are jobs (config.rs), and print what they found
+ * in the files' order when they're all done: the output is the same
+ * however many there were.
+ */
+
+use crate::harness::{self, Outcome, Verdict};
+use crate::split;
+use crate::theme::{Role, Theme};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+pub use crate::split::heading;     // notes::heading, for the commands that read headings
+
+struct Paragraph {
+    first: usize,                      // line number, from 1
+    lines: Vec<String>,
+}
+
+impl Paragraph {
+    fn prints(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().filter_map(|l| l.split_once("// prints:")).map(|(_, text)| text.trim())
+    }
+
+    fn panics(&self) -> Option<&str> {
+        self.lines.iter().find_map(|l| l.split_once("// panics:")).map(|(_, text)| text.trim())
+    }
+
+    fn undefined(&self) -> Option<&str> {
+        self.lines.iter().find_map(|l| l.split_once("// ub:")).map(|(_, text)| text.trim())
+    }
+
+    fn compile_error(&self) -> Option<&str> {
+        self.lines.iter().find_map(|l| l.split_once("// compile error:")).map(|(_, text)| text.trim())
+    }
+
+    fn last(&self) -> usize {
+        self.first + self.lines.len() - 1
+    }
+
+    // the line of the first annotation
+    fn claim_line(&self) -> Option<usize> {
+        let claim = |l: &String| ["// prints:", "// panics:", "// ub:", "// compile error:"].iter().any(|a| l.contains(a));
+        self.lines.iter().position(claim).map(|i| self.first + i)
+    }
+}
+
+// paragraphs of code, grouped by the block comments between them
+fn sections(text: &str) -> Vec<Vec<Paragraph>> {
+    let mut sections: Vec<Vec<Paragraph>> = Vec::new();
+    for p in split::paragraphs(text) {
+        if sections.len() <= p.comments {
+            sections.resize_with(p.comments + 1, Vec::new);
+        }
+        let lines = text[p.bytes].lines().map(str::to_string).collect();
+        sections[p.comments].push(Paragraph { first: p.first, lines });
+    }
+    sections
+}
+
+fn program(paragraphs: &[&Paragraph]) -> String {
+    let body: Vec<&str> = paragraphs.iter().flat_map(|p| &p.lines).map(String::as_str).collect();
+    let body = body.join("\n");
+    if body.contains("fn main(") { body } else { format!("fn main() {{\n{body}\n}}\n") }
+}
+
+// the same, with every line at its line number in the notes, so rustc's
+// spans point into the file; main opens on the line before the first.
+// `shift` moves them all up that many lines
+fn positioned(paragraphs: &[&Paragraph], shift: usize) -> String {
+    let mut lines = vec![String::new(); paragraphs.last().map_or(0, |p| p.last() - shift)];
+    for p in paragraphs {
+        lines.splice(p.first - 1 - shift..p.last() - shift, p.lines.iter().cloned());
+    }
+    if !lines.iter().any(|l| l.contains("fn main(")) {
+        match paragraphs.first().map(|p| p.first - shift) {
+            Some(first) if first >= 2 => lines[first - 2] = "fn main() {".to_string(),
+            _ => lines.insert(0, "fn main() {".to_string()),
+        }
+        lines.push("}".to_string());
+    }
+    lines.join("\n") + "\n"
+}
+
+// paragraph j and the ones from `start` before it, leaving out those
+// that are there to not compile
+fn context(section: &[Paragraph], start: usize, j: usize) -> Vec<&Paragraph> {
+    (start..=j).filter(|&k| k == j || section[k].compile_error().is_none()).map(|k| &section[k]).collect()
+}
+
+// paragraph j, with paragraphs before it added until it fails with
+// `expected` (E0502 needs the borrow made above it). With `relative` the
+// snippet starts on line 2, main on 1, wherever it is in the file
+fn failing(section: &[Paragraph], j: usize, expected: &str, relative: bool, edition: &str) -> io::Result<Verdict> {
+    let mut start = j;
+    loop {
+        let shift = if relative { section[start].first.saturating_sub(2) } else { 0 };
+        let verdict = harness::compile(&positioned(&context(section, start, j), shift), edition)?;
+        if verdict.has_error(expected) || start == 0 {
+            return Ok(verdict);
+        }
+        start -= 1;
+    }
+}
+
+// paragraph j run (under Miri when it's unsafe), with the paragraphs
+// before it added until it compiles: the first of those, and what it did
+fn outcome(section: &[Paragraph], j: usize, edition: &str) -> io::Result<(usize, Outcome)> {
+    let mut start = j;
+    loop {
+        let source = program(&context(section, start, j));
+        let run = if source.contains("unsafe") { harness::miri } else { harness::run };
+        match run(&source, edition)? {
+            Outcome::CompileError(_) if start > 0 => start -= 1,
+            outcome => return Ok((start, outcome)),
+        }
+    }
+}
+
+// None if the annotation holds, otherwise what went wrong
+fn check_paragraph(section: &[Paragraph], j: usize, edition: &str) -> io::Result<Option<String>> {
+    if let Some(expected) = section[j].compile_error() {
+        let verdict = failing(section, j, expected, false, edition)?;
+        return Ok(if verdict.has_error(expected) {
+            None
+        } else if verdict.compiles {
+            Some(format!("the note says it fails with `{expected}`, it compiles"))
+        } else {
+            Some(format!("the note says it fails with `{expected}`, it fails with {}", verdict.summary()))
+        });
+    }
+
+    let (start, outcome) = outcome(section, j, edition)?;
+    let context = context(section, start, j);
+    let expected: Vec<&str> = context.iter().flat_map(|p| p.prints()).collect();
+    let expected = expected.join(" ");
+    let words = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let undefined = |v: harness::Violation| format!("undefined behaviour: {}", v.message);
+    if let Some(expected) = context.iter().find_map(|p| p.undefined()) {
+        return Ok(match outcome {
+            Outcome::Undefined(v) if v.message.contains(expected) => None,
+            Outcome::Undefined(v) => Some(format!("the note says Miri finds `{expected}`, it finds `{}`", v.message)),
+            Outcome::Printed(_) => Some(format!("the note says Miri finds `{expected}`, it finds nothing")),
+            Outcome::Panicked(message, _) => Some(format!("panics: {message}")),
+            Outcome::CompileError(verdict) => Some(format!("does not compile: {}", verdict.summary())),
+        });
+    }
+    if let Some(expected) = context.iter().find_map(|p| p.panics()) {
+        return Ok(match outcome {
+            Outcome::Panicked(message, _) if message.contains(expected) => None,
+            Outcome::Panicked(message, _) => Some(format!("the note says it panics with `{expected}`, it panics with `{message}`")),
+            Outcome::Printed(_) => Some(format!("the note says it panics with `{expected}`, it doesn't")),
+            Outcome::CompileError(verdict) => Some(format!("does not compile: {}", verdict.summary())),
+            Outcome::Undefined(v) => Some(undefined(v)),
+        });
+    }
+    Ok(match outcome {
+        Outcome::Printed(printed) if words(&printed) == words(&expected) => None,
+        Outcome::Printed(printed) => Some(format!("the note says `{expected}`, it prints `{}`", words(&printed))),
+        Outcome::Panicked(message, _) => Some(format!("panics: {message}")),
+        Outcome::CompileError(verdict) => Some(format!("does not compile: {}", verdict.summary())),
+        Outcome::Undefined(v) => Some(undefined(v)),
+    })
+}
+
+// `quiz miri`: runs one snippet under Miri and shows what it found;
+// false if that's undefined behaviour
+pub fn miri(snippet: &str, edition: &str) -> io::Result<bool> {
+    // `fn main() {` on the snippet's first line, so Miri's line numbers
+    // are the snippet's
+    let source = if snippet.contains("fn main(") { snippet.to_string() } else { format!("fn main() {{ {snippet}\n}}\n") };
+    match harness::miri(&source, edition)? {
+        Outcome::Printed(printed) => {
+            print!("{printed}");
+            println!("\nMiri found no undefined behaviour (on the path this run took)");
+            Ok(true)
+        }
+        Outcome::Undefined(v) => {
+            let line = v.line.map_or(String::new(), |l| format!(" on line {l}"));
+            println!("Miri found undefined behaviour{line}:\n\n{}", v.report);
+            Ok(false)
+        }
+        Outcome::Panicked(message, _) => {
+            println!("panics: {message}");
+            Ok(true)
+        }
+        Outcome::CompileError(verdict) => {
+            println!("does not compile: {}", verdict.summary());
+            Ok(false)
+        }
+    }
+}
+
+// a snippet that says it doesn't compile, and what rustc said
+pub struct Failure {
+    pub line: usize,                   // of the `// compile error:`
+    pub end: usize,                    // the paragraph's last line
+    pub expected: String,
+    pub verdict: Verdict,
+}
+
+// every `// compile error:` snippet in `text`, compiled (see failing)
+pub fn failures(text: &str, relative: bool, edition: &str) -> io::Result<Vec<Failure>> {
+    let sections = sections(text);
+    let snippets: Vec<(&[Paragraph], usize, &str)> = sections
+        .iter()
+        .flat_map(|section| section.iter().enumerate().filter_map(move |(j, p)| Some((section.as_slice(), j, p.compile_error()?))))
+        .collect();
+    let failures = snippets.into_par_iter().map(|(section, j, expected)| {
+        Ok(Failure {
+            line: section[j].claim_line().unwrap_or(section[j].first),
+            end: section[j].last(),
+            expected: expected.to_string(),
+            verdict: failing(section, j, expected, relative, edition)?,
+        })
+    });
+    failures.collect()
+}
+
+// rustc's errors as it prints them, with the scratch file's path
+// replaced by `name`: " --> /tmp/quiz-../snippet.rs:5:1" -> " --> name:5:1"
+pub fn rendered(verdict: &Verdict, name: &str) -> String {
+    if verdict.compiles {
+        return "it compiles\n".to_string();
+    }
+    let rendered: Vec<&str> = verdict.errors.iter().map(|e| e.rendered.trim_end()).collect();
+    let lines = rendered.join("\n\n");
+    let lines = lines.lines().map(|l| match l.split_once("--> ") {
+        Some((arrow, at)) if at.contains("snippet.rs:") => {
+            format!("{arrow}--> {name}:{}", at.rsplit_once("snippet.rs:").map_or("", |(_, n)| n))
+        }
+        _ => l.trim_end().to_string(),
+    });
+    lines.map(|l| l + "\n").collect()
+}
+
+// every heading's title, in order
+pub fn headings(text: &str) -> Vec<&str> {
+    text.lines().filter_map(heading).map(|(title, _)| title).collect()
+}
+
+// the text split at every heading: each piece's first line, its
+// heading (none for what comes before the first) and its text
+pub fn parts(text: &str) -> Vec<(usize, Option<&str>, String)> {
+    let mut parts = vec![(1, None, String::new())];
+    for (n, line) in (1..).zip(text.lines()) {
+        if let Some((title, _)) = heading(line) {
+            parts.push((n, Some(title), String::new()));
+        }
+        if let Some((_, _, part)) = parts.last_mut() {
+            *part += line;
+            *part += "\n";
+        }
+    }
+    parts.retain(|(_, heading, text)| heading.is_some() || !text.trim().is_empty());
+    parts
+}
+
+// the lines (numbered from 1) under the first heading with `query` in it
+pub fn section_lines(name: &str, text: &str, query: &str) -> io::Result<Range<usize>> {
+    let headings: Vec<(usize, &str, bool)> =
+        (1..).zip(text.lines()).filter_map(|(n, line)| heading(line).map(|(title, part)| (n, title, part))).collect();
+    let lower = query.to_lowercase();
+    let Some(i) = headings.iter().position(|(_, title, _)| title.to_lowercase().contains(&lower)) else {
+        let titles: Vec<&str> = headings.iter().map(|(_, title, _)| *title).collect();
+        let message = format!("no heading in {name} has `{query}` in it ({})", titles.join(", "));
+        return Err(io::Error::new(io::ErrorKind::NotFound, message));
+    };
+    let (start, _, part) = headings[i];
+    let end = headings[i + 1..].iter().find(|(_, _, next)| *next || !part).map_or(text.lines().count() + 1, |(n, _, _)| *n);
+    Ok(start..end)
+}
+
+// the code paragraphs of a notes file, for the JSON API: the first line
+// number of each, and its lines
+pub fn snippets(text: &str) -> Vec<(usize, String)> {
+    let paragraphs = sections(text).into_iter().flatten();
+    let code = paragraphs.filter(|p| p.lines.iter().any(|l| !l.trim_start().starts_with("//")));
+    code.map(|p| (p.first, p.lines.join("\n"))).collect()
+}
+
+// a snippet as quiz run knows it
+pub struct Block {
+    pub number: usize,                 // from 1, in the file
+    pub name: String,                  // "updating_a_string", "updating_a_string_2"
+    pub line: usize,                   // its first
+    pub code: String,
+}
+
+// the snippets, numbered, and named after the heading they're under
+// (the topic before the first), snake_case, with _2, _3.. after the
+// first under the same one
+pub fn blocks(topic: &str, text: &str) -> Vec<Block> {
+    let headings: Vec<(usize, &str)> = (1..).zip(text.lines()).filter_map(|(n, line)| Some((n, heading(line)?.0))).collect();
+    let mut taken: HashMap<String, usize> = HashMap::new();
+    let mut blocks = Vec::new();
+    for (number, (line, code)) in (1..).zip(snippets(text)) {
+        let under = headings.iter().rev().find(|(n, _)| *n < line).map_or(topic, |(_, title)| title);
+        let words = under.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty());
+        let base = words.map(str::to_lowercase).collect::<Vec<_>>().join("_");
+        let count = taken.entry(base.clone()).or_insert(0);
+        *count += 1;
+        let name = if *count == 1 { base } else { format!("{base}_{count}") };
+        blocks.push(Block { number, name, line, code });
+    }
+    blocks
+}
+
+// `quiz run`: the snippet on `line` run as quiz notes runs it, with the
+// paragraphs before it it needs. A compile error is compiled again with
+// every line where it is in the file, for rustc to point at `name`'s
+// lines
+pub fn run(name: &str, text: &str, line: usize, edition: &str) -> io::Result<Outcome> {
+    let sections = sections(text);
+    let found = sections.iter().find_map(|section| Some((section, section.iter().position(|p| p.first == line)?)));
+    let Some((section, j)) = found else {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("no snippet of {name} starts on line {line}")));
+    };
+    match outcome(section, j, edition)? {
+        (start, Outcome::CompileError(_)) => Ok(Outcome::CompileError(harness::compile(&positioned(&context(section, start, j), 0), edition)?)),
+        (_, outcome) => Ok(outcome),
+    }
+}
+
+// `quiz show`: the notes file as it is, its `lines` of it (section_lines
+// for a section), and with `rustc` what rustc says today under every snippet that says it doesn't
+// compile
+pub fn show(name: &str, text: &str, lines: Range<usize>, rustc: bool, edition: &str, theme: &Theme, plain: bool) -> io::Result<String> {
+    let mut after: HashMap<usize, String> = HashMap::new(); // line number -> what goes under it
+    if rustc {
+        let version = harness::rustc_version()?;
+        let gutter = if plain { "   ".to_string() } else { theme.paint(Role::Dim, "  ┃") };
+        // the snippets are compiled in the whole file, for their context
+        for failure in failures(text, false, edition)?.into_iter().filter(|f| lines.contains(&f.end)) {
+            let lines = rendered(&failure.verdict, name);
+            let lines: Vec<String> = lines
+                .lines()
+                .map(|l| match l {
+                    "" => gutter.trim_end().to_string(),
+                    l if l.starts_with("error") => format!("{gutter} {}", theme.paint(Role::Wrong, l)),
+                    l => format!("{gutter} {}", l.trim_end()),
+                })
+                .collect();
+            let header = theme.paint(Role::Dim, &format!("{version}:"));
+            let blank = gutter.trim_end();
+            after.insert(failure.end, format!("\n{gutter} {header}\n{blank}\n{}\n\n", lines.join("\n")));
+        }
+    }
+    let mut out = String::new();
+    // highlighted as a whole, for the comments that span lines
+    let text = theme.code(text);
+    let (mut words, mut drawing) = (false, false);
+    for (n, line) in (1..).zip(text.lines()) {
+        if plain {
+            match line.trim() {
+                l if l.starts_with("* In words") => words = true,
+                "*" if words => (words, drawing) = (false, true),
+                l if l.starts_with("*/") => drawing = false,
+                _ => {}
+            }
+        }
+        if !lines.contains(&n) {
+            continue;
+        }
+        if !drawing {
+            out += line;
+            out += "\n";
+        }
+        out += after.get(&n).map_or("", String::as_str);
+    }
+    Ok(out)
+}
+
+// $QUIZ_NOTES, or the directory above this crate, as build.rs finds
+// them; an error if there are no notes there, rather than nothing to do
+pub fn notes_dir() -> io::Result<PathBuf> {
+    let (dir, from) = match std::env::var_os("QUIZ_NOTES") {
+        Some(dir) => (PathBuf::from(dir), "$QUIZ_NOTES"),
+        None => (PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."), "the directory above the quiz crate"),
+    };
+    if !dir.join("basics.rs").is_file() {
+        let message = format!("{} ({from}) has no basics.rs, so it isn't the notes: set QUIZ_NOTES to the notes directory (Rust/)", dir.display());
+        return Err(io::Error::new(io::ErrorKind::NotFound, message));
+    }
+    Ok(dir)
+}
+
+// every .rs file directly in `dir`, sorted
+pub fn files_in(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|e| e == "rs"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+// false if any annotation is wrong
+pub fn check(files: &[PathBuf], edition: &str, theme: &Theme) -> io::Result<bool> {
+    let mut parsed = Vec::new();
+    for file in files {
+        let text = fs::read_to_string(file).map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", file.display())))?;
+        parsed.push((file.file_name().unwrap_or_default().to_string_lossy().into_owned(), sections(&text)));
+    }
+    // every annotated paragraph of every file: its file, section and
+    // index there, and line
+    let mut claims: Vec<(&str, &[Paragraph], usize, usize)> = Vec::new();
+    for (name, sections) in &parsed {
+        for section in sections {
+            let annotated = section.iter().enumerate().filter_map(|(j, p)| Some((name.as_str(), section.as_slice(), j, p.claim_line()?)));
+            claims.extend(annotated);
+        }
+    }
+    let problems: Vec<io::Result<Option<String>>> = claims.par_iter().map(|(_, section, j, _)| check_paragraph(section, *j, edition)).collect();
+    let mut failures = 0;
+    for ((name, .., line), problem) in claims.iter().zip(problems) {
+        let problem = problem?;
+        match &problem {
+            None => println!("{}    {name}:{line}", theme.paint(Role::Right, "ok")),
+            Some(why) => println!("{}  {name}:{line}: {why}", theme.paint(Role::Wrong, "FAIL")),
+        }
+        failures += problem.is_some() as usize;
+    }
+    if failures > 0 {
+        println!("\n{failures} wrong");
+    }
+    Ok(failures == 0)
+}
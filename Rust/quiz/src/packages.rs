@@ -0,0 +1,347 @@
+// QUIZ: PACK PACKAGES, AND INSTALLING THEM -------------------------------
+
+/*
+ *     quiz pack install PATH|URL [--force]
+ *     quiz pack remove NAME
+ *
+ * A package is a directory with a manifest, langscape-pack.toml, and the
+ * packs and topics it brings:
+ *
+ *     langscape-pack.toml      name = "embedded"
+ *                              version = "0.2.0"
+ *                              description = "Rust on microcontrollers"
+ *                              quiz = "0.1"        the quiz versions it's
+ *                                                  for (a cargo-style
+ *                                                  requirement; optional)
+ *     packs/NAME.toml          question packs, as in packs/ (bank.rs)
+ *     topics/NAME.rs           notes, shown by quiz show and quiz list
+ *
+ * It's handed around as a .tar.gz of that directory, or as a crate: a
+ * .crate from cargo package is a .tar.gz with everything in NAME-VERSION/,
+ * so a crate with the manifest next to its Cargo.toml is a package too,
+ * and one on crates.io installs from its download URL
+ * (https://crates.io/api/v1/crates/NAME/VERSION/download).
+ *
+ * Installing checks everything before anything is copied, like quiz
+ * import: the manifest, every pack (it must load, its ids can't be taken
+ * by another provider's, its topic must exist), and the answers, with
+ * rustc. A version older than the one installed needs --force, and so
+ * does the same version again. Installed packages go to the content
+ * directory, one directory per package:
+ *
+ *     $QUIZ_CONTENT, or $XDG_DATA_HOME/langscape/packs, or
+ *     ~/.local/share/langscape/packs
+ *
+ * and each is a content provider of its own (providers.rs), named after
+ * the package. Only the manifest, packs/ and topics/ are kept. One
+ * that can't be used any more (it's for another quiz version) is only
+ * listed, with the reason, by quiz list; the others are used as ever,
+ * and it's still removed, or installed over, by name.
+ *
+ * Installed files are yours to annotate. A newer version doesn't undo
+ * that: a file edited here is merged with upstream's changes (git
+ * merge-file, against the version installed before), and where both
+ * changed the same lines, yours stay, and upstream's whole file is put
+ * next to it as FILE.upstream to compare. quiz update (sync.rs) upgrades
+ * the same way.
+ */
+
+use crate::bank::{self, Pack};
+use crate::config;
+use crate::notes;
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+pub const MANIFEST: &str = "langscape-pack.toml";
+
+pub fn content_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("QUIZ_CONTENT") {
+        return PathBuf::from(dir);
+    }
+    config::data_home().join("packs")
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub name: String,
+    pub version: Version,
+    #[serde(default)]
+    pub description: String,
+    pub quiz: Option<VersionReq>,
+}
+
+impl Manifest {
+    // the manifest in `dir`, if this quiz can use the package
+    pub fn read(dir: &Path) -> Result<Manifest, String> {
+        let path = dir.join(MANIFEST);
+        let text = fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let manifest: Manifest = toml::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))?;
+        let name_ok = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+        if manifest.name.is_empty() || !manifest.name.chars().all(name_ok) {
+            return Err(format!("{}: `{}` can't be a package name (letters, digits, - and _)", path.display(), manifest.name));
+        }
+        let quiz = Version::parse(env!("CARGO_PKG_VERSION")).map_err(|e| e.to_string())?;
+        if let Some(wanted) = manifest.quiz.as_ref().filter(|r| !r.matches(&quiz)) {
+            return Err(format!("{} {} is for quiz {wanted}; this is quiz {quiz}", manifest.name, manifest.version));
+        }
+        Ok(manifest)
+    }
+}
+
+// A package read and loaded, but not installed yet
+pub struct Package {
+    pub manifest: Manifest,
+    pub packs: Vec<Pack>,
+    pub topics: Vec<PathBuf>,
+    root: PathBuf,
+    _scratch: Option<TempDir>,         // where a tarball was unpacked
+}
+
+// a directory, a .tar.gz or .crate file, or an http(s) URL of one
+pub fn open(source: &str) -> Result<Package, String> {
+    let (root, scratch) = if source.starts_with("http://") || source.starts_with("https://") {
+        let mut response = ureq::get(source).call().map_err(|e| format!("{source}: {e}"))?;
+        let bytes = response.body_mut().read_to_vec().map_err(|e| format!("{source}: {e}"))?;
+        unpack(&bytes[..], source)?
+    } else if Path::new(source).is_dir() {
+        (PathBuf::from(source), None)
+    } else {
+        let file = fs::File::open(source).map_err(|e| format!("{source}: {e}"))?;
+        unpack(file, source)?
+    };
+    let manifest = Manifest::read(&root)?;
+    let packs = match root.join("packs") {
+        dir if dir.is_dir() => bank::load_dir(&dir)?,
+        _ => Vec::new(),
+    };
+    let topics = match root.join("topics") {
+        dir if dir.is_dir() => notes::files_in(&dir).map_err(|e| format!("{}: {e}", dir.display()))?,
+        _ => Vec::new(),
+    };
+    if packs.is_empty() && topics.is_empty() {
+        return Err(format!("{source}: nothing to install, no packs/*.toml or topics/*.rs"));
+    }
+    Ok(Package { manifest, packs, topics, root, _scratch: scratch })
+}
+
+// a gzipped tarball unpacked into a new scratch directory, gone when
+// it's dropped; the package is at its top, or in its only directory (a
+// .crate's NAME-VERSION/)
+fn unpack(reader: impl Read, source: &str) -> Result<(PathBuf, Option<TempDir>), String> {
+    let scratch = TempDir::new().map_err(|e| format!("cannot make a scratch directory: {e}"))?;
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(reader));
+    // unpack() keeps every entry inside the directory, `..` or not
+    archive.unpack(scratch.path()).map_err(|e| format!("{source}: not a .tar.gz or .crate: {e}"))?;
+    if scratch.path().join(MANIFEST).exists() {
+        return Ok((scratch.path().to_path_buf(), Some(scratch)));
+    }
+    let entries: Vec<PathBuf> = fs::read_dir(scratch.path()).map_err(|e| e.to_string())?.filter_map(|e| e.ok().map(|e| e.path())).collect();
+    match &entries[..] {
+        [only] if only.join(MANIFEST).exists() => Ok((only.clone(), Some(scratch))),
+        _ => Err(format!("{source}: no {MANIFEST}, at the top or in a single directory")),
+    }
+}
+
+// a package's directory, and its manifest or why it can't be used (a
+// package for another quiz version, say)
+pub type Installed = (PathBuf, Result<Manifest, String>);
+
+// the installed packages, by name: one that can't be used doesn't stop
+// the others
+pub fn installed() -> Result<Vec<Installed>, String> {
+    let dir = content_dir();
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {e}", dir.display())),
+    };
+    let mut dirs: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path())).filter(|p| p.join(MANIFEST).exists()).collect();
+    // .base and .remote are ours
+    dirs.retain(|d| !d.file_name().unwrap_or_default().to_string_lossy().starts_with('.'));
+    dirs.sort();
+    let packages = dirs.into_iter().map(|dir| {
+        let manifest = Manifest::read(&dir);
+        (dir, manifest)
+    });
+    Ok(packages.collect())
+}
+
+// what placing a package did to one of its files
+pub enum Change {
+    Added,
+    Updated,                           // not edited here: replaced
+    Merged,                            // edited here and upstream: both kept
+    Conflicts(usize),                  // the same lines edited on both sides:
+                                       // the local ones kept there, and
+                                       // upstream's file put next to it
+    Removed,
+    KeptRemoved,                       // gone upstream, but edited here
+}
+
+impl Change {
+    pub fn describe(&self) -> String {
+        match self {
+            Change::Added => "added".to_string(),
+            Change::Updated => "updated".to_string(),
+            Change::Merged => "merged, your edits kept".to_string(),
+            Change::Conflicts(n) => format!("merged, {n} place(s) edited on both sides kept as yours; upstream's is in .upstream"),
+            Change::Removed => "removed".to_string(),
+            Change::KeptRemoved => "removed upstream, kept for your edits".to_string(),
+        }
+    }
+}
+
+// the package's files, relative to its root
+fn files(package: &Package) -> Vec<PathBuf> {
+    let packs = package.packs.iter().map(|p| Path::new("packs").join(format!("{}.toml", p.id)));
+    let topics = package.topics.iter().map(|t| Path::new("topics").join(t.file_name().unwrap_or_default()));
+    std::iter::once(PathBuf::from(MANIFEST)).chain(packs).chain(topics).collect()
+}
+
+// every file under `dir`, relative to it
+fn walk(dir: &Path, under: &Path, found: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir.join(under))? {
+        let path = under.join(entry?.file_name());
+        if dir.join(&path).is_dir() {
+            walk(dir, &path, found)?;
+        } else {
+            found.push(path);
+        }
+    }
+    Ok(())
+}
+
+// a three-way merge of a file edited here (`ours`) and upstream
+// (`theirs`) since `base`, by git merge-file: the merged text, and how
+// many places both sides changed, where ours wins
+fn merge(ours: &str, base: &str, theirs: &str) -> io::Result<(String, usize)> {
+    let scratch = TempDir::new()?;
+    let [o, b, t] = ["ours", "base", "theirs"].map(|name| scratch.path().join(name));
+    fs::write(&o, ours)?;
+    fs::write(&b, base)?;
+    fs::write(&t, theirs)?;
+    // the exit status is the number of conflicts, or negative on an error
+    let counted = Command::new("git").arg("merge-file").arg("-p").args([&o, &b, &t]).output()?;
+    let conflicts = match counted.status.code() {
+        Some(n) if (0..128).contains(&n) => n as usize,
+        _ => return Err(io::Error::other(format!("git merge-file: {}", String::from_utf8_lossy(&counted.stderr).trim()))),
+    };
+    let merged = Command::new("git").arg("merge-file").arg("-p").arg("--ours").args([&o, &b, &t]).output()?;
+    Ok((String::from_utf8_lossy(&merged.stdout).into_owned(), conflicts))
+}
+
+// Copies the package into the content directory. Over a version that was
+// there, the files nobody touched are replaced and the ones edited here
+// (an annotation, a question of your own) are merged with upstream's
+// changes, against a copy of what was installed last, in .base/NAME.
+// Files added here are left alone. The package's directory, and what
+// happened to each file that changed
+pub fn place(package: &Package) -> io::Result<(PathBuf, Vec<(PathBuf, Change)>)> {
+    let content = content_dir();
+    let target = content.join(&package.manifest.name);
+    let base = content.join(".base").join(&package.manifest.name);
+    let read = |path: PathBuf| fs::read_to_string(path).ok();
+    let mut changes = Vec::new();
+
+    let incoming = files(package);
+    for file in &incoming {
+        let theirs = fs::read_to_string(package.root.join(file))?;
+        let (ours, old) = (read(target.join(file)), read(base.join(file)));
+        let (text, change) = match ours {
+            Some(ours) if ours == theirs => continue,
+            // not changed upstream: whatever is here stays as it is
+            Some(_) if old.as_ref() == Some(&theirs) => continue,
+            None => (theirs.clone(), Change::Added),
+            Some(ours) if old.as_ref() == Some(&ours) || file == Path::new(MANIFEST) => (theirs.clone(), Change::Updated),
+            Some(ours) => match merge(&ours, old.as_deref().unwrap_or(""), &theirs)? {
+                (merged, 0) => (merged, Change::Merged),
+                (merged, n) => (merged, Change::Conflicts(n)),
+            },
+        };
+        fs::create_dir_all(target.join(file).parent().unwrap_or(&target))?;
+        fs::write(target.join(file), text)?;
+        // "embedded_hal.rs.upstream": neither a topic nor a pack
+        let mut upstream = target.join(file).into_os_string();
+        upstream.push(".upstream");
+        match change {
+            Change::Conflicts(_) => fs::write(&upstream, &theirs)?,
+            _ => fs::remove_file(&upstream).or_else(|e| if e.kind() == io::ErrorKind::NotFound { Ok(()) } else { Err(e) })?,
+        }
+        changes.push((file.clone(), change));
+    }
+
+    let mut before = Vec::new();
+    if base.is_dir() {
+        walk(&base, Path::new(""), &mut before)?;
+    }
+    for file in before.iter().filter(|f| !incoming.contains(f)) {
+        match (read(target.join(file)), read(base.join(file))) {
+            (Some(ours), Some(old)) if ours == old => {
+                fs::remove_file(target.join(file))?;
+                changes.push((file.clone(), Change::Removed));
+            }
+            (Some(_), _) => changes.push((file.clone(), Change::KeptRemoved)),
+            (None, _) => {}
+        }
+    }
+
+    // what was installed, for the next merge
+    if base.exists() {
+        fs::remove_dir_all(&base)?;
+    }
+    for file in &incoming {
+        fs::create_dir_all(base.join(file).parent().unwrap_or(&base))?;
+        fs::copy(package.root.join(file), base.join(file))?;
+    }
+    for part in ["packs", "topics"] {
+        fs::create_dir_all(target.join(part))?;
+    }
+    Ok((target, changes))
+}
+
+// `quiz pack remove`: the package's manifest, if it could still be read.
+// One that can't be used can still be removed
+pub fn remove(name: &str) -> Result<Option<Manifest>, String> {
+    let Some((dir, manifest)) = installed()?.into_iter().find(|(dir, _)| dir.file_name().is_some_and(|n| n == name)) else {
+        return Err(format!("no package `{name}` installed in {}", content_dir().display()));
+    };
+    fs::remove_dir_all(&dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+    fs::remove_dir_all(content_dir().join(".base").join(name)).ok();
+    Ok(manifest.ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\nnine\n";
+
+    #[test]
+    fn edits_to_different_lines_merge() {
+        let ours = BASE.replace("one", "one, annotated");
+        let theirs = BASE.replace("nine", "nine, fixed");
+        let (merged, conflicts) = merge(&ours, BASE, &theirs).unwrap();
+        assert_eq!((merged, conflicts), (BASE.replace("one", "one, annotated").replace("nine", "nine, fixed"), 0));
+    }
+
+    #[test]
+    fn where_both_edited_a_line_ours_stays() {
+        let ours = BASE.replace("two", "two, mine").replace("eight", "eight, mine");
+        let theirs = BASE.replace("two", "two, upstream").replace("five", "five, upstream").replace("eight", "eight, upstream");
+        let (merged, conflicts) = merge(&ours, BASE, &theirs).unwrap();
+        assert_eq!(conflicts, 2);
+        assert_eq!(merged, BASE.replace("two", "two, mine").replace("five", "five, upstream").replace("eight", "eight, mine"));
+    }
+
+    #[test]
+    fn with_no_base_every_difference_is_a_conflict() {
+        let (merged, conflicts) = merge("mine\n", "", "theirs\n").unwrap();
+        assert_eq!((merged.as_str(), conflicts), ("mine\n", 1));
+    }
+}
@@ -0,0 +1,87 @@
+// QUIZ: TOPICS PARSED ONCE -----------------------------------------------
+
+/*
+ * What the commands want from a topic besides its text: its sections
+ * (notes::parts), with the words of each for similar.rs, and the std
+ * items it uses (apis.rs), which is a walk over every token of every
+ * snippet. None of it is worked out until a command asks a topic for it
+ * (Topic::parsed), so `quiz show traits` parses one topic and `quiz
+ * list` none, and what's worked out is kept:
+ *
+ *     $XDG_CACHE_HOME/langscape/parsed/<sha256>.json
+ *
+ * (~/.cache/langscape without $XDG_CACHE_HOME, $QUIZ_CACHE in place of
+ * both), named by a hash of the topic's text. An edited topic hashes to
+ * another name, and is parsed again, so nothing ever has to be told a
+ * file changed; what's left behind is only unused. FORMAT is in the
+ * hash too, and goes up when what's parsed changes, for the same
+ * reason. `quiz cache clean` clears them out (builds.rs).
+ *
+ * It's a cache: one that can't be read is parsed again, one that can't
+ * be written isn't kept, and neither is an error.
+ */
+
+use crate::apis::{self, Used};
+use crate::config;
+use crate::notes;
+use crate::report;
+use crate::similar;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const FORMAT: &str = "1";
+
+#[derive(Serialize, Deserialize)]
+pub struct Part {
+    pub line: usize,                   // its first, from 1
+    pub end: usize,                    // the line after its last
+    pub heading: Option<String>,       // None: what's before the first
+    pub words: HashMap<String, usize>, // similar::document's counts
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Parsed {
+    pub parts: Vec<Part>,
+    pub apis: Vec<Used>,
+}
+
+pub fn parse(text: &str) -> Parsed {
+    let parts = notes::parts(text).into_iter().map(|(line, heading, part)| Part {
+        line,
+        end: line + part.lines().count(),
+        heading: heading.map(str::to_string),
+        words: similar::document(heading, &part),
+    });
+    Parsed { parts: parts.collect(), apis: apis::used(text) }
+}
+
+pub fn dir() -> PathBuf {
+    config::cache_home().join("parsed")
+}
+
+fn file(text: &str) -> PathBuf {
+    let key = report::sha256(&format!("{FORMAT}\n{text}"));
+    dir().join(format!("{key}.json"))
+}
+
+// through a temporary file, as two quizzes may be writing the same one
+fn save(file: &Path, parsed: &Parsed) -> io::Result<()> {
+    fs::create_dir_all(file.parent().unwrap_or(file))?;
+    let tmp = file.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&tmp, serde_json::to_vec(parsed)?)?;
+    fs::rename(tmp, file)
+}
+
+// from the cache, or parsed and cached
+pub fn load(text: &str) -> Parsed {
+    let file = file(text);
+    if let Some(parsed) = fs::read(&file).ok().and_then(|json| serde_json::from_slice(&json).ok()) {
+        return parsed;
+    }
+    let parsed = parse(text);
+    save(&file, &parsed).ok();
+    parsed
+}
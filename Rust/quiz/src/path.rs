@@ -0,0 +1,151 @@
+// QUIZ: WHERE TO PICK UP -------------------------------------------------
+
+/*
+ *     quiz next          the next section to read, or questions to answer
+ *
+ * The topics make a path, each after the ones it builds on (AFTER
+ * below): basics first, ownership before structures and collections,
+ * generics before traits, closures and iterators before concurrency. A
+ * topic that isn't in the table, a provider's, comes at the end.
+ *
+ * Along the path, quiz next finds the first topic not done with, and in
+ * it the first of
+ *
+ *     a part not read yet      shown, as quiz show shows a section
+ *     questions not answered   once every part is read: the topic's
+ *                              packs', practised, one pack at a time
+ *
+ * A part is what's under a heading, or before the first (parsed.rs); one
+ * with nothing under its heading goes with the part after it. Whatever
+ * quiz show or quiz next has shown is read: each part of it is a line in
+ * the reading log next to the history (progress.rs),
+ *
+ *     {"topic":"ownership","heading":"REFERENCE","at":1792144800}
+ *
+ * An answer is done with, right or wrong: mastery, quiz adapt and
+ * quiz remind are for coming back to the wrong ones.
+ */
+
+use crate::bank::{Pack, Question};
+use crate::progress::{Attempt, Read};
+use crate::providers::Topic;
+use std::collections::HashSet;
+use std::ops::Range;
+
+// each topic, after the topics it builds on
+const AFTER: &[(&str, &[&str])] = &[
+    ("basics", &[]),
+    ("ownership", &["basics"]),
+    ("structures", &["ownership"]),
+    ("enums_pattern_matching", &["structures"]),
+    ("enums", &["enums_pattern_matching", "collections"]),
+    ("option_layout", &["enums"]),
+    ("collections", &["ownership"]),
+    ("error_handling", &["enums_pattern_matching"]),
+    ("error_chains", &["error_handling", "traits"]),
+    ("generics", &["structures"]),
+    ("traits", &["generics"]),
+    ("monomorphization", &["traits"]),
+    ("coherence", &["traits"]),
+    ("formatting", &["structures", "traits"]),
+    ("parsing", &["collections", "error_handling", "traits"]),
+    ("any", &["traits", "collections"]),
+    ("api_design", &["closures_and_iterators", "traits"]),
+    ("temp_files", &["error_handling", "api_design"]),
+    ("fuzzing", &["error_handling", "collections"]),
+    ("test_doubles", &["traits", "temp_files"]),
+    ("closures_and_iterators", &["traits", "collections"]),
+    ("concurrency", &["closures_and_iterators"]),
+    ("async_sync", &["concurrency"]),
+    ("backpressure", &["concurrency"]),
+    ("shutdown", &["concurrency", "backpressure"]),
+    ("loop_performance", &["closures_and_iterators", "profiling"]),
+    ("macros", &["basics"]),
+    ("unsafe_rust", &["ownership", "traits"]),
+    ("profiling", &["basics"]),
+    ("editions", &["basics"]),
+];
+
+pub enum Next<'a> {
+    Read { topic: Topic, lines: Range<usize> },
+    Practise { pack: &'a Pack, questions: Vec<&'a Question> },
+    Done,
+}
+
+fn visit(id: &str, placed: &mut Vec<String>) {
+    if placed.iter().any(|p| p == id) {
+        return;
+    }
+    let before = AFTER.iter().find(|(topic, _)| *topic == id).map_or(&[][..], |(_, before)| before);
+    for topic in before {
+        visit(topic, placed);
+    }
+    placed.push(id.to_string());
+}
+
+// the topics in the order of the path, each after the ones it builds on
+pub fn order(mut topics: Vec<Topic>) -> Vec<Topic> {
+    let mut placed = Vec::new();
+    for (id, _) in AFTER {
+        visit(id, &mut placed);
+    }
+    let at = |topic: &Topic| placed.iter().position(|id| *id == topic.id).unwrap_or(placed.len());
+    topics.sort_by_key(at);
+    topics
+}
+
+// a part with nothing under its heading
+fn empty(text: &str, line: usize, end: usize) -> bool {
+    let body = text.lines().take(end - 1).skip(line);
+    body.map(|l| l.trim_matches(|c: char| c.is_whitespace() || "/-=*".contains(c))).all(str::is_empty)
+}
+
+// the first unread part of `topic`, and any empty ones after it, as lines
+fn unread(topic: &Topic, read: &HashSet<(&str, Option<&str>)>) -> Option<Range<usize>> {
+    let parts = &topic.parsed().parts;
+    let first = parts.iter().position(|p| !read.contains(&(topic.id.as_str(), p.heading.as_deref())))?;
+    let last = parts[first..].iter().position(|p| !empty(&topic.text, p.line, p.end)).map_or(parts.len() - 1, |i| first + i);
+    Some(parts[first].line..parts[last].end)
+}
+
+// the parts of `topic` that start in `lines`, read now
+pub fn reads(topic: &Topic, lines: &Range<usize>, at: u64) -> Vec<Read> {
+    let parts = topic.parsed().parts.iter().filter(|p| lines.contains(&p.line));
+    parts.map(|p| Read { topic: topic.id.clone(), heading: p.heading.clone(), at }).collect()
+}
+
+// the first of `topic`'s packs with questions not answered, and those
+fn unanswered<'a>(topic: &Topic, packs: &'a [Pack], answered: &HashSet<&str>) -> Option<(&'a Pack, Vec<&'a Question>)> {
+    packs.iter().filter(|p| p.topic == topic.id).find_map(|pack| {
+        let questions: Vec<&Question> = pack.questions.iter().filter(|q| !answered.contains(format!("{}/{}", pack.id, q.id).as_str())).collect();
+        (!questions.is_empty()).then_some((pack, questions))
+    })
+}
+
+fn read_set(read: &[Read]) -> HashSet<(&str, Option<&str>)> {
+    read.iter().map(|r| (r.topic.as_str(), r.heading.as_deref())).collect()
+}
+
+fn answered_set(history: &[Attempt]) -> HashSet<&str> {
+    history.iter().map(|a| a.question.as_str()).collect()
+}
+
+pub fn next<'a>(topics: Vec<Topic>, packs: &'a [Pack], read: &[Read], history: &[Attempt]) -> Next<'a> {
+    let (read, answered) = (read_set(read), answered_set(history));
+    for topic in order(topics) {
+        if let Some(lines) = unread(&topic, &read) {
+            return Next::Read { topic, lines };
+        }
+        if let Some((pack, questions)) = unanswered(&topic, packs, &answered) {
+            return Next::Practise { pack, questions };
+        }
+    }
+    Next::Done
+}
+
+// the topics with nothing left: every part read, every question answered
+pub fn finished(topics: Vec<Topic>, packs: &[Pack], read: &[Read], history: &[Attempt]) -> Vec<Topic> {
+    let (read, answered) = (read_set(read), answered_set(history));
+    let done = |topic: &Topic| unread(topic, &read).is_none() && unanswered(topic, packs, &answered).is_none();
+    topics.into_iter().filter(done).collect()
+}
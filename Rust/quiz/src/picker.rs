@@ -0,0 +1,107 @@
+// QUIZ: PICKING WHAT TO DO -----------------------------------------------
+
+/*
+ *     quiz               on a terminal: pick a topic to read or a pack to
+ *                        practise, searching as you type
+ *
+ * Every topic, every pack, a line each:
+ *
+ *     read      ownership                 Ownership
+ *     practise  lifetimes                 Lifetimes (12 questions)
+ *
+ * The searching is a fuzzy finder's when there is one: `picker` in the
+ * config file or --picker ("fzf --height 40%"), else sk or fzf on the
+ * PATH. It gets the lines on its input and gives back the one picked.
+ * Without one, quiz asks for some of a name, numbers what's near it
+ * (fuzzy.rs) and takes a number; nothing typed, or the end of input,
+ * picks nothing.
+ *
+ * A topic picked is shown as quiz show shows it, a pack practised as
+ * quiz practice does. Not on a terminal, plain quiz is quiz list, as
+ * it was.
+ */
+
+use crate::bank::Pack;
+use crate::config::{self, Config};
+use crate::fuzzy;
+use crate::providers::Topic;
+use std::io::{self, BufRead, Write};
+use std::process::Stdio;
+
+// numbered at a time by quiz's own picker
+const SHOWN: usize = 10;
+
+pub enum Choice {
+    Read(String),                      // a topic's id
+    Practise(String),                  // a pack's
+}
+
+struct Line {
+    choice: Choice,
+    name: String,                      // what's searched: the id and title
+    line: String,
+}
+
+fn lines(topics: &[Topic], packs: &[Pack]) -> Vec<Line> {
+    let topics = topics.iter().map(|t| Line {
+        choice: Choice::Read(t.id.clone()),
+        name: format!("{} {}", t.id, t.title),
+        line: format!("read      {:<25} {}", t.id, t.title),
+    });
+    let packs = packs.iter().map(|p| Line {
+        choice: Choice::Practise(p.id.clone()),
+        name: format!("{} {}", p.id, p.title),
+        line: format!("practise  {:<25} {} ({} questions)", p.id, p.title, p.questions.len()),
+    });
+    topics.chain(packs).collect()
+}
+
+// the line the picker gave back, if any: it exits non-zero when it's
+// left without one
+fn external(picker: &str, lines: &[&str]) -> io::Result<Option<usize>> {
+    // the picker draws on the terminal itself: only its input and output
+    // are quiz's
+    let mut child = config::shell(picker).stdin(Stdio::piped()).stdout(Stdio::piped()).spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // a picker quitting before reading them all: not an error
+        stdin.write_all(lines.join("\n").as_bytes()).or_else(|e| if e.kind() == io::ErrorKind::BrokenPipe { Ok(()) } else { Err(e) })?;
+    }
+    let output = child.wait_with_output()?;
+    let picked = String::from_utf8_lossy(&output.stdout);
+    Ok(lines.iter().position(|l| *l == picked.trim_end_matches('\n')))
+}
+
+// quiz's own: a search, the lines near it numbered, and a number
+fn own(lines: &[Line], input: &mut impl BufRead) -> Option<usize> {
+    let mut near: Vec<usize> = Vec::new();
+    loop {
+        let prompt = if near.is_empty() { "Read or practise (some of a name): " } else { "A number, or another name: " };
+        let answer = crate::ask(input, prompt).filter(|a| !a.is_empty())?;
+        if let Ok(n) = answer.parse::<usize>() {
+            match near.get(n.wrapping_sub(1)) {
+                Some(i) => return Some(*i),
+                None => println!("there's no {n} to pick"),
+            }
+            continue;
+        }
+        let mut scored: Vec<(usize, usize)> = (0..lines.len()).filter_map(|i| Some((fuzzy::closeness(&answer, &lines[i].name)?, i))).collect();
+        scored.sort();
+        near = scored.into_iter().take(SHOWN).map(|(_, i)| i).collect();
+        if near.is_empty() {
+            println!("nothing is named like `{answer}`");
+        }
+        for (n, i) in (1..).zip(&near) {
+            println!("{n:3}  {}", lines[*i].line);
+        }
+    }
+}
+
+// what was picked; None for nothing
+pub fn pick(topics: &[Topic], packs: &[Pack], config: &Config) -> io::Result<Option<Choice>> {
+    let mut lines = lines(topics, packs);
+    let picked = match config.picker() {
+        Some(picker) => external(&picker, &lines.iter().map(|l| l.line.as_str()).collect::<Vec<_>>())?,
+        None => own(&lines, &mut io::stdin().lock()),
+    };
+    Ok(picked.map(|i| lines.swap_remove(i).choice))
+}
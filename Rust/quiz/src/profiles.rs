@@ -0,0 +1,89 @@
+// QUIZ: PROFILES, ONE PER LEARNER ----------------------------------------
+
+/*
+ *     quiz --profile ann practice lifetimes
+ *     quiz profiles                       the profiles, and how far along
+ *
+ * For a machine several learners share, a lab's or a family's: each
+ * profile has its own history (progress.rs), and so its own mastery,
+ * streak, reminders and reports, and solutions kept under its name
+ * (solutions.rs). The profile is --profile, or $QUIZ_PROFILE, or
+ * `profile` in the config file (config.rs), in that order.
+ *
+ * Without one it's the default profile, the history in $QUIZ_PROGRESS
+ * or ~/.quiz-progress.jsonl as before profiles. A named one keeps its
+ * files in the data directory:
+ *
+ *     ~/.local/share/langscape/profiles/ann/progress.jsonl
+ *
+ * (under $XDG_DATA_HOME when that's set), made on its first answer.
+ * Packs, verdicts and the config stay shared: they're the same for
+ * everyone. A profile isn't a login, just a name: anyone at the
+ * machine can pass any --profile.
+ */
+
+use crate::config;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+pub struct Profile {
+    name: Option<String>,              // None: the default profile
+}
+
+// letters, digits, - and _, so a name is a directory and a branch
+pub fn check_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("`{name}` can't be a profile name: letters, digits, - and _ only"));
+    }
+    Ok(())
+}
+
+fn profiles_dir() -> PathBuf {
+    config::data_home().join("profiles")
+}
+
+impl Profile {
+    pub fn new(name: Option<String>) -> Profile {
+        Profile { name }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    // the history file
+    pub fn progress(&self) -> PathBuf {
+        match &self.name {
+            Some(name) => profiles_dir().join(name).join("progress.jsonl"),
+            None => std::env::var_os("QUIZ_PROGRESS").map_or_else(|| config::home().join(".quiz-progress.jsonl"), PathBuf::from),
+        }
+    }
+
+    // the reading log (progress.rs), next to the history
+    pub fn reading(&self) -> PathBuf {
+        self.progress().with_extension("read.jsonl")
+    }
+
+    // the key reports are stamped with (report.rs), next to the history
+    pub fn report_key(&self) -> PathBuf {
+        self.progress().with_extension("report-key")
+    }
+}
+
+// the named profiles, sorted
+pub fn list() -> io::Result<Vec<Profile>> {
+    let entries = match fs::read_dir(profiles_dir()) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .filter(|name| check_name(name).is_ok())
+        .collect();
+    names.sort();
+    Ok(names.into_iter().map(|name| Profile::new(Some(name))).collect())
+}
@@ -0,0 +1,299 @@
+// QUIZ: PROGRESS AND MASTERY ---------------------------------------------
+
+/*
+ * Every answer given in practice, exams and classroom submissions is
+ * appended to a history file, one JSON line per answer:
+ *
+ *     {"question":"ownership/assign-1","correct":true,"at":1792144800}
+ *
+ * It's personal, so it lives outside the packs: $QUIZ_PROGRESS, or
+ * ~/.quiz-progress.jsonl, or the profile's own file (profiles.rs).
+ * Appending a line is all a session does, so quitting halfway loses
+ * nothing.
+ *
+ * From the history, each topic gets a mastery score from 0 to 100:
+ *
+ *     completion   the share of the topic's questions answered at least
+ *                  once (fix exercises and quiz questions alike)
+ *     accuracy     the share right among the last 20 answers on the
+ *                  topic, so old mistakes stop counting once fixed
+ *     freshness    1 when practised today, halving every 30 days down
+ *                  to a floor of 0.5: knowledge fades, but not to zero
+ *
+ *     mastery = 100 * completion * accuracy * freshness
+ *
+ * A product, not an average: answering two questions perfectly isn't
+ * mastering a topic with fifty. `quiz status` shows the scores as bars;
+ * `quiz status --json` prints the same Mastery records as JSON.
+ *
+ * The history gives the streak too: the days in a row with at least one
+ * answer, up to today, or up to yesterday while today's is still to
+ * come. Days are UTC days, the same everywhere, so a streak doesn't
+ * change with the clock: practice at 1am in Berlin counts for the day
+ * before. `quiz status` shows it above the scores. `quiz remind`
+ * (remind.rs) nudges when it's about to end, and goes by local days
+ * instead: it runs at a local hour, and asks about the reader's today.
+ *
+ * What's been read is a second file next to the history, the reading
+ * log: the parts of topics quiz show has shown, for quiz next (path.rs).
+ */
+
+use crate::bank::Pack;
+use crate::profiles::Profile;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const WINDOW: usize = 20;              // answers that count for accuracy
+const HALF_LIFE_DAYS: f64 = 30.0;
+
+// HISTORY ----------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attempt {
+    pub question: String,              // "pack/question"
+    pub correct: bool,
+    pub at: u64,                       // unix time
+}
+
+impl Attempt {
+    pub fn now(pack: &str, question: &str, correct: bool) -> Attempt {
+        Attempt { question: format!("{pack}/{question}"), correct, at: now() }
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+// each item a line at the end of `path`
+fn append<T: Serialize>(path: &Path, items: &[T]) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for item in items {
+        writeln!(file, "{}", serde_json::to_string(item)?)?;
+    }
+    Ok(())
+}
+
+// oldest first; a line that doesn't parse (a write cut short) is skipped
+fn lines<T: DeserializeOwned>(path: &Path) -> io::Result<Vec<T>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(text.lines().filter_map(|line| serde_json::from_str(line).ok()).collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn record(profile: &Profile, attempts: &[Attempt]) -> io::Result<()> {
+    append(&profile.progress(), attempts)
+}
+
+pub fn load(profile: &Profile) -> io::Result<Vec<Attempt>> {
+    lines(&profile.progress())
+}
+
+// READING ----------------------------------------------------------------
+
+// a part of a topic that quiz show or quiz next has shown (path.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Read {
+    pub topic: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,       // None: what's before the first
+    pub at: u64,
+}
+
+pub fn record_reads(profile: &Profile, reads: &[Read]) -> io::Result<()> {
+    append(&profile.reading(), reads)
+}
+
+pub fn reads(profile: &Profile) -> io::Result<Vec<Read>> {
+    lines(&profile.reading())
+}
+
+// MASTERY ----------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Mastery {
+    pub topic: String,
+    pub score: u8,                     // 0 to 100
+    pub questions: usize,
+    pub answered: usize,               // distinct questions answered
+    pub completion: f64,
+    pub accuracy: f64,
+    pub freshness: f64,
+    pub last: Option<u64>,             // unix time of the latest answer
+}
+
+// one entry per topic that has packs, in alphabetical order
+pub fn mastery(packs: &[Pack], history: &[Attempt], now: u64) -> Vec<Mastery> {
+    let mut topics: Vec<&str> = packs.iter().map(|p| p.topic.as_str()).collect();
+    topics.sort();
+    topics.dedup();
+
+    // question -> topic, for the questions that still exist
+    let topic_of: HashMap<String, &str> = packs
+        .iter()
+        .flat_map(|p| p.questions.iter().map(move |q| (format!("{}/{}", p.id, q.id), p.topic.as_str())))
+        .collect();
+
+    topics
+        .into_iter()
+        .map(|topic| {
+            let questions = topic_of.values().filter(|t| **t == topic).count();
+            let mine: Vec<&Attempt> = history.iter().filter(|a| topic_of.get(&a.question) == Some(&topic)).collect();
+            let answered = mine.iter().map(|a| &a.question).collect::<HashSet<_>>().len();
+            let recent = &mine[mine.len().saturating_sub(WINDOW)..];
+            let last = mine.iter().map(|a| a.at).max();
+
+            let completion = answered as f64 / questions.max(1) as f64;
+            let accuracy = match recent.len() {
+                0 => 0.0,
+                n => recent.iter().filter(|a| a.correct).count() as f64 / n as f64,
+            };
+            let freshness = match last {
+                None => 0.0,
+                Some(last) => {
+                    let days = now.saturating_sub(last) as f64 / 86400.0;
+                    0.5 + 0.5 * 0.5f64.powf(days / HALF_LIFE_DAYS)
+                }
+            };
+            let score = (100.0 * completion * accuracy * freshness).round() as u8;
+            Mastery { topic: topic.to_string(), score, questions, answered, completion, accuracy, freshness, last }
+        })
+        .collect()
+}
+
+// a 0-100 score as a bar of `width` cells
+pub fn bar(score: u8, width: usize) -> String {
+    let filled = (score as usize * width + 50) / 100;
+    "█".repeat(filled) + &"░".repeat(width - filled)
+}
+
+// STREAKS ----------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Streak {
+    pub days: u64,                     // in a row, to today or yesterday
+    pub longest: u64,
+    pub today: bool,                   // practised today already
+}
+
+pub fn day(at: u64) -> u64 {
+    at / 86400
+}
+
+// `offset`: seconds east of UTC, for days that start at a local
+// midnight; 0 for UTC days
+pub fn streak(history: &[Attempt], now: u64, offset: i64) -> Streak {
+    let day = |at: u64| day(at.saturating_add_signed(offset));
+    let mut days: Vec<u64> = history.iter().map(|a| day(a.at)).collect();
+    days.sort_unstable();
+    days.dedup();
+
+    let (mut longest, mut run) = (0, 0);
+    for (i, d) in days.iter().enumerate() {
+        run = if i > 0 && days[i - 1] + 1 == *d { run + 1 } else { 1 };
+        longest = longest.max(run);
+    }
+    let today = day(now);
+    let last = days.last().copied();
+    let alive = last.is_some_and(|last| last + 1 >= today);
+    Streak { days: if alive { run } else { 0 }, longest, today: last == Some(today) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bank;
+
+    const DAY: u64 = 86400;
+    const NOW: u64 = 1000 * DAY + 12 * 3600;   // noon, UTC
+
+    // a pack on `topic` with output questions named `ids`
+    fn pack(id: &str, topic: &str, ids: &[&str]) -> Pack {
+        let mut text = format!("title = \"{id}\"\ntopic = \"{topic}\"\n");
+        for q in ids {
+            text += &format!("[[question]]\nid = \"{q}\"\nkind = \"output\"\ncode = \"1\"\nanswer = \"1\"\nexplanation = \"one\"\n");
+        }
+        bank::parse(id, &text, "test").unwrap()
+    }
+
+    fn attempt(question: &str, correct: bool, at: u64) -> Attempt {
+        Attempt { question: question.to_string(), correct, at }
+    }
+
+    #[test]
+    fn mastery_is_completion_times_accuracy_times_freshness() {
+        let packs = [pack("p", "traits", &["a", "b", "c", "d"])];
+        let history = [attempt("p/a", true, NOW), attempt("p/b", false, NOW), attempt("p/a", true, NOW)];
+        let [m] = &mastery(&packs, &history, NOW)[..] else { panic!() };
+        assert_eq!((m.questions, m.answered), (4, 2));
+        assert_eq!((m.completion, m.freshness), (0.5, 1.0));
+        assert!((m.accuracy - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(m.score, 33);
+    }
+
+    #[test]
+    fn only_the_last_answers_count_for_accuracy() {
+        let packs = [pack("p", "traits", &["a"])];
+        let mut history: Vec<Attempt> = (0..WINDOW).map(|_| attempt("p/a", false, NOW)).collect();
+        history.extend((0..WINDOW).map(|_| attempt("p/a", true, NOW)));
+        assert_eq!(mastery(&packs, &history, NOW)[0].score, 100);
+    }
+
+    #[test]
+    fn freshness_halves_every_30_days_towards_a_half() {
+        let packs = [pack("p", "traits", &["a"])];
+        let freshness = |days: u64| mastery(&packs, &[attempt("p/a", true, NOW - days * DAY)], NOW)[0].freshness;
+        assert_eq!(freshness(0), 1.0);
+        assert_eq!(freshness(30), 0.75);
+        assert_eq!(freshness(60), 0.625);
+        assert!((freshness(900) - 0.5).abs() < 1e-9);
+        assert_eq!(mastery(&packs, &[], NOW)[0].freshness, 0.0);
+    }
+
+    #[test]
+    fn answers_to_questions_gone_from_the_packs_dont_count() {
+        let packs = [pack("p", "traits", &["a"]), pack("q", "closures", &["a"])];
+        let history = [attempt("p/gone", true, NOW), attempt("q/a", true, NOW)];
+        let scores: Vec<(String, u8)> = mastery(&packs, &history, NOW).into_iter().map(|m| (m.topic, m.score)).collect();
+        assert_eq!(scores, [("closures".to_string(), 100), ("traits".to_string(), 0)]);
+    }
+
+    #[test]
+    fn a_streak_counts_utc_days() {
+        let midnight = 1000 * DAY;
+        // a second before and after midnight are two days
+        let history = [attempt("p/a", true, midnight - 1), attempt("p/a", true, midnight)];
+        assert_eq!(streak(&history, midnight, 0), Streak { days: 2, longest: 2, today: true });
+        // still alive the next day, until it ends
+        assert_eq!(streak(&history, midnight + DAY, 0), Streak { days: 2, longest: 2, today: false });
+        assert_eq!(streak(&history, midnight + 2 * DAY, 0), Streak { days: 0, longest: 2, today: false });
+        // two answers on one day are one day
+        let history = [attempt("p/a", true, midnight), attempt("p/a", true, midnight + DAY - 1)];
+        assert_eq!(streak(&history, midnight + DAY - 1, 0).days, 1);
+        assert_eq!(streak(&[], NOW, 0), Streak { days: 0, longest: 0, today: false });
+    }
+
+    #[test]
+    fn a_streak_can_count_local_days() {
+        let midnight = 1000 * DAY;
+        // 23:30 and 00:30 UTC are both after midnight an hour east
+        let history = [attempt("p/a", true, midnight - 1800), attempt("p/a", true, midnight + 1800)];
+        assert_eq!(streak(&history, midnight + 1800, 0).days, 2);
+        assert_eq!(streak(&history, midnight + 1800, 3600).days, 1);
+        // at 23:30 UTC it's tomorrow there already, and nothing's been
+        // answered in it yet
+        let late = midnight + DAY - 1800;
+        assert!(streak(&history[1..], late, 0).today);
+        assert!(!streak(&history[1..], late, 3600).today);
+    }
+}
@@ -0,0 +1,390 @@
+// QUIZ: CONTENT PROVIDERS, FOR PACKS FROM OUTSIDE THE REPOSITORY ---------
+
+/*
+ * Where topics and question packs come from. Everything the quiz knows
+ * is asked of a ContentProvider, kept in a Registry the way
+ * projects/plugins.rs keeps its plugins:
+ *
+ *     builtin                 packs/ and the notes in Rust/, built
+ *                             into the binary (embedded.rs)
+ *     a directory             each of the config file's `packs`: its
+ *                             .toml packs, and its .rs files as topics
+ *     a package               installed with quiz pack install
+ *                             (packages.rs)
+ *     langscape-pack-NAME     a program on the PATH (below)
+ *
+ * `quiz list` shows them all, and their packs are practised, checked and
+ * examined like the built-in ones. `quiz show TOPIC` finds a topic by
+ * name in any of them.
+ *
+ * A third-party pack ("embedded Rust") that is only files is a package.
+ * One that makes its content as it runs is a crate of its own, installed
+ * with cargo install, the way cargo finds cargo-NAME subcommands: its
+ * binary is called langscape-pack-NAME, and run with `content` it prints
+ * its content as JSON:
+ *
+ *     {
+ *       "description": "Rust on microcontrollers",
+ *       "topics": [{ "id": "embedded_hal", "title": "embedded-hal",
+ *                    "text": "// ====\n// EMBEDDED-HAL ..." }],
+ *       "packs": [{ "id": "embedded", "toml": "title = \"Embedded\"\n..." }]
+ *     }
+ *
+ * A pack's toml is what a file in packs/ would hold (bank.rs), so a pack
+ * can be written and checked there first. The simplest provider is a
+ * main that prints include_str!s of its files with serde_json. Nothing
+ * is linked into the quiz, so a provider can't break it: a program that
+ * fails or prints something else is an error naming it.
+ *
+ * Names are unique, and so are pack ids across providers: a pack that's
+ * already there is an error, not a silent replacement.
+ */
+
+use crate::bank::{self, Pack};
+use crate::config::Config;
+use crate::embedded::Embedded;
+use crate::notes;
+use crate::packages;
+use crate::parsed::{self, Parsed};
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+const PREFIX: &str = "langscape-pack-";
+
+pub struct Topic {
+    pub id: String,                    // "unsafe_rust": the file's name
+    pub title: String,
+    pub text: String,
+    parsed: OnceLock<Parsed>,          // parsed.rs, the first time it's asked
+}
+
+impl Topic {
+    pub fn new(id: String, title: String, text: String) -> Topic {
+        Topic { id, title, text, parsed: OnceLock::new() }
+    }
+
+    pub fn parsed(&self) -> &Parsed {
+        self.parsed.get_or_init(|| parsed::load(&self.text))
+    }
+}
+
+// Object safe, like plugins.rs's Plugin, so the registry can hold them
+// as Box<dyn ContentProvider>
+pub trait ContentProvider {
+    fn name(&self) -> &str;
+
+    fn description(&self) -> &str {
+        "(no description)"
+    }
+
+    fn topics(&self) -> Result<Vec<Topic>, String>;
+
+    // one topic; a provider that can find it without reading them all
+    // does
+    fn topic(&self, id: &str) -> Result<Option<Topic>, String> {
+        Ok(self.topics()?.into_iter().find(|t| t.id == id))
+    }
+
+    fn packs(&self) -> Result<Vec<Pack>, String>;
+}
+
+// the notes' first comment line, with its ---- or ==== rule taken off:
+// "// UNSAFE RUST ====" -> "Unsafe Rust", "// Traits and trait objects"
+// as it is; the file name when the file starts with something else
+pub fn title_of(id: &str, text: &str) -> String {
+    let first = text.lines().find(|l| !l.trim().is_empty() && !l.trim_start_matches('/').trim().chars().all(|c| c == '='));
+    let heading = first.and_then(|l| l.strip_prefix("// ")).map(|l| l.trim_end_matches(['-', '=', ' ']));
+    match heading.filter(|h| !h.is_empty()) {
+        Some(h) if h.chars().any(char::is_lowercase) => h.to_string(),
+        Some(h) => capitalized(&h.to_lowercase()),
+        None => capitalized(&id.replace('_', " ")),
+    }
+}
+
+// every word's first letter upper case
+fn capitalized(text: &str) -> String {
+    let words = text.split(' ').map(|w| {
+        let mut chars = w.chars();
+        chars.next().map_or(String::new(), |c| c.to_uppercase().chain(chars).collect())
+    });
+    words.collect::<Vec<_>>().join(" ")
+}
+
+// THE PROVIDERS ----------------------------------------------------------
+
+// .toml packs from one directory, .rs topics from another (or the same)
+pub struct Directory {
+    name: String,
+    description: String,
+    packs: PathBuf,
+    notes: PathBuf,
+}
+
+impl ContentProvider for Directory {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn topics(&self) -> Result<Vec<Topic>, String> {
+        let files = notes::files_in(&self.notes).map_err(|e| format!("{}: {e}", self.notes.display()))?;
+        let topics = files.iter().map(|file| {
+            let text = fs::read_to_string(file).map_err(|e| format!("{}: {e}", file.display()))?;
+            let id = file.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+            Ok(Topic::new(id.clone(), title_of(&id, &text), text))
+        });
+        topics.collect()
+    }
+
+    // the one file, not the directory
+    fn topic(&self, id: &str) -> Result<Option<Topic>, String> {
+        if id.contains(['/', '\\']) {
+            return Ok(None);
+        }
+        let file = self.notes.join(format!("{id}.rs"));
+        match fs::read_to_string(&file) {
+            Ok(text) => Ok(Some(Topic::new(id.to_string(), title_of(id, &text), text))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("{}: {e}", file.display())),
+        }
+    }
+
+    fn packs(&self) -> Result<Vec<Pack>, String> {
+        bank::load_dir(&self.packs)
+    }
+}
+
+// an installed package that can't be used, one for another quiz
+// version say: listed with why, and nothing in it read
+pub struct Unusable {
+    name: String,
+    description: String,
+}
+
+impl ContentProvider for Unusable {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn topics(&self) -> Result<Vec<Topic>, String> {
+        Ok(Vec::new())
+    }
+
+    fn packs(&self) -> Result<Vec<Pack>, String> {
+        Ok(Vec::new())
+    }
+}
+
+#[derive(Deserialize)]
+struct Content {
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    topics: Vec<ExternalTopic>,
+    #[serde(default)]
+    packs: Vec<ExternalPack>,
+}
+
+#[derive(Deserialize)]
+struct ExternalTopic {
+    id: String,
+    #[serde(default)]
+    title: String,
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct ExternalPack {
+    id: String,
+    toml: String,
+}
+
+// a langscape-pack-NAME program, run once, when something of it is
+// first asked for: a quiz that wants none of its content doesn't start
+// it
+pub struct Program {
+    name: String,
+    path: PathBuf,
+    content: OnceLock<Result<Content, String>>,
+}
+
+impl Program {
+    pub fn new(path: &Path) -> Program {
+        let file = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        let name = file.trim_start_matches(PREFIX).trim_end_matches(".exe").to_string();
+        Program { name, path: path.to_path_buf(), content: OnceLock::new() }
+    }
+
+    fn run(&self) -> Result<Content, String> {
+        let file = self.path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        let output = Command::new(&self.path).arg("content").output().map_err(|e| format!("{file}: {e}"))?;
+        if !output.status.success() {
+            return Err(format!("{file} content: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        serde_json::from_slice(&output.stdout).map_err(|e| format!("{file} content: not the JSON expected: {e}"))
+    }
+
+    fn content(&self) -> Result<&Content, String> {
+        self.content.get_or_init(|| self.run()).as_ref().map_err(String::clone)
+    }
+}
+
+impl ContentProvider for Program {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        match self.content() {
+            Ok(content) if !content.description.is_empty() => &content.description,
+            _ => "(no description)",
+        }
+    }
+
+    fn topics(&self) -> Result<Vec<Topic>, String> {
+        let topics = self.content()?.topics.iter().map(|t| {
+            let title = if t.title.is_empty() { title_of(&t.id, &t.text) } else { t.title.clone() };
+            Topic::new(t.id.clone(), title, t.text.clone())
+        });
+        Ok(topics.collect())
+    }
+
+    fn packs(&self) -> Result<Vec<Pack>, String> {
+        let origin = |id: &str| format!("{} (pack {id})", self.path.display());
+        self.content()?.packs.iter().map(|p| bank::parse(&p.id, &p.toml, &origin(&p.id))).collect()
+    }
+}
+
+// THE REGISTRY -----------------------------------------------------------
+
+#[derive(Default)]
+pub struct Registry {
+    providers: Vec<Box<dyn ContentProvider>>,
+}
+
+impl Registry {
+    pub fn register(&mut self, provider: Box<dyn ContentProvider>) -> Result<(), String> {
+        if self.providers.iter().any(|p| p.name() == provider.name()) {
+            return Err(format!("two content providers are called `{}`", provider.name()));
+        }
+        self.providers.push(provider);
+        Ok(())
+    }
+
+    pub fn providers(&self) -> &[Box<dyn ContentProvider>] {
+        &self.providers
+    }
+
+    // every provider's packs, in the order they were registered
+    pub fn packs(&self) -> Result<Vec<Pack>, String> {
+        let mut packs: Vec<Pack> = Vec::new();
+        for provider in &self.providers {
+            for pack in provider.packs()? {
+                if packs.iter().any(|p| p.id == pack.id) {
+                    return Err(format!("{}: there's a pack `{}` already", provider.name(), pack.id));
+                }
+                packs.push(pack);
+            }
+        }
+        Ok(packs)
+    }
+
+    // every provider's topics; an id that's taken already is skipped, as
+    // topic() would never find it
+    pub fn topics(&self) -> Result<Vec<Topic>, String> {
+        let mut topics: Vec<Topic> = Vec::new();
+        for provider in &self.providers {
+            for topic in provider.topics()? {
+                if !topics.iter().any(|t| t.id == topic.id) {
+                    topics.push(topic);
+                }
+            }
+        }
+        Ok(topics)
+    }
+
+    // the first topic called `id`
+    pub fn topic(&self, id: &str) -> Result<Option<Topic>, String> {
+        for provider in &self.providers {
+            if let Some(topic) = provider.topic(id)? {
+                return Ok(Some(topic));
+            }
+        }
+        Ok(None)
+    }
+}
+
+// langscape-pack-* programs in the PATH's directories, each name once:
+// the first on the PATH wins, as the shell would pick it
+fn programs() -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = Vec::new();
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    for dir in std::env::split_paths(&path) {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        let mut here: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path())).collect();
+        here.sort();
+        for program in here {
+            let name = program.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            if name.starts_with(PREFIX) && !found.iter().any(|f| f.file_name() == program.file_name()) {
+                found.push(program);
+            }
+        }
+    }
+    found
+}
+
+// builtin, then the config file's directories, the installed packages
+// (packages.rs) and the programs. builtin's notes are the ones built in,
+// or with $QUIZ_NOTES that directory's
+pub fn discover(packs_dir: &Path, config: &Config) -> Result<Registry, String> {
+    let mut registry = Registry::default();
+    match std::env::var_os("QUIZ_NOTES") {
+        Some(_) => registry.register(Box::new(Directory {
+            name: "builtin".to_string(),
+            description: "the packs and notes in this repository".to_string(),
+            packs: packs_dir.to_path_buf(),
+            notes: notes::notes_dir().map_err(|e| e.to_string())?,
+        }))?,
+        None => registry.register(Box::new(Embedded { packs: packs_dir.to_path_buf() }))?,
+    }
+    for dir in config.packs() {
+        registry.register(Box::new(Directory {
+            name: dir.file_name().map_or_else(|| dir.display().to_string(), |n| n.to_string_lossy().into_owned()),
+            description: format!("{} (config file)", dir.display()),
+            packs: dir.clone(),
+            notes: dir.clone(),
+        }))?;
+    }
+    for (dir, manifest) in packages::installed()? {
+        let manifest = match manifest {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                let name = dir.file_name().map_or_else(String::new, |n| n.to_string_lossy().into_owned());
+                registry.register(Box::new(Unusable { description: format!("(not used: {e}; quiz pack remove {name})"), name }))?;
+                continue;
+            }
+        };
+        let description = if manifest.description.is_empty() { "(no description)" } else { &manifest.description };
+        registry.register(Box::new(Directory {
+            description: format!("{description} ({} {}, installed)", manifest.name, manifest.version),
+            name: manifest.name,
+            packs: dir.join("packs"),
+            notes: dir.join("topics"),
+        }))?;
+    }
+    for program in programs() {
+        registry.register(Box::new(Program::new(&program)))?;
+    }
+    Ok(registry)
+}
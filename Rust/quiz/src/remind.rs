@@ -0,0 +1,225 @@
+// QUIZ: A DAILY REMINDER -------------------------------------------------
+
+/*
+ *     quiz remind                              the reminder itself
+ *     quiz remind install [--at HH:MM] [--cron]
+ *     quiz remind remove
+ *
+ * `quiz remind` says nothing if there's an answer today already, today
+ * as the local clock has it (the streak in `quiz status` goes by UTC
+ * days, progress.rs).
+ * Otherwise it says what a short review would keep, and where to start:
+ *
+ *     Five questions today keep your 4-day streak: quiz adapt --count 5
+ *     (weakest: lifetimes, 12 of 100)
+ *
+ * on stdout, and through notify-send too when there's a desktop to show
+ * it on.
+ *
+ * install runs it every day at --at (19:00 without it, local time): as a
+ * systemd user timer where `systemctl --user` works, and as a crontab
+ * line otherwise, or with --cron. Either way it's this binary by its
+ * full path, with the $QUIZ_* variables set when installing, so the
+ * reminder reads the same history as the practice does. Each profile
+ * (profiles.rs) has a reminder of its own, installed and removed with
+ * --profile like the rest. remove takes out both kinds. A timer that
+ * missed its time (the machine was off) runs at the next boot; cron
+ * just skips the day.
+ */
+
+use crate::bank::Pack;
+use crate::config;
+use crate::profiles::Profile;
+use crate::progress;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+// the timer's name, and the comment that ends the crontab line:
+// langscape-remind, or langscape-remind-ann for a profile
+fn unit(profile: &Profile) -> String {
+    profile.name().map_or_else(|| "langscape-remind".to_string(), |name| format!("langscape-remind-{name}"))
+}
+
+fn mark(profile: &Profile) -> String {
+    format!("# {}", unit(profile))
+}
+
+fn run(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program).args(args).output().map_err(|e| format!("{program}: {e}"))?;
+    if !output.status.success() {
+        return Err(format!("{program} {}: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// THE REMINDER -----------------------------------------------------------
+
+// seconds east of UTC, from date(1); 0, UTC, where it can't say
+fn utc_offset() -> i64 {
+    run("date", &["+%z"]).ok().and_then(|text| parse_offset(text.trim())).unwrap_or(0)
+}
+
+// "+0200" -> 7200, "-0330" -> -12600
+fn parse_offset(text: &str) -> Option<i64> {
+    let (sign, digits) = match text.split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes): (i64, i64) = (digits[..2].parse().ok()?, digits[2..].parse().ok()?);
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+// None when there's nothing to say: practised today, in the local day
+// `offset` gives
+pub fn message(packs: &[Pack], history: &[progress::Attempt], now: u64, offset: i64) -> Option<String> {
+    let streak = progress::streak(history, now, offset);
+    if streak.today {
+        return None;
+    }
+    let mut text = match streak.days {
+        0 => "Five questions today start a streak: quiz adapt --count 5".to_string(),
+        n => format!("Five questions today keep your {n}-day streak: quiz adapt --count 5"),
+    };
+    let mastery = progress::mastery(packs, history, now);
+    if let Some(weakest) = mastery.iter().filter(|m| m.last.is_some()).min_by_key(|m| m.score) {
+        text += &format!("\n(weakest: {}, {} of 100)", weakest.topic, weakest.score);
+    }
+    Some(text)
+}
+
+pub fn remind(packs: &[Pack], profile: &Profile) -> Result<(), String> {
+    let history = progress::load(profile).map_err(|e| format!("cannot read progress: {e}"))?;
+    let Some(text) = message(packs, &history, progress::now(), utc_offset()) else { return Ok(()) };
+    println!("{text}");
+    // a desktop notification is extra: no notify-send, or no desktop, is fine
+    if std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        let (title, body) = text.split_once('\n').unwrap_or((&text, ""));
+        let _ = Command::new("notify-send").args(["--app-name=langscape", title, body]).status();
+    }
+    Ok(())
+}
+
+// INSTALLING IT ----------------------------------------------------------
+
+// "19:00" -> (19, 0)
+pub fn parse_time(at: &str) -> Option<(u8, u8)> {
+    let (hour, minute) = at.split_once(':')?;
+    let (hour, minute) = (hour.parse().ok()?, minute.parse().ok()?);
+    (hour < 24 && minute < 60).then_some((hour, minute))
+}
+
+// the command the timer or cron runs: `env QUIZ_..=.. /path/to/quiz remind`
+fn command_line(profile: &Profile) -> Result<String, String> {
+    let exe = std::env::current_exe().map_err(|e| format!("cannot find the quiz binary: {e}"))?;
+    let quote = |s: &str| format!("'{}'", s.replace('\'', r"'\''"));
+    let mut vars: Vec<String> = std::env::vars()
+        .filter(|(name, _)| name.starts_with("QUIZ_"))
+        .map(|(name, value)| format!("{name}={}", quote(&value)))
+        .collect();
+    vars.sort();
+    let mut exe = quote(&exe.display().to_string());
+    if let Some(name) = profile.name() {
+        exe += &format!(" --profile {name}");
+    }
+    Ok(if vars.is_empty() { format!("{exe} remind") } else { format!("env {} {exe} remind", vars.join(" ")) })
+}
+
+fn units_dir() -> PathBuf {
+    let config = std::env::var_os("XDG_CONFIG_HOME").map_or_else(|| config::home().join(".config"), PathBuf::from);
+    config.join("systemd").join("user")
+}
+
+fn systemd() -> bool {
+    run("systemctl", &["--user", "show-environment"]).is_ok()
+}
+
+// the crontab without our line. No crontab yet is an error to
+// crontab -l ("no crontab for ann", or a bare exit 1), and an empty one
+// to us; so is no crontab program. Any other error stops here: the
+// crontab is written back after, and must not lose the lines it had
+fn crontab(profile: &Profile) -> Result<(Vec<String>, bool), String> {
+    let output = match Command::new("crontab").arg("-l").output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((Vec::new(), false)),
+        Err(e) => return Err(format!("crontab: {e}")),
+    };
+    let (stdout, stderr) = (String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    let none = stderr.contains("no crontab for") || (output.status.code() == Some(1) && stdout.trim().is_empty());
+    let current = match (output.status.success(), none) {
+        (true, _) => stdout.into_owned(),
+        (false, true) => String::new(),
+        (false, false) => return Err(format!("crontab -l: {}", stderr.trim())),
+    };
+    let mark = mark(profile);
+    let others: Vec<String> = current.lines().filter(|l| !l.ends_with(&mark)).map(str::to_string).collect();
+    let ours = others.len() < current.lines().count();
+    Ok((others, ours))
+}
+
+fn write_crontab(lines: &[String]) -> Result<(), String> {
+    let mut child = Command::new("crontab").arg("-").stdin(Stdio::piped()).spawn().map_err(|e| format!("crontab: {e}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all((lines.join("\n") + "\n").as_bytes()).map_err(|e| format!("crontab: {e}"))?;
+    }
+    let status = child.wait().map_err(|e| format!("crontab: {e}"))?;
+    if !status.success() {
+        return Err("crontab - failed".to_string());
+    }
+    Ok(())
+}
+
+pub fn install((hour, minute): (u8, u8), cron: bool, profile: &Profile) -> Result<(), String> {
+    let command = command_line(profile)?;
+    let unit = unit(profile);
+    if !cron && systemd() {
+        let dir = units_dir();
+        fs::create_dir_all(&dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        let service = format!("[Unit]\nDescription=langscape practice reminder\n\n[Service]\nType=oneshot\nExecStart={command}\n");
+        let timer = format!(
+            "[Unit]\nDescription=langscape practice reminder, daily\n\n[Timer]\nOnCalendar=*-*-* {hour:02}:{minute:02}:00\nPersistent=true\n\n[Install]\nWantedBy=timers.target\n"
+        );
+        for (file, text) in [(format!("{unit}.service"), service), (format!("{unit}.timer"), timer)] {
+            fs::write(dir.join(&file), text).map_err(|e| format!("{}: {e}", dir.join(&file).display()))?;
+        }
+        run("systemctl", &["--user", "daemon-reload"])?;
+        run("systemctl", &["--user", "enable", "--now", &format!("{unit}.timer")])?;
+        println!("reminder at {hour:02}:{minute:02} every day: the systemd timer {unit}.timer in {}", dir.display());
+    } else {
+        let (mut lines, _) = crontab(profile)?;
+        lines.push(format!("{minute} {hour} * * * {command} {}", mark(profile)));
+        write_crontab(&lines)?;
+        println!("reminder at {hour:02}:{minute:02} every day: a line in your crontab");
+    }
+    Ok(())
+}
+
+// whichever of the two is there; false if neither was
+pub fn remove(profile: &Profile) -> Result<bool, String> {
+    let unit = unit(profile);
+    let mut removed = false;
+    let timer = units_dir().join(format!("{unit}.timer"));
+    if timer.exists() {
+        if systemd() {
+            run("systemctl", &["--user", "disable", "--now", &format!("{unit}.timer")])?;
+        }
+        for ext in ["timer", "service"] {
+            let file = units_dir().join(format!("{unit}.{ext}"));
+            if file.exists() {
+                fs::remove_file(&file).map_err(|e| format!("{}: {e}", file.display()))?;
+            }
+        }
+        removed = true;
+    }
+    let (others, ours) = crontab(profile)?;
+    if ours {
+        write_crontab(&others)?;
+        removed = true;
+    }
+    Ok(removed)
+}
@@ -0,0 +1,219 @@
+// QUIZ: A REPORT OF WHAT'S BEEN LEARNT -----------------------------------
+
+/*
+ *     quiz report [--name NAME] [--exam FILE.json].. [--project DIR]..
+ *                 [--out FILE] [--pdf]
+ *     quiz report verify FILE.md
+ *
+ * One Markdown file (FILE.md, FILE is progress-report unless given) to
+ * attach to a job application or send to a mentor:
+ *
+ *     mastery     the score per topic and how it's made up, and the
+ *                 streak (progress.rs)
+ *     projects    each --project DIR, a guided project (projects/) done
+ *                 in a cargo package of its own. It's built and tested
+ *                 when the report is made, and one that fails either
+ *                 stops the report: only finished work is listed, with
+ *                 its git commit when it's in a repository, so a reader
+ *                 can look at the code
+ *     exams       each --exam, a report quiz exam wrote (its .json): the
+ *                 topics, the score and the time
+ *
+ * The last line is a stamp, an HMAC-SHA256 of everything above it keyed
+ * with the profile's report key: 32 random bytes made with the first
+ * report, next to its history (profiles.rs), readable by its owner only.
+ * `quiz report verify` checks it with the same key, so it's checked as
+ * the profile that made it, or by someone the key file is given to, a
+ * mentor say. Without the key a stamp can't be made, so an edited report
+ * can't be stamped again; but anyone with the key can, and the key says
+ * nothing to a reader who hasn't got it. To vouch for a report to anyone,
+ * sign the file too (ssh-keygen -Y sign, or gpg).
+ *
+ * --pdf also writes FILE.pdf from the Markdown, with pandoc; the stamp is
+ * the Markdown's, and verify takes the .md.
+ */
+
+use crate::bank::Pack;
+use crate::exam::{self, Report as Exam};
+use crate::profiles::Profile;
+use crate::progress;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::Command;
+
+const STAMP: &str = "\n---\n\nhmac-sha256 ";
+
+pub struct Project {
+    pub name: String,
+    pub commit: Option<String>,
+}
+
+// `dir` built and tested with cargo; an error if either fails
+pub fn project(dir: &Path) -> Result<Project, String> {
+    let manifest = dir.join("Cargo.toml");
+    let text = fs::read_to_string(&manifest).map_err(|e| format!("{}: {e}", manifest.display()))?;
+    let toml: toml::Table = toml::from_str(&text).map_err(|e| format!("{}: {e}", manifest.display()))?;
+    let name = toml.get("package").and_then(|p| p.get("name")).and_then(|n| n.as_str());
+    let name = name.ok_or_else(|| format!("{}: no [package] name", manifest.display()))?.to_string();
+    for step in ["build", "test"] {
+        let output = Command::new("cargo").arg(step).arg("--quiet").current_dir(dir).output().map_err(|e| format!("cargo: {e}"))?;
+        if !output.status.success() {
+            let why = String::from_utf8_lossy(&output.stderr).lines().find(|l| l.starts_with("error")).unwrap_or("").to_string();
+            return Err(format!("{name} ({}): cargo {step} fails, so it isn't finished: {why}", dir.display()));
+        }
+    }
+    let commit = Command::new("git").arg("-C").arg(dir).args(["rev-parse", "--short", "HEAD"]).output().ok();
+    let commit = commit.filter(|o| o.status.success()).map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+    Ok(Project { name, commit })
+}
+
+pub fn read_exam(path: &str) -> Result<Exam, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    serde_json::from_str(&text).map_err(|e| format!("{path}: not an exam report: {e}"))
+}
+
+pub fn sha256(text: &str) -> String {
+    Sha256::digest(text.as_bytes()).iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// HMAC (RFC 2104) over SHA-256, in hex
+fn hmac(key: &[u8], text: &str) -> String {
+    let mut block = [0u8; 64];
+    if key.len() > block.len() {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let pad = |byte: u8| block.map(|b| b ^ byte);
+    let inner = Sha256::new().chain_update(pad(0x36)).chain_update(text.as_bytes()).finalize();
+    let outer = Sha256::new().chain_update(pad(0x5c)).chain_update(inner).finalize();
+    outer.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// the profile's report key, made if `make` and there isn't one
+fn key(profile: &Profile, make: bool) -> Result<Vec<u8>, String> {
+    let path = profile.report_key();
+    match fs::read(&path) {
+        Ok(key) => return Ok(key),
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(format!("{}: {e}", path.display())),
+        Err(_) if !make => return Err(format!("{}: no report key, so this profile hasn't stamped a report", path.display())),
+        Err(_) => {}
+    }
+    let mut key = vec![0; 32];
+    let made = fs::File::open("/dev/urandom").and_then(|mut random| random.read_exact(&mut key)).and_then(|()| {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::OpenOptions::new();
+        file.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut file, 0o600);
+        file.open(&path)?.write_all(&key)
+    });
+    made.map(|()| key).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+pub fn markdown(name: Option<&str>, packs: &[Pack], profile: &Profile, projects: &[Project], exams: &[Exam]) -> Result<String, String> {
+    let history = progress::load(profile).map_err(|e| format!("cannot read progress: {e}"))?;
+    let now = progress::now();
+    let mut md = match name {
+        Some(name) => format!("# Rust: {name}'s progress\n\n"),
+        None => "# Rust: progress\n\n".to_string(),
+    };
+    let streak = progress::streak(&history, now, 0);
+    md += &format!(
+        "From the langscape notes' quiz, on {}: {} answers given, on {} days; the longest streak {} days.\n\n",
+        exam::utc(now),
+        history.len(),
+        history.iter().map(|a| progress::day(a.at)).collect::<HashSet<_>>().len(),
+        streak.longest
+    );
+
+    md += "## Mastery\n\n";
+    md += "Per topic, 100 x completion x recent accuracy x freshness.\n\n";
+    md += "| Topic | Mastery | Answered | Accuracy (last 20) | Last practised |\n";
+    md += "|-------|---------|----------|--------------------|----------------|\n";
+    for m in progress::mastery(packs, &history, now).iter().filter(|m| m.last.is_some()) {
+        let last = m.last.map(|at| exam::utc(at)[..10].to_string()).unwrap_or_default();
+        md += &format!(
+            "| {} | {} | {} of {} | {:.0}% | {last} |\n",
+            m.topic,
+            m.score,
+            m.answered,
+            m.questions,
+            m.accuracy * 100.0
+        );
+    }
+
+    if !projects.is_empty() {
+        md += "\n## Projects\n\nBuilt, with their tests passing, when this report was made.\n\n";
+        for p in projects {
+            match &p.commit {
+                Some(commit) => md += &format!("- {} (commit `{commit}`)\n", p.name),
+                None => md += &format!("- {}\n", p.name),
+            }
+        }
+    }
+
+    if !exams.is_empty() {
+        md += "\n## Exams\n\nTimed, without hints.\n\n";
+        md += "| Topics | Score | Time | Taken |\n";
+        md += "|--------|-------|------|-------|\n";
+        for e in exams {
+            let total = e.questions.len();
+            md += &format!(
+                "| {} | {} of {total} ({}%) | {} of {} minutes | {} |\n",
+                e.topics.join(", "),
+                e.score,
+                e.score * 100 / total.max(1),
+                exam::clock(e.seconds),
+                e.minutes,
+                &exam::utc(e.started)[..10]
+            );
+        }
+    }
+    Ok(md)
+}
+
+// writes `out`.md, stamped with the profile's key, and `out`.pdf with `pdf`
+pub fn write(md: &str, out: &str, pdf: bool, profile: &Profile) -> Result<(), String> {
+    let stamped = format!("{md}{STAMP}{}\n", hmac(&key(profile, true)?, md));
+    let path = format!("{out}.md");
+    fs::write(&path, stamped).map_err(|e| format!("{path}: {e}"))?;
+    println!("report: {path}");
+    if pdf {
+        let status = Command::new("pandoc").args([&path, "-o", &format!("{out}.pdf")]).status();
+        match status {
+            Ok(status) if status.success() => println!("report: {out}.pdf"),
+            Ok(_) => return Err(format!("pandoc couldn't make {out}.pdf")),
+            Err(e) => return Err(format!("pandoc: {e} (the PDF needs pandoc; the .md is written)")),
+        }
+    }
+    Ok(())
+}
+
+// true if the stamp matches what's above it, with the profile's key
+pub fn verify(path: &str, profile: &Profile) -> Result<bool, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    let Some(at) = text.rfind(STAMP) else { return Err(format!("{path}: no stamp")) };
+    let (body, stamp) = (&text[..at], text[at + STAMP.len()..].trim());
+    Ok(stamp == hmac(&key(profile, false)?, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 4231's test cases 2 and 6
+    #[test]
+    fn hmac_is_rfc_4231s() {
+        assert_eq!(hmac(b"Jefe", "what do ya want for nothing?"), "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+        assert_eq!(
+            hmac(&[0xaa; 131], "Test Using Larger Than Block-Size Key - Hash Key First"),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+}
@@ -0,0 +1,264 @@
+// QUIZ: RELATED TOPICS, BY WHAT THEY SAY ---------------------------------
+
+/*
+ *     quiz show traits           ...and last:
+ *                                Related: generics, "TRAITS as
+ *                                PARAMETERS"; closures_and_iterators, ..
+ *     quiz search trait object   the sections with every word in them,
+ *                                or, when none has, the nearest by what
+ *                                they say
+ *
+ * Every topic is cut at its headings (notes::parts) and each section's
+ * explanation, its comments, is a document; the code isn't, where every
+ * section says `let` and `println`, and neither are `// prints:` lines.
+ * The documents are weighed by tf-idf: a word counts for more the more
+ * often a section says it (1 + ln of the count), and for less the more
+ * sections say it (ln of sections over those that do), so "borrow"
+ * says something about a section and "value" doesn't. A query, a word
+ * searched for or a whole topic to find the relatives of, is weighed by
+ * its plain counts instead, so a topic that says "trait" fifty times is
+ * about traits. Near is the cosine of the weights.
+ *
+ * A word is its letters and digits, lowercased, at least three of them
+ * (snake_case is split), without the commonest English ones and with a
+ * plural or -ing or -ed cut off: "borrowed", "borrowing" and "borrows"
+ * are all "borrow". That's the whole of the "semantics": there are no
+ * synonyms. A search word no section has is replaced by the nearest
+ * words that one has (fuzzy.rs), so a typo still finds something.
+ *
+ * Related lists the nearest section of each other topic, the three
+ * nearest of those.
+ *
+ * The index is kept, whole, in ~/.cache/langscape/search.json
+ * (config::cache_home), with a sha256 of each topic's text as it was
+ * indexed. When the topics are those, it's read and used as it is;
+ * when some changed, or came or went, only those are read again
+ * (their sections, from parsed.rs) and the rest's words are the kept
+ * ones. The weights are worked out again then, as a new section changes
+ * every word's idf, but that's arithmetic, not reading. So a search with
+ * a thousand installed topics reads one file, not a thousand.
+ */
+
+use crate::config;
+use crate::fuzzy;
+use crate::notes;
+use crate::providers::Topic;
+use crate::report;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+// goes up when what's kept changes, so an old file is built again
+const FORMAT: u32 = 1;
+
+const STOP: &[&str] = &[
+    "the", "and", "for", "that", "this", "with", "are", "was", "not", "but", "you", "can", "its", "has", "have", "from", "into",
+    "when", "what", "which", "there", "their", "they", "one", "all", "any", "only", "just", "then", "than", "also", "here", "some",
+    "does", "doesn", "don", "isn", "won", "like", "same", "other", "more", "most", "every", "each", "how", "why", "now", "would",
+];
+
+#[derive(Serialize, Deserialize)]
+pub struct Section {
+    pub topic: String,
+    pub line: usize,                   // its first, in the topic's text
+    pub heading: Option<String>,       // None: what's before the first
+    text: String,                      // lowercased, for exact search
+    counts: HashMap<String, usize>,    // document()'s
+    weights: HashMap<String, f64>,     // tf-idf, of length 1
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Index {
+    format: u32,
+    pub sections: Vec<Section>,
+    idf: HashMap<String, f64>,
+    hashes: BTreeMap<String, String>,  // topic -> sha256 of the text indexed
+}
+
+// "borrowed", "borrowing", "borrows" -> "borrow"
+fn stem(word: &str) -> &str {
+    for (suffix, shortest) in [("ing", 6), ("ed", 5), ("s", 4)] {
+        if word.len() >= shortest && word.ends_with(suffix) && !word.ends_with("ss") {
+            return &word[..word.len() - suffix.len()];
+        }
+    }
+    word
+}
+
+// the comments in `text`, without the code around them
+fn explanation(text: &str) -> String {
+    let mut out = String::new();
+    let mut block = false;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if block || trimmed.starts_with("/*") {
+            block = !trimmed.contains("*/");
+            out += trimmed;
+        } else if let Some((_, comment)) = line.split_once("//").filter(|(_, c)| !c.trim_start().starts_with("prints:")) {
+            out += comment;
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn counts(text: &str) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    let words = text.split(|c: char| !c.is_alphanumeric()).map(str::to_lowercase);
+    for word in words.filter(|w| w.len() >= 3 && !STOP.contains(&w.as_str()) && !w.chars().all(|c| c.is_ascii_digit())) {
+        *counts.entry(stem(&word).to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+// a section's words: its explanation's, and its heading's twice more,
+// for what a heading says is what the section is about
+pub fn document(heading: Option<&str>, text: &str) -> HashMap<String, usize> {
+    let mut words = explanation(text);
+    for _ in 0..2 {
+        words += "\n";
+        words += heading.unwrap_or_default();
+    }
+    counts(&words)
+}
+
+fn cosine(a: &HashMap<String, f64>, b: &HashMap<String, f64>) -> f64 {
+    a.iter().filter_map(|(word, x)| Some(x * b.get(word)?)).sum()
+}
+
+// a topic's sections, not weighed yet
+fn sections(topic: &Topic) -> Vec<Section> {
+    let lines: Vec<&str> = topic.text.lines().collect();
+    let sections = topic.parsed().parts.iter().map(|part| Section {
+        topic: topic.id.clone(),
+        line: part.line,
+        heading: part.heading.clone(),
+        text: lines[part.line - 1..part.end - 1].join("\n").to_lowercase(),
+        counts: part.words.clone(),
+        weights: HashMap::new(),
+    });
+    sections.collect()
+}
+
+pub fn file() -> PathBuf {
+    config::cache_home().join("search.json")
+}
+
+fn kept() -> Option<Index> {
+    let index: Index = serde_json::from_slice(&fs::read(file()).ok()?).ok()?;
+    (index.format == FORMAT).then_some(index)
+}
+
+// through a temporary file, as parsed.rs's are
+fn save(index: &Index) -> io::Result<()> {
+    let file = file();
+    fs::create_dir_all(file.parent().unwrap_or(&file))?;
+    let tmp = file.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&tmp, serde_json::to_vec(index)?)?;
+    fs::rename(tmp, file)
+}
+
+impl Index {
+    // the index of `topics`: the one kept, as it is or with what changed
+    // read again, or (with none kept) built
+    pub fn new(topics: &[Topic]) -> Index {
+        let hashes: BTreeMap<String, String> = topics.iter().map(|t| (t.id.clone(), report::sha256(&t.text))).collect();
+        let (old_hashes, old_sections) = match kept() {
+            Some(index) if index.hashes == hashes => return index,
+            Some(index) => (index.hashes, index.sections),
+            None => (BTreeMap::new(), Vec::new()),
+        };
+        let unchanged = |id: &str| old_hashes.get(id).is_some_and(|hash| hashes.get(id) == Some(hash));
+        let mut by_topic: HashMap<String, Vec<Section>> = HashMap::new();
+        for section in old_sections.into_iter().filter(|s| unchanged(&s.topic)) {
+            by_topic.entry(section.topic.clone()).or_default().push(section);
+        }
+        let mut all = Vec::new();
+        for topic in topics {
+            all.extend(by_topic.remove(&topic.id).unwrap_or_else(|| sections(topic)));
+        }
+        let index = Index::weighed(all, hashes);
+        save(&index).ok();
+        index
+    }
+
+    // the idf of every word, and every section's weights, from their
+    // counts
+    fn weighed(mut sections: Vec<Section>, hashes: BTreeMap<String, String>) -> Index {
+        let mut df: HashMap<&str, usize> = HashMap::new();
+        for section in &sections {
+            for word in section.counts.keys() {
+                *df.entry(word).or_insert(0) += 1;
+            }
+        }
+        let n = sections.len() as f64;
+        let idf = df.into_iter().map(|(word, df)| (word.to_string(), (n / df as f64).ln())).collect();
+        let mut index = Index { format: FORMAT, sections: Vec::new(), idf, hashes };
+        for section in &mut sections {
+            section.weights = index.weigh(&section.counts, |n| 1.0 + n.ln());
+        }
+        index.sections = sections;
+        index
+    }
+
+    // words the index has never seen weigh nothing
+    fn weigh(&self, counts: &HashMap<String, usize>, tf: fn(f64) -> f64) -> HashMap<String, f64> {
+        let mut weights: HashMap<String, f64> = counts
+            .iter()
+            .filter_map(|(word, &count)| Some((word.clone(), tf(count as f64) * self.idf.get(word)?)))
+            .filter(|(_, w)| *w > 0.0)
+            .collect();
+        let length = weights.values().map(|w| w * w).sum::<f64>().sqrt();
+        weights.values_mut().for_each(|w| *w /= length);
+        weights
+    }
+
+    // the nearest section of each topic but `topic`, nearest first
+    pub fn related(&self, topic: &str, text: &str, count: usize) -> Vec<&Section> {
+        let mut words = HashMap::new();
+        for (word, n) in notes::parts(text).into_iter().flat_map(|(_, heading, text)| document(heading, &text)) {
+            *words.entry(word).or_insert(0) += n;
+        }
+        let weights = self.weigh(&words, |n| n);
+        let mut best: HashMap<&str, (f64, &Section)> = HashMap::new();
+        for section in self.sections.iter().filter(|s| s.topic != topic) {
+            let score = cosine(&weights, &section.weights);
+            let entry = best.entry(&section.topic).or_insert((0.0, section));
+            if score > entry.0 {
+                *entry = (score, section);
+            }
+        }
+        let mut best: Vec<(f64, &Section)> = best.into_values().filter(|(score, _)| *score > 0.0).collect();
+        best.sort_by(|a, b| b.0.total_cmp(&a.0));
+        best.into_iter().take(count).map(|(_, section)| section).collect()
+    }
+
+    // the sections that have every one of the words, in order
+    pub fn exact(&self, query: &str) -> Vec<&Section> {
+        let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        self.sections.iter().filter(|s| !words.is_empty() && words.iter().all(|w| s.text.contains(w.as_str()))).collect()
+    }
+
+    // the sections nearest to the query, nearest first, with how near
+    pub fn nearest(&self, query: &str, count: usize) -> Vec<(f64, &Section)> {
+        let mut query_counts = HashMap::new();
+        for (word, n) in counts(query) {
+            if self.idf.contains_key(&word) {
+                *query_counts.entry(word).or_insert(0) += n;
+                continue;
+            }
+            let near: Vec<(usize, &String)> = self.idf.keys().filter_map(|known| Some((fuzzy::closeness(&word, known)?, known))).collect();
+            let nearest = near.iter().map(|(d, _)| *d).min();
+            for (_, known) in near.iter().filter(|(d, _)| Some(*d) == nearest) {
+                *query_counts.entry(known.to_string()).or_insert(0) += n;
+            }
+        }
+        let weights = self.weigh(&query_counts, |n| n);
+        let mut scored: Vec<(f64, &Section)> = self.sections.iter().map(|s| (cosine(&weights, &s.weights), s)).filter(|(score, _)| *score > 0.0).collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.truncate(count);
+        scored
+    }
+}
@@ -0,0 +1,131 @@
+// QUIZ: SNAPSHOTS OF WHAT RUSTC SAYS ABOUT THE NOTES ---------------------
+
+/*
+ *     quiz snapshots [FILE..]             compare, .snap.new for what changed
+ *     quiz snapshots --accept             the .snap.new files become the .snap
+ *     quiz snapshots --reject             delete them
+ *
+ * `quiz notes` checks that a `// compile error: E0502` snippet still fails
+ * with E0502. The prose around it explains the message, though, and a new
+ * rustc can reword it or change its suggestion without the error code
+ * changing. So each of those snippets has rustc's full message kept in
+ * snapshots/, the way insta keeps a test's output (cargo insta review):
+ *
+ *     snapshots/collections__E0502.snap
+ *     ---
+ *     source: collections.rs:69
+ *     claim: compile error: E0502
+ *     rustc: rustc 1.95.0 (59807616e 2026-04-14)
+ *     ---
+ *     error[E0502]: cannot borrow `v` as mutable because it is also ...
+ *
+ * Only what's under the second --- is compared. When that differs, the
+ * new message is written next to the old as .snap.new and the diff is
+ * printed; read it, fix the prose if it explains something that's gone,
+ * and --accept. A snippet without a .snap yet shows as all added.
+ *
+ * Line numbers in the message count from the snippet (main is line 1),
+ * so adding lines above a snippet doesn't change its snapshot. Exits
+ * non-zero while anything differs, to run in CI after a toolchain update.
+ */
+
+use crate::diff::diff;
+use crate::harness;
+use crate::notes;
+use crate::theme::{Role, Theme};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub fn snapshots_dir() -> PathBuf {
+    std::env::var_os("QUIZ_SNAPSHOTS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("snapshots"))
+}
+
+// "collections.rs", "E0502" -> "collections__E0502"; a second E0502 in the
+// same file is "collections__E0502-2"
+fn snapshot_name(file: &str, expected: &str, taken: &mut HashSet<String>) -> String {
+    let stem = file.trim_end_matches(".rs");
+    let claim: String = expected.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    let base = format!("{stem}__{claim}");
+    let mut name = base.clone();
+    for n in 2.. {
+        if taken.insert(name.clone()) {
+            break;
+        }
+        name = format!("{base}-{n}");
+    }
+    name
+}
+
+// the message part of a .snap file: what's after the header
+fn body(snap: &str) -> &str {
+    snap.strip_prefix("---\n").and_then(|rest| rest.split_once("\n---\n")).map_or(snap, |(_, body)| body)
+}
+
+// `quiz snapshots`: true if every snapshot matches
+pub fn check(files: &[PathBuf], dir: &Path, edition: &str, theme: &Theme) -> io::Result<bool> {
+    fs::create_dir_all(dir)?;
+    let version = harness::rustc_version()?;
+    let mut changed = 0;
+    for file in files {
+        let text = fs::read_to_string(file).map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", file.display())))?;
+        let file = file.file_name().unwrap_or_default().to_string_lossy();
+        let mut taken = HashSet::new();
+        for failure in notes::failures(&text, true, edition)? {
+            let name = snapshot_name(&file, &failure.expected, &mut taken);
+            let message = notes::rendered(&failure.verdict, "snippet.rs");
+            let path = dir.join(format!("{name}.snap"));
+            let new = dir.join(format!("{name}.snap.new"));
+            let old = fs::read_to_string(&path).unwrap_or_default();
+            if body(&old) == message {
+                println!("{}    {name}", theme.paint(Role::Right, "ok"));
+                fs::remove_file(&new).ok();
+                continue;
+            }
+            let header = format!("source: {file}:{}\nclaim: compile error: {}\nrustc: {version}", failure.line, failure.expected);
+            fs::write(&new, format!("---\n{header}\n---\n{message}"))?;
+            println!("{}  {name} ({file}:{})", theme.paint(Role::Header, if old.is_empty() { "NEW " } else { "DIFF" }), failure.line);
+            for (sign, _, line) in diff(body(&old), &message) {
+                let role = match sign {
+                    '-' => Role::Removed,
+                    '+' => Role::Added,
+                    _ => Role::Dim,
+                };
+                println!("      {}", theme.paint(role, &format!("{sign} {line}")));
+            }
+            changed += 1;
+        }
+        // a snapshot whose snippet has gone, or changed its claim
+        let stem = format!("{}__", file.trim_end_matches(".rs"));
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?.file_name().to_string_lossy().into_owned();
+            if let Some(name) = entry.strip_suffix(".snap").filter(|n| n.starts_with(&stem) && !taken.contains(*n)) {
+                println!("{}  {name}: no snippet in {file} has this claim now; delete the .snap", theme.paint(Role::Header, "GONE"));
+                changed += 1;
+            }
+        }
+    }
+    if changed > 0 {
+        println!("\n{changed} changed: quiz snapshots --accept to keep the new ones, --reject to drop them");
+    }
+    Ok(changed == 0)
+}
+
+// --accept and --reject: what happens to the .snap.new files
+pub fn resolve(dir: &Path, accept: bool) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()?.strip_suffix(".new")) else { continue };
+        if accept {
+            fs::rename(&path, dir.join(name))?;
+            println!("accepted {name}");
+        } else {
+            fs::remove_file(&path)?;
+            println!("rejected {name}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,223 @@
+// QUIZ: FIX SOLUTIONS, KEPT IN GIT ---------------------------------------
+
+/*
+ *     quiz verify PACK/QUESTION [FILE] [--learner NAME]
+ *     quiz history PACK/QUESTION [--learner NAME]
+ *
+ * Practice can only ask whether a fix was got right. `verify` grades a
+ * fix written out in a file, the way the classroom server does (fixes.rs):
+ * it must compile, still define what the broken version did, and fit the
+ * solution's signatures. The result goes into the progress history like
+ * any answer. Without a FILE, the broken
+ * code is opened in the editor (config.rs), and verified when it closes.
+ *
+ * With $QUIZ_SOLUTIONS set to a directory, each solution that passes is
+ * also committed there, to a git repository with a branch per learner
+ * (--learner, or the profile, or $USER). A directory that's empty, or
+ * not there yet, becomes a bare repository; one with other things in it
+ * and no repository is refused:
+ *
+ *     learner/ann    lifetimes/two-inputs.rs   lifetimes/struct-field.rs ..
+ *
+ * A solution verified again after a change is a new commit, so `history`
+ * shows how it got to where it is, oldest first, as git log -p does. An
+ * instructor with everyone's solutions in one repository (or learners
+ * pushing to it) reviews them with git itself:
+ *
+ *     git -C $QUIZ_SOLUTIONS log --oneline learner/ann
+ *     git -C $QUIZ_SOLUTIONS diff learner/ann learner/bo -- lifetimes/two-inputs.rs
+ *
+ * Nothing is checked out: the commits are made with git's plumbing
+ * (hash-object, a scratch index, commit-tree), so verifying never touches
+ * a working tree. Needs git on the PATH; without $QUIZ_SOLUTIONS it isn't
+ * used at all.
+ */
+
+use crate::bank::{Kind, Pack};
+use crate::classroom;
+use crate::config::Config;
+use crate::fixes::{self, Fix};
+use crate::harness;
+use crate::profiles::Profile;
+use crate::progress;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+pub fn solutions_dir() -> Option<PathBuf> {
+    std::env::var_os("QUIZ_SOLUTIONS").map(PathBuf::from)
+}
+
+// --learner, or the profile's name, or the account's
+pub fn learner(flag: Option<&str>, profile: &Profile) -> String {
+    let from_env = || std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok();
+    let name = flag.or(profile.name()).map(str::to_string).or_else(from_env);
+    name.unwrap_or_else(|| "learner".to_string())
+}
+
+// "Ann Lee" -> "learner/Ann-Lee": git's rules for a ref, roughly
+fn branch(learner: &str) -> String {
+    let name: String = learner.chars().map(|c| if c.is_alphanumeric() || "-_".contains(c) { c } else { '-' }).collect();
+    format!("learner/{}", name.trim_matches('-'))
+}
+
+// runs git in `repo` with `index` as the index file; its stdout, trimmed
+fn git(repo: &Path, index: &Path, learner: &str, args: &[&str], input: Option<&[u8]>) -> io::Result<String> {
+    let mut command = Command::new("git");
+    command.arg("-C").arg(repo).args(args).env("GIT_INDEX_FILE", index);
+    for (variable, value) in [("NAME", learner.to_string()), ("EMAIL", format!("{learner}@quiz"))] {
+        command.env(format!("GIT_AUTHOR_{variable}"), &value).env(format!("GIT_COMMITTER_{variable}"), value);
+    }
+    let mut child = command.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        stdin.write_all(input)?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("git {}: {}", args.join(" "), stderr.trim())));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// true if `repo` is a repository of its own, bare or not; a directory
+// inside some other repository's work tree isn't
+fn is_repository(repo: &Path) -> bool {
+    let output = Command::new("git").arg("-C").arg(repo).args(["rev-parse", "--absolute-git-dir"]).stderr(Stdio::null()).output();
+    let Ok(output) = output else { return false };
+    if !output.status.success() {
+        return false;
+    }
+    let git_dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    let same = |dir: &Path| matches!((dir.canonicalize(), git_dir.canonicalize()), (Ok(a), Ok(b)) if a == b);
+    same(repo) || same(&repo.join(".git"))
+}
+
+// `repo` as it was if it's a repository already, else a new bare one,
+// but only where there's nothing yet: $QUIZ_SOLUTIONS pointed at a
+// directory of other things is an error, not a place to init
+fn repository(repo: &Path) -> io::Result<()> {
+    if is_repository(repo) {
+        return Ok(());
+    }
+    match fs::read_dir(repo).map(|mut entries| entries.next().is_none()) {
+        Ok(false) => {
+            let message = format!("{} isn't a git repository, and isn't empty: QUIZ_SOLUTIONS wants one or the other", repo.display());
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, message));
+        }
+        Ok(true) => {}
+        Err(e) if e.kind() == io::ErrorKind::NotFound => fs::create_dir_all(repo)?,
+        Err(e) => return Err(io::Error::new(e.kind(), format!("{}: {e}", repo.display()))),
+    }
+    let output = Command::new("git").arg("init").arg("--bare").arg("--quiet").arg(repo).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!("git init: {}", String::from_utf8_lossy(&output.stderr).trim())));
+    }
+    Ok(())
+}
+
+// `code` as `path` on the learner's branch; the new commit's short hash,
+// or None if the branch has it like that already
+fn commit(repo: &Path, learner: &str, path: &str, code: &str, message: &str) -> io::Result<Option<String>> {
+    repository(repo)?;
+    // the scratch index in a directory of its own, made fresh: nobody
+    // else's file can be there first
+    let scratch = tempfile::TempDir::new()?;
+    let index = scratch.path().join("index");
+    let git = |args: &[&str], input: Option<&[u8]>| git(repo, &index, learner, args, input);
+    let branch = format!("refs/heads/{}", branch(learner));
+    let parent = git(&["rev-parse", "--verify", "--quiet", &branch], None).ok().filter(|p| !p.is_empty());
+    match &parent {
+        Some(parent) => git(&["read-tree", parent], None)?,
+        None => git(&["read-tree", "--empty"], None)?,
+    };
+    let blob = git(&["hash-object", "-w", "--stdin"], Some(code.as_bytes()))?;
+    git(&["update-index", "--add", "--cacheinfo", &format!("100644,{blob},{path}")], None)?;
+    let tree = git(&["write-tree"], None)?;
+    let old_tree = match &parent {
+        Some(parent) => Some(git(&["rev-parse", &format!("{parent}^{{tree}}")], None)?),
+        None => None,
+    };
+    if old_tree.as_ref() == Some(&tree) {
+        return Ok(None);
+    }
+    let mut args = vec!["commit-tree", &tree, "-m", message];
+    if let Some(parent) = &parent {
+        args.extend(["-p", parent]);
+    }
+    let commit = git(&args, None)?;
+    git(&["update-ref", &branch, &commit, parent.as_deref().unwrap_or("")], None)?;
+    Ok(Some(commit[..7.min(commit.len())].to_string()))
+}
+
+// for `quiz verify ID` without a FILE: the question's broken code in a
+// scratch file, opened in the editor. The file stays, so verifying again
+// starts from the last try
+pub fn draft(packs: &[Pack], reference: &str, config: &Config) -> io::Result<PathBuf> {
+    let Some((_, question)) = classroom::find(packs, reference) else {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("no question {reference} (PACK/QUESTION)")));
+    };
+    let Kind::Fix { broken, .. } = &question.kind else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{reference} isn't a fix question")));
+    };
+    let file = std::env::temp_dir().join(format!("quiz-{}.rs", reference.replace('/', "-")));
+    if !file.exists() {
+        fs::write(&file, broken.trim_start())?;
+    }
+    if !config.edit(&file)? {
+        return Err(io::Error::other(format!("the editor ({}) failed", config.editor())));
+    }
+    Ok(file)
+}
+
+// `quiz verify`: true if the solution passes
+pub fn verify(packs: &[Pack], reference: &str, file: &Path, learner: &str, profile: &Profile) -> io::Result<bool> {
+    let Some((pack, question)) = classroom::find(packs, reference) else {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("no question {reference} (PACK/QUESTION)")));
+    };
+    let Kind::Fix { broken, solved, .. } = &question.kind else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{reference} isn't a fix question")));
+    };
+    let code = fs::read_to_string(file).map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", file.display())))?;
+
+    let fix = fixes::grade(broken, solved, &code, pack.edition_of(question))?;
+    let passed = matches!(fix, Fix::Passes);
+    match fix {
+        Fix::Missing(missing) => println!("{reference}: no {} any more; the fix has to keep them", missing.join(", ")),
+        Fix::Fails(verdict) => println!("{reference}: not yet, {}", verdict.summary()),
+        Fix::Differs(verdict) => {
+            println!("{reference}: compiles, but not the way the solution's items are used: {}", verdict.summary())
+        }
+        Fix::Passes => println!("{reference}: compiles, and fits everything the solution defines"),
+    }
+    if let Err(e) = progress::record(profile, &[progress::Attempt::now(&pack.id, &question.id, passed)]) {
+        eprintln!("quiz: cannot record progress: {e}");
+    }
+
+    if let (true, Some(repo)) = (passed, solutions_dir()) {
+        let message = format!("{reference}: passes\n\n{}", harness::rustc_version()?);
+        match commit(&repo, learner, &format!("{reference}.rs"), &code, &message)? {
+            Some(hash) => println!("committed {hash} to {} in {}", branch(learner), repo.display()),
+            None => println!("the same as the last solution on {}, nothing to commit", branch(learner)),
+        }
+    }
+    Ok(passed)
+}
+
+// `quiz history`: the learner's commits of that solution, oldest first
+pub fn history(reference: &str, learner: &str) -> io::Result<()> {
+    let Some(repo) = solutions_dir() else {
+        return Err(io::Error::new(io::ErrorKind::NotFound, "no solutions kept: set QUIZ_SOLUTIONS to a directory"));
+    };
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(&repo)
+        .args(["log", "--reverse", "-p", "--date=format:%Y-%m-%d %H:%M", "--format=%h  %ad  %s", &branch(learner), "--"])
+        .arg(format!("{reference}.rs"))
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("no solutions from {learner} in {}", repo.display())));
+    }
+    Ok(())
+}
@@ -0,0 +1,84 @@
+// QUIZ: HOW A NOTES FILE SPLITS ------------------------------------------
+
+/*
+ * The headings of a notes file, and the paragraphs of code between
+ * them. notes.rs splits the files with this, and so does the langscape
+ * crate's registry (src/registry.rs, which has this file as a module of
+ * its own), so the two can't disagree about where a snippet starts. It
+ * needs nothing from the rest of the quiz, and has to stay that way.
+ *
+ * A heading is `// TITLE -----` or `// TITLE =====` for a part,
+ * `// Title . . .` for a section in one. A paragraph is the lines
+ * between two blank lines, outside block comments; a blank line inside
+ * braces doesn't end one.
+ */
+
+use std::ops::Range;
+
+// "// SLICE -----" -> ("SLICE", true); "// Updating a string . . ." ->
+// ("Updating a string", false)
+pub fn heading(line: &str) -> Option<(&str, bool)> {
+    let text = line.strip_prefix("// ")?.trim_end();
+    let (title, part) = match text.strip_suffix(". . .") {
+        Some(title) => (title, false),
+        None if text.ends_with("---") || text.ends_with("===") => (text.trim_end_matches(['-', '=']), true),
+        None => return None,
+    };
+    let title = title.trim();
+    (!title.is_empty()).then_some((title, part))
+}
+
+pub struct Paragraph {
+    pub first: usize,                  // line number, from 1
+    pub bytes: Range<usize>,           // in the text, without the last line's newline
+    pub comments: usize,               // block comments before it
+}
+
+// every paragraph, comments only or not, in order
+pub fn paragraphs(text: &str) -> Vec<Paragraph> {
+    let mut paragraphs = Vec::new();
+    let mut current: Option<Paragraph> = None;
+    let (mut in_comment, mut comments, mut depth, mut offset) = (false, 0, 0i32, 0);
+
+    for (n, raw) in (1..).zip(text.split_inclusive('\n')) {
+        let (start, line) = (offset, raw.trim_end_matches(['\n', '\r']));
+        offset += raw.len();
+        let trimmed = line.trim();
+        let opens_comment = !in_comment && trimmed.starts_with("/*");
+        if in_comment || opens_comment {
+            in_comment = !trimmed.contains("*/");
+            paragraphs.extend(current.take());
+            comments += usize::from(opens_comment);
+            continue;
+        }
+        if trimmed.is_empty() && depth == 0 {
+            paragraphs.extend(current.take());
+            continue;
+        }
+        let code = line.split("//").next().unwrap_or_default();
+        depth += code.matches('{').count() as i32 - code.matches('}').count() as i32;
+        current.get_or_insert(Paragraph { first: n, bytes: start..start, comments }).bytes.end = start + line.len();
+    }
+    paragraphs.extend(current);
+    paragraphs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paragraphs_end_at_blank_lines_outside_braces() {
+        let text = "let a = 1;\n\nfn f() {\n\n    2\n}\n/*\n * b\n */\n// only a comment\n";
+        let found: Vec<_> = paragraphs(text).into_iter().map(|p| (p.first, &text[p.bytes], p.comments)).collect();
+        assert_eq!(found, [(1, "let a = 1;", 0), (3, "fn f() {\n\n    2\n}", 0), (10, "// only a comment", 1)]);
+    }
+
+    #[test]
+    fn headings() {
+        assert_eq!(heading("// SLICE -----"), Some(("SLICE", true)));
+        assert_eq!(heading("// Updating a string . . ."), Some(("Updating a string", false)));
+        assert_eq!(heading("// ----------"), None);
+        assert_eq!(heading("// a comment"), None);
+    }
+}
@@ -0,0 +1,120 @@
+// QUIZ: UPDATES FROM A REMOTE --------------------------------------------
+
+/*
+ *     quiz update [--remote URL]
+ *
+ * Installs the packages (packages.rs) a remote has that aren't here yet,
+ * and upgrades the ones it has a newer version of. The remote is the
+ * config file's `remote`, or --remote:
+ *
+ *     a git repository    "https://github.com/someone/rust-packs.git"
+ *                         a package at its top, or one per directory;
+ *                         cloned into the content directory's .remote,
+ *                         and fetched again each time
+ *     an index            "https://example.org/packs/index.json"
+ *                         anything ending in .json (a path on disk works
+ *                         too, for a mirror):
+ *
+ *         { "packages": [{ "name": "embedded", "version": "0.3.0",
+ *                          "url": "embedded-0.3.0.tar.gz" }] }
+ *
+ *                         each url a package, as quiz pack install takes
+ *                         it; relative to the index's
+ *
+ * Only a version newer than the installed one is downloaded, and it goes
+ * through everything quiz pack install checks. Installed files edited
+ * here are merged with the new version, not overwritten: an annotation
+ * added to a topic stays, and so does a question added to a pack. Each
+ * package that changed is listed with what happened to its files, then
+ * the ones that were up to date.
+ */
+
+use crate::packages::{self, MANIFEST, Manifest};
+use semver::Version;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// a package the remote has, and where to install it from
+pub struct Offer {
+    pub name: String,
+    pub version: Version,
+    pub source: String,
+}
+
+#[derive(Deserialize)]
+struct Index {
+    packages: Vec<Listed>,
+}
+
+#[derive(Deserialize)]
+struct Listed {
+    name: String,
+    version: Version,
+    url: String,
+}
+
+// what the remote has; a package it has that can't be read is an error
+// of its own, so the others still come
+pub fn fetch(remote: &str) -> Result<Vec<Result<Offer, String>>, String> {
+    if remote.ends_with(".json") { index(remote) } else { clone(remote) }
+}
+
+fn index(url: &str) -> Result<Vec<Result<Offer, String>>, String> {
+    let index: Index = if url.starts_with("http://") || url.starts_with("https://") {
+        let mut response = ureq::get(url).call().map_err(|e| format!("{url}: {e}"))?;
+        response.body_mut().read_json().map_err(|e| format!("{url}: not an index: {e}"))?
+    } else {
+        let text = fs::read_to_string(url).map_err(|e| format!("{url}: {e}"))?;
+        serde_json::from_str(&text).map_err(|e| format!("{url}: not an index: {e}"))?
+    };
+    // "embedded-0.3.0.tar.gz" next to the index
+    let base = &url[..url.rfind('/').map_or(0, |i| i + 1)];
+    let offers = index.packages.into_iter().map(|listed| {
+        let source = if listed.url.contains("://") || listed.url.starts_with('/') { listed.url } else { format!("{base}{}", listed.url) };
+        Ok(Offer { name: listed.name, version: listed.version, source })
+    });
+    Ok(offers.collect())
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git").arg("-C").arg(dir).args(args).output().map_err(|e| format!("git: {e}"))?;
+    if !output.status.success() {
+        return Err(format!("git {}: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// the repository's latest commit in .remote, only that commit: the
+// history isn't needed
+fn clone(url: &str) -> Result<Vec<Result<Offer, String>>, String> {
+    let content = packages::content_dir();
+    let dir = content.join(".remote");
+    let origin = if dir.join(".git").exists() { git(&dir, &["remote", "get-url", "origin"]).ok() } else { None };
+    if origin.as_deref() == Some(url) {
+        git(&dir, &["fetch", "--quiet", "--depth", "1", "origin"])?;
+        git(&dir, &["reset", "--quiet", "--hard", "FETCH_HEAD"])?;
+    } else {
+        // none yet, or one of another remote
+        if dir.exists() {
+            fs::remove_dir_all(&dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        }
+        fs::create_dir_all(&content).map_err(|e| format!("{}: {e}", content.display()))?;
+        git(&content, &["clone", "--quiet", "--depth", "1", url, ".remote"])?;
+    }
+
+    let roots: Vec<PathBuf> = if dir.join(MANIFEST).exists() {
+        vec![dir]
+    } else {
+        let entries = fs::read_dir(&dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        let mut roots: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path())).filter(|p| p.join(MANIFEST).exists()).collect();
+        roots.sort();
+        roots
+    };
+    let offers = roots.into_iter().map(|root| {
+        let manifest = Manifest::read(&root)?;
+        Ok(Offer { name: manifest.name, version: manifest.version, source: root.display().to_string() })
+    });
+    Ok(offers.collect())
+}
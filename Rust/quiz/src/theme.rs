@@ -0,0 +1,204 @@
+// QUIZ: COLOURS, AND THEMES FOR THEM -------------------------------------
+
+/*
+ * What the quiz colours, and the palettes to do it with:
+ *
+ *     code        keywords, strings, comments, numbers and lifetimes, in
+ *                 questions and in quiz show
+ *     diffs       added and removed lines (quiz snapshots), headers
+ *     feedback    Right / No in practice and adapt, ok / FAIL in the
+ *                 checks, rustc's errors under quiz show
+ *
+ * Three themes, picked with `theme = "light"` in the config file or
+ * --theme on the command line:
+ *
+ *     dark            the default, for a dark background
+ *     light           darker colours, for a light one
+ *     high-contrast   bold and bright, with backgrounds behind the diff
+ *                     and the feedback, for low vision or a projector
+ *
+ * Colour never says anything on its own: the words (Right, FAIL) and
+ * the diff's - and + are there either way. It's only written to a
+ * terminal, so a pipe or a file gets plain text, and NO_COLOR
+ * (https://no-color.org) turns it off. For quiz show through a pager,
+ * the pager has to pass it on: less -R.
+ */
+
+#[derive(Clone, Copy)]
+pub enum Role {
+    Keyword,
+    Str,
+    Comment,
+    Number,
+    Lifetime,
+    Added,
+    Removed,
+    Header,
+    Right,
+    Wrong,
+    Dim,
+}
+
+// SGR parameters, as in "\x1b[1;32m"
+struct Palette {
+    keyword: &'static str,
+    string: &'static str,
+    comment: &'static str,
+    number: &'static str,
+    lifetime: &'static str,
+    added: &'static str,
+    removed: &'static str,
+    header: &'static str,
+    right: &'static str,
+    wrong: &'static str,
+    dim: &'static str,
+}
+
+const DARK: Palette = Palette {
+    keyword: "95",
+    string: "92",
+    comment: "90",
+    number: "93",
+    lifetime: "96",
+    added: "32",
+    removed: "31",
+    header: "36",
+    right: "1;32",
+    wrong: "1;31",
+    dim: "90",
+};
+
+// no yellow, and no bright grey: both fade into white
+const LIGHT: Palette = Palette {
+    keyword: "35",
+    string: "32",
+    comment: "34",
+    number: "31",
+    lifetime: "36",
+    added: "32",
+    removed: "31",
+    header: "34",
+    right: "1;32",
+    wrong: "1;31",
+    dim: "2",
+};
+
+// red and green told apart by more than their hue: the background and
+// the text colour differ too
+const HIGH_CONTRAST: Palette = Palette {
+    keyword: "1;96",
+    string: "1;93",
+    comment: "3;97",
+    number: "1;95",
+    lifetime: "1;92",
+    added: "1;30;102",
+    removed: "1;97;41",
+    header: "1;4;97",
+    right: "1;30;102",
+    wrong: "1;97;41",
+    dim: "97",
+};
+
+pub const NAMES: [&str; 3] = ["dark", "light", "high-contrast"];
+
+pub struct Theme {
+    palette: Option<&'static Palette>, // None: no colour at all
+}
+
+impl Theme {
+    // one of NAMES (config.rs checks; anything else is dark). `colour`
+    // false gives plain text whatever the theme
+    pub fn new(name: &str, colour: bool) -> Theme {
+        let palette = match name {
+            "light" => &LIGHT,
+            "high-contrast" => &HIGH_CONTRAST,
+            _ => &DARK,
+        };
+        Theme { palette: colour.then_some(palette) }
+    }
+
+    // `text` in the role's colour, line by line, so a pager or a diff of
+    // the output never sees a colour run on past a newline
+    pub fn paint(&self, role: Role, text: &str) -> String {
+        let Some(palette) = self.palette else { return text.to_string() };
+        let sgr = match role {
+            Role::Keyword => palette.keyword,
+            Role::Str => palette.string,
+            Role::Comment => palette.comment,
+            Role::Number => palette.number,
+            Role::Lifetime => palette.lifetime,
+            Role::Added => palette.added,
+            Role::Removed => palette.removed,
+            Role::Header => palette.header,
+            Role::Right => palette.right,
+            Role::Wrong => palette.wrong,
+            Role::Dim => palette.dim,
+        };
+        let lines = text.split('\n').map(|l| if l.is_empty() { String::new() } else { format!("\x1b[{sgr}m{l}\x1b[0m") });
+        lines.collect::<Vec<_>>().join("\n")
+    }
+
+    // Rust code, highlighted. A lexer of a few lines, not a parser: it
+    // knows comments, strings, chars, lifetimes, numbers and keywords,
+    // which is what a reader's eye looks for in a snippet
+    pub fn code(&self, code: &str) -> String {
+        if self.palette.is_none() {
+            return code.to_string();
+        }
+        let chars: Vec<char> = code.chars().collect();
+        let mut out = String::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let rest = &chars[i..];
+            let (len, role) = token(rest);
+            let text: String = rest[..len].iter().collect();
+            match role {
+                Some(role) => out += &self.paint(role, &text),
+                None => out += &text,
+            }
+            i += len;
+        }
+        out
+    }
+}
+
+const KEYWORDS: [&str; 40] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static",
+    "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while", "yield", "gen",
+];
+
+// the token at the start of `rest`: its length, and how it's coloured
+fn token(rest: &[char]) -> (usize, Option<Role>) {
+    let word = |from: usize| from + rest[from..].iter().take_while(|c| c.is_alphanumeric() || **c == '_').count();
+    let until = |pattern: &[char]| rest.windows(pattern.len()).position(|w| w == pattern);
+    match rest {
+        ['/', '/', ..] => (until(&['\n']).unwrap_or(rest.len()), Some(Role::Comment)),
+        ['/', '*', ..] => (until(&['*', '/']).map_or(rest.len(), |end| end + 2), Some(Role::Comment)),
+        ['"', ..] => {
+            let mut end = 1;
+            while end < rest.len() && rest[end] != '"' {
+                end += if rest[end] == '\\' { 2 } else { 1 };
+            }
+            ((end + 1).min(rest.len()), Some(Role::Str))
+        }
+        // 'x' and '\n' are chars; 'a on its own is a lifetime
+        ['\'', '\\', _, ..] => (rest[3..].iter().position(|&c| c == '\'').map_or(rest.len(), |end| end + 4), Some(Role::Str)),
+        ['\'', _, '\'', ..] => (3, Some(Role::Str)),
+        ['\'', c, ..] if c.is_alphabetic() || *c == '_' => (word(1), Some(Role::Lifetime)),
+        // 1_000, 0xff, 2.5 and 1u8, but 0..10 stops at the range
+        [c, ..] if c.is_ascii_digit() => {
+            let mut end = word(0);
+            if rest.get(end) == Some(&'.') && rest.get(end + 1).is_some_and(char::is_ascii_digit) {
+                end = word(end + 1);
+            }
+            (end, Some(Role::Number))
+        }
+        [c, ..] if c.is_alphabetic() || *c == '_' => {
+            let end = word(0);
+            let text: String = rest[..end].iter().collect();
+            (end, KEYWORDS.contains(&text.as_str()).then_some(Role::Keyword))
+        }
+        _ => (1, None),
+    }
+}
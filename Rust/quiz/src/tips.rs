@@ -0,0 +1,107 @@
+// QUIZ: A TIP FOR THE LOGIN MESSAGE --------------------------------------
+
+/*
+ *     quiz random        a snippet from a topic already finished
+ *
+ * One short snippet from the notes, with what they say about it, for
+ * the message at login: in ~/.bashrc, or a script in /etc/update-motd.d,
+ *
+ *     quiz random 2>/dev/null
+ *
+ *     ownership.rs:154  REFERENCE
+ *
+ *     // mutable references allows modifying borrowed values
+ *
+ *     fn main() {
+ *         let mut s = String::from("hello");       // necessary to be mutable
+ *
+ *         change(&mut s);
+ *     }
+ *
+ * A tip is a code paragraph (notes::snippets) of at most MAX_LINES lines
+ * that starts unindented and closes every brace it opens, so it reads on
+ * its own, with the comment lines right above it, which say what it is.
+ * A paragraph without them isn't one.
+ *
+ * It comes from a topic finished (path.rs: every part read and every
+ * question answered), picked with a weight of one more than the days
+ * since it was last read or practised: the ones longest ago, due for a
+ * review, come up most. Within the topic every tip is as likely. With
+ * nothing finished yet, it's one line pointing to quiz next.
+ */
+
+use crate::bank::Pack;
+use crate::notes;
+use crate::progress::{Attempt, Read, day};
+use crate::providers::Topic;
+use std::hash::{BuildHasher, Hasher};
+
+const MAX_LINES: usize = 12;
+const MAX_COMMENT: usize = 6;          // lines of comment above it
+
+pub struct Tip {
+    pub line: usize,                   // the comment's first
+    pub heading: Option<String>,
+    pub text: String,                  // the comment, a blank line, the code
+}
+
+// the comment lines right above line `line`, blank lines between them
+// and it skipped
+fn comment_above(lines: &[&str], line: usize) -> Option<(usize, Vec<String>)> {
+    let mut end = line - 1;
+    while end > 0 && lines[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+    let mut start = end;
+    while start > 0 && lines[start - 1].trim_start().starts_with("//") && notes::heading(lines[start - 1]).is_none() {
+        start -= 1;
+    }
+    let comment: Vec<String> = lines[start..end].iter().map(|l| l.trim_end().to_string()).collect();
+    (!comment.is_empty() && comment.len() <= MAX_COMMENT).then_some((start + 1, comment))
+}
+
+// every tip in a topic's text
+pub fn tips(text: &str) -> Vec<Tip> {
+    let lines: Vec<&str> = text.lines().collect();
+    let headings: Vec<(usize, &str)> = (1..).zip(&lines).filter_map(|(n, line)| Some((n, notes::heading(line)?.0))).collect();
+    let mut tips = Vec::new();
+    for (line, code) in notes::snippets(text) {
+        let opened = code.matches('{').count() == code.matches('}').count();
+        if code.lines().count() > MAX_LINES || code.starts_with(char::is_whitespace) || !opened {
+            continue;
+        }
+        let Some((start, comment)) = comment_above(&lines, line) else { continue };
+        let heading = headings.iter().rev().find(|(n, _)| *n < start).map(|(_, title)| title.to_string());
+        tips.push(Tip { line: start, heading, text: format!("{}\n\n{}\n", comment.join("\n"), code.trim_end()) });
+    }
+    tips
+}
+
+// a number from 0 to below `n`, from std: RandomState is seeded from the OS
+fn below(n: u64) -> u64 {
+    std::collections::hash_map::RandomState::new().build_hasher().finish() % n.max(1)
+}
+
+// one of `finished`'s tips, the topics last read or practised longest
+// ago the likeliest
+pub fn random<'a>(finished: &'a [Topic], packs: &[Pack], read: &[Read], history: &[Attempt], now: u64) -> Option<(&'a Topic, Tip)> {
+    let last = |topic: &Topic| {
+        let pack = |question: &str| question.split('/').next().and_then(|id| packs.iter().find(|p| p.id == id));
+        let answered = history.iter().filter(|a| pack(&a.question).is_some_and(|p| p.topic == topic.id)).map(|a| a.at);
+        read.iter().filter(|r| r.topic == topic.id).map(|r| r.at).chain(answered).max().unwrap_or(0)
+    };
+    let mut candidates: Vec<(&Topic, Vec<Tip>, u64)> = finished
+        .iter()
+        .map(|topic| (topic, tips(&topic.text), day(now).saturating_sub(day(last(topic))) + 1))
+        .filter(|(_, tips, _)| !tips.is_empty())
+        .collect();
+    let mut pick = below(candidates.iter().map(|(_, _, weight)| weight).sum());
+    let i = candidates.iter().position(|(_, _, weight)| {
+        let here = pick < *weight;
+        pick = pick.saturating_sub(*weight);
+        here
+    })?;
+    let (topic, mut tips, _) = candidates.swap_remove(i);
+    let tip = tips.swap_remove(below(tips.len() as u64) as usize);
+    Some((topic, tip))
+}
@@ -0,0 +1,237 @@
+// ====================================================
+// SIGNALS AND GRACEFUL SHUTDOWN ======================
+// ====================================================
+
+/*
+ * Ctrl-C sends the process SIGINT; kill, systemd and docker stop send
+ * SIGTERM. Left alone, either ends the process where it stands: no
+ * destructors run, no BufWriter is flushed, a half-written file stays
+ * half-written, a request being answered is cut off. Graceful shutdown
+ * is doing it in order instead:
+ *
+ *     1. catch the signal            (a handler, or a future that waits)
+ *     2. tell every worker           (a flag, a closed channel, a token)
+ *     3. stop taking new work        (the listener, the reader)
+ *     4. drain the work in flight    (finish it, with a deadline)
+ *     5. save, flush, exit
+ *
+ * The signal itself needs a crate (ctrlc for threads) or tokio's
+ * tokio::signal; the parts after are std, and run below. Applied:
+ * projects/pipeline.rs stops its reader on Ctrl-C and lets the stages
+ * drain, projects/url_shortener.rs stops serving and saves its store.
+ */
+
+// CATCHING Ctrl-C --------------------------------------------------------
+
+/*
+ * The ctrlc crate runs a closure on its own thread when SIGINT (and,
+ * with its "termination" feature, SIGTERM) arrives. The closure can't
+ * stop the other threads; all it should do is tell them, by setting a
+ * flag they look at:
+ *
+ *     Cargo.toml: [dependencies]
+ *     ctrlc = "3"
+ *
+ *     let stop = Arc::new(AtomicBool::new(false));
+ *     let flag = stop.clone();
+ *     ctrlc::set_handler(move || {
+ *         if flag.swap(true, Ordering::SeqCst) {
+ *             std::process::exit(130);      // a second Ctrl-C: now
+ *         }
+ *         eprintln!("stopping (Ctrl-C again to quit now)");
+ *     })
+ *     .expect("could not set the Ctrl-C handler");
+ *
+ * The second Ctrl-C is worth the two lines: a shutdown that hangs (a
+ * stuck worker, a network that won't answer) would otherwise leave only
+ * kill -9. 130 is 128 + 2, SIGINT's number, what a shell reports for a
+ * program Ctrl-C ended.
+ */
+
+// A FLAG FOR THE THREADS -------------------------------------------------
+
+// The workers look at the flag between items, never in the middle of
+// one: an item is done, or not started. Below, main sets the flag where
+// the handler would.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+let stop = Arc::new(AtomicBool::new(false));
+let started = Arc::new(AtomicUsize::new(0));
+let finished = Arc::new(AtomicUsize::new(0));
+let workers: Vec<_> = (0..4)
+    .map(|_| {
+        let (stop, started, finished) = (stop.clone(), started.clone(), finished.clone());
+        thread::spawn(move || {
+            while !stop.load(Ordering::SeqCst) {
+                started.fetch_add(1, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(10));   // the item
+                finished.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+    })
+    .collect();
+thread::sleep(Duration::from_millis(55));
+stop.store(true, Ordering::SeqCst);        // Ctrl-C
+for worker in workers {
+    worker.join().unwrap();
+}
+println!("{}", started.load(Ordering::SeqCst) == finished.load(Ordering::SeqCst));   // prints: true
+
+// Relaxed would do for a flag alone; SeqCst costs nothing worth
+// measuring here, and doesn't need a second thought.
+
+// A CLOSED CHANNEL -------------------------------------------------------
+
+/*
+ * A worker waiting in recv() won't see a flag until a message wakes it.
+ * For workers fed by a channel there's a shutdown already built in:
+ * drop the senders. recv() hands over what's still queued, then returns
+ * Err, and `for job in rx` ends. That's stopping and draining in one,
+ * and it's how pipeline.rs shuts down, stage after stage.
+ */
+
+use std::sync::mpsc;
+use std::thread;
+
+let (tx, rx) = mpsc::channel();
+let worker = thread::spawn(move || {
+    let mut done = 0;
+    for _job in rx {
+        done += 1;                          // every queued job, then the loop ends
+    }
+    done
+});
+for job in 0..10 {
+    tx.send(job).unwrap();
+}
+drop(tx);                                   // the shutdown
+println!("drained {} of 10", worker.join().unwrap());   // prints: drained 10 of 10
+
+// To drop what's queued instead of doing it, the worker checks a flag
+// too, and stops at the next job. A worker that waits on something else
+// as well can use recv_timeout and look at the flag each time it wakes.
+
+// DRAINING, WITH A DEADLINE ----------------------------------------------
+
+/*
+ * Waiting for the work in flight should have an end: a worker stuck on
+ * a network call would hold the process up forever. std's join() has no
+ * timeout, but a channel's recv_timeout does: each worker says when it's
+ * done, and main waits for the messages until a deadline.
+ */
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+let (done_tx, done_rx) = mpsc::channel();
+for (id, millis) in [(0, 10), (1, 20), (2, 5000)] {   // worker 2 is stuck
+    let done_tx = done_tx.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(millis));
+        done_tx.send(id).ok();
+    });
+}
+drop(done_tx);
+
+let deadline = Instant::now() + Duration::from_millis(200);
+let mut drained = Vec::new();
+while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+    match done_rx.recv_timeout(left) {
+        Ok(id) => drained.push(id),
+        Err(_) => break,                    // the deadline, or every worker done
+    }
+}
+println!("drained {drained:?}, gave up on the rest");   // prints: drained [0, 1], gave up on the rest
+
+// Returning from main then ends the stuck thread with the process, as
+// std::process::exit would. Whatever it held is cut off, which is why
+// the deadline comes after the others have had their chance.
+
+// IN TOKIO: A SIGNAL IS A FUTURE ------------------------------------------
+
+/*
+ * tokio::signal::ctrl_c() is a future that's ready when Ctrl-C comes,
+ * and tokio::signal::unix::signal(SignalKind::terminate()) a stream of
+ * SIGTERMs. With select! a loop waits for work and the signal at once:
+ *
+ *     loop {
+ *         tokio::select! {
+ *             _ = tokio::signal::ctrl_c() => break,
+ *             Ok((socket, _)) = listener.accept() => {
+ *                 tokio::spawn(handle(socket));
+ *             }
+ *         }
+ *     }
+ *
+ * url_shortener.rs's shutdown_signal() waits for either signal, and
+ * axum's serve(..).with_graceful_shutdown(signal) does steps 3 and 4:
+ * it stops accepting, and returns once the connections open have
+ * finished. Only then does main save the store.
+ */
+
+// CANCELLATION TOKENS ----------------------------------------------------
+
+/*
+ * The flag for tasks is tokio_util's CancellationToken: cancel() once,
+ * and every clone's cancelled().await is ready. A task waits for it next
+ * to its work in select!, and stops between steps. child_token() makes
+ * a token cancelled with its parent, or on its own: a token per
+ * connection, under the server's.
+ *
+ *     Cargo.toml: [dependencies]
+ *     tokio = { version = "1", features = ["full"] }
+ *     tokio-util = { version = "0.7", features = ["rt"] }
+ *
+ *     async fn worker(id: u32, token: CancellationToken) {
+ *         let mut done = 0;
+ *         loop {
+ *             tokio::select! {
+ *                 _ = token.cancelled() => break,
+ *                 _ = job() => done += 1,
+ *             }
+ *         }
+ *         println!("worker {id}: stopped after {done} jobs");
+ *     }
+ *
+ * select! drops the branch that lost: a job() halfway through is
+ * cancelled at its .await, and what it had done so far is lost. If a job
+ * must finish once begun, look at token.is_cancelled() between jobs
+ * instead of racing it.
+ *
+ * TaskTracker, also from tokio_util, is step 4: spawn through it,
+ * close() it when no more tasks will come, and wait() is ready when the
+ * last one has finished. tokio::time::timeout puts the deadline on it:
+ *
+ *     let token = CancellationToken::new();
+ *     let tracker = TaskTracker::new();
+ *     for id in 0..4 {
+ *         tracker.spawn(worker(id, token.child_token()));
+ *     }
+ *     tracker.close();
+ *
+ *     tokio::signal::ctrl_c().await?;
+ *     token.cancel();
+ *     if timeout(Duration::from_secs(10), tracker.wait()).await.is_err() {
+ *         eprintln!("gave up on {} tasks", tracker.len());
+ *     }
+ *
+ * Returning from main then drops the runtime, and with it every task
+ * still running, at its next .await.
+ */
+
+/*
+ * Which tool tells the workers:
+ *
+ *     threads, a loop of items         Arc<AtomicBool>, looked at between
+ *     threads fed by a channel         drop the senders: drains, then ends
+ *     tasks                            CancellationToken, in select!
+ *     state, more than stop/go         a watch channel ([[async_sync#watch]])
+ *
+ * And whichever, a deadline on the drain, and a second Ctrl-C that
+ * doesn't wait for it.
+ */
@@ -83,11 +83,12 @@ let subject = AlwaysEqual;
 //           you often see borrows of structs
 
 // Tuples and arrays can be printed directly with println! macro
-//           using {:?} formatter
+//           using {:?} formatter (the rest of what goes in the braces:
+//           [[formatting#display and debug]])
 
 let tuple1 = (729, 2.18, true, "Roza");
-println!("The tuple is: {0:?}", tuple1);            // print the tuple
-println!("The name is: {0:?}", tuple1.3);           // print indexed element
+println!("The tuple is: {0:?}", tuple1);            // prints: The tuple is: (729, 2.18, true, "Roza")
+println!("The name is: {0:?}", tuple1.3);           // prints: The name is: "Roza"
 
 // To extend this to structures we use
 
@@ -103,6 +104,7 @@ let rect1 = Rectangle {
 };
 
 println!("The struct instance is: {:?}", rect1);    // {:#?} for pretty print
+                                                    // prints: The struct instance is: Rectangle { length: 42, width: 36 }
 
 // dbg! ()                 takes ownership of an expression 
 // println! ()             takes reference
@@ -0,0 +1,197 @@
+// ====================================================
+// TESTS THAT TOUCH FILES: TEMPORARY DIRECTORIES ======
+// ====================================================
+
+/*
+ * The examples in error_handling.rs open "esabi.txt": a name, so a path
+ * relative to wherever the program was started. Testing code like that
+ * goes wrong in ways that have nothing to do with the code:
+ *
+ *     where        cargo test runs in the package's directory, so the
+ *                  file has to be there, and a test that creates it
+ *                  leaves it there, in git status
+ *     together     the tests of a crate run at once, on threads of one
+ *                  process: two tests writing esabi.txt write the same
+ *                  file, and each passes alone and fails now and then
+ *                  with the other
+ *     after        a test that fails halfway leaves its files behind,
+ *                  and the next run starts from them
+ *
+ * The fix is in two parts: the code takes the path, and each test makes
+ * a directory of its own to point it at, removed when the test ends.
+ */
+
+// TAKE THE PATH ----------------------------------------------------------
+
+// error_handling.rs's read_username_from_file, with the file to read
+// as a parameter ([[api_design#impl asref: whatever can be seen as one]]
+// for why impl AsRef<Path>). The program passes "esabi.txt"; a test
+// passes a file it made.
+
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::Path;
+
+fn read_username(path: impl AsRef<Path>) -> Result<String, io::Error> {
+    let mut username = String::new();
+    File::open(path)?.read_to_string(&mut username)?;
+    Ok(username)
+}
+
+let path = std::env::temp_dir().join(format!("esabi-{}.txt", std::process::id()));
+fs::write(&path, "ferris").unwrap();
+println!("{:?}", read_username(&path));    // prints: Ok("ferris")
+fs::remove_file(&path).unwrap();
+println!("{:?}", read_username(&path).map_err(|e| e.kind()));   // prints: Err(NotFound)
+
+/*
+ * The same for a directory, when the code writes several files: take
+ * the directory, and join the names onto it. And for anything else
+ * from outside: a function that reads $HOME, or the current directory,
+ * is easier to test if main reads it and passes it in. (For a server,
+ * what's passed in is a trait: [[test_doubles#the seam]].)
+ */
+
+// A TEMPORARY DIRECTORY BY HAND ------------------------------------------
+
+/*
+ * What each test needs: a directory nobody else is using, gone at the
+ * end, pass or panic. std has the pieces: std::env::temp_dir() for
+ * where, the process id and a counter for a name no other test has,
+ * and Drop, which runs when the test's function ends or unwinds from a
+ * panic. (quiz/src/packages.rs uses the crate's, below.)
+ */
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> std::io::Result<TempDir> {
+        let n = NEXT.fetch_add(1, Ordering::SeqCst);
+        let path = std::env::temp_dir().join(format!("notes-{}-{n}", std::process::id()));
+        fs::create_dir(&path)?;            // create_dir, not _all: fails if it's there
+        Ok(TempDir(path))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        fs::remove_dir_all(&self.0).ok();   // nothing to do about an error in a drop
+    }
+}
+
+let (a, b) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+fs::write(a.path().join("esabi.txt"), "in a").unwrap();
+fs::write(b.path().join("esabi.txt"), "in b").unwrap();
+println!("{}", fs::read_to_string(a.path().join("esabi.txt")).unwrap());   // prints: in a
+let kept = a.path().to_path_buf();
+drop(a);
+println!("{}", kept.exists());             // prints: false
+
+// The tempfile crate is this, done with more care: names that are
+// random rather than guessed (another user on the machine can't make the
+// directory first), and the file permissions set so only you can read.
+
+// THE tempfile CRATE -----------------------------------------------------
+
+/*
+ *     Cargo.toml: [dev-dependencies]
+ *     tempfile = "3"
+ *
+ *     tempdir()              a TempDir: a new directory, removed on drop
+ *     NamedTempFile::new()   a file with a path, removed on drop
+ *     tempfile()             a File with no name at all, for scratch space
+ *                            the code under test gets as a File
+ *     dir.keep()             keep the directory (it returns the path), to
+ *                            look at what a failing test left
+ *
+ * A dev-dependency is only built for tests, examples and benches: the
+ * program doesn't carry it. The tests for error_handling.rs's examples,
+ * with read_username above and its open-or-create, taking a path too:
+ *
+ *     fn open_or_create(path: impl AsRef<Path>) -> io::Result<File> {
+ *         let path = path.as_ref();
+ *         match File::open(path) {
+ *             Err(e) if e.kind() == ErrorKind::NotFound => File::create(path),
+ *             other => other,
+ *         }
+ *     }
+ *
+ *     #[cfg(test)]
+ *     mod tests {
+ *         use super::*;
+ *         use std::io::Write;
+ *         use tempfile::{tempdir, NamedTempFile};
+ *
+ *         #[test]
+ *         fn reads_the_username() {
+ *             let dir = tempdir().unwrap();
+ *             let path = dir.path().join("esabi.txt");
+ *             fs::write(&path, "ferris").unwrap();
+ *             assert_eq!(read_username(&path).unwrap(), "ferris");
+ *         }
+ *
+ *         #[test]
+ *         fn a_missing_file_is_not_found() {
+ *             let dir = tempdir().unwrap();
+ *             let error = read_username(dir.path().join("esabi.txt")).unwrap_err();
+ *             assert_eq!(error.kind(), ErrorKind::NotFound);
+ *         }
+ *
+ *         #[test]
+ *         fn creates_the_file_when_missing() {
+ *             let dir = tempdir().unwrap();
+ *             let path = dir.path().join("esabi.txt");
+ *             open_or_create(&path).unwrap();
+ *             assert!(path.exists());
+ *         }
+ *
+ *         #[test]
+ *         fn one_file_is_enough() {
+ *             let mut file = NamedTempFile::new().unwrap();
+ *             write!(file, "ferris").unwrap();
+ *             assert_eq!(read_username(file.path()).unwrap(), "ferris");
+ *         }
+ *     }
+ *
+ * Each test has esabi.txt, a different one, and cargo test runs them
+ * all at once without them meeting. Keep the TempDir in a variable
+ * until the end: `let path = tempdir().unwrap().path().join(..)` drops
+ * the directory at the end of the line, and the path is to nothing.
+ */
+
+// SHARED FIXTURES --------------------------------------------------------
+
+/*
+ * Files a test reads and never changes (sample inputs, expected
+ * outputs) can live in the repository, in tests/fixtures say, shared by
+ * every test. Find them from the crate, not the current directory:
+ *
+ *     let input = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/users.csv");
+ *
+ * What's race-prone is sharing anything that's written:
+ *
+ *     a fixture the code changes    copy it into a tempdir first, and
+ *                                   point the code at the copy
+ *     one output file for all       a tempdir per test, so two outputs
+ *                                   never have one name
+ *     set_current_dir, set_var      the whole process's: a test that
+ *                                   changes them changes them under
+ *                                   every test running beside it; pass
+ *                                   the directory or value in instead
+ *     a cleanup at the end          won't run when the test panics
+ *                                   first; Drop does
+ *
+ * cargo test -- --test-threads=1 makes the races go away, and hides
+ * them: the test suite is then as slow as its tests end to end, and a
+ * race that's still in the code is found by users instead.
+ */
@@ -0,0 +1,320 @@
+// ====================================================
+// TEST DOUBLES: FAKING WHAT THE CODE TALKS TO ========
+// ====================================================
+
+/*
+ * Code that talks to the outside world (a server, a clock, a payment
+ * provider) is awkward to test against the real thing: the server is
+ * slow, or down, or charges per call, and the cases most worth testing,
+ * a 503 and then a 200, a timeout, a malformed answer, are the ones it
+ * can't be asked to produce.
+ *
+ * A test double stands in for the real thing during a test. There are
+ * a few kinds, by what the test wants from it:
+ *
+ *     stub       answers with what the test scripted, nothing more
+ *     fake       a working, simpler version: a HashMap for a database,
+ *                answers kept in memory instead of a server
+ *     spy        a stub or fake that also records how it was called,
+ *                for the test to check after
+ *     mock       told in advance what calls to expect, and fails the
+ *                test when they don't come, or others do
+ *
+ * Each needs the same thing from the code: a seam, a place where the
+ * real thing can be swapped out. In Rust the seam is a trait. Applied
+ * below to projects/downloader.rs's retry loop.
+ */
+
+// THE SEAM ---------------------------------------------------------------
+
+/*
+ * downloader.rs's download() calls reqwest directly, and its tests need
+ * a server on a socket (its test_server()) to make a 503 happen. Below
+ * is the same loop with what it needs from HTTP as a trait, and the
+ * sleeping passed in too: a test that waits out real backoffs is a slow
+ * test, and can't see how long it was asked to wait.
+ *
+ * The program implements the trait once, for the real client, and
+ * passes std::thread::sleep:
+ *
+ *     struct Reqwest(reqwest::blocking::Client);
+ *
+ *     impl Http for Reqwest {
+ *         fn get(&self, url: &str) -> Result<Response, String> {
+ *             let response = self.0.get(url).send().map_err(|e| e.to_string())?;
+ *             let status = response.status().as_u16();
+ *             Ok(Response { status, body: response.text().map_err(|e| e.to_string())? })
+ *         }
+ *     }
+ *
+ *     fetch(&Reqwest(Client::new()), url, std::thread::sleep)
+ *
+ * A generic (impl Http) costs nothing at run time; &dyn Http works as
+ * well, when the client is chosen while running
+ * ([[traits#trait objects: dynamic polymorphism]]). Keep the trait
+ * narrow: the one or two calls the code makes, in the code's own types.
+ * A trait that copies reqwest's whole API is as hard to fake as reqwest.
+ */
+
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+// what the retry loop needs from an HTTP client, and no more
+trait Http {
+    fn get(&self, url: &str) -> Result<Response, String>;   // Err: the network failed
+}
+
+const MAX_ATTEMPTS: u32 = 4;
+
+// 5xx and network errors are retried, 200ms, 400ms, ... apart
+fn fetch(http: &impl Http, url: &str, mut sleep: impl FnMut(Duration)) -> Result<String, String> {
+    let mut backoff = Duration::from_millis(200);
+    let mut attempt = 1;
+    loop {
+        let (retryable, error) = match http.get(url) {
+            Ok(response) if response.status == 200 => return Ok(response.body),
+            Ok(response) => (response.status >= 500, format!("server answered {}", response.status)),
+            Err(network) => (true, network),
+        };
+        if !retryable || attempt == MAX_ATTEMPTS {
+            return Err(error);
+        }
+        sleep(backoff);
+        backoff *= 2;
+        attempt += 1;
+    }
+}
+
+// A STUB -----------------------------------------------------------------
+
+// Answers in the order given. The RefCell is because get takes &self,
+// as the real client's does, and the stub still has to move on to its
+// next answer.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+struct Scripted(RefCell<VecDeque<Result<Response, String>>>);
+
+impl Scripted {
+    fn new(answers: Vec<Result<Response, String>>) -> Self {
+        Scripted(RefCell::new(answers.into()))
+    }
+}
+
+impl Http for Scripted {
+    fn get(&self, _url: &str) -> Result<Response, String> {
+        self.0.borrow_mut().pop_front().unwrap_or_else(|| Err("no more answers".to_string()))
+    }
+}
+
+fn answer(status: u16, body: &str) -> Result<Response, String> {
+    Ok(Response { status, body: body.to_string() })
+}
+
+let flaky = Scripted::new(vec![answer(503, ""), Err("connection reset".to_string()), answer(200, "finally")]);
+let mut slept = Vec::new();
+println!("{:?}", fetch(&flaky, "https://example.com/a", |d| slept.push(d)));   // prints: Ok("finally")
+println!("{slept:?}");                      // prints: [200ms, 400ms]
+
+let missing = Scripted::new(vec![answer(404, "")]);
+println!("{:?}", fetch(&missing, "https://example.com/b", |_| panic!("slept")));   // prints: Err("server answered 404")
+
+let down = Scripted::new(vec![answer(503, ""); 5]);
+println!("{:?}", fetch(&down, "https://example.com/c", |_| {}));   // prints: Err("server answered 503")
+
+// Three cases a live server can't be made to produce on demand, and
+// no time spent sleeping. In a crate each is a #[test] with assert_eq!
+// in place of the printing, as in the mockall part below.
+
+// A RECORDING FAKE -------------------------------------------------------
+
+// A stub ignores the URL; a test that cares what was asked wants it
+// kept. This one wraps any other Http and records each request with its
+// answer: a spy, around whatever does the answering.
+
+struct Recording<H> {
+    inner: H,
+    calls: RefCell<Vec<(String, Result<Response, String>)>>,
+}
+
+impl<H: Http> Recording<H> {
+    fn new(inner: H) -> Self {
+        Recording { inner, calls: RefCell::new(Vec::new()) }
+    }
+
+    fn urls(&self) -> Vec<String> {
+        self.calls.borrow().iter().map(|(url, _)| url.clone()).collect()
+    }
+}
+
+impl<H: Http> Http for Recording<H> {
+    fn get(&self, url: &str) -> Result<Response, String> {
+        let result = self.inner.get(url);
+        self.calls.borrow_mut().push((url.to_string(), result.clone()));
+        result
+    }
+}
+
+let http = Recording::new(Scripted::new(vec![answer(502, ""), answer(200, "ok")]));
+fetch(&http, "https://example.com/a", |_| {}).unwrap();
+println!("{:?}", http.urls());              // prints: ["https://example.com/a", "https://example.com/a"]
+
+// RECORD AND REPLAY ------------------------------------------------------
+
+// A scripted stub is only as right as its author's idea of the server.
+// The other way is to record the real server once, save the requests
+// and answers to a file (a cassette), and have the tests replay it: real
+// answers, with no network when the tests run. The recorder is Recording
+// above, around the real client, with a way to write its calls out. The
+// replayer serves them back in order, and refuses a request that isn't
+// the one recorded next: the code has changed what it asks, and the
+// cassette is out of date.
+
+impl<H: Http> Recording<H> {
+    // one line per call: url, then status and body, or ! and the error
+    fn cassette(&self) -> String {
+        let mut out = String::new();
+        for (url, result) in self.calls.borrow().iter() {
+            match result {
+                Ok(r) => out += &format!("{url} {} {}\n", r.status, r.body),
+                Err(e) => out += &format!("{url} ! {e}\n"),
+            }
+        }
+        out
+    }
+}
+
+struct Replay(RefCell<VecDeque<(String, Result<Response, String>)>>);
+
+impl Replay {
+    fn new(cassette: &str) -> Self {
+        let calls = cassette.lines().map(|line| {
+            let mut parts = line.splitn(3, ' ');
+            let (url, status, rest) = (parts.next().unwrap(), parts.next().unwrap(), parts.next().unwrap_or(""));
+            let result = match status {
+                "!" => Err(rest.to_string()),
+                status => Ok(Response { status: status.parse().unwrap(), body: rest.to_string() }),
+            };
+            (url.to_string(), result)
+        });
+        Replay(RefCell::new(calls.collect()))
+    }
+}
+
+impl Http for Replay {
+    fn get(&self, url: &str) -> Result<Response, String> {
+        match self.0.borrow_mut().pop_front() {
+            Some((recorded, result)) if recorded == url => result,
+            Some((recorded, _)) => panic!("asked for {url}, the cassette has {recorded}"),
+            None => panic!("asked for {url}, the cassette has ended"),
+        }
+    }
+}
+
+// the "live" server here is a Scripted; in a project it's the real client
+let live = Recording::new(Scripted::new(vec![answer(503, ""), answer(200, "hello")]));
+fetch(&live, "https://example.com/a", |_| {}).unwrap();
+let cassette = live.cassette();
+print!("{cassette}");
+// prints: https://example.com/a 503
+// prints: https://example.com/a 200 hello
+
+println!("{:?}", fetch(&Replay::new(&cassette), "https://example.com/a", |_| {}));   // prints: Ok("hello")
+
+/*
+ * In a project the cassette is a file under tests/cassettes/, found
+ * with env!("CARGO_MANIFEST_DIR") ([[temp_files#shared fixtures]]), and
+ * re-recorded by running the tests with an environment variable set,
+ * say RECORD=1, that makes them wrap the real client instead. A real
+ * format keeps headers and the method too, and is JSON or YAML rather
+ * than a line each.
+ *
+ * What replay can't catch: the server changing. The cassette is the
+ * server as it was the day it was recorded. A few tests against the
+ * real thing (marked #[ignore], run by hand or nightly with
+ * cargo test -- --ignored) are how that's noticed.
+ */
+
+// MOCKS WITH mockall -----------------------------------------------------
+
+/*
+ * Writing Scripted and Recording by hand is fine for a trait of one
+ * method; for a trait of ten, the mockall crate writes them.
+ * #[automock] on the trait makes a MockHttp with an expect_ method for
+ * each of its methods, and each expectation says what arguments it
+ * matches, how many times it may be called, and what it returns:
+ *
+ *     Cargo.toml: [dev-dependencies]
+ *     mockall = "0.13"
+ *
+ *     #[cfg_attr(test, mockall::automock)]
+ *     trait Http {
+ *         fn get(&self, url: &str) -> Result<Response, String>;
+ *     }
+ *
+ *     #[cfg(test)]
+ *     mod tests {
+ *         use super::*;
+ *         use mockall::predicate::eq;
+ *         use mockall::Sequence;
+ *
+ *         #[test]
+ *         fn retries_a_503() {
+ *             let mut http = MockHttp::new();
+ *             let mut order = Sequence::new();
+ *             http.expect_get()
+ *                 .with(eq("https://example.com/a"))
+ *                 .times(2)
+ *                 .in_sequence(&mut order)
+ *                 .returning(|_| answer(503, ""));
+ *             http.expect_get().times(1).in_sequence(&mut order).returning(|_| answer(200, "finally"));
+ *
+ *             assert_eq!(fetch(&http, "https://example.com/a", |_| {}), Ok("finally".to_string()));
+ *         }
+ *
+ *         #[test]
+ *         fn a_404_is_asked_for_once() {
+ *             let mut http = MockHttp::new();
+ *             http.expect_get().times(1).returning(|_| answer(404, ""));
+ *             assert!(fetch(&http, "https://example.com/b", |_| panic!("slept")).is_err());
+ *         }
+ *     }
+ *
+ * cfg_attr(test, ..) makes the mock only in test builds, so the program
+ * doesn't carry it (nor mockall, as a dev-dependency). A call no
+ * expectation matches panics at the call; an expectation called fewer
+ * times than times() says panics when the mock is dropped, at the end
+ * of the test. Sequence pins the order across expectations: the 200
+ * can't come before the two 503s.
+ *
+ * For a trait in another crate, mock! { .. } takes a copy of its
+ * definition and does the same. For async methods, #[automock] goes
+ * above #[async_trait], or on a trait with async fn, as mockall's
+ * documentation shows for the version in use.
+ */
+
+// WHICH ------------------------------------------------------------------
+
+/*
+ *     a few fixed answers            a stub: a struct and ten lines
+ *     what was asked matters         a recording fake, and assert on
+ *                                    what it kept after
+ *     many methods, exact calls      mockall: expectations written out,
+ *                                    checked for you
+ *     answers should be real         record and replay a cassette
+ *     the server itself is tested    a local server: downloader.rs's
+ *                                    test_server, or wiremock
+ *
+ * The usual failing is too much mock: a test that expects every call
+ * in its exact order tests how the code does its work, and breaks when
+ * that changes for the better. Check what the caller would notice (the
+ * result, the requests that reached the server), and leave the rest
+ * free. A mock is worth its strictness where the calls are the
+ * behaviour: a payment taken once, not twice.
+ */
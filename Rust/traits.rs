@@ -1,7 +1,8 @@
 // Traits and trait objects in Rust
+// (practice: quiz/packs/traits.toml, multiple choice)
 
 // Traits: Defining shared behavior ---------------------------------------
-// like interfaces in Java
+// like interfaces in Java; as bounds on generics: [[generics#trait bound]]
 // basic trait definition
 trait Greet {
     // method signature (no implementation)
@@ -49,14 +50,14 @@ let person = Person { name: "Rust".to_string(), };
 let robot = Robot;
 let color = Color::Blue;
 
-greet_someone(&person);
-greet_someone(&robot);
-greet_someone(&color);
+greet_someone(&person);                 // prints: My name is Rust
+greet_someone(&robot);                  // prints: I am a robot
+greet_someone(&color);                  // prints: Blue
 
 // also possible
-println!("{}", person.say_hello());
-println!("{}", robot.say_hello());
-println!("{}", color.say_hello());
+println!("{}", person.say_hello());     // prints: My name is Rust
+println!("{}", robot.say_hello());      // prints: I am a robot
+println!("{}", color.say_hello());      // prints: Blue
 
 // Static dispatch (monomorphization):
 //        The compiler generates optimized code for each
@@ -88,7 +89,7 @@ for greeter in greeters {
 
 // Key points for trait objects
 // 1. Dynamic dispatch (The method call is resolved at runtime
-//                      (slightly slower than static dispatch))
+//                      (slightly slower than static dispatch, measured below))
 // 2. Must be behind a pointer (&dyn Trait, Box<dyn Trait>, etc.)
 // 3. Object-safe traits only: Traits must not return Self
 //                              or use generic methods
@@ -99,6 +100,9 @@ for greeter in greeters {
 // Flexibility	Less flexible (types known at compile time)	More flexible (heterogeneous collections)
 // Binary Size	Larger (due to monomorphization)	Smaller (one implementation)
 // Use Case	When types are known at compile time	When types vary at runtime (e.g., plugins)
+// How much larger, and slower to build (diagrams/bloat.rs measures it):
+// [[monomorphization#what it costs]]; with 200 types, 675 KB and 11 s
+// generic against 440 KB and 1 s dyn
 
 // default implementation ------------------------------------------------
 trait Greet {
@@ -121,7 +125,7 @@ dynamic_greet(&bob);
 
 // Box<dyn Trait> --------------------------------------------------------
 
-// Box is a smart pointer that allocates its contents on the heap
+// Box is a smart pointer that allocates its contents on the heap ([[ownership]])
 // dyn Trait is a trait object that enables runtime polymorphism (dynamic dispatch).
 // Together, Box<dyn Trait> means a heap-allocated object that implements Trait, where the exact type is determined at runtime.
 
@@ -168,7 +172,39 @@ for animal in animals {
 //         Uses a vtable (virtual method table) at runtime to look up the correct method,
 //         Slightly slower (indirection cost), but flexible.
 
+// How much slower, and why (see diagrams/dispatch.rs): the same sum of
+// areas, over &[Square] through a generic and over &[&dyn Shape], with -O:
+//
+// static_call: &[Square]                      │ dyn_call: &[&dyn Shape]
+// .LBB9_5:                                    │ .LBB8_4:
+//     movsd xmm1, qword ptr [rdi + 8*rcx]     │     movsd qword ptr [rsp + 8], xmm0
+//     movsd xmm2, qword ptr [rdi + 8*rcx + 8] │     mov rdi, qword ptr [r14 + r15]       <- data pointer
+//     mulsd xmm1, xmm1                        │     mov rax, qword ptr [r14 + r15 + 8]   <- vtable pointer
+//     addsd xmm1, xmm0                        │     call qword ptr [rax + 24]            <- area(), 4th slot
+//     mulsd xmm2, xmm2                        │     movsd xmm1, qword ptr [rsp + 8]
+//     addsd xmm2, xmm1                        │     addsd xmm1, xmm0
+//     ...                                     │     ...
+//     add rcx, 4                              │     add r15, 16
+//     cmp rsi, rcx                            │     cmp rbx, r15
+//     jne .LBB9_5                             │     jne .LBB8_4
+//
+// static: area() is inlined (self.0 * self.0 is the mulsd), four squares per
+//         round, no call at all
+// dyn:    one call per element, through the vtable (drop, size, align, then
+//         the methods: area is at 3 * 8 = 24), and the sum goes back to the
+//         stack around every call
+//
+// ns per element, the average over 2000 rounds of 1000 elements
+//   static_call, &[Square]                         0.79 ns    1.0x
+//   dyn_call, &[&dyn Shape], all Squares           6.96 ns    8.8x
+//   dyn_call, Squares and Circles taking turns     6.61 ns    8.4x
+//
+// so "slightly slower" is per call: a few ns. In a tight loop over a tiny
+// method it's ~9x, because what dyn costs is mostly the inlining it
+// prevents. When the method does real work, the difference disappears
+
 // Use Box<dyn Trait> for heterogenous collections
+// (and getting the concrete type back out of one: [[any#downcasting]])
 
 // Alternatives to Box<dyn Trait>
 // Approach	Pros                            Cons
@@ -0,0 +1,142 @@
+// ====================================================
+// UNSAFE RUST ========================================
+// ====================================================
+
+/*
+ * unsafe doesn't turn the borrow checker off. It allows five more things,
+ * and the programmer, not the compiler, promises they're used correctly:
+ *   (1) dereference a raw pointer
+ *   (2) call an unsafe function or method (FFI included, see ffi/)
+ *   (3) access or modify a mutable static variable
+ *   (4) implement an unsafe trait (Send, Sync by hand)
+ *   (5) access fields of a union
+ *
+ * Breaking the promise is undefined behaviour (UB): the program may
+ * crash, print garbage, or, worst, print the right thing today. Miri is
+ * an interpreter for Rust that checks every memory access as it goes:
+ *     rustup +nightly component add miri
+ *     cargo +nightly miri run
+ * or, for one snippet: quiz miri snippet.rs (see quiz/src/notes.rs).
+ * Every example here is run under it by `quiz notes`.
+ */
+
+// raw pointers: *const T and *mut T; making one is safe, using one is not
+
+let mut num = 5;
+let r2 = &raw mut num;                // or: &mut num as *mut i32
+let r1 = r2 as *const i32;            // a *const and a *mut to the same place: fine, they're raw
+unsafe {
+    *r2 += 1;
+    println!("r1 is: {}", *r1);       // prints: r1 is: 6
+}
+// r1 is made from r2 on purpose. With `let r1 = &raw const num;` first and
+// r2 = &raw mut num after it, taking r2 counts as a write to num, which
+// ends r1; reading *r1 is then UB (see the aliasing example below, Miri
+// says "that tag does not exist in the borrow stack")
+
+// a safe function can use unsafe code inside: the usual way to use it,
+// with the check that makes it sound done before the unsafe block
+
+fn split_at_mut(values: &mut [i32], mid: usize) -> (&mut [i32], &mut [i32]) {
+    let len = values.len();
+    let ptr = values.as_mut_ptr();
+    assert!(mid <= len);                // without this, the slices below could run past the end
+    unsafe {
+        (
+            std::slice::from_raw_parts_mut(ptr, mid),
+            std::slice::from_raw_parts_mut(ptr.add(mid), len - mid),
+        )
+    }
+}
+// (&mut values[..mid], &mut values[mid..]) won't compile: two &mut of values at
+// once (E0499). The two halves don't overlap, which the borrow checker can't see
+
+let mut v = vec![1, 2, 3, 4, 5, 6];
+let (a, b) = split_at_mut(&mut v, 3);
+a[0] = 10;
+b[0] = 40;
+println!("{v:?}");                      // prints: [10, 2, 3, 40, 5, 6]
+
+/*
+ * WHAT MIRI CATCHES
+ * All of these compile, and most print a plausible number when run
+ * normally. Under Miri each one stops with the report shown.
+ */
+
+// use after free: the pointer outlives what it points to
+
+let v = vec![1, 2, 3];
+let p = v.as_ptr();
+drop(v);
+println!("{}", unsafe { *p });          // ub: has been freed
+
+// error: Undefined Behavior: memory access failed: alloc238 has been freed, so this pointer is dangling
+//  --> src/main.rs:4:25
+//   |
+// 4 | println!("{}", unsafe { *p });
+//   |                         ^^ Undefined Behavior occurred here
+// help: alloc238 was allocated here:
+//   |
+// 1 | let v = vec![1, 2, 3];
+// help: alloc238 was deallocated here:
+//   |
+// 3 | drop(v);
+
+/*
+ * out of bounds: a[3] would panic, p.add(3) is just an address
+ */
+
+let a = [1, 2, 3];
+let p = a.as_ptr();
+println!("{}", unsafe { *p.add(3) });   // ub: beyond the end of the allocation
+
+// error: Undefined Behavior: memory access failed: attempting to access 4 bytes, but got
+//        alloc152+0xc which is at or beyond the end of the allocation of size 12 bytes
+
+/*
+ * reading uninitialized memory: not "some random number", UB
+ */
+
+let x: i32 = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+println!("{x}");                        // ub: memory is uninitialized
+
+// error: Undefined Behavior: reading memory at alloc154[0x0..0x4], but memory is
+//        uninitialized at [0x0..0x4], and this operation requires initialized memory
+
+/*
+ * an invalid value: a bool is 0 or 1, and the compiler relies on it
+ */
+
+let b: bool = unsafe { std::mem::transmute(3u8) };
+println!("{b}");                        // ub: expected a boolean
+
+// error: Undefined Behavior: constructing invalid value of type bool: encountered 0x03,
+//        but expected a boolean
+
+/*
+ * aliasing: a &mut promises it's the only way to reach the value while
+ * it's in use. Writing through the raw pointer it came from ends that,
+ * so using r afterwards is UB even though nothing is freed
+ */
+
+let mut x = 5;
+let p = &raw mut x;
+let r = unsafe { &mut *p };
+unsafe { *p = 6 };
+*r += 1;                                // ub: does not exist in the borrow stack
+println!("{x}");
+
+// error: Undefined Behavior: attempting a read access using <325> at alloc152[0x0], but
+//        that tag does not exist in the borrow stack for this location
+// help: <325> was created by a Unique retag at offsets [0x0..0x4]    (line 3, &mut *p)
+// help: <325> was later invalidated at offsets [0x0..0x4] by a write access
+//                                                                    (line 4, *p = 6)
+//
+// (the "borrow stack" is Miri's Stacked Borrows model: the rules for
+//  mixing references and raw pointers are still being settled, which is
+//  why Miri calls this one a potential bug. Swap the two writes and it's fine)
+
+/*
+ * Miri only sees what the run does: a branch not taken isn't checked,
+ * and it can't run FFI calls into C. No UB reported is evidence, not proof.
+ */
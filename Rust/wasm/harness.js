@@ -0,0 +1,42 @@
+// WASM: THE JAVASCRIPT SIDE ---------------------------------------------
+//
+// Loads the module built by `wasm-pack build --target nodejs` (see
+// lib.rs) and calls every export. Prints what it gets back and checks it.
+//
+//     node harness.js
+
+const assert = require("node:assert");
+const { count_words, count_words_timed, top_words, word_lengths, WordCounter } = require("./pkg/wordcount_wasm.js");
+
+const text = "It was the best of times it was the worst of times";
+
+// plain functions: the string is copied into wasm memory on each call
+assert.strictEqual(count_words(text), 12);
+assert.strictEqual(count_words_timed(text), 12);            // logs via console.log
+
+// Vec<String> comes back as a JS array; Vec<f64> as a Float64Array
+assert.deepStrictEqual(top_words(text, 3), ["it", "of", "the"]);
+assert.ok(word_lengths("a bb ccc") instanceof Float64Array);
+console.log("lengths", Array.from(word_lengths("a bb ccc")));   // [ 1, 2, 3 ]
+
+// Result::Err is thrown as an Error
+assert.throws(() => top_words(text, 0), /n must be at least 1/);
+
+// a struct is a class; its memory is in wasm, so free it when done
+const counter = new WordCounter();
+counter.feed(text);
+counter.feed("IT IS");
+console.log("count it", counter.count("it"), "distinct", counter.distinct);   // 3 8
+assert.strictEqual(counter.top(), "it");
+counter.free();
+
+// after free() the wrapper is dead: using it throws instead of reading
+// freed memory
+assert.throws(() => counter.count("it"));
+
+// Option<String>::None is undefined
+const empty = new WordCounter();
+assert.strictEqual(empty.top(), undefined);
+empty.free();
+
+console.log("ok");
@@ -0,0 +1,231 @@
+// WASM: RUST IN JAVASCRIPT -----------------------------------------------
+
+/*
+ * The word counter from ffi/ again, compiled to WebAssembly this time
+ * and called from JavaScript. wasm-bindgen generates the JS glue: it
+ * turns JS strings and arrays into something the wasm module can read,
+ * and Rust structs into JS classes.
+ *
+ * The pieces, in this directory:
+ *     lib.rs        the Rust side (this file)
+ *     harness.js    a node script that loads the module and calls it
+ *
+ * cargo new --lib wordcount-wasm
+ * (copy lib.rs into src/lib.rs, harness.js next to Cargo.toml)
+ *
+ * Cargo.toml:
+ * [lib]
+ * crate-type = ["cdylib", "rlib"]
+ *
+ * [dependencies]
+ * wasm-bindgen = "0.2"
+ *
+ * rustup target add wasm32-unknown-unknown
+ * cargo install wasm-pack
+ * wasm-pack build --target nodejs       writes pkg/: the .wasm, the JS
+ *                                       glue, and a .d.ts with the types
+ * node harness.js
+ *
+ * --target web instead gives an ES module for the browser (load it
+ * with `await init()` first); --target bundler is for webpack/vite.
+ *
+ * Why bother: the same code runs natively and in the browser, and
+ * number-crunching in wasm runs at near-native speed with no GC pauses.
+ * Why not: every call across the boundary has a cost (see the end), and
+ * DOM work is still JS's job.
+ */
+
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+// EXPORTING A FUNCTION ---------------------------------------------------
+
+/*
+ * #[wasm_bindgen] on a pub fn exports it to JS under the same name.
+ * For this one, the glue does the following on each call:
+ *     (1) encode the JS string (UTF-16 inside) as UTF-8 with TextEncoder
+ *     (2) allocate space in the module's linear memory and copy it in
+ *     (3) call the wasm function with (pointer, length)
+ *     (4) free the copy afterwards
+ * So &str in a signature means "copied in", not "borrowed from JS":
+ * wasm can't see JS's heap at all.
+ */
+
+#[wasm_bindgen]
+pub fn count_words(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+/*
+ * usize is 32 bits on wasm32, and comes back as a JS number. u64/i64
+ * come back as BigInt (42n), because a JS number only holds integers
+ * exactly up to 2^53.
+ */
+
+// WHAT CROSSES THE BOUNDARY ----------------------------------------------
+
+/*
+ *     Rust                      JavaScript             how
+ *     i8..i32, u8..u32, f32/f64 number                 by value
+ *     i64, u64, i128, u128      bigint                 by value
+ *     bool                      boolean                by value
+ *     char                      string (1 char)        by value
+ *     &str, String              string                 copied, re-encoded
+ *     &[u8], Vec<u8>            Uint8Array             copied
+ *     &[f64], Vec<f64>          Float64Array           copied (same for
+ *                                                      other number types)
+ *     Vec<String>               string[]               copied
+ *     Option<T>                 T | undefined
+ *     Result<T, JsError>        T, or throws Error
+ *     #[wasm_bindgen] struct    class                  a pointer into wasm
+ *                                                      memory, see below
+ *     JsValue                   any                    a handle to a JS value
+ *
+ * Doesn't cross: references to Rust values (no &Foo return types),
+ * tuples, generic functions, trait objects, HashMap. For structured
+ * data, either export a struct with methods, or serialize (the
+ * serde-wasm-bindgen crate turns any Serialize type into a JS object).
+ */
+
+/// The `n` most frequent words, most frequent first (ties:
+/// alphabetical). Throws if `n` is 0.
+#[wasm_bindgen]
+pub fn top_words(text: &str, n: usize) -> Result<Vec<String>, JsError> {
+    if n == 0 {
+        // JsError becomes a real `Error` on the JS side: try/catch works
+        return Err(JsError::new("n must be at least 1"));
+    }
+    let mut counter = WordCounter::new();
+    counter.feed(text);
+    let mut counts: Vec<(&String, &u32)> = counter.counts.iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    Ok(counts.into_iter().take(n).map(|(word, _)| word.clone()).collect())
+}
+
+/// Word lengths as a Float64Array-friendly vector.
+#[wasm_bindgen]
+pub fn word_lengths(text: &str) -> Vec<f64> {
+    text.split_whitespace().map(|w| w.chars().count() as f64).collect()
+}
+
+// STRUCTS AS CLASSES -----------------------------------------------------
+
+/*
+ * An exported struct becomes a JS class. The object JS holds is a thin
+ * wrapper around a pointer into the module's memory; the HashMap itself
+ * never leaves wasm. Fields stay private unless they're Copy types
+ * marked pub (then JS gets a getter/setter).
+ *
+ * The memory is Rust's, so JS should call .free() when done. If it
+ * doesn't, a FinalizationRegistry frees it eventually after the wrapper
+ * is garbage-collected, but "eventually" may be never.
+ *
+ *     const counter = new WordCounter();
+ *     counter.feed("it was the best of times");
+ *     counter.count("it");          // 1
+ *     counter.distinct;             // 6 (a getter, no parentheses)
+ *     counter.free();
+ */
+
+#[wasm_bindgen]
+pub struct WordCounter {
+    counts: HashMap<String, u32>,
+}
+
+#[wasm_bindgen]
+impl WordCounter {
+    // constructor: called by `new WordCounter()` in JS
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WordCounter {
+        WordCounter { counts: HashMap::new() }
+    }
+
+    pub fn feed(&mut self, text: &str) {
+        for word in text.split_whitespace() {
+            *self.counts.entry(word.to_lowercase()).or_insert(0) += 1;
+        }
+    }
+
+    pub fn count(&self, word: &str) -> u32 {
+        self.counts.get(&word.to_lowercase()).copied().unwrap_or(0)
+    }
+
+    // Option<String>: undefined in JS when empty
+    pub fn top(&self) -> Option<String> {
+        self.counts.iter().max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0))).map(|(word, _)| word.clone())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn distinct(&self) -> usize {
+        self.counts.len()
+    }
+}
+
+impl Default for WordCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/*
+ * Borrow rules still apply, checked at run time: while a &mut self
+ * method is running, any other call on the same object throws
+ * "recursive use of an object detected". In single-threaded JS that
+ * only happens through callbacks.
+ */
+
+// CALLING JAVASCRIPT FROM RUST -------------------------------------------
+
+/*
+ * The other direction: declare JS functions in an extern block and
+ * wasm-bindgen imports them. js_namespace picks the object they live
+ * on. (The js-sys and web-sys crates have these declarations for all
+ * of JS's built-ins and the browser APIs, ready-made.)
+ */
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console)]
+    fn log(message: &str);
+
+    #[wasm_bindgen(js_namespace = Date, js_name = now)]
+    fn date_now() -> f64;
+}
+
+/// Counts words and logs how long it took, using JS's clock and
+/// console.
+#[wasm_bindgen]
+pub fn count_words_timed(text: &str) -> usize {
+    let start = date_now();
+    let n = count_words(text);
+    log(&format!("counted {n} words in {} ms", date_now() - start));
+    n
+}
+
+/*
+ * Debugging: a panic in wasm shows up in JS as "RuntimeError:
+ * unreachable", with no message. The console_error_panic_hook crate
+ * installs a panic hook that logs the real message first; call its
+ * set_once() from an exported init function.
+ */
+
+// THE COST OF CROSSING ---------------------------------------------------
+
+/*
+ * A call is cheap; what you pass may not be. Numbers cost nothing,
+ * strings and arrays are copied every time. So:
+ *     (1) Make calls coarse: one call with the whole text, not one per
+ *         word. A loop in JS calling count("w") 100k times spends most
+ *         of its time encoding strings.
+ *     (2) Keep state on the Rust side (WordCounter) instead of passing
+ *         big structures back and forth.
+ *     (3) For large numeric data, JS can write straight into wasm
+ *         memory through a typed-array view and pass only the pointer;
+ *         that is zero-copy, at the price of unsafe code on the Rust
+ *         side.
+ *
+ * Everything above is plain Rust as well: on a native target the
+ * attribute only adds glue, so count_words and WordCounter can be used
+ * and tested with cargo test. Only the imported JS functions (log,
+ * date_now) don't exist outside wasm; calling them natively panics.
+ */
@@ -0,0 +1,49 @@
+// FUZZ TARGET: THE CALCULATOR'S WHOLE PIPELINE ---------------------------
+
+/*
+ * Feeds whatever libFuzzer makes up to projects/calculator.rs's
+ * calculate(): tokenize, parse, eval. Any input may be an error; none
+ * may panic, overflow the stack, or hang. Rust/fuzzing.rs goes through
+ * what it found, and the fix.
+ *
+ * The calculator is a program, and a fuzz target can only call a
+ * library, so the langscape crate has it as a module,
+ * langscape::calculator, and this crate (fuzz/, as cargo fuzz init
+ * makes it) depends on that. It's a member of the workspace, so cargo
+ * build and clippy there build it too; that needs no nightly, only
+ * fuzzing does.
+ *
+ * Each input runs on a thread with a 128 KB stack. Deep nesting finds
+ * no new code to cover, so the fuzzer has no reason to grow an input
+ * towards the thousands of levels an 8 MB stack takes to overflow; on
+ * a small stack, a hundred or so is enough, and that it finds.
+ *
+ * cargo install cargo-fuzz            (once; the fuzzing needs nightly)
+ *
+ * cargo +nightly fuzz run calculate                 until a crash, or Ctrl-C
+ * cargo +nightly fuzz run calculate -- -max_total_time=300
+ * cargo +nightly fuzz run calculate fuzz/artifacts/calculate/crash-..
+ *                                                   run one input again
+ * cargo +nightly fuzz tmin calculate fuzz/artifacts/calculate/crash-..
+ *                                                   shrink it
+ */
+
+#![no_main]
+
+use langscape::calculator::calculate;
+use libfuzzer_sys::fuzz_target;
+
+const STACK: usize = 128 * 1024;
+
+fuzz_target!(|data: &[u8]| {
+    // the calculator takes text: bytes that aren't UTF-8 never reach it
+    if let Ok(source) = std::str::from_utf8(data) {
+        let source = source.to_string();
+        std::thread::Builder::new()
+            .stack_size(STACK)
+            .spawn(move || calculate(&source).ok())
+            .unwrap()
+            .join()
+            .unwrap();                     // a panic in calculate() is a crash here too
+    }
+});
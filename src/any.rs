@@ -0,0 +1,149 @@
+//! Rust/any.rs: TypeId, downcasting, a property bag keyed by type, and
+//! Any from a trait object.
+
+#![allow(clippy::approx_constant)]
+
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+
+// TypeId ------------------------------------------------------------------
+
+pub fn type_ids() -> (bool, bool, &'static str) {
+    (
+        TypeId::of::<u32>() == TypeId::of::<u32>(),     // true
+        TypeId::of::<u32>() == TypeId::of::<i32>(),     // false
+        type_name::<Vec<u32>>(),                        // alloc::vec::Vec<u32>
+    )
+}
+
+// DOWNCASTING ------------------------------------------------------------
+
+pub fn describe(value: &dyn Any) -> String {
+    if let Some(n) = value.downcast_ref::<i32>() {
+        format!("an i32, {n}")
+    } else if let Some(s) = value.downcast_ref::<String>() {
+        format!("a String, {s:?}")
+    } else if value.is::<&str>() {
+        "a &str".to_string()
+    } else {
+        "something else".to_string()
+    }
+}
+
+// A PROPERTY BAG ---------------------------------------------------------
+
+// The TypeId is the key, the Box<dyn Any> the value; the downcast can't
+// fail, because the key says what's in it.
+
+#[derive(Default)]
+pub struct Bag {
+    values: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl Bag {
+    pub fn insert<T: Any>(&mut self, value: T) -> Option<T> {
+        let old = self.values.insert(TypeId::of::<T>(), Box::new(value))?;
+        old.downcast().ok().map(|boxed| *boxed)
+    }
+
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.values.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    pub fn get_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.values.get_mut(&TypeId::of::<T>())?.downcast_mut()
+    }
+}
+
+// newtypes make keys out of types: two Strings would be one entry
+pub struct UserName(pub String);
+pub struct RequestId(pub u64);
+
+pub fn bag() -> Bag {
+    let mut bag = Bag::default();
+    bag.insert(UserName("ada".to_string()));
+    bag.insert(RequestId(7));
+    if let Some(id) = bag.get_mut::<RequestId>() {
+        id.0 += 1;
+    }
+    println!("{} {}", bag.get::<UserName>().unwrap().0, bag.get::<RequestId>().unwrap().0);   // prints: ada 8
+    bag
+}
+
+// ANY FROM A TRAIT OBJECT ------------------------------------------------
+
+// With Any as a supertrait, a &dyn Shape converts to a &dyn Any by
+// itself (trait upcasting, Rust 1.86).
+
+pub trait Shape: Any {
+    fn area(&self) -> f64;
+}
+
+pub struct Square(pub f64);
+pub struct Circle(pub f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 { self.0 * self.0 }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 { 3.14 * self.0 * self.0 }
+}
+
+pub fn count_squares(shapes: &[Box<dyn Shape>]) -> usize {
+    shapes.iter().map(|s| &**s as &dyn Any).filter(|any| any.is::<Square>()).count()
+}
+
+// Careful which thing is made an Any: a Box<dyn Shape> is 'static, so
+// it's an Any itself, and asking it is a question about the Box.
+pub fn box_or_inside(shapes: &[Box<dyn Shape>]) -> (bool, bool) {
+    let first: &dyn Any = &shapes[0];            // the Box
+    let inside: &dyn Any = &*shapes[0];          // the Square
+    (first.is::<Square>(), inside.is::<Square>())
+}
+
+// A Box<dyn Any> is what a panic's payload is: a String for a formatted
+// panic!.
+pub fn panic_payload() -> Option<String> {
+    let what = String::from("full");
+    let payload = std::panic::catch_unwind(|| panic!("disk {what}")).unwrap_err();
+    payload.downcast_ref::<String>().cloned()   // Some("disk full")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn type_ids() {
+        let (same, different, name) = super::type_ids();
+        assert!(same); // prints: true
+        assert!(!different); // prints: false
+        assert_eq!(name, "alloc::vec::Vec<u32>"); // prints: alloc::vec::Vec<u32>
+    }
+
+    #[test]
+    fn downcasting() {
+        assert_eq!(describe(&7), "an i32, 7"); // prints: an i32, 7
+        assert_eq!(describe(&String::from("hi")), "a String, \"hi\""); // prints: a String, "hi"
+        assert_eq!(describe(&"hi"), "a &str"); // prints: a &str
+        assert_eq!(describe(&7u8), "something else"); // prints: something else
+    }
+
+    #[test]
+    fn a_property_bag() {
+        let mut bag = bag();
+        // prints: ada 8
+        assert_eq!((bag.get::<UserName>().unwrap().0.as_str(), bag.get::<RequestId>().unwrap().0), ("ada", 8));
+        assert!(bag.get::<f64>().is_none()); // prints: true
+        assert_eq!(bag.insert(RequestId(9)).map(|old| old.0), Some(8)); // prints: 8
+    }
+
+    #[test]
+    fn any_from_a_trait_object() {
+        let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Square(2.0)), Box::new(Circle(1.0))];
+        assert_eq!(count_squares(&shapes), 1); // prints: 1
+        assert_eq!(box_or_inside(&shapes), (false, true)); // prints: false true
+        assert_eq!(panic_payload().as_deref(), Some("disk full")); // prints: Some("disk full")
+    }
+}
@@ -0,0 +1,144 @@
+//! Rust/api_design.rs: signatures that take more, each as a before and
+//! an after.
+
+// the befores are what clippy's ptr_arg flags, on purpose
+#![allow(clippy::ptr_arg)]
+
+use std::path::{Path, PathBuf};
+
+// BORROW THE SLICE, NOT THE CONTAINER ------------------------------------
+
+/// Before: a `&Vec<u32>` only takes a Vec.
+///
+/// ```compile_fail,E0308
+/// # use langscape::api_design::total_before as total;
+/// println!("{}", total(&[1, 2, 3]));
+/// ```
+pub fn total_before(numbers: &Vec<u32>) -> u32 {
+    numbers.iter().sum()
+}
+
+// After: &[u32] takes an array, a slice of a Vec, and a &Vec<u32> still,
+// since it derefs to &[u32].
+pub fn total(numbers: &[u32]) -> u32 {
+    numbers.iter().sum()
+}
+
+// impl AsRef: WHATEVER CAN BE SEEN AS ONE ---------------------------------
+
+// before
+pub fn extension_before(path: &PathBuf) -> Option<String> {
+    Some(path.extension()?.to_str()?.to_string())
+}
+
+// after: &str, String, &Path and PathBuf all implement AsRef<Path>
+pub fn extension(path: impl AsRef<Path>) -> Option<String> {
+    Some(path.as_ref().extension()?.to_str()?.to_string())
+}
+
+// impl IntoIterator: ANY COLLECTION, OR NONE -----------------------------
+
+// before: the caller collects into a Vec first
+pub fn longest_before(words: Vec<String>) -> usize {
+    words.iter().map(|w| w.len()).max().unwrap_or(0)
+}
+
+// after: anything that can be iterated, of &str or String
+pub fn longest(words: impl IntoIterator<Item = impl AsRef<str>>) -> usize {
+    words.into_iter().map(|w| w.as_ref().len()).max().unwrap_or(0)
+}
+
+// RETURN impl Iterator ---------------------------------------------------
+
+// before
+pub fn evens_before(numbers: &[u32]) -> Vec<u32> {
+    numbers.iter().copied().filter(|n| n % 2 == 0).collect()
+}
+
+// after: the '_ says the iterator borrows `numbers`
+pub fn evens(numbers: &[u32]) -> impl Iterator<Item = u32> + '_ {
+    numbers.iter().copied().filter(|n| n % 2 == 0)
+}
+
+// impl Into: CONSTRUCTORS THAT TAKE EITHER -------------------------------
+
+// before
+pub struct UserBefore {
+    pub name: String,
+}
+
+impl UserBefore {
+    pub fn new(name: String) -> UserBefore {
+        UserBefore { name }
+    }
+}
+
+// after: a String moves in as it is, a &str is copied once, inside
+pub struct User {
+    pub name: String,
+    pub email: Option<String>,
+}
+
+impl User {
+    pub fn new(name: impl Into<String>) -> User {
+        User { name: name.into(), email: None }
+    }
+
+    pub fn with_email(mut self, email: impl Into<String>) -> User {
+        self.email = Some(email.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn borrow_the_slice() {
+        let v = vec![1, 2, 3, 4];
+        assert_eq!(total(&v), 10); // prints: 10
+        assert_eq!(total(&v[1..]), 9);
+        assert_eq!(total(&[5, 5]), 10);
+    }
+
+    #[test]
+    fn as_ref() {
+        assert_eq!(extension_before(&PathBuf::from("notes.rs")), Some("rs".to_string())); // prints: Some("rs")
+        let owned = PathBuf::from("b.toml");
+        assert_eq!(extension("a.rs"), Some("rs".to_string()));
+        assert_eq!(extension(String::from("x.json")), Some("json".to_string()));
+        assert_eq!(extension(&owned), Some("toml".to_string()));
+        assert_eq!(extension(Path::new("README")), None);
+    }
+
+    #[test]
+    fn into_iterator() {
+        assert_eq!(longest_before(vec!["a".to_string(), "abc".to_string()]), 3); // prints: 3
+        let set: HashSet<&str> = ["ab", "abcd"].into_iter().collect();
+        assert_eq!(longest(["a", "abc"]), 3);
+        assert_eq!(longest(vec![String::from("hello")]), 5);
+        assert_eq!(longest(set), 4);
+        assert_eq!(longest("one two three".split(' ')), 5);
+        assert_eq!(longest(Vec::<String>::new()), 0); // prints: 0
+    }
+
+    #[test]
+    fn return_impl_iterator() {
+        assert_eq!(evens_before(&[1, 2, 3, 4]), [2, 4]); // prints: [2, 4]
+        let numbers: Vec<u32> = (1..=1_000_000).collect();
+        assert_eq!(evens(&numbers).next(), Some(2));
+        assert_eq!(evens(&[1, 2, 3, 4]).sum::<u32>(), 6);
+        assert_eq!(evens(&numbers).take(3).collect::<Vec<_>>(), [2, 4, 6]); // prints: [2, 4, 6]
+    }
+
+    #[test]
+    fn into_string() {
+        assert_eq!(UserBefore::new("ada".to_string()).name, "ada"); // prints: ada
+        let a = User::new("ada");
+        let b = User::new(String::from("grace")).with_email("grace@example.com");
+        assert_eq!(b.email.as_deref(), Some("grace@example.com"));
+        assert_eq!((a.name.as_str(), b.name.as_str()), ("ada", "grace")); // prints: ada grace
+    }
+}
@@ -0,0 +1,147 @@
+//! Rust/backpressure.rs: sync_channel, blocking or dropping when it's
+//! full, and a queue that drops the oldest.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// sync_channel ------------------------------------------------------------
+
+// A channel with room for n messages. send() waits while it's full;
+// try_send() doesn't, and hands the message back in the error.
+
+// Returns the lines the notes print.
+pub fn try_send() -> [String; 4] {
+    let (tx, rx) = mpsc::sync_channel(2);
+    let both = format!("{:?} {:?}", tx.try_send(1), tx.try_send(2));   // Ok(()) Ok(())
+    let full = match tx.try_send(3) {
+        Err(TrySendError::Full(n)) => format!("full, {n} came back"),
+        other => format!("{other:?}"),
+    };
+    let first = format!("{:?}", rx.recv());     // Ok(1)
+    let again = format!("{:?}", tx.try_send(3));    // Ok(()): there's room now
+    [both, full, first, again]
+}
+
+// The blocking send, from another thread: with nobody receiving, the
+// producer gets two messages in and waits at the third. Returns how many
+// were sent after `pause`, and all of them.
+pub fn blocking_send(pause: Duration) -> (usize, Vec<i32>) {
+    let (tx, rx) = mpsc::sync_channel(2);
+    let sent = Arc::new(AtomicUsize::new(0));
+    let producer = {
+        let sent = sent.clone();
+        thread::spawn(move || {
+            for i in 0..5 {
+                tx.send(i).unwrap();
+                sent.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+    };
+    thread::sleep(pause);
+    let waiting = sent.load(Ordering::SeqCst);  // 2: the third is waiting
+    let all: Vec<i32> = rx.iter().collect();    // taking them lets it go on
+    producer.join().unwrap();
+    (waiting, all)
+}
+
+// sync_channel(0) has no room at all: each send() waits for a recv(),
+// the two threads meet (a rendezvous).
+pub fn handoff() -> (Result<&'static str, mpsc::RecvError>, bool) {
+    let (tx, rx) = mpsc::sync_channel::<&str>(0);
+    let handoff = thread::spawn(move || tx.send("here").is_ok());
+    (rx.recv(), handoff.join().unwrap())
+}
+
+// DROPPING THE NEW ONE ----------------------------------------------------
+
+// When the producer mustn't wait, try_send and count what didn't fit.
+
+pub fn send_or_drop<T>(tx: &SyncSender<T>, message: T, dropped: &mut u64) -> bool {
+    match tx.try_send(message) {
+        Ok(()) => true,
+        Err(TrySendError::Full(_)) => {
+            *dropped += 1;
+            true
+        }
+        Err(TrySendError::Disconnected(_)) => false,   // the receiver is gone: stop
+    }
+}
+
+// DROPPING THE OLD ONE ----------------------------------------------------
+
+// A VecDeque behind a Mutex can make room, with a Condvar for the
+// receiver to wait on.
+
+pub struct Latest<T> {
+    queue: Mutex<VecDeque<T>>,
+    ready: Condvar,
+    capacity: usize,
+}
+
+impl<T> Latest<T> {
+    pub fn new(capacity: usize) -> Self {
+        Latest { queue: Mutex::new(VecDeque::with_capacity(capacity)), ready: Condvar::new(), capacity }
+    }
+
+    // never waits; returns the message that made room, if one did
+    pub fn send(&self, message: T) -> Option<T> {
+        let mut queue = self.queue.lock().unwrap();
+        let old = if queue.len() == self.capacity { queue.pop_front() } else { None };
+        queue.push_back(message);
+        self.ready.notify_one();
+        old
+    }
+
+    pub fn recv(&self) -> T {
+        let mut queue = self.queue.lock().unwrap();
+        loop {
+            if let Some(message) = queue.pop_front() {
+                return message;
+            }
+            queue = self.ready.wait(queue).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync_channel() {
+        let [both, full, first, again] = try_send();
+        assert_eq!(both, "Ok(()) Ok(())"); // prints: Ok(()) Ok(())
+        assert_eq!(full, "full, 3 came back"); // prints: full, 3 came back
+        assert_eq!(first, "Ok(1)"); // prints: Ok(1)
+        assert_eq!(again, "Ok(())"); // prints: Ok(())
+
+        let (waiting, all) = blocking_send(Duration::from_millis(100));
+        assert_eq!(waiting, 2); // prints: 2 sent, the third waiting
+        assert_eq!(all, [0, 1, 2, 3, 4]); // prints: [0, 1, 2, 3, 4]
+
+        assert_eq!(handoff(), (Ok("here"), true)); // prints: Ok("here") true
+    }
+
+    #[test]
+    fn dropping_the_new_one() {
+        let (tx, rx) = mpsc::sync_channel(3);
+        let mut dropped = 0;
+        for reading in 0..5 {
+            send_or_drop(&tx, reading, &mut dropped);
+        }
+        drop(tx);
+        // prints: [0, 1, 2], dropped 2
+        assert_eq!((rx.iter().collect::<Vec<_>>(), dropped), (vec![0, 1, 2], 2));
+    }
+
+    #[test]
+    fn dropping_the_old_one() {
+        let prices = Latest::new(3);
+        let replaced: Vec<_> = (100..105).filter_map(|p| prices.send(p)).collect();
+        assert_eq!((replaced, prices.recv()), (vec![100, 101], 102)); // prints: [100, 101] replaced, 102 first
+    }
+}
@@ -0,0 +1,184 @@
+//! Rust/basics.rs: variables, data types, functions, control flow.
+
+// The examples bind values to show them, and leave most of them unused.
+#![allow(unused_variables, unused_assignments, unused_mut, clippy::let_and_return)]
+
+// VARIABLES -----------------------------------------------------
+
+pub const MAX_HEIGHT: u32 = 100_000;     // const must always be annotated
+
+pub fn variables() {
+    let x = 5;                           // immutable, by default
+    let mut y = 5;                       // mutable
+
+    let x = x + 1;                       // shadowing (redefining)
+    {
+        let x = x * 2;                   // shadowing (block scope)
+    }                                    // end of scope for x inside { }
+
+    let spaces = "   ";                  // use of shadowing
+    let spaces = spaces.len();           // no need to define another variable
+
+    let x: char = 'x';                   // type annotation
+}
+
+// DATA TYPES ----------------------------------------------------
+
+pub fn data_types() {
+    let tup: (u16, char, bool) = (12, 'A', true);  // annotation not necessary
+
+    let a = tup.0;                                 // indexing
+    let (x, y, z) = tup;                           // destructuring
+
+    let days = ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+
+    let a: [u32; 5] = [124, 166, 582, 2, 8];       // annotation [type; size]
+
+    let a = [3; 5];                                // [3, 3, 3, 3, 3]
+
+    let first = a[0];                              // access by indexing
+}
+
+// FUNCTIONS -----------------------------------------------------
+
+pub fn add(x: u64, y: u64) -> u64 {       // parameter annotation necessary
+    x + y                                 // no semicolon, implicit return
+}
+
+// a block as an expression
+pub fn block() -> i32 {
+    let y = {
+        let x = 5;
+        x + 1
+    };
+    y
+}
+
+// CONTROL FLOW --------------------------------------------------
+
+pub fn if_else(y: i32) -> i32 {
+    if y > 5 {
+        println!("Greater");
+    } else if y < 5 {
+        println!("Lesser");
+    } else {
+        println!("Perfect");
+    }
+
+    // if is an expression: the arms must have the same type
+    if y > 5 { 12 } else if y < 5 { 22 } else { 2 }
+}
+
+// loop: forever until you explicitly tell it to stop
+pub fn forever() -> ! {
+    loop {
+        println!("Infinite... ");
+    }
+}
+
+// returning values from loops
+pub fn loop_value() -> i32 {
+    let mut counter = 0;
+    let result = loop {
+        counter += 1;
+
+        if counter == 10 {
+            break counter * 2;          // semi-colon
+        }
+    };                                  // semi-colon
+    result
+}
+
+// loop labels to disambiguate between multiple loops
+pub fn labels() -> (i32, i32) {
+    let mut count = 0;
+    let mut inner = 0;
+    'label: loop {                      // labelled
+        count += 1;
+        loop {                          // inner loop
+            inner += 1;
+            if inner % 3 == 0 {
+                break;                  // break the inner loop
+            }
+            if count == 2 {
+                break 'label;           // break both
+            }
+        }
+    }
+    (count, inner)                      // (2, 4)
+}
+
+// returns the numbers each for loop over a range prints
+pub fn loops() -> [Vec<usize>; 3] {
+    let mut x = 10;
+    while x > 5 {
+        x -= 1;
+    }
+
+    // for loop: good for iterating over a collection
+    let a = [10, 20, 30, 40, 50];
+    for element in a {
+        println!("{element}");
+    }
+
+    let mut printed: [Vec<usize>; 3] = Default::default();
+    for number in 0..5 {              // prints: 0 1 2 3 4
+        println!("{number}");
+        printed[0].push(number);
+    }
+
+    let var = [1, 2, 3, 4, 5];
+    for number in 0..var.len() {      // prints: 0 1 2 3 4
+        println!("{number}");
+        printed[1].push(number);
+    }
+
+    for number in (0..5).rev() {      // prints: 4 3 2 1 0
+        println!("{number}");
+        printed[2].push(number);
+    }
+    printed
+}
+
+// REFERENCES ----------------------------------------------------
+
+pub fn references() {
+    let mut a = 1;
+    let mut c = 2;
+
+    let b = &mut a;   // b is an immutable variable but
+                      //       a   mutable reference to a (a must be mutable)
+    *b += 1;
+
+    let mut b = &a;   // b is a mutable variable and immutable reference to a
+    b = &c;           // b can  point to some other variable but
+                      //   cant modify value of a
+
+    let mut b = &mut a;
+    b = &mut c;       // both: b can point elsewhere, and change what it points to
+    *b += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn functions() {
+        assert_eq!(add(2, 3), 5);
+        assert_eq!(block(), 6);
+    }
+
+    #[test]
+    fn control_flow() {
+        assert_eq!(if_else(6), 12);
+        assert_eq!(if_else(4), 22);
+        assert_eq!(if_else(5), 2);
+        assert_eq!(loop_value(), 20);
+        assert_eq!(labels(), (2, 4));
+        let [up, indices, down] = loops();
+        assert_eq!(up, [0, 1, 2, 3, 4]); // prints: 0 1 2 3 4
+        assert_eq!(indices, [0, 1, 2, 3, 4]); // prints: 0 1 2 3 4
+        assert_eq!(down, [4, 3, 2, 1, 0]); // prints: 4 3 2 1 0
+    }
+}
@@ -0,0 +1,154 @@
+//! Rust/compare/c.rs's gallery: the classic C memory bugs, each a
+//! compile error in Rust. Each bug's Rust is a doc test here, fenced
+//! `compile_fail` with its error code, on a function that does the same
+//! thing the way that compiles; `cargo test --doc` fails if a bug starts
+//! compiling.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+// (1) USE AFTER FREE -----------------------------------------------------
+
+/// A reference can't outlive what it borrows:
+///
+/// ```compile_fail,E0505
+/// let data = vec![1, 2, 3];
+/// let first = &data[0];
+/// drop(data);
+/// println!("{first}");
+/// ```
+///
+/// A copy of the element can.
+pub fn use_after_free() -> i32 {
+    let data = vec![1, 2, 3];
+    let first = data[0];
+    drop(data);
+    first
+}
+
+// (2) DOUBLE FREE --------------------------------------------------------
+
+/// Dropping moves the value, so there's nothing left to drop again:
+///
+/// ```compile_fail,E0382
+/// let s = String::from("hi");
+/// drop(s);
+/// drop(s);
+/// ```
+///
+/// Once is all it takes, and scope's end would do it anyway.
+pub fn double_free() -> usize {
+    let s = String::from("hi");
+    let len = s.len();
+    drop(s);
+    len
+}
+
+// (3) RETURNING THE ADDRESS OF A LOCAL -----------------------------------
+
+/// The local is gone when the function returns:
+///
+/// ```compile_fail,E0515
+/// fn make_counter() -> &'static i32 {
+///     let count = 0;
+///     &count
+/// }
+/// ```
+///
+/// Return the value, or a Box of it.
+pub fn make_counter() -> Box<i32> {
+    let count = 0;
+    Box::new(count)
+}
+
+// (4) UNINITIALISED VARIABLE ---------------------------------------------
+
+/// A variable is read only after every path has written it:
+///
+/// ```compile_fail,E0381
+/// let mut total: i32;
+/// for i in 0..3 {
+///     total += i;
+/// }
+/// println!("{total}");
+/// ```
+pub fn uninitialised() -> i32 {
+    let mut total = 0;
+    for i in 0..3 {
+        total += i;
+    }
+    total
+}
+
+// (5) POINTER INTO A BUFFER THAT GETS REALLOC'ED -------------------------
+
+/// A push may move the elements, so it can't happen while one is
+/// borrowed:
+///
+/// ```compile_fail,E0502
+/// let mut v = vec![1, 2, 3];
+/// let first = &v[0];
+/// v.push(4);
+/// println!("{first}");
+/// ```
+///
+/// Borrow again after the push.
+pub fn realloc() -> i32 {
+    let mut v = vec![1, 2, 3];
+    v.push(4);
+    v[0]
+}
+
+// (6) DATA RACE ----------------------------------------------------------
+
+/// Two threads can't both have the counter mutably:
+///
+/// ```compile_fail,E0499
+/// let mut count = 0;
+/// std::thread::scope(|s| {
+///     s.spawn(|| count += 1);
+///     s.spawn(|| count += 1);
+/// });
+/// ```
+///
+/// An atomic is shared, not borrowed mutably.
+pub fn data_race() -> u32 {
+    let count = AtomicU32::new(0);
+    std::thread::scope(|s| {
+        s.spawn(|| count.fetch_add(1, Ordering::Relaxed));
+        s.spawn(|| count.fetch_add(1, Ordering::Relaxed));
+    });
+    count.into_inner()
+}
+
+// (7) CONSTANT OUT-OF-BOUNDS INDEX ---------------------------------------
+
+/// A lint, `unconditional_panic`, not a type error, so it has no error
+/// code:
+///
+/// ```compile_fail
+/// let mut a = [0; 4];
+/// a[4] = 1;
+/// ```
+///
+/// With an index only known at run time it's a panic instead; `get`
+/// says None.
+pub fn out_of_bounds(i: usize) -> Option<i32> {
+    let a = [0; 4];
+    a.get(i).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn done_the_way_that_compiles() {
+        assert_eq!(use_after_free(), 1);
+        assert_eq!(double_free(), 2);
+        assert_eq!(*make_counter(), 0);
+        assert_eq!(uninitialised(), 3);
+        assert_eq!(realloc(), 1);
+        assert_eq!(data_race(), 2);
+        assert_eq!((out_of_bounds(3), out_of_bounds(4)), (Some(0), None));
+    }
+}
@@ -0,0 +1,136 @@
+//! Rust/closures_and_iterators.rs: closures, what they capture, and
+//! iterator pipelines.
+
+#![allow(unused_variables, unused_assignments, clippy::useless_vec, clippy::redundant_closure_call)]
+
+// CLOSURES ---------------------------------------------------------------
+
+pub fn definitions() -> i32 {
+    let add_one_v1 = |x| { x + 1 };    // compiler will infer types for its first usage
+    let add_one_v2 = |x| x + 1;        // an equivalent definition; works because there is
+                                       //          only one expression in the closure body
+
+    let add_one_v3 = |mut x, mut y| { x += 1; y += 1; x * y };
+
+    add_one_v1(1) + add_one_v2(2) + add_one_v3(3, 4)
+}
+
+/// A closure that borrows mutably holds the borrow until its last call:
+///
+/// ```compile_fail,E0502
+/// let mut v = vec![1, 2, 3];
+/// let immutable_borrow = || println!("{v:?}");
+/// let mut mutable_borrow = || v.push(4);
+/// mutable_borrow();
+/// immutable_borrow();
+/// ```
+///
+/// and one that moves leaves nothing behind:
+///
+/// ```compile_fail,E0382
+/// let v = vec![1, 2, 3];
+/// let take_ownership = move || println!("{v:?}");
+/// take_ownership();
+/// println!("{v:?}");
+/// ```
+pub fn captures() {
+    let mut v = vec![1, 2, 3];
+    let immutable_borrow = || println!("{v:?}");       // immutable borrow
+    immutable_borrow();                                // step 1: v, immutable_borrow
+
+    let mut mutable_borrow = || v.push(4);             // mutable borrow
+    mutable_borrow();                                  // step 2: mutable_borrow
+
+    // the following compiles because it's a new immutable borrow
+    let echo = || println!("{v:?}");
+    echo();                                            // step 3: v
+
+    // move keyword: useful in spawning threads
+    let take_ownership = move || println!("{v:?}");    // move keyword forces to take ownership
+    take_ownership();                                  // step 4: take_ownership
+}
+
+// Some uses of closures
+
+pub fn uses() -> i32 {
+    // Pass closures as arguments to higher-order functions like map, filter and fold
+    let v = vec![1, 2, 3];
+    let w: Vec<_> = v.iter().map(|x| x * 2).collect();
+
+    // Store closures in variables for future use
+    let greet = || println!("Hello, world");
+    greet();
+
+    let doubler = create_multiplier(2);
+    let tripler = create_multiplier(3);
+    let mut a = 4;
+    println!("{}", doubler(a));             // prints: 8
+
+    // Capture variables from their surrounding scope, either by reference or by value
+    let add_x = |x| x + a;  // captures a, by reference
+    let b = add_x(6);
+
+    let add_x = move |x| x + a; // capture a, by value (a copy: i32 is Copy)
+    a = add_x(6);
+    a + b
+}
+
+// Return closures from functions
+pub fn create_multiplier(factor: i32) -> impl Fn(i32) -> i32 {
+    move |x| x * factor
+}
+
+// ITERATORS --------------------------------------------------------------
+
+pub fn pipelines() -> Vec<bool> {
+    let v = vec![1, 2, 3, 4, 5];
+    let vect: Vec<_> = v.iter()          // immutable borrow (&T)
+        .map(|x| x * x)                  // applies to each element in the iterator, returns an iterator
+        .filter(|&x| x > 5)              // pick only those elements returning true
+        .collect();                      // materialize into a vector, needs annotation such as Vec<_> or
+                                         //                            .collect::<Vec<_>>()
+
+    let vect: Vec<_> = v.into_iter()     // v is consumed
+        .map(|x| x * x)
+        .filter(|&x| x > 5)
+        .collect();
+
+    let v = vec![String::from("Vito"), String::from("Michael")];
+
+    let vect: Vec<_> = v.into_iter()     // consume v
+        .map(|x| x + " Huidrom")
+        .filter(|x| x.len() > 12)
+        .collect();
+
+    let v = vec![true, true, false, true, false, false, false, true];
+
+    v.iter()                             // immutable borrow (&T)
+        .map(|x| !x)                     // true -> false and false -> true
+        .filter(|&x| x)
+        .collect()
+}
+
+// fold() reduces a collection to a single value by repeatedly applying
+// an operation (for a sum, .sum() is the short way)
+#[allow(clippy::unnecessary_fold)]
+pub fn sum(v: &[i32]) -> i32 {
+    v.iter().fold(0, |acc, x| acc + x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closures() {
+        assert_eq!(definitions(), 2 + 3 + 4 * 5);
+        assert_eq!(create_multiplier(2)(4), 8); // prints: 8
+        assert_eq!(uses(), 10 + 10);
+    }
+
+    #[test]
+    fn iterators() {
+        assert_eq!(pipelines(), [true; 4]);
+        assert_eq!(sum(&[1, 2, 3]), 6);
+    }
+}
@@ -0,0 +1,163 @@
+//! Rust/coherence.rs: the orphan rule, overlapping impls, newtypes,
+//! extension traits, and sealed traits.
+
+use std::fmt;
+
+// THE ORPHAN RULE --------------------------------------------------------
+
+/// Display and Vec are both std's: this impl isn't this crate's to make.
+///
+/// ```compile_fail,E0117
+/// use std::fmt;
+///
+/// impl fmt::Display for Vec<String> {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         write!(f, "[{}]", self.join(", "))
+///     }
+/// }
+/// ```
+///
+/// Two impls covering i32: the blanket one and the one for i32.
+///
+/// ```compile_fail,E0119
+/// trait Describe { fn describe(&self) -> String; }
+///
+/// impl<T: std::fmt::Display> Describe for T {
+///     fn describe(&self) -> String { format!("<{self}>") }
+/// }
+///
+/// impl Describe for i32 {
+///     fn describe(&self) -> String { format!("the number {self}") }
+/// }
+/// ```
+pub trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl<T: fmt::Display> Describe for T {
+    fn describe(&self) -> String {
+        format!("<{self}>")
+    }
+}
+
+// THE NEWTYPE WRAPPER ----------------------------------------------------
+
+// A tuple struct around the foreign type is a local type, so any trait
+// can be implemented for it.
+
+pub struct Wrapper(pub Vec<String>);
+
+impl fmt::Display for Wrapper {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}]", self.0.join(", "))
+    }
+}
+
+// Generic over the element, with the bound the impl needs:
+
+pub struct List<T>(pub Vec<T>);
+
+impl<T: fmt::Display> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let items: Vec<String> = self.0.iter().map(|i| i.to_string()).collect();
+        write!(f, "[{}]", items.join(", "))
+    }
+}
+
+/// Deref gives the Vec's own methods back, at the price of the wrapper
+/// leaking what's in it.
+pub mod deref {
+    use std::ops::Deref;
+
+    pub struct List<T>(pub Vec<T>);
+
+    impl<T> Deref for List<T> {
+        type Target = Vec<T>;
+        fn deref(&self) -> &Vec<T> {
+            &self.0
+        }
+    }
+}
+
+// A TRAIT OF YOUR OWN ----------------------------------------------------
+
+// An "extension trait" adds methods to a type that isn't yours; it's
+// used where it's in scope.
+
+pub trait Shout {
+    fn shout(&self) -> String;
+}
+
+impl Shout for str {
+    fn shout(&self) -> String {
+        format!("{}!", self.to_uppercase())
+    }
+}
+
+// SEALED TRAITS ----------------------------------------------------------
+
+/// Other crates can name and use `Shape`, but not implement it: its
+/// supertrait isn't implemented,
+///
+/// ```compile_fail,E0277
+/// struct Pentagon;
+///
+/// impl langscape::coherence::shapes::Shape for Pentagon {
+///     fn sides(&self) -> u32 { 5 }
+/// }
+/// ```
+///
+/// and can't be, since it can't even be named:
+///
+/// ```compile_fail,E0603
+/// struct Hexagon;
+///
+/// impl langscape::coherence::shapes::private::Sealed for Hexagon {}
+/// ```
+pub mod shapes {
+    mod private {
+        pub trait Sealed {}
+    }
+
+    pub trait Shape: private::Sealed {
+        fn sides(&self) -> u32;
+    }
+
+    pub struct Triangle;
+    pub struct Square;
+
+    impl private::Sealed for Triangle {}
+    impl private::Sealed for Square {}
+
+    impl Shape for Triangle {
+        fn sides(&self) -> u32 { 3 }
+    }
+    impl Shape for Square {
+        fn sides(&self) -> u32 { 4 }
+    }
+}
+
+pub fn total(all: &[&dyn shapes::Shape]) -> u32 {
+    all.iter().map(|s| s.sides()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newtypes() {
+        let w = Wrapper(vec![String::from("hello"), String::from("world")]);
+        assert_eq!(w.to_string(), "[hello, world]"); // prints: w = [hello, world]
+        assert_eq!(List(vec![1, 2, 3]).to_string(), "[1, 2, 3]"); // prints: [1, 2, 3]
+        let list = deref::List(vec!['a', 'b']);
+        assert_eq!((list.len(), list.first()), (2, Some(&'a'))); // prints: 2 Some('a')
+    }
+
+    #[test]
+    fn traits_of_your_own() {
+        assert_eq!(5.describe(), "<5>");
+        assert_eq!("hello".shout(), "HELLO!"); // prints: HELLO!
+        assert_eq!(total(&[&shapes::Triangle, &shapes::Square]), 7); // prints: 7
+    }
+}
@@ -0,0 +1,296 @@
+//! Rust/collections.rs: vectors, strings and hash maps.
+
+#![allow(unused_variables, unused_mut, unused_assignments, clippy::useless_vec)]
+
+use std::collections::HashMap;
+
+// VECTORS ----------------------------------------------------------------
+
+pub fn vectors() -> Vec<i32> {
+    let mut v: Vec<i32> = Vec::new(); // type annotation
+
+    let v = vec![1, 2, 3];            // type inference
+
+    // pushing elements
+    let mut v = Vec::new();
+    v.push(5);                        // type inference
+    v.push(6);
+
+    let mut x = v.pop().unwrap();     // because v.pop() returns Some<T>
+    x = v.pop().unwrap_or(0);         // if v is empty, it returns None
+    v
+}
+
+/// `[]` panics for an index past the end, `get` returns None (the
+/// tests below). A reference into a vector stops it being pushed to, as the push may
+/// move the elements:
+///
+/// ```compile_fail,E0502
+/// let mut v = vec![1, 2, 3, 4, 5];
+///
+/// let first = &v[0];       // immutable reference to the first element
+///
+/// v.push(6);               // mutable borrow
+///
+/// println!("{}", first);   // immutable borrow again
+/// ```
+pub fn reading() -> i32 {
+    let mut v = vec![1, 2, 3, 4, 5];
+
+    let element: &i32 = &v[2];        // annotation not necessary
+                                      // Using & and [] gives us a reference
+
+    let x = &mut v[1];                // want to modify the value
+    *x = 4;
+
+    let element: Option<&i32> = v.get(2);  // we get Option<&T>
+
+    // we can use Option<&T> with match
+    match element {
+        Some(third) => println!("The element is {third}."),
+        None => println!("There is no element at that index."),
+    }
+
+    *v.first().unwrap_or(&0)          // &0; because it returns Option<&T>
+}
+
+// iterating over a vector . . .
+
+pub fn iterating() -> Vec<i32> {
+    let v = vec![1, 2, 3, 4, 5];
+
+    for i in &v {                       // immutable reference
+        println!("{i}");
+    }
+
+    let mut v = vec![1, 2, 3, 4, 5];
+
+    for i in &mut v {                   // mutable reference
+        *i += 50;                       // deference (using asterisk)
+    }
+    v
+}
+
+// using an enum to store multiple types . . .
+
+pub enum SpreadsheetCell {
+    Int(i32),
+    Float(f64),
+    Text(String),
+}
+
+pub fn row() -> Vec<SpreadsheetCell> {
+    vec![
+        SpreadsheetCell::Int(24),
+        SpreadsheetCell::Text(String::from("Saileza")),
+        SpreadsheetCell::Float(12.8),
+    ]
+}
+
+// Like any other struct, a vector is freed when it goes out of scope.
+pub fn scope() {
+    {
+        let v = vec![1, 2, 3, 4, 5];
+
+        // do stuff
+    } // v goes out of scope and is freed here
+}
+
+// STRING -----------------------------------------------------------------
+
+pub fn creating() {
+    let mut s = String::new();
+
+    let data = "initialization";
+
+    let mut s = data.to_string();          // s is mutable String type
+
+    let mut s = "initialization".to_string();
+
+    let mut s = String::from("initialization");   // equivalent to above
+
+    // any properly encoded UTF-8 data
+    let hello = String::from("Здравствуйте");
+}
+
+// Updating a string . . .
+
+pub fn updating() -> String {
+    let mut s = String::from("existentialism");
+
+    s.push_str(" is ");       // to append a string slice
+
+    let ss = " is humanism.";
+    s.push_str(ss);
+    println!("ss is {ss}");   // legal, because push_str does not take
+                              // ownership of ss
+
+    let mut s = String::from("hell");
+
+    s.push('o');              // push() takes a single character
+
+    let s1 = String::from("Hello, ");
+    let s2 = String::from("world!");
+    let s3 = s1 + &s2;                  // s1 has been moved here
+                                        // and can no longer be used
+    s3
+}
+
+// For combining strings in more complicated ways, format!:
+
+pub fn combining() -> String {
+    let s1 = String::from("tic");
+    let s2 = String::from("tac");
+    let s3 = String::from("toe");
+
+    format!("{s1}-{s2}-{s3}")             // works like println!
+}
+
+// Slicing strings . . .
+
+// A range that cuts a character in two panics at runtime (the tests
+// below).
+pub fn slicing() -> (String, String) {
+    let hello = "hello";             // each character here is of one byte
+    let s     = &hello[0..4];        // s will be "hell"
+
+    let hello = "Здравствуйте";      // each character here is of two bytes
+    let t     = &hello[0..4];        // s will be a &str that contains
+                                     // the first four bytes of the string
+                                     // so, s will contain "Зд"
+    (s.to_string(), t.to_string())
+}
+
+// Methods for iterating over strings
+
+pub fn chars_and_bytes() -> (usize, usize) {
+    let s = String::from("ꯁꯥꯏꯂꯦꯖꯥ ꯍꯤꯗꯥꯡꯃꯌꯨꯝ");
+
+    let mut chars = 0;
+    for c in s.chars() {
+        chars += 1;
+    }
+
+    // The bytes method returns each raw byte
+    let mut bytes = 0;
+    for b in s.bytes() {
+        bytes += 1;
+    }
+    (chars, bytes)
+}
+
+// HASH MAPS --------------------------------------------------------------
+
+pub fn hash_maps() -> i32 {
+    let mut scores = HashMap::new();
+
+    scores.insert(String::from("Blue"), 10);
+    scores.insert(String::from("Green"), 40);
+
+    // Accessing values in a hash map . . .
+    let team_name = String::from("Blue");
+    let score = scores.get(&team_name).copied().unwrap_or(0);
+
+    for (key, value) in &scores {        // iterating over a hash map
+        println!("{key}: {value}");      // in arbitrary order
+    }
+    score
+}
+
+// note: unwrap_or gives the value in a Some, or the default for None
+//       (Some(28) gives 28, None gives 0)
+pub fn or_zero(v: Option<i32>) -> i32 {
+    v.unwrap_or(0)
+}
+
+// owned values are moved into the map
+
+pub fn ownership() {
+    let field_name  = String::from("Favorite color");
+    let field_value = String::from("Blue");
+
+    let mut map = HashMap::new();
+    map.insert(field_name.clone(), field_value.clone());   // a copy: the originals stay
+    map.insert(field_name, field_value);                   // ownership
+
+    // or pass references:
+    let (field_name, field_value) = (String::from("Favorite color"), String::from("Blue"));
+    let mut map = HashMap::new();
+    map.insert(&field_name, &field_value);
+}
+
+// Updating a hash map . . .
+
+pub fn updating_map() -> HashMap<String, i32> {
+    let mut scores = HashMap::new();
+
+    scores.insert(String::from("Blue"), 10);
+    scores.insert(String::from("Blue"), 20);    // replaces the old value
+
+    println!("{scores:?}");
+
+    // Adding a key only if it isn't there: the Entry API . . .
+    scores.entry(String::from("Blue")).or_insert(30);
+    scores.entry(String::from("Yellow")).or_insert(50);
+    scores
+}
+
+// to look up a key's value and then update it based on the old value . . .
+
+pub fn word_count(text: &str) -> HashMap<&str, i32> {
+    let mut map = HashMap::new();
+
+    // counting word counts of each unique word in text
+    for word in text.split_whitespace() {      // process each word
+        let count = map.entry(word).or_insert(0);
+        *count += 1;     // because a mutable reference is returned
+                         // by or_insert() method on entry() method
+    }
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vectors() {
+        assert_eq!(super::vectors(), Vec::<i32>::new());
+        assert_eq!(reading(), 1);
+        assert_eq!(iterating(), [51, 52, 53, 54, 55]);
+        assert_eq!(row().len(), 3);
+        let v = vec![1, 2, 3, 4, 5];
+        assert_eq!(v.get(100), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn an_index_past_the_end() {
+        let v = vec![1, 2, 3, 4, 5];
+        let does_not_exist = &v[100];
+    }
+
+    #[test]
+    fn strings() {
+        assert_eq!(super::updating(), "Hello, world!");
+        assert_eq!(combining(), "tic-tac-toe");
+        assert_eq!(slicing(), ("hell".to_string(), "Зд".to_string()));
+        assert_eq!(chars_and_bytes(), (17, 49));
+    }
+
+    #[test]
+    #[should_panic(expected = "byte index 1 is not a char boundary")]
+    fn a_slice_through_a_char() {
+        let hello = "Здравствуйте";
+        let s = &hello[0..1];
+    }
+
+    #[test]
+    fn hash_maps() {
+        assert_eq!(super::hash_maps(), 10);
+        assert_eq!(or_zero(Some(28)), 28);
+        assert_eq!(or_zero(None), 0);
+        assert_eq!(updating_map(), HashMap::from([("Blue".to_string(), 20), ("Yellow".to_string(), 50)]));
+        assert_eq!(word_count("hello world wonderful world"), HashMap::from([("hello", 1), ("world", 2), ("wonderful", 1)]));
+    }
+}
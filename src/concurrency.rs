@@ -0,0 +1,182 @@
+//! Rust/concurrency.rs: threads, channels, and shared state.
+
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// THREADS ----------------------------------------------------------------
+
+pub fn spawning() {
+    // pass a closure to be run in that thread
+    let handle = thread::spawn(|| {
+        // snippet
+    });
+
+    // main thread awaits until the spawn thread is finished
+    handle.join().unwrap();
+
+    // move keyword in spawn: force the closure to take ownership
+    let name = "Rust".to_string();
+
+    let handle = thread::spawn(move || {
+        println!("Hello, I am {}.", name);
+    });
+    handle.join().unwrap();
+}
+
+// MESSAGE PASSING TO TRANSFER DATA BETWEEN THREADS -----------------------
+
+pub fn message_passing() -> String {
+    // tx: transmitter
+    // rx: receiver
+    let (tx, rx) = mpsc::channel();       // tuple destructuring
+
+    thread::spawn(move || {
+        let val = "Rust from spawned thread".to_string();
+        tx.send(val).unwrap();
+    });
+
+    let received = rx.recv().unwrap();    // blocks until a value comes
+    println!("Received: {}", received);
+
+    // try_recv doesn't block: Err(Empty) if nothing is there yet, or
+    // Err(Disconnected) here, as the sender is gone
+    assert!(rx.try_recv().is_err());
+    received
+}
+
+// sending multiple values ------------------------------------------------
+
+pub fn multiple_values(pause: Duration) -> Vec<String> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let vals = vec![
+            String::from("hi"),
+            String::from("from"),
+            String::from("the"),
+            String::from("thread"),
+        ];
+
+        for val in vals {
+            tx.send(val).unwrap();
+            thread::sleep(pause);
+        }
+    });
+
+    // treating rx as an iterator: it ends when the channel is closed
+    let mut got = Vec::new();
+    for received in rx {
+        println!("Got: {received}");
+        got.push(received);
+    }
+    got
+}
+
+// creating multiple producers by cloning the transmitter
+pub fn multiple_producers(pause: Duration) -> Vec<String> {
+    let (tx, rx) = mpsc::channel();
+
+    // clone; to be moved to a thread
+    let tx1 = tx.clone();
+    thread::spawn(move || {
+        let vals = vec!["some".to_string(), "messages".to_string()];
+
+        for val in vals {
+            tx1.send(val).unwrap();                      // step 1: val
+            thread::sleep(pause);
+        }
+    });
+
+    thread::spawn(move || {
+        let vals = vec!["more".to_string(), "messages".to_string()];
+
+        for val in vals {
+            tx.send(val).unwrap();                       // step 2: val
+            thread::sleep(pause);
+        }
+    });
+
+    let mut got = Vec::new();
+    for received in rx {
+        println!("Received: {received}");                // step 3: received
+        got.push(received);
+    }
+    got
+}
+
+// SHARED-STATE CONCURRENCY -----------------------------------------------
+
+pub fn mutex() -> i32 {
+    let m = Mutex::new(4);
+
+    {
+        // the guard works as a mutable reference to the data inside
+        let mut num = m.lock().unwrap();
+        *num = 8;              // Mutex<T> is a smart pointer
+
+        // the guard's Drop releases the lock at the end of the scope
+    }
+
+    println!("m = {m:?}");
+    m.into_inner().unwrap()
+}
+
+// sharing a Mutex<T> between multiple threads: Arc<T>, atomically
+// reference counted, for the owners
+pub fn counter() -> i32 {
+    let counter = Arc::new(Mutex::new(0));
+    let mut handles = vec![];
+
+    for _ in 1..10 {
+        // a new owner of the same data, not a copy of it
+        let counter = Arc::clone(&counter);
+
+        let handle = thread::spawn(move || {
+            let mut sptr = counter.lock().unwrap();
+            *sptr += 1;
+        });
+
+        handles.push(handle);
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // need to lock() to access the data
+    println!("Result: {}", *counter.lock().unwrap());   // prints: Result: 9
+    let result = *counter.lock().unwrap();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_message_arrives() {
+        assert_eq!(message_passing(), "Rust from spawned thread");
+    }
+
+    #[test]
+    fn in_the_order_sent() {
+        assert_eq!(multiple_values(Duration::ZERO), ["hi", "from", "the", "thread"]);
+    }
+
+    // two senders interleave, but each one's values stay in its order
+    #[test]
+    fn from_both_producers() {
+        let mut got = multiple_producers(Duration::ZERO);
+        assert_eq!(got.len(), 4);
+        got.sort();
+        assert_eq!(got, ["messages", "messages", "more", "some"]);
+    }
+
+    #[test]
+    fn counted_under_the_lock() {
+        assert_eq!(mutex(), 8);
+        assert_eq!(counter(), 9);
+    }
+}
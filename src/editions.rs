@@ -0,0 +1,28 @@
+//! Rust/editions.rs: the one change the notes run, closures capturing
+//! fields (2021).
+//!
+//! The rest of the notes are diffs from diagrams/migrate.rs, which
+//! builds on its own.
+
+// 2018 -> 2021: CLOSURES, ARRAYS, MACROS ---------------------------------
+
+/// The closure uses `pair.0` only, so it moves only that; `pair.1` is
+/// still here afterwards. Under 2018 the closure moved all of `pair`, and
+/// the last line was E0382.
+pub fn disjoint_captures() -> String {
+    let pair = (1, String::from("one"));
+    let c = move || println!("{}", pair.0);  // 2021: moves pair.0 only (an i32, so a copy)
+    c();
+    println!("{}", pair.1);                  // so pair.1 is still here; in 2018, E0382
+    pair.1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pair_1_is_still_there() {
+        assert_eq!(disjoint_captures(), "one");
+    }
+}
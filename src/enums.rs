@@ -0,0 +1,255 @@
+//! Rust/enums.rs: variants with data, discriminants, non-exhaustive
+//! enums, matching ergonomics, and size.
+
+#![allow(dead_code)]
+
+// DATA IN VARIANTS -------------------------------------------------------
+
+#[derive(Debug)]
+pub enum Shape {
+    Point,                                  // unit
+    Circle(f64),                            // tuple: the radius
+    Rect { w: f64, h: f64 },                // struct-like
+}
+
+pub fn area(shape: &Shape) -> f64 {
+    match shape {
+        Shape::Point => 0.0,
+        Shape::Circle(r) => 3.0 * r * r,    // close enough
+        Shape::Rect { w, h } => w * h,
+    }
+}
+
+pub fn areas() {
+    let shapes = [Shape::Point, Shape::Circle(1.0), Shape::Rect { w: 2.0, h: 3.0 }];
+    for s in &shapes {
+        println!("{}", area(s));            // prints: 0 3 6
+    }
+
+    // A tuple variant's name is a function too: it can be passed to map.
+    let circles: Vec<Shape> = vec![1.0, 2.0].into_iter().map(Shape::Circle).collect();
+    println!("{circles:?}");                // prints: [Circle(1.0), Circle(2.0)]
+}
+
+// An enum can hold itself only behind a pointer: the size must be known.
+
+#[derive(Debug)]
+pub enum List {
+    Cons(i32, Box<List>),
+    Nil,
+}
+
+pub fn list() {
+    let list = List::Cons(1, Box::new(List::Cons(2, Box::new(List::Nil))));
+    println!("{list:?}");                   // prints: Cons(1, Cons(2, Nil))
+}
+
+// DISCRIMINANTS ----------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Created = 201,
+    NotFound = 404,
+    Teapot = 418,
+}
+
+pub enum Weekday {
+    Mon = 1,
+    Tue,                                    // 2
+    Wed,                                    // 3
+}
+
+// The other way isn't `as`: a number may be no variant at all.
+impl TryFrom<u16> for Status {
+    type Error = u16;
+    fn try_from(code: u16) -> Result<Status, u16> {
+        match code {
+            201 => Ok(Status::Created),
+            404 => Ok(Status::NotFound),
+            418 => Ok(Status::Teapot),
+            other => Err(other),
+        }
+    }
+}
+
+#[repr(u8)]
+pub enum Opcode {
+    Nop = 0x00,
+    Jump = 0xEB,
+}
+
+pub fn discriminants() {
+    println!("{} {}", Status::NotFound as u16, Weekday::Wed as u8);   // prints: 404 3
+    println!("{:?} {:?}", Status::try_from(418), Status::try_from(500));  // prints: Ok(Teapot) Err(500)
+    println!("{:#x} {}", Opcode::Jump as u8, std::mem::size_of::<Opcode>());   // prints: 0xeb 1
+}
+
+// NON-EXHAUSTIVE ENUMS ---------------------------------------------------
+
+// Inside the defining crate #[non_exhaustive] changes nothing: no `_`.
+#[non_exhaustive]
+pub enum Level {
+    Low,
+    High,
+}
+
+pub fn describe(level: Level) -> &'static str {
+    match level {
+        Level::Low => "low",
+        Level::High => "high",
+    }
+}
+
+/// A match that misses a variant doesn't compile, in any crate:
+///
+/// ```compile_fail,E0004
+/// enum Light {
+///     Red,
+///     Amber,
+///     Green,
+/// }
+///
+/// fn next(light: Light) -> Light {
+///     match light {
+///         Light::Red => Light::Green,
+///         Light::Green => Light::Amber,
+///     }
+/// }
+/// ```
+///
+/// And outside it, a match on a non-exhaustive enum needs the `_` even
+/// with every variant there:
+///
+/// ```compile_fail,E0004
+/// use langscape::enums::Level;
+///
+/// fn describe(level: Level) -> &'static str {
+///     match level {
+///         Level::Low => "low",
+///         Level::High => "high",
+///     }
+/// }
+/// ```
+pub fn non_exhaustive() {
+    println!("{}", describe(Level::High)); // prints: high
+}
+
+// MATCHING ERGONOMICS ----------------------------------------------------
+
+// Matching a &Shape against Shape:: patterns: the bindings are references.
+pub fn radius(shape: &Shape) -> Option<f64> {
+    match shape {                           // &Shape against Shape::..
+        Shape::Circle(r) => Some(*r),       // r: &f64
+        _ => None,
+    }
+}
+
+pub fn ergonomics() {
+    println!("{:?}", radius(&Shape::Circle(2.5)));   // prints: Some(2.5)
+
+    // Through &mut the bindings are &mut: the data can be changed in place.
+    let mut shape = Shape::Rect { w: 1.0, h: 1.0 };
+    if let Shape::Rect { w, .. } = &mut shape {
+        *w *= 4.0;                          // w: &mut f64
+    }
+    println!("{shape:?}");                  // prints: Rect { w: 4.0, h: 1.0 }
+}
+
+pub enum Event {
+    Key(char),
+    Click { x: i32, y: i32 },
+    Scroll { x: i32, y: i32 },
+}
+
+// |, .. and @ in one match
+pub fn place(event: &Event) -> String {
+    match event {
+        Event::Click { x, .. } | Event::Scroll { x, .. } if *x < 0 => "off screen".to_string(),
+        Event::Click { x, y } | Event::Scroll { x, y } => format!("{x},{y}"),
+        Event::Key(c @ 'a'..='z') => format!("letter {c}"),
+        Event::Key(_) => "key".to_string(),
+    }
+}
+
+// let-else: the variant wanted, or leave
+pub fn key(event: &Event) -> char {
+    let Event::Key(c) = event else { return '?' };
+    *c
+}
+
+pub fn events() {
+    println!("{} / {} / {}", place(&Event::Scroll { x: -1, y: 0 }), place(&Event::Click { x: 3, y: 4 }), place(&Event::Key('q')));
+    // prints: off screen / 3,4 / letter q
+    println!("{} {}", key(&Event::Key('x')), matches!(Event::Key('x'), Event::Key('a'..='z')));   // prints: x true
+}
+
+// SIZE -------------------------------------------------------------------
+
+pub enum Small { A(u8), B(u16) }
+pub enum Big { A(u8), B([u8; 100]) }
+
+// The big variant boxed: a pointer's size and a tag.
+pub enum Boxed { A(u8), B(Box<[u8; 100]>) }
+
+pub fn sizes() {
+    use std::mem::size_of;
+
+    println!("{} {}", size_of::<Small>(), size_of::<Big>());   // prints: 4 101
+    println!("{} {}", size_of::<&u64>(), size_of::<Option<&u64>>());   // prints: 8 8
+    println!("{} {}", size_of::<bool>(), size_of::<Option<Option<bool>>>());   // prints: 1 1
+    println!("{}", size_of::<Boxed>());    // prints: 16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::size_of;
+
+    fn values(mut list: &List) -> Vec<i32> {
+        let mut values = Vec::new();
+        while let List::Cons(value, rest) = list {
+            values.push(*value);
+            list = rest;
+        }
+        values
+    }
+
+    #[test]
+    fn data_in_variants() {
+        let shapes = [Shape::Point, Shape::Circle(1.0), Shape::Rect { w: 2.0, h: 3.0 }];
+        assert_eq!(shapes.iter().map(area).collect::<Vec<_>>(), [0.0, 3.0, 6.0]); // prints: 0 3 6
+        let circles: Vec<Shape> = vec![1.0, 2.0].into_iter().map(Shape::Circle).collect();
+        assert!(matches!(circles[..], [Shape::Circle(1.0), Shape::Circle(2.0)])); // prints: [Circle(1.0), Circle(2.0)]
+        let list = List::Cons(1, Box::new(List::Cons(2, Box::new(List::Nil))));
+        assert_eq!(values(&list), [1, 2]); // prints: Cons(1, Cons(2, Nil))
+    }
+
+    #[test]
+    fn discriminants() {
+        assert_eq!((Status::NotFound as u16, Weekday::Wed as u8), (404, 3)); // prints: 404 3
+        assert_eq!((Status::try_from(418), Status::try_from(500)), (Ok(Status::Teapot), Err(500))); // prints: Ok(Teapot) Err(500)
+        assert_eq!((Opcode::Jump as u8, size_of::<Opcode>()), (0xeb, 1)); // prints: 0xeb 1
+        assert_eq!(describe(Level::High), "high"); // prints: high
+    }
+
+    #[test]
+    fn matching_ergonomics() {
+        assert_eq!(radius(&Shape::Circle(2.5)), Some(2.5)); // prints: Some(2.5)
+        let mut shape = Shape::Rect { w: 1.0, h: 1.0 };
+        if let Shape::Rect { w, .. } = &mut shape {
+            *w *= 4.0;
+        }
+        assert!(matches!(shape, Shape::Rect { w: 4.0, h: 1.0 })); // prints: Rect { w: 4.0, h: 1.0 }
+        let places = [place(&Event::Scroll { x: -1, y: 0 }), place(&Event::Click { x: 3, y: 4 }), place(&Event::Key('q'))];
+        assert_eq!(places, ["off screen", "3,4", "letter q"]); // prints: off screen / 3,4 / letter q
+        assert_eq!((key(&Event::Key('x')), matches!(Event::Key('x'), Event::Key('a'..='z'))), ('x', true)); // prints: x true
+    }
+
+    #[test]
+    fn size() {
+        assert_eq!((size_of::<Small>(), size_of::<Big>()), (4, 101)); // prints: 4 101
+        assert_eq!((size_of::<&u64>(), size_of::<Option<&u64>>()), (8, 8)); // prints: 8 8
+        assert_eq!((size_of::<bool>(), size_of::<Option<Option<bool>>>()), (1, 1)); // prints: 1 1
+        assert_eq!(size_of::<Boxed>(), 16); // prints: 16
+    }
+}
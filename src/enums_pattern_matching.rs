@@ -0,0 +1,435 @@
+//! Rust/enums_pattern_matching.rs: enums, Option, match, if let, and
+//! the places patterns go.
+
+#![allow(dead_code, unused_variables, clippy::single_match, clippy::match_single_binding)]
+
+// ENUMS ------------------------------------------------------------------
+
+pub enum IpAddressVariant {             // a custom data type
+    V4,                                 // with two possible values
+    V6,
+}
+
+pub fn route(ip_variant: IpAddressVariant) {}   // function accepting enum object
+
+// Each enum variant can have different data associated with them.
+pub enum IpAddress {
+    V4(u8, u8, u8, u8),
+    V6(String),
+}
+
+// Can put any kind of data inside an enum variant
+//         (can even include another enum)
+
+pub struct Ipv4Addr {
+    // --snip--
+}
+
+pub struct Ipv6Addr {
+    // --snip--
+}
+
+pub enum IpAddr {
+    V4(Ipv4Addr),
+    V6(Ipv6Addr),
+}
+
+// An enum whose variants each store different amounts and types of values:
+pub enum Message {
+    Quit,
+    Move { x: i32, y: i32 },        // has named fields, like a struct does
+    Write(String),                  // includes a single String
+    ChangeColor(i32, i32, i32),     // includes three i32 values
+}
+
+// The above enum is similar to having four structs:
+
+pub struct QuitMessage;                      // unit struct
+pub struct MoveMessage {
+    x: i32,
+    y: i32,
+}
+pub struct WriteMessage(String);             // tuple struct
+pub struct ChangeColorMessage(i32, i32, i32);    // tuple struct
+
+// Another similarity between enums and structs: can define methods
+impl Message {
+    pub fn call(&self) {
+        // body
+    }
+}
+
+pub fn enums() {
+    let four = IpAddressVariant::V4;    // declare value
+    route(IpAddressVariant::V6);        // function call
+
+    // then IpAddress::V4() acts like a constructor
+    let home     = IpAddress::V4(127, 0, 0, 1);
+    let loopback = IpAddress::V6(String::from("::1"));
+
+    let m = Message::Write(String::from("Devil May Cry"));
+    m.call();
+}
+
+// Option: a value, or none, instead of null
+
+pub fn options() {
+    let some_number = Some(5);           // type of some_number is Option<i32>
+    let some_char   = Some('e');         // type of some_char   is Option<char>
+
+    let absent_number: Option<i32> = None;   // None alone needs the annotation
+}
+
+// MATCH CONTROL FLOW ------------------------------------------------------
+
+pub enum Coin {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter,
+}
+
+pub fn value_in_cents(coin: Coin) -> u8 {
+    match coin {
+        Coin::Penny   =>  1,
+        Coin::Nickel  =>  5,
+        Coin::Dime    => 10,
+        Coin::Quarter => 25,
+    }
+}
+
+// an arm can be a block
+pub fn value_in_cents_lucky(coin: Coin) -> u8 {
+    match coin {
+        Coin::Penny => {
+            println!("Lucky penny!");       // will print this
+            1                               //      but return 1
+        }
+        Coin::Nickel => 5,
+        Coin::Dime => 10,
+        Coin::Quarter => 25,
+    }
+}
+
+/// Match arms can bind to the parts of the values that match the pattern.
+pub mod states {
+    #[derive(Debug)]
+    pub enum UsState {
+        Alabama,
+        Alaska,
+        // --snip--
+    }
+
+    pub enum Coin {
+        Penny,
+        Nickel,
+        Dime,
+        Quarter(UsState),
+    }
+
+    pub fn value_in_cents(coin: Coin) -> u8 {
+        match coin {
+            Coin::Penny => 1,
+            Coin::Nickel => 5,
+            Coin::Dime => 10,
+            Coin::Quarter(state) => {
+                println!("State quarter from {state:?}");
+                25
+            }
+        }
+    }
+
+    // if let and else construct
+    pub fn count_others(coins: &[Coin]) -> usize {
+        let mut count = 0;
+        for coin in coins {
+            match coin {
+                Coin::Quarter(state) => println!("State quarter from {state:?}!"),
+                _ => count += 1,
+            }
+        }
+
+        // The above may be rewritten as
+        let mut rewritten = 0;
+        for coin in coins {
+            if let Coin::Quarter(state) = coin {
+                println!("State quarter from {state:?}!");
+            } else {
+                rewritten += 1;
+            }
+        }
+        assert_eq!(count, rewritten);
+        count
+    }
+}
+
+// Matching with Option<T> (x.map(|i| i + 1) does the same)
+#[allow(clippy::manual_map)]
+pub fn plus_one(x: Option<i32>) -> Option<i32> {
+    match x {
+        None => None,
+        Some(i) => Some(i + 1),
+    }
+}
+
+pub fn matching_option() {
+    let five = Some(5);
+    let six  = plus_one(five);
+    let none = plus_one(None);
+}
+
+// Catch-all Patterns and the _ Placeholder
+
+fn add_fancy_hat() {}
+fn remove_fancy_hat() {}
+fn move_player(num_spaces: u8) {}
+
+pub fn catch_all(dice_roll: u8) {
+    match dice_roll {
+        3 => add_fancy_hat(),
+        7 => remove_fancy_hat(),
+        other => move_player(other),  // must be put last (top-down order
+    }                                 //                   evaluation)
+
+    // Catch-all: use _ so that the compiler doesn't warn about being unused
+    match dice_roll {
+        3 => add_fancy_hat(),
+        7 => remove_fancy_hat(),
+        _ => (),                      // () means do nothing
+    }
+}
+
+// The if let syntax: one pattern, the rest ignored
+
+pub fn if_let() {
+    let config_max = Some(3u8);
+    match config_max {
+        Some(max) => println!("The maximum is configured to be {max}"),
+        _ => (),
+    }
+
+    // The above can be concisely written as
+    let config_max = Some(3u8);
+    if let Some(max) = config_max {
+        println!("The maximum is configured to be {max}");
+    }
+}
+
+// MORE PATTERN MATCHING -----------------------------------------------------
+
+// you can mix if let, else if, else if let expressions
+pub fn background() {
+    let favorite_color: Option<&str> = None;
+    let is_tuesday = false;
+    let age: Result<u8, _> = "34".parse();
+
+    if let Some(color) = favorite_color {
+        println!("Using your favorite color, {color}, as the background");
+    } else if is_tuesday {
+        println!("Tuesday is green day!");
+    } else if let Ok(age) = age {
+        if age > 30 {
+            println!("Using purple as the background color");
+        } else {
+            println!("Using orange as the background color");
+        }
+    } else {
+        println!("Using blue as the background color");
+    }
+}
+
+// while let conditional loops: for as long as a pattern continues to match
+pub fn while_let() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for val in [1, 2, 3] {
+            tx.send(val).unwrap();
+        }
+    });
+
+    while let Ok(value) = rx.recv() {
+        println!("{value}");
+    }
+}
+
+// for loops: the value that directly follows the keyword for is a pattern
+pub fn for_patterns() {
+    let v = ['a', 'b', 'c'];
+
+    for (index, value) in v.iter().enumerate() {
+        println!("{value} is at index {index}");
+    }
+
+    // so is what follows let
+    let (x, y, z) = (2, 3, 4);
+}
+
+// function parameters
+pub fn print_coordinates(&(x, y): &(i32, i32)) {
+    println!("Current location: ({x}, {y})");
+}
+
+pub fn literals(x: Option<i32>, n: i32, c: char) {
+    // named variables
+    match x {
+        Some(50) => println!("50"),
+        Some(k) => println!("{}", k),
+        _ => (),
+    }
+
+    // multiple patterns
+    match n {
+        1 | 2 => println!("Either one or two"),
+        _ => (),
+    }
+
+    // matching ranges with ..=
+    match n {
+        1..=5 => println!("Between 1 and 5 (inclusive)"),
+        _ => (),
+    }
+
+    match c {
+        'a'..='z' => println!("Lowercase"),
+        _ => println!("Not lowercase"),
+    }
+}
+
+// destructuring
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub fn destructuring() {
+    let p = Point { x: 1, y: 2 };
+
+    let Point { x: a, y: b } = p;
+    assert_eq!(1, a);
+    assert_eq!(2, b);
+
+    // the following is cool
+    match p {
+        Point { x, y: 0 } => println!("On x-axis at {x}"),
+        Point { x: 0, y } => println!("On y-axis at {y}"),
+        Point { x, y } => println!("On neither axis, at {:?}", (x, y)),
+    }
+
+    // more destructuring
+    let p = Point { x: 12, y: 14 };
+    let ((feet, inches), Point { x, y }) = ((5, 11), p);
+}
+
+/// Nested enums, matched at once.
+pub mod nested {
+    pub enum Color {
+        Rgb(i32, i32, i32),
+        Hsv(i32, i32, i32),
+    }
+
+    pub enum Message {
+        Quit,
+        Move { x: i32, y: i32 },
+        Write(String),
+        ChangeColor(Color),
+    }
+
+    pub fn describe(msg: Message) {
+        match msg {
+            Message::ChangeColor(Color::Rgb(x, y, z)) =>
+                println!("The rgb values are: {x}, {y}, {z}"),
+            Message::Move { x, .. } =>
+                println!("The value of x is {x}"),
+            _ =>
+                println!("Don't care"),
+        }
+    }
+}
+
+// ignore values with _
+pub fn foo(_: i32, y: i32) {
+    println!("Only uses y parameter: {y}");
+}
+
+pub fn ignoring() {
+    foo(3, 4);
+
+    // ignore parts of a value with a nested _
+    let one = Some(1);
+    let two = Some(2);
+
+    match (one, two) {
+        (Some(_), Some(_)) => println!("Yo"),
+        _ => (),
+    }
+
+    let numbers = (1, 2, 3, 4, 5);
+
+    match numbers {
+        (x, _, y, _, z) =>
+            println!("The first, third and fifth are {x}, {y}, {z}"),
+    }
+
+    // .. for the rest; in a tuple it may be in the middle
+    match numbers {
+        (first, .., last) => println!("The first and last are {first} and {last}"),
+    }
+}
+
+// ignoring remaining parts of a value with ..
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+pub fn rest() {
+    let origin = Point3 { x: 0, y: 0, z: 0 };
+
+    match origin {
+        Point3 { x, .. } => println!("The x is {x}"),
+    }
+}
+
+// extra conditionals with match guards
+pub fn guards() {
+    let num = Some(4);
+
+    match num {
+        Some(k) if k % 2 == 0 => println!("The number {k} is even."),
+        Some(k) => println!("The number {k} is odd."),
+        None => (),
+    }
+
+    let x = 4;
+    let y = false;
+
+    match x {
+        4..=6 if y => println!("yes"),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cents() {
+        assert_eq!(value_in_cents(Coin::Dime), 10);
+        assert_eq!(value_in_cents_lucky(Coin::Penny), 1);
+        assert_eq!(states::value_in_cents(states::Coin::Quarter(states::UsState::Alaska)), 25);
+    }
+
+    #[test]
+    fn all_but_the_quarters() {
+        let coins = [states::Coin::Penny, states::Coin::Quarter(states::UsState::Alabama), states::Coin::Dime];
+        assert_eq!(states::count_others(&coins), 2);
+    }
+
+    #[test]
+    fn plus_one_or_none() {
+        assert_eq!(plus_one(Some(5)), Some(6));
+        assert_eq!(plus_one(None), None);
+    }
+}
@@ -0,0 +1,158 @@
+//! Rust/error_chains.rs: an error wrapping its source, context added at
+//! each level, the chain printed, and a backtrace.
+
+use std::backtrace::Backtrace;
+use std::error::Error;
+use std::fmt::{self, Write};
+use std::io;
+
+// A LAYERED ERROR --------------------------------------------------------
+
+// An error's Display is its own message, without the source's; source()
+// returns the error it wraps.
+
+#[derive(Debug)]
+pub struct ConfigError {
+    pub path: String,
+    source: io::Error,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot load the config from {}", self.path)
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+pub fn load(path: &str) -> Result<String, ConfigError> {
+    std::fs::read_to_string(path).map_err(|source| ConfigError { path: path.to_string(), source })
+}
+
+// CONTEXT AT EACH LEVEL --------------------------------------------------
+
+// One error type that's a message and a boxed source, with an extension
+// trait to add it on any Result, the way anyhow's .context() does.
+
+#[derive(Debug)]
+pub struct Context {
+    message: String,
+    source: Box<dyn Error + Send + Sync>,
+}
+
+impl fmt::Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for Context {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+pub trait WithContext<T> {
+    fn context(self, message: &str) -> Result<T, Context>;
+    fn with_context(self, message: impl FnOnce() -> String) -> Result<T, Context>;
+}
+
+impl<T, E: Error + Send + Sync + 'static> WithContext<T> for Result<T, E> {
+    fn context(self, message: &str) -> Result<T, Context> {
+        self.with_context(|| message.to_string())
+    }
+    fn with_context(self, message: impl FnOnce() -> String) -> Result<T, Context> {
+        self.map_err(|e| Context { message: message(), source: Box::new(e) })
+    }
+}
+
+// Each level says what it was doing; with_context builds the message
+// only on an error.
+
+pub fn port(text: &str) -> Result<u16, Context> {
+    let line = text.lines().find(|l| l.starts_with("port")).unwrap_or("port = ");
+    let value = line.trim_start_matches("port").trim_start_matches([' ', '=']);
+    value.parse::<u16>().with_context(|| format!("invalid port `{value}`"))
+}
+
+pub fn start(config: &str) -> Result<u16, Context> {
+    port(config).context("cannot start the server")
+}
+
+// PRINTING THE CHAIN -----------------------------------------------------
+
+// source() is a linked list: std::iter::successors walks it. The first
+// is the headline, the rest are numbered below it.
+
+pub fn report(error: &(dyn Error + 'static)) -> String {
+    let mut out = format!("error: {error}");
+    let causes: Vec<&dyn Error> = std::iter::successors(error.source(), |&e| e.source()).collect();
+    if !causes.is_empty() {
+        out += "\ncaused by:";
+    }
+    for (i, cause) in causes.iter().enumerate() {
+        let _ = write!(out, "\n    {i}: {cause}");
+    }
+    out
+}
+
+// BACKTRACES -------------------------------------------------------------
+
+// Captured where the error is made, at the bottom of the chain, and kept
+// in it.
+
+#[derive(Debug)]
+pub struct Failure {
+    pub message: String,
+    pub backtrace: Backtrace,
+}
+
+pub fn fail(message: &str) -> Failure {
+    Failure { message: message.to_string(), backtrace: Backtrace::force_capture() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::backtrace::BacktraceStatus;
+
+    #[test]
+    fn a_layered_error() {
+        let e = load("/no/such/app.toml").unwrap_err();
+        assert_eq!(e.to_string(), "cannot load the config from /no/such/app.toml"); // prints: cannot load the config from /no/such/app.toml
+        let source = e.source().unwrap().downcast_ref::<io::Error>().unwrap();
+        assert_eq!(source.kind(), io::ErrorKind::NotFound); // prints: No such file or directory (os error 2)
+    }
+
+    #[test]
+    fn context_at_each_level() {
+        let e = start("name = \"app\"\nport = 80a").unwrap_err();
+        assert_eq!(e.to_string(), "cannot start the server"); // prints: cannot start the server / invalid port `80a`
+        assert_eq!(e.source().unwrap().to_string(), "invalid port `80a`");
+        assert_eq!(start("port = 80").unwrap(), 80);
+    }
+
+    // the lines, as the notes write them: without the indent
+    #[test]
+    fn printing_the_chain() {
+        let chain = report(&start("port = 80a").unwrap_err());
+        let lines: Vec<&str> = chain.lines().map(str::trim).collect();
+        // prints: error: cannot start the server
+        // prints: caused by:
+        // prints: 0: invalid port `80a`
+        // prints: 1: invalid digit found in string
+        assert_eq!(lines, ["error: cannot start the server", "caused by:", "0: invalid port `80a`", "1: invalid digit found in string"]);
+        let not_a_number = "80a".parse::<u16>().unwrap_err();
+        assert_eq!(report(&not_a_number), "error: invalid digit found in string"); // prints: error: invalid digit found in string
+    }
+
+    #[test]
+    fn backtraces() {
+        let failure = fail("disk full");
+        assert_eq!((failure.message.as_str(), failure.backtrace.status()), ("disk full", BacktraceStatus::Captured)); // prints: disk full true
+    }
+}
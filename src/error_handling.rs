@@ -0,0 +1,141 @@
+//! Rust/error_handling.rs: panic!, Result, unwrap and expect, and `?`.
+
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, ErrorKind, Read};
+use std::path::Path;
+
+// RECOVERABLE ERRORS WITH Result ----------------------------------------
+
+// File::open returns Result<File, io::Error>: the file, or why not.
+
+pub fn open_or_panic(path: impl AsRef<Path>) -> File {
+    match File::open(path) {
+        Ok(file) => file,                       // Result::Ok(file) => file
+        Err(error) => panic!("{error:?}"),
+    }
+}
+
+// a nested match: create the file if it does not exist, otherwise panic
+pub fn open_or_create(path: impl AsRef<Path>) -> File {
+    let path = path.as_ref();
+    match File::open(path) {
+        Ok(file)   => file,
+        Err(error) => match error.kind() {
+            ErrorKind::NotFound => match File::create(path) {
+                Ok(file) => file,
+                Err(e)   => panic!("Problem creating file: {e:?}"),
+            },
+            other_error => panic!("Some error: {other_error:?}"),
+        },
+    }
+}
+
+// the same with closures and unwrap_or_else()
+pub fn open_or_create_closures(path: impl AsRef<Path>) -> File {
+    let path = path.as_ref();
+    File::open(path).unwrap_or_else(|error| {
+        if error.kind() == ErrorKind::NotFound {
+            File::create(path).unwrap_or_else(|error| {
+                panic!("Problem creating file: {error:?}")
+            })
+        } else {
+            panic!("Some error: {error:?}")
+        }
+    })
+}
+
+// using unwrap and expect . . .
+
+/// `unwrap` panics with the error; `expect` with a message of your own
+/// in front of it:
+///
+/// ```should_panic
+/// use std::fs::File;
+///
+/// let file_handle = File::open("no-such-dir/esabi.txt")
+///     .expect("esabi.txt should be included in the directory");
+/// ```
+pub fn open_or_expect(path: impl AsRef<Path>) -> File {
+    File::open(path).expect("esabi.txt should be included in the directory")
+}
+
+// Propagating errors . . .
+
+// The notes open "esabi.txt"; these take the path, so each test can
+// hand them a file of its own (temp_files)
+pub fn read_username_from_file(path: impl AsRef<Path>) -> Result<String, io::Error> {
+    let mut username_file = File::open(path)?;
+    let mut username = String::new();
+    username_file.read_to_string(&mut username)?;
+    Ok(username)
+}
+
+// We can even shorten the code by chaining method calls . . .
+pub fn read_username_chained(path: impl AsRef<Path>) -> Result<String, io::Error> {
+    let mut username = String::new();
+
+    File::open(path)?.read_to_string(&mut username)?;
+
+    Ok(username)
+}
+
+// We can make this even shorter using fs::read_to_string
+pub fn read_username_fs(path: impl AsRef<Path>) -> Result<String, io::Error> {
+    fs::read_to_string(path)
+}
+
+// where the ? operator can be used . . .
+
+/// `?` on an Option, in a function that returns one.
+///
+/// And only in a function whose return type it fits:
+///
+/// ```compile_fail,E0277
+/// use std::fs::File;
+///
+/// fn main() {
+///     let file_handle = File::open("esabi.txt")?;
+/// }
+/// ```
+pub fn last_char_of_first_line(text: &str) -> Option<char> {
+    text.lines().next()?.chars().last()
+}
+
+// main can also return a Result<(), Box<dyn Error>>: "any kind of error"
+pub fn run(path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+    let file_handle = File::open(path)?;        // ? operator
+    drop(file_handle);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp_files::TempDir;
+
+    #[test]
+    fn question_mark_on_an_option() {
+        assert_eq!(last_char_of_first_line("Hello, world\nHow are you"), Some('d'));
+        assert_eq!(last_char_of_first_line("\nhi"), None);
+        assert_eq!(last_char_of_first_line(""), None);
+    }
+
+    #[test]
+    fn propagating_errors() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("esabi.txt");
+        fs::write(&path, "ferris").unwrap();
+        assert_eq!(read_username_from_file(&path).unwrap(), "ferris");
+        assert_eq!(read_username_chained(&path).unwrap(), "ferris");
+        assert_eq!(read_username_fs(&path).unwrap(), "ferris");
+        assert!(run(&path).is_ok());
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read_username_from_file(&path).unwrap_err().kind(), ErrorKind::NotFound);
+        assert_eq!(read_username_chained(&path).unwrap_err().kind(), ErrorKind::NotFound);
+        assert_eq!(read_username_fs(&path).unwrap_err().kind(), ErrorKind::NotFound);
+        assert!(run(&path).is_err());
+    }
+}
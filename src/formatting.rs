@@ -0,0 +1,184 @@
+//! Rust/formatting.rs: the format language, and Display implemented by
+//! hand.
+
+#![allow(clippy::approx_constant)]
+
+use std::fmt::{self, Write};
+
+// ARGUMENTS --------------------------------------------------------------
+
+// By position, by name, or a variable in scope captured by its name.
+pub fn arguments() -> String {
+    let (city, degrees) = ("Oslo", -3);
+    println!("{} is at {}", city, degrees);                  // prints: Oslo is at -3
+    println!("{0} is at {1}, and {0} is cold", city, degrees);   // prints: Oslo is at -3, and Oslo is cold
+    println!("{place} is at {t}", place = city, t = degrees);   // prints: Oslo is at -3
+    format!("{{{city}}}")                                   // {Oslo}
+}
+
+// DISPLAY AND DEBUG ------------------------------------------------------
+
+// {} is what a user should read, {:?} what a programmer wants to see,
+// derived; {:#?} is Debug spread over lines.
+
+#[derive(Debug)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+// WIDTH, FILL AND ALIGNMENT ----------------------------------------------
+
+pub fn padded(width: usize) -> String {
+    format!("[{:>width$}]", "ab")           // width from a captured variable
+}
+
+// PRECISION --------------------------------------------------------------
+
+pub fn precision() -> String {
+    let pi = 3.14159265;
+    format!("{:.2} {:.0} {:.4} {:.3} {:.*}", pi, pi, 2.0, "truncated", 1, pi)   // 3.14 3 2.0000 tru 3.1
+}
+
+// NUMBERS ----------------------------------------------------------------
+
+pub fn numbers() -> String {
+    format!("{:x} {:X} {:#x} {:o} {:#b}", 255, 255, 255, 8, 5)   // ff FF 0xff 10 0b101
+}
+
+// IMPLEMENTING DISPLAY ---------------------------------------------------
+
+/// write! ignores the width, fill and alignment asked for:
+/// `format!("[{:>10}]", price)` is `[$19.99]`.
+pub mod plain {
+    use std::fmt;
+
+    pub struct Money {
+        pub cents: i64,
+    }
+
+    impl fmt::Display for Money {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "${}.{:02}", self.cents / 100, self.cents % 100)
+        }
+    }
+}
+
+// To take part in width, fill and alignment, build the text and hand it
+// to f.pad(). f.alternate() is there for a second form, under {:#}.
+
+pub struct Money {
+    pub cents: i64,
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = if f.alternate() {
+            format!("{} cents", self.cents)
+        } else {
+            format!("${}.{:02}", self.cents / 100, self.cents % 100)
+        };
+        f.pad(&text)
+    }
+}
+
+// WRITING ELSEWHERE ------------------------------------------------------
+
+// write!/writeln! into a String, with std::fmt::Write in scope; for a
+// String it can't be an error.
+pub fn table(rows: &[(&str, u32)]) -> String {
+    let mut table = String::new();
+    for (item, qty) in rows {
+        writeln!(table, "{item:<8}{qty:>4}").unwrap();
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // compared a word at a time, as `quiz notes` does
+    fn words(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn arguments() {
+        let (city, degrees) = ("Oslo", -3);
+        assert_eq!(format!("{} is at {}", city, degrees), "Oslo is at -3"); // prints: Oslo is at -3
+        assert_eq!(format!("{0} is at {1}, and {0} is cold", city, degrees), "Oslo is at -3, and Oslo is cold"); // prints: Oslo is at -3, and Oslo is cold
+        assert_eq!(format!("{place} is at {t}", place = city, t = degrees), "Oslo is at -3"); // prints: Oslo is at -3
+        assert_eq!(format!("{city} is at {degrees}"), "Oslo is at -3"); // prints: Oslo is at -3
+        assert_eq!(super::arguments(), "{Oslo}"); // prints: {Oslo}
+    }
+
+    #[test]
+    fn display_and_debug() {
+        let name = "tab\there";
+        assert_eq!(format!("{name} / {name:?}"), "tab\there / \"tab\\there\""); // prints: tab	here / "tab\there"
+        assert_eq!(format!("{:?}", vec![Some(1), None]), "[Some(1), None]"); // prints: [Some(1), None]
+        let point = format!("{:#?}", Point { x: 1, y: 2 });
+        // prints: Point {
+        // prints: x: 1,
+        // prints: y: 2,
+        // prints: }
+        assert_eq!(point.lines().map(str::trim).collect::<Vec<_>>(), ["Point {", "x: 1,", "y: 2,", "}"]);
+    }
+
+    #[test]
+    fn width_fill_and_alignment() {
+        assert_eq!(format!("[{:6}]", "ab"), "[ab    ]");
+        assert_eq!(format!("[{:6}]", 42), "[    42]");
+        assert_eq!(format!("[{:^6}]", "ab"), "[  ab  ]");
+        assert_eq!(format!("{:*<6}|{:*^6}|{:*>6}", "ab", "ab", "ab"), "ab****|**ab**|****ab"); // prints: ab****|**ab**|****ab
+        assert_eq!(format!("{:-^20}", " menu "), "------- menu -------"); // prints: ------- menu -------
+        let width = 8;
+        assert_eq!(padded(width), "[      ab]");
+        assert_eq!(format!("{:.>width$}", 7), ".......7"); // prints: .......7
+    }
+
+    #[test]
+    fn precision() {
+        // prints: 3.14 3 2.0000
+        // prints: tru
+        // prints: 3.1
+        assert_eq!(words(&super::precision()), "3.14 3 2.0000 tru 3.1");
+        let pi = 3.14159265;
+        assert_eq!(format!("{:.2} {:.0} {:.4}", pi, pi, 2.0), "3.14 3 2.0000");
+        assert_eq!(format!("{:.3}", "truncated"), "tru");
+        assert_eq!(format!("{:.*}", 1, pi), "3.1");
+        assert_eq!(format!("[{:8.3}]", pi), "[   3.142]");
+        assert_eq!(format!("{:.2} {:.2}", 0.125, 2.675), "0.12 2.67"); // prints: 0.12 2.67
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(format!("{:+} {:+}", 5, -5), "+5 -5"); // prints: +5 -5
+        assert_eq!(format!("{:05} {:+05} {:05.1}", 42, 42, -1.5), "00042 +0042 -01.5"); // prints: 00042 +0042 -01.5
+        assert_eq!(super::numbers(), "ff FF 0xff 10 0b101"); // prints: ff FF 0xff 10 0b101
+        assert_eq!(format!("{:#010x} {:08b}", 255, 5u8), "0x000000ff 00000101"); // prints: 0x000000ff 00000101
+        assert_eq!(format!("{:e} {:.2e}", 1234.5, 0.000123), "1.2345e3 1.23e-4"); // prints: 1.2345e3 1.23e-4
+        assert_eq!(format!("{:x}", -1i8), "ff"); // prints: ff
+    }
+
+    #[test]
+    fn implementing_display() {
+        let price = plain::Money { cents: 1999 };
+        assert_eq!(price.to_string(), "$19.99"); // prints: $19.99
+        assert_eq!(format!("[{:>10}]", price), "[$19.99]");
+        let price = Money { cents: 1999 };
+        assert_eq!(format!("[{:>10}]", price), "[    $19.99]");
+        assert_eq!(format!("{:*<10}|{:#}", price, price), "$19.99****|1999 cents"); // prints: $19.99****|1999 cents
+        assert_eq!(price.to_string().len(), 6); // prints: 6
+    }
+
+    #[test]
+    fn writing_elsewhere() {
+        let table = table(&[("apples", 3), ("kiwis", 12)]);
+        assert_eq!(table, "apples     3\nkiwis     12\n");
+        // prints: apples 3
+        // prints: kiwis 12
+        assert_eq!(table.lines().map(words).collect::<Vec<_>>(), ["apples 3", "kiwis 12"]);
+    }
+}
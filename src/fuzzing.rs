@@ -0,0 +1,68 @@
+//! Rust/fuzzing.rs: what a fuzzer does, by hand; the fuzz target itself
+//! is fuzz/fuzz_targets/calculate.rs, over [`crate::calculator`].
+
+use std::panic;
+
+// WHAT A FUZZER DOES, BY HAND --------------------------------------------
+
+/// The first letter of each word, with a byte slice that assumes each
+/// word has one. A trailing space makes an empty last word:
+///
+/// ```should_panic
+/// langscape::fuzzing::initials("BBBé ");
+/// ```
+pub fn initials(name: &str) -> String {
+    name.split(' ').map(|word| &word[..1]).collect()
+}
+
+// The fix: ask for chars, and skip words with none.
+pub fn initials_fixed(name: &str) -> String {
+    name.split(' ').filter_map(|word| word.chars().next()).collect()
+}
+
+// xorshift: random enough, and the same every run
+pub struct Rng(pub u64);
+
+impl Rng {
+    // never ends, so it isn't an Iterator
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+// Make inputs, call `f` under catch_unwind, report the first that
+// panicked, and after how many tries: Some((3, "BBBé ")) for initials.
+pub fn first_panic(f: fn(&str) -> String, tries: usize) -> Option<(usize, String)> {
+    let alphabet = ['a', 'B', ' ', 'é'];
+    let mut rng = Rng(1);
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));          // quiet: the loop reports
+    let found = (1..=tries).find_map(|tries| {
+        let len = rng.next() % 8;
+        let input: String = (0..len).map(|_| alphabet[(rng.next() % 4) as usize]).collect();
+        panic::catch_unwind(|| f(&input)).is_err().then_some((tries, input))
+    });
+    panic::set_hook(hook);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn what_it_found() {
+        assert_eq!(initials("Grace Brewster Hopper"), "GBH"); // prints: GBH
+        assert_eq!(first_panic(initials, 1000), Some((3, "BBBé ".to_string()))); // prints: Some((3, "BBBé "))
+    }
+
+    #[test]
+    fn the_fix() {
+        assert_eq!(initials_fixed("é B "), "éB");
+        assert_eq!(first_panic(initials_fixed, 1000), None);
+    }
+}
@@ -0,0 +1,230 @@
+//! Rust/generics.rs: generic structs, traits, trait bounds, and
+//! lifetimes.
+
+#![allow(dead_code, unused_variables)]
+
+use std::fmt::{Debug, Display};
+
+// GENERICS ---------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct Point<X1, Y1> {
+    a: X1,
+    b: Y1,
+}
+
+impl<X1, Y1> Point<X1, Y1> {
+    pub fn createnew<X2, Y2>(self, other: Point<X2, Y2>) -> Point<X1, Y2> {  // cannot write &self
+        Point {                             // cannot write Point<X1, Y2
+            a: self.a,
+            b: other.b,
+        }
+    }
+}
+
+pub fn mixup() -> Point<i32, bool> {
+    let object_one = Point {
+        a: 37,
+        b: String::from("Rust"),
+    };
+    let object_two = Point {
+        a: String::from("Programming"),
+        b: true,
+    };
+    let object_three = object_one.createnew(object_two);       // object_one and object_two cannot be used again
+    // let object_three = Point::createnew(object_one, object_two);  // this will work the same way
+
+    println!("{:?}", object_three);                             // prints: Point { a: 37, b: true }
+    object_three
+}
+
+// TRAITS -----------------------------------------------------------------
+
+pub trait Summary {         // pub, because we want this to be visible from outside this module
+    fn summarize(&self) -> String;    // no definition, waiting for an implementer, observe the semi-colon
+}
+
+pub struct Newspaper {
+    pub title: String,
+    pub editor: String,
+    pub id: u32,
+}
+
+pub struct Team {
+    pub name: String,
+    pub captain: String,
+    pub champion: bool,
+}
+
+// then, in the same or another module
+impl Summary for Newspaper {
+    fn summarize(&self) -> String {
+        format!("title: {}, editor: {}, id: {}", self.title, self.editor, self.id) // Returns String, not &str
+    }
+}
+
+impl Summary for Team {
+    fn summarize(&self) -> String {
+        format!("name: {}, captain {}, champion {}", self.name, self.captain, self.champion)
+    }
+}
+
+pub fn summaries() {
+    let newspaper = Newspaper {
+        title: "Huiyen Lampao".to_string(), // Need String type
+        editor: "Hemanta".to_string(),
+        id: 34,
+    };
+    let team = Team {
+        name: "Chennai Super Kings".to_string(),
+        captain: "MS Dhoni".to_string(),
+        champion: true,
+    };
+    println!("{}", newspaper.summarize());
+    // The following also works the same
+    println!("{}", Summary::summarize(&newspaper)); // Need &newspaper as argument
+    println!("{}", team.summarize());
+}
+
+/// Default implementation: use it or override it.
+pub mod defaults {
+    use super::Newspaper;
+
+    pub trait Summary {
+        fn summarize(&self) -> String {            // use this if not overriden
+            String::from("This is the default output")
+        }
+    }
+
+    impl Summary for Newspaper {}                  // use default, need to put the braces {}
+}
+
+/// Default implementations can call other methods in the same trait,
+/// even ones without a default.
+pub mod default_calls {
+    use super::Newspaper;
+
+    pub trait Summary {
+        fn summarize_name(&self) -> String;
+
+        fn summarize(&self) -> String {                             // default implementation
+            format!("The string is then {}", self.summarize_name())
+        }
+    }
+
+    impl Summary for Newspaper {
+        fn summarize_name(&self) -> String {                        // implemented summarize_name
+            self.title.to_string()
+        }
+    }
+}
+
+// TRAITS AS PARAMETERS ---------------------------------------------------
+
+pub fn notify(item: &impl Summary) {   // the argument must be of any type for which
+                                       // the Summary trait is implemented
+    println!("Breaking news! {}", item.summarize());
+}
+
+// The above is a syntax sugar for a longer form known as trait bound
+pub fn notify_bound<T: Summary>(item: &T) {
+    println!("Breaking news! {}", item.summarize());
+}
+
+pub fn notify_two(item1: &impl Summary, item2: &impl Summary) {  // item1 and item2 can be of any types
+                                                                 // for which Summary trait is implemented
+}
+
+// To make item1 and item2 the same type, a trait bound
+pub fn notify_same<T: Summary>(item1: &T, item2: &T) {           // item1 and item2 are now of the same type T
+}
+
+// SPECIFY MULTIPLE TRAIT BOUNDS WITH + SYNTAX
+
+pub fn notify_display(item: &(impl Summary + Display)) {
+    println!("{item}: {}", item.summarize());
+}
+
+pub fn notify_display_bound<T: Summary + Display>(item: &T) {
+    println!("{item}: {}", item.summarize());
+}
+
+// Clearer TRAIT BOUNDS with WHERE clauses
+
+pub fn some_function<T: Summary + Display, U: Display + Debug>(t: &T, u: &U) -> usize {
+    t.summarize().len() + u.to_string().len()
+}
+
+// we can write, using where clause:
+pub fn some_function_where<T, U>(t: &T, u: &U) -> usize
+where
+    T: Summary + Display,
+    U: Display + Debug,
+{
+    t.summarize().len() + u.to_string().len()
+}
+
+// Returning Types that implement Traits
+pub fn returns_summarizable() -> impl Summary {
+    Team {                                     // but this can return only one Type that implements Summary
+        name: "Kangleipak".to_string(),
+        captain: "Nongban".to_string(),
+        champion: false,
+    }
+}
+
+// LIFETIME ANNOTATIONS ---------------------------------------------------
+
+/// The returned reference is valid as long as both parameters are.
+///
+/// Without the annotations the compiler can't tell which of the two the
+/// result borrows from:
+///
+/// ```compile_fail,E0106
+/// fn function(x: &str, y: &str) -> &str {
+///     x
+/// }
+/// ```
+pub fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() { x } else { y }
+}
+
+// an instance of Excerpt cannot outlive the reference it holds in its part field
+pub struct Excerpt<'a> {
+    pub part: &'a str,
+}
+
+impl<'a> Excerpt<'a> {  // Put lifetime annotations after impl and then after struct name
+    pub fn level(&self) -> i32 {
+        3
+    }
+}
+
+// the third elision rule applies to the following case:
+impl Excerpt<'_> {
+    pub fn announce(&self, a: &str) -> &str {
+        println!("Attention please: {a}");
+        self.part
+    }
+}
+
+// The static lifetime: string literals live in the binary
+pub const STATIC: &str = "I have a static lifetime.";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixed_up() {
+        let point = mixup();
+        assert_eq!((point.a, point.b), (37, true));
+    }
+
+    #[test]
+    fn the_longer_one() {
+        assert_eq!(longest("abcd", "xyz"), "abcd");
+        let excerpt = Excerpt { part: "Call me Ishmael" };
+        assert_eq!(excerpt.announce("now"), "Call me Ishmael");
+    }
+}
@@ -0,0 +1,64 @@
+//! The examples of the Rust notes, as a library that compiles.
+//!
+//! The notes in Rust/ are written to be read: a topic is a file of
+//! snippets between comments, one paragraph at a time, and isn't a
+//! program. Each module here is one of those files, with its examples
+//! wrapped in functions, so that `cargo build` proves they compile
+//! together. The functions return what the notes say they print, and
+//! each module's tests assert it, the assert marked with the note's own
+//! `// prints: X`; a panic the notes show is a test's, or a doc test's,
+//! `should_panic`. The examples of a compile error are doc tests
+//! (`compile_fail`) on the function nearest them, and the undefined
+//! behaviour in unsafe_rust is only compiled (`no_run`).
+//!
+//! The notes stay the text; `quiz notes` still checks what each snippet
+//! there prints, and tests/notes.rs that its module's tests say the
+//! same: an annotation changed in the notes fails there until the
+//! module follows.
+//! The module names are the files' names. Code that needs a crate
+//! (tokio, mockall) stays in the notes, so the crate has no
+//! dependencies: async_sync.rs, all of it tokio, has no module. Only the
+//! benchmark, Rust/benches/loops.rs, has one, criterion, as a
+//! dev-dependency.
+//!
+//! Three modules aren't topics: `registry` has the notes' own text,
+//! split into sections and snippets, for tools that enumerate them,
+//! `calculator` is Rust/projects/calculator.rs, for the fuzz target in
+//! fuzz/ to call, and `c_gallery` has Rust/compare/c.rs's gallery of
+//! C bugs that don't compile in Rust, as doc tests.
+
+pub mod any;
+pub mod api_design;
+pub mod backpressure;
+pub mod basics;
+pub mod c_gallery;
+// a program of its own: its main() is only used there
+#[path = "../Rust/projects/calculator.rs"]
+#[allow(dead_code)]
+pub mod calculator;
+pub mod closures_and_iterators;
+pub mod coherence;
+pub mod collections;
+pub mod concurrency;
+pub mod editions;
+pub mod enums;
+pub mod enums_pattern_matching;
+pub mod error_chains;
+pub mod error_handling;
+pub mod formatting;
+pub mod fuzzing;
+pub mod generics;
+pub mod loop_performance;
+pub mod macros;
+pub mod monomorphization;
+pub mod option_layout;
+pub mod ownership;
+pub mod parsing;
+pub mod profiling;
+pub mod registry;
+pub mod shutdown;
+pub mod structures;
+pub mod temp_files;
+pub mod test_doubles;
+pub mod traits;
+pub mod unsafe_rust;
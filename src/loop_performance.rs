@@ -0,0 +1,102 @@
+//! Rust/loop_performance.rs: the same loops as a chain, an index loop
+//! and a for loop; the benchmarks are Rust/benches/loops.rs.
+
+// the index loops are what's being compared; % 2 is how the notes test for even
+#![allow(clippy::needless_range_loop, clippy::manual_is_multiple_of)]
+
+// THE SAME LOOP ----------------------------------------------------------
+
+// The same results, and nearly the same machine code.
+
+pub fn even_squares_iter(v: &[u64]) -> u64 {
+    v.iter().filter(|&&x| x % 2 == 0).map(|&x| x * x).sum()
+}
+
+pub fn even_squares_index(v: &[u64]) -> u64 {
+    let mut sum = 0;
+    for i in 0..v.len() {
+        if v[i] % 2 == 0 {
+            sum += v[i] * v[i];
+        }
+    }
+    sum
+}
+
+pub fn even_squares_for(v: &[u64]) -> u64 {
+    let mut sum = 0;
+    for &x in v {
+        if x % 2 == 0 {
+            sum += x * x;
+        }
+    }
+    sum
+}
+
+// BOUNDS CHECKS ----------------------------------------------------------
+
+/// Over two slices the compiler can't prove the index in bounds: nothing
+/// says b is as long as a.
+///
+/// ```should_panic
+/// langscape::loop_performance::dot_index(&[1, 2, 3], &[4, 5]);
+/// ```
+pub fn dot_index(a: &[u64], b: &[u64]) -> u64 {
+    let mut sum = 0;
+    for i in 0..a.len() {
+        sum += a[i] * b[i];                 // a[i] is fine, b[i] may not be
+    }
+    sum
+}
+
+// Cutting both slices to one length first makes it provable.
+pub fn dot_sliced(a: &[u64], b: &[u64]) -> u64 {
+    let n = a.len().min(b.len());
+    let (a, b) = (&a[..n], &b[..n]);         // now b[i] is in bounds wherever a[i] is
+    let mut sum = 0;
+    for i in 0..n {
+        sum += a[i] * b[i];
+    }
+    sum
+}
+
+// WHERE IT WASN'T THE SAME -----------------------------------------------
+
+// collect() knows the exact count up front and writes straight into the
+// buffer; each push checks the capacity and bumps the length.
+
+pub fn differences_iter(v: &[u64]) -> Vec<u64> {
+    v.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+pub fn differences_index(v: &[u64]) -> Vec<u64> {
+    let mut out = Vec::new();
+    for i in 1..v.len() {
+        out.push(v[i] - v[i - 1]);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_same_loop() {
+        let v: Vec<u64> = (1..=10).collect();
+        assert_eq!([even_squares_iter(&v), even_squares_index(&v), even_squares_for(&v)], [220; 3]); // prints: 220 220 220
+    }
+
+    #[test]
+    fn bounds_checks() {
+        assert_eq!(dot_index(&[1, 2, 3], &[4, 5, 6]), 32); // prints: 32
+        assert!(std::panic::catch_unwind(|| dot_index(&[1, 2, 3], &[4, 5])).is_err()); // prints: true
+        assert_eq!(dot_sliced(&[1, 2, 3], &[4, 5, 6]), 32); // prints: 32
+        assert_eq!(dot_sliced(&[1, 2, 3], &[4, 5]), 14);
+    }
+
+    #[test]
+    fn where_it_wasnt_the_same() {
+        // prints: [3, 5, 7] [3, 5, 7]
+        assert_eq!((differences_iter(&[1, 4, 9, 16]), differences_index(&[1, 4, 9, 16])), (vec![3, 5, 7], vec![3, 5, 7]));
+    }
+}
@@ -0,0 +1,62 @@
+//! Rust/macros.rs: macro_rules! and derive.
+
+// MACRO_RULES! -----------------------------------------------------------
+
+/// One `$x * $x` per argument, in a vec.
+#[macro_export]
+macro_rules! square_all {
+    ($($x:expr),*) => {                 // $x: an expression; $(...),* : zero or more, comma separated
+        vec![$($x * $x),*]              // one $x * $x per $x, comma separated again
+    };
+}
+
+/// The largest of its arguments; the second arm recurses.
+#[macro_export]
+macro_rules! maximum {
+    ($x:expr) => { $x };
+    ($x:expr, $($rest:expr),+) => {     // arms are tried in order; this one recurses
+        { let a = $x; let b = $crate::maximum!($($rest),+); if a > b { a } else { b } }
+    };
+}
+
+pub fn macro_rules() {
+    let squares = square_all!(1, 2, 3);
+    println!("{squares:?}");            // prints: [1, 4, 9]
+
+    println!("{}", maximum!(3, 8, 5));  // prints: 8
+
+    // Hygiene: the macro's a and b can't clash with the caller's
+    let (a, b) = (1, 2);
+    println!("{}", maximum!(b, a));     // prints: 2
+}
+
+// DERIVE -----------------------------------------------------------------
+
+// a procedural macro writes the impl: a field-by-field clone, so it only
+// compiles if every field is Clone
+#[derive(Clone)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn macro_rules() {
+        let squares = square_all!(1, 2, 3);
+        assert_eq!(squares, [1, 4, 9]); // prints: [1, 4, 9]
+        assert_eq!(maximum!(3, 8, 5), 8); // prints: 8
+        let (a, b) = (1, 2);
+        assert_eq!(maximum!(b, a), 2);
+    }
+
+    #[test]
+    fn derive() {
+        let p = Point { x: 1, y: 2 };
+        let q = p.clone();
+        assert_eq!((q.x, q.y), (1, 2));
+    }
+}
@@ -0,0 +1,73 @@
+//! Rust/monomorphization.rs: a generic compiled once per type, dyn once,
+//! and an inner function to keep the copies small.
+
+use std::fmt::Display;
+use std::path::Path;
+
+// WHAT THE COMPILER MAKES ------------------------------------------------
+
+// largest is written once; largest::<i32> and largest::<char> are two
+// functions in the binary, at two addresses.
+
+pub fn largest<T: PartialOrd + Copy>(list: &[T]) -> T {
+    let mut largest = list[0];
+    for &item in list {
+        if item > largest {
+            largest = item;
+        }
+    }
+    largest
+}
+
+pub fn two_copies() -> bool {
+    println!("{} {}", largest(&[3, 7, 2]), largest(&['q', 'z', 'a']));   // prints: 7 z
+    let for_i32 = largest::<i32> as fn(&[i32]) -> i32 as usize;
+    let for_char = largest::<char> as fn(&[char]) -> char as usize;
+    for_i32 != for_char                         // true
+}
+
+// The same with dyn: one function, whatever the types behind it.
+
+pub fn show(items: &[&dyn Display]) -> String {
+    items.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+// KEEPING IT SMALL -------------------------------------------------------
+
+// The part that depends on P is turned into a &Path first; the rest is
+// an inner function, compiled once.
+
+pub fn size<P: AsRef<Path>>(path: P) -> u64 {
+    fn inner(path: &Path) -> u64 {
+        std::fs::metadata(path).map_or(0, |m| m.len())
+    }
+    inner(path.as_ref())                    // the only line copied per P
+}
+
+// The same for a trait: the generic function turns its T into a &dyn
+// and hands it on.
+
+pub fn log<T: Display>(value: T) -> String {
+    fn inner(value: &dyn Display) -> String {
+        format!("[log] {value}")
+    }
+    inner(&value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn what_the_compiler_makes() {
+        assert_eq!((largest(&[3, 7, 2]), largest(&['q', 'z', 'a'])), (7, 'z')); // prints: 7 z
+        assert!(two_copies()); // prints: true
+        assert_eq!(show(&[&7, &'z', &"text"]), "7 z text"); // prints: 7 z text
+    }
+
+    #[test]
+    fn keeping_it_small() {
+        assert_eq!((size("/no/such/file"), size(String::from("/no/such/file"))), (0, 0)); // prints: 0 0
+        assert_eq!(log(42), "[log] 42"); // prints: [log] 42
+    }
+}
@@ -0,0 +1,94 @@
+//! Rust/option_layout.rs: what an Option costs, and the niches that
+//! make it free. The sizes are for a 64-bit target.
+
+use std::mem::size_of;
+use std::num::NonZeroU32;
+
+// THE TAG ----------------------------------------------------------------
+
+pub fn tag() {
+    assert_eq!(size_of::<Option<u8>>(), 2);
+    assert_eq!(size_of::<Option<u32>>(), 8);
+    assert_eq!(size_of::<Option<u64>>(), 16);
+    println!("{} {}", size_of::<u32>(), size_of::<Option<u32>>());   // prints: 4 8
+}
+
+// NICHES -----------------------------------------------------------------
+
+pub fn niches() {
+    assert_eq!(size_of::<Option<&u64>>(), size_of::<&u64>());
+    assert_eq!(size_of::<Option<Box<u64>>>(), size_of::<Box<u64>>());
+    assert_eq!(size_of::<Option<NonZeroU32>>(), size_of::<u32>());
+    println!("{} {} {}", size_of::<Option<&u64>>(), size_of::<Option<Box<u64>>>(), size_of::<Option<NonZeroU32>>());   // prints: 8 8 4
+
+    // A niche anywhere inside counts
+    assert_eq!(size_of::<Option<(u32, &u8)>>(), size_of::<(u32, &u8)>());
+    assert_eq!(size_of::<Option<String>>(), size_of::<String>());
+    println!("{} {}", size_of::<String>(), size_of::<Option<String>>());   // prints: 24 24
+}
+
+// Since None is promised to be zero, it's the null pointer, bit for bit.
+pub fn none_is_null() -> usize {
+    let none: Option<&u8> = None;
+    // SAFETY: Option<&u8> is guaranteed to have the size and layout of a
+    // pointer, with None as null
+    unsafe { std::mem::transmute::<Option<&u8>, usize>(none) }   // 0
+}
+
+// NONZERO ----------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy)]
+pub struct Id(pub NonZeroU32);
+
+pub fn nonzero() {
+    let ids = [NonZeroU32::new(7).map(Id), NonZeroU32::new(0).map(Id)];
+    println!("{ids:?}");                        // prints: [Some(Id(7)), None]
+    println!("{}", size_of::<[Option<Id>; 1000]>());   // prints: 4000
+
+    // Getting the number back out is get().
+    let id = NonZeroU32::new(42).unwrap();
+    println!("{}", id.get() + 1);               // prints: 43
+}
+
+// WHERE THE NICHES RUN OUT -----------------------------------------------
+
+pub fn nested() {
+    assert_eq!(size_of::<Option<Option<&u64>>>(), 16);
+    assert_eq!(size_of::<Option<Option<bool>>>(), 1);
+    assert_eq!(size_of::<Option<f64>>(), 16);
+    println!("{} {}", size_of::<Option<&u64>>(), size_of::<Option<Option<&u64>>>());   // prints: 8 16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_tag() {
+        tag();
+        assert_eq!((size_of::<u32>(), size_of::<Option<u32>>()), (4, 8)); // prints: 4 8
+    }
+
+    #[test]
+    fn niches() {
+        super::niches();
+        // prints: 8 8 4
+        assert_eq!((size_of::<Option<&u64>>(), size_of::<Option<Box<u64>>>(), size_of::<Option<NonZeroU32>>()), (8, 8, 4));
+        assert_eq!(none_is_null(), 0); // prints: 0
+        assert_eq!((size_of::<String>(), size_of::<Option<String>>()), (24, 24)); // prints: 24 24
+    }
+
+    #[test]
+    fn nonzero() {
+        let ids = [NonZeroU32::new(7).map(Id), NonZeroU32::new(0).map(Id)];
+        assert_eq!(ids.map(|id| id.map(|Id(n)| n.get())), [Some(7), None]); // prints: [Some(Id(7)), None]
+        assert_eq!(size_of::<[Option<Id>; 1000]>(), 4000); // prints: 4000
+        assert_eq!(NonZeroU32::new(42).unwrap().get() + 1, 43); // prints: 43
+    }
+
+    #[test]
+    fn where_the_niches_run_out() {
+        nested();
+        assert_eq!((size_of::<Option<&u64>>(), size_of::<Option<Option<&u64>>>()), (8, 16)); // prints: 8 16
+    }
+}
@@ -0,0 +1,125 @@
+//! Rust/ownership.rs: moves, clones, references and slices.
+
+#![allow(unused_variables, unused_mut, clippy::ptr_arg)]
+
+// OWNERSHIP -------------------------------------------------------------
+
+pub fn moves() {
+    let mut s = String::from("hello");      // requests memory at runtime
+    s.push_str(", world!");
+
+    let mut s1 = String::from("string");
+    let s2 = s1;                            // move: Rust now considers s1 invalid
+
+    let s1 = String::from("new string");
+
+    let s2 = s1.clone();                    // like deep copy
+}
+
+// Passing a variable to a function will move or copy (like assignment).
+
+pub fn passing() {
+    let s = String::from("hello");  // s comes into scope
+
+    takes_ownership(s);             // s's value moves into the function...
+                                    // ... and so is no longer valid here
+
+    let x = 5;                      // x comes into scope
+
+    makes_copy(x);                  // x would move into the function,
+                                    // but i32 is Copy, so it's okay to still
+                                    // use x afterward
+
+} // Here, x goes out of scope, then s.
+  // But because s's value was moved, nothing special happens.
+
+pub fn takes_ownership(some_string: String) { // some_string comes into scope
+    println!("{some_string}");
+} // Here, some_string goes out of scope and `drop` is called.
+  // The backing memory is freed.
+
+pub fn makes_copy(some_integer: i32) { // some_integer comes into scope
+    println!("{some_integer}");
+} // Here, some_integer goes out of scope. Nothing special happens.
+
+pub fn concatenation() {
+    let mut s1 = String::from("Good");
+    let s2 = " Will".to_string();      // s2 is String
+    s1.push_str(&s2);                  // push_str needs &str argument
+                                       // s1.push_str(s2) would fail
+                                       // s1.push_str(" Will") will work
+
+    let s3 = s1 + " Hunt" + "ing";     // s1 loses ownership
+    let s5 = s3.clone();               // s3 does not lose ownership
+    let s6 = &s3;                      // s3 does not lose ownsership
+
+    println!("{:?}", *s6);             // prints: "Good Will Hunting"
+    let s4 = s3;                       // s3 loses ownership
+}
+
+// REFERENCE -------------------------------------------------------------
+
+pub fn calculate_len(s: &String) -> usize {   // reference
+    s.len()                                   // allows referring to some value
+}                                             // without taking ownership
+
+/// Can't modify something we're borrowing:
+///
+/// ```compile_fail,E0596
+/// fn change_borrowed(some_string: &String) {
+///     some_string.push_str(", world");
+/// }
+/// ```
+pub fn change(some_string: &mut String) {
+    some_string.push_str(", world");
+}
+
+pub fn borrowing() -> String {
+    let mut s = String::from("hello");       // necessary to be mutable
+    println!("{}", calculate_len(&s));       // prints: 5
+
+    change(&mut s);
+
+    let r1 = &s; // no problem
+    let r2 = &s; // no problem
+    println!("{r1} and {r2}");
+    // variables r1 and r2 will not be used after this point
+
+    let r3 = &mut s; // no problem
+    println!("{r3}");
+    s
+}
+
+// SLICE ---------------------------------------------------------------
+
+pub fn slices() -> (String, String) {
+    let s = String::from("Saileza is my girlfriend");
+
+    let saileza    = &s[..7];            // [0..7]
+    let girlfriend = &s[14..];           // [14..24]
+    (saileza.to_string(), girlfriend.to_string())
+}
+
+// Taking a &str rather than a &String makes the function more general:
+// both a String (by &s) and a literal can be passed.
+pub fn first_word(s: &str) -> &str {
+    s.split(' ').next().unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrowed_and_changed() {
+        assert_eq!(calculate_len(&String::from("hello")).to_string(), "5");
+        assert_eq!(borrowing(), "hello, world");
+    }
+
+    #[test]
+    fn sliced() {
+        assert_eq!(slices(), ("Saileza".to_string(), "girlfriend".to_string()));
+        assert_eq!(first_word("Good Will Hunting"), "Good");
+        assert_eq!(first_word(""), "");
+    }
+}
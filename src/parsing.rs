@@ -0,0 +1,150 @@
+//! Rust/parsing.rs: str::parse, its errors, `?`, and FromStr for a type
+//! of your own.
+
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+// WHICH TYPE -------------------------------------------------------------
+
+/// The type can come from further along: here from the function the
+/// value is passed to. Without any, rustc can't guess:
+///
+/// ```compile_fail,E0284
+/// let n = "42".parse().unwrap();
+/// ```
+pub fn double(x: u64) -> u64 {
+    x * 2
+}
+
+pub fn which_type() -> u64 {
+    let a: i32 = "42".parse().unwrap();
+    let b = "42".parse::<u8>().unwrap();
+    let c = "2.5".parse::<f64>().unwrap();
+    println!("{} {} {}", a, b, c);              // prints: 42 42 2.5
+
+    let n = "21".parse().unwrap();
+    double(n)                                   // 42
+}
+
+// WHAT CAN GO WRONG ------------------------------------------------------
+
+// A ParseIntError's kind() says which way it went wrong. Nothing is
+// trimmed: " 42" is an error, and "+42" isn't.
+pub fn as_u8(text: &str) -> String {
+    match text.parse::<u8>() {
+        Ok(n) => format!("{text:?}: {n}"),
+        Err(e) => format!("{text:?}: {:?}", e.kind()),
+    }
+}
+
+// WITH ? -----------------------------------------------------------------
+
+pub fn sum(text: &str) -> Result<i64, ParseIntError> {
+    let mut total = 0;
+    for word in text.split(',') {
+        total += word.trim().parse::<i64>()?;
+    }
+    Ok(total)
+}
+
+// Or collect straight into a Result: the first error stops it, like ?.
+pub fn all(text: &str) -> Result<Vec<u32>, ParseIntError> {
+    text.split(' ').map(str::parse::<u32>).collect()
+}
+
+// And when a bad value should just be skipped, filter_map with ok().
+pub fn good(text: &str) -> Vec<u32> {
+    text.split(' ').filter_map(|w| w.parse().ok()).collect()
+}
+
+// FromStr FOR YOUR OWN TYPE ----------------------------------------------
+
+#[derive(Debug, PartialEq)]
+pub struct Rectangle {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RectangleError {
+    NoX,                                    // nothing to split at
+    Side(ParseIntError),                    // a side that isn't a number
+}
+
+impl fmt::Display for RectangleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RectangleError::NoX => write!(f, "expected WIDTHxHEIGHT, like 3x4"),
+            RectangleError::Side(e) => write!(f, "bad side: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for RectangleError {}
+
+// lets ? turn a ParseIntError into a RectangleError
+impl From<ParseIntError> for RectangleError {
+    fn from(e: ParseIntError) -> Self {
+        RectangleError::Side(e)
+    }
+}
+
+impl FromStr for Rectangle {
+    type Err = RectangleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (width, height) = s.trim().split_once('x').ok_or(RectangleError::NoX)?;
+        Ok(Rectangle { width: width.parse()?, height: height.parse()? })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::IntErrorKind;
+
+    #[test]
+    fn which_type() {
+        let a: i32 = "42".parse().unwrap();
+        let b = "42".parse::<u8>().unwrap();
+        let c = "2.5".parse::<f64>().unwrap();
+        assert_eq!((a, b, c), (42, 42, 2.5)); // prints: 42 42 2.5
+        assert_eq!(super::which_type(), 42); // prints: 42
+    }
+
+    #[test]
+    fn what_can_go_wrong() {
+        let texts = ["42", " 42", "+42", "", "4x2", "300", "-1"];
+        let printed: Vec<String> = texts.into_iter().map(as_u8).collect();
+        // prints: "42": 42
+        // prints: " 42": InvalidDigit
+        // prints: "+42": 42
+        // prints: "": Empty
+        // prints: "4x2": InvalidDigit
+        // prints: "300": PosOverflow
+        // prints: "-1": InvalidDigit
+        assert_eq!(
+            printed,
+            ["\"42\": 42", "\" 42\": InvalidDigit", "\"+42\": 42", "\"\": Empty", "\"4x2\": InvalidDigit", "\"300\": PosOverflow", "\"-1\": InvalidDigit"]
+        );
+        assert_eq!(("1e3".parse::<f64>(), "inf".parse::<f64>()), (Ok(1000.0), Ok(f64::INFINITY))); // prints: Ok(1000.0) Ok(inf)
+        assert_eq!(u8::from_str_radix("ff", 16), Ok(255)); // prints: Ok(255)
+    }
+
+    #[test]
+    fn with_the_question_mark() {
+        assert_eq!(sum("1, 2, 3"), Ok(6)); // prints: Ok(6)
+        assert_eq!(sum("1, two, 3").unwrap_err().kind(), &IntErrorKind::InvalidDigit); // prints: Err(ParseIntError { kind: InvalidDigit })
+        assert_eq!(all("4 8 15"), Ok(vec![4, 8, 15])); // prints: Ok([4, 8, 15])
+        assert_eq!(good("4 x 15"), [4, 15]); // prints: [4, 15]
+    }
+
+    #[test]
+    fn from_str_for_your_own_type() {
+        assert_eq!("3x4".parse::<Rectangle>(), Ok(Rectangle { width: 3, height: 4 })); // prints: Ok(Rectangle { width: 3, height: 4 })
+        assert_eq!(Rectangle::from_str("3 by 4"), Err(RectangleError::NoX));
+        assert_eq!("3 by 4".parse::<Rectangle>().unwrap_err().to_string(), "expected WIDTHxHEIGHT, like 3x4"); // prints: expected WIDTHxHEIGHT, like 3x4
+        assert_eq!("3xfour".parse::<Rectangle>().unwrap_err().to_string(), "bad side: invalid digit found in string"); // prints: bad side: invalid digit found in string
+    }
+}
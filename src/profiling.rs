@@ -0,0 +1,15 @@
+//! Rust/profiling.rs: timing with Instant. The rest of the notes is
+//! perf, flamegraphs and the other tools, run from the shell.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+// (1) timing: the spell checker project already does this
+
+// black_box hides the input from the optimizer, which in --release
+// could otherwise compute the whole sum at compile time.
+pub fn timed(n: u64) -> (u64, Duration) {
+    let start = Instant::now();
+    let total: u64 = (0..black_box(n)).map(|i| i * i).sum();
+    (total, start.elapsed())
+}
@@ -0,0 +1,123 @@
+//! The notes themselves, as data: every file in Rust/, its sections, and
+//! the snippets of code in each, for tools that would otherwise read and
+//! split the files on their own.
+//!
+//! The files are compiled in (`include_str!`), so a topic's text is a
+//! `&'static str` and everything below borrows from it. They're split by
+//! the code `quiz notes` splits them with, Rust/quiz/src/split.rs,
+//! compiled in here as a module too:
+//!
+//! - a section starts at each heading, `// TITLE -----` or `// TITLE =====`
+//!   for a part, `// Title . . .` for a section in one; what comes before
+//!   the first heading is a section with no title
+//! - a snippet is a paragraph of code: the lines between two blank lines,
+//!   outside block comments, with at least one line that isn't a `//`
+//!   comment. A blank line inside braces doesn't end one
+//!
+//! ```
+//! let collections = langscape::registry::topics().find(|t| t.name == "collections").unwrap();
+//! let strings = collections.sections.iter().find(|s| s.title == Some("Updating a string")).unwrap();
+//! assert!(strings.snippets.iter().any(|s| s.code.contains("push_str")));
+//! ```
+//!
+//! Every notes file is a topic:
+//!
+//! ```
+//! let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/Rust");
+//! let files = std::fs::read_dir(dir).unwrap().filter(|e| {
+//!     e.as_ref().unwrap().path().extension().is_some_and(|x| x == "rs")
+//! });
+//! assert_eq!(files.count(), langscape::registry::topics().count());
+//! ```
+
+#[path = "../Rust/quiz/src/split.rs"]
+#[allow(dead_code)]                     // Paragraph::comments: only quiz groups by them
+mod split;
+
+// a file added to Rust/ goes here too
+macro_rules! files {
+    ($($name:ident),* $(,)?) => {
+        &[$((stringify!($name), include_str!(concat!("../Rust/", stringify!($name), ".rs")))),*]
+    };
+}
+
+const FILES: &[(&str, &str)] = files![
+    any, api_design, async_sync, backpressure, basics, closures_and_iterators, coherence,
+    collections, concurrency, editions, enums, enums_pattern_matching, error_chains,
+    error_handling, formatting, fuzzing, generics, loop_performance, macros, monomorphization,
+    option_layout, ownership, parsing, profiling, shutdown, structures, temp_files, test_doubles,
+    traits, unsafe_rust,
+];
+
+/// One notes file.
+pub struct Topic {
+    pub name: &'static str,            // the file's name: "collections"
+    pub text: &'static str,            // all of it
+    pub sections: Vec<Section>,
+}
+
+/// The text under one heading, to the next.
+pub struct Section {
+    pub title: Option<&'static str>,   // "SLICE", "Updating a string"; None before the first
+    pub part: bool,                    // a `----` or `====` heading, not a `. . .` one
+    pub line: usize,                   // the heading's, from 1
+    pub snippets: Vec<Snippet>,
+}
+
+/// A paragraph of code, comments and annotations included.
+pub struct Snippet {
+    pub line: usize,                   // its first, from 1
+    pub code: &'static str,
+}
+
+impl Snippet {
+    /// The lines it's said to print, in order.
+    pub fn prints(&self) -> impl Iterator<Item = &'static str> {
+        self.annotations("// prints:")
+    }
+
+    /// A piece of the message it's said to panic with.
+    pub fn panics(&self) -> Option<&'static str> {
+        self.annotations("// panics:").next()
+    }
+
+    /// A piece of what Miri says about it.
+    pub fn undefined(&self) -> Option<&'static str> {
+        self.annotations("// ub:").next()
+    }
+
+    /// The error it's there to not compile with, by code or message.
+    pub fn compile_error(&self) -> Option<&'static str> {
+        self.annotations("// compile error:").next()
+    }
+
+    fn annotations(&self, marker: &'static str) -> impl Iterator<Item = &'static str> {
+        self.code.lines().filter_map(move |l| l.split_once(marker)).map(|(_, text)| text.trim())
+    }
+}
+
+/// Every topic, in the order of the files' names.
+pub fn topics() -> impl Iterator<Item = Topic> {
+    FILES.iter().map(|&(name, text)| Topic { name, text, sections: sections(text) })
+}
+
+fn sections(text: &'static str) -> Vec<Section> {
+    let mut sections = vec![Section { title: None, part: true, line: 1, snippets: Vec::new() }];
+    for (n, line) in (1..).zip(text.lines()) {
+        if let Some((title, part)) = split::heading(line) {
+            sections.push(Section { title: Some(title), part, line: n, snippets: Vec::new() });
+        }
+    }
+    for snippet in snippets(text) {
+        let under = sections.iter().rposition(|s| s.line <= snippet.line).unwrap_or(0);
+        sections[under].snippets.push(snippet);
+    }
+    sections.retain(|s| s.title.is_some() || !s.snippets.is_empty());
+    sections
+}
+
+// the paragraphs with code in them, not only comments
+fn snippets(text: &'static str) -> Vec<Snippet> {
+    let paragraphs = split::paragraphs(text).into_iter().map(|p| Snippet { line: p.first, code: &text[p.bytes] });
+    paragraphs.filter(|s| s.code.lines().any(|l| !l.trim_start().starts_with("//"))).collect()
+}
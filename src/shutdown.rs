@@ -0,0 +1,106 @@
+//! Rust/shutdown.rs: telling threads to stop with a flag or a closed
+//! channel, and draining with a deadline. Catching the signal needs the
+//! ctrlc crate or tokio, so the callers here stand in for the handler.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// A FLAG FOR THE THREADS -------------------------------------------------
+
+// The workers look at the flag between items, never in the middle of
+// one. Runs `workers` of them for `run`, then sets the flag where the
+// handler would; returns items started and finished, which are equal.
+pub fn flag(workers: usize, run: Duration) -> (usize, usize) {
+    let stop = Arc::new(AtomicBool::new(false));
+    let started = Arc::new(AtomicUsize::new(0));
+    let finished = Arc::new(AtomicUsize::new(0));
+    let handles: Vec<_> = (0..workers)
+        .map(|_| {
+            let (stop, started, finished) = (stop.clone(), started.clone(), finished.clone());
+            thread::spawn(move || {
+                while !stop.load(Ordering::SeqCst) {
+                    started.fetch_add(1, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(10));   // the item
+                    finished.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+        })
+        .collect();
+    thread::sleep(run);
+    stop.store(true, Ordering::SeqCst);        // Ctrl-C
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    (started.load(Ordering::SeqCst), finished.load(Ordering::SeqCst))
+}
+
+// A CLOSED CHANNEL -------------------------------------------------------
+
+// Drop the senders: recv() hands over what's still queued, then returns
+// Err, and `for job in rx` ends.
+pub fn closed_channel(jobs: u32) -> u32 {
+    let (tx, rx) = mpsc::channel();
+    let worker = thread::spawn(move || {
+        let mut done = 0;
+        for _job in rx {
+            done += 1;                          // every queued job, then the loop ends
+        }
+        done
+    });
+    for job in 0..jobs {
+        tx.send(job).unwrap();
+    }
+    drop(tx);                                   // the shutdown
+    worker.join().unwrap()
+}
+
+// DRAINING, WITH A DEADLINE ----------------------------------------------
+
+// Each worker, sleeping for its millis, says when it's done; the ids of
+// those done before the deadline.
+pub fn drain(work: &[(u32, u64)], deadline: Duration) -> Vec<u32> {
+    let (done_tx, done_rx) = mpsc::channel();
+    for &(id, millis) in work {
+        let done_tx = done_tx.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(millis));
+            done_tx.send(id).ok();
+        });
+    }
+    drop(done_tx);
+
+    let deadline = Instant::now() + deadline;
+    let mut drained = Vec::new();
+    while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+        match done_rx.recv_timeout(left) {
+            Ok(id) => drained.push(id),
+            Err(_) => break,                    // the deadline, or every worker done
+        }
+    }
+    drained
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_flag_for_the_threads() {
+        let (started, finished) = flag(4, Duration::from_millis(55));
+        assert_eq!(started, finished); // prints: true
+    }
+
+    #[test]
+    fn a_closed_channel() {
+        assert_eq!(closed_channel(10), 10); // prints: drained 10 of 10
+    }
+
+    // worker 2 is stuck
+    #[test]
+    fn draining_with_a_deadline() {
+        let drained = drain(&[(0, 10), (1, 20), (2, 5000)], Duration::from_millis(200));
+        assert_eq!(drained, [0, 1]); // prints: drained [0, 1], gave up on the rest
+    }
+}
@@ -0,0 +1,126 @@
+//! Rust/structures.rs: structs, tuple structs, and methods.
+
+#![allow(unused_variables)]
+
+// STRUCTURES -------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct User {
+    active: bool,
+    username: String,
+}                               // no semicolon
+
+// The following is a nice shorthand
+pub fn build_user(username: String) -> User {
+    User {
+        active: true,
+        username,
+    }
+}
+
+pub fn users() -> User {
+    let user1 = User {
+        active: true,
+        username: String::from("Saileza"),
+    };                                         // semicolon
+
+    // The entire instance must be mutable to change a field
+    let mut user2 = User {
+        active: true,
+        username: String::from("Salza"),
+    };
+    user2.username = String::from("Saileza Sharma");
+
+    // struct update syntax: the rest of the fields from user1, whose
+    // username is moved, not copied
+    User {
+        active: false,
+        ..user1
+    }
+}
+
+// Tuple structs: a name for the whole tuple, and a type of its own.
+pub struct Color(pub i32, pub i32, pub i32);
+pub struct Point(pub i32, pub i32, pub i32);
+
+// Unit-like structs without any fields
+pub struct AlwaysEqual;                        // behave similarly to ()
+
+pub fn tuple_structs() {
+    let black  = Color(0, 0, 0);
+    let origin = Point(0, 0, 0);
+    let subject = AlwaysEqual;
+}
+
+// Tuples and arrays can be printed directly with {:?}
+
+pub fn debug_tuple() {
+    let tuple1 = (729, 2.18, true, "Roza");
+    println!("The tuple is: {0:?}", tuple1);            // prints: The tuple is: (729, 2.18, true, "Roza")
+    println!("The name is: {0:?}", tuple1.3);           // prints: The name is: "Roza"
+}
+
+// To extend this to structures we use
+
+#[derive(Debug)]                                        // useful for debugging
+pub struct Rectangle {
+    pub length: usize,
+    pub width : usize,
+}
+
+pub fn debug_struct() -> Rectangle {
+    let rect1 = Rectangle {
+        length: 42,
+        width : 36,
+    };
+
+    println!("The struct instance is: {:?}", rect1);    // {:#?} for pretty print
+                                                        // prints: The struct instance is: Rectangle { length: 42, width: 36 }
+
+    dbg!(&rect1);           // to allow using rect1 after this line
+                            //          beccause dbg! takes ownership
+    rect1
+}
+
+// METHOD SYNTAX -----------------------------------------------------------
+
+impl User {
+    pub fn change_activity_status(&mut self) {     // mutable reference
+        self.active = !self.active;
+    }
+}
+
+impl Rectangle {
+    pub fn square(size: usize) -> Self {
+        Self {
+            length: size,
+            width : size,
+        }
+    }
+}
+
+// Each struct is allowed to have multiple impl blocks.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn structures() {
+        let user = users();
+        assert_eq!((user.active, user.username.as_str()), (false, "Saileza"));
+        let mut user = build_user(String::from("Roza"));
+        user.change_activity_status();
+        assert!(!user.active);
+    }
+
+    #[test]
+    fn debug() {
+        let tuple1 = (729, 2.18, true, "Roza");
+        // what's printed is what Debug writes
+        assert_eq!(format!("{tuple1:?}"), "(729, 2.18, true, \"Roza\")"); // prints: The tuple is: (729, 2.18, true, "Roza")
+        assert_eq!(format!("{:?}", tuple1.3), "\"Roza\""); // prints: The name is: "Roza"
+        // prints: The struct instance is: Rectangle { length: 42, width: 36 }
+        assert_eq!(format!("{:?}", debug_struct()), "Rectangle { length: 42, width: 36 }");
+    }
+}
@@ -0,0 +1,68 @@
+//! Rust/temp_files.rs: code that takes its path, and a temporary
+//! directory per test, removed on drop.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// TAKE THE PATH ----------------------------------------------------------
+
+// The program passes "esabi.txt"; a test passes a file it made. The
+// function is error_handling's read_username_chained, which takes it.
+
+// A TEMPORARY DIRECTORY BY HAND ------------------------------------------
+
+// std::env::temp_dir() for where, the process id and a counter for a
+// name no other test has, and Drop, which runs when the test's function
+// ends or unwinds from a panic.
+
+static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new() -> io::Result<TempDir> {
+        let n = NEXT.fetch_add(1, Ordering::SeqCst);
+        let path = std::env::temp_dir().join(format!("notes-{}-{n}", std::process::id()));
+        fs::create_dir(&path)?;            // create_dir, not _all: fails if it's there
+        Ok(TempDir(path))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        fs::remove_dir_all(&self.0).ok();   // nothing to do about an error in a drop
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error_handling::read_username_chained as read_username;
+
+    #[test]
+    fn take_the_path() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("esabi.txt");
+        fs::write(&path, "ferris").unwrap();
+        assert_eq!(read_username(&path).unwrap(), "ferris"); // prints: Ok("ferris")
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read_username(&path).unwrap_err().kind(), io::ErrorKind::NotFound); // prints: Err(NotFound)
+    }
+
+    #[test]
+    fn a_temporary_directory_by_hand() {
+        let (a, b) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        fs::write(a.path().join("esabi.txt"), "in a").unwrap();
+        fs::write(b.path().join("esabi.txt"), "in b").unwrap();
+        assert_eq!(fs::read_to_string(a.path().join("esabi.txt")).unwrap(), "in a"); // prints: in a
+        let kept = a.path().to_path_buf();
+        drop(a);
+        assert!(!kept.exists()); // prints: false
+    }
+}
@@ -0,0 +1,181 @@
+//! Rust/test_doubles.rs: a trait as the seam, a stub, a recording fake,
+//! and record and replay. The mockall part needs the crate, and is only
+//! in the notes.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+// THE SEAM ---------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+// what the retry loop needs from an HTTP client, and no more
+pub trait Http {
+    fn get(&self, url: &str) -> Result<Response, String>;   // Err: the network failed
+}
+
+pub const MAX_ATTEMPTS: u32 = 4;
+
+// 5xx and network errors are retried, 200ms, 400ms, ... apart
+pub fn fetch(http: &impl Http, url: &str, mut sleep: impl FnMut(Duration)) -> Result<String, String> {
+    let mut backoff = Duration::from_millis(200);
+    let mut attempt = 1;
+    loop {
+        let (retryable, error) = match http.get(url) {
+            Ok(response) if response.status == 200 => return Ok(response.body),
+            Ok(response) => (response.status >= 500, format!("server answered {}", response.status)),
+            Err(network) => (true, network),
+        };
+        if !retryable || attempt == MAX_ATTEMPTS {
+            return Err(error);
+        }
+        sleep(backoff);
+        backoff *= 2;
+        attempt += 1;
+    }
+}
+
+// A STUB -----------------------------------------------------------------
+
+// Answers in the order given. The RefCell is because get takes &self,
+// as the real client's does, and the stub still has to move on to its
+// next answer.
+
+pub struct Scripted(RefCell<VecDeque<Result<Response, String>>>);
+
+impl Scripted {
+    pub fn new(answers: Vec<Result<Response, String>>) -> Self {
+        Scripted(RefCell::new(answers.into()))
+    }
+}
+
+impl Http for Scripted {
+    fn get(&self, _url: &str) -> Result<Response, String> {
+        self.0.borrow_mut().pop_front().unwrap_or_else(|| Err("no more answers".to_string()))
+    }
+}
+
+pub fn answer(status: u16, body: &str) -> Result<Response, String> {
+    Ok(Response { status, body: body.to_string() })
+}
+
+// A RECORDING FAKE -------------------------------------------------------
+
+// Wraps any other Http and records each request with its answer: a spy,
+// around whatever does the answering.
+
+pub struct Recording<H> {
+    inner: H,
+    calls: RefCell<Vec<(String, Result<Response, String>)>>,
+}
+
+impl<H: Http> Recording<H> {
+    pub fn new(inner: H) -> Self {
+        Recording { inner, calls: RefCell::new(Vec::new()) }
+    }
+
+    pub fn urls(&self) -> Vec<String> {
+        self.calls.borrow().iter().map(|(url, _)| url.clone()).collect()
+    }
+
+    // one line per call: url, then status and body, or ! and the error
+    pub fn cassette(&self) -> String {
+        let mut out = String::new();
+        for (url, result) in self.calls.borrow().iter() {
+            match result {
+                Ok(r) => out += &format!("{url} {} {}\n", r.status, r.body),
+                Err(e) => out += &format!("{url} ! {e}\n"),
+            }
+        }
+        out
+    }
+}
+
+impl<H: Http> Http for Recording<H> {
+    fn get(&self, url: &str) -> Result<Response, String> {
+        let result = self.inner.get(url);
+        self.calls.borrow_mut().push((url.to_string(), result.clone()));
+        result
+    }
+}
+
+// RECORD AND REPLAY ------------------------------------------------------
+
+/// Serves a cassette's calls back in order, and refuses a request that
+/// isn't the one recorded next: the code has changed what it asks.
+///
+/// ```should_panic
+/// use langscape::test_doubles::{fetch, Replay};
+///
+/// let replay = Replay::new("https://example.com/a 200 hello\n");
+/// fetch(&replay, "https://example.com/b", |_| {}).ok();   // panics: the cassette has /a
+/// ```
+pub struct Replay(RefCell<VecDeque<(String, Result<Response, String>)>>);
+
+impl Replay {
+    pub fn new(cassette: &str) -> Self {
+        let calls = cassette.lines().map(|line| {
+            let mut parts = line.splitn(3, ' ');
+            let (url, status, rest) = (parts.next().unwrap(), parts.next().unwrap(), parts.next().unwrap_or(""));
+            let result = match status {
+                "!" => Err(rest.to_string()),
+                status => Ok(Response { status: status.parse().unwrap(), body: rest.to_string() }),
+            };
+            (url.to_string(), result)
+        });
+        Replay(RefCell::new(calls.collect()))
+    }
+}
+
+impl Http for Replay {
+    fn get(&self, url: &str) -> Result<Response, String> {
+        match self.0.borrow_mut().pop_front() {
+            Some((recorded, result)) if recorded == url => result,
+            Some((recorded, _)) => panic!("asked for {url}, the cassette has {recorded}"),
+            None => panic!("asked for {url}, the cassette has ended"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_stub() {
+        let flaky = Scripted::new(vec![answer(503, ""), Err("connection reset".to_string()), answer(200, "finally")]);
+        let mut slept = Vec::new();
+        assert_eq!(fetch(&flaky, "https://example.com/a", |d| slept.push(d)), Ok("finally".to_string())); // prints: Ok("finally")
+        assert_eq!(slept, [Duration::from_millis(200), Duration::from_millis(400)]); // prints: [200ms, 400ms]
+
+        let missing = Scripted::new(vec![answer(404, "")]);
+        // prints: Err("server answered 404")
+        assert_eq!(fetch(&missing, "https://example.com/b", |_| panic!("slept")), Err("server answered 404".to_string()));
+
+        let down = Scripted::new(vec![answer(503, ""); 5]);
+        assert_eq!(fetch(&down, "https://example.com/c", |_| {}), Err("server answered 503".to_string())); // prints: Err("server answered 503")
+    }
+
+    #[test]
+    fn a_recording_fake() {
+        let http = Recording::new(Scripted::new(vec![answer(502, ""), answer(200, "ok")]));
+        fetch(&http, "https://example.com/a", |_| {}).unwrap();
+        assert_eq!(http.urls(), ["https://example.com/a", "https://example.com/a"]); // prints: ["https://example.com/a", "https://example.com/a"]
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let live = Recording::new(Scripted::new(vec![answer(503, ""), answer(200, "hello")]));
+        fetch(&live, "https://example.com/a", |_| {}).unwrap();
+        let cassette = live.cassette();
+        // prints: https://example.com/a 503
+        // prints: https://example.com/a 200 hello
+        assert_eq!(cassette.lines().map(str::trim_end).collect::<Vec<_>>(), ["https://example.com/a 503", "https://example.com/a 200 hello"]);
+        assert_eq!(fetch(&Replay::new(&cassette), "https://example.com/a", |_| {}), Ok("hello".to_string())); // prints: Ok("hello")
+    }
+}
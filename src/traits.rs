@@ -0,0 +1,173 @@
+//! Rust/traits.rs: traits, static and dynamic dispatch, and
+//! `Box<dyn Trait>`.
+
+// TRAITS: DEFINING SHARED BEHAVIOR ---------------------------------------
+
+pub trait Greet {
+    // method signature (no implementation)
+    fn say_hello(&self) -> String;
+}
+
+pub struct Person {
+    pub name: String,
+}
+
+// implement a trait for a type
+impl Greet for Person {
+    fn say_hello(&self) -> String {
+        format!("My name is {}", self.name)
+    }
+}
+
+pub struct Robot;
+impl Greet for Robot {
+    fn say_hello(&self) -> String {
+        "I am a robot".to_string()
+    }
+}
+
+pub enum Color { Red, Blue, Green }
+impl Greet for Color {
+    fn say_hello(&self) -> String {
+        match self {
+            Color::Red => "Red".to_string(),
+            Color::Blue => "Blue".to_string(),
+            Color::Green => "Green".to_string(),
+        }
+    }
+}
+
+// function accepting a reference to any type implementing Greet
+pub fn greet_someone(greeter: &impl Greet) {
+    println!("{}", greeter.say_hello());
+}
+
+pub fn static_dispatch() {
+    let person = Person { name: "Rust".to_string() };
+    let robot = Robot;
+    let color = Color::Blue;
+
+    greet_someone(&person);                 // prints: My name is Rust
+    greet_someone(&robot);                  // prints: I am a robot
+    greet_someone(&color);                  // prints: Blue
+
+    // also possible
+    println!("{}", person.say_hello());     // prints: My name is Rust
+    println!("{}", robot.say_hello());      // prints: I am a robot
+    println!("{}", color.say_hello());      // prints: Blue
+}
+
+// TRAIT OBJECTS: DYNAMIC POLYMORPHISM -----------------------------------
+
+// a function taking any type implementing Greet via a trait object
+pub fn dynamic_greet(greeter: &dyn Greet) {
+    println!("{}", greeter.say_hello());
+}
+
+pub fn dynamic_dispatch() {
+    let alice = Person { name: "Alice".to_string() };
+    let bot = Robot;
+    let col = Color::Green;
+
+    // store trait objects in a vector
+    let greeters: Vec<&dyn Greet> = vec![&alice, &bot, &col];
+
+    // dynamic dispatch: no monomorphization
+    for greeter in greeters {
+        dynamic_greet(greeter);
+    }
+}
+
+/// A default implementation: use it or override it.
+pub mod defaults {
+    pub trait Greet {
+        fn say_hello(&self) -> String {
+            "Default hello".to_string()
+        }
+    }
+
+    pub struct Robot;
+
+    // use default implementation
+    impl Greet for Robot {}
+
+    pub struct Bird;
+    impl Greet for Bird {}
+
+    // existing data, and only temporary polymorphism: &dyn
+    pub fn borrowed() -> String {
+        let bird = Bird;
+        let greeter: &dyn Greet = &bird;
+        greeter.say_hello()
+    }
+
+    // the borrowed data must outlive the trait object (the lifetimes
+    // could be elided; written out, they say so)
+    #[allow(clippy::needless_lifetimes)]
+    pub fn borrowed_greeter<'a>(creature: &'a impl Greet) -> &'a dyn Greet {
+        creature                // Trait object tied to creature's lifetime
+    }
+
+    // Box<dyn Trait> owns its data, so no lifetime annotations are needed
+    pub fn boxed_greeter() -> Box<dyn Greet> {
+        Box::new(Bird)          // Owned, no lifetimes
+    }
+}
+
+// IMPL TRAIT IN RETURN POSITION -----------------------------------------
+
+// return a concrete type implementing Greet
+pub fn get_greeter(name: &str) -> impl Greet {
+    Person { name: name.to_string() }
+}
+
+pub fn returned() {
+    let bob = get_greeter("Bob");
+    dynamic_greet(&bob);
+}
+
+// Box<dyn Trait> --------------------------------------------------------
+
+pub trait Animal {
+    fn speak(&self);
+}
+
+pub struct Dog;
+pub struct Cat;
+
+impl Animal for Dog { fn speak(&self) { println!("Woof!"); } }
+impl Animal for Cat { fn speak(&self) { println!("Meow!"); } }
+
+pub fn animals() {
+    // a vector containing Box<dyn Animal>
+    let animals: Vec<Box<dyn Animal>> = vec![
+        Box::new(Dog),  // Stored as a trait object
+        Box::new(Cat),  // Different types, same interface
+    ];
+
+    // trait object dyn Animal dispatches to the correct method at runtime
+    for animal in animals {
+        animal.speak();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_and_dynamic() {
+        let person = Person { name: "Rust".to_string() };
+        assert_eq!(person.say_hello(), "My name is Rust"); // prints: My name is Rust
+        assert_eq!(Robot.say_hello(), "I am a robot"); // prints: I am a robot
+        assert_eq!(Color::Blue.say_hello(), "Blue"); // prints: Blue
+        let greeter: &dyn Greet = &person;
+        assert_eq!(greeter.say_hello(), "My name is Rust");
+    }
+
+    #[test]
+    fn default_implementation() {
+        assert_eq!(defaults::borrowed(), "Default hello");
+        assert_eq!(defaults::boxed_greeter().say_hello(), "Default hello");
+    }
+}
@@ -0,0 +1,112 @@
+//! Rust/unsafe_rust.rs: raw pointers, a safe function over unsafe code,
+//! and what Miri catches.
+//!
+//! The examples of undefined behaviour compile, and are only compiled
+//! here (`no_run`): run under Miri, each stops with the report the notes
+//! show. Use after free:
+//!
+//! ```no_run
+//! let v = vec![1, 2, 3];
+//! let p = v.as_ptr();
+//! drop(v);
+//! println!("{}", unsafe { *p });          // ub: has been freed
+//! ```
+//!
+//! Out of bounds: `a[3]` would panic, `p.add(3)` is just an address:
+//!
+//! ```no_run
+//! let a = [1, 2, 3];
+//! let p = a.as_ptr();
+//! println!("{}", unsafe { *p.add(3) });   // ub: beyond the end of the allocation
+//! ```
+//!
+//! Uninitialized memory: reading it isn't "some random number", it's UB:
+//!
+//! ```no_run
+//! let x: i32 = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+//! println!("{x}");                        // ub: memory is uninitialized
+//! ```
+//!
+//! An invalid value: a bool is 0 or 1, and the compiler relies on it:
+//!
+//! ```no_run
+//! let b: bool = unsafe { std::mem::transmute(3u8) };
+//! println!("{b}");                        // ub: expected a boolean
+//! ```
+//!
+//! Aliasing: writing through the raw pointer a `&mut` came from ends
+//! the `&mut`:
+//!
+//! ```no_run
+//! let mut x = 5;
+//! let p = &raw mut x;
+//! let r = unsafe { &mut *p };
+//! unsafe { *p = 6 };
+//! *r += 1;                                // ub: does not exist in the borrow stack
+//! println!("{x}");
+//! ```
+
+// RAW POINTERS -----------------------------------------------------------
+
+// making one is safe, using one is not
+pub fn raw_pointers() -> i32 {
+    let mut num = 5;
+    let r2 = &raw mut num;                // or: &mut num as *mut i32
+    let r1 = r2 as *const i32;            // a *const and a *mut to the same place: fine, they're raw
+    // SAFETY: both point to num, which is alive, and no reference to it
+    // is in use
+    unsafe {
+        *r2 += 1;
+        println!("r1 is: {}", *r1);       // prints: r1 is: 6
+        *r1
+    }
+}
+
+// A SAFE FUNCTION OVER UNSAFE CODE ---------------------------------------
+
+/// `values[..mid]` and `values[mid..]`, both mutable.
+///
+/// The borrow checker can't see that the halves don't overlap:
+///
+/// ```compile_fail,E0499
+/// fn split_at_mut(values: &mut [i32], mid: usize) -> (&mut [i32], &mut [i32]) {
+///     (&mut values[..mid], &mut values[mid..])
+/// }
+/// ```
+pub fn split_at_mut(values: &mut [i32], mid: usize) -> (&mut [i32], &mut [i32]) {
+    let len = values.len();
+    let ptr = values.as_mut_ptr();
+    assert!(mid <= len);                // without this, the slices below could run past the end
+    // SAFETY: mid <= len, so both slices are inside values, and they
+    // don't overlap
+    unsafe {
+        (
+            std::slice::from_raw_parts_mut(ptr, mid),
+            std::slice::from_raw_parts_mut(ptr.add(mid), len - mid),
+        )
+    }
+}
+
+pub fn split() -> Vec<i32> {
+    let mut v = vec![1, 2, 3, 4, 5, 6];
+    let (a, b) = split_at_mut(&mut v, 3);
+    a[0] = 10;
+    b[0] = 40;
+    println!("{v:?}");                  // prints: [10, 2, 3, 40, 5, 6]
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn through_a_raw_pointer() {
+        assert_eq!(raw_pointers(), 6); // prints: r1 is: 6
+    }
+
+    #[test]
+    fn split_halves() {
+        assert_eq!(split(), [10, 2, 3, 40, 5, 6]); // prints: [10, 2, 3, 40, 5, 6]
+    }
+}
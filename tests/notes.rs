@@ -0,0 +1,141 @@
+//! The modules against the notes they're made from: what a snippet in
+//! Rust/ says it does, the module of the same name must say too, so a
+//! note that changes can't leave its module behind.
+//!
+//! - `// prints: X` is a test's assert with the same `// prints: X`
+//!   after it, or on the lines just above it: the assert checks the
+//!   value, the marker says which note it stands for
+//! - `// panics: X` is a test's `#[should_panic(expected = "X")]`
+//! - `// compile error: X` is a doc test fenced ```` ```compile_fail,X ````
+//!   and `// ub: X` a doc comment with the same `// ub: X`
+//!
+//! Only tests and doc comments count: the rest of the module's comments
+//! say nothing a test holds it to. A snippet that needs a crate (tokio,
+//! mockall, tempfile) isn't in a module, and isn't checked.
+
+use langscape::registry::{self, Snippet};
+use std::collections::BTreeSet;
+use std::fs;
+
+const CRATES: &[&str] = &["tokio", "mockall", "tempfile", "anyhow", "thiserror", "signal_hook", "ctrlc"];
+
+fn needs_a_crate(snippet: &Snippet) -> bool {
+    CRATES.iter().any(|c| snippet.code.contains(&format!("{c}::")) || snippet.code.contains(&format!("#[{c}")))
+}
+
+// what the module must have for each annotation in the snippet, as
+// found() finds them
+fn expected(snippet: &Snippet) -> Vec<String> {
+    let mut expected: Vec<String> = snippet.prints().map(|p| format!("prints: {p}")).collect();
+    expected.extend(snippet.panics().map(|p| format!("panics: {p}")));
+    expected.extend(snippet.compile_error().map(|e| format!("compile error: {e}")));
+    expected.extend(snippet.undefined().map(|u| format!("ub: {u}")));
+    expected
+}
+
+// the lines of each #[test] fn in `module`, its attributes first
+fn tests(module: &str) -> Vec<Vec<&str>> {
+    let (mut tests, mut lines) = (Vec::new(), module.lines());
+    while let Some(line) = lines.next() {
+        if line.trim() != "#[test]" {
+            continue;
+        }
+        let (mut test, mut depth, mut opened) = (Vec::new(), 0, false);
+        for line in lines.by_ref() {
+            test.push(line);
+            let code = line.split("// prints: ").next().unwrap_or_default();
+            depth += code.matches('{').count() as i32 - code.matches('}').count() as i32;
+            opened |= depth > 0;
+            if opened && depth == 0 {
+                break;
+            }
+        }
+        tests.push(test);
+    }
+    tests
+}
+
+// what the module's tests and doc comments hold it to
+fn found(module: &str) -> BTreeSet<String> {
+    let mut found = BTreeSet::new();
+    for test in tests(module) {
+        // markers, each for the next line that's code
+        let mut markers = Vec::new();
+        for line in test {
+            let (code, marker) = line.split_once("// prints: ").map_or((line, None), |(code, marker)| (code, Some(marker)));
+            let code = code.trim();
+            if let Some(expected) = code.strip_prefix("#[should_panic(expected = ").and_then(|e| e.strip_suffix(")]")) {
+                found.insert(format!("panics: {}", expected.trim_matches('"')));
+            }
+            markers.extend(marker.map(|m| m.trim_end().to_string()));
+            if code.is_empty() || code.starts_with("//") {
+                continue;
+            }
+            if code.contains("assert") {
+                found.extend(markers.iter().map(|m| format!("prints: {m}")));
+            }
+            markers.clear();
+        }
+    }
+    for line in module.lines().map(str::trim) {
+        let Some(doc) = line.strip_prefix("///").or_else(|| line.strip_prefix("//!")) else { continue };
+        if let Some(code) = doc.trim().strip_prefix("```compile_fail,") {
+            found.insert(format!("compile error: {code}"));
+        }
+        if let Some((_, ub)) = doc.split_once("// ub: ") {
+            found.insert(format!("ub: {}", ub.trim()));
+        }
+    }
+    found
+}
+
+#[test]
+fn every_annotation_is_in_its_module() {
+    let mut missing = Vec::new();
+    for topic in registry::topics() {
+        let path = format!("{}/src/{}.rs", env!("CARGO_MANIFEST_DIR"), topic.name);
+        let Ok(module) = fs::read_to_string(&path) else { continue };
+        let found = found(&module);
+        for section in &topic.sections {
+            for snippet in section.snippets.iter().filter(|s| !needs_a_crate(s)) {
+                for text in expected(snippet).into_iter().filter(|text| !found.contains(text)) {
+                    missing.push(format!("Rust/{}.rs:{}: {text}", topic.name, snippet.line));
+                }
+            }
+        }
+    }
+    assert!(missing.is_empty(), "not in the modules:\n{}", missing.join("\n"));
+}
+
+#[test]
+fn only_tests_and_doc_comments_count() {
+    let module = "\
+// prints: 1
+fn one() -> u8 { 1 } // prints: 2
+
+/// ```compile_fail,E0308
+/// let x: u8 = \"one\";
+/// ```
+#[test]
+#[should_panic(expected = \"index out of bounds\")]
+fn three() {
+    // prints: 3
+    // prints: 4
+    assert_eq!(one() + 2, 3);
+    let five = 5; // prints: 5
+    // compile error: E0499
+}
+";
+    let found: Vec<String> = found(module).into_iter().collect();
+    assert_eq!(found, ["compile error: E0308", "panics: index out of bounds", "prints: 3", "prints: 4"]);
+}
+
+// the topics without a module are the ones lib.rs says
+#[test]
+fn every_topic_has_a_module() {
+    let without: Vec<_> = registry::topics()
+        .map(|t| t.name)
+        .filter(|name| !fs::exists(format!("{}/src/{name}.rs", env!("CARGO_MANIFEST_DIR"))).unwrap())
+        .collect();
+    assert_eq!(without, ["async_sync"]);
+}